@@ -0,0 +1,12 @@
+-- Migration 012: session-scoped working memory.
+--
+-- A free-form text scratchpad the reasoning modes inject into prompts and the
+-- model can update via a `memory_update` field, giving cross-mode continuity
+-- within a session without re-sending the full thought history.
+--
+-- NOTE: ALTER TABLE ADD COLUMN is not idempotent and migrations re-run every
+-- startup, so core.rs executes this statement inline and tolerates the
+-- "duplicate column name" error on subsequent boots. This file documents the
+-- schema change.
+
+ALTER TABLE sessions ADD COLUMN working_memory TEXT;
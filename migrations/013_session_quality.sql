@@ -0,0 +1,13 @@
+-- Migration 013: session-level aggregate quality.
+--
+-- A recency-weighted aggregate of the session's thought confidence scores,
+-- folded in on every thought write (exponential moving average; see
+-- update_session_quality in storage/session.rs). NULL until the session has
+-- a scored thought; surfaced by the reasoning_session_quality tool.
+--
+-- NOTE: ALTER TABLE ADD COLUMN is not idempotent and migrations re-run every
+-- startup, so core.rs executes this statement inline and tolerates the
+-- "duplicate column name" error on subsequent boots. This file documents the
+-- schema change.
+
+ALTER TABLE sessions ADD COLUMN quality REAL;
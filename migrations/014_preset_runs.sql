@@ -0,0 +1,18 @@
+-- Migration 014: persisted preset run history.
+--
+-- Each completed preset run is recorded here as the full PresetResult
+-- serialized to JSON, tagged by preset id and timestamp. Teams rerunning the
+-- same preset can then track how outputs change across runs: the
+-- reasoning_preset_history tool lists a preset's runs and diffs the two most
+-- recent (success/failure flips per step, score deltas).
+
+CREATE TABLE IF NOT EXISTS preset_runs (
+    id          TEXT PRIMARY KEY,
+    preset_id   TEXT NOT NULL,
+    session_id  TEXT NOT NULL,
+    result_json TEXT NOT NULL,
+    created_at  TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_preset_runs_preset
+    ON preset_runs (preset_id, created_at);
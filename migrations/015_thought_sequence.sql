@@ -0,0 +1,26 @@
+-- Migration 015: per-session thought sequence numbers.
+--
+-- Thoughts were ordered by their RFC 3339 timestamp, which collides under
+-- concurrent saves (equal timestamps have no stable order). Each thought now
+-- gets a monotonic per-session sequence assigned at insert time — the INSERT
+-- computes MAX(sequence) + 1 for the session in the same statement, so
+-- concurrent saves serialize on SQLite's write lock and the sequence stays
+-- contiguous and gap-free. Reads order by sequence.
+--
+-- NOTE: ALTER TABLE ADD COLUMN is not idempotent and migrations re-run every
+-- startup, so core.rs executes these statements inline, tolerating the
+-- "duplicate column name" error on subsequent boots; the backfill only
+-- touches rows with a NULL sequence. This file documents the schema change.
+
+ALTER TABLE thoughts ADD COLUMN sequence INTEGER;
+
+-- Backfill existing rows from their timestamp order (ties broken by id).
+UPDATE thoughts SET sequence = (
+    SELECT COUNT(*) FROM thoughts t2
+    WHERE t2.session_id = thoughts.session_id
+      AND (t2.created_at < thoughts.created_at
+           OR (t2.created_at = thoughts.created_at AND t2.id <= thoughts.id))
+) WHERE sequence IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_thoughts_session_sequence
+    ON thoughts (session_id, sequence);
@@ -0,0 +1,14 @@
+-- Migration 016: opt-in raw request/response capture per thought.
+--
+-- When STORE_RAW_IO is enabled, a mode that saves a thought also records the
+-- exact messages it sent to the API and the raw completion text it got back,
+-- keyed by thought id, so reasoning_inspect_thought can replay the exchange
+-- for deep debugging. Rows are written redacted and size-capped (see
+-- modes/core.rs); with the flag off (the default) this table stays empty.
+
+CREATE TABLE IF NOT EXISTS thought_raw_io (
+    thought_id     TEXT PRIMARY KEY,
+    messages_json  TEXT NOT NULL,
+    raw_completion TEXT NOT NULL,
+    created_at     TEXT NOT NULL
+);
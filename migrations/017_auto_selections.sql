@@ -0,0 +1,23 @@
+-- Migration 017: structured auto-selection history.
+--
+-- Each reasoning_auto run records what mode it chose and how confident the
+-- router was. `score` starts NULL and is backfilled with the confidence of
+-- the first thought the chosen mode later writes in the same session — the
+-- "resulting score" of following the suggestion. The reasoning_auto_stats
+-- tool aggregates this table per chosen mode (selection frequency, average
+-- routing confidence, average resulting score).
+
+CREATE TABLE IF NOT EXISTS auto_selections (
+    id            TEXT PRIMARY KEY,
+    session_id    TEXT NOT NULL,
+    selected_mode TEXT NOT NULL,
+    confidence    REAL NOT NULL,
+    score         REAL,
+    created_at    TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_auto_selections_mode
+    ON auto_selections (selected_mode, created_at);
+
+CREATE INDEX IF NOT EXISTS idx_auto_selections_session
+    ON auto_selections (session_id, created_at);
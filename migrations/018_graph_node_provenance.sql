@@ -0,0 +1,15 @@
+-- Migration 018: structured provenance on graph nodes.
+--
+-- Each graph node records which operation produced it (init/generate/
+-- aggregate/refine/import) and from which parent node(s), so `state` and the
+-- DOT export can show how a node arose rather than only what it says.
+-- `origin` holds the operation name; `parent_ids` a JSON array of the
+-- (namespaced) parent node ids, NULL when the operation had no inputs.
+--
+-- NOTE: ALTER TABLE ADD COLUMN is not idempotent and migrations re-run every
+-- startup, so core.rs executes these statements inline, tolerating the
+-- "duplicate column name" error on subsequent boots. Rows predating this
+-- migration keep NULLs, read back as "no provenance recorded".
+
+ALTER TABLE graph_nodes ADD COLUMN origin TEXT;
+ALTER TABLE graph_nodes ADD COLUMN parent_ids TEXT;
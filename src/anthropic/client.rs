@@ -20,8 +20,8 @@ use tokio::sync::mpsc;
 use super::config::{ClientConfig, DEFAULT_MODEL};
 use super::streaming::parse_sse_line;
 use super::types::{
-    ApiMessage, ApiRequest, ApiResponse, ContentBlock, ReasoningResponse, StreamEvent,
-    ThinkingConfig, ToolUseResult,
+    ApiMessage, ApiRequest, ApiResponse, ContentBlock, ContentPart, ReasoningResponse, StreamEvent,
+    ThinkingConfig, ToolChoice, ToolDefinition, ToolUseResult,
 };
 use crate::error::{AnthropicError, ModeError};
 use crate::traits::{AnthropicClientTrait, CompletionConfig, CompletionResponse, Message, Usage};
@@ -36,6 +36,11 @@ pub const MAX_CONTENT_LENGTH: usize = 50_000;
 /// Anthropic API version header value.
 const ANTHROPIC_VERSION: &str = "2023-06-01";
 
+/// Maximum continuation rounds when a response is truncated at `max_tokens`
+/// and auto-continue is enabled. Bounds cost when the model keeps filling the
+/// output window.
+const MAX_AUTO_CONTINUATIONS: u32 = 3;
+
 /// Emit an `Anthropic` activity event (dashboard). No-op when the dashboard is
 /// not wired; never blocks. `model` may be empty for terminal events.
 fn emit_anthropic(model: &str, phase: crate::dashboard::Phase) {
@@ -47,6 +52,43 @@ fn emit_anthropic(model: &str, phase: crate::dashboard::Phase) {
     crate::dashboard::emit(ev);
 }
 
+/// A shared pool of retries spanning several API calls.
+///
+/// Per-call retry logic caps each call independently, so a multi-step workflow
+/// (e.g. a preset) can multiply its worst-case latency by the number of steps.
+/// A `RetryBudget` is cloned across the steps of one run; every retry anywhere
+/// in the run draws from the same pool, and once the pool is empty the
+/// remaining steps fail fast instead of retrying.
+#[derive(Debug, Clone)]
+pub struct RetryBudget {
+    remaining: Arc<std::sync::atomic::AtomicU32>,
+}
+
+impl RetryBudget {
+    /// Create a budget allowing at most `max_total_retries` retries in total.
+    #[must_use]
+    pub fn new(max_total_retries: u32) -> Self {
+        Self {
+            remaining: Arc::new(std::sync::atomic::AtomicU32::new(max_total_retries)),
+        }
+    }
+
+    /// Consume one retry from the pool. Returns `false` when the pool is
+    /// exhausted, in which case the caller must not retry.
+    pub fn try_consume(&self) -> bool {
+        use std::sync::atomic::Ordering;
+        self.remaining
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok()
+    }
+
+    /// Retries left in the pool.
+    #[must_use]
+    pub fn remaining(&self) -> u32 {
+        self.remaining.load(std::sync::atomic::Ordering::SeqCst)
+    }
+}
+
 /// Anthropic API client.
 #[derive(Debug)]
 pub struct AnthropicClient {
@@ -56,6 +98,15 @@ pub struct AnthropicClient {
     /// Optional metrics handle. When set, each call records its pinned model
     /// identifier so a model-version change can be detected (spec 001, FR-017).
     metrics: Option<Arc<crate::metrics::MetricsCollector>>,
+    /// Sticky authentication-failure flag. `AuthenticationFailed` is
+    /// non-retryable and a bad key cannot fix itself, so once one call hits it
+    /// every subsequent call on this client short-circuits without touching
+    /// the API — one failure instead of a wall of them, one per tool call.
+    /// Cleared only on restart (the server holds its clients for its lifetime).
+    auth_failed: std::sync::atomic::AtomicBool,
+    /// Deterministic offline stub (`OFFLINE_MODE`). When set, every trait
+    /// call is served from canned responses and the API is never touched.
+    offline: Option<crate::anthropic::OfflineClient>,
 }
 
 impl AnthropicClient {
@@ -75,6 +126,8 @@ impl AnthropicClient {
             api_key: api_key.into(),
             config,
             metrics: None,
+            auth_failed: std::sync::atomic::AtomicBool::new(false),
+            offline: None,
         })
     }
 
@@ -91,6 +144,38 @@ impl AnthropicClient {
         self
     }
 
+    /// Serve all completions from the deterministic offline stub when `offline`
+    /// is true (`OFFLINE_MODE`). A no-op when false, so call sites can wire the
+    /// flag unconditionally.
+    #[must_use]
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        if offline {
+            self.offline = Some(crate::anthropic::OfflineClient);
+        }
+        self
+    }
+
+    /// Fail fast when a previous call already hit an authentication failure.
+    ///
+    /// The flag is sticky: a bad `ANTHROPIC_API_KEY` fails every call the same
+    /// way, so re-hitting the API only adds latency and noise.
+    fn ensure_auth_not_failed(&self) -> Result<(), AnthropicError> {
+        if self.auth_failed.load(std::sync::atomic::Ordering::Relaxed) {
+            return Err(AnthropicError::AuthenticationFailed);
+        }
+        Ok(())
+    }
+
+    /// Latch the sticky authentication-failure flag (see [`Self::ensure_auth_not_failed`]).
+    fn record_auth_failure(&self) {
+        tracing::error!(
+            "Authentication failed — short-circuiting all further API calls until restart; \
+             check ANTHROPIC_API_KEY"
+        );
+        self.auth_failed
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
     /// Record the pinned model identifier for a call, if metrics are attached.
     fn observe_model(&self, model: &str) {
         if let Some(metrics) = &self.metrics {
@@ -115,10 +200,33 @@ impl AnthropicClient {
 
     /// Send a completion request with retry logic.
     pub async fn complete(&self, request: ApiRequest) -> Result<ReasoningResponse, AnthropicError> {
+        self.complete_inner(request, None).await
+    }
+
+    /// Send a completion request whose retries draw from a shared budget.
+    ///
+    /// Behaves like [`complete`](Self::complete), except every retry first
+    /// consumes from `budget`; when the budget is exhausted the call fails
+    /// fast with the triggering error instead of retrying. Used by multi-step
+    /// runners so a run-wide retry pool bounds total latency.
+    pub async fn complete_with_budget(
+        &self,
+        request: ApiRequest,
+        budget: &RetryBudget,
+    ) -> Result<ReasoningResponse, AnthropicError> {
+        self.complete_inner(request, Some(budget)).await
+    }
+
+    async fn complete_inner(
+        &self,
+        request: ApiRequest,
+        budget: Option<&RetryBudget>,
+    ) -> Result<ReasoningResponse, AnthropicError> {
+        self.ensure_auth_not_failed()?;
         Self::validate_request(&request)?;
         self.observe_model(&request.model);
         emit_anthropic(&request.model, crate::dashboard::Phase::Started);
-        let result = self.execute_with_retry(request).await;
+        let result = self.execute_to_completion(request, budget).await;
         emit_anthropic(
             "",
             if result.is_ok() {
@@ -143,6 +251,7 @@ impl AnthropicClient {
         &self,
         request: ApiRequest,
     ) -> Result<mpsc::Receiver<Result<StreamEvent, AnthropicError>>, AnthropicError> {
+        self.ensure_auth_not_failed()?;
         Self::validate_request(&request)?;
         self.observe_model(&request.model);
         emit_anthropic(&request.model, crate::dashboard::Phase::Started);
@@ -184,6 +293,7 @@ impl AnthropicClient {
 
         // Handle error status codes - fail fast, no fallbacks
         if status.as_u16() == 401 {
+            self.record_auth_failure();
             return Err(AnthropicError::AuthenticationFailed);
         }
         if status.as_u16() == 429 {
@@ -309,15 +419,84 @@ impl AnthropicClient {
         Ok(())
     }
 
-    /// Execute request with retry logic.
+    /// Execute a request and resolve `max_tokens` truncation.
+    ///
+    /// A response cut off at the token cap would otherwise surface downstream
+    /// as a confusing JSON parse failure. When auto-continue is enabled the
+    /// completion is continued until it finishes (bounded by
+    /// [`MAX_AUTO_CONTINUATIONS`]); otherwise truncation is a distinct error.
+    async fn execute_to_completion(
+        &self,
+        request: ApiRequest,
+        budget: Option<&RetryBudget>,
+    ) -> Result<ReasoningResponse, AnthropicError> {
+        let response = self.execute_with_retry(request.clone(), budget).await?;
+        if !response.is_truncated() {
+            return Ok(response);
+        }
+        if !self.config.auto_continue {
+            return Err(AnthropicError::UnexpectedResponse {
+                message: "response truncated at max_tokens".to_string(),
+            });
+        }
+        self.continue_completion(&request, response, budget).await
+    }
+
+    /// Gather the rest of a truncated completion by re-sending the
+    /// conversation with the partial output as an assistant prefill, so the
+    /// model picks up exactly where it stopped.
+    async fn continue_completion(
+        &self,
+        request: &ApiRequest,
+        mut partial: ReasoningResponse,
+        budget: Option<&RetryBudget>,
+    ) -> Result<ReasoningResponse, AnthropicError> {
+        for round in 1..=MAX_AUTO_CONTINUATIONS {
+            tracing::warn!(round, "Response truncated at max_tokens — auto-continuing");
+
+            let mut next = request.clone();
+            next.messages.push(ApiMessage::assistant(&partial.raw_text));
+            // Assistant prefill cannot be combined with extended thinking.
+            next.thinking = None;
+
+            let continued = self.execute_with_retry(next, budget).await?;
+
+            // Prefill continuations resume mid-stream: concatenate directly.
+            partial.raw_text.push_str(&continued.raw_text);
+            partial.usage.input_tokens += continued.usage.input_tokens;
+            partial.usage.output_tokens += continued.usage.output_tokens;
+            partial.tool_uses.extend(continued.tool_uses);
+            partial.stop_reason = continued.stop_reason;
+
+            if !partial.is_truncated() {
+                partial.parsed = extract_json(&partial.raw_text);
+                return Ok(partial);
+            }
+        }
+
+        Err(AnthropicError::UnexpectedResponse {
+            message: format!(
+                "response truncated at max_tokens after {MAX_AUTO_CONTINUATIONS} continuations"
+            ),
+        })
+    }
+
+    /// Execute request with retry logic. When `budget` is set, each retry
+    /// first consumes from the shared pool and the call fails fast once the
+    /// pool is exhausted.
     async fn execute_with_retry(
         &self,
         request: ApiRequest,
+        budget: Option<&RetryBudget>,
     ) -> Result<ReasoningResponse, AnthropicError> {
         // Cap on a single retry wait so one large `retry-after` cannot block a
         // run indefinitely.
         const MAX_RETRY_DELAY_MS: u64 = 120_000;
 
+        // Retry-metric label: the mode this call serves, when the caller
+        // tagged it (the trait path does; untagged callers share one bucket).
+        let mode = request.metrics_mode.as_deref().unwrap_or("unlabeled");
+
         let mut last_error = None;
         let mut delay = self.config.retry_delay_ms;
 
@@ -327,12 +506,37 @@ impl AnthropicClient {
                 tokio::time::sleep(Duration::from_millis(delay)).await;
             }
 
+            if let Some(metrics) = &self.metrics {
+                metrics.record_attempt(mode);
+            }
+
             match self.execute_once(&request).await {
                 Ok(response) => return Ok(response),
                 Err(e) => {
                     if !e.is_retryable() {
                         return Err(e);
                     }
+                    // A retry must be paid for from the shared pool (when one
+                    // is attached) before it is scheduled; an empty pool means
+                    // the whole run has spent its retries, so fail fast.
+                    if let Some(budget) = budget {
+                        if !budget.try_consume() {
+                            tracing::warn!(error = %e, "Retry budget exhausted; failing fast");
+                            if let Some(metrics) = &self.metrics {
+                                metrics.record_retry_exhausted(mode, e.kind());
+                            }
+                            return Err(e);
+                        }
+                    }
+                    if attempt < self.config.max_retries {
+                        // Another attempt will be scheduled — count the retry
+                        // under the error that triggered it.
+                        if let Some(metrics) = &self.metrics {
+                            metrics.record_retry(mode, e.kind());
+                        }
+                    } else if let Some(metrics) = &self.metrics {
+                        metrics.record_retry_exhausted(mode, e.kind());
+                    }
                     // Honor the server's `retry-after` on a 429 — the previous
                     // blind exponential backoff gave up during the real cooldown
                     // window, dropping calls and biasing eval results; otherwise
@@ -419,6 +623,7 @@ impl AnthropicClient {
 
         // Handle specific error status codes
         if status.as_u16() == 401 {
+            self.record_auth_failure();
             return Err(AnthropicError::AuthenticationFailed);
         }
 
@@ -459,6 +664,47 @@ impl AnthropicClient {
         Self::parse_response(body)
     }
 
+    /// Convert trait messages to API messages.
+    ///
+    /// When prompt caching is enabled and the caller declared a static prompt
+    /// prefix (`cached_prompt_len`), the first user message is split at that
+    /// boundary into a cacheable part (the mode prompt, identical across
+    /// calls) and an uncached part (session context and content). A length
+    /// that does not fall strictly inside the message on a character boundary
+    /// is ignored and the message is sent unsplit.
+    fn to_api_messages(
+        &self,
+        messages: Vec<Message>,
+        cached_prompt_len: Option<usize>,
+    ) -> Vec<ApiMessage> {
+        let mut split_len = if self.config.prompt_caching {
+            cached_prompt_len
+        } else {
+            None
+        };
+
+        messages
+            .into_iter()
+            .map(|m| {
+                if m.role == "user" {
+                    // Only the first user message carries the static prompt.
+                    if let Some(len) = split_len.take() {
+                        if len > 0 && len < m.content.len() && m.content.is_char_boundary(len) {
+                            let (prefix, rest) = m.content.split_at(len);
+                            return ApiMessage::user_multipart(vec![
+                                ContentPart::cached_text(prefix),
+                                ContentPart::text(rest),
+                            ]);
+                        }
+                    }
+                    ApiMessage::user(&m.content)
+                } else {
+                    ApiMessage::assistant(&m.content)
+                }
+            })
+            .collect()
+    }
+
     /// Parse API response into `ReasoningResponse`.
     fn parse_response(response: ApiResponse) -> Result<ReasoningResponse, AnthropicError> {
         let mut raw_text = String::new();
@@ -488,7 +734,8 @@ impl AnthropicClient {
             });
         }
 
-        let mut result = ReasoningResponse::new(&raw_text, response.usage);
+        let mut result = ReasoningResponse::new(&raw_text, response.usage)
+            .with_stop_reason(&response.stop_reason);
 
         if let Some(t) = thinking {
             result = result.with_thinking(t);
@@ -511,6 +758,20 @@ impl AnthropicClient {
 // AnthropicClientTrait implementations
 // ============================================================================
 
+/// Map a client error into the [`ModeError`] modes consume. Authentication
+/// failures name the fix, since they surface on every call until the key is
+/// corrected and the server restarted.
+fn to_mode_error(e: AnthropicError) -> ModeError {
+    match e {
+        AnthropicError::AuthenticationFailed => ModeError::ApiUnavailable {
+            message: "authentication failed; check ANTHROPIC_API_KEY".to_string(),
+        },
+        e => ModeError::ApiUnavailable {
+            message: e.to_string(),
+        },
+    }
+}
+
 /// Convert trait types to API types and call the underlying client.
 #[async_trait]
 impl AnthropicClientTrait for AnthropicClient {
@@ -519,17 +780,15 @@ impl AnthropicClientTrait for AnthropicClient {
         messages: Vec<Message>,
         config: CompletionConfig,
     ) -> Result<CompletionResponse, ModeError> {
-        // Convert messages to API format
-        let api_messages: Vec<ApiMessage> = messages
-            .into_iter()
-            .map(|m| {
-                if m.role == "user" {
-                    ApiMessage::user(&m.content)
-                } else {
-                    ApiMessage::assistant(&m.content)
-                }
-            })
-            .collect();
+        // OFFLINE_MODE: short-circuit to the deterministic stub, never
+        // touching the API.
+        if let Some(stub) = &self.offline {
+            return stub.complete(messages, config).await;
+        }
+
+        // Convert messages to API format (splitting out a cacheable prompt
+        // prefix when prompt caching is enabled)
+        let api_messages = self.to_api_messages(messages, config.cached_prompt_len);
 
         // Build API request using the default model
         let max_tokens = config.max_tokens.unwrap_or(4096);
@@ -539,29 +798,81 @@ impl AnthropicClientTrait for AnthropicClient {
         // Note: When thinking is enabled, temperature must be 1 (API constraint)
         if let Some(budget) = config.thinking_budget {
             request = request.with_thinking(ThinkingConfig::enabled(budget));
-            // Temperature is implicitly 1 when thinking is enabled
-        } else if let Some(temp) = config.temperature {
-            // Only set custom temperature when thinking is NOT enabled
-            request = request.with_temperature(f64::from(temp));
+            // Temperature is implicitly 1 and sampling overrides are
+            // disallowed when thinking is enabled
+        } else {
+            // Only set custom sampling when thinking is NOT enabled
+            if let Some(temp) = config.temperature {
+                request = request.with_temperature(f64::from(temp));
+            }
+            if let Some(top_p) = config.top_p {
+                request = request.with_top_p(f64::from(top_p));
+            }
+            if let Some(top_k) = config.top_k {
+                request = request.with_top_k(top_k);
+            }
         }
 
         if let Some(system) = config.system_prompt.as_ref() {
             request = request.with_system(system);
         }
 
+        // Structured output: present the schema as a single tool and force
+        // the model to call it, so the answer arrives as schema-conforming
+        // JSON instead of free-form text. A forced tool choice is not
+        // allowed alongside extended thinking, so under a thinking budget
+        // the tool is offered but not forced — the free-form fallback
+        // covers the case where the model answers in text.
+        if let Some(schema) = config.output_schema.as_ref() {
+            request = request
+                .with_tools(vec![ToolDefinition::new(
+                    &schema.name,
+                    "Record the final result. Every field must conform to the schema.",
+                    schema.schema.clone(),
+                )])
+                .with_tool_choice(if config.thinking_budget.is_some() {
+                    ToolChoice::auto()
+                } else {
+                    ToolChoice::specific(&schema.name)
+                });
+        }
+
+        // Tag the request so the retry loop attributes its counters to the
+        // calling mode.
+        if let Some(mode) = config.mode.as_ref() {
+            request = request.with_metrics_mode(mode.clone());
+        }
+
         // Call the underlying API method (not the trait method)
-        let response =
-            Self::complete(self, request)
-                .await
-                .map_err(|e| ModeError::ApiUnavailable {
-                    message: e.to_string(),
-                })?;
+        let response = Self::complete(self, request).await.map_err(to_mode_error)?;
 
-        // Convert to trait response
-        Ok(CompletionResponse::new(
-            response.raw_text,
-            Usage::new(response.usage.input_tokens, response.usage.output_tokens),
-        ))
+        // Convert to trait response, carrying cache reads separately so they
+        // stay distinguishable from regular input tokens.
+        let usage = Usage::new(response.usage.input_tokens, response.usage.output_tokens)
+            .with_cache_read_tokens(response.usage.cache_read_input_tokens);
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_token_usage(
+                config.mode.as_deref().unwrap_or("unlabeled"),
+                usage.input_tokens,
+                usage.output_tokens,
+                usage.cache_read_input_tokens,
+            );
+        }
+
+        let mut completion = CompletionResponse::new(response.raw_text, usage);
+        if let Some(thinking) = response.thinking {
+            completion = completion.with_thinking(thinking);
+        }
+        // Surface the schema-constrained payload when the model called the
+        // output tool; otherwise `structured` stays `None` and the caller
+        // parses the free-form text.
+        if let Some(schema) = config.output_schema.as_ref() {
+            if let Some(tool_use) = response.tool_uses.iter().find(|tu| tu.name == schema.name) {
+                completion = completion.with_structured(tool_use.input.clone());
+            }
+        }
+        Ok(completion)
     }
 
     async fn complete_streaming(
@@ -569,17 +880,15 @@ impl AnthropicClientTrait for AnthropicClient {
         messages: Vec<Message>,
         config: CompletionConfig,
     ) -> Result<mpsc::Receiver<Result<StreamEvent, ModeError>>, ModeError> {
-        // Convert messages to API format
-        let api_messages: Vec<ApiMessage> = messages
-            .into_iter()
-            .map(|m| {
-                if m.role == "user" {
-                    ApiMessage::user(&m.content)
-                } else {
-                    ApiMessage::assistant(&m.content)
-                }
-            })
-            .collect();
+        // OFFLINE_MODE: short-circuit to the deterministic stub, never
+        // touching the API.
+        if let Some(stub) = &self.offline {
+            return stub.complete_streaming(messages, config).await;
+        }
+
+        // Convert messages to API format (splitting out a cacheable prompt
+        // prefix when prompt caching is enabled)
+        let api_messages = self.to_api_messages(messages, config.cached_prompt_len);
 
         // Build API request using the default model
         let max_tokens = config.max_tokens.unwrap_or(4096);
@@ -588,8 +897,16 @@ impl AnthropicClientTrait for AnthropicClient {
         // Wire extended thinking if budget is specified
         if let Some(budget) = config.thinking_budget {
             request = request.with_thinking(ThinkingConfig::enabled(budget));
-        } else if let Some(temp) = config.temperature {
-            request = request.with_temperature(f64::from(temp));
+        } else {
+            if let Some(temp) = config.temperature {
+                request = request.with_temperature(f64::from(temp));
+            }
+            if let Some(top_p) = config.top_p {
+                request = request.with_top_p(f64::from(top_p));
+            }
+            if let Some(top_k) = config.top_k {
+                request = request.with_top_k(top_k);
+            }
         }
 
         if let Some(system) = config.system_prompt.as_ref() {
@@ -597,11 +914,9 @@ impl AnthropicClientTrait for AnthropicClient {
         }
 
         // Call the underlying streaming API method
-        let mut inner_rx = Self::complete_streaming(self, request).await.map_err(|e| {
-            ModeError::ApiUnavailable {
-                message: e.to_string(),
-            }
-        })?;
+        let mut inner_rx = Self::complete_streaming(self, request)
+            .await
+            .map_err(to_mode_error)?;
 
         // Create new channel with mapped error type
         let (tx, rx) = mpsc::channel(32);
@@ -609,9 +924,7 @@ impl AnthropicClientTrait for AnthropicClient {
         // Spawn task to forward events with error mapping
         tokio::spawn(async move {
             while let Some(event_result) = inner_rx.recv().await {
-                let mapped = event_result.map_err(|e| ModeError::ApiUnavailable {
-                    message: e.to_string(),
-                });
+                let mapped = event_result.map_err(to_mode_error);
                 if tx.send(mapped).await.is_err() {
                     // Receiver dropped
                     return;
@@ -980,6 +1293,77 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn test_auth_failure_is_sticky_and_short_circuits() {
+        let server = MockServer::start().await;
+
+        // Exactly one request may reach the transport: after the first 401,
+        // every further call must short-circuit without hitting the API.
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .respond_with(ResponseTemplate::new(401).set_body_string("Unauthorized"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = create_mock_client(&server).await;
+        let request = ApiRequest::new("claude-3", 1000, vec![ApiMessage::user("Hi")]);
+
+        let first = client.complete(request.clone()).await;
+        assert!(matches!(
+            first.unwrap_err(),
+            AnthropicError::AuthenticationFailed
+        ));
+
+        // Second call fails instantly with the same error.
+        let second = client.complete(request.clone()).await;
+        assert!(matches!(
+            second.unwrap_err(),
+            AnthropicError::AuthenticationFailed
+        ));
+
+        // Streaming shares the latch.
+        let streaming = client.complete_streaming(request).await;
+        assert!(matches!(
+            streaming.err(),
+            Some(AnthropicError::AuthenticationFailed)
+        ));
+
+        // MockServer verifies the expect(1) bound on drop.
+    }
+
+    #[tokio::test]
+    async fn test_mode_call_after_auth_failure_names_the_key() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .respond_with(ResponseTemplate::new(401).set_body_string("Unauthorized"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = create_mock_client(&server).await;
+
+        // Two mode-level (trait) calls: the first hits the API, the second is
+        // short-circuited by the sticky flag. Both surface the actionable
+        // message.
+        for _ in 0..2 {
+            let result = AnthropicClientTrait::complete(
+                &client,
+                vec![Message::user("Hi")],
+                CompletionConfig::new(),
+            )
+            .await;
+            match result.unwrap_err() {
+                ModeError::ApiUnavailable { message } => {
+                    assert!(message.contains("ANTHROPIC_API_KEY"), "message: {message}");
+                }
+                other => panic!("expected ApiUnavailable, got {other:?}"),
+            }
+        }
+    }
+
     #[tokio::test]
     async fn test_complete_rate_limited() {
         let server = MockServer::start().await;
@@ -1150,6 +1534,360 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn test_retry_metrics_count_attempts_and_retries_separately() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::sync::Arc;
+
+        let server = MockServer::start().await;
+        let call_count = Arc::new(AtomicU32::new(0));
+        let call_count_clone = Arc::clone(&call_count);
+
+        // Fail twice with 429, then succeed.
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .respond_with(move |_req: &wiremock::Request| {
+                let count = call_count_clone.fetch_add(1, Ordering::SeqCst);
+                if count < 2 {
+                    ResponseTemplate::new(429)
+                } else {
+                    ResponseTemplate::new(200).set_body_json(success_response_body("Success!"))
+                }
+            })
+            .mount(&server)
+            .await;
+
+        let config = ClientConfig::default()
+            .with_base_url(server.uri())
+            .with_max_retries(2)
+            .with_retry_delay_ms(10);
+
+        let metrics = Arc::new(crate::metrics::MetricsCollector::new());
+        let client = AnthropicClient::new("test-key", config)
+            .unwrap()
+            .with_metrics(Arc::clone(&metrics));
+        let request = ApiRequest::new("claude-3", 1000, vec![ApiMessage::user("Hi")])
+            .with_metrics_mode("linear");
+
+        let result = client.complete(request).await;
+        assert!(result.is_ok());
+
+        // 3 physical attempts served 1 logical operation; 2 were retries.
+        let summary = metrics.retry_summary();
+        assert_eq!(summary.attempts_total.get("linear"), Some(&3));
+        assert_eq!(summary.retries_total.get("linear/rate_limited"), Some(&2));
+        assert!(summary.retry_exhausted_total.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_retry_metrics_record_exhaustion() {
+        use std::sync::Arc;
+
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .respond_with(ResponseTemplate::new(529))
+            .mount(&server)
+            .await;
+
+        let config = ClientConfig::default()
+            .with_base_url(server.uri())
+            .with_max_retries(2)
+            .with_retry_delay_ms(10);
+
+        let metrics = Arc::new(crate::metrics::MetricsCollector::new());
+        let client = AnthropicClient::new("test-key", config)
+            .unwrap()
+            .with_metrics(Arc::clone(&metrics));
+        // Untagged request: counted under the shared "unlabeled" bucket.
+        let request = ApiRequest::new("claude-3", 1000, vec![ApiMessage::user("Hi")]);
+
+        let result = client.complete(request).await;
+        assert!(result.is_err());
+
+        let summary = metrics.retry_summary();
+        assert_eq!(summary.attempts_total.get("unlabeled"), Some(&3));
+        assert_eq!(summary.retries_total.get("unlabeled/overloaded"), Some(&2));
+        assert_eq!(
+            summary.retry_exhausted_total.get("unlabeled/overloaded"),
+            Some(&1)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prompt_caching_marks_static_prefix_cacheable() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(success_response_body("ok")))
+            .mount(&server)
+            .await;
+
+        let config = ClientConfig::default()
+            .with_base_url(server.uri())
+            .with_prompt_caching(true);
+        let client = AnthropicClient::new("test-key", config).unwrap();
+
+        let static_prompt = "You are a bias detector. Respond with JSON.\n";
+        let dynamic = "Content to analyze:\nthe argument under review";
+        let result = AnthropicClientTrait::complete(
+            &client,
+            vec![Message::user(format!("{static_prompt}{dynamic}"))],
+            CompletionConfig::new().with_cached_prompt_len(static_prompt.len()),
+        )
+        .await;
+        assert!(result.is_ok());
+
+        // The outgoing message is split at the declared boundary: the static
+        // prompt is marked cacheable, the dynamic remainder is not.
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        let parts = body["messages"][0]["content"].as_array().unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0]["text"], static_prompt);
+        assert_eq!(parts[0]["cache_control"]["type"], "ephemeral");
+        assert_eq!(parts[1]["text"], dynamic);
+        assert!(parts[1].get("cache_control").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_prompt_caching_disabled_leaves_message_unsplit() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(success_response_body("ok")))
+            .mount(&server)
+            .await;
+
+        // Caching off (the default): the declared prefix length is ignored.
+        let config = ClientConfig::default().with_base_url(server.uri());
+        let client = AnthropicClient::new("test-key", config).unwrap();
+
+        let result = AnthropicClientTrait::complete(
+            &client,
+            vec![Message::user("prompt then content")],
+            CompletionConfig::new().with_cached_prompt_len(7),
+        )
+        .await;
+        assert!(result.is_ok());
+
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["messages"][0]["content"], "prompt then content");
+    }
+
+    #[tokio::test]
+    async fn test_output_schema_sends_tool_and_surfaces_structured_payload() {
+        use crate::traits::OutputSchema;
+
+        let server = MockServer::start().await;
+
+        // The model honors the forced tool choice: the answer arrives as a
+        // tool call carrying the schema-conforming payload.
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "msg_123",
+                "content": [{
+                    "type": "tool_use",
+                    "id": "toolu_1",
+                    "name": "linear_result",
+                    "input": {"analysis": "structured", "confidence": 0.9}
+                }],
+                "model": "claude-3",
+                "usage": {"input_tokens": 10, "output_tokens": 20},
+                "stop_reason": "tool_use"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = create_mock_client(&server).await;
+        let schema = json!({
+            "type": "object",
+            "properties": {"analysis": {"type": "string"}},
+            "required": ["analysis"]
+        });
+        let result = AnthropicClientTrait::complete(
+            &client,
+            vec![Message::user("analyze")],
+            CompletionConfig::new()
+                .with_output_schema(OutputSchema::new("linear_result", schema.clone())),
+        )
+        .await;
+
+        let response = result.unwrap();
+        assert_eq!(
+            response.structured,
+            Some(json!({"analysis": "structured", "confidence": 0.9}))
+        );
+
+        // The outgoing request carries the schema as a forced tool choice.
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["tools"][0]["name"], "linear_result");
+        assert_eq!(body["tools"][0]["input_schema"], schema);
+        assert_eq!(body["tool_choice"]["type"], "tool");
+        assert_eq!(body["tool_choice"]["name"], "linear_result");
+    }
+
+    #[tokio::test]
+    async fn test_output_schema_falls_back_to_text_when_model_answers_free_form() {
+        use crate::traits::OutputSchema;
+
+        let server = MockServer::start().await;
+
+        // The model ignored the tool and answered in text: `structured`
+        // stays unset and the caller's free-form parse takes over.
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(success_response_body(r#"{"analysis": "text"}"#)),
+            )
+            .mount(&server)
+            .await;
+
+        let client = create_mock_client(&server).await;
+        let result = AnthropicClientTrait::complete(
+            &client,
+            vec![Message::user("analyze")],
+            CompletionConfig::new().with_output_schema(OutputSchema::new(
+                "linear_result",
+                json!({"type": "object"}),
+            )),
+        )
+        .await;
+
+        let response = result.unwrap();
+        assert!(response.structured.is_none());
+        assert_eq!(response.content, r#"{"analysis": "text"}"#);
+    }
+
+    #[tokio::test]
+    async fn test_output_schema_with_thinking_offers_tool_without_forcing() {
+        use crate::traits::OutputSchema;
+
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(success_response_body("ok")))
+            .mount(&server)
+            .await;
+
+        let client = create_mock_client(&server).await;
+        let result = AnthropicClientTrait::complete(
+            &client,
+            vec![Message::user("analyze")],
+            CompletionConfig::new()
+                .with_output_schema(OutputSchema::new(
+                    "linear_result",
+                    json!({"type": "object"}),
+                ))
+                .with_thinking_budget(2048),
+        )
+        .await;
+        assert!(result.is_ok());
+
+        // A forced tool choice is incompatible with extended thinking, so
+        // the tool is offered under `auto` instead.
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["tools"][0]["name"], "linear_result");
+        assert_eq!(body["tool_choice"]["type"], "auto");
+        assert!(body["tool_choice"].get("name").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cache_read_tokens_surface_in_usage_and_metrics() {
+        use std::sync::Arc;
+
+        let server = MockServer::start().await;
+
+        // The mock reports most of the input served from the prompt cache.
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "msg_123",
+                "content": [{"type": "text", "text": "ok"}],
+                "model": "claude-3",
+                "usage": {
+                    "input_tokens": 10,
+                    "output_tokens": 20,
+                    "cache_read_input_tokens": 900
+                },
+                "stop_reason": "end_turn"
+            })))
+            .mount(&server)
+            .await;
+
+        let config = ClientConfig::default()
+            .with_base_url(server.uri())
+            .with_prompt_caching(true);
+        let metrics = Arc::new(crate::metrics::MetricsCollector::new());
+        let client = AnthropicClient::new("test-key", config)
+            .unwrap()
+            .with_metrics(Arc::clone(&metrics));
+
+        let response = AnthropicClientTrait::complete(
+            &client,
+            vec![Message::user("static prompt then content")],
+            CompletionConfig::new()
+                .with_mode("detect")
+                .with_cached_prompt_len(13),
+        )
+        .await
+        .unwrap();
+
+        // Cache reads are carried distinctly, not folded into input tokens.
+        assert_eq!(response.usage.input_tokens, 10);
+        assert_eq!(response.usage.cache_read_input_tokens, 900);
+        assert_eq!(response.usage.total(), 30);
+
+        let summary = metrics.token_usage_summary();
+        assert_eq!(summary.input_tokens_total.get("detect"), Some(&10));
+        assert_eq!(summary.cache_read_tokens_total.get("detect"), Some(&900));
+    }
+
+    #[tokio::test]
+    async fn test_trait_complete_carries_thinking_trace() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "msg_123",
+                "content": [
+                    {"type": "thinking", "thinking": "Considering both options..."},
+                    {"type": "text", "text": "{\"answer\": 42}"}
+                ],
+                "model": "claude-3",
+                "usage": {"input_tokens": 10, "output_tokens": 30},
+                "stop_reason": "end_turn"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = create_mock_client(&server).await;
+        let response = AnthropicClientTrait::complete(
+            &client,
+            vec![Message::user("Think hard")],
+            CompletionConfig::new().with_deep_thinking(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.content, r#"{"answer": 42}"#);
+        assert_eq!(
+            response.thinking.as_deref(),
+            Some("Considering both options...")
+        );
+    }
+
     #[tokio::test]
     async fn test_no_retry_on_auth_failure() {
         let server = MockServer::start().await;
@@ -1178,6 +1916,207 @@ mod tests {
         ));
     }
 
+    // Shared retry budget tests
+    #[test]
+    fn test_retry_budget_consume_until_empty() {
+        let budget = RetryBudget::new(2);
+        assert_eq!(budget.remaining(), 2);
+        assert!(budget.try_consume());
+        assert!(budget.try_consume());
+        assert_eq!(budget.remaining(), 0);
+        assert!(!budget.try_consume());
+        assert_eq!(budget.remaining(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_shared_budget_spans_calls() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::sync::Arc;
+
+        let server = MockServer::start().await;
+        let call_count = Arc::new(AtomicU32::new(0));
+        let call_count_clone = Arc::clone(&call_count);
+
+        // Every odd-numbered request fails transiently (529), so each call
+        // wants one retry: call 1 = fail, retry, succeed; call 2 = fail.
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .respond_with(move |_req: &wiremock::Request| {
+                let count = call_count_clone.fetch_add(1, Ordering::SeqCst);
+                if count.is_multiple_of(2) {
+                    ResponseTemplate::new(529)
+                } else {
+                    ResponseTemplate::new(200).set_body_json(success_response_body("Success!"))
+                }
+            })
+            .mount(&server)
+            .await;
+
+        let config = ClientConfig::default()
+            .with_base_url(server.uri())
+            .with_max_retries(3)
+            .with_retry_delay_ms(10);
+        let client = AnthropicClient::new("test-key", config).unwrap();
+
+        // One retry for the whole "preset": the first step may use it, the
+        // second step must then fail fast without retrying.
+        let budget = RetryBudget::new(1);
+
+        let first = client
+            .complete_with_budget(
+                ApiRequest::new("claude-3", 1000, vec![ApiMessage::user("step 1")]),
+                &budget,
+            )
+            .await;
+        assert!(first.is_ok(), "first step retries and succeeds");
+        assert_eq!(budget.remaining(), 0);
+
+        let second = client
+            .complete_with_budget(
+                ApiRequest::new("claude-3", 1000, vec![ApiMessage::user("step 2")]),
+                &budget,
+            )
+            .await;
+        assert!(matches!(
+            second.unwrap_err(),
+            AnthropicError::ModelOverloaded { .. }
+        ));
+        // 2 requests for step 1 (fail + retry) and exactly 1 for step 2:
+        // the exhausted pool means the second failing step is not retried.
+        assert_eq!(call_count.load(Ordering::SeqCst), 3);
+    }
+
+    // Truncation (stop_reason == max_tokens) tests
+
+    fn response_body_with_stop(text: &str, stop_reason: &str) -> serde_json::Value {
+        json!({
+            "id": "msg_123",
+            "content": [{"type": "text", "text": text}],
+            "model": "claude-3",
+            "usage": {"input_tokens": 10, "output_tokens": 20},
+            "stop_reason": stop_reason
+        })
+    }
+
+    #[tokio::test]
+    async fn test_complete_truncated_returns_distinct_error() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(response_body_with_stop(
+                    r#"{"partial": "json that never clo"#,
+                    "max_tokens",
+                )),
+            )
+            .mount(&server)
+            .await;
+
+        let client = create_mock_client(&server).await;
+        let request = ApiRequest::new("claude-3", 1000, vec![ApiMessage::user("Hi")]);
+
+        let result = client.complete(request).await;
+        assert!(result.is_err());
+        let err = result.unwrap_err();
+        assert!(matches!(err, AnthropicError::UnexpectedResponse { .. }));
+        assert!(err.to_string().contains("truncated at max_tokens"));
+    }
+
+    #[tokio::test]
+    async fn test_complete_auto_continue_gathers_rest() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::sync::Arc;
+
+        let server = MockServer::start().await;
+        let call_count = Arc::new(AtomicU32::new(0));
+        let call_count_clone = Arc::clone(&call_count);
+
+        // First call is cut off mid-JSON at max_tokens; the continuation
+        // (with the partial text as an assistant prefill) finishes it.
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .respond_with(move |_req: &wiremock::Request| {
+                let count = call_count_clone.fetch_add(1, Ordering::SeqCst);
+                if count == 0 {
+                    ResponseTemplate::new(200).set_body_json(response_body_with_stop(
+                        r#"{"answer": "first ha"#,
+                        "max_tokens",
+                    ))
+                } else {
+                    ResponseTemplate::new(200).set_body_json(response_body_with_stop(
+                        r#"lf and second half"}"#,
+                        "end_turn",
+                    ))
+                }
+            })
+            .mount(&server)
+            .await;
+
+        let config = ClientConfig::default()
+            .with_base_url(server.uri())
+            .with_max_retries(0)
+            .with_auto_continue(true);
+        let client = AnthropicClient::new("test-api-key", config).unwrap();
+        let request = ApiRequest::new("claude-3", 1000, vec![ApiMessage::user("Hi")]);
+
+        let result = client.complete(request).await;
+        assert!(result.is_ok());
+
+        let response = result.unwrap();
+        assert_eq!(
+            response.raw_text,
+            r#"{"answer": "first half and second half"}"#
+        );
+        // The reassembled text parses as JSON even though each half did not.
+        assert_eq!(
+            response.parsed.as_ref().expect("parsed")["answer"],
+            "first half and second half"
+        );
+        // Usage accumulates across the continuation.
+        assert_eq!(response.usage.output_tokens, 40);
+        assert!(!response.is_truncated());
+        assert_eq!(call_count.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_complete_auto_continue_gives_up_after_cap() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::sync::Arc;
+
+        let server = MockServer::start().await;
+        let call_count = Arc::new(AtomicU32::new(0));
+        let call_count_clone = Arc::clone(&call_count);
+
+        // Every response is truncated, so continuation never finishes.
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .respond_with(move |_req: &wiremock::Request| {
+                call_count_clone.fetch_add(1, Ordering::SeqCst);
+                ResponseTemplate::new(200)
+                    .set_body_json(response_body_with_stop("still going", "max_tokens"))
+            })
+            .mount(&server)
+            .await;
+
+        let config = ClientConfig::default()
+            .with_base_url(server.uri())
+            .with_max_retries(0)
+            .with_auto_continue(true);
+        let client = AnthropicClient::new("test-api-key", config).unwrap();
+        let request = ApiRequest::new("claude-3", 1000, vec![ApiMessage::user("Hi")]);
+
+        let result = client.complete(request).await;
+        let err = result.unwrap_err();
+        assert!(matches!(err, AnthropicError::UnexpectedResponse { .. }));
+        assert!(err.to_string().contains("after 3 continuations"));
+        // Initial request plus the capped continuation rounds.
+        assert_eq!(
+            call_count.load(Ordering::SeqCst),
+            1 + MAX_AUTO_CONTINUATIONS
+        );
+    }
+
     // JSON extraction tests
     #[test]
     fn test_extract_json_raw_valid() {
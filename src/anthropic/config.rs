@@ -33,6 +33,18 @@ pub struct ClientConfig {
     pub max_retries: u32,
     /// Initial retry delay in milliseconds.
     pub retry_delay_ms: u64,
+    /// Automatically continue completions truncated at `max_tokens`.
+    ///
+    /// Off by default: without it a truncated response surfaces as a distinct
+    /// error instead of a confusing downstream JSON parse failure.
+    pub auto_continue: bool,
+    /// Mark static prompt prefixes cacheable (prompt caching).
+    ///
+    /// When enabled, a call whose config declares a `cached_prompt_len` has
+    /// that prefix of its first user message marked with `cache_control`, so
+    /// repeated calls with the same mode prompt read it from the prompt cache
+    /// at a reduced input-token rate. Off by default.
+    pub prompt_caching: bool,
 }
 
 impl ClientConfig {
@@ -63,12 +75,26 @@ impl ClientConfig {
         self
     }
 
+    /// Enable automatic continuation of completions truncated at `max_tokens`.
+    #[must_use]
+    pub const fn with_auto_continue(mut self, auto_continue: bool) -> Self {
+        self.auto_continue = auto_continue;
+        self
+    }
+
     /// Set retry delay in milliseconds.
     #[must_use]
     pub const fn with_retry_delay_ms(mut self, retry_delay_ms: u64) -> Self {
         self.retry_delay_ms = retry_delay_ms;
         self
     }
+
+    /// Enable prompt caching of static prompt prefixes.
+    #[must_use]
+    pub const fn with_prompt_caching(mut self, prompt_caching: bool) -> Self {
+        self.prompt_caching = prompt_caching;
+        self
+    }
 }
 
 impl Default for ClientConfig {
@@ -78,6 +104,8 @@ impl Default for ClientConfig {
             timeout_ms: DEFAULT_TIMEOUT_MS,
             max_retries: DEFAULT_MAX_RETRIES,
             retry_delay_ms: DEFAULT_RETRY_DELAY_MS,
+            auto_continue: false,
+            prompt_caching: false,
         }
     }
 }
@@ -219,6 +247,14 @@ mod tests {
         assert_eq!(config.max_retries, 5);
     }
 
+    #[test]
+    fn test_client_config_with_prompt_caching() {
+        let config = ClientConfig::new();
+        assert!(!config.prompt_caching);
+        let config = config.with_prompt_caching(true);
+        assert!(config.prompt_caching);
+    }
+
     #[test]
     fn test_client_config_with_retry_delay_ms() {
         let config = ClientConfig::new().with_retry_delay_ms(2_000);
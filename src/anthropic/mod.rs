@@ -34,14 +34,18 @@
 
 mod client;
 mod config;
+mod offline;
 mod streaming;
 mod types;
 
-pub use client::{AnthropicClient, MAX_CONTENT_LENGTH, MAX_MESSAGES, MAX_REQUEST_BYTES};
+pub use client::{
+    AnthropicClient, RetryBudget, MAX_CONTENT_LENGTH, MAX_MESSAGES, MAX_REQUEST_BYTES,
+};
 pub use config::{
     ClientConfig, ModeConfig, DEFAULT_BASE_URL, DEFAULT_MAX_RETRIES, DEFAULT_MAX_TOKENS,
     DEFAULT_MODEL, DEFAULT_RETRY_DELAY_MS, DEFAULT_TIMEOUT_MS,
 };
+pub use offline::{canned_content, OfflineClient};
 pub use streaming::{parse_sse_line, StreamAccumulator};
 pub use types::{
     ApiErrorBody, ApiErrorDetails, ApiMessage, ApiRequest, ApiResponse, ApiUsage, ContentBlock,
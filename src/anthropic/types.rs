@@ -23,6 +23,12 @@ pub struct ApiRequest {
     /// Temperature for sampling (0.0-1.0).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f64>,
+    /// Nucleus-sampling cumulative probability cutoff (0.0-1.0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    /// Top-k sampling cutoff.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<u32>,
     /// System prompt.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub system: Option<String>,
@@ -40,6 +46,10 @@ pub struct ApiRequest {
     /// Enable streaming.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stream: Option<bool>,
+    /// Mode label for retry-metrics attribution. Local bookkeeping only —
+    /// never serialized to the API.
+    #[serde(skip)]
+    pub metrics_mode: Option<String>,
 }
 
 impl ApiRequest {
@@ -50,12 +60,15 @@ impl ApiRequest {
             model: model.into(),
             max_tokens,
             temperature: None,
+            top_p: None,
+            top_k: None,
             system: None,
             messages,
             thinking: None,
             tools: None,
             tool_choice: None,
             stream: None,
+            metrics_mode: None,
         }
     }
 
@@ -66,6 +79,20 @@ impl ApiRequest {
         self
     }
 
+    /// Set nucleus-sampling cutoff.
+    #[must_use]
+    pub fn with_top_p(mut self, top_p: f64) -> Self {
+        self.top_p = Some(top_p);
+        self
+    }
+
+    /// Set top-k sampling cutoff.
+    #[must_use]
+    pub fn with_top_k(mut self, top_k: u32) -> Self {
+        self.top_k = Some(top_k);
+        self
+    }
+
     /// Set system prompt.
     #[must_use]
     pub fn with_system(mut self, system: impl Into<String>) -> Self {
@@ -94,6 +121,13 @@ impl ApiRequest {
         self
     }
 
+    /// Set the mode label for retry-metrics attribution.
+    #[must_use]
+    pub fn with_metrics_mode(mut self, mode: impl Into<String>) -> Self {
+        self.metrics_mode = Some(mode.into());
+        self
+    }
+
     /// Enable streaming.
     #[must_use]
     pub fn with_streaming(mut self, stream: bool) -> Self {
@@ -168,6 +202,28 @@ impl MessageContent {
     }
 }
 
+/// Prompt-cache directive for a content part.
+///
+/// Marking a part with this tells the API to cache everything up to and
+/// including it, so a stable prompt prefix is read from cache on later calls
+/// instead of being reprocessed (and billed) in full.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CacheControl {
+    /// Cache type - always "ephemeral".
+    #[serde(rename = "type")]
+    pub type_: String,
+}
+
+impl CacheControl {
+    /// Create the ephemeral cache directive (the only type the API supports).
+    #[must_use]
+    pub fn ephemeral() -> Self {
+        Self {
+            type_: "ephemeral".to_string(),
+        }
+    }
+}
+
 /// Content part for multimodal messages.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type")]
@@ -177,6 +233,9 @@ pub enum ContentPart {
     Text {
         /// The text content.
         text: String,
+        /// Prompt-cache directive; omitted from the wire format when unset.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
     },
     /// Image content.
     #[serde(rename = "image")]
@@ -190,7 +249,19 @@ impl ContentPart {
     /// Create a text content part.
     #[must_use]
     pub fn text(text: impl Into<String>) -> Self {
-        Self::Text { text: text.into() }
+        Self::Text {
+            text: text.into(),
+            cache_control: None,
+        }
+    }
+
+    /// Create a text content part marked cacheable (prompt caching).
+    #[must_use]
+    pub fn cached_text(text: impl Into<String>) -> Self {
+        Self::Text {
+            text: text.into(),
+            cache_control: Some(CacheControl::ephemeral()),
+        }
     }
 
     /// Create an image content part from base64 data.
@@ -216,7 +287,7 @@ impl ContentPart {
     #[must_use]
     pub fn len(&self) -> usize {
         match self {
-            Self::Text { text } => text.len(),
+            Self::Text { text, .. } => text.len(),
             Self::Image { source } => source.len(),
         }
     }
@@ -464,6 +535,13 @@ pub struct ApiUsage {
     pub input_tokens: u32,
     /// Output tokens generated.
     pub output_tokens: u32,
+    /// Input tokens served from the prompt cache (absent when caching was not
+    /// in play; counted separately from `input_tokens` by the API).
+    #[serde(default)]
+    pub cache_read_input_tokens: u32,
+    /// Input tokens written to the prompt cache on this call.
+    #[serde(default)]
+    pub cache_creation_input_tokens: u32,
 }
 
 impl ApiUsage {
@@ -473,6 +551,8 @@ impl ApiUsage {
         Self {
             input_tokens,
             output_tokens,
+            cache_read_input_tokens: 0,
+            cache_creation_input_tokens: 0,
         }
     }
 
@@ -496,6 +576,8 @@ pub struct ReasoningResponse {
     pub thinking: Option<String>,
     /// Tool use blocks (if any).
     pub tool_uses: Vec<ToolUseResult>,
+    /// Reason the response stopped (e.g. `end_turn`, `max_tokens`).
+    pub stop_reason: Option<String>,
 }
 
 impl ReasoningResponse {
@@ -508,9 +590,24 @@ impl ReasoningResponse {
             usage,
             thinking: None,
             tool_uses: Vec::new(),
+            stop_reason: None,
         }
     }
 
+    /// Set the stop reason.
+    #[must_use]
+    pub fn with_stop_reason(mut self, stop_reason: impl Into<String>) -> Self {
+        self.stop_reason = Some(stop_reason.into());
+        self
+    }
+
+    /// True when the response was cut off at the `max_tokens` cap and is
+    /// therefore incomplete.
+    #[must_use]
+    pub fn is_truncated(&self) -> bool {
+        self.stop_reason.as_deref() == Some("max_tokens")
+    }
+
     /// Set parsed JSON.
     #[must_use]
     pub fn with_parsed(mut self, parsed: serde_json::Value) -> Self {
@@ -699,6 +796,30 @@ mod tests {
         assert!(json.contains("1000"));
     }
 
+    #[test]
+    fn test_api_request_serialization_omits_unset_sampling() {
+        let req = ApiRequest::new("claude-3", 1000, vec![ApiMessage::user("Hi")]);
+        let json = serde_json::to_string(&req).unwrap();
+        assert!(!json.contains("top_p"));
+        assert!(!json.contains("top_k"));
+    }
+
+    #[test]
+    fn test_api_request_with_top_p_serialization() {
+        let req = ApiRequest::new("claude-3", 1000, vec![]).with_top_p(0.9);
+        assert_eq!(req.top_p, Some(0.9));
+        let json = serde_json::to_string(&req).unwrap();
+        assert!(json.contains("\"top_p\":0.9"));
+    }
+
+    #[test]
+    fn test_api_request_with_top_k_serialization() {
+        let req = ApiRequest::new("claude-3", 1000, vec![]).with_top_k(40);
+        assert_eq!(req.top_k, Some(40));
+        let json = serde_json::to_string(&req).unwrap();
+        assert!(json.contains("\"top_k\":40"));
+    }
+
     // ApiMessage tests
     #[test]
     fn test_api_message_user() {
@@ -777,6 +898,21 @@ mod tests {
         assert!(json.contains("\"text\":\"Hello\""));
     }
 
+    #[test]
+    fn test_content_part_text_serialization_omits_cache_control() {
+        let part = ContentPart::text("Hello");
+        let json = serde_json::to_string(&part).unwrap();
+        assert!(!json.contains("cache_control"));
+    }
+
+    #[test]
+    fn test_content_part_cached_text_serialization() {
+        let part = ContentPart::cached_text("Static prompt");
+        let json = serde_json::to_string(&part).unwrap();
+        assert!(json.contains("\"type\":\"text\""));
+        assert!(json.contains("\"cache_control\":{\"type\":\"ephemeral\"}"));
+    }
+
     #[test]
     fn test_content_part_image_base64_serialization() {
         let part = ContentPart::image_base64("image/png", "abc123");
@@ -982,6 +1118,26 @@ mod tests {
         let usage = ApiUsage::default();
         assert_eq!(usage.input_tokens, 0);
         assert_eq!(usage.output_tokens, 0);
+        assert_eq!(usage.cache_read_input_tokens, 0);
+        assert_eq!(usage.cache_creation_input_tokens, 0);
+    }
+
+    #[test]
+    fn test_api_usage_deserializes_cache_fields() {
+        // Cache fields are optional on the wire: absent means zero.
+        let usage: ApiUsage =
+            serde_json::from_value(json!({"input_tokens": 10, "output_tokens": 20})).unwrap();
+        assert_eq!(usage.cache_read_input_tokens, 0);
+
+        let usage: ApiUsage = serde_json::from_value(json!({
+            "input_tokens": 10,
+            "output_tokens": 20,
+            "cache_read_input_tokens": 900,
+            "cache_creation_input_tokens": 50
+        }))
+        .unwrap();
+        assert_eq!(usage.cache_read_input_tokens, 900);
+        assert_eq!(usage.cache_creation_input_tokens, 50);
     }
 
     // ReasoningResponse tests
@@ -49,7 +49,12 @@ async fn main() {
         }
     };
 
-    let storage = match SqliteStorage::new(&config.database_path).await {
+    let storage = match SqliteStorage::new_with_max_connections(
+        &config.database_path,
+        config.database_max_connections,
+    )
+    .await
+    {
         Ok(s) => s,
         Err(e) => {
             eprintln!("storage error: {e}");
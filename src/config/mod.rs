@@ -15,6 +15,7 @@
 //! let config = Config {
 //!     api_key: SecretString::new("sk-ant-example-key"),
 //!     database_path: "./data/reasoning.db".to_string(),
+//!     database_max_connections: 5,
 //!     log_level: "info".to_string(),
 //!     request_timeout_ms: 30000,
 //!     request_timeout_deep_ms: 60000,
@@ -28,6 +29,28 @@
 //!     reflection_quality_threshold: 0.8,
 //!     mcts_quality_threshold: 0.5,
 //!     graph_prune_threshold: 0.3,
+//!     graph_max_nodes: 200,
+//!     graph_max_returned_children: None,
+//!     rubric_llm_weight: 0.5,
+//!     evidence_interval_width: 0.1,
+//!     sticky_session: false,
+//!     detect_filter_unverified: false,
+//!     strict_parsing: false,
+//!     require_session_id: false,
+//!     self_correct_parse: false,
+//!     structured_output: false,
+//!     confidence_floor: None,
+//!     prompt_caching: false,
+//!     response_language: None,
+//!     enabled_tools: None,
+//!     disabled_tools: vec![],
+//!     divergent_per_perspective: false,
+//!     divergent_max_concurrency: 3,
+//!     linear_store_raw_io: false,
+//!     max_pending_requests: 32,
+//!     auto_history_bias: false,
+//!     retry_degenerate: false,
+//!     prompt_version: None,
 //! };
 //!
 //! println!("Using model: {}", config.model);
@@ -44,13 +67,18 @@ mod self_improvement;
 
 pub use secret::SecretString;
 pub use self_improvement::SelfImprovementConfig;
-pub use validation::{validate_config, MAX_RETRIES, MAX_TIMEOUT_MS, MIN_TIMEOUT_MS};
+pub use validation::{
+    validate_config, MAX_DATABASE_CONNECTIONS, MAX_RETRIES, MAX_TIMEOUT_MS, MIN_TIMEOUT_MS,
+};
 
 use crate::error::ConfigError;
 
 /// Default database path.
 pub const DEFAULT_DATABASE_PATH: &str = "./data/reasoning.db";
 
+/// Default maximum `SQLite` connection-pool size.
+pub const DEFAULT_DATABASE_MAX_CONNECTIONS: u32 = 5;
+
 /// Default log level.
 pub const DEFAULT_LOG_LEVEL: &str = "info";
 
@@ -84,6 +112,30 @@ pub const DEFAULT_MCTS_QUALITY_THRESHOLD: f64 = 0.5;
 /// are pruning candidates), used when a caller omits `threshold`.
 pub const DEFAULT_GRAPH_PRUNE_THRESHOLD: f64 = 0.3;
 
+/// Default half-width of the credible interval around a probabilistic
+/// posterior, applied to each likelihood the model did not bound with an
+/// explicit range.
+pub const DEFAULT_EVIDENCE_INTERVAL_WIDTH: f64 = 0.1;
+
+/// Default cap on concurrent per-perspective divergent completions.
+pub const DEFAULT_DIVERGENT_MAX_CONCURRENCY: u32 = 3;
+
+/// Upper bound on `DIVERGENT_MAX_CONCURRENCY` (matches the perspective cap).
+const MAX_DIVERGENT_CONCURRENCY: u32 = 5;
+
+/// Default bound on admitted-but-unfinished tool calls (`MAX_PENDING_REQUESTS`).
+pub const DEFAULT_MAX_PENDING_REQUESTS: u32 = 32;
+
+/// Default per-session graph node ceiling (`GRAPH_MAX_NODES`).
+pub const DEFAULT_GRAPH_MAX_NODES: u32 = 200;
+
+/// Upper bound on `GRAPH_MAX_NODES` (keeps graph reads and prune scans bounded).
+const MAX_GRAPH_MAX_NODES: u32 = 10_000;
+
+/// Default weight of the model's self-reported quality in the graph finalize
+/// rubric (`QUALITY_RUBRIC_LLM_WEIGHT`); the structural remainder gets the rest.
+pub const DEFAULT_RUBRIC_LLM_WEIGHT: f64 = 0.5;
+
 /// Default Anthropic model.
 pub const DEFAULT_MODEL: &str = "claude-sonnet-4-20250514";
 
@@ -101,12 +153,18 @@ pub const DEFAULT_MODEL: &str = "claude-sonnet-4-20250514";
 /// - Deep modes (8K tokens): `request_timeout_deep_ms` (default: 60s)
 /// - Maximum modes (16K tokens): `request_timeout_maximum_ms` (default: 120s)
 // `PartialEq` only (not `Eq`): `high_confidence_threshold` is an `f64`.
+// Independent opt-in feature flags, each from its own env var — not a state machine.
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone, PartialEq)]
 pub struct Config {
     /// Anthropic API key (protected from logging via [`SecretString`]).
     pub api_key: SecretString,
     /// Database path.
     pub database_path: String,
+    /// Maximum `SQLite` connection-pool size. Concurrent tool calls each
+    /// acquire a connection from the pool; writes still serialize at the
+    /// `SQLite` level (WAL mode), but reads proceed in parallel.
+    pub database_max_connections: u32,
     /// Log level (error, warn, info, debug, trace).
     pub log_level: String,
     /// Request timeout in milliseconds (fast/standard modes).
@@ -143,6 +201,129 @@ pub struct Config {
     /// are pruning candidates), used when a caller omits it. A real, tunable knob
     /// the self-improvement system can adjust. 0.0–1.0.
     pub graph_prune_threshold: f64,
+    /// Per-session graph node ceiling (`GRAPH_MAX_NODES`): `reasoning_graph`
+    /// refuses to grow a session's graph past this many nodes and suggests
+    /// pruning instead. Clamped to 1–10000.
+    pub graph_max_nodes: usize,
+    /// Cap on children returned by a single `reasoning_graph` generate or
+    /// advance response (`GRAPH_MAX_RETURNED_CHILDREN`). Only trims the
+    /// response — every child is still persisted. `None` (the default) means
+    /// uncapped; a configured value must be a positive integer.
+    pub graph_max_returned_children: Option<usize>,
+    /// Weight of the model's self-reported quality in the blended
+    /// `reasoning_graph` finalize rubric (`QUALITY_RUBRIC_LLM_WEIGHT`); the
+    /// three structural components share `1 - weight`. 0.0–1.0.
+    pub rubric_llm_weight: f64,
+    /// Half-width applied to each likelihood without a model-provided range
+    /// when computing the credible interval around a probabilistic posterior
+    /// (`EVIDENCE_INTERVAL_WIDTH`). 0.0–1.0.
+    pub evidence_interval_width: f64,
+    /// Sticky-session mode (`STICKY_SESSION=true`): when a reasoning tool call
+    /// omits `session_id`, reuse the session the last reasoning call ran in
+    /// instead of starting a new one. Off by default so existing behavior
+    /// (each bare call is a fresh session) is unchanged.
+    pub sticky_session: bool,
+    /// Drop unverified detections (`DETECT_FILTER_UNVERIFIED=true`): when
+    /// `reasoning_detect` finds that a detection's cited passage/evidence is
+    /// not present in the analyzed content, omit it from the response instead
+    /// of just flagging it. Off by default so callers see every finding.
+    pub detect_filter_unverified: bool,
+    /// Strict response parsing (`STRICT_PARSING=true`): mode parsers error on
+    /// unexpected top-level keys in model responses instead of silently
+    /// dropping them, naming the unknown keys. Off by default for leniency
+    /// toward schema drift.
+    pub strict_parsing: bool,
+    /// Required session ids for stateful modes (`REQUIRE_SESSION_ID=true`):
+    /// graph, timeline, checkpoint, and reflection evaluate refuse to run
+    /// without a session id instead of silently creating a throwaway
+    /// session, so context is never lost to an unnamed session. Stateless
+    /// modes are unaffected. Off by default.
+    pub require_session_id: bool,
+    /// One-shot parse self-correction (`SELF_CORRECT_PARSE=true`): when a
+    /// model response fails to parse (invalid JSON, missing field, invalid
+    /// value), send one follow-up completion explaining the specific error
+    /// and re-parse the corrected output instead of failing. Capped at a
+    /// single correction per call to bound cost. Off by default.
+    pub self_correct_parse: bool,
+    /// Structured output (`STRUCTURED_OUTPUT=true`): modes that support it
+    /// constrain completions to their response schema via a forced tool
+    /// call, so the result parses directly instead of going through the
+    /// free-form JSON extraction heuristics. When the API returns text
+    /// anyway (e.g. under extended thinking), the free-form parse is the
+    /// fallback. Off by default.
+    pub structured_output: bool,
+    /// Confidence floor (`CONFIDENCE_FLOOR`, 0.0–1.0): when set, a linear
+    /// reasoning pass whose reported confidence falls below the floor is
+    /// rerun once with a deep thinking budget and the higher-confidence
+    /// result is returned. Unset by default (no escalation), since the rerun
+    /// doubles the call's cost in the worst case.
+    pub confidence_floor: Option<f64>,
+    /// Prompt caching (`PROMPT_CACHING=true`): mark the static mode prompt at
+    /// the start of each request as cacheable so repeated calls read it from
+    /// the Anthropic prompt cache at a reduced input-token rate; dynamic
+    /// content stays uncached. Off by default.
+    pub prompt_caching: bool,
+    /// Default output language for reasoning results (`RESPONSE_LANGUAGE`):
+    /// when set, modes instruct the model to write natural-language field
+    /// values in this language while keeping JSON keys and enumeration
+    /// values in English so parsing is unaffected. The special value `auto`
+    /// matches the language detected from the request content; the
+    /// `auto:<Fallback>` form names the language used when detection is
+    /// uncertain (bare `auto` then adds no instruction). Unset by default
+    /// (English); individual tool calls can override it via `language`.
+    pub response_language: Option<String>,
+    /// Tool allowlist (`ENABLED_TOOLS`, comma-separated tool names): when set,
+    /// only the listed tools appear in `tools/list` and are accepted by
+    /// `tools/call`; every other tool is rejected as unknown. Unset by default
+    /// (every tool exposed). Names are checked against the real tool set at
+    /// startup so a typo fails fast instead of silently hiding tools.
+    pub enabled_tools: Option<Vec<String>>,
+    /// Tool denylist (`DISABLED_TOOLS`, comma-separated tool names): the listed
+    /// tools are hidden from `tools/list` and rejected by `tools/call`.
+    /// Applied on top of `enabled_tools`. Empty by default.
+    pub disabled_tools: Vec<String>,
+    /// Per-perspective divergent fan-out (`DIVERGENT_PER_PERSPECTIVE=true`):
+    /// `reasoning_divergent` runs one deep completion per perspective
+    /// concurrently and aggregates them with a synthesis pass, instead of
+    /// generating every perspective in a single call. Richer independent
+    /// viewpoints at the cost of one API call per perspective plus one for
+    /// synthesis. Off by default.
+    pub divergent_per_perspective: bool,
+    /// Cap on concurrent per-perspective divergent completions
+    /// (`DIVERGENT_MAX_CONCURRENCY`, default `3`, clamped to 1–5). Only
+    /// consulted when `divergent_per_perspective` is set.
+    pub divergent_max_concurrency: usize,
+    /// Raw IO capture (`LINEAR_STORE_RAW_IO=true`): persist the exact request
+    /// messages and unparsed completion behind each thought saved by
+    /// `reasoning_linear` (redacted and size-capped, keyed by thought id) so
+    /// `reasoning_inspect_thought` can return them for deep debugging. Only
+    /// linear captures raw IO — the other modes save thoughts without it.
+    /// Off by default — raw exchanges are large and usually only wanted
+    /// while diagnosing a prompt.
+    pub linear_store_raw_io: bool,
+    /// Bound on admitted-but-unfinished tool calls (`MAX_PENDING_REQUESTS`,
+    /// default `32`, minimum `1`). A call arriving when this many are already
+    /// pending is rejected up front with a busy error instead of being
+    /// accepted as unbounded work, so a request flood cannot exhaust memory
+    /// spawning tasks. Capacity frees as calls complete.
+    pub max_pending_requests: usize,
+    /// History-biased auto selection (`AUTO_HISTORY_BIAS=true`):
+    /// `reasoning_auto` blends the LLM's mode choice with per-mode average
+    /// confidence from stored thoughts similar to the current content, and
+    /// re-routes when a different mode has consistently scored higher. Off
+    /// by default — the prompt-based choice alone decides.
+    pub auto_history_bias: bool,
+    /// Degenerate-output retry (`RETRY_DEGENERATE=true`): a detect call whose
+    /// first pass parses cleanly but reports zero findings is rerun exactly
+    /// once with a sharper instruction; the original empty result is kept
+    /// when the rerun is also empty or fails. Off by default — an empty
+    /// finding list is accepted as-is.
+    pub retry_degenerate: bool,
+    /// Process-wide default prompt version (`PROMPT_VERSION`): the named
+    /// [`crate::prompts::PromptRegistry`] version modes resolve when a call
+    /// does not request one. `None` (the default) selects the built-in
+    /// prompts; an unregistered name falls back to them per mode.
+    pub prompt_version: Option<String>,
 }
 
 impl Config {
@@ -153,6 +334,7 @@ impl Config {
     ///
     /// Optional environment variables (with defaults):
     /// - `DATABASE_PATH`: Path to `SQLite` database (default: `./data/reasoning.db`)
+    /// - `DATABASE_MAX_CONNECTIONS`: `SQLite` connection-pool size (default: `5`)
     /// - `LOG_LEVEL`: Logging level (default: `info`)
     /// - `REQUEST_TIMEOUT_MS`: Request timeout for fast/standard modes (default: `30000`)
     /// - `REQUEST_TIMEOUT_DEEP_MS`: Request timeout for deep modes (default: `60000`)
@@ -160,6 +342,43 @@ impl Config {
     /// - `FACTORY_TIMEOUT_MS`: Factory timeout for metadata builder (default: `30000`)
     /// - `MAX_RETRIES`: Maximum retry attempts (default: `3`)
     /// - `ANTHROPIC_MODEL`: Model to use (default: `claude-sonnet-4-20250514`)
+    /// - `STICKY_SESSION`: Reuse the last session when `session_id` is omitted
+    /// - `DETECT_FILTER_UNVERIFIED`: Drop detections whose citation is not in the content
+    ///   (default: `false`)
+    /// - `STRICT_PARSING`: Error on unexpected top-level response keys (default: `false`)
+    /// - `SELF_CORRECT_PARSE`: On a parse/validation error, send one follow-up
+    ///   completion explaining the error and re-parse (default: `false`)
+    /// - `CONFIDENCE_FLOOR`: Escalate a low-confidence linear pass once with deep
+    ///   thinking (default: unset, no escalation)
+    /// - `PROMPT_CACHING`: Mark static mode prompts cacheable (default: `false`)
+    /// - `RESPONSE_LANGUAGE`: Default output language for reasoning results;
+    ///   `auto` / `auto:<Fallback>` match the detected content language
+    ///   (default: unset, meaning English)
+    /// - `GRAPH_MAX_NODES`: Per-session graph node ceiling (default: `200`,
+    ///   clamped to 1–10000)
+    /// - `GRAPH_MAX_RETURNED_CHILDREN`: Cap on children returned by a single
+    ///   graph generate/advance response (default: unset, uncapped)
+    /// - `QUALITY_RUBRIC_LLM_WEIGHT`: Weight of the LLM self-report in the
+    ///   graph finalize quality rubric (default: `0.5`, 0.0–1.0)
+    /// - `ENABLED_TOOLS`: Comma-separated allowlist of tool names to expose
+    ///   (default: unset, meaning every tool)
+    /// - `DISABLED_TOOLS`: Comma-separated denylist of tool names to hide
+    ///   (default: empty)
+    /// - `DIVERGENT_PER_PERSPECTIVE`: Run one deep completion per divergent
+    ///   perspective concurrently and synthesize (default: `false`)
+    /// - `DIVERGENT_MAX_CONCURRENCY`: Cap on concurrent per-perspective
+    ///   completions (default: `3`, clamped to 1–5)
+    /// - `LINEAR_STORE_RAW_IO`: Persist the raw request/response behind each
+    ///   `reasoning_linear` thought for `reasoning_inspect_thought`
+    ///   (default: `false`; linear is the only mode that captures raw IO)
+    /// - `MAX_PENDING_REQUESTS`: Bound on admitted-but-unfinished tool calls;
+    ///   excess calls are rejected busy (default: `32`, minimum `1`)
+    /// - `AUTO_HISTORY_BIAS`: Blend historical per-mode confidence into auto
+    ///   mode selection (default: `false`)
+    /// - `RETRY_DEGENERATE`: Rerun a detect call once with a sharper prompt
+    ///   when a clean first pass reports zero findings (default: `false`)
+    /// - `PROMPT_VERSION`: Default named prompt version to resolve through the
+    ///   prompt registry (default: unset, the built-in prompts)
     ///
     /// # Errors
     ///
@@ -173,14 +392,26 @@ impl Config {
         // Load .env file if present (ignore errors)
         let _ = dotenvy::dotenv();
 
-        let api_key =
-            std::env::var("ANTHROPIC_API_KEY").map_err(|_| ConfigError::MissingRequired {
-                var: "ANTHROPIC_API_KEY".into(),
-            })?;
+        // OFFLINE_MODE serves all completions from the deterministic stub, so
+        // a real key is not needed — a placeholder keeps the rest of startup
+        // unchanged. With offline mode off, the key stays required.
+        let offline_mode = std::env::var("OFFLINE_MODE").is_ok_and(|v| v.to_lowercase() == "true");
+        let api_key = match std::env::var("ANTHROPIC_API_KEY") {
+            Ok(key) => key,
+            Err(_) if offline_mode => "offline-placeholder-key".to_string(),
+            Err(_) => {
+                return Err(ConfigError::MissingRequired {
+                    var: "ANTHROPIC_API_KEY".into(),
+                })
+            }
+        };
 
         let database_path =
             std::env::var("DATABASE_PATH").unwrap_or_else(|_| DEFAULT_DATABASE_PATH.into());
 
+        let database_max_connections =
+            parse_env_u32("DATABASE_MAX_CONNECTIONS", DEFAULT_DATABASE_MAX_CONNECTIONS)?;
+
         let log_level = std::env::var("LOG_LEVEL").unwrap_or_else(|_| DEFAULT_LOG_LEVEL.into());
 
         let request_timeout_ms = parse_env_u64("REQUEST_TIMEOUT_MS", DEFAULT_REQUEST_TIMEOUT_MS)?;
@@ -213,10 +444,74 @@ impl Config {
             parse_env_f64("MCTS_QUALITY_THRESHOLD", DEFAULT_MCTS_QUALITY_THRESHOLD)?;
         let graph_prune_threshold =
             parse_env_f64("GRAPH_PRUNE_THRESHOLD", DEFAULT_GRAPH_PRUNE_THRESHOLD)?;
+        let graph_max_nodes = parse_env_u32("GRAPH_MAX_NODES", DEFAULT_GRAPH_MAX_NODES)?
+            .clamp(1, MAX_GRAPH_MAX_NODES) as usize;
+        let graph_max_returned_children = match std::env::var("GRAPH_MAX_RETURNED_CHILDREN") {
+            Ok(val) => match val.trim().parse::<usize>() {
+                Ok(max) if max > 0 => Some(max),
+                _ => {
+                    return Err(ConfigError::InvalidValue {
+                        var: "GRAPH_MAX_RETURNED_CHILDREN".into(),
+                        reason: "must be a positive integer".into(),
+                    })
+                }
+            },
+            Err(_) => None,
+        };
+        let rubric_llm_weight =
+            parse_env_f64("QUALITY_RUBRIC_LLM_WEIGHT", DEFAULT_RUBRIC_LLM_WEIGHT)?;
+        let evidence_interval_width =
+            parse_env_f64("EVIDENCE_INTERVAL_WIDTH", DEFAULT_EVIDENCE_INTERVAL_WIDTH)?;
+
+        let sticky_session =
+            std::env::var("STICKY_SESSION").is_ok_and(|v| v.to_lowercase() == "true");
+        let detect_filter_unverified =
+            std::env::var("DETECT_FILTER_UNVERIFIED").is_ok_and(|v| v.to_lowercase() == "true");
+        let strict_parsing =
+            std::env::var("STRICT_PARSING").is_ok_and(|v| v.to_lowercase() == "true");
+        let require_session_id =
+            std::env::var("REQUIRE_SESSION_ID").is_ok_and(|v| v.to_lowercase() == "true");
+        let self_correct_parse =
+            std::env::var("SELF_CORRECT_PARSE").is_ok_and(|v| v.to_lowercase() == "true");
+        let structured_output =
+            std::env::var("STRUCTURED_OUTPUT").is_ok_and(|v| v.to_lowercase() == "true");
+        let prompt_caching =
+            std::env::var("PROMPT_CACHING").is_ok_and(|v| v.to_lowercase() == "true");
+        let confidence_floor = match std::env::var("CONFIDENCE_FLOOR") {
+            Ok(val) => Some(val.parse().map_err(|_| ConfigError::InvalidValue {
+                var: "CONFIDENCE_FLOOR".into(),
+                reason: "must be a number".into(),
+            })?),
+            Err(_) => None,
+        };
+        let response_language = std::env::var("RESPONSE_LANGUAGE")
+            .ok()
+            .filter(|v| !v.trim().is_empty());
+        let enabled_tools = parse_env_tool_list("ENABLED_TOOLS");
+        let disabled_tools = parse_env_tool_list("DISABLED_TOOLS").unwrap_or_default();
+        let divergent_per_perspective =
+            std::env::var("DIVERGENT_PER_PERSPECTIVE").is_ok_and(|v| v.to_lowercase() == "true");
+        let divergent_max_concurrency = parse_env_u32(
+            "DIVERGENT_MAX_CONCURRENCY",
+            DEFAULT_DIVERGENT_MAX_CONCURRENCY,
+        )?
+        .clamp(1, MAX_DIVERGENT_CONCURRENCY) as usize;
+        let linear_store_raw_io =
+            std::env::var("LINEAR_STORE_RAW_IO").is_ok_and(|v| v.to_lowercase() == "true");
+        let max_pending_requests =
+            parse_env_u32("MAX_PENDING_REQUESTS", DEFAULT_MAX_PENDING_REQUESTS)?.max(1) as usize;
+        let auto_history_bias =
+            std::env::var("AUTO_HISTORY_BIAS").is_ok_and(|v| v.to_lowercase() == "true");
+        let retry_degenerate =
+            std::env::var("RETRY_DEGENERATE").is_ok_and(|v| v.to_lowercase() == "true");
+        let prompt_version = std::env::var("PROMPT_VERSION")
+            .ok()
+            .filter(|v| !v.trim().is_empty());
 
         let config = Self {
             api_key: SecretString::new(api_key),
             database_path,
+            database_max_connections,
             log_level,
             request_timeout_ms,
             request_timeout_deep_ms,
@@ -230,6 +525,28 @@ impl Config {
             reflection_quality_threshold,
             mcts_quality_threshold,
             graph_prune_threshold,
+            graph_max_nodes,
+            graph_max_returned_children,
+            rubric_llm_weight,
+            evidence_interval_width,
+            sticky_session,
+            detect_filter_unverified,
+            strict_parsing,
+            require_session_id,
+            self_correct_parse,
+            structured_output,
+            confidence_floor,
+            prompt_caching,
+            response_language,
+            enabled_tools,
+            disabled_tools,
+            divergent_per_perspective,
+            divergent_max_concurrency,
+            linear_store_raw_io,
+            max_pending_requests,
+            auto_history_bias,
+            retry_degenerate,
+            prompt_version,
         };
 
         validate_config(&config)?;
@@ -250,6 +567,7 @@ impl Config {
     /// # let config = Config {
     /// #     api_key: mcp_reasoning::config::SecretString::new("test"),
     /// #     database_path: "./test.db".into(),
+    /// #     database_max_connections: 5,
     /// #     log_level: "info".into(),
     /// #     request_timeout_ms: 30_000,
     /// #     request_timeout_deep_ms: 60_000,
@@ -263,6 +581,28 @@ impl Config {
     /// #     reflection_quality_threshold: 0.8,
     /// #     mcts_quality_threshold: 0.5,
     /// #     graph_prune_threshold: 0.3,
+    /// #     graph_max_nodes: 200,
+    /// #     graph_max_returned_children: None,
+    /// #     rubric_llm_weight: 0.5,
+    /// #     evidence_interval_width: 0.1,
+    /// #     sticky_session: false,
+    /// #     detect_filter_unverified: false,
+    /// #     strict_parsing: false,
+    /// #     require_session_id: false,
+    /// #     self_correct_parse: false,
+    /// #     structured_output: false,
+    /// #     confidence_floor: None,
+    /// #     prompt_caching: false,
+    /// #     response_language: None,
+    /// #     enabled_tools: None,
+    /// #     disabled_tools: vec![],
+    /// #     divergent_per_perspective: false,
+    /// #     divergent_max_concurrency: 3,
+    /// #     linear_store_raw_io: false,
+    /// #     max_pending_requests: 32,
+    /// #     auto_history_bias: false,
+    /// #     retry_degenerate: false,
+    /// #     prompt_version: None,
     /// # };
     ///
     /// assert_eq!(config.timeout_for_thinking_budget(None), 30_000);
@@ -403,6 +743,25 @@ fn parse_env_u32(name: &str, default: u32) -> Result<u32, ConfigError> {
     })
 }
 
+/// Parse an environment variable as a comma-separated list of tool names,
+/// trimming whitespace and dropping empty entries. Returns `None` when the
+/// variable is unset or contains no names, so an empty `ENABLED_TOOLS` does
+/// not hide every tool.
+fn parse_env_tool_list(name: &str) -> Option<Vec<String>> {
+    let raw = std::env::var(name).ok()?;
+    let names: Vec<String> = raw
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect();
+    if names.is_empty() {
+        None
+    } else {
+        Some(names)
+    }
+}
+
 /// Parse an environment variable as f64, using a default if not set.
 fn parse_env_f64(name: &str, default: f64) -> Result<f64, ConfigError> {
     std::env::var(name).map_or(Ok(default), |val| {
@@ -432,11 +791,33 @@ mod tests {
         // Clear all relevant env vars
         env::remove_var("ANTHROPIC_API_KEY");
         env::remove_var("DATABASE_PATH");
+        env::remove_var("DATABASE_MAX_CONNECTIONS");
         env::remove_var("LOG_LEVEL");
         env::remove_var("REQUEST_TIMEOUT_MS");
         env::remove_var("FACTORY_TIMEOUT_MS");
         env::remove_var("MAX_RETRIES");
         env::remove_var("ANTHROPIC_MODEL");
+        env::remove_var("STICKY_SESSION");
+        env::remove_var("DETECT_FILTER_UNVERIFIED");
+        env::remove_var("STRICT_PARSING");
+        env::remove_var("SELF_CORRECT_PARSE");
+        env::remove_var("STRUCTURED_OUTPUT");
+        env::remove_var("CONFIDENCE_FLOOR");
+        env::remove_var("PROMPT_CACHING");
+        env::remove_var("RESPONSE_LANGUAGE");
+        env::remove_var("ENABLED_TOOLS");
+        env::remove_var("DISABLED_TOOLS");
+        env::remove_var("DIVERGENT_PER_PERSPECTIVE");
+        env::remove_var("DIVERGENT_MAX_CONCURRENCY");
+        env::remove_var("GRAPH_MAX_NODES");
+        env::remove_var("GRAPH_MAX_RETURNED_CHILDREN");
+        env::remove_var("QUALITY_RUBRIC_LLM_WEIGHT");
+        env::remove_var("LINEAR_STORE_RAW_IO");
+        env::remove_var("MAX_PENDING_REQUESTS");
+        env::remove_var("AUTO_HISTORY_BIAS");
+        env::remove_var("RETRY_DEGENERATE");
+        env::remove_var("PROMPT_VERSION");
+        env::remove_var("OFFLINE_MODE");
     }
 
     #[test]
@@ -477,6 +858,423 @@ mod tests {
         assert_eq!(config.factory_timeout_ms, DEFAULT_FACTORY_TIMEOUT_MS);
         assert_eq!(config.max_retries, DEFAULT_MAX_RETRIES);
         assert_eq!(config.model, DEFAULT_MODEL);
+        assert!(!config.sticky_session);
+        assert!(!config.detect_filter_unverified);
+        assert!(!config.strict_parsing);
+        assert!(!config.self_correct_parse);
+        assert!(!config.structured_output);
+        assert!(config.confidence_floor.is_none());
+        assert!(!config.prompt_caching);
+        assert!(config.enabled_tools.is_none());
+        assert!(config.disabled_tools.is_empty());
+        assert!(!config.divergent_per_perspective);
+        assert!(!config.linear_store_raw_io);
+        assert_eq!(
+            config.divergent_max_concurrency,
+            DEFAULT_DIVERGENT_MAX_CONCURRENCY as usize
+        );
+        assert_eq!(
+            config.max_pending_requests,
+            DEFAULT_MAX_PENDING_REQUESTS as usize
+        );
+        assert!(!config.auto_history_bias);
+        assert!(!config.retry_degenerate);
+        assert!(config.prompt_version.is_none());
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_divergent_per_perspective_from_env() {
+        setup_test_env();
+
+        env::set_var("ANTHROPIC_API_KEY", "sk-ant-test-key");
+        env::set_var("DIVERGENT_PER_PERSPECTIVE", "true");
+
+        let config = Config::from_env().expect("should load config");
+        assert!(config.divergent_per_perspective);
+
+        // Concurrency clamps to [1, 5] rather than erroring.
+        env::set_var("DIVERGENT_MAX_CONCURRENCY", "9");
+        let config = Config::from_env().expect("should load config");
+        assert_eq!(config.divergent_max_concurrency, 5);
+
+        env::set_var("DIVERGENT_MAX_CONCURRENCY", "0");
+        let config = Config::from_env().expect("should load config");
+        assert_eq!(config.divergent_max_concurrency, 1);
+
+        env::remove_var("DIVERGENT_PER_PERSPECTIVE");
+        env::remove_var("DIVERGENT_MAX_CONCURRENCY");
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_graph_max_nodes_from_env() {
+        setup_test_env();
+
+        env::set_var("ANTHROPIC_API_KEY", "sk-ant-test-key");
+        let config = Config::from_env().expect("should load config");
+        assert_eq!(config.graph_max_nodes, DEFAULT_GRAPH_MAX_NODES as usize);
+
+        env::set_var("GRAPH_MAX_NODES", "500");
+        let config = Config::from_env().expect("should load config");
+        assert_eq!(config.graph_max_nodes, 500);
+
+        // Out-of-range values clamp rather than fail: the cap is a guard
+        // rail, not a correctness knob.
+        env::set_var("GRAPH_MAX_NODES", "0");
+        let config = Config::from_env().expect("should load config");
+        assert_eq!(config.graph_max_nodes, 1);
+
+        env::set_var("GRAPH_MAX_NODES", "999999");
+        let config = Config::from_env().expect("should load config");
+        assert_eq!(config.graph_max_nodes, 10_000);
+
+        env::remove_var("GRAPH_MAX_NODES");
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_graph_max_returned_children_from_env() {
+        setup_test_env();
+
+        env::set_var("ANTHROPIC_API_KEY", "sk-ant-test-key");
+        let config = Config::from_env().expect("should load config");
+        assert_eq!(config.graph_max_returned_children, None);
+
+        env::set_var("GRAPH_MAX_RETURNED_CHILDREN", "4");
+        let config = Config::from_env().expect("should load config");
+        assert_eq!(config.graph_max_returned_children, Some(4));
+
+        // Unlike GRAPH_MAX_NODES, a bad value fails at load: a cap of zero
+        // would silently swallow every generated child.
+        env::set_var("GRAPH_MAX_RETURNED_CHILDREN", "0");
+        assert!(Config::from_env().is_err());
+
+        env::set_var("GRAPH_MAX_RETURNED_CHILDREN", "abc");
+        assert!(Config::from_env().is_err());
+
+        env::remove_var("GRAPH_MAX_RETURNED_CHILDREN");
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_rubric_llm_weight_from_env() {
+        setup_test_env();
+
+        env::set_var("ANTHROPIC_API_KEY", "sk-ant-test-key");
+        let config = Config::from_env().expect("should load config");
+        assert_eq!(config.rubric_llm_weight, DEFAULT_RUBRIC_LLM_WEIGHT);
+
+        env::set_var("QUALITY_RUBRIC_LLM_WEIGHT", "0.8");
+        let config = Config::from_env().expect("should load config");
+        assert_eq!(config.rubric_llm_weight, 0.8);
+
+        // Out-of-range or unparsable values fail at load rather than being
+        // silently clamped to a weight the operator did not ask for.
+        env::set_var("QUALITY_RUBRIC_LLM_WEIGHT", "3.0");
+        assert!(Config::from_env().is_err());
+
+        env::set_var("QUALITY_RUBRIC_LLM_WEIGHT", "heavy");
+        assert!(Config::from_env().is_err());
+
+        env::remove_var("QUALITY_RUBRIC_LLM_WEIGHT");
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_linear_store_raw_io_from_env() {
+        setup_test_env();
+
+        env::set_var("ANTHROPIC_API_KEY", "sk-ant-test-key");
+
+        let config = Config::from_env().expect("should load config");
+        assert!(!config.linear_store_raw_io);
+
+        env::set_var("LINEAR_STORE_RAW_IO", "true");
+        let config = Config::from_env().expect("should load config");
+        assert!(config.linear_store_raw_io);
+
+        env::remove_var("LINEAR_STORE_RAW_IO");
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_max_pending_requests_from_env() {
+        setup_test_env();
+
+        env::set_var("ANTHROPIC_API_KEY", "sk-ant-test-key");
+        env::set_var("MAX_PENDING_REQUESTS", "8");
+
+        let config = Config::from_env().expect("should load config");
+        assert_eq!(config.max_pending_requests, 8);
+
+        // Zero clamps to 1 rather than disabling admission entirely.
+        env::set_var("MAX_PENDING_REQUESTS", "0");
+        let config = Config::from_env().expect("should load config");
+        assert_eq!(config.max_pending_requests, 1);
+
+        env::remove_var("MAX_PENDING_REQUESTS");
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_auto_history_bias_from_env() {
+        setup_test_env();
+
+        env::set_var("ANTHROPIC_API_KEY", "sk-ant-test-key");
+
+        let config = Config::from_env().expect("should load config");
+        assert!(!config.auto_history_bias);
+
+        env::set_var("AUTO_HISTORY_BIAS", "TRUE");
+        let config = Config::from_env().expect("should load config");
+        assert!(config.auto_history_bias);
+
+        env::remove_var("AUTO_HISTORY_BIAS");
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_retry_degenerate_from_env() {
+        setup_test_env();
+
+        env::set_var("ANTHROPIC_API_KEY", "sk-ant-test-key");
+
+        let config = Config::from_env().expect("should load config");
+        assert!(!config.retry_degenerate);
+
+        env::set_var("RETRY_DEGENERATE", "true");
+        let config = Config::from_env().expect("should load config");
+        assert!(config.retry_degenerate);
+
+        env::remove_var("RETRY_DEGENERATE");
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_prompt_version_from_env() {
+        setup_test_env();
+
+        env::set_var("ANTHROPIC_API_KEY", "sk-ant-test-key");
+
+        let config = Config::from_env().expect("should load config");
+        assert!(config.prompt_version.is_none());
+
+        // Blank selects nothing; a real name is kept verbatim.
+        env::set_var("PROMPT_VERSION", "   ");
+        let config = Config::from_env().expect("should load config");
+        assert!(config.prompt_version.is_none());
+
+        env::set_var("PROMPT_VERSION", "terse-v2");
+        let config = Config::from_env().expect("should load config");
+        assert_eq!(config.prompt_version.as_deref(), Some("terse-v2"));
+
+        env::remove_var("PROMPT_VERSION");
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_confidence_floor_from_env() {
+        setup_test_env();
+
+        env::set_var("ANTHROPIC_API_KEY", "sk-ant-test-key");
+        env::set_var("CONFIDENCE_FLOOR", "0.6");
+
+        let config = Config::from_env().expect("should load config");
+        assert_eq!(config.confidence_floor, Some(0.6));
+
+        // Non-numeric values fail loudly rather than silently disabling the gate.
+        env::set_var("CONFIDENCE_FLOOR", "high");
+        let result = Config::from_env();
+        assert!(matches!(
+            result,
+            Err(ConfigError::InvalidValue { var, .. }) if var == "CONFIDENCE_FLOOR"
+        ));
+        env::remove_var("CONFIDENCE_FLOOR");
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_prompt_caching_from_env() {
+        setup_test_env();
+
+        env::set_var("ANTHROPIC_API_KEY", "sk-ant-test-key");
+        env::set_var("PROMPT_CACHING", "true");
+
+        let config = Config::from_env().expect("should load config");
+        assert!(config.prompt_caching);
+
+        env::remove_var("PROMPT_CACHING");
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_tool_lists_from_env() {
+        setup_test_env();
+
+        env::set_var("ANTHROPIC_API_KEY", "sk-ant-test-key");
+        env::set_var("ENABLED_TOOLS", "reasoning_linear, reasoning_tree");
+        env::set_var("DISABLED_TOOLS", "reasoning_mcts");
+
+        let config = Config::from_env().expect("should load config");
+        assert_eq!(
+            config.enabled_tools,
+            Some(vec![
+                "reasoning_linear".to_string(),
+                "reasoning_tree".to_string()
+            ])
+        );
+        assert_eq!(config.disabled_tools, vec!["reasoning_mcts".to_string()]);
+
+        // A blank allowlist means "no filter", not "hide everything".
+        env::set_var("ENABLED_TOOLS", " , ");
+        let config = Config::from_env().expect("should load config");
+        assert!(config.enabled_tools.is_none());
+
+        env::remove_var("ENABLED_TOOLS");
+        env::remove_var("DISABLED_TOOLS");
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_database_max_connections_from_env() {
+        setup_test_env();
+
+        env::set_var("ANTHROPIC_API_KEY", "sk-ant-test-key");
+        let config = Config::from_env().expect("should load config");
+        assert_eq!(
+            config.database_max_connections,
+            DEFAULT_DATABASE_MAX_CONNECTIONS
+        );
+
+        env::set_var("DATABASE_MAX_CONNECTIONS", "16");
+        let config = Config::from_env().expect("should load config");
+        assert_eq!(config.database_max_connections, 16);
+
+        // Out of range fails validation rather than silently clamping.
+        env::set_var("DATABASE_MAX_CONNECTIONS", "0");
+        let result = Config::from_env();
+        assert!(matches!(
+            result,
+            Err(ConfigError::InvalidValue { var, .. }) if var == "DATABASE_MAX_CONNECTIONS"
+        ));
+        env::remove_var("DATABASE_MAX_CONNECTIONS");
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_sticky_session_from_env() {
+        setup_test_env();
+
+        env::set_var("ANTHROPIC_API_KEY", "sk-ant-test-key");
+        env::set_var("STICKY_SESSION", "true");
+
+        let config = Config::from_env().expect("should load config");
+        assert!(config.sticky_session);
+
+        env::set_var("STICKY_SESSION", "no");
+        let config = Config::from_env().expect("should load config");
+        assert!(!config.sticky_session);
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_detect_filter_unverified_from_env() {
+        setup_test_env();
+
+        env::set_var("ANTHROPIC_API_KEY", "sk-ant-test-key");
+        env::set_var("DETECT_FILTER_UNVERIFIED", "true");
+
+        let config = Config::from_env().expect("should load config");
+        assert!(config.detect_filter_unverified);
+
+        env::set_var("DETECT_FILTER_UNVERIFIED", "0");
+        let config = Config::from_env().expect("should load config");
+        assert!(!config.detect_filter_unverified);
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_strict_parsing_from_env() {
+        setup_test_env();
+
+        env::set_var("ANTHROPIC_API_KEY", "sk-ant-test-key");
+        env::set_var("STRICT_PARSING", "TRUE");
+
+        let config = Config::from_env().expect("should load config");
+        assert!(config.strict_parsing);
+
+        env::set_var("STRICT_PARSING", "0");
+        let config = Config::from_env().expect("should load config");
+        assert!(!config.strict_parsing);
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_require_session_id_from_env() {
+        setup_test_env();
+
+        env::set_var("ANTHROPIC_API_KEY", "sk-ant-test-key");
+        env::set_var("REQUIRE_SESSION_ID", "true");
+
+        let config = Config::from_env().expect("should load config");
+        assert!(config.require_session_id);
+
+        env::set_var("REQUIRE_SESSION_ID", "0");
+        let config = Config::from_env().expect("should load config");
+        assert!(!config.require_session_id);
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_self_correct_parse_from_env() {
+        setup_test_env();
+
+        env::set_var("ANTHROPIC_API_KEY", "sk-ant-test-key");
+        env::set_var("SELF_CORRECT_PARSE", "true");
+
+        let config = Config::from_env().expect("should load config");
+        assert!(config.self_correct_parse);
+
+        env::set_var("SELF_CORRECT_PARSE", "no");
+        let config = Config::from_env().expect("should load config");
+        assert!(!config.self_correct_parse);
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_structured_output_from_env() {
+        setup_test_env();
+
+        env::set_var("ANTHROPIC_API_KEY", "sk-ant-test-key");
+        env::set_var("STRUCTURED_OUTPUT", "true");
+
+        let config = Config::from_env().expect("should load config");
+        assert!(config.structured_output);
+
+        env::set_var("STRUCTURED_OUTPUT", "off");
+        let config = Config::from_env().expect("should load config");
+        assert!(!config.structured_output);
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_response_language_from_env() {
+        setup_test_env();
+
+        env::set_var("ANTHROPIC_API_KEY", "sk-ant-test-key");
+
+        let config = Config::from_env().expect("should load config");
+        assert_eq!(config.response_language, None);
+
+        env::set_var("RESPONSE_LANGUAGE", "Spanish");
+        let config = Config::from_env().expect("should load config");
+        assert_eq!(config.response_language, Some("Spanish".to_string()));
+
+        // A blank value is treated as unset.
+        env::set_var("RESPONSE_LANGUAGE", "   ");
+        let config = Config::from_env().expect("should load config");
+        assert_eq!(config.response_language, None);
     }
 
     #[test]
@@ -493,6 +1291,28 @@ mod tests {
         ));
     }
 
+    #[test]
+    #[serial]
+    fn test_config_offline_mode_makes_api_key_optional() {
+        setup_test_env();
+
+        env::set_var("OFFLINE_MODE", "true");
+        let config = Config::from_env().expect("should load config without a key");
+        assert_eq!(config.api_key.expose(), "offline-placeholder-key");
+
+        // A real key still wins when both are present
+        env::set_var("ANTHROPIC_API_KEY", "sk-ant-real-key");
+        let config = Config::from_env().expect("should load config");
+        assert_eq!(config.api_key.expose(), "sk-ant-real-key");
+
+        // Any value other than "true" leaves the key required
+        env::remove_var("ANTHROPIC_API_KEY");
+        env::set_var("OFFLINE_MODE", "1");
+        assert!(Config::from_env().is_err());
+
+        env::remove_var("OFFLINE_MODE");
+    }
+
     #[test]
     #[serial]
     fn test_config_invalid_timeout_format() {
@@ -626,8 +1446,10 @@ mod tests {
     #[test]
     fn test_config_clone() {
         let config = Config {
+            response_language: None,
             api_key: SecretString::new("test-key"),
             database_path: "/path/to/db".to_string(),
+            database_max_connections: 5,
             log_level: "debug".to_string(),
             request_timeout_ms: 5000,
             request_timeout_deep_ms: 10000,
@@ -641,6 +1463,27 @@ mod tests {
             reflection_quality_threshold: 0.8,
             mcts_quality_threshold: 0.5,
             graph_prune_threshold: 0.3,
+            graph_max_nodes: 200,
+            graph_max_returned_children: None,
+            rubric_llm_weight: 0.5,
+            evidence_interval_width: 0.1,
+            sticky_session: false,
+            detect_filter_unverified: false,
+            strict_parsing: false,
+            require_session_id: false,
+            self_correct_parse: false,
+            structured_output: false,
+            confidence_floor: None,
+            prompt_caching: false,
+            enabled_tools: None,
+            disabled_tools: vec![],
+            divergent_per_perspective: false,
+            divergent_max_concurrency: 3,
+            linear_store_raw_io: false,
+            max_pending_requests: 32,
+            auto_history_bias: false,
+            retry_degenerate: false,
+            prompt_version: None,
         };
 
         let cloned = config.clone();
@@ -649,8 +1492,10 @@ mod tests {
 
     fn overridable_config() -> Config {
         Config {
+            response_language: None,
             api_key: SecretString::new("test-key"),
             database_path: "/db".to_string(),
+            database_max_connections: 5,
             log_level: "info".to_string(),
             request_timeout_ms: 30_000,
             request_timeout_deep_ms: 60_000,
@@ -664,6 +1509,27 @@ mod tests {
             reflection_quality_threshold: 0.8,
             mcts_quality_threshold: 0.5,
             graph_prune_threshold: 0.3,
+            graph_max_nodes: 200,
+            graph_max_returned_children: None,
+            rubric_llm_weight: 0.5,
+            evidence_interval_width: 0.1,
+            sticky_session: false,
+            detect_filter_unverified: false,
+            strict_parsing: false,
+            require_session_id: false,
+            self_correct_parse: false,
+            structured_output: false,
+            confidence_floor: None,
+            prompt_caching: false,
+            enabled_tools: None,
+            disabled_tools: vec![],
+            divergent_per_perspective: false,
+            divergent_max_concurrency: 3,
+            linear_store_raw_io: false,
+            max_pending_requests: 32,
+            auto_history_bias: false,
+            retry_degenerate: false,
+            prompt_version: None,
         }
     }
 
@@ -791,8 +1657,10 @@ mod tests {
     #[test]
     fn test_config_debug_redacts_api_key() {
         let config = Config {
+            response_language: None,
             api_key: SecretString::new("super-secret-key"),
             database_path: "/path/to/db".to_string(),
+            database_max_connections: 5,
             log_level: "debug".to_string(),
             request_timeout_ms: 5000,
             request_timeout_deep_ms: 10000,
@@ -806,6 +1674,27 @@ mod tests {
             reflection_quality_threshold: 0.8,
             mcts_quality_threshold: 0.5,
             graph_prune_threshold: 0.3,
+            graph_max_nodes: 200,
+            graph_max_returned_children: None,
+            rubric_llm_weight: 0.5,
+            evidence_interval_width: 0.1,
+            sticky_session: false,
+            detect_filter_unverified: false,
+            strict_parsing: false,
+            require_session_id: false,
+            self_correct_parse: false,
+            structured_output: false,
+            confidence_floor: None,
+            prompt_caching: false,
+            enabled_tools: None,
+            disabled_tools: vec![],
+            divergent_per_perspective: false,
+            divergent_max_concurrency: 3,
+            linear_store_raw_io: false,
+            max_pending_requests: 32,
+            auto_history_bias: false,
+            retry_degenerate: false,
+            prompt_version: None,
         };
 
         let debug = format!("{config:?}");
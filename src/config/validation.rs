@@ -15,6 +15,9 @@ pub const MAX_TIMEOUT_MS: u64 = 300_000;
 /// Maximum allowed retry count.
 pub const MAX_RETRIES: u32 = 10;
 
+/// Maximum allowed `SQLite` connection-pool size.
+pub const MAX_DATABASE_CONNECTIONS: u32 = 64;
+
 /// Validate configuration values.
 ///
 /// # Errors
@@ -23,6 +26,7 @@ pub const MAX_RETRIES: u32 = 10;
 /// - `ANTHROPIC_API_KEY` must not be empty
 /// - `REQUEST_TIMEOUT_MS` must be between 1000 and 300000
 /// - `MAX_RETRIES` must be between 0 and 10
+/// - `DATABASE_MAX_CONNECTIONS` must be between 1 and 64
 #[must_use = "validation result should be checked"]
 pub fn validate_config(config: &Config) -> Result<(), ConfigError> {
     // API key must not be empty
@@ -49,6 +53,17 @@ pub fn validate_config(config: &Config) -> Result<(), ConfigError> {
         });
     }
 
+    // Pool size must be at least one connection, bounded above so a typo
+    // can't exhaust file descriptors.
+    if config.database_max_connections < 1
+        || config.database_max_connections > MAX_DATABASE_CONNECTIONS
+    {
+        return Err(ConfigError::InvalidValue {
+            var: "DATABASE_MAX_CONNECTIONS".into(),
+            reason: format!("must be between 1 and {MAX_DATABASE_CONNECTIONS}"),
+        });
+    }
+
     // Decision thresholds must be probabilities in [0, 1].
     for (name, value) in [
         (
@@ -61,6 +76,8 @@ pub fn validate_config(config: &Config) -> Result<(), ConfigError> {
         ),
         ("MCTS_QUALITY_THRESHOLD", config.mcts_quality_threshold),
         ("GRAPH_PRUNE_THRESHOLD", config.graph_prune_threshold),
+        ("QUALITY_RUBRIC_LLM_WEIGHT", config.rubric_llm_weight),
+        ("EVIDENCE_INTERVAL_WIDTH", config.evidence_interval_width),
     ] {
         if !(0.0..=1.0).contains(&value) {
             return Err(ConfigError::InvalidValue {
@@ -70,6 +87,16 @@ pub fn validate_config(config: &Config) -> Result<(), ConfigError> {
         }
     }
 
+    // The optional confidence floor is a probability too.
+    if let Some(floor) = config.confidence_floor {
+        if !(0.0..=1.0).contains(&floor) {
+            return Err(ConfigError::InvalidValue {
+                var: "CONFIDENCE_FLOOR".into(),
+                reason: "must be between 0.0 and 1.0".into(),
+            });
+        }
+    }
+
     Ok(())
 }
 
@@ -88,8 +115,10 @@ mod tests {
 
     fn create_valid_config() -> Config {
         Config {
+            response_language: None,
             api_key: SecretString::new("sk-ant-test-key"),
             database_path: "./data/reasoning.db".to_string(),
+            database_max_connections: 5,
             log_level: "info".to_string(),
             request_timeout_ms: 30000,
             request_timeout_deep_ms: 60000,
@@ -103,6 +132,27 @@ mod tests {
             reflection_quality_threshold: 0.8,
             mcts_quality_threshold: 0.5,
             graph_prune_threshold: 0.3,
+            graph_max_nodes: 200,
+            graph_max_returned_children: None,
+            rubric_llm_weight: 0.5,
+            evidence_interval_width: 0.1,
+            sticky_session: false,
+            detect_filter_unverified: false,
+            strict_parsing: false,
+            require_session_id: false,
+            self_correct_parse: false,
+            structured_output: false,
+            confidence_floor: None,
+            prompt_caching: false,
+            enabled_tools: None,
+            disabled_tools: vec![],
+            divergent_per_perspective: false,
+            divergent_max_concurrency: 3,
+            linear_store_raw_io: false,
+            max_pending_requests: 32,
+            auto_history_bias: false,
+            retry_degenerate: false,
+            prompt_version: None,
         }
     }
 
@@ -115,8 +165,10 @@ mod tests {
     #[test]
     fn test_empty_api_key() {
         let config = Config {
+            response_language: None,
             api_key: SecretString::new(""),
             database_path: "./data/reasoning.db".to_string(),
+            database_max_connections: 5,
             log_level: "info".to_string(),
             request_timeout_ms: 30000,
             request_timeout_deep_ms: 60000,
@@ -130,6 +182,27 @@ mod tests {
             reflection_quality_threshold: 0.8,
             mcts_quality_threshold: 0.5,
             graph_prune_threshold: 0.3,
+            graph_max_nodes: 200,
+            graph_max_returned_children: None,
+            rubric_llm_weight: 0.5,
+            evidence_interval_width: 0.1,
+            sticky_session: false,
+            detect_filter_unverified: false,
+            strict_parsing: false,
+            require_session_id: false,
+            self_correct_parse: false,
+            structured_output: false,
+            confidence_floor: None,
+            prompt_caching: false,
+            enabled_tools: None,
+            disabled_tools: vec![],
+            divergent_per_perspective: false,
+            divergent_max_concurrency: 3,
+            linear_store_raw_io: false,
+            max_pending_requests: 32,
+            auto_history_bias: false,
+            retry_degenerate: false,
+            prompt_version: None,
         };
         let result = validate_config(&config);
         assert!(result.is_err());
@@ -171,6 +244,29 @@ mod tests {
         assert!(matches!(err, ConfigError::InvalidValue { var, .. } if var == "MAX_RETRIES"));
     }
 
+    #[test]
+    fn test_database_max_connections_bounds() {
+        let mut config = create_valid_config();
+        config.database_max_connections = 0; // Below minimum
+        let result = validate_config(&config);
+        assert!(matches!(
+            result,
+            Err(ConfigError::InvalidValue { var, .. }) if var == "DATABASE_MAX_CONNECTIONS"
+        ));
+
+        config.database_max_connections = MAX_DATABASE_CONNECTIONS + 1; // Above maximum
+        let result = validate_config(&config);
+        assert!(matches!(
+            result,
+            Err(ConfigError::InvalidValue { var, .. }) if var == "DATABASE_MAX_CONNECTIONS"
+        ));
+
+        config.database_max_connections = MAX_DATABASE_CONNECTIONS; // Exactly at maximum
+        assert!(validate_config(&config).is_ok());
+        config.database_max_connections = 1; // Exactly at minimum
+        assert!(validate_config(&config).is_ok());
+    }
+
     #[test]
     fn test_boundary_timeout_min() {
         let mut config = create_valid_config();
@@ -198,4 +294,20 @@ mod tests {
         config.max_retries = MAX_RETRIES; // Maximum allowed
         assert!(validate_config(&config).is_ok());
     }
+
+    #[test]
+    fn test_confidence_floor_bounds() {
+        let mut config = create_valid_config();
+        config.confidence_floor = Some(1.5); // Above maximum
+        let result = validate_config(&config);
+        assert!(matches!(
+            result,
+            Err(ConfigError::InvalidValue { var, .. }) if var == "CONFIDENCE_FLOOR"
+        ));
+
+        config.confidence_floor = Some(0.6); // In range
+        assert!(validate_config(&config).is_ok());
+        config.confidence_floor = None; // Unset is always valid
+        assert!(validate_config(&config).is_ok());
+    }
 }
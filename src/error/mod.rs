@@ -121,6 +121,21 @@ impl AnthropicError {
                 | Self::Network { .. }
         )
     }
+
+    /// Returns a stable label for this error, used as the error-kind
+    /// dimension on retry metrics.
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::AuthenticationFailed => "auth",
+            Self::RateLimited { .. } => "rate_limited",
+            Self::ModelOverloaded { .. } => "overloaded",
+            Self::Timeout { .. } => "timeout",
+            Self::InvalidRequest { .. } => "invalid_request",
+            Self::Network { .. } => "network",
+            Self::UnexpectedResponse { .. } => "unexpected_response",
+        }
+    }
 }
 
 /// Storage errors.
@@ -5,8 +5,8 @@
 //!
 //! # Features
 //!
-//! - 35 tools: 17 core reasoning tools (a consolidated surface vs 40 in the
-//!   predecessor), 7 self-improvement, 4 session-management, 7 agent/team
+//! - 49 tools: 24 core reasoning tools (a consolidated surface vs 40 in the
+//!   predecessor), 7 self-improvement, 11 session-management, 7 agent/team
 //! - Direct Anthropic API integration
 //! - Extended thinking support with configurable budgets
 //! - `SQLite` persistence for sessions and state
@@ -69,6 +69,7 @@ pub mod metrics;
 pub mod modes;
 pub mod presets;
 pub mod prompts;
+pub mod replay;
 pub mod self_improvement;
 pub mod server;
 pub mod skills;
@@ -127,6 +128,29 @@ pub mod doctest_helpers {
             ))
         }
 
+        async fn get_working_memory(
+            &self,
+            _session_id: &str,
+        ) -> Result<Option<String>, StorageError> {
+            Ok(None)
+        }
+
+        async fn set_working_memory(
+            &self,
+            _session_id: &str,
+            _memory: &str,
+        ) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        async fn merge_sessions(
+            &self,
+            _target_id: &str,
+            _source_id: &str,
+        ) -> Result<(), StorageError> {
+            Ok(())
+        }
+
         async fn save_thought(&self, _thought: &Thought) -> Result<(), StorageError> {
             Ok(())
         }
@@ -135,10 +159,31 @@ pub mod doctest_helpers {
             Ok(vec![])
         }
 
+        async fn mode_confidence_stats(
+            &self,
+            _fts_query: Option<String>,
+        ) -> Result<Vec<crate::traits::ModeConfidenceStat>, StorageError> {
+            Ok(vec![])
+        }
+
         async fn delete_thought(&self, _id: &str) -> Result<(), StorageError> {
             Ok(())
         }
 
+        async fn delete_last_thought(
+            &self,
+            _session_id: &str,
+        ) -> Result<Option<Thought>, StorageError> {
+            Ok(None)
+        }
+
+        async fn save_thought_raw_io(
+            &self,
+            _raw_io: &crate::traits::StoredRawIo,
+        ) -> Result<(), StorageError> {
+            Ok(())
+        }
+
         async fn save_checkpoint(
             &self,
             _checkpoint: &crate::traits::StoredCheckpoint,
@@ -219,6 +264,15 @@ pub mod doctest_helpers {
             Ok(vec![])
         }
 
+        async fn save_graph_batch(
+            &self,
+            _thought: &Thought,
+            _nodes: &[crate::traits::StoredGraphNode],
+            _edges: &[crate::traits::StoredGraphEdge],
+        ) -> Result<(), StorageError> {
+            Ok(())
+        }
+
         async fn save_graph_edge(
             &self,
             _edge: &crate::traits::StoredGraphEdge,
@@ -244,6 +298,10 @@ pub mod doctest_helpers {
         async fn delete_graph_node(&self, _id: &str) -> Result<(), StorageError> {
             Ok(())
         }
+
+        async fn delete_graph_edge(&self, _id: &str) -> Result<(), StorageError> {
+            Ok(())
+        }
     }
 
     /// A simple mock Anthropic client for doctests.
@@ -524,6 +582,8 @@ mod doctest_helpers_tests {
             score: Some(0.8),
             is_terminal: false,
             metadata: None,
+            origin: None,
+            parent_ids: Vec::new(),
             created_at: chrono::Utc::now(),
         };
         let result = storage.save_graph_node(&node).await;
@@ -179,7 +179,12 @@ async fn run_health_check() -> Result<(), Box<dyn std::error::Error>> {
 
     // Check 3: Database connectivity
     print!("3. Database connection... ");
-    match SqliteStorage::new(&config.database_path).await {
+    match SqliteStorage::new_with_max_connections(
+        &config.database_path,
+        config.database_max_connections,
+    )
+    .await
+    {
         Ok(_) => println!("✅"),
         Err(e) => {
             println!("❌");
@@ -37,6 +37,10 @@ use std::time::Instant;
 
 use crate::dashboard::{ActivityBus, ActivityEvent, EdgeId, Node, Phase};
 
+mod snapshot;
+
+pub use snapshot::{run_snapshot_writer, write_snapshot, MetricsSnapshot};
+
 /// Maximum number of transitions to keep in circular buffer.
 const MAX_TRANSITIONS: usize = 10_000;
 
@@ -366,6 +370,23 @@ pub struct MetricsCollector {
     /// Recorded model-version-change events, oldest first, bounded by
     /// [`MAX_MODEL_VERSION_CHANGES`]. Read by the drift classifier (FR-017, D3).
     model_version_changes: RwLock<Vec<ModelVersionChange>>,
+    /// Physical API attempts per mode label, including retries — one increment
+    /// per request actually sent. Incremented inside the client retry loop.
+    retry_attempts: RwLock<HashMap<String, u64>>,
+    /// Retries per `mode/error_kind` label (attempts after the first, keyed by
+    /// the error that triggered them).
+    retry_retries: RwLock<HashMap<String, u64>>,
+    /// Calls that gave up with their retries (or the shared retry budget)
+    /// exhausted, per `mode/error_kind` label.
+    retry_exhausted: RwLock<HashMap<String, u64>>,
+    /// Uncached input tokens consumed per mode label, summed across calls.
+    token_input: RwLock<HashMap<String, u64>>,
+    /// Output tokens generated per mode label, summed across calls.
+    token_output: RwLock<HashMap<String, u64>>,
+    /// Input tokens served from the prompt cache per mode label. Kept separate
+    /// from `token_input` (cache reads are billed at a reduced rate), so the
+    /// savings from prompt caching are directly visible.
+    token_cache_read: RwLock<HashMap<String, u64>>,
     /// Optional activity bus for the real-time dashboard. Set once at startup via
     /// [`MetricsCollector::set_activity`]; when present, [`MetricsCollector::record`]
     /// emits a `Mode` completed/failed activity for every tool call (the single
@@ -390,6 +411,36 @@ pub struct ModelVersionChange {
     pub at_millis: i64,
 }
 
+/// Snapshot of the retry counters, distinguishing logical operations from
+/// physical attempts so rate-limit pressure is visible.
+///
+/// `retries_total` and `retry_exhausted_total` are keyed by `mode/error_kind`
+/// (e.g. `linear/rate_limited`); `attempts_total` is keyed by mode alone since
+/// a successful attempt has no error kind.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetrySummary {
+    /// Physical API attempts per mode, including retries.
+    pub attempts_total: HashMap<String, u64>,
+    /// Retries (attempts after the first) per `mode/error_kind`.
+    pub retries_total: HashMap<String, u64>,
+    /// Calls that gave up with retries exhausted, per `mode/error_kind`.
+    pub retry_exhausted_total: HashMap<String, u64>,
+}
+
+/// Snapshot of the token-usage counters, both keyed by mode label.
+///
+/// Cache reads are reported separately from regular input tokens — they are
+/// billed at a reduced rate, and the split shows what prompt caching saves.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsageSummary {
+    /// Uncached input tokens per mode.
+    pub input_tokens_total: HashMap<String, u64>,
+    /// Output tokens per mode.
+    pub output_tokens_total: HashMap<String, u64>,
+    /// Input tokens served from the prompt cache per mode.
+    pub cache_read_tokens_total: HashMap<String, u64>,
+}
+
 impl MetricsCollector {
     /// Create a new metrics collector.
     #[must_use]
@@ -420,6 +471,97 @@ impl MetricsCollector {
         }
     }
 
+    /// Record a physical API attempt for `mode` (every request actually sent,
+    /// including retries). Incremented inside the client retry loop.
+    pub fn record_attempt(&self, mode: &str) {
+        if let Ok(mut m) = self.retry_attempts.write() {
+            *m.entry(mode.to_string()).or_default() += 1;
+        }
+    }
+
+    /// Record a retry for `mode` triggered by an error of `error_kind`
+    /// (a retryable failure that will be attempted again).
+    pub fn record_retry(&self, mode: &str, error_kind: &str) {
+        if let Ok(mut m) = self.retry_retries.write() {
+            *m.entry(format!("{mode}/{error_kind}")).or_default() += 1;
+        }
+    }
+
+    /// Record a call for `mode` that gave up with its retries (or the shared
+    /// retry budget) exhausted; `error_kind` is the final error.
+    pub fn record_retry_exhausted(&self, mode: &str, error_kind: &str) {
+        if let Ok(mut m) = self.retry_exhausted.write() {
+            *m.entry(format!("{mode}/{error_kind}")).or_default() += 1;
+        }
+    }
+
+    /// Record the token usage of a completed call for `mode`, keeping cache
+    /// reads separate from regular input tokens so prompt-cache savings are
+    /// visible per mode.
+    pub fn record_token_usage(
+        &self,
+        mode: &str,
+        input_tokens: u32,
+        output_tokens: u32,
+        cache_read_tokens: u32,
+    ) {
+        if let Ok(mut m) = self.token_input.write() {
+            *m.entry(mode.to_string()).or_default() += u64::from(input_tokens);
+        }
+        if let Ok(mut m) = self.token_output.write() {
+            *m.entry(mode.to_string()).or_default() += u64::from(output_tokens);
+        }
+        if cache_read_tokens > 0 {
+            if let Ok(mut m) = self.token_cache_read.write() {
+                *m.entry(mode.to_string()).or_default() += u64::from(cache_read_tokens);
+            }
+        }
+    }
+
+    /// Snapshot of the token-usage counters for reporting.
+    #[must_use]
+    pub fn token_usage_summary(&self) -> TokenUsageSummary {
+        TokenUsageSummary {
+            input_tokens_total: self
+                .token_input
+                .read()
+                .map(|m| m.clone())
+                .unwrap_or_default(),
+            output_tokens_total: self
+                .token_output
+                .read()
+                .map(|m| m.clone())
+                .unwrap_or_default(),
+            cache_read_tokens_total: self
+                .token_cache_read
+                .read()
+                .map(|m| m.clone())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Snapshot of the retry counters for reporting.
+    #[must_use]
+    pub fn retry_summary(&self) -> RetrySummary {
+        RetrySummary {
+            attempts_total: self
+                .retry_attempts
+                .read()
+                .map(|m| m.clone())
+                .unwrap_or_default(),
+            retries_total: self
+                .retry_retries
+                .read()
+                .map(|m| m.clone())
+                .unwrap_or_default(),
+            retry_exhausted_total: self
+                .retry_exhausted
+                .read()
+                .map(|m| m.clone())
+                .unwrap_or_default(),
+        }
+    }
+
     /// Total parse failures recorded for `component` (0 if none).
     #[must_use]
     pub fn parse_failure_count(&self, component: &str) -> u64 {
@@ -1488,6 +1630,58 @@ impl Default for Timer {
 mod tests {
     use super::*;
 
+    #[test]
+    fn retry_counters_start_empty() {
+        let m = MetricsCollector::new();
+        let summary = m.retry_summary();
+        assert!(summary.attempts_total.is_empty());
+        assert!(summary.retries_total.is_empty());
+        assert!(summary.retry_exhausted_total.is_empty());
+    }
+
+    #[test]
+    fn retry_counters_track_attempts_retries_and_exhaustion() {
+        let m = MetricsCollector::new();
+        // A call that fails twice then succeeds: 3 attempts, 2 retries.
+        m.record_attempt("linear");
+        m.record_retry("linear", "rate_limited");
+        m.record_attempt("linear");
+        m.record_retry("linear", "rate_limited");
+        m.record_attempt("linear");
+        // A call for another mode that gives up.
+        m.record_attempt("tree");
+        m.record_retry_exhausted("tree", "overloaded");
+
+        let summary = m.retry_summary();
+        assert_eq!(summary.attempts_total.get("linear"), Some(&3));
+        assert_eq!(summary.attempts_total.get("tree"), Some(&1));
+        assert_eq!(summary.retries_total.get("linear/rate_limited"), Some(&2));
+        assert_eq!(
+            summary.retry_exhausted_total.get("tree/overloaded"),
+            Some(&1)
+        );
+        assert!(!summary.retries_total.contains_key("tree/overloaded"));
+    }
+
+    #[test]
+    fn token_usage_counters_keep_cache_reads_separate() {
+        let m = MetricsCollector::new();
+        assert_eq!(m.token_usage_summary(), TokenUsageSummary::default());
+
+        // Two calls for one mode: one cold, one served from the prompt cache.
+        m.record_token_usage("detect", 1200, 400, 0);
+        m.record_token_usage("detect", 200, 100, 1000);
+        m.record_token_usage("linear", 300, 50, 0);
+
+        let summary = m.token_usage_summary();
+        assert_eq!(summary.input_tokens_total.get("detect"), Some(&1400));
+        assert_eq!(summary.output_tokens_total.get("detect"), Some(&500));
+        assert_eq!(summary.cache_read_tokens_total.get("detect"), Some(&1000));
+        assert_eq!(summary.input_tokens_total.get("linear"), Some(&300));
+        // A mode with no cache hits has no cache-read entry at all.
+        assert!(!summary.cache_read_tokens_total.contains_key("linear"));
+    }
+
     #[test]
     fn model_version_first_observation_sets_baseline_without_event() {
         let m = MetricsCollector::new();
@@ -1972,7 +2166,7 @@ mod tests {
         collector.record_tool_use("s2", "decision", true);
 
         let summary = collector.chain_summary();
-        assert!(summary.transitions.get("linear").is_none());
+        assert!(!summary.transitions.contains_key("linear"));
         assert_eq!(
             summary
                 .transitions
@@ -0,0 +1,221 @@
+//! JSON metrics snapshot export.
+//!
+//! Produces a point-in-time, serde-serializable [`MetricsSnapshot`] of the
+//! collector — invocation counts, token totals, nominal cost, and latency
+//! percentiles — and, when `METRICS_SNAPSHOT_PATH` is set, a background task
+//! that writes it to that path on an interval. Writes are atomic (temp file
+//! + rename), so a reader never observes a half-written snapshot.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+
+use super::{MetricsCollector, MetricsSummary, TokenUsageSummary};
+
+/// Point-in-time JSON export of the metrics collector.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsSnapshot {
+    /// When the snapshot was taken (Unix epoch seconds).
+    pub generated_at: u64,
+    /// Total invocations across all modes.
+    pub total_invocations: u64,
+    /// Successful invocations across all modes.
+    pub success_count: u64,
+    /// Failed invocations across all modes.
+    pub failure_count: u64,
+    /// Per-mode counts and latency stats (the regular summary).
+    pub summary: MetricsSummary,
+    /// Token counters per mode (input / output / cache reads).
+    pub tokens: TokenUsageSummary,
+    /// Uncached input tokens summed across all modes.
+    pub total_input_tokens: u64,
+    /// Output tokens summed across all modes.
+    pub total_output_tokens: u64,
+    /// Prompt-cache read tokens summed across all modes.
+    pub total_cache_read_tokens: u64,
+    /// Nominal API spend in USD for the recorded token totals, at the same
+    /// pricing the preset cost ceiling uses. Cache reads are excluded (they
+    /// are billed at a reduced rate and small next to the totals).
+    pub estimated_cost_usd: f64,
+    /// Median latency across all recorded invocations, in milliseconds.
+    pub latency_p50_ms: u64,
+    /// 95th-percentile latency in milliseconds.
+    pub latency_p95_ms: u64,
+    /// 99th-percentile latency in milliseconds.
+    pub latency_p99_ms: u64,
+}
+
+impl MetricsCollector {
+    /// Take a point-in-time snapshot of counts, token totals, cost, and
+    /// latency percentiles, suitable for serializing to JSON.
+    #[must_use]
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let summary = self.summary();
+        let tokens = self.token_usage_summary();
+
+        let success_count: u64 = summary.by_mode.values().map(|m| m.successful).sum();
+        let failure_count: u64 = summary.by_mode.values().map(|m| m.failed).sum();
+
+        let total_input_tokens: u64 = tokens.input_tokens_total.values().sum();
+        let total_output_tokens: u64 = tokens.output_tokens_total.values().sum();
+        let total_cache_read_tokens: u64 = tokens.cache_read_tokens_total.values().sum();
+
+        let mut latencies: Vec<u64> = self
+            .events
+            .read()
+            .map(|events| events.iter().map(|e| e.latency_ms).collect())
+            .unwrap_or_default();
+        latencies.sort_unstable();
+        let percentile = |p: usize| {
+            latencies
+                .get(latencies.len() * p / 100)
+                .copied()
+                .unwrap_or(0)
+        };
+
+        MetricsSnapshot {
+            generated_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            total_invocations: summary.total_invocations,
+            success_count,
+            failure_count,
+            estimated_cost_usd: crate::presets::cost_usd_for_totals(
+                total_input_tokens,
+                total_output_tokens,
+            ),
+            total_input_tokens,
+            total_output_tokens,
+            total_cache_read_tokens,
+            latency_p50_ms: percentile(50),
+            latency_p95_ms: percentile(95),
+            latency_p99_ms: percentile(99),
+            summary,
+            tokens,
+        }
+    }
+}
+
+/// Serialize the current snapshot and write it to `path` atomically.
+///
+/// The JSON is written to a sibling `.tmp` file first and renamed into
+/// place, so a concurrent reader sees either the previous snapshot or the
+/// new one, never a partial write.
+///
+/// # Errors
+///
+/// Returns an IO error when the temp file cannot be written or renamed;
+/// serialization itself cannot fail for this type.
+pub async fn write_snapshot(metrics: &MetricsCollector, path: &Path) -> std::io::Result<()> {
+    let snapshot = metrics.snapshot();
+    let json = serde_json::to_vec_pretty(&snapshot).map_err(std::io::Error::other)?;
+
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    tokio::fs::write(&tmp, &json).await?;
+    tokio::fs::rename(&tmp, path).await
+}
+
+/// Run the snapshot writer until `shutdown_rx` flips to `true`, writing the
+/// snapshot to `path` on a fixed interval. A failed write is logged and the
+/// next tick tries again.
+#[cfg_attr(coverage_nightly, coverage(off))]
+pub async fn run_snapshot_writer(
+    metrics: Arc<MetricsCollector>,
+    path: std::path::PathBuf,
+    interval: Duration,
+    mut shutdown_rx: watch::Receiver<bool>,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {
+                if let Err(e) = write_snapshot(&metrics, &path).await {
+                    tracing::warn!(path = %path.display(), error = %e, "Metrics snapshot write failed");
+                }
+            }
+            _ = shutdown_rx.changed() => {
+                if *shutdown_rx.borrow() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::metrics::MetricEvent;
+
+    #[test]
+    fn snapshot_reflects_recorded_operations() {
+        let metrics = MetricsCollector::new();
+        metrics.record(MetricEvent::new("linear", 100, true));
+        metrics.record(MetricEvent::new("linear", 200, true));
+        metrics.record(MetricEvent::new("tree", 300, false));
+        metrics.record_token_usage("linear", 1_000_000, 500_000, 250);
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.total_invocations, 3);
+        assert_eq!(snapshot.success_count, 2);
+        assert_eq!(snapshot.failure_count, 1);
+        assert_eq!(snapshot.total_input_tokens, 1_000_000);
+        assert_eq!(snapshot.total_output_tokens, 500_000);
+        assert_eq!(snapshot.total_cache_read_tokens, 250);
+        // 1M input @ $3/MTok + 0.5M output @ $15/MTok.
+        assert!((snapshot.estimated_cost_usd - 10.5).abs() < 1e-9);
+        assert_eq!(snapshot.latency_p50_ms, 200);
+        assert_eq!(snapshot.latency_p99_ms, 300);
+        assert!(snapshot.generated_at > 0);
+        assert!(snapshot.summary.by_mode.contains_key("tree"));
+    }
+
+    #[test]
+    fn snapshot_on_empty_collector_is_all_zeroes() {
+        let snapshot = MetricsCollector::new().snapshot();
+        assert_eq!(snapshot.total_invocations, 0);
+        assert_eq!(snapshot.latency_p50_ms, 0);
+        assert!((snapshot.estimated_cost_usd).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn snapshot_roundtrips_through_json() {
+        let metrics = MetricsCollector::new();
+        metrics.record(MetricEvent::new("linear", 150, true));
+        let snapshot = metrics.snapshot();
+
+        let json = serde_json::to_string(&snapshot).expect("serialize");
+        let parsed: MetricsSnapshot = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(parsed.total_invocations, 1);
+        assert_eq!(parsed.latency_p50_ms, 150);
+    }
+
+    #[tokio::test]
+    async fn write_snapshot_produces_valid_json_atomically() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("metrics.json");
+
+        let metrics = MetricsCollector::new();
+        metrics.record(MetricEvent::new("linear", 100, true));
+        write_snapshot(&metrics, &path).await.expect("write");
+
+        let contents = tokio::fs::read_to_string(&path).await.expect("read");
+        let parsed: MetricsSnapshot = serde_json::from_str(&contents).expect("valid JSON");
+        assert_eq!(parsed.total_invocations, 1);
+
+        // The temp file is renamed away, not left behind.
+        assert!(!dir.path().join("metrics.json.tmp").exists());
+
+        // A second write replaces the snapshot in place.
+        metrics.record(MetricEvent::new("tree", 200, false));
+        write_snapshot(&metrics, &path).await.expect("rewrite");
+        let contents = tokio::fs::read_to_string(&path).await.expect("reread");
+        let parsed: MetricsSnapshot = serde_json::from_str(&contents).expect("valid JSON");
+        assert_eq!(parsed.total_invocations, 2);
+    }
+}
@@ -7,14 +7,31 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt::Write as _;
 
 use crate::error::ModeError;
-use crate::modes::{extract_json, generate_thought_id, validate_content};
+use crate::modes::{
+    append_language_instruction, enforce_context_budget, extract_json, generate_thought_id,
+    reject_unknown_keys, validate_content, ModeFlags,
+};
 use crate::prompts::{get_prompt_for_mode, ReasoningMode};
 use crate::traits::{
-    AnthropicClientTrait, CompletionConfig, Message, Session, StorageTrait, Thought,
+    AnthropicClientTrait, CompletionConfig, Message, ModeConfidenceStat, Session, StorageTrait,
+    Thought,
 };
 
+/// Minimum stored thoughts a mode needs before its history can bias routing.
+/// Below this the average is noise, not a track record.
+const HISTORY_MIN_SAMPLES: u32 = 3;
+
+/// Weight of historical confidence in the blended selection score; the
+/// model's own judgment keeps the remainder.
+const HISTORY_BLEND_WEIGHT: f64 = 0.4;
+
+/// Neutral term used when one side of the blend has no signal for a mode
+/// (the model didn't pick it, or it has no qualifying history).
+const HISTORY_NEUTRAL_SCORE: f64 = 0.5;
+
 /// Response from auto mode selection.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AutoResponse {
@@ -35,6 +52,11 @@ pub struct AutoResponse {
     /// Alternative mode recommendation.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub alternative_mode: Option<AlternativeMode>,
+    /// Historical mode recommendation (only when history bias is enabled and
+    /// qualifying history exists). `applied` says whether it changed the
+    /// selection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history_recommendation: Option<HistoryRecommendation>,
 }
 
 impl AutoResponse {
@@ -58,6 +80,7 @@ impl AutoResponse {
             characteristics,
             suggested_parameters,
             alternative_mode: None,
+            history_recommendation: None,
         }
     }
 
@@ -67,6 +90,13 @@ impl AutoResponse {
         self.alternative_mode = Some(alternative);
         self
     }
+
+    /// Attach the historical mode recommendation.
+    #[must_use]
+    pub fn with_history_recommendation(mut self, recommendation: HistoryRecommendation) -> Self {
+        self.history_recommendation = Some(recommendation);
+        self
+    }
 }
 
 /// Alternative mode recommendation.
@@ -89,6 +119,26 @@ impl AlternativeMode {
     }
 }
 
+/// The mode history says has produced the highest-confidence thoughts for
+/// inputs similar to the current content.
+///
+/// Built from [`StorageTrait::mode_confidence_stats`] when history bias is
+/// enabled: thoughts matching the content via full-text search are aggregated
+/// by mode, and the strongest mode with at least [`HISTORY_MIN_SAMPLES`]
+/// samples becomes the recommendation. It is blended with the LLM's choice —
+/// `applied` is true when the blend actually re-routed the selection.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HistoryRecommendation {
+    /// The historically strongest mode for similar inputs.
+    pub mode: ReasoningMode,
+    /// Mean confidence of that mode's stored thoughts (0.0-1.0).
+    pub avg_confidence: f64,
+    /// Number of thoughts the mean is computed over.
+    pub samples: u32,
+    /// True when the recommendation overrode the LLM's selection.
+    pub applied: bool,
+}
+
 /// Auto reasoning mode.
 ///
 /// Analyzes content and selects the optimal reasoning mode.
@@ -99,6 +149,14 @@ where
 {
     storage: S,
     client: C,
+    /// Behavior flags injected from server configuration (all off by default).
+    flags: ModeFlags,
+    /// Effective output language (per-request override merged with the
+    /// configured default at the server boundary).
+    language: Option<String>,
+    /// When true, stored thought scores by mode bias the selection toward
+    /// historically successful modes.
+    history_bias: bool,
 }
 
 impl<S, C> AutoMode<S, C>
@@ -109,7 +167,40 @@ where
     /// Create a new auto mode instance.
     #[must_use]
     pub fn new(storage: S, client: C) -> Self {
-        Self { storage, client }
+        Self {
+            storage,
+            client,
+            flags: ModeFlags::default(),
+            language: None,
+            history_bias: false,
+        }
+    }
+
+    /// Set the output language for natural-language response fields. The
+    /// server passes the per-request override merged with the configured
+    /// `RESPONSE_LANGUAGE` default; `None` adds no language instruction.
+    #[must_use]
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Inject the server's behavior flags ([`ModeFlags`]). A mode built
+    /// without them — as in most tests — uses the all-off defaults.
+    #[must_use]
+    pub fn with_flags(mut self, flags: ModeFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Bias selection toward modes that historically produced high-confidence
+    /// thoughts for similar inputs (`AUTO_HISTORY_BIAS`). The LLM still
+    /// chooses first; its pick is blended against the stored track record and
+    /// only re-routed when history clearly outweighs it.
+    #[must_use]
+    pub const fn with_history_bias(mut self, enabled: bool) -> Self {
+        self.history_bias = enabled;
+        self
     }
 
     /// Analyze content and select the optimal reasoning mode.
@@ -137,7 +228,11 @@ where
         } else {
             String::new()
         };
-        let prompt = get_prompt_for_mode(ReasoningMode::Auto, None);
+        let prompt = append_language_instruction(
+            get_prompt_for_mode(ReasoningMode::Auto, None),
+            self.language.as_deref(),
+            content,
+        );
 
         // Fold caller-supplied hints into the prompt so they actually influence
         // the selection.
@@ -152,26 +247,39 @@ where
 
         let user_message =
             format!("{prompt}\n\n{prior_context}{hints_block}Analyze this content:\n{content}");
-        let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("auto")
             .with_max_tokens(4096)
             .with_temperature(0.5);
+        enforce_context_budget(&user_message, config.max_tokens)?;
+        let messages = vec![Message::user(user_message)];
 
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
 
         // Parse selected mode
-        let selected_mode = Self::parse_mode(&json, "selected_mode")?;
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &[
+                "selected_mode",
+                "reasoning",
+                "confidence",
+                "characteristics",
+                "alternative_mode",
+            ],
+        )?;
+        let mut selected_mode = Self::parse_mode(&json, "selected_mode")?;
 
         // Parse reasoning
-        let reasoning = json
+        let mut reasoning = json
             .get("reasoning")
             .and_then(|v| v.as_str())
             .unwrap_or("No reasoning provided")
             .to_string();
 
         // Parse confidence (0.0–1.0), default to 0.7 if absent or out of range
-        let confidence = json
+        let mut confidence = json
             .get("confidence")
             .and_then(serde_json::Value::as_f64)
             .map_or(0.7, |c| c.clamp(0.0, 1.0));
@@ -188,7 +296,22 @@ where
             .unwrap_or_default();
 
         // Parse alternative mode
-        let alternative_mode = Self::parse_alternative(&json);
+        let mut alternative_mode = Self::parse_alternative(&json);
+
+        // Historical bias (opt-in): blend the LLM's choice against the stored
+        // track record for similar inputs, possibly re-routing the selection.
+        let history_recommendation = if self.history_bias {
+            self.recommend_from_history(
+                content,
+                &mut selected_mode,
+                &mut confidence,
+                &mut reasoning,
+                &mut alternative_mode,
+            )
+            .await
+        } else {
+            None
+        };
 
         // Generate thought ID and save using actual confidence
         let thought_id = generate_thought_id();
@@ -211,9 +334,100 @@ where
             response = response.with_alternative(alt);
         }
 
+        if let Some(history) = history_recommendation {
+            response = response.with_history_recommendation(history);
+        }
+
         Ok(response)
     }
 
+    /// Consult stored thought scores by mode for inputs similar to `content`
+    /// and blend them with the LLM's choice, mutating the selection in place
+    /// when history wins.
+    ///
+    /// Each side contributes a blended score
+    /// `(1 - w) * llm_term + w * history_term` with
+    /// `w = HISTORY_BLEND_WEIGHT`; a side without a signal for a mode uses
+    /// [`HISTORY_NEUTRAL_SCORE`]. The historical favorite only takes over
+    /// when its blended score strictly beats the LLM pick's — agreement or a
+    /// weak track record leaves the selection alone. Best-effort: a storage
+    /// failure or absent history biases nothing.
+    async fn recommend_from_history(
+        &self,
+        content: &str,
+        selected_mode: &mut ReasoningMode,
+        confidence: &mut f64,
+        reasoning: &mut String,
+        alternative_mode: &mut Option<AlternativeMode>,
+    ) -> Option<HistoryRecommendation> {
+        let stats = match self
+            .storage
+            .mode_confidence_stats(Some(content.to_string()))
+            .await
+        {
+            Ok(stats) => stats,
+            Err(e) => {
+                tracing::warn!(
+                    error = %e,
+                    "Mode history lookup failed — routing on the model's choice alone"
+                );
+                return None;
+            }
+        };
+
+        // Only selectable reasoning modes with a real track record qualify.
+        // Auxiliary thought kinds ("assumption", "decision_weighted", ...)
+        // don't parse as modes and drop out; auto never recommends itself.
+        let candidates: Vec<(ReasoningMode, &ModeConfidenceStat)> = stats
+            .iter()
+            .filter(|s| s.samples >= HISTORY_MIN_SAMPLES)
+            .filter_map(|s| s.mode.parse::<ReasoningMode>().ok().map(|m| (m, s)))
+            .filter(|(m, _)| *m != ReasoningMode::Auto)
+            .collect();
+        let history_for = |mode: ReasoningMode| {
+            candidates
+                .iter()
+                .find(|(m, _)| *m == mode)
+                .map(|(_, s)| s.avg_confidence)
+        };
+        // Stats arrive strongest first, so the first candidate is the favorite.
+        let (favorite_mode, favorite_stat) = candidates.first()?;
+
+        let llm_weight = 1.0 - HISTORY_BLEND_WEIGHT;
+        let selected_score = llm_weight * *confidence
+            + HISTORY_BLEND_WEIGHT * history_for(*selected_mode).unwrap_or(HISTORY_NEUTRAL_SCORE);
+        let favorite_score = if *favorite_mode == *selected_mode {
+            selected_score
+        } else {
+            llm_weight * HISTORY_NEUTRAL_SCORE + HISTORY_BLEND_WEIGHT * favorite_stat.avg_confidence
+        };
+
+        let applied = *favorite_mode != *selected_mode && favorite_score > selected_score;
+        if applied {
+            // Demote the LLM's pick to the alternative slot so it stays visible.
+            *alternative_mode = Some(AlternativeMode::new(
+                *selected_mode,
+                "Prompt-based choice, outweighed by historical success",
+            ));
+            let _ = write!(
+                reasoning,
+                " Re-routed to {}: it averaged {:.2} confidence over {} similar stored thoughts.",
+                favorite_mode.as_str(),
+                favorite_stat.avg_confidence,
+                favorite_stat.samples
+            );
+            *selected_mode = *favorite_mode;
+            *confidence = favorite_score.clamp(0.0, 1.0);
+        }
+
+        Some(HistoryRecommendation {
+            mode: *favorite_mode,
+            avg_confidence: favorite_stat.avg_confidence,
+            samples: favorite_stat.samples,
+            applied,
+        })
+    }
+
     /// Parse a reasoning mode from JSON.
     fn parse_mode(json: &serde_json::Value, key: &str) -> Result<ReasoningMode, ModeError> {
         let mode_str =
@@ -784,6 +998,178 @@ mod tests {
         assert!(alt.is_none());
     }
 
+    // History-bias tests
+
+    /// LLM picks linear at 0.6 while tree has a strong, well-sampled track
+    /// record for similar inputs — the recommender re-routes to tree.
+    #[tokio::test]
+    async fn test_history_bias_favors_consistently_strong_mode() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("test-session")));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        mock_storage
+            .expect_mode_confidence_stats()
+            .withf(|fts_query| fts_query.as_deref() == Some("compare three database designs"))
+            .returning(|_| {
+                Ok(vec![
+                    ModeConfidenceStat::new("tree", 0.92, 8),
+                    ModeConfidenceStat::new("linear", 0.55, 6),
+                ])
+            });
+
+        mock_client.expect_complete().returning(|_, _| {
+            Ok(CompletionResponse::new(
+                r#"{"selected_mode": "linear", "reasoning": "Steps", "confidence": 0.6}"#,
+                Usage::new(50, 100),
+            ))
+        });
+
+        let mode = AutoMode::new(mock_storage, mock_client).with_history_bias(true);
+        let result = mode
+            .select("compare three database designs", None, &[])
+            .await
+            .unwrap();
+
+        assert_eq!(result.selected_mode, ReasoningMode::Tree);
+        let history = result.history_recommendation.expect("recommendation");
+        assert_eq!(history.mode, ReasoningMode::Tree);
+        assert!((history.avg_confidence - 0.92).abs() < 1e-9);
+        assert_eq!(history.samples, 8);
+        assert!(history.applied);
+        // The demoted LLM pick stays visible as the alternative.
+        assert_eq!(
+            result.alternative_mode.expect("alternative").mode,
+            ReasoningMode::Linear
+        );
+        assert!(result.reasoning.contains("Re-routed to tree"));
+    }
+
+    /// A confident LLM pick survives mediocre history: the blend only
+    /// re-routes on a strict improvement, and `applied` records the outcome.
+    #[tokio::test]
+    async fn test_history_bias_keeps_confident_llm_choice() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("test-session")));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        mock_storage
+            .expect_mode_confidence_stats()
+            .returning(|_| Ok(vec![ModeConfidenceStat::new("tree", 0.62, 5)]));
+
+        mock_client.expect_complete().returning(|_, _| {
+            Ok(CompletionResponse::new(
+                r#"{"selected_mode": "linear", "reasoning": "Steps", "confidence": 0.95}"#,
+                Usage::new(50, 100),
+            ))
+        });
+
+        let mode = AutoMode::new(mock_storage, mock_client).with_history_bias(true);
+        let result = mode.select("Content", None, &[]).await.unwrap();
+
+        assert_eq!(result.selected_mode, ReasoningMode::Linear);
+        assert!((result.confidence - 0.95).abs() < 1e-9);
+        let history = result.history_recommendation.expect("recommendation");
+        assert_eq!(history.mode, ReasoningMode::Tree);
+        assert!(!history.applied);
+    }
+
+    /// Thin samples and non-mode thought kinds never qualify as candidates.
+    #[tokio::test]
+    async fn test_history_bias_ignores_thin_and_auxiliary_history() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("test-session")));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        mock_storage.expect_mode_confidence_stats().returning(|_| {
+            Ok(vec![
+                ModeConfidenceStat::new("assumption", 0.99, 20),
+                ModeConfidenceStat::new("tree", 0.95, 2),
+            ])
+        });
+
+        mock_client.expect_complete().returning(|_, _| {
+            Ok(CompletionResponse::new(
+                r#"{"selected_mode": "linear", "reasoning": "Steps", "confidence": 0.6}"#,
+                Usage::new(50, 100),
+            ))
+        });
+
+        let mode = AutoMode::new(mock_storage, mock_client).with_history_bias(true);
+        let result = mode.select("Content", None, &[]).await.unwrap();
+
+        assert_eq!(result.selected_mode, ReasoningMode::Linear);
+        assert!(result.history_recommendation.is_none());
+    }
+
+    /// A failed history lookup biases nothing — routing falls back to the
+    /// model's choice alone.
+    #[tokio::test]
+    async fn test_history_bias_tolerates_storage_failure() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("test-session")));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        mock_storage.expect_mode_confidence_stats().returning(|_| {
+            Err(crate::error::StorageError::QueryFailed {
+                query: "mode_confidence_stats".to_string(),
+                message: "boom".to_string(),
+            })
+        });
+
+        mock_client.expect_complete().returning(|_, _| {
+            Ok(CompletionResponse::new(
+                r#"{"selected_mode": "linear", "reasoning": "Steps", "confidence": 0.6}"#,
+                Usage::new(50, 100),
+            ))
+        });
+
+        let mode = AutoMode::new(mock_storage, mock_client).with_history_bias(true);
+        let result = mode.select("Content", None, &[]).await.unwrap();
+
+        assert_eq!(result.selected_mode, ReasoningMode::Linear);
+        assert!(result.history_recommendation.is_none());
+    }
+
+    /// Off by default: history is never consulted (the un-expected mock call
+    /// would panic) and no recommendation is attached.
+    #[tokio::test]
+    async fn test_history_bias_off_by_default() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("test-session")));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        // No expect_mode_confidence_stats: a call would panic.
+
+        mock_client.expect_complete().returning(|_, _| {
+            Ok(CompletionResponse::new(
+                r#"{"selected_mode": "linear", "reasoning": "Steps", "confidence": 0.6}"#,
+                Usage::new(50, 100),
+            ))
+        });
+
+        let mode = AutoMode::new(mock_storage, mock_client);
+        let result = mode.select("Content", None, &[]).await.unwrap();
+
+        assert_eq!(result.selected_mode, ReasoningMode::Linear);
+        assert!(result.history_recommendation.is_none());
+    }
+
     #[test]
     fn test_auto_mode_debug() {
         let mock_storage = MockStorageTrait::new();
@@ -11,7 +11,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::error::ModeError;
-use crate::modes::generate_checkpoint_id;
+use crate::modes::{generate_checkpoint_id, ModeFlags};
 use crate::traits::{AnthropicClientTrait, StorageTrait, StoredCheckpoint, Thought};
 
 /// Context captured in a checkpoint.
@@ -207,6 +207,8 @@ where
     // Reserved for future AI-enhanced checkpoint features (e.g., smart restore suggestions)
     #[allow(dead_code)]
     client: C,
+    /// Behavior flags injected from server configuration (all off by default).
+    flags: ModeFlags,
 }
 
 impl<S, C> CheckpointMode<S, C>
@@ -217,7 +219,19 @@ where
     /// Create a new checkpoint mode instance.
     #[must_use]
     pub fn new(storage: S, client: C) -> Self {
-        Self { storage, client }
+        Self {
+            storage,
+            client,
+            flags: ModeFlags::default(),
+        }
+    }
+
+    /// Inject the server's behavior flags ([`ModeFlags`]). A mode built
+    /// without them — as in most tests — uses the all-off defaults.
+    #[must_use]
+    pub fn with_flags(mut self, flags: ModeFlags) -> Self {
+        self.flags = flags;
+        self
     }
 
     /// Create a new checkpoint.
@@ -243,6 +257,11 @@ where
         context: CheckpointContext,
         resumption_hint: &str,
     ) -> Result<CreateResponse, ModeError> {
+        crate::modes::enforce_session_required(
+            self.flags.require_session_id,
+            "checkpoint",
+            Some(session_id),
+        )?;
         // Verify session exists
         let session = self
             .storage
@@ -311,6 +330,11 @@ where
     ///
     /// Returns [`ModeError`] if the session doesn't exist or storage fails.
     pub async fn list(&self, session_id: &str) -> Result<ListResponse, ModeError> {
+        crate::modes::enforce_session_required(
+            self.flags.require_session_id,
+            "checkpoint",
+            Some(session_id),
+        )?;
         // Verify session exists
         self.storage
             .get_session(session_id)
@@ -0,0 +1,221 @@
+//! Content chunking for oversized inputs.
+//!
+//! Very long documents exceed the per-message size limit enforced by the
+//! Anthropic client (`MAX_CONTENT_LENGTH`, 50KB). This module splits such
+//! content on paragraph boundaries into chunks that fit, preserving each
+//! chunk's byte offset into the original so passages cited in per-chunk
+//! results can be mapped back to their position in the full document.
+//!
+//! Used by the detect and evidence modes when chunking is enabled on the
+//! request: each chunk is analyzed separately and the per-chunk findings are
+//! merged and deduplicated by the mode.
+
+/// A contiguous slice of the original content, produced by [`chunk_content`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentChunk {
+    /// Zero-based position of this chunk in the split sequence.
+    pub index: usize,
+    /// Byte offset of `text` within the original content.
+    pub offset: usize,
+    /// The chunk's text, sliced verbatim from the original (inner paragraph
+    /// separators preserved).
+    pub text: String,
+}
+
+impl ContentChunk {
+    /// Map a passage cited against this chunk back to its byte offset in the
+    /// original content. Returns `None` when the passage does not occur
+    /// verbatim in the chunk (e.g. a hallucinated or paraphrased citation).
+    #[must_use]
+    pub fn locate(&self, passage: &str) -> Option<usize> {
+        self.text.find(passage).map(|pos| self.offset + pos)
+    }
+}
+
+/// Maximum chunk size in bytes. Kept below the client's 50KB per-message
+/// limit to leave headroom for the operation prompt, session context, and
+/// working-memory blocks prepended to each chunk.
+pub const CHUNK_MAX_BYTES: usize = 40_000;
+
+/// Split `content` into chunks of at most `max_bytes`, breaking on paragraph
+/// boundaries (blank lines).
+///
+/// Consecutive paragraphs are packed greedily into a chunk until adding the
+/// next one would exceed `max_bytes`; a single paragraph larger than
+/// `max_bytes` is hard-split at character boundaries. Content that already
+/// fits comes back as one chunk, so callers can treat the single-chunk case
+/// as the ordinary unchunked path.
+#[must_use]
+pub fn chunk_content(content: &str, max_bytes: usize) -> Vec<ContentChunk> {
+    let max_bytes = max_bytes.max(1);
+    if content.len() <= max_bytes {
+        return vec![ContentChunk {
+            index: 0,
+            offset: 0,
+            text: content.to_string(),
+        }];
+    }
+
+    let mut chunks = Vec::new();
+    // Current chunk as a byte range into `content`; None when no paragraph
+    // has been placed yet.
+    let mut current: Option<(usize, usize)> = None;
+
+    for (start, end) in paragraph_spans(content) {
+        if let Some((cur_start, cur_end)) = current {
+            // Extending keeps the original text between paragraphs intact.
+            if end - cur_start <= max_bytes {
+                current = Some((cur_start, end));
+                continue;
+            }
+            push_chunk(&mut chunks, content, cur_start, cur_end);
+            current = None;
+        }
+
+        if end - start > max_bytes {
+            // A single paragraph that cannot fit: hard-split at char boundaries.
+            for (piece_start, piece_end) in char_splits(content, start, end, max_bytes) {
+                push_chunk(&mut chunks, content, piece_start, piece_end);
+            }
+        } else {
+            current = Some((start, end));
+        }
+    }
+
+    if let Some((cur_start, cur_end)) = current {
+        push_chunk(&mut chunks, content, cur_start, cur_end);
+    }
+
+    if chunks.is_empty() {
+        // Whitespace-only content over the limit: degrade to a single chunk.
+        chunks.push(ContentChunk {
+            index: 0,
+            offset: 0,
+            text: content.to_string(),
+        });
+    }
+    chunks
+}
+
+/// Byte ranges of the non-blank paragraphs in `content`, split on blank lines.
+fn paragraph_spans(content: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    for part in content.split("\n\n") {
+        let trimmed_start = part.len() - part.trim_start().len();
+        let trimmed = part.trim();
+        if !trimmed.is_empty() {
+            let start = offset + trimmed_start;
+            spans.push((start, start + trimmed.len()));
+        }
+        offset += part.len() + 2; // account for the "\n\n" separator
+    }
+    spans
+}
+
+/// Split the byte range `[start, end)` of `content` into pieces of at most
+/// `max_bytes`, cutting only at character boundaries.
+fn char_splits(content: &str, start: usize, end: usize, max_bytes: usize) -> Vec<(usize, usize)> {
+    let mut pieces = Vec::new();
+    let mut piece_start = start;
+    while piece_start < end {
+        let mut piece_end = (piece_start + max_bytes).min(end);
+        while !content.is_char_boundary(piece_end) {
+            piece_end -= 1;
+        }
+        pieces.push((piece_start, piece_end));
+        piece_start = piece_end;
+    }
+    pieces
+}
+
+fn push_chunk(chunks: &mut Vec<ContentChunk>, content: &str, start: usize, end: usize) {
+    chunks.push(ContentChunk {
+        index: chunks.len(),
+        offset: start,
+        text: content[start..end].to_string(),
+    });
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_within_limit_is_single_chunk() {
+        let chunks = chunk_content("first paragraph\n\nsecond paragraph", 1000);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].index, 0);
+        assert_eq!(chunks[0].offset, 0);
+        assert_eq!(chunks[0].text, "first paragraph\n\nsecond paragraph");
+    }
+
+    #[test]
+    fn test_split_on_paragraph_boundary_preserves_offsets() {
+        let content = "aaaa aaaa\n\nbbbb bbbb\n\ncccc cccc";
+        let chunks = chunk_content(content, 22);
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].text, "aaaa aaaa\n\nbbbb bbbb");
+        assert_eq!(chunks[0].offset, 0);
+        assert_eq!(chunks[1].text, "cccc cccc");
+        assert_eq!(chunks[1].offset, 22);
+        // Every chunk slices the original verbatim at its offset.
+        for chunk in &chunks {
+            assert_eq!(
+                &content[chunk.offset..chunk.offset + chunk.text.len()],
+                chunk.text
+            );
+        }
+    }
+
+    #[test]
+    fn test_oversized_paragraph_is_hard_split() {
+        let content = "x".repeat(25);
+        let chunks = chunk_content(&content, 10);
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].text.len(), 10);
+        assert_eq!(chunks[1].offset, 10);
+        assert_eq!(chunks[2].text.len(), 5);
+        let rejoined: String = chunks.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(rejoined, content);
+    }
+
+    #[test]
+    fn test_hard_split_respects_char_boundaries() {
+        // 'é' is two bytes in UTF-8; a naive byte cut would land mid-char.
+        let content = "é".repeat(20);
+        let chunks = chunk_content(&content, 5);
+        let rejoined: String = chunks.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(rejoined, content);
+        for chunk in &chunks {
+            assert!(chunk.text.len() <= 5);
+        }
+    }
+
+    #[test]
+    fn test_locate_maps_passage_to_original_offset() {
+        let content = "the first point\n\nthe second point stands alone here";
+        let chunks = chunk_content(content, 20);
+        assert!(chunks.len() > 1);
+
+        let offset = chunks[1].locate("second point").unwrap();
+        assert_eq!(
+            &content[offset..offset + "second point".len()],
+            "second point"
+        );
+        assert!(chunks[1].locate("not in the text").is_none());
+    }
+
+    #[test]
+    fn test_blank_paragraphs_are_skipped() {
+        let content = "alpha\n\n\n\n   \n\nbeta";
+        let chunks = chunk_content(content, 6);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].text, "alpha");
+        assert_eq!(chunks[1].text, "beta");
+        assert_eq!(chunks[1].offset, content.find("beta").unwrap());
+    }
+}
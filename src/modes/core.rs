@@ -315,6 +315,42 @@ pub fn validate_confidence(confidence: f64) -> Result<(), ModeError> {
     Ok(())
 }
 
+/// Extract a probability-like field (0.0-1.0) from a JSON object.
+///
+/// Shared by the mode parsers for every field whose schema is a probability
+/// or normalized score — confidences, plausibilities, likelihoods, closeness.
+/// Using one helper keeps the range check and error wording uniform instead
+/// of each parser deciding (or forgetting) to validate.
+///
+/// # Arguments
+///
+/// * `json` - The JSON object to read from
+/// * `field` - The field name to extract
+///
+/// # Returns
+///
+/// The value if present, numeric, and in range `[0.0, 1.0]`.
+///
+/// # Errors
+///
+/// Returns `ModeError::MissingField` if the field is absent or not numeric,
+/// or `ModeError::InvalidValue` if it is outside `[0.0, 1.0]`.
+pub fn parse_probability(json: &serde_json::Value, field: &str) -> Result<f64, ModeError> {
+    let value = json
+        .get(field)
+        .and_then(serde_json::Value::as_f64)
+        .ok_or_else(|| ModeError::MissingField {
+            field: field.to_string(),
+        })?;
+    if !(0.0..=1.0).contains(&value) {
+        return Err(ModeError::InvalidValue {
+            field: field.to_string(),
+            reason: format!("must be between 0.0 and 1.0, got {value}"),
+        });
+    }
+    Ok(value)
+}
+
 /// Validate that content is not empty.
 ///
 /// # Arguments
@@ -337,6 +373,555 @@ pub fn validate_content(content: &str) -> Result<(), ModeError> {
     Ok(())
 }
 
+/// Model context window in tokens, shared by input and requested output.
+pub const MODEL_CONTEXT_WINDOW_TOKENS: usize = 200_000;
+
+/// Characters per token for the pre-flight estimate. Deliberately
+/// conservative for English prose (real averages run closer to 4.5); an
+/// exact count would need the model's tokenizer.
+const ESTIMATE_CHARS_PER_TOKEN: usize = 4;
+
+/// Fraction of the input budget at which a near-limit request logs a warning.
+const BUDGET_WARN_PERCENT: usize = 80;
+
+/// Estimate the token count of `text` (chars / [`ESTIMATE_CHARS_PER_TOKEN`],
+/// rounded up). A heuristic for pre-flight budgeting, not an exact count.
+#[must_use]
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(ESTIMATE_CHARS_PER_TOKEN)
+}
+
+/// Pre-flight check that an assembled user message plus the requested
+/// output budget fits the model's context window.
+///
+/// An oversized request fails here with the estimated overflow instead of
+/// an opaque API rejection.
+///
+/// A request within budget but above [`BUDGET_WARN_PERCENT`] of it logs a
+/// warning. `max_tokens` is the completion's requested output budget; `None`
+/// reserves nothing.
+///
+/// # Errors
+///
+/// Returns `ModeError::InvalidValue` for `content` when the estimated input
+/// tokens exceed the window minus `max_tokens`, including how far over the
+/// request is.
+pub fn enforce_context_budget(
+    user_message: &str,
+    max_tokens: Option<u32>,
+) -> Result<(), ModeError> {
+    let reserved = max_tokens.unwrap_or(0) as usize;
+    let input_budget = MODEL_CONTEXT_WINDOW_TOKENS.saturating_sub(reserved);
+    let estimated = estimate_tokens(user_message);
+
+    if estimated > input_budget {
+        return Err(ModeError::InvalidValue {
+            field: "content".to_string(),
+            reason: format!(
+                "estimated {estimated} input tokens exceed the {input_budget}-token budget \
+                 by {} ({MODEL_CONTEXT_WINDOW_TOKENS}-token context window minus {reserved} \
+                 reserved output tokens)",
+                estimated - input_budget
+            ),
+        });
+    }
+    if estimated * 100 >= input_budget * BUDGET_WARN_PERCENT {
+        tracing::warn!(
+            estimated_tokens = estimated,
+            input_budget,
+            "Request is near the context window — consider trimming the content"
+        );
+    }
+    Ok(())
+}
+
+/// Per-mode behavior flags, populated from server configuration.
+///
+/// The server builds one value from `Config` and injects it into each mode
+/// through its `with_flags` builder, the same way per-call options like the
+/// output language are injected. Defaults are all off, matching the config
+/// defaults, so a mode constructed without flags — as in most tests —
+/// behaves exactly like an unconfigured server.
+// Independent opt-in toggles mirroring their Config counterparts — not a state machine.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ModeFlags {
+    /// Reject model responses whose top level carries unexpected keys
+    /// (`STRICT_PARSING`); consulted through [`reject_unknown_keys`].
+    pub strict_parsing: bool,
+    /// Issue exactly one corrective follow-up completion when a response
+    /// fails to parse, instead of failing outright (`SELF_CORRECT_PARSE`);
+    /// see [`build_correction_message`].
+    pub self_correct_parse: bool,
+    /// Attach the response schema to completion requests so supporting
+    /// modes receive schema-conforming JSON that parses directly, skipping
+    /// the [`extract_json`] heuristics (`STRUCTURED_OUTPUT`).
+    pub structured_output: bool,
+    /// Make stateful modes refuse to run without an explicit session id
+    /// instead of silently creating a throwaway session
+    /// (`REQUIRE_SESSION_ID`); consulted through
+    /// [`enforce_session_required`].
+    pub require_session_id: bool,
+    /// Rerun a completion once with a sharper instruction when the response
+    /// parses but is degenerate — an empty finding list where the content
+    /// clearly warranted findings (`RETRY_DEGENERATE`).
+    pub retry_degenerate: bool,
+}
+
+/// The prompt instruction selecting the output language, or an empty string.
+///
+/// `language` is the effective choice: the per-call override merged with the
+/// configured `RESPONSE_LANGUAGE` default at the server boundary, so modes
+/// carry a single resolved value. A value of `auto` (or `auto:<Fallback>`)
+/// selects the language detected from `content` via [`detect_language`],
+/// falling back to the `<Fallback>` language — or to no instruction — when
+/// detection is uncertain. The instruction pins JSON keys and fixed
+/// enumeration values to English so a translated response body still
+/// parses; only natural-language field values change language.
+///
+/// [`detect_language`]: super::language::detect_language
+#[must_use]
+pub fn language_instruction(language: Option<&str>, content: &str) -> String {
+    let language = language.and_then(|configured| resolve_auto_language(configured, content));
+    match language {
+        Some(language) if !language.trim().is_empty() => format!(
+            "\n\nWrite every natural-language field value in {language}. \
+             Keep all JSON keys, structure, and fixed enumeration values \
+             (severities, statuses, modes) in English so the response can \
+             be parsed."
+        ),
+        _ => String::new(),
+    }
+}
+
+/// Resolve a configured language value, expanding the `auto` form.
+///
+/// `auto` means "match the language detected from the content"; the
+/// `auto:<Fallback>` form names the language used when detection is
+/// uncertain (bare `auto` falls back to no instruction). Anything else is
+/// returned as-is.
+fn resolve_auto_language(configured: &str, content: &str) -> Option<String> {
+    let trimmed = configured.trim();
+    let lowered = trimmed.to_lowercase();
+    if lowered != "auto" && !lowered.starts_with("auto:") {
+        return Some(trimmed.to_string());
+    }
+    if let Some(detected) = super::language::detect_language(content) {
+        return Some(detected.to_string());
+    }
+    trimmed
+        .split_once(':')
+        .map(|(_, fallback)| fallback.trim().to_string())
+        .filter(|fallback| !fallback.is_empty())
+}
+
+/// Append the output-language instruction to a mode prompt.
+///
+/// The shared prompt-building step every mode routes its base prompt
+/// through: returns the prompt unchanged when no language is configured,
+/// otherwise with the [`language_instruction`] appended. `language` is the
+/// mode's resolved choice and `content` is the user text the request
+/// reasons over, consulted only by the `auto` configuration form.
+#[must_use]
+pub fn append_language_instruction(prompt: &str, language: Option<&str>, content: &str) -> String {
+    let instruction = language_instruction(language, content);
+    format!("{prompt}{instruction}")
+}
+
+/// Top-level keys every mode may legitimately return regardless of operation:
+/// the working-memory update hook, surfaced assumptions, and open questions.
+const UNIVERSAL_RESPONSE_KEYS: &[&str] = &["memory_update", "assumptions", "open_questions"];
+
+/// In strict mode, reject a model response whose top level carries keys the
+/// parser would silently drop — extra keys are an early sign of schema drift.
+///
+/// `strict` is the caller's [`ModeFlags::strict_parsing`]. Lenient when it
+/// is off (the default) or when the response is not a JSON object. The
+/// universal `memory_update`, `assumptions`, and `open_questions` keys are
+/// tolerated everywhere, since any mode may return them.
+///
+/// # Errors
+///
+/// Returns [`ModeError::InvalidValue`] naming the unknown key(s) when strict
+/// parsing is enabled and the object contains keys outside `expected`.
+pub fn reject_unknown_keys(
+    strict: bool,
+    json: &serde_json::Value,
+    expected: &[&str],
+) -> Result<(), ModeError> {
+    if !strict {
+        return Ok(());
+    }
+    let Some(object) = json.as_object() else {
+        return Ok(());
+    };
+    let unknown: Vec<&str> = object
+        .keys()
+        .map(String::as_str)
+        .filter(|k| !expected.contains(k) && !UNIVERSAL_RESPONSE_KEYS.contains(k))
+        .collect();
+    if unknown.is_empty() {
+        return Ok(());
+    }
+    Err(ModeError::InvalidValue {
+        field: "response".to_string(),
+        reason: format!(
+            "unexpected top-level key(s) in model response: {} (strict parsing is on; expected only: {})",
+            unknown.join(", "),
+            expected.join(", ")
+        ),
+    })
+}
+
+/// Maximum characters of the original model output echoed back in a
+/// correction request. Bounds the cost of the follow-up completion.
+const MAX_CORRECTION_OUTPUT_CHARS: usize = 8_000;
+
+/// In a stateful mode, reject a call that carries no session id when
+/// required session ids are enabled. A blank id counts as absent.
+///
+/// `required` is the caller's [`ModeFlags::require_session_id`]. Lenient
+/// when it is off (the default), so stateless modes and ad-hoc exploration
+/// are unaffected.
+///
+/// # Errors
+///
+/// Returns [`ModeError::SessionRequired`] when the flag is on and
+/// `session_id` is `None` or blank.
+pub fn enforce_session_required(
+    required: bool,
+    mode: &str,
+    session_id: Option<&str>,
+) -> Result<(), ModeError> {
+    if !required {
+        return Ok(());
+    }
+    if session_id.is_some_and(|id| !id.trim().is_empty()) {
+        return Ok(());
+    }
+    tracing::warn!(
+        mode,
+        "Rejecting stateful call without a session id (REQUIRE_SESSION_ID is on)"
+    );
+    Err(ModeError::SessionRequired)
+}
+
+/// Whether an error is the kind a corrected completion could fix.
+///
+/// Eligible errors mean the model produced output that failed to parse or
+/// violated the schema. Transport and storage errors are not correctable —
+/// retrying tells the model nothing.
+#[must_use]
+pub const fn correction_eligible(error: &ModeError) -> bool {
+    matches!(
+        error,
+        ModeError::JsonParseFailed { .. }
+            | ModeError::MissingField { .. }
+            | ModeError::InvalidValue { .. }
+    )
+}
+
+/// Build the user message for a one-shot correction completion: the
+/// correction prompt, the specific parse error, and the (bounded) original
+/// output the model must fix.
+#[must_use]
+pub fn build_correction_message(original_output: &str, error: &ModeError) -> String {
+    let prompt = crate::prompts::json_correction_prompt();
+    let truncated: String = original_output
+        .chars()
+        .take(MAX_CORRECTION_OUTPUT_CHARS)
+        .collect();
+    format!("{prompt}\n\nParse error: {error}\n\nYour previous response:\n{truncated}")
+}
+
+/// Maximum characters of working memory injected into a prompt.
+const MAX_WORKING_MEMORY_CHARS: usize = 2_000;
+
+/// Load a session's working memory, formatted as a prompt context block.
+///
+/// The working memory is a session-scoped scratchpad shared across modes: any
+/// mode injects it into its prompt, and the model can replace it by returning a
+/// `memory_update` string field (applied via [`apply_memory_update`]). Returns
+/// an empty string when no memory is set.
+///
+/// Like prior-thought context, this is an enhancement, not a precondition: a
+/// lookup failure proceeds without the block (logged) rather than failing.
+pub async fn load_working_memory_block<S: crate::traits::StorageTrait>(
+    storage: &S,
+    session_id: &str,
+) -> String {
+    let memory = match storage.get_working_memory(session_id).await {
+        Ok(memory) => memory,
+        Err(e) => {
+            tracing::warn!(
+                error = %e,
+                "Failed to load working memory — proceeding without it"
+            );
+            return String::new();
+        }
+    };
+
+    match memory {
+        Some(memory) if !memory.trim().is_empty() => {
+            let truncated: String = memory.chars().take(MAX_WORKING_MEMORY_CHARS).collect();
+            format!(
+                "Working memory (session scratchpad, shared across reasoning modes):\n{truncated}\n\
+                 To update it, include a \"memory_update\" string field in your JSON response.\n"
+            )
+        }
+        _ => String::new(),
+    }
+}
+
+/// Persist a `memory_update` field from a mode's parsed JSON output, if present.
+///
+/// Persisting the update is best-effort: a storage failure is logged and the
+/// reasoning result is returned unaffected, matching how modes treat thought
+/// persistence.
+pub async fn apply_memory_update<S: crate::traits::StorageTrait>(
+    storage: &S,
+    session_id: &str,
+    json: &serde_json::Value,
+) {
+    if let Some(update) = json.get("memory_update").and_then(|v| v.as_str()) {
+        if let Err(e) = storage.set_working_memory(session_id, update).await {
+            tracing::warn!(
+                error = %e,
+                "Failed to persist working-memory update — reasoning result preserved"
+            );
+        }
+    }
+}
+
+/// Maximum characters kept per raw IO field (`LINEAR_STORE_RAW_IO`). Matches the
+/// client's per-message content cap, so a capture is never larger than what
+/// the API would have accepted.
+const MAX_RAW_IO_CHARS: usize = 50_000;
+
+/// Marker appended when a raw IO field is cut at [`MAX_RAW_IO_CHARS`].
+const RAW_IO_TRUNCATION_MARKER: &str = "… [truncated]";
+
+/// The exact request/response pair behind one completion, captured for
+/// `LINEAR_STORE_RAW_IO` before any parsing (see [`persist_raw_io`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawExchange {
+    /// The request messages, serialized as a JSON array of `{role, content}`.
+    pub messages_json: String,
+    /// The unparsed completion text.
+    pub raw_completion: String,
+}
+
+impl RawExchange {
+    /// Capture `messages` and the raw completion text of one API call.
+    #[must_use]
+    pub fn capture(messages: &[crate::traits::Message], raw_completion: &str) -> Self {
+        let entries: Vec<serde_json::Value> = messages
+            .iter()
+            .map(|m| serde_json::json!({"role": m.role, "content": m.content}))
+            .collect();
+        Self {
+            messages_json: serde_json::Value::Array(entries).to_string(),
+            raw_completion: raw_completion.to_string(),
+        }
+    }
+}
+
+/// Persist the raw exchange behind a saved thought (`LINEAR_STORE_RAW_IO`).
+///
+/// Both fields are redacted (any `sk-ant-…` token is masked, in case a key
+/// ever leaks into prompt content) and capped at [`MAX_RAW_IO_CHARS`] before
+/// storage. Best-effort like thought persistence itself: a storage failure is
+/// logged and the reasoning result is returned unaffected.
+pub async fn persist_raw_io<S: crate::traits::StorageTrait>(
+    storage: &S,
+    thought_id: &str,
+    exchange: &RawExchange,
+) {
+    let raw_io = crate::traits::StoredRawIo::new(
+        thought_id,
+        cap_raw_io(&redact_api_keys(&exchange.messages_json)),
+        cap_raw_io(&redact_api_keys(&exchange.raw_completion)),
+    );
+    if let Err(e) = storage.save_thought_raw_io(&raw_io).await {
+        tracing::warn!(
+            error = %e,
+            thought_id,
+            "Failed to persist raw IO — reasoning result preserved"
+        );
+    }
+}
+
+/// Mask every `sk-ant-…` token in `text`, keeping only the recognizable
+/// prefix. Defense in depth: the API key travels in a header, not the body,
+/// but a key pasted into analyzed content must not be persisted verbatim.
+fn redact_api_keys(text: &str) -> String {
+    const KEY_PREFIX: &str = "sk-ant-";
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(KEY_PREFIX) {
+        redacted.push_str(&rest[..start]);
+        redacted.push_str("sk-ant-[REDACTED]");
+        let after = &rest[start + KEY_PREFIX.len()..];
+        let token_len = after
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '-' && c != '_')
+            .unwrap_or(after.len());
+        rest = &after[token_len..];
+    }
+    redacted.push_str(rest);
+    redacted
+}
+
+/// Cap a raw IO field at [`MAX_RAW_IO_CHARS`], marking the cut.
+fn cap_raw_io(text: &str) -> String {
+    if text.chars().count() <= MAX_RAW_IO_CHARS {
+        return text.to_string();
+    }
+    let mut capped: String = text.chars().take(MAX_RAW_IO_CHARS).collect();
+    capped.push_str(RAW_IO_TRUNCATION_MARKER);
+    capped
+}
+
+/// A hidden assumption surfaced by a reasoning mode.
+///
+/// Many conclusions hinge on premises the model never states. Modes that opt
+/// in ask for these explicitly and attach them to their responses, so a later
+/// `detect` pass can challenge the load-bearing ones.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct Assumption {
+    /// The assumption, stated explicitly.
+    pub assumption: String,
+    /// Probability the assumption actually holds (0.0-1.0).
+    pub confidence: f64,
+    /// How much the conclusion depends on it (0.0-1.0).
+    pub criticality: f64,
+}
+
+/// Parse an optional `assumptions` array from a mode's JSON output.
+///
+/// Tolerant by design: a missing or malformed field yields an empty list, and
+/// individual entries lacking the `assumption` string are skipped — surfacing
+/// assumptions must never fail an otherwise good reasoning result. Entries are
+/// returned most-critical first.
+#[must_use]
+pub fn parse_assumptions(json: &serde_json::Value) -> Vec<Assumption> {
+    let Some(entries) = json.get("assumptions").and_then(|v| v.as_array()) else {
+        return Vec::new();
+    };
+
+    let mut assumptions: Vec<Assumption> = entries
+        .iter()
+        .filter_map(|entry| {
+            let assumption = entry.get("assumption").and_then(|v| v.as_str())?;
+            Some(Assumption {
+                assumption: assumption.to_string(),
+                confidence: entry
+                    .get("confidence")
+                    .and_then(serde_json::Value::as_f64)
+                    .unwrap_or(0.5)
+                    .clamp(0.0, 1.0),
+                criticality: entry
+                    .get("criticality")
+                    .and_then(serde_json::Value::as_f64)
+                    .unwrap_or(0.5)
+                    .clamp(0.0, 1.0),
+            })
+        })
+        .collect();
+
+    assumptions.sort_by(|a, b| {
+        b.criticality
+            .partial_cmp(&a.criticality)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    assumptions
+}
+
+/// Persist surfaced assumptions as `assumption` thoughts in the session.
+///
+/// Stored thoughts flow into later prior-context blocks, so a subsequent
+/// `detect` pass over the session sees — and can challenge — them. Best-effort:
+/// a storage failure is logged and the reasoning result is unaffected.
+pub async fn persist_assumptions<S: crate::traits::StorageTrait>(
+    storage: &S,
+    session_id: &str,
+    assumptions: &[Assumption],
+) {
+    for assumption in assumptions {
+        let thought = crate::traits::Thought::new(
+            generate_thought_id(),
+            session_id,
+            format!(
+                "Assumption (criticality {:.2}): {}",
+                assumption.criticality, assumption.assumption
+            ),
+            "assumption",
+            assumption.confidence,
+        );
+        if let Err(e) = storage.save_thought(&thought).await {
+            tracing::warn!(
+                error = %e,
+                "Failed to persist assumption — reasoning result preserved"
+            );
+        }
+    }
+}
+
+/// Prefix [`persist_open_questions`] writes in front of each stored question,
+/// stripped back off when the session's open questions are aggregated.
+pub const OPEN_QUESTION_PREFIX: &str = "Open question: ";
+
+/// Neutral confidence for `open_question` thoughts: an unresolved question
+/// neither supports nor undermines the session's quality aggregate.
+const OPEN_QUESTION_CONFIDENCE: f64 = 0.5;
+
+/// Parse an optional `open_questions` array from a mode's JSON output.
+///
+/// Tolerant by design: a missing or malformed field yields an empty list, and
+/// non-string or blank entries are skipped — surfacing unknowns must never
+/// fail an otherwise good reasoning result.
+#[must_use]
+pub fn parse_open_questions(json: &serde_json::Value) -> Vec<String> {
+    let Some(entries) = json.get("open_questions").and_then(|v| v.as_array()) else {
+        return Vec::new();
+    };
+
+    entries
+        .iter()
+        .filter_map(|entry| entry.as_str())
+        .map(str::trim)
+        .filter(|question| !question.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Persist surfaced open questions as `open_question` thoughts in the session.
+///
+/// Stored thoughts flow into later prior-context blocks, and the
+/// `reasoning_open_questions` tool aggregates them across the session so
+/// accumulated unknowns stay visible. Best-effort: a storage failure is logged
+/// and the reasoning result is unaffected.
+pub async fn persist_open_questions<S: crate::traits::StorageTrait>(
+    storage: &S,
+    session_id: &str,
+    open_questions: &[String],
+) {
+    for question in open_questions {
+        let thought = crate::traits::Thought::new(
+            generate_thought_id(),
+            session_id,
+            format!("{OPEN_QUESTION_PREFIX}{question}"),
+            "open_question",
+            OPEN_QUESTION_CONFIDENCE,
+        );
+        if let Err(e) = storage.save_thought(&thought).await {
+            tracing::warn!(
+                error = %e,
+                "Failed to persist open question — reasoning result preserved"
+            );
+        }
+    }
+}
+
 /// Generate a unique thought ID.
 ///
 /// Uses UUID v4 for uniqueness.
@@ -587,6 +1172,38 @@ That's all!"#;
         assert!(matches!(err, ModeError::InvalidValue { field, .. } if field == "confidence"));
     }
 
+    // parse_probability tests
+    #[test]
+    fn test_parse_probability_valid() {
+        let json = serde_json::json!({"score": 0.85, "low": 0.0, "high": 1.0});
+        assert!((parse_probability(&json, "score").unwrap() - 0.85).abs() < f64::EPSILON);
+        assert_eq!(parse_probability(&json, "low").unwrap(), 0.0);
+        assert_eq!(parse_probability(&json, "high").unwrap(), 1.0);
+    }
+
+    #[test]
+    fn test_parse_probability_missing_or_non_numeric() {
+        let json = serde_json::json!({"score": "high"});
+        assert!(matches!(
+            parse_probability(&json, "score"),
+            Err(ModeError::MissingField { field }) if field == "score"
+        ));
+        assert!(matches!(
+            parse_probability(&json, "absent"),
+            Err(ModeError::MissingField { field }) if field == "absent"
+        ));
+    }
+
+    #[test]
+    fn test_parse_probability_out_of_range() {
+        for value in [-0.1, 1.1] {
+            let json = serde_json::json!({"plausibility": value});
+            let err = parse_probability(&json, "plausibility").unwrap_err();
+            assert!(matches!(err, ModeError::InvalidValue { field, ref reason }
+                    if field == "plausibility" && reason.contains("between 0.0 and 1.0")));
+        }
+    }
+
     // validate_content tests
     #[test]
     fn test_validate_content_valid() {
@@ -610,6 +1227,134 @@ That's all!"#;
         assert!(matches!(err, ModeError::MissingField { field } if field == "content"));
     }
 
+    // estimate_tokens / enforce_context_budget tests
+    #[test]
+    fn test_estimate_tokens_rounds_up() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+        // Chars, not bytes: multi-byte characters count once.
+        assert_eq!(estimate_tokens(&"é".repeat(8)), 2);
+    }
+
+    #[test]
+    fn test_enforce_context_budget_within_limit_passes() {
+        assert!(enforce_context_budget("a short prompt and content", Some(4096)).is_ok());
+        assert!(enforce_context_budget("no output reservation", None).is_ok());
+    }
+
+    #[test]
+    fn test_enforce_context_budget_oversized_rejected_with_overflow() {
+        // ~250K estimated tokens against a ~196K input budget.
+        let oversized = "x".repeat(MODEL_CONTEXT_WINDOW_TOKENS * 5);
+        let result = enforce_context_budget(&oversized, Some(16384));
+        let err = result.unwrap_err();
+        match err {
+            ModeError::InvalidValue { field, reason } => {
+                assert_eq!(field, "content");
+                assert!(reason.contains("exceed"), "reason: {reason}");
+                // The estimated overflow is surfaced, not just "too big".
+                assert!(reason.contains("by 66384"), "reason: {reason}");
+            }
+            other => panic!("expected InvalidValue, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_enforce_context_budget_output_reservation_counts() {
+        // Fits with nothing reserved, but not once the output budget is
+        // carved out of the window.
+        let message = "x".repeat((MODEL_CONTEXT_WINDOW_TOKENS - 1000) * 4);
+        assert!(enforce_context_budget(&message, None).is_ok());
+        assert!(enforce_context_budget(&message, Some(4096)).is_err());
+    }
+
+    // parse_assumptions tests
+    #[test]
+    fn test_parse_assumptions_orders_by_criticality() {
+        let json = serde_json::json!({
+            "analysis": "...",
+            "assumptions": [
+                {"assumption": "Minor premise", "confidence": 0.9, "criticality": 0.2},
+                {"assumption": "Load-bearing premise", "confidence": 0.6, "criticality": 0.95},
+                {"assumption": "Middling premise", "confidence": 0.7, "criticality": 0.5}
+            ]
+        });
+        let assumptions = parse_assumptions(&json);
+        assert_eq!(assumptions.len(), 3);
+        assert_eq!(assumptions[0].assumption, "Load-bearing premise");
+        assert_eq!(assumptions[0].criticality, 0.95);
+        assert_eq!(assumptions[1].assumption, "Middling premise");
+        assert_eq!(assumptions[2].assumption, "Minor premise");
+    }
+
+    #[test]
+    fn test_parse_assumptions_missing_field_is_empty() {
+        let json = serde_json::json!({"analysis": "no assumptions key"});
+        assert!(parse_assumptions(&json).is_empty());
+
+        // A non-array value is tolerated the same way.
+        let json = serde_json::json!({"assumptions": "not an array"});
+        assert!(parse_assumptions(&json).is_empty());
+    }
+
+    #[test]
+    fn test_parse_assumptions_skips_malformed_and_clamps() {
+        let json = serde_json::json!({
+            "assumptions": [
+                {"confidence": 0.9, "criticality": 0.9},
+                {"assumption": "Valid", "confidence": 7.0, "criticality": -1.0},
+                {"assumption": "Defaults"}
+            ]
+        });
+        let assumptions = parse_assumptions(&json);
+        assert_eq!(assumptions.len(), 2);
+        // Missing confidence/criticality default to 0.5; out-of-range clamps.
+        assert_eq!(assumptions[0].assumption, "Defaults");
+        assert_eq!(assumptions[0].criticality, 0.5);
+        assert_eq!(assumptions[1].assumption, "Valid");
+        assert_eq!(assumptions[1].confidence, 1.0);
+        assert_eq!(assumptions[1].criticality, 0.0);
+    }
+
+    // parse_open_questions tests
+    #[test]
+    fn test_parse_open_questions_extracts_strings() {
+        let json = serde_json::json!({
+            "analysis": "...",
+            "open_questions": [
+                "What is the expected traffic growth?",
+                "Is the Q3 budget already committed?"
+            ]
+        });
+        let questions = parse_open_questions(&json);
+        assert_eq!(
+            questions,
+            vec![
+                "What is the expected traffic growth?",
+                "Is the Q3 budget already committed?"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_open_questions_missing_field_is_empty() {
+        let json = serde_json::json!({"analysis": "no open_questions key"});
+        assert!(parse_open_questions(&json).is_empty());
+
+        // A non-array value is tolerated the same way.
+        let json = serde_json::json!({"open_questions": "not an array"});
+        assert!(parse_open_questions(&json).is_empty());
+    }
+
+    #[test]
+    fn test_parse_open_questions_skips_blank_and_non_string_entries() {
+        let json = serde_json::json!({
+            "open_questions": ["  Valid question?  ", "", "   ", 42, {"q": "object"}]
+        });
+        assert_eq!(parse_open_questions(&json), vec!["Valid question?"]);
+    }
+
     // ID generation tests
     #[test]
     fn test_generate_thought_id_unique() {
@@ -874,4 +1619,234 @@ That's all!"#;
         let cloned = core.client_arc();
         assert!(Arc::ptr_eq(&client_arc, &cloned));
     }
+
+    // reject_unknown_keys tests
+
+    #[test]
+    fn test_reject_unknown_keys_lenient_by_default() {
+        let json = serde_json::json!({"analysis": "fine", "bogus": true});
+        assert!(reject_unknown_keys(false, &json, &["analysis"]).is_ok());
+    }
+
+    #[test]
+    fn test_reject_unknown_keys_strict_names_unknown_keys() {
+        let json = serde_json::json!({"analysis": "fine", "bogus": true});
+        let result = reject_unknown_keys(true, &json, &["analysis"]);
+
+        let err = result.expect_err("strict mode should reject");
+        let message = err.to_string();
+        assert!(
+            message.contains("bogus"),
+            "names the unknown key: {message}"
+        );
+        assert!(
+            message.contains("analysis"),
+            "lists expected keys: {message}"
+        );
+    }
+
+    #[test]
+    fn test_reject_unknown_keys_strict_tolerates_universal_keys() {
+        let json = serde_json::json!({
+            "analysis": "fine",
+            "memory_update": {"facts": []},
+            "assumptions": ["a"],
+        });
+        assert!(reject_unknown_keys(true, &json, &["analysis"]).is_ok());
+    }
+
+    #[test]
+    fn test_reject_unknown_keys_strict_ignores_non_objects() {
+        let result = reject_unknown_keys(true, &serde_json::json!(["not", "an", "object"]), &[]);
+        assert!(result.is_ok());
+    }
+
+    // enforce_session_required tests
+
+    #[test]
+    fn test_enforce_session_required_lenient_by_default() {
+        assert!(enforce_session_required(false, "graph", None).is_ok());
+        assert!(enforce_session_required(false, "graph", Some("sess-1")).is_ok());
+    }
+
+    #[test]
+    fn test_enforce_session_required_strict_rejects_absent_or_blank() {
+        let absent = enforce_session_required(true, "timeline", None);
+        let blank = enforce_session_required(true, "timeline", Some("   "));
+        let present = enforce_session_required(true, "timeline", Some("sess-1"));
+
+        assert!(matches!(absent, Err(ModeError::SessionRequired)));
+        assert!(matches!(blank, Err(ModeError::SessionRequired)));
+        assert!(present.is_ok());
+    }
+
+    // self-correction helper tests
+
+    #[test]
+    fn test_correction_eligible_covers_parse_errors_only() {
+        assert!(correction_eligible(&ModeError::JsonParseFailed {
+            message: "bad".to_string(),
+        }));
+        assert!(correction_eligible(&ModeError::MissingField {
+            field: "analysis".to_string(),
+        }));
+        assert!(correction_eligible(&ModeError::InvalidValue {
+            field: "confidence".to_string(),
+            reason: "out of range".to_string(),
+        }));
+        assert!(!correction_eligible(&ModeError::ApiUnavailable {
+            message: "down".to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_build_correction_message_includes_error_and_output() {
+        let error = ModeError::MissingField {
+            field: "analysis".to_string(),
+        };
+        let message = build_correction_message("{\"confidence\": 0.5}", &error);
+        assert!(message.contains("could not be parsed"));
+        assert!(message.contains("analysis"));
+        assert!(message.contains("{\"confidence\": 0.5}"));
+    }
+
+    #[test]
+    fn test_build_correction_message_truncates_long_output() {
+        let error = ModeError::MissingField {
+            field: "analysis".to_string(),
+        };
+        let long_output = "x".repeat(MAX_CORRECTION_OUTPUT_CHARS + 100);
+        let message = build_correction_message(&long_output, &error);
+        assert!(message.len() < long_output.len() + 500);
+    }
+
+    // language_instruction tests
+
+    #[test]
+    fn test_language_instruction_empty_when_unconfigured() {
+        assert_eq!(language_instruction(None, "content"), "");
+        assert_eq!(
+            append_language_instruction("prompt", None, "content"),
+            "prompt"
+        );
+    }
+
+    #[test]
+    fn test_language_instruction_names_language_and_pins_keys() {
+        let instruction = language_instruction(Some("Spanish"), "content");
+
+        assert!(instruction.contains("Spanish"), "{instruction}");
+        assert!(
+            instruction.contains("JSON keys"),
+            "pins keys to English: {instruction}"
+        );
+    }
+
+    #[test]
+    fn test_language_instruction_blank_language_adds_nothing() {
+        assert_eq!(language_instruction(Some("   "), "content"), "");
+        assert_eq!(language_instruction(Some(""), "content"), "");
+    }
+
+    #[test]
+    fn test_append_language_instruction_appends_after_prompt() {
+        let prompt = append_language_instruction("Analyze this.", Some("French"), "content");
+        assert!(prompt.starts_with("Analyze this."), "{prompt}");
+        assert!(prompt.contains("French"), "{prompt}");
+    }
+
+    #[test]
+    fn test_language_instruction_auto_matches_detected_language() {
+        let instruction = language_instruction(
+            Some("auto"),
+            "El problema es que la base de datos no responde a las consultas",
+        );
+
+        assert!(instruction.contains("Spanish"), "{instruction}");
+    }
+
+    #[test]
+    fn test_language_instruction_auto_uncertain_uses_fallback() {
+        // Gibberish defeats detection: `auto:<Fallback>` names the language
+        // used instead, while bare `auto` adds no instruction at all.
+        let with_fallback = language_instruction(Some("auto:German"), "foo bar baz qux");
+        let without_fallback = language_instruction(Some("auto"), "foo bar baz qux");
+
+        assert!(with_fallback.contains("German"), "{with_fallback}");
+        assert_eq!(without_fallback, "");
+    }
+
+    #[test]
+    fn test_language_instruction_auto_detects_cyrillic() {
+        let instruction =
+            language_instruction(Some("auto"), "Почему сервер не отвечает на запросы");
+
+        assert!(instruction.contains("Russian"), "{instruction}");
+    }
+
+    // Raw IO capture tests (LINEAR_STORE_RAW_IO)
+
+    #[test]
+    fn test_raw_exchange_capture_serializes_messages() {
+        let messages = vec![crate::traits::Message::user("Analyze this")];
+        let exchange = RawExchange::capture(&messages, "{\"analysis\": \"done\"}");
+
+        let parsed: serde_json::Value =
+            serde_json::from_str(&exchange.messages_json).expect("valid JSON");
+        assert_eq!(parsed[0]["role"], "user");
+        assert_eq!(parsed[0]["content"], "Analyze this");
+        assert_eq!(exchange.raw_completion, "{\"analysis\": \"done\"}");
+    }
+
+    #[test]
+    fn test_redact_api_keys_masks_every_token() {
+        let text = "key sk-ant-api03-abc_123 and again sk-ant-xyz, done";
+        let redacted = redact_api_keys(text);
+        assert_eq!(
+            redacted,
+            "key sk-ant-[REDACTED] and again sk-ant-[REDACTED], done"
+        );
+    }
+
+    #[test]
+    fn test_redact_api_keys_leaves_plain_text_alone() {
+        let text = "no secrets here";
+        assert_eq!(redact_api_keys(text), text);
+    }
+
+    #[test]
+    fn test_cap_raw_io_truncates_with_marker() {
+        let long = "x".repeat(MAX_RAW_IO_CHARS + 10);
+        let capped = cap_raw_io(&long);
+        assert!(capped.ends_with(RAW_IO_TRUNCATION_MARKER));
+        assert_eq!(
+            capped.chars().count(),
+            MAX_RAW_IO_CHARS + RAW_IO_TRUNCATION_MARKER.chars().count()
+        );
+
+        let short = "short";
+        assert_eq!(cap_raw_io(short), short);
+    }
+
+    #[tokio::test]
+    async fn test_persist_raw_io_stores_redacted_exchange() {
+        let storage = crate::storage::SqliteStorage::new_in_memory()
+            .await
+            .expect("create storage");
+        let exchange = RawExchange::capture(
+            &[crate::traits::Message::user("my key is sk-ant-oops123")],
+            "raw output",
+        );
+
+        persist_raw_io(&storage, "thought-1", &exchange).await;
+
+        let stored = storage
+            .get_thought_raw_io("thought-1")
+            .await
+            .expect("get")
+            .expect("recorded");
+        assert!(stored.messages_json.contains("sk-ant-[REDACTED]"));
+        assert!(!stored.messages_json.contains("sk-ant-oops123"));
+        assert_eq!(stored.raw_completion, "raw output");
+    }
 }
@@ -11,6 +11,7 @@
 //! - `causal_model`: Nodes, edges, and confounders
 //! - `analysis`: Three-level analysis (association, intervention, counterfactual)
 //! - `conclusions`: Causal claims with strength and caveats
+//! - `consequence_chains`: Per-assumption downstream consequence traces
 
 #![allow(clippy::missing_const_for_fn)]
 
@@ -20,7 +21,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::anthropic::StreamAccumulator;
 use crate::error::ModeError;
-use crate::modes::{extract_json, generate_thought_id, validate_content};
+use crate::modes::{
+    apply_memory_update, extract_json, generate_thought_id, language_instruction,
+    load_working_memory_block, reject_unknown_keys, validate_content, ModeFlags,
+};
 use crate::prompts::counterfactual_prompt;
 use crate::server::{ProgressMilestone, ProgressReporter};
 use crate::traits::{
@@ -181,6 +185,20 @@ pub struct CausalConclusions {
     pub actionable_insight: String,
 }
 
+/// The chain of downstream consequences following from one changed assumption.
+///
+/// Steps are ordered first-order effect first; an assumption with no
+/// downstream consequences has an empty `steps` list.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConsequenceChain {
+    /// The assumption that was changed in the counterfactual.
+    pub assumption: String,
+    /// Downstream consequences in causal order (first-order effect first).
+    pub steps: Vec<String>,
+    /// Confidence that the chain holds end to end (0.0-1.0).
+    pub confidence: f64,
+}
+
 /// Response from counterfactual analysis.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CounterfactualResponse {
@@ -196,6 +214,9 @@ pub struct CounterfactualResponse {
     pub analysis: CausalAnalysis,
     /// Conclusions and recommendations.
     pub conclusions: CausalConclusions,
+    /// Per-assumption ripple effects of the intervention.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub consequence_chains: Vec<ConsequenceChain>,
 }
 
 impl CounterfactualResponse {
@@ -218,8 +239,16 @@ impl CounterfactualResponse {
             causal_model,
             analysis,
             conclusions,
+            consequence_chains: Vec::new(),
         }
     }
+
+    /// Attach the per-assumption consequence chains.
+    #[must_use]
+    pub fn with_consequence_chains(mut self, chains: Vec<ConsequenceChain>) -> Self {
+        self.consequence_chains = chains;
+        self
+    }
 }
 
 // ============================================================================
@@ -236,6 +265,11 @@ where
 {
     storage: S,
     client: C,
+    /// Behavior flags injected from server configuration (all off by default).
+    flags: ModeFlags,
+    /// Effective output language (per-request override merged with the
+    /// configured default at the server boundary).
+    language: Option<String>,
 }
 
 impl<S, C> CounterfactualMode<S, C>
@@ -246,7 +280,29 @@ where
     /// Create a new counterfactual mode instance.
     #[must_use]
     pub fn new(storage: S, client: C) -> Self {
-        Self { storage, client }
+        Self {
+            storage,
+            client,
+            flags: ModeFlags::default(),
+            language: None,
+        }
+    }
+
+    /// Set the output language for natural-language response fields. The
+    /// server passes the per-request override merged with the configured
+    /// `RESPONSE_LANGUAGE` default; `None` adds no language instruction.
+    #[must_use]
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Inject the server's behavior flags ([`ModeFlags`]). A mode built
+    /// without them — as in most tests — uses the all-off defaults.
+    #[must_use]
+    pub fn with_flags(mut self, flags: ModeFlags) -> Self {
+        self.flags = flags;
+        self
     }
 
     /// Perform counterfactual causal analysis.
@@ -276,17 +332,32 @@ where
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("counterfactual")
             .with_max_tokens(32768)
             .with_temperature(0.3)
             .with_maximum_thinking();
 
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
+        apply_memory_update(&self.storage, &session.id, &json).await;
+
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &[
+                "causal_question",
+                "causal_model",
+                "analysis",
+                "conclusions",
+                "consequence_chains",
+            ],
+        )?;
 
         let causal_question = Self::parse_causal_question(&json)?;
         let causal_model = Self::parse_causal_model(&json)?;
         let analysis = Self::parse_analysis(&json)?;
         let conclusions = Self::parse_conclusions(&json)?;
+        let consequence_chains = Self::parse_consequence_chains(&json)?;
 
         let thought_id = generate_thought_id();
         let thought = Thought::new(
@@ -316,7 +387,8 @@ where
             causal_model,
             analysis,
             conclusions,
-        ))
+        )
+        .with_consequence_chains(consequence_chains))
     }
 
     /// Perform counterfactual causal analysis using streaming.
@@ -354,6 +426,7 @@ where
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("counterfactual")
             .with_max_tokens(32768)
             .with_temperature(0.3)
             .with_maximum_thinking();
@@ -380,11 +453,25 @@ where
 
         let response_text = accumulator.text();
         let json = extract_json(&response_text)?;
+        apply_memory_update(&self.storage, &session.id, &json).await;
+
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &[
+                "causal_question",
+                "causal_model",
+                "analysis",
+                "conclusions",
+                "consequence_chains",
+            ],
+        )?;
 
         let causal_question = Self::parse_causal_question(&json)?;
         let causal_model = Self::parse_causal_model(&json)?;
         let analysis = Self::parse_analysis(&json)?;
         let conclusions = Self::parse_conclusions(&json)?;
+        let consequence_chains = Self::parse_consequence_chains(&json)?;
 
         let thought_id = generate_thought_id();
         let thought = Thought::new(
@@ -418,7 +505,8 @@ where
             causal_model,
             analysis,
             conclusions,
-        ))
+        )
+        .with_consequence_chains(consequence_chains))
     }
 
     // ========================================================================
@@ -480,17 +568,27 @@ where
         session_id: &str,
         has_prior_session: bool,
     ) -> String {
-        let prior_context = if has_prior_session {
-            self.load_prior_context(session_id).await
+        let (prior_context, memory_block) = if has_prior_session {
+            (
+                self.load_prior_context(session_id).await,
+                load_working_memory_block(&self.storage, session_id).await,
+            )
         } else {
-            String::new()
+            (String::new(), String::new())
         };
 
-        if prior_context.is_empty() {
-            format!("{prompt}\n\nCausal question to analyze:\n{content}")
-        } else {
-            format!("{prompt}\n\n{prior_context}\nCausal question to analyze:\n{content}")
+        let mut message = format!(
+            "{prompt}{}\n",
+            language_instruction(self.language.as_deref(), content)
+        );
+        if !memory_block.is_empty() {
+            let _ = write!(message, "\n{memory_block}");
         }
+        if !prior_context.is_empty() {
+            let _ = write!(message, "\n{prior_context}");
+        }
+        let _ = write!(message, "\nCausal question to analyze:\n{content}");
+        message
     }
 
     fn parse_causal_question(json: &serde_json::Value) -> Result<CausalQuestion, ModeError> {
@@ -673,6 +771,41 @@ where
         })
     }
 
+    /// Parse the optional `consequence_chains` section. The model may omit it
+    /// entirely (older prompt outputs), which parses as no chains; present
+    /// entries must carry an assumption and an ordered steps array (empty when
+    /// the assumption has no downstream consequences).
+    fn parse_consequence_chains(
+        json: &serde_json::Value,
+    ) -> Result<Vec<ConsequenceChain>, ModeError> {
+        let Some(chains) = json.get("consequence_chains") else {
+            return Ok(Vec::new());
+        };
+
+        let chains = chains.as_array().ok_or_else(|| ModeError::InvalidValue {
+            field: "consequence_chains".to_string(),
+            reason: "must be an array".to_string(),
+        })?;
+
+        chains
+            .iter()
+            .map(|chain| {
+                let assumption = Self::get_str(chain, "assumption")?;
+                let steps = Self::get_string_array(chain, "steps")?;
+
+                // Use default confidence of 0.5 if not provided by the model
+                let confidence = Self::get_f64(chain, "confidence").unwrap_or(0.5);
+                let confidence = confidence.clamp(0.0, 1.0); // Ensure valid range
+
+                Ok(ConsequenceChain {
+                    assumption,
+                    steps,
+                    confidence,
+                })
+            })
+            .collect()
+    }
+
     // ========================================================================
     // Utility Helpers
     // ========================================================================
@@ -790,6 +923,9 @@ mod tests {
         });
         mock_storage.expect_save_thought().returning(|_| Ok(()));
         mock_storage.expect_get_thoughts().returning(|_| Ok(vec![]));
+        mock_storage
+            .expect_get_working_memory()
+            .returning(|_| Ok(None));
 
         let response_json = mock_counterfactual_response();
         mock_client.expect_complete().returning(move |_, _| {
@@ -1748,6 +1884,9 @@ mod tests {
                 0.7,
             )])
         });
+        mock_storage
+            .expect_get_working_memory()
+            .returning(|_| Ok(None));
         mock_storage.expect_save_thought().returning(|_| Ok(()));
 
         let response_json = mock_counterfactual_response();
@@ -1813,4 +1952,193 @@ mod tests {
         assert_eq!(truncate_chars("12345", 5), "12345");
         assert_eq!(truncate_chars("123456789", 4), "1234…");
     }
+
+    // Consequence chain tests
+
+    fn mock_response_with_chains(chains: &str) -> String {
+        format!(
+            r#"{{
+                "causal_question": {{"statement": "S", "ladder_rung": "counterfactual", "variables": {{"cause": "C", "effect": "E", "intervention": "I"}}}},
+                "causal_model": {{"nodes": [], "edges": [], "confounders": []}},
+                "analysis": {{"association_level": {{"observed_correlation": 0.5, "interpretation": "I"}}, "intervention_level": {{"causal_effect": 0.5, "mechanism": "M"}}, "counterfactual_level": {{"scenario": "S", "outcome": "O", "confidence": 0.5}}}},
+                "conclusions": {{"causal_claim": "C", "strength": "moderate", "caveats": [], "actionable_insight": "A"}},
+                "consequence_chains": {chains}
+            }}"#
+        )
+    }
+
+    fn mode_returning<Sf>(
+        response_json: String,
+        storage_setup: Sf,
+    ) -> CounterfactualMode<MockStorageTrait, MockAnthropicClientTrait>
+    where
+        Sf: FnOnce(&mut MockStorageTrait),
+    {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("test-session")));
+        storage_setup(&mut mock_storage);
+
+        mock_client.expect_complete().returning(move |_, _| {
+            Ok(CompletionResponse::new(
+                response_json.clone(),
+                Usage::new(50, 100),
+            ))
+        });
+
+        CounterfactualMode::new(mock_storage, mock_client)
+    }
+
+    #[tokio::test]
+    async fn test_analyze_parses_consequence_chains_in_order() {
+        let chains = r#"[
+            {"assumption": "Treatment withheld", "steps": ["Inflammation persists", "Organ function declines", "Survival probability drops"], "confidence": 0.8},
+            {"assumption": "Severity unchanged", "steps": [], "confidence": 0.9}
+        ]"#;
+        let mode = mode_returning(mock_response_with_chains(chains), |s| {
+            s.expect_save_thought().returning(|_| Ok(()));
+        });
+
+        let response = mode.analyze("Test", None).await.expect("Should succeed");
+
+        assert_eq!(response.consequence_chains.len(), 2);
+        let first = &response.consequence_chains[0];
+        assert_eq!(first.assumption, "Treatment withheld");
+        assert_eq!(
+            first.steps,
+            vec![
+                "Inflammation persists",
+                "Organ function declines",
+                "Survival probability drops"
+            ]
+        );
+        assert!((first.confidence - 0.8).abs() < f64::EPSILON);
+        // An assumption with no downstream consequences keeps an empty chain.
+        let second = &response.consequence_chains[1];
+        assert_eq!(second.assumption, "Severity unchanged");
+        assert!(second.steps.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_analyze_consequence_chain_confidence_bounds() {
+        let chains = r#"[
+            {"assumption": "A", "steps": ["S"], "confidence": 1.7},
+            {"assumption": "B", "steps": ["S"], "confidence": -0.2},
+            {"assumption": "C", "steps": ["S"]}
+        ]"#;
+        let mode = mode_returning(mock_response_with_chains(chains), |s| {
+            s.expect_save_thought().returning(|_| Ok(()));
+        });
+
+        let response = mode.analyze("Test", None).await.expect("Should succeed");
+
+        let confidences: Vec<f64> = response
+            .consequence_chains
+            .iter()
+            .map(|c| c.confidence)
+            .collect();
+        // Clamped above, clamped below, defaulted when missing.
+        assert_eq!(confidences, vec![1.0, 0.0, 0.5]);
+    }
+
+    #[tokio::test]
+    async fn test_analyze_missing_consequence_chains_is_empty() {
+        let mode = mode_returning(mock_counterfactual_response(), |s| {
+            s.expect_save_thought().returning(|_| Ok(()));
+            s.expect_get_thoughts().returning(|_| Ok(vec![]));
+            s.expect_get_working_memory().returning(|_| Ok(None));
+        });
+
+        let response = mode.analyze("Test", None).await.expect("Should succeed");
+        assert!(response.consequence_chains.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_analyze_consequence_chain_missing_assumption() {
+        let chains = r#"[{"steps": ["S"], "confidence": 0.5}]"#;
+        let mode = mode_returning(mock_response_with_chains(chains), |_| {});
+
+        let result = mode.analyze("Test", None).await;
+        assert!(matches!(result, Err(ModeError::MissingField { field }) if field == "assumption"));
+    }
+
+    #[tokio::test]
+    async fn test_analyze_consequence_chains_not_an_array() {
+        let mode = mode_returning(mock_response_with_chains(r#""not-a-list""#), |_| {});
+
+        let result = mode.analyze("Test", None).await;
+        assert!(
+            matches!(result, Err(ModeError::InvalidValue { field, .. }) if field == "consequence_chains")
+        );
+    }
+
+    #[test]
+    fn test_consequence_chain_serialization_roundtrip() {
+        let chain = ConsequenceChain {
+            assumption: "A".to_string(),
+            steps: vec!["first".to_string(), "second".to_string()],
+            confidence: 0.6,
+        };
+        let json = serde_json::to_string(&chain).unwrap();
+        let back: ConsequenceChain = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, chain);
+    }
+
+    #[test]
+    fn test_counterfactual_response_omits_empty_chains() {
+        let response = CounterfactualResponse::new(
+            "t-1",
+            "s-1",
+            CausalQuestion {
+                statement: "Test".to_string(),
+                ladder_rung: LadderRung::Counterfactual,
+                variables: CausalVariables {
+                    cause: "C".to_string(),
+                    effect: "E".to_string(),
+                    intervention: "I".to_string(),
+                },
+            },
+            CausalModel {
+                nodes: vec![],
+                edges: vec![],
+                confounders: vec![],
+            },
+            CausalAnalysis {
+                association_level: AssociationLevel {
+                    observed_correlation: 0.5,
+                    interpretation: "I".to_string(),
+                },
+                intervention_level: InterventionLevel {
+                    causal_effect: 0.5,
+                    mechanism: "M".to_string(),
+                },
+                counterfactual_level: CounterfactualLevel {
+                    scenario: "S".to_string(),
+                    outcome: "O".to_string(),
+                    confidence: 0.7,
+                },
+            },
+            CausalConclusions {
+                causal_claim: "C".to_string(),
+                strength: CausalStrength::Moderate,
+                caveats: vec![],
+                actionable_insight: "A".to_string(),
+            },
+        );
+
+        assert!(response.consequence_chains.is_empty());
+        let json = serde_json::to_value(&response).unwrap();
+        assert!(json.get("consequence_chains").is_none());
+
+        let with_chains = response.with_consequence_chains(vec![ConsequenceChain {
+            assumption: "A".to_string(),
+            steps: vec![],
+            confidence: 0.5,
+        }]);
+        let json = serde_json::to_value(&with_chains).unwrap();
+        assert_eq!(json["consequence_chains"][0]["assumption"], "A");
+    }
 }
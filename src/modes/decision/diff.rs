@@ -0,0 +1,328 @@
+//! Structured diffing of two weighted decision analyses.
+//!
+//! Rerunning a decision after changing criteria often flips the ranking, and
+//! eyeballing two result payloads makes the flip easy to miss.
+//! [`diff_decisions`] compares two [`WeightedResponse`]s and reports rank
+//! changes per option, score deltas, and criteria weight changes, handling
+//! options that are present in only one of the analyses.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::types::WeightedResponse;
+
+/// Weight difference below which a criterion counts as unchanged.
+const WEIGHT_EPSILON: f64 = 1e-9;
+
+/// Rank and score movement of a single option between two analyses.
+///
+/// Fields are `None` on the side where the option is absent, so an option
+/// that was added or removed still gets an entry with its known half filled.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OptionDiff {
+    /// Option name.
+    pub option: String,
+    /// Rank in the first analysis (1 = best), if present there.
+    pub rank_a: Option<u32>,
+    /// Rank in the second analysis (1 = best), if present there.
+    pub rank_b: Option<u32>,
+    /// Rank movement: positive means the option improved (moved toward
+    /// rank 1) in the second analysis. `None` when absent from either side.
+    pub rank_delta: Option<i64>,
+    /// Weighted score in the first analysis, if present there.
+    pub score_a: Option<f64>,
+    /// Weighted score in the second analysis, if present there.
+    pub score_b: Option<f64>,
+    /// Score movement (`score_b - score_a`). `None` when absent from either.
+    pub score_delta: Option<f64>,
+}
+
+/// Weight movement of a single criterion between two analyses.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CriterionWeightChange {
+    /// Criterion name.
+    pub name: String,
+    /// Weight in the first analysis, if the criterion exists there.
+    pub weight_a: Option<f64>,
+    /// Weight in the second analysis, if the criterion exists there.
+    pub weight_b: Option<f64>,
+    /// Weight movement (`weight_b - weight_a`). `None` when the criterion
+    /// only exists on one side.
+    pub delta: Option<f64>,
+}
+
+/// Structured comparison of two weighted decision analyses.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DecisionDiff {
+    /// Per-option movements. Ordered by rank in the second analysis, with
+    /// options that only appear in the first analysis appended in their
+    /// original rank order.
+    pub options: Vec<OptionDiff>,
+    /// Criteria whose weight changed, appeared, or disappeared. Criteria
+    /// with identical weights on both sides are omitted.
+    pub criteria_changes: Vec<CriterionWeightChange>,
+    /// Options present in the second analysis but not the first.
+    pub added_options: Vec<String>,
+    /// Options present in the first analysis but not the second.
+    pub removed_options: Vec<String>,
+    /// True when the top-ranked option differs between the analyses.
+    pub winner_changed: bool,
+}
+
+/// Compare two weighted decision analyses and report what moved.
+///
+/// Ranks and scores are taken from each response's `ranking` (the verified
+/// values), not the raw model output. The diff is purely structural — no API
+/// calls and no storage access.
+#[must_use]
+pub fn diff_decisions(a: &WeightedResponse, b: &WeightedResponse) -> DecisionDiff {
+    let by_option = |resp: &WeightedResponse| -> HashMap<String, (u32, f64)> {
+        resp.ranking
+            .iter()
+            .map(|r| (r.option.clone(), (r.rank, r.score)))
+            .collect()
+    };
+    let in_a = by_option(a);
+    let in_b = by_option(b);
+
+    // Ordered by rank in `b`, then options only `a` knows in their own order.
+    let mut options = Vec::with_capacity(in_a.len().max(in_b.len()));
+    for ranked in &b.ranking {
+        options.push(option_diff(
+            &ranked.option,
+            in_a.get(&ranked.option).copied(),
+            Some((ranked.rank, ranked.score)),
+        ));
+    }
+    for ranked in &a.ranking {
+        if !in_b.contains_key(&ranked.option) {
+            options.push(option_diff(
+                &ranked.option,
+                Some((ranked.rank, ranked.score)),
+                None,
+            ));
+        }
+    }
+
+    let added_options = options
+        .iter()
+        .filter(|o| o.rank_a.is_none())
+        .map(|o| o.option.clone())
+        .collect();
+    let removed_options = options
+        .iter()
+        .filter(|o| o.rank_b.is_none())
+        .map(|o| o.option.clone())
+        .collect();
+
+    let winner_changed =
+        a.ranking.first().map(|r| &r.option) != b.ranking.first().map(|r| &r.option);
+
+    DecisionDiff {
+        options,
+        criteria_changes: criteria_changes(a, b),
+        added_options,
+        removed_options,
+        winner_changed,
+    }
+}
+
+/// Build one [`OptionDiff`] from the option's `(rank, score)` on each side.
+fn option_diff(option: &str, in_a: Option<(u32, f64)>, in_b: Option<(u32, f64)>) -> OptionDiff {
+    let (rank_a, score_a) = in_a.map_or((None, None), |(r, s)| (Some(r), Some(s)));
+    let (rank_b, score_b) = in_b.map_or((None, None), |(r, s)| (Some(r), Some(s)));
+    OptionDiff {
+        option: option.to_string(),
+        rank_a,
+        rank_b,
+        // A drop in rank number is an improvement, so delta is a - b.
+        rank_delta: rank_a
+            .zip(rank_b)
+            .map(|(ra, rb)| i64::from(ra) - i64::from(rb)),
+        score_a,
+        score_b,
+        score_delta: score_a.zip(score_b).map(|(sa, sb)| sb - sa),
+    }
+}
+
+/// Criteria whose weight moved, appeared, or disappeared between `a` and `b`.
+fn criteria_changes(a: &WeightedResponse, b: &WeightedResponse) -> Vec<CriterionWeightChange> {
+    let weights_b: HashMap<&str, f64> = b
+        .criteria
+        .iter()
+        .map(|c| (c.name.as_str(), c.weight))
+        .collect();
+    let weights_a: HashMap<&str, f64> = a
+        .criteria
+        .iter()
+        .map(|c| (c.name.as_str(), c.weight))
+        .collect();
+
+    let mut changes = Vec::new();
+    for criterion in &a.criteria {
+        match weights_b.get(criterion.name.as_str()) {
+            Some(&new_weight) if (new_weight - criterion.weight).abs() <= WEIGHT_EPSILON => {}
+            Some(&new_weight) => changes.push(CriterionWeightChange {
+                name: criterion.name.clone(),
+                weight_a: Some(criterion.weight),
+                weight_b: Some(new_weight),
+                delta: Some(new_weight - criterion.weight),
+            }),
+            None => changes.push(CriterionWeightChange {
+                name: criterion.name.clone(),
+                weight_a: Some(criterion.weight),
+                weight_b: None,
+                delta: None,
+            }),
+        }
+    }
+    for criterion in &b.criteria {
+        if !weights_a.contains_key(criterion.name.as_str()) {
+            changes.push(CriterionWeightChange {
+                name: criterion.name.clone(),
+                weight_a: None,
+                weight_b: Some(criterion.weight),
+                delta: None,
+            });
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp,
+    clippy::approx_constant,
+    clippy::unreadable_literal
+)]
+mod tests {
+    use super::super::types::{Criterion, RankedOption};
+    use super::*;
+
+    fn criterion(name: &str, weight: f64) -> Criterion {
+        Criterion {
+            name: name.to_string(),
+            weight,
+            description: format!("{name} criterion"),
+        }
+    }
+
+    fn response(ranking: &[(&str, f64, u32)], criteria: Vec<Criterion>) -> WeightedResponse {
+        let options = ranking.iter().map(|(o, _, _)| (*o).to_string()).collect();
+        let ranking = ranking
+            .iter()
+            .map(|(option, score, rank)| RankedOption {
+                option: (*option).to_string(),
+                score: *score,
+                rank: *rank,
+            })
+            .collect();
+        WeightedResponse::new(
+            "t",
+            "s",
+            options,
+            criteria,
+            HashMap::new(),
+            HashMap::new(),
+            ranking,
+            "notes",
+        )
+    }
+
+    #[test]
+    fn test_diff_identifies_rank_flip_and_deltas() {
+        let a = response(
+            &[("Alpha", 0.8, 1), ("Beta", 0.6, 2)],
+            vec![criterion("cost", 0.5), criterion("speed", 0.5)],
+        );
+        let b = response(
+            &[("Beta", 0.75, 1), ("Alpha", 0.7, 2)],
+            vec![criterion("cost", 0.5), criterion("speed", 0.5)],
+        );
+
+        let diff = diff_decisions(&a, &b);
+
+        assert!(diff.winner_changed);
+        assert!(diff.added_options.is_empty());
+        assert!(diff.removed_options.is_empty());
+        assert!(diff.criteria_changes.is_empty());
+
+        // Ordered by rank in `b`: Beta first.
+        assert_eq!(diff.options[0].option, "Beta");
+        assert_eq!(diff.options[0].rank_delta, Some(1));
+        assert!((diff.options[0].score_delta.unwrap() - 0.15).abs() < 1e-9);
+        assert_eq!(diff.options[1].option, "Alpha");
+        assert_eq!(diff.options[1].rank_delta, Some(-1));
+        assert!((diff.options[1].score_delta.unwrap() + 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_diff_reports_criteria_weight_changes() {
+        let a = response(
+            &[("Alpha", 0.8, 1)],
+            vec![criterion("cost", 0.5), criterion("speed", 0.5)],
+        );
+        let b = response(
+            &[("Alpha", 0.7, 1)],
+            vec![criterion("cost", 0.3), criterion("risk", 0.7)],
+        );
+
+        let diff = diff_decisions(&a, &b);
+        assert!(!diff.winner_changed);
+        assert_eq!(diff.criteria_changes.len(), 3);
+
+        let cost = &diff.criteria_changes[0];
+        assert_eq!(cost.name, "cost");
+        assert!((cost.delta.unwrap() + 0.2).abs() < 1e-9);
+
+        // "speed" was dropped; "risk" is new. One-sided entries carry no delta.
+        let speed = &diff.criteria_changes[1];
+        assert_eq!((speed.name.as_str(), speed.weight_b), ("speed", None));
+        assert!(speed.delta.is_none());
+        let risk = &diff.criteria_changes[2];
+        assert_eq!((risk.name.as_str(), risk.weight_a), ("risk", None));
+        assert_eq!(risk.weight_b, Some(0.7));
+    }
+
+    #[test]
+    fn test_diff_handles_added_and_removed_options() {
+        let a = response(&[("Alpha", 0.8, 1), ("Beta", 0.6, 2)], vec![]);
+        let b = response(&[("Alpha", 0.9, 1), ("Gamma", 0.5, 2)], vec![]);
+
+        let diff = diff_decisions(&a, &b);
+
+        assert_eq!(diff.added_options, vec!["Gamma"]);
+        assert_eq!(diff.removed_options, vec!["Beta"]);
+        assert!(!diff.winner_changed);
+
+        // Removed options come last and keep their known half.
+        let beta = diff.options.last().unwrap();
+        assert_eq!(beta.option, "Beta");
+        assert_eq!(beta.rank_a, Some(2));
+        assert!(beta.rank_b.is_none());
+        assert!(beta.rank_delta.is_none());
+        assert!(beta.score_delta.is_none());
+
+        let gamma = &diff.options[1];
+        assert_eq!(gamma.option, "Gamma");
+        assert!(gamma.rank_a.is_none());
+        assert_eq!(gamma.score_b, Some(0.5));
+    }
+
+    #[test]
+    fn test_diff_of_identical_analyses_is_quiet() {
+        let a = response(&[("Alpha", 0.8, 1)], vec![criterion("cost", 1.0)]);
+        let diff = diff_decisions(&a, &a.clone());
+
+        assert!(!diff.winner_changed);
+        assert!(diff.criteria_changes.is_empty());
+        assert!(diff.added_options.is_empty());
+        assert!(diff.removed_options.is_empty());
+        assert_eq!(diff.options.len(), 1);
+        assert_eq!(diff.options[0].rank_delta, Some(0));
+        assert_eq!(diff.options[0].score_delta, Some(0.0));
+    }
+}
@@ -6,6 +6,10 @@
 //! - `topsis`: TOPSIS ideal solution method
 //! - `perspectives`: Multi-stakeholder perspective analysis
 //!
+//! A persisted weighted analysis can additionally be stress-tested with
+//! [`DecisionMode::challenge`], which questions the assumptions behind its
+//! criteria weights.
+//!
 //! # Output Schema
 //!
 //! ## Weighted Operation
@@ -27,24 +31,34 @@
 //! - `conflicts`: Areas of disagreement
 //! - `balanced_recommendation`: Synthesized recommendation
 
+mod diff;
+mod normalize;
 mod parsing;
 mod types;
 mod verify;
 
+pub use diff::{diff_decisions, CriterionWeightChange, DecisionDiff, OptionDiff};
+pub use normalize::Normalization;
 pub use types::{
-    Alignment, BalancedRecommendation, Conflict, ConflictSeverity, Criterion, CriterionType,
-    DecisionValidation, InfluenceLevel, PairwiseComparison, PairwiseRank, PairwiseResponse,
-    PerspectivesResponse, PreferenceResult, PreferenceStrength, RankedOption, Stakeholder,
-    TopsisCreterion, TopsisDistances, TopsisRank, TopsisResponse, WeightedResponse,
+    minority_reports, Alignment, BalancedRecommendation, ChallengeResponse, Conflict,
+    ConflictMatrix, ConflictSeverity, Criterion, CriterionJustification, CriterionType,
+    DecisionValidation, InfluenceLevel, MinorityReport, PairwiseComparison, PairwiseRank,
+    PairwiseResponse, PerspectivesResponse, PreferenceResult, PreferenceStrength, RankedOption,
+    Stakeholder, TopsisCreterion, TopsisDistances, TopsisRank, TopsisResponse, WeightChallenge,
+    WeightedResponse,
 };
 
 use std::fmt::Write as _;
 
 use crate::error::ModeError;
-use crate::modes::{extract_json, generate_thought_id, validate_content};
+use crate::modes::{
+    apply_memory_update, extract_json, generate_thought_id, language_instruction,
+    load_working_memory_block, parse_assumptions, persist_assumptions, reject_unknown_keys,
+    validate_content, ModeFlags,
+};
 use crate::prompts::{
-    decision_pairwise_prompt, decision_perspectives_prompt, decision_topsis_prompt,
-    decision_weighted_prompt,
+    decision_challenge_prompt, decision_pairwise_prompt, decision_perspectives_prompt,
+    decision_topsis_prompt, decision_weighted_justification_addendum, decision_weighted_prompt,
 };
 use crate::traits::{
     AnthropicClientTrait, CompletionConfig, Message, Session, StorageTrait, Thought,
@@ -65,6 +79,17 @@ where
 {
     storage: S,
     client: C,
+    /// Behavior flags injected from server configuration (all off by default).
+    flags: ModeFlags,
+    /// Effective output language (per-request override merged with the
+    /// configured default at the server boundary).
+    language: Option<String>,
+    /// Raw normalization strategy for weighted scoring, parsed (and rejected
+    /// if unknown) when `weighted` runs.
+    normalization: Option<String>,
+    /// When true, `weighted` requests an evidence-based justification and
+    /// confidence for every criterion weight.
+    justify_weights: bool,
 }
 
 impl<S, C> DecisionMode<S, C>
@@ -75,7 +100,51 @@ where
     /// Create a new decision mode instance.
     #[must_use]
     pub fn new(storage: S, client: C) -> Self {
-        Self { storage, client }
+        Self {
+            storage,
+            client,
+            flags: ModeFlags::default(),
+            language: None,
+            normalization: None,
+            justify_weights: false,
+        }
+    }
+
+    /// Set the output language for natural-language response fields. The
+    /// server passes the per-request override merged with the configured
+    /// `RESPONSE_LANGUAGE` default; `None` adds no language instruction.
+    #[must_use]
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Inject the server's behavior flags ([`ModeFlags`]). A mode built
+    /// without them — as in most tests — uses the all-off defaults.
+    #[must_use]
+    pub fn with_flags(mut self, flags: ModeFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Set the normalization strategy `weighted` applies to the score matrix
+    /// before computing totals (`none`, `min_max`, or `z_score`). `None` keeps
+    /// the default of using scores as-is; an unknown value makes `weighted`
+    /// fail before any API call.
+    #[must_use]
+    pub fn with_normalization(mut self, normalization: Option<String>) -> Self {
+        self.normalization = normalization;
+        self
+    }
+
+    /// Request an evidence-based justification and confidence for every
+    /// criterion weight `weighted` produces. The response then carries one
+    /// [`CriterionJustification`] per criterion, and the linkage is persisted
+    /// so a report can show why each weight was chosen.
+    #[must_use]
+    pub const fn with_weight_justifications(mut self, justify: bool) -> Self {
+        self.justify_weights = justify;
+        self
     }
 
     /// Perform weighted multi-criteria analysis.
@@ -94,14 +163,30 @@ where
         session_id: Option<String>,
     ) -> Result<WeightedResponse, ModeError> {
         validate_content(content)?;
+        // Parse the normalization strategy up front so a bad value fails
+        // before any API call.
+        let normalization: Normalization = match self.normalization.as_deref() {
+            Some(s) => s.parse()?,
+            None => Normalization::None,
+        };
 
         let has_prior_session = session_id.is_some();
         let session = self.get_or_create_session(session_id).await?;
 
-        let prompt = decision_weighted_prompt();
+        // The justification addendum is opt-in: the base prompt stays
+        // byte-identical when weight justifications are off.
+        let prompt = if self.justify_weights {
+            format!(
+                "{}\n\n{}",
+                decision_weighted_prompt(),
+                decision_weighted_justification_addendum()
+            )
+        } else {
+            decision_weighted_prompt().to_string()
+        };
         let user_message = self
             .build_user_message(
-                prompt,
+                &prompt,
                 content,
                 &session.id,
                 has_prior_session,
@@ -111,23 +196,62 @@ where
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("decision")
             .with_max_tokens(16384)
             .with_temperature(0.3)
             .with_deep_thinking();
 
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
-
+        apply_memory_update(&self.storage, &session.id, &json).await;
+
+        // Surface and persist hidden assumptions (best-effort, empty if absent).
+        let assumptions = parse_assumptions(&json);
+        persist_assumptions(&self.storage, &session.id, &assumptions).await;
+
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &[
+                "options",
+                "criteria",
+                "scores",
+                "weighted_totals",
+                "ranking",
+                "sensitivity_notes",
+                "criterion_justifications",
+            ],
+        )?;
         let options = parsing::get_string_array(&json, "options")?;
         let criteria = parsing::parse_criteria(&json)?;
-        let scores = parsing::parse_scores(&json)?;
+
+        // Align justifications to the criteria (one per criterion, missing
+        // ones tolerated as empty) and persist the linkage so a report can
+        // show why each weight was chosen.
+        let criterion_justifications = if self.justify_weights {
+            let justifications = parsing::parse_criterion_justifications(&json, &criteria);
+            self.persist_weight_justifications(&session.id, &justifications)
+                .await;
+            justifications
+        } else {
+            Vec::new()
+        };
+        let mut scores = parsing::parse_scores(&json)?;
         let mut weighted_totals = parsing::parse_weighted_totals(&json)?;
         let mut ranking = parsing::parse_weighted_ranking(&json)?;
         let sensitivity_notes = parsing::get_str(&json, "sensitivity_notes")?;
 
-        // Recompute the arithmetic and correct the ranking if the model slipped.
-        let validation =
-            verify::verify_weighted(&criteria, &scores, &mut weighted_totals, &mut ranking);
+        // Recompute the arithmetic and correct the ranking if the model
+        // slipped. When a normalization strategy is configured this also
+        // rescales the score matrix first, so the response's scores, totals,
+        // and ranking all reflect the normalized arithmetic.
+        let validation = verify::verify_weighted(
+            &criteria,
+            &mut scores,
+            &mut weighted_totals,
+            &mut ranking,
+            normalization,
+        );
 
         let thought_id = generate_thought_id();
         let best_option = ranking.first().map_or("none", |r| r.option.as_str());
@@ -157,9 +281,44 @@ where
             ranking,
             sensitivity_notes,
         )
+        .with_assumptions(assumptions)
+        .with_criterion_justifications(criterion_justifications)
         .with_validation(validation))
     }
 
+    /// Persist the criterion → evidence linkage as `weight_justification`
+    /// thoughts, mirroring how assumptions are stored: best-effort, so a
+    /// storage hiccup never discards the analysis itself.
+    async fn persist_weight_justifications(
+        &self,
+        session_id: &str,
+        justifications: &[CriterionJustification],
+    ) {
+        for justification in justifications {
+            let detail = if justification.justification.is_empty() {
+                "(no justification given)"
+            } else {
+                justification.justification.as_str()
+            };
+            let thought = Thought::new(
+                generate_thought_id(),
+                session_id,
+                format!(
+                    "Weight justification ({}): {detail}",
+                    justification.criterion
+                ),
+                "weight_justification",
+                justification.evidence_confidence,
+            );
+            if let Err(e) = self.storage.save_thought(&thought).await {
+                tracing::warn!(
+                    error = %e,
+                    "Failed to persist weight justification — reasoning result preserved"
+                );
+            }
+        }
+    }
+
     /// Perform pairwise comparison analysis.
     ///
     /// # Arguments
@@ -193,20 +352,42 @@ where
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("decision")
             .with_max_tokens(16384)
             .with_temperature(0.3)
             .with_deep_thinking();
 
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
-
+        apply_memory_update(&self.storage, &session.id, &json).await;
+
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &[
+                "comparisons",
+                "pairwise_matrix",
+                "ranking",
+                "consistency_check",
+            ],
+        )?;
         let comparisons = parsing::parse_comparisons(&json)?;
         let pairwise_matrix = parsing::parse_pairwise_matrix(&json)?;
         let mut ranking = parsing::parse_pairwise_ranking(&json)?;
         let consistency_check = parsing::get_str(&json, "consistency_check")?;
 
         // Recount wins from the comparisons and re-derive the ranking.
-        let validation = verify::verify_pairwise(&comparisons, &mut ranking);
+        let mut validation = verify::verify_pairwise(&comparisons, &mut ranking);
+
+        // Numeric transitivity measure alongside the model's narrative check.
+        let consistency_ratio = verify::compute_pairwise_consistency(&comparisons);
+        if consistency_ratio < verify::MIN_RELIABLE_CONSISTENCY {
+            validation.consistent = false;
+            validation.warnings.push(format!(
+                "Only {:.0}% of preference triples are transitive; the ranking is unreliable.",
+                consistency_ratio * 100.0
+            ));
+        }
 
         let thought_id = generate_thought_id();
         let thought = Thought::new(
@@ -229,6 +410,7 @@ where
             ranking,
             consistency_check,
         )
+        .with_consistency_ratio(consistency_ratio)
         .with_validation(validation))
     }
 
@@ -265,13 +447,28 @@ where
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("decision")
             .with_max_tokens(16384)
             .with_temperature(0.3)
             .with_deep_thinking();
 
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
-
+        apply_memory_update(&self.storage, &session.id, &json).await;
+
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &[
+                "criteria",
+                "decision_matrix",
+                "ideal_solution",
+                "anti_ideal_solution",
+                "distances",
+                "relative_closeness",
+                "ranking",
+            ],
+        )?;
         let criteria = parsing::parse_topsis_criteria(&json)?;
         let decision_matrix = parsing::parse_decision_matrix(&json)?;
         let ideal_solution = parsing::parse_f64_array(&json, "ideal_solution")?;
@@ -347,13 +544,25 @@ where
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("decision")
             .with_max_tokens(16384)
             .with_temperature(0.4)
             .with_deep_thinking();
 
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
-
+        apply_memory_update(&self.storage, &session.id, &json).await;
+
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &[
+                "stakeholders",
+                "conflicts",
+                "alignments",
+                "balanced_recommendation",
+            ],
+        )?;
         let stakeholders = parsing::parse_stakeholders(&json)?;
         let conflicts = parsing::parse_conflicts(&json)?;
         let alignments = parsing::parse_alignments(&json)?;
@@ -386,6 +595,150 @@ where
         ))
     }
 
+    /// Challenge the assumptions behind a prior weighted decision.
+    ///
+    /// Chains decision → divergent internally: loads the prior decision's
+    /// criteria and weights and runs a divergent-style stress test of the
+    /// assumptions behind them, returning which weights are most questionable
+    /// and how the ranking would shift if challenged. The challenge lands in
+    /// the prior decision's session unless `session_id` overrides it.
+    ///
+    /// # Arguments
+    ///
+    /// * `prior_thought_id` - The decision thought being challenged
+    /// * `prior` - The persisted analysis of that thought
+    /// * `session_id` - Optional session override
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModeError`] if the prior decision has no criteria, the API
+    /// fails, parsing fails, or the model challenges a criterion the prior
+    /// decision does not have.
+    pub async fn challenge(
+        &self,
+        prior_thought_id: &str,
+        prior: &WeightedResponse,
+        session_id: Option<String>,
+    ) -> Result<ChallengeResponse, ModeError> {
+        if prior.criteria.is_empty() {
+            return Err(ModeError::InvalidValue {
+                field: "prior_thought_id".to_string(),
+                reason: format!("Decision '{prior_thought_id}' has no criteria to challenge"),
+            });
+        }
+
+        let session_id = session_id.or_else(|| Some(prior.session_id.clone()));
+        let session = self.get_or_create_session(session_id).await?;
+
+        // Restate the prior decision so the challenge is grounded in the
+        // actual criteria, weights, and ranking it produced.
+        let mut prior_block = String::from("Criteria and weights:\n");
+        for c in &prior.criteria {
+            let _ = writeln!(
+                prior_block,
+                "- {} (weight {:.2}): {}",
+                c.name, c.weight, c.description
+            );
+        }
+        prior_block.push_str("Ranking:\n");
+        for r in &prior.ranking {
+            let _ = writeln!(
+                prior_block,
+                "{}. {} (score {:.2})",
+                r.rank, r.option, r.score
+            );
+        }
+        if !prior.sensitivity_notes.is_empty() {
+            let _ = writeln!(
+                prior_block,
+                "Sensitivity notes: {}",
+                prior.sensitivity_notes
+            );
+        }
+
+        let prompt = decision_challenge_prompt();
+        let user_message = self
+            .build_user_message(
+                prompt,
+                &prior_block,
+                &session.id,
+                true,
+                "Prior decision to challenge",
+            )
+            .await;
+
+        // Divergent-style temperature: the point is to argue against the
+        // weights, not to reproduce them.
+        let messages = vec![Message::user(user_message)];
+        let config = CompletionConfig::new()
+            .with_mode("decision")
+            .with_max_tokens(16384)
+            .with_temperature(0.9)
+            .with_deep_thinking();
+
+        let response = self.client.complete(messages, config).await?;
+        let json = extract_json(&response.content)?;
+        apply_memory_update(&self.storage, &session.id, &json).await;
+
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &["challenged_weights", "projected_ranking_shift", "summary"],
+        )?;
+        let challenged_weights = parsing::parse_weight_challenges(&json)?;
+        let projected_ranking_shift = parsing::get_str(&json, "projected_ranking_shift")?;
+        let summary = parsing::get_str(&json, "summary")?;
+
+        // The challenge must reference the prior decision, not invent inputs:
+        // every challenged criterion has to exist there, and a response that
+        // questions nothing defeats the point of the stress test.
+        if challenged_weights.is_empty() {
+            return Err(ModeError::InvalidValue {
+                field: "challenged_weights".to_string(),
+                reason: "must flag at least one questionable weight".to_string(),
+            });
+        }
+        for challenge in &challenged_weights {
+            if !prior.criteria.iter().any(|c| c.name == challenge.criterion) {
+                return Err(ModeError::InvalidValue {
+                    field: "challenged_weights".to_string(),
+                    reason: format!(
+                        "criterion '{}' is not in the prior decision",
+                        challenge.criterion
+                    ),
+                });
+            }
+        }
+
+        let thought_id = generate_thought_id();
+        let thought = Thought::new(
+            &thought_id,
+            &session.id,
+            format!(
+                "Decision challenge: {} of {} weights questioned on {prior_thought_id}",
+                challenged_weights.len(),
+                prior.criteria.len()
+            ),
+            "decision_challenge",
+            challenged_weights
+                .first()
+                .map_or(0.0, |c| c.questionability),
+        );
+
+        if let Err(e) = self.storage.save_thought(&thought).await {
+            tracing::warn!(error = %e, "Storage write failed — reasoning result preserved, thought not persisted");
+        }
+
+        Ok(ChallengeResponse::new(
+            thought_id,
+            session.id,
+            prior_thought_id,
+            challenged_weights,
+            projected_ranking_shift,
+            summary,
+        ))
+    }
+
     // ========================================================================
     // Private Helpers
     // ========================================================================
@@ -449,17 +802,27 @@ where
         has_prior_session: bool,
         content_label: &str,
     ) -> String {
-        let prior_context = if has_prior_session {
-            self.load_prior_context(session_id).await
+        let (prior_context, memory_block) = if has_prior_session {
+            (
+                self.load_prior_context(session_id).await,
+                load_working_memory_block(&self.storage, session_id).await,
+            )
         } else {
-            String::new()
+            (String::new(), String::new())
         };
 
-        if prior_context.is_empty() {
-            format!("{prompt}\n\n{content_label}:\n{content}")
-        } else {
-            format!("{prompt}\n\n{prior_context}\n{content_label}:\n{content}")
+        let mut message = format!(
+            "{prompt}{}\n",
+            language_instruction(self.language.as_deref(), content)
+        );
+        if !memory_block.is_empty() {
+            let _ = write!(message, "\n{memory_block}");
+        }
+        if !prior_context.is_empty() {
+            let _ = write!(message, "\n{prior_context}");
         }
+        let _ = write!(message, "\n{content_label}:\n{content}");
+        message
     }
 }
 
@@ -599,6 +962,9 @@ mod tests {
         mock_storage.expect_save_thought().returning(|_| Ok(()));
         // A referenced session triggers a prior-thoughts lookup for context.
         mock_storage.expect_get_thoughts().returning(|_| Ok(vec![]));
+        mock_storage
+            .expect_get_working_memory()
+            .returning(|_| Ok(None));
 
         let resp = mock_weighted_response();
         mock_client
@@ -618,6 +984,275 @@ mod tests {
         assert_eq!(response.ranking[0].option, "Option A");
         assert!(response.validation.consistent);
         assert!(!response.validation.ranking_corrected);
+        // No assumptions field in the mock: tolerated as an empty list.
+        assert!(response.assumptions.is_empty());
+    }
+
+    /// Weighted response whose criteria are scored on disparate raw scales:
+    /// throughput in requests/sec next to a 0-1 usability rating.
+    fn mock_disparate_scale_response() -> String {
+        r#"{
+            "options": ["Option A", "Option B"],
+            "criteria": [
+                {"name": "Throughput", "weight": 0.4, "description": "Requests per second"},
+                {"name": "Usability", "weight": 0.6, "description": "Ease of use"}
+            ],
+            "scores": {
+                "Option A": {"Throughput": 1000.0, "Usability": 0.1},
+                "Option B": {"Throughput": 900.0, "Usability": 0.9}
+            },
+            "weighted_totals": {"Option A": 400.06, "Option B": 360.54},
+            "ranking": [
+                {"option": "Option A", "score": 0.9, "rank": 1},
+                {"option": "Option B", "score": 0.7, "rank": 2}
+            ],
+            "sensitivity_notes": "Throughput scale dominates"
+        }"#
+        .to_string()
+    }
+
+    fn weighted_test_storage() -> MockStorageTrait {
+        let mut mock_storage = MockStorageTrait::new();
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        mock_storage.expect_get_thoughts().returning(|_| Ok(vec![]));
+        mock_storage
+            .expect_get_working_memory()
+            .returning(|_| Ok(None));
+        mock_storage
+    }
+
+    #[tokio::test]
+    async fn test_weighted_min_max_normalization_flips_ranking() {
+        let mut mock_client = MockAnthropicClientTrait::new();
+        let resp = mock_disparate_scale_response();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        // Without normalization the raw throughput scale decides the ranking.
+        let mode = DecisionMode::new(weighted_test_storage(), mock_client);
+        let response = mode
+            .weighted("Compare options", Some("test".to_string()))
+            .await
+            .expect("weighted succeeds");
+        assert_eq!(response.ranking[0].option, "Option A");
+
+        // Min-max puts both criteria on [0, 1], and usability's higher weight
+        // flips the outcome.
+        let mut mock_client = MockAnthropicClientTrait::new();
+        let resp = mock_disparate_scale_response();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+        let mode = DecisionMode::new(weighted_test_storage(), mock_client)
+            .with_normalization(Some("min_max".to_string()));
+        let response = mode
+            .weighted("Compare options", Some("test".to_string()))
+            .await
+            .expect("weighted succeeds");
+        assert_eq!(response.ranking[0].option, "Option B");
+        assert!((response.weighted_totals["Option B"] - 0.6).abs() < 1e-9);
+        // The returned scores are the normalized matrix, consistent with the
+        // totals, and the silent replacement is not flagged as inconsistent.
+        assert!((response.scores["Option A"]["Throughput"] - 1.0).abs() < 1e-9);
+        assert!(response.validation.consistent);
+    }
+
+    #[tokio::test]
+    async fn test_weighted_rejects_unknown_normalization_before_api_call() {
+        // No expectations: neither storage nor the client may be touched.
+        let mode = DecisionMode::new(MockStorageTrait::new(), MockAnthropicClientTrait::new())
+            .with_normalization(Some("median".to_string()));
+
+        let err = mode
+            .weighted("Compare options", None)
+            .await
+            .expect_err("unknown strategy rejected");
+        assert!(
+            matches!(&err, ModeError::InvalidValue { field, .. } if field == "normalization"),
+            "{err}"
+        );
+        assert!(err.to_string().contains("min_max"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_weighted_extracts_and_persists_assumptions() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage
+            .expect_save_thought()
+            .withf(|t| t.mode == "assumption")
+            .times(2)
+            .returning(|_| Ok(()));
+        mock_storage
+            .expect_save_thought()
+            .withf(|t| t.mode == "decision_weighted")
+            .times(1)
+            .returning(|_| Ok(()));
+
+        // The weighted fixture plus an assumptions array.
+        let resp = mock_weighted_response().replace(
+            "\"sensitivity_notes\":",
+            r#""assumptions": [
+                {"assumption": "Costs stay within estimates", "confidence": 0.7, "criticality": 0.5},
+                {"assumption": "Criteria weights reflect priorities", "confidence": 0.8, "criticality": 0.9}
+            ],
+            "sensitivity_notes":"#,
+        );
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = DecisionMode::new(mock_storage, mock_client);
+        let result = mode.weighted("Compare options", None).await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        // Attached most-critical first.
+        assert_eq!(response.assumptions.len(), 2);
+        assert_eq!(
+            response.assumptions[0].assumption,
+            "Criteria weights reflect priorities"
+        );
+        assert_eq!(
+            response.assumptions[1].assumption,
+            "Costs stay within estimates"
+        );
+    }
+
+    /// Weighted response with two criteria, the shape the justification
+    /// tests extend with a `criterion_justifications` array.
+    fn mock_two_criterion_response() -> String {
+        r#"{
+            "options": ["Option A", "Option B"],
+            "criteria": [
+                {"name": "Cost", "weight": 0.6, "description": "Total cost"},
+                {"name": "Quality", "weight": 0.4, "description": "Build quality"}
+            ],
+            "scores": {
+                "Option A": {"Cost": 0.8, "Quality": 0.5},
+                "Option B": {"Cost": 0.6, "Quality": 0.9}
+            },
+            "weighted_totals": {"Option A": 0.68, "Option B": 0.72},
+            "ranking": [
+                {"option": "Option B", "score": 0.72, "rank": 1},
+                {"option": "Option A", "score": 0.68, "rank": 2}
+            ],
+            "sensitivity_notes": "Robust to small weight changes"
+        }"#
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_weighted_justifies_every_criterion_and_persists_linkage() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        // The criterion → evidence linkage is persisted, one thought per
+        // criterion, alongside the usual decision thought.
+        mock_storage
+            .expect_save_thought()
+            .withf(|t| t.mode == "weight_justification")
+            .times(2)
+            .returning(|_| Ok(()));
+        mock_storage
+            .expect_save_thought()
+            .withf(|t| t.mode == "decision_weighted")
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let resp = mock_two_criterion_response().replace(
+            "\"sensitivity_notes\":",
+            r#""criterion_justifications": [
+                {"criterion": "Cost", "justification": "Budget caps dominate this purchase", "evidence_confidence": 0.9},
+                {"criterion": "Quality", "justification": "Defect rates from last quarter", "evidence_confidence": 0.6}
+            ],
+            "sensitivity_notes":"#,
+        );
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = DecisionMode::new(mock_storage, mock_client).with_weight_justifications(true);
+        let response = mode
+            .weighted("Compare options", None)
+            .await
+            .expect("weighted succeeds");
+
+        // One justification per criterion, in criteria order.
+        assert_eq!(response.criterion_justifications.len(), 2);
+        assert_eq!(response.criterion_justifications[0].criterion, "Cost");
+        assert_eq!(
+            response.criterion_justifications[0].justification,
+            "Budget caps dominate this purchase"
+        );
+        assert!((response.criterion_justifications[0].evidence_confidence - 0.9).abs() < 1e-9);
+        assert_eq!(response.criterion_justifications[1].criterion, "Quality");
+        assert!((response.criterion_justifications[1].evidence_confidence - 0.6).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_weighted_missing_justification_tolerated_as_empty() {
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        // The model justified Cost but left Quality out entirely.
+        let resp = mock_two_criterion_response().replace(
+            "\"sensitivity_notes\":",
+            r#""criterion_justifications": [
+                {"criterion": "Cost", "justification": "Budget caps dominate", "evidence_confidence": 0.9}
+            ],
+            "sensitivity_notes":"#,
+        );
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = DecisionMode::new(weighted_test_storage(), mock_client)
+            .with_weight_justifications(true);
+        let response = mode
+            .weighted("Compare options", None)
+            .await
+            .expect("missing justification is not fatal");
+
+        // Quality still gets an entry — empty rather than an error.
+        assert_eq!(response.criterion_justifications.len(), 2);
+        assert_eq!(response.criterion_justifications[1].criterion, "Quality");
+        assert!(response.criterion_justifications[1]
+            .justification
+            .is_empty());
+        assert!(
+            response.criterion_justifications[1]
+                .evidence_confidence
+                .abs()
+                < 1e-9
+        );
+    }
+
+    #[tokio::test]
+    async fn test_weighted_justifications_off_by_default() {
+        let mut mock_client = MockAnthropicClientTrait::new();
+        let resp = mock_two_criterion_response();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = DecisionMode::new(weighted_test_storage(), mock_client);
+        let response = mode
+            .weighted("Compare options", None)
+            .await
+            .expect("weighted succeeds");
+
+        assert!(response.criterion_justifications.is_empty());
     }
 
     #[tokio::test]
@@ -642,6 +1277,55 @@ mod tests {
         let response = result.unwrap();
         assert_eq!(response.comparisons.len(), 1);
         assert_eq!(response.comparisons[0].preferred, PreferenceResult::OptionA);
+        // A single comparison has no fully-compared triple to contradict.
+        assert_eq!(response.consistency_ratio, 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_pairwise_cyclic_preferences_flagged_unreliable() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+
+        // A > B > C > A: every triple cycles.
+        let resp = r#"{
+            "comparisons": [
+                {"option_a": "A", "option_b": "B", "preferred": "option_a",
+                 "strength": "strong", "reasoning": ""},
+                {"option_a": "B", "option_b": "C", "preferred": "option_a",
+                 "strength": "strong", "reasoning": ""},
+                {"option_a": "C", "option_b": "A", "preferred": "option_a",
+                 "strength": "strong", "reasoning": ""}
+            ],
+            "pairwise_matrix": {"A": 1, "B": 1, "C": 1},
+            "ranking": [
+                {"option": "A", "wins": 1, "rank": 1},
+                {"option": "B", "wins": 1, "rank": 2},
+                {"option": "C", "wins": 1, "rank": 3}
+            ],
+            "consistency_check": "Preferences cycle"
+        }"#
+        .to_string();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = DecisionMode::new(mock_storage, mock_client);
+        let result = mode.pairwise("A vs B vs C", None).await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.consistency_ratio, 0.0);
+        assert!(!response.validation.consistent);
+        assert!(response
+            .validation
+            .warnings
+            .iter()
+            .any(|w| w.contains("unreliable")));
     }
 
     #[tokio::test]
@@ -1381,6 +2065,9 @@ mod tests {
                 0.85,
             )])
         });
+        mock_storage
+            .expect_get_working_memory()
+            .returning(|_| Ok(None));
         mock_storage.expect_save_thought().returning(|_| Ok(()));
 
         let resp = mock_weighted_response();
@@ -1406,6 +2093,42 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_weighted_injects_working_memory() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("wm-session")));
+        mock_storage
+            .expect_get_thoughts()
+            .returning(|_| Ok(Vec::new()));
+        // Working memory set by an earlier mode in the same session.
+        mock_storage
+            .expect_get_working_memory()
+            .returning(|_| Ok(Some("Budget cap: $50k".to_string())));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+
+        let resp = mock_weighted_response();
+        // The scratchpad must reach the prompt even though no thoughts exist.
+        mock_client
+            .expect_complete()
+            .withf(|messages, _| {
+                messages.first().is_some_and(|m| {
+                    m.content.contains("Working memory") && m.content.contains("Budget cap: $50k")
+                })
+            })
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = DecisionMode::new(mock_storage, mock_client);
+        let result = mode
+            .weighted("Pick a vendor", Some("wm-session".to_string()))
+            .await;
+
+        assert!(result.is_ok());
+    }
+
     #[tokio::test]
     async fn test_weighted_new_session_skips_history_lookup() {
         let mut mock_storage = MockStorageTrait::new();
@@ -1440,4 +2163,168 @@ mod tests {
         assert_eq!(truncate_chars("12345", 5), "12345");
         assert_eq!(truncate_chars("123456789", 4), "1234…");
     }
+
+    // Challenge tests
+
+    fn prior_weighted_decision() -> WeightedResponse {
+        WeightedResponse::new(
+            "dw-1",
+            "test",
+            vec!["Option A".to_string(), "Option B".to_string()],
+            vec![
+                Criterion {
+                    name: "Cost".to_string(),
+                    weight: 0.6,
+                    description: "Total cost".to_string(),
+                },
+                Criterion {
+                    name: "Quality".to_string(),
+                    weight: 0.4,
+                    description: "Build quality".to_string(),
+                },
+            ],
+            std::collections::HashMap::new(),
+            std::collections::HashMap::new(),
+            vec![RankedOption {
+                option: "Option A".to_string(),
+                score: 0.8,
+                rank: 1,
+            }],
+            "Robust to small weight changes",
+        )
+    }
+
+    fn challenge_test_storage() -> MockStorageTrait {
+        let mut mock_storage = MockStorageTrait::new();
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        mock_storage.expect_get_thoughts().returning(|_| Ok(vec![]));
+        mock_storage
+            .expect_get_working_memory()
+            .returning(|_| Ok(None));
+        mock_storage
+    }
+
+    #[tokio::test]
+    async fn test_challenge_references_prior_criteria_and_flags_weight() {
+        let mut mock_client = MockAnthropicClientTrait::new();
+        let resp = r#"{
+            "challenged_weights": [
+                {
+                    "criterion": "Cost",
+                    "current_weight": 0.6,
+                    "challenge": "Assumes budget pressure persists through next year",
+                    "suggested_weight": 0.3,
+                    "questionability": 0.8
+                }
+            ],
+            "projected_ranking_shift": "Option B overtakes Option A if Cost drops to 0.3",
+            "summary": "The decision is most exposed to the budget-pressure assumption"
+        }"#
+        .to_string();
+        // The prompt must restate the prior decision's criteria and weights.
+        mock_client
+            .expect_complete()
+            .withf(|messages, _| {
+                messages
+                    .first()
+                    .is_some_and(|m| m.content.contains("Cost") && m.content.contains("0.60"))
+            })
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = DecisionMode::new(challenge_test_storage(), mock_client);
+        let prior = prior_weighted_decision();
+        let response = mode
+            .challenge("dw-1", &prior, None)
+            .await
+            .expect("challenge succeeds");
+
+        assert_eq!(response.prior_thought_id, "dw-1");
+        // Defaults to the prior decision's session.
+        assert_eq!(response.session_id, "test");
+        // At least one weight is flagged, and every challenged criterion
+        // comes from the prior decision.
+        assert!(!response.challenged_weights.is_empty());
+        for challenge in &response.challenged_weights {
+            assert!(prior.criteria.iter().any(|c| c.name == challenge.criterion));
+        }
+        assert_eq!(response.challenged_weights[0].questionability, 0.8);
+        assert_eq!(response.challenged_weights[0].suggested_weight, Some(0.3));
+        assert!(response.projected_ranking_shift.contains("Option B"));
+    }
+
+    #[tokio::test]
+    async fn test_challenge_rejects_unknown_criterion() {
+        let mut mock_client = MockAnthropicClientTrait::new();
+        let resp = r#"{
+            "challenged_weights": [
+                {
+                    "criterion": "Vibes",
+                    "current_weight": 0.6,
+                    "challenge": "Not a real criterion",
+                    "questionability": 0.9
+                }
+            ],
+            "projected_ranking_shift": "Unclear",
+            "summary": "Invented input"
+        }"#
+        .to_string();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = DecisionMode::new(challenge_test_storage(), mock_client);
+        let err = mode
+            .challenge("dw-1", &prior_weighted_decision(), None)
+            .await
+            .expect_err("unknown criterion rejected");
+        assert!(
+            matches!(&err, ModeError::InvalidValue { field, .. } if field == "challenged_weights"),
+            "{err}"
+        );
+        assert!(err.to_string().contains("Vibes"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_challenge_requires_at_least_one_questioned_weight() {
+        let mut mock_client = MockAnthropicClientTrait::new();
+        let resp = r#"{
+            "challenged_weights": [],
+            "projected_ranking_shift": "Stable",
+            "summary": "Nothing to question"
+        }"#
+        .to_string();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = DecisionMode::new(challenge_test_storage(), mock_client);
+        let err = mode
+            .challenge("dw-1", &prior_weighted_decision(), None)
+            .await
+            .expect_err("empty challenge rejected");
+        assert!(
+            matches!(&err, ModeError::InvalidValue { field, .. } if field == "challenged_weights"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_challenge_without_criteria_fails_before_api_call() {
+        // No expectations: neither storage nor the client may be touched.
+        let mode = DecisionMode::new(MockStorageTrait::new(), MockAnthropicClientTrait::new());
+        let mut prior = prior_weighted_decision();
+        prior.criteria.clear();
+
+        let err = mode
+            .challenge("dw-1", &prior, None)
+            .await
+            .expect_err("criteria-less decision rejected");
+        assert!(
+            matches!(&err, ModeError::InvalidValue { field, .. } if field == "prior_thought_id"),
+            "{err}"
+        );
+    }
 }
@@ -0,0 +1,222 @@
+//! Criterion score normalization for weighted decisions.
+//!
+//! The weighted operation assumes per-criterion scores are already
+//! comparable, but raw criteria can arrive on disparate scales (cost in
+//! thousands next to a 1-5 usability rating). These strategies rescale the
+//! score matrix per criterion, across options, before weighted totals are
+//! computed — in Rust, so the rescaled arithmetic is deterministic. This
+//! mirrors the normalization TOPSIS performs as part of its method.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ModeError;
+
+/// How to rescale the score matrix before computing weighted totals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Normalization {
+    /// Use the scores as-is (default).
+    #[default]
+    None,
+    /// Rescale each criterion to `[0, 1]` via `(x - min) / (max - min)`.
+    MinMax,
+    /// Center each criterion on its mean, in standard-deviation units.
+    ZScore,
+}
+
+impl Normalization {
+    /// Returns the strategy name as a string.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::MinMax => "min_max",
+            Self::ZScore => "z_score",
+        }
+    }
+}
+
+impl std::fmt::Display for Normalization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Normalization {
+    type Err = ModeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(Self::None),
+            "min_max" | "minmax" => Ok(Self::MinMax),
+            "z_score" | "zscore" => Ok(Self::ZScore),
+            _ => Err(ModeError::InvalidValue {
+                field: "normalization".to_string(),
+                reason: format!("unknown strategy '{s}'. Valid strategies: none, min_max, z_score"),
+            }),
+        }
+    }
+}
+
+/// Rescale the score matrix in place, per criterion across options.
+///
+/// A criterion all options score identically carries no preference signal:
+/// min-max maps it to 0.5 and z-score to 0.0, so it contributes the same
+/// amount to every option's total either way.
+pub(super) fn normalize_scores(
+    scores: &mut HashMap<String, HashMap<String, f64>>,
+    strategy: Normalization,
+) {
+    if strategy == Normalization::None {
+        return;
+    }
+
+    // Collect each criterion's values across options.
+    let mut by_criterion: HashMap<String, Vec<f64>> = HashMap::new();
+    for option_scores in scores.values() {
+        for (criterion, &value) in option_scores {
+            by_criterion
+                .entry(criterion.clone())
+                .or_default()
+                .push(value);
+        }
+    }
+
+    for option_scores in scores.values_mut() {
+        for (criterion, value) in option_scores.iter_mut() {
+            let Some(values) = by_criterion.get(criterion) else {
+                continue;
+            };
+            *value = match strategy {
+                Normalization::None => *value,
+                Normalization::MinMax => min_max(*value, values),
+                Normalization::ZScore => z_score(*value, values),
+            };
+        }
+    }
+}
+
+fn min_max(value: f64, values: &[f64]) -> f64 {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+    if range.abs() < f64::EPSILON {
+        0.5
+    } else {
+        (value - min) / range
+    }
+}
+
+fn z_score(value: f64, values: &[f64]) -> f64 {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    let std_dev = variance.sqrt();
+    if std_dev.abs() < f64::EPSILON {
+        0.0
+    } else {
+        (value - mean) / std_dev
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    fn matrix(entries: &[(&str, &[(&str, f64)])]) -> HashMap<String, HashMap<String, f64>> {
+        entries
+            .iter()
+            .map(|(option, scores)| {
+                (
+                    (*option).to_string(),
+                    scores
+                        .iter()
+                        .map(|(criterion, value)| ((*criterion).to_string(), *value))
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_normalization_from_str() {
+        assert_eq!(
+            "none".parse::<Normalization>().unwrap(),
+            Normalization::None
+        );
+        assert_eq!(
+            "min_max".parse::<Normalization>().unwrap(),
+            Normalization::MinMax
+        );
+        assert_eq!(
+            "MinMax".parse::<Normalization>().unwrap(),
+            Normalization::MinMax
+        );
+        assert_eq!(
+            "z_score".parse::<Normalization>().unwrap(),
+            Normalization::ZScore
+        );
+        let err = "median".parse::<Normalization>().expect_err("rejected");
+        assert!(err.to_string().contains("median"), "{err}");
+        assert!(err.to_string().contains("min_max"), "{err}");
+    }
+
+    #[test]
+    fn test_normalize_none_is_identity() {
+        let mut scores = matrix(&[("A", &[("cost", 1200.0)]), ("B", &[("cost", 300.0)])]);
+        let original = scores.clone();
+        normalize_scores(&mut scores, Normalization::None);
+        assert_eq!(scores, original);
+    }
+
+    #[test]
+    fn test_min_max_rescales_per_criterion() {
+        let mut scores = matrix(&[
+            ("A", &[("cost", 1200.0), ("usability", 3.0)]),
+            ("B", &[("cost", 300.0), ("usability", 5.0)]),
+            ("C", &[("cost", 750.0), ("usability", 1.0)]),
+        ]);
+        normalize_scores(&mut scores, Normalization::MinMax);
+        assert_eq!(scores["A"]["cost"], 1.0);
+        assert_eq!(scores["B"]["cost"], 0.0);
+        assert_eq!(scores["C"]["cost"], 0.5);
+        assert_eq!(scores["A"]["usability"], 0.5);
+        assert_eq!(scores["B"]["usability"], 1.0);
+        assert_eq!(scores["C"]["usability"], 0.0);
+    }
+
+    #[test]
+    fn test_min_max_constant_criterion_maps_to_half() {
+        let mut scores = matrix(&[("A", &[("cost", 7.0)]), ("B", &[("cost", 7.0)])]);
+        normalize_scores(&mut scores, Normalization::MinMax);
+        assert_eq!(scores["A"]["cost"], 0.5);
+        assert_eq!(scores["B"]["cost"], 0.5);
+    }
+
+    #[test]
+    fn test_z_score_centers_on_mean() {
+        let mut scores = matrix(&[
+            ("A", &[("latency", 10.0)]),
+            ("B", &[("latency", 20.0)]),
+            ("C", &[("latency", 30.0)]),
+        ]);
+        normalize_scores(&mut scores, Normalization::ZScore);
+        // Mean 20, population std dev ≈ 8.165.
+        assert!(scores["A"]["latency"] < 0.0);
+        assert!((scores["B"]["latency"]).abs() < f64::EPSILON);
+        assert!(scores["C"]["latency"] > 0.0);
+        assert!((scores["A"]["latency"] + scores["C"]["latency"]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_z_score_constant_criterion_maps_to_zero() {
+        let mut scores = matrix(&[("A", &[("cost", 7.0)]), ("B", &[("cost", 7.0)])]);
+        normalize_scores(&mut scores, Normalization::ZScore);
+        assert_eq!(scores["A"]["cost"], 0.0);
+        assert_eq!(scores["B"]["cost"], 0.0);
+    }
+}
@@ -8,13 +8,56 @@
 use std::collections::HashMap;
 
 use crate::error::ModeError;
+use crate::modes::parse_probability;
 
 use super::types::{
-    Alignment, BalancedRecommendation, Conflict, ConflictSeverity, Criterion, CriterionType,
-    InfluenceLevel, PairwiseComparison, PairwiseRank, PreferenceResult, PreferenceStrength,
-    RankedOption, Stakeholder, TopsisCreterion, TopsisDistances, TopsisRank,
+    Alignment, BalancedRecommendation, Conflict, ConflictSeverity, Criterion,
+    CriterionJustification, CriterionType, InfluenceLevel, PairwiseComparison, PairwiseRank,
+    PreferenceResult, PreferenceStrength, RankedOption, Stakeholder, TopsisCreterion,
+    TopsisDistances, TopsisRank, WeightChallenge,
 };
 
+// ============================================================================
+// Challenge Parsing
+// ============================================================================
+
+/// Parse the `challenged_weights` array from a challenge JSON response,
+/// most questionable first.
+pub fn parse_weight_challenges(
+    json: &serde_json::Value,
+) -> Result<Vec<WeightChallenge>, ModeError> {
+    let arr = json
+        .get("challenged_weights")
+        .and_then(serde_json::Value::as_array)
+        .ok_or_else(|| ModeError::MissingField {
+            field: "challenged_weights".to_string(),
+        })?;
+
+    let mut challenges: Vec<WeightChallenge> = arr
+        .iter()
+        .map(|c| {
+            let suggested_weight = c
+                .get("suggested_weight")
+                .filter(|v| !v.is_null())
+                .map(|_| parse_probability(c, "suggested_weight"))
+                .transpose()?;
+            Ok(WeightChallenge {
+                criterion: get_str(c, "criterion")?,
+                current_weight: parse_probability(c, "current_weight")?,
+                challenge: get_str(c, "challenge")?,
+                suggested_weight,
+                questionability: parse_probability(c, "questionability")?,
+            })
+        })
+        .collect::<Result<_, ModeError>>()?;
+    challenges.sort_by(|a, b| {
+        b.questionability
+            .partial_cmp(&a.questionability)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    Ok(challenges)
+}
+
 // ============================================================================
 // Weighted Parsing
 // ============================================================================
@@ -32,13 +75,56 @@ pub fn parse_criteria(json: &serde_json::Value) -> Result<Vec<Criterion>, ModeEr
         .map(|c| {
             Ok(Criterion {
                 name: get_str(c, "name")?,
-                weight: get_f64(c, "weight")?,
+                weight: parse_probability(c, "weight")?,
                 description: get_str(c, "description")?,
             })
         })
         .collect()
 }
 
+/// Parse the `criterion_justifications` array from a weighted decision JSON
+/// response, aligned to the parsed criteria.
+///
+/// Tolerant by design: every criterion gets exactly one entry in criteria
+/// order, and a criterion the model left unjustified — or gave a malformed
+/// entry — falls back to an empty justification with zero evidence
+/// confidence. Evidence for a weight is a report-quality extra, so its
+/// absence must never fail an otherwise good analysis.
+#[must_use]
+pub fn parse_criterion_justifications(
+    json: &serde_json::Value,
+    criteria: &[Criterion],
+) -> Vec<CriterionJustification> {
+    let entries = json
+        .get("criterion_justifications")
+        .and_then(serde_json::Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    criteria
+        .iter()
+        .map(|criterion| {
+            let entry = entries.iter().find(|e| {
+                e.get("criterion")
+                    .and_then(serde_json::Value::as_str)
+                    .is_some_and(|name| name == criterion.name)
+            });
+            CriterionJustification {
+                criterion: criterion.name.clone(),
+                justification: entry
+                    .and_then(|e| e.get("justification"))
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                evidence_confidence: entry
+                    .and_then(|e| e.get("evidence_confidence"))
+                    .and_then(serde_json::Value::as_f64)
+                    .map_or(0.0, |c| c.clamp(0.0, 1.0)),
+            }
+        })
+        .collect()
+}
+
 /// Parse the `scores` map (option → criterion → score) from a weighted decision JSON response.
 pub fn parse_scores(
     json: &serde_json::Value,
@@ -109,7 +195,7 @@ pub fn parse_weighted_ranking(json: &serde_json::Value) -> Result<Vec<RankedOpti
 
             Ok(RankedOption {
                 option: get_str(r, "option")?,
-                score: get_f64(r, "score")?,
+                score: parse_probability(r, "score")?,
                 rank,
             })
         })
@@ -252,7 +338,7 @@ pub fn parse_topsis_criteria(json: &serde_json::Value) -> Result<Vec<TopsisCrete
             Ok(TopsisCreterion {
                 name: get_str(c, "name")?,
                 criterion_type,
-                weight: get_f64(c, "weight")?,
+                weight: parse_probability(c, "weight")?,
             })
         })
         .collect()
@@ -359,7 +445,7 @@ pub fn parse_topsis_ranking(json: &serde_json::Value) -> Result<Vec<TopsisRank>,
 
             Ok(TopsisRank {
                 option: get_str(r, "option")?,
-                closeness: get_f64(r, "closeness")?,
+                closeness: parse_probability(r, "closeness")?,
                 rank,
             })
         })
@@ -527,6 +613,57 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    // Challenge parsing tests
+    #[test]
+    fn test_parse_weight_challenges_sorted_most_questionable_first() {
+        let json = json!({
+            "challenged_weights": [
+                {
+                    "criterion": "cost",
+                    "current_weight": 0.5,
+                    "challenge": "Assumes budget pressure persists",
+                    "suggested_weight": 0.3,
+                    "questionability": 0.4
+                },
+                {
+                    "criterion": "quality",
+                    "current_weight": 0.5,
+                    "challenge": "Assumes users notice quality differences",
+                    "questionability": 0.9
+                }
+            ]
+        });
+        let challenges = parse_weight_challenges(&json).unwrap();
+        assert_eq!(challenges.len(), 2);
+        assert_eq!(challenges[0].criterion, "quality");
+        assert_eq!(challenges[0].suggested_weight, None);
+        assert_eq!(challenges[1].criterion, "cost");
+        assert_eq!(challenges[1].suggested_weight, Some(0.3));
+    }
+
+    #[test]
+    fn test_parse_weight_challenges_missing() {
+        let result = parse_weight_challenges(&json!({}));
+        assert!(matches!(
+            result,
+            Err(ModeError::MissingField { field }) if field == "challenged_weights"
+        ));
+    }
+
+    #[test]
+    fn test_parse_weight_challenges_missing_challenge_text() {
+        let json = json!({
+            "challenged_weights": [
+                {"criterion": "cost", "current_weight": 0.5, "questionability": 0.4}
+            ]
+        });
+        let result = parse_weight_challenges(&json);
+        assert!(matches!(
+            result,
+            Err(ModeError::MissingField { field }) if field == "challenge"
+        ));
+    }
+
     // Weighted parsing tests
     #[test]
     fn test_parse_criteria_success() {
@@ -9,6 +9,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::modes::Assumption;
+
 // ============================================================================
 // Validation
 // ============================================================================
@@ -54,6 +56,25 @@ pub struct Criterion {
     pub description: String,
 }
 
+/// Evidence backing a criterion weight.
+///
+/// Produced when `weighted` runs with weight justifications enabled: each
+/// criterion's weight is paired with a short evidence-based justification and
+/// a confidence in that evidence, so a report can show why the weight was
+/// chosen. A justification the model omitted is tolerated as an empty string
+/// with zero evidence confidence rather than failing the analysis.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CriterionJustification {
+    /// Name of the criterion this justification backs.
+    pub criterion: String,
+    /// Evidence for why the weight was chosen (empty when the model gave none).
+    #[serde(default)]
+    pub justification: String,
+    /// Probability (0.0-1.0) that the cited evidence supports the weight.
+    #[serde(default)]
+    pub evidence_confidence: f64,
+}
+
 /// A ranked option with its score.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct RankedOption {
@@ -84,6 +105,13 @@ pub struct WeightedResponse {
     pub ranking: Vec<RankedOption>,
     /// Notes on sensitivity to weight changes.
     pub sensitivity_notes: String,
+    /// Hidden assumptions the analysis rests on, most critical first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assumptions: Vec<Assumption>,
+    /// Evidence backing each criterion weight, one entry per criterion in
+    /// criteria order. Empty unless weight justifications were requested.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub criterion_justifications: Vec<CriterionJustification>,
     /// Result of verifying the weighted arithmetic.
     #[serde(default)]
     pub validation: DecisionValidation,
@@ -113,10 +141,29 @@ impl WeightedResponse {
             weighted_totals,
             ranking,
             sensitivity_notes: sensitivity_notes.into(),
+            assumptions: Vec::new(),
+            criterion_justifications: Vec::new(),
             validation: DecisionValidation::default(),
         }
     }
 
+    /// Attach the assumptions surfaced by the analysis.
+    #[must_use]
+    pub fn with_assumptions(mut self, assumptions: Vec<Assumption>) -> Self {
+        self.assumptions = assumptions;
+        self
+    }
+
+    /// Attach the evidence backing each criterion weight.
+    #[must_use]
+    pub fn with_criterion_justifications(
+        mut self,
+        justifications: Vec<CriterionJustification>,
+    ) -> Self {
+        self.criterion_justifications = justifications;
+        self
+    }
+
     /// Attach an arithmetic-verification result.
     #[must_use]
     pub fn with_validation(mut self, validation: DecisionValidation) -> Self {
@@ -125,6 +172,65 @@ impl WeightedResponse {
     }
 }
 
+// ============================================================================
+// Response Types - Challenge
+// ============================================================================
+
+/// A challenged criterion weight from a prior weighted decision.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WeightChallenge {
+    /// Name of the criterion from the prior decision.
+    pub criterion: String,
+    /// The weight the prior decision used.
+    pub current_weight: f64,
+    /// The assumption behind the weight and why it is doubtful.
+    pub challenge: String,
+    /// Alternative weight under the challenged assumption, if one is proposed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggested_weight: Option<f64>,
+    /// How doubtful the assumption behind the weight is (0.0-1.0).
+    pub questionability: f64,
+}
+
+/// Response from challenging a prior weighted decision's assumptions.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChallengeResponse {
+    /// Unique identifier for this thought.
+    pub thought_id: String,
+    /// Session this thought belongs to.
+    pub session_id: String,
+    /// The decision thought whose assumptions were challenged.
+    pub prior_thought_id: String,
+    /// Challenged weights, most questionable first.
+    pub challenged_weights: Vec<WeightChallenge>,
+    /// How the ranking would shift under the challenged weights.
+    pub projected_ranking_shift: String,
+    /// Which assumptions the decision is most exposed to.
+    pub summary: String,
+}
+
+impl ChallengeResponse {
+    /// Create a new challenge response.
+    #[must_use]
+    pub fn new(
+        thought_id: impl Into<String>,
+        session_id: impl Into<String>,
+        prior_thought_id: impl Into<String>,
+        challenged_weights: Vec<WeightChallenge>,
+        projected_ranking_shift: impl Into<String>,
+        summary: impl Into<String>,
+    ) -> Self {
+        Self {
+            thought_id: thought_id.into(),
+            session_id: session_id.into(),
+            prior_thought_id: prior_thought_id.into(),
+            challenged_weights,
+            projected_ranking_shift: projected_ranking_shift.into(),
+            summary: summary.into(),
+        }
+    }
+}
+
 // ============================================================================
 // Response Types - Pairwise
 // ============================================================================
@@ -179,8 +285,13 @@ pub struct PairwiseRank {
     pub rank: u32,
 }
 
+/// Default consistency ratio for payloads that predate the field.
+const fn default_consistency_ratio() -> f64 {
+    1.0
+}
+
 /// Response from pairwise operation.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PairwiseResponse {
     /// Unique identifier for this thought.
     pub thought_id: String,
@@ -194,6 +305,10 @@ pub struct PairwiseResponse {
     pub ranking: Vec<PairwiseRank>,
     /// Check for preference transitivity.
     pub consistency_check: String,
+    /// Fraction of fully-compared option triples that are transitive
+    /// (1.0 = perfectly consistent, lower when preferences cycle).
+    #[serde(default = "default_consistency_ratio")]
+    pub consistency_ratio: f64,
     /// Result of verifying win counts and ranking against the comparisons.
     #[serde(default)]
     pub validation: DecisionValidation,
@@ -219,10 +334,18 @@ impl PairwiseResponse {
             pairwise_matrix,
             ranking,
             consistency_check: consistency_check.into(),
+            consistency_ratio: default_consistency_ratio(),
             validation: DecisionValidation::default(),
         }
     }
 
+    /// Attach the computed transitivity ratio.
+    #[must_use]
+    pub const fn with_consistency_ratio(mut self, ratio: f64) -> Self {
+        self.consistency_ratio = ratio;
+        self
+    }
+
     /// Attach an arithmetic-verification result.
     #[must_use]
     pub fn with_validation(mut self, validation: DecisionValidation) -> Self {
@@ -368,6 +491,18 @@ pub enum InfluenceLevel {
     Low,
 }
 
+impl InfluenceLevel {
+    /// Numeric dissent weight for minority reports.
+    #[must_use]
+    pub const fn weight(&self) -> u32 {
+        match self {
+            Self::High => 3,
+            Self::Medium => 2,
+            Self::Low => 1,
+        }
+    }
+}
+
 /// Conflict severity.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -406,6 +541,107 @@ pub struct Conflict {
     pub severity: ConflictSeverity,
 }
 
+impl ConflictSeverity {
+    /// Numeric conflict intensity for matrix aggregation.
+    #[must_use]
+    pub const fn intensity(&self) -> u32 {
+        match self {
+            Self::High => 3,
+            Self::Medium => 2,
+            Self::Low => 1,
+        }
+    }
+}
+
+/// Symmetric stakeholder-by-stakeholder conflict intensity matrix.
+///
+/// Built from the parsed conflicts: each conflict adds its severity's
+/// [`ConflictSeverity::intensity`] to every pair of stakeholders it names, so
+/// repeated disagreements between the same pair accumulate. The matrix is
+/// symmetric by construction (`matrix[a][b] == matrix[b][a]`), and every
+/// stakeholder gets a row even with no conflicts, making clusters easy to
+/// spot across many stakeholders.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConflictMatrix {
+    /// Accumulated intensity per stakeholder pair. Inner maps only contain
+    /// entries for pairs with at least one conflict.
+    pub intensities: HashMap<String, HashMap<String, u32>>,
+}
+
+impl ConflictMatrix {
+    /// Build the matrix from parsed conflicts and the stakeholder list.
+    ///
+    /// Stakeholders that appear in a conflict but not in `stakeholders` are
+    /// still included (with a warning) so the matrix never drops a
+    /// participant the model named.
+    #[must_use]
+    pub fn from_conflicts(conflicts: &[Conflict], stakeholders: &[Stakeholder]) -> Self {
+        let mut intensities: HashMap<String, HashMap<String, u32>> = stakeholders
+            .iter()
+            .map(|s| (s.name.clone(), HashMap::new()))
+            .collect();
+
+        for conflict in conflicts {
+            for name in &conflict.between {
+                if !intensities.contains_key(name) {
+                    tracing::warn!(
+                        stakeholder = %name,
+                        "Conflict references stakeholder missing from stakeholders list — including in matrix"
+                    );
+                    intensities.insert(name.clone(), HashMap::new());
+                }
+            }
+
+            let intensity = conflict.severity.intensity();
+            for (i, a) in conflict.between.iter().enumerate() {
+                for b in conflict.between.iter().skip(i + 1) {
+                    if a == b {
+                        continue;
+                    }
+                    for (row, col) in [(a, b), (b, a)] {
+                        *intensities
+                            .entry(row.clone())
+                            .or_default()
+                            .entry(col.clone())
+                            .or_insert(0) += intensity;
+                    }
+                }
+            }
+        }
+
+        Self { intensities }
+    }
+
+    /// The pair of stakeholders with the highest accumulated conflict
+    /// intensity, or `None` when there are no conflicts. Ties break
+    /// lexicographically so the result is deterministic.
+    #[must_use]
+    pub fn top_conflict_pair(&self) -> Option<(String, String, u32)> {
+        let mut top: Option<(String, String, u32)> = None;
+        for (a, row) in &self.intensities {
+            for (b, &intensity) in row {
+                // Each pair appears twice (symmetry); only consider a < b.
+                if a >= b {
+                    continue;
+                }
+                let better = top
+                    .as_ref()
+                    .is_none_or(|(ta, tb, ti)| match intensity.cmp(ti) {
+                        std::cmp::Ordering::Greater => true,
+                        std::cmp::Ordering::Less => false,
+                        std::cmp::Ordering::Equal => {
+                            (a.as_str(), b.as_str()) < (ta.as_str(), tb.as_str())
+                        }
+                    });
+                if better {
+                    top = Some((a.clone(), b.clone(), intensity));
+                }
+            }
+        }
+        top
+    }
+}
+
 /// An alignment between stakeholders.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Alignment {
@@ -426,6 +662,64 @@ pub struct BalancedRecommendation {
     pub mitigation: String,
 }
 
+/// A strong dissenting view preserved alongside the balanced recommendation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MinorityReport {
+    /// The dissenting stakeholder.
+    pub stakeholder: String,
+    /// What they wanted instead, with their stated concerns.
+    pub dissent: String,
+    /// Dissent weight from the stakeholder's [`InfluenceLevel::weight`]
+    /// (3 = high, 2 = medium, 1 = low).
+    pub strength: u32,
+}
+
+/// Collect dissenting stakeholders — those whose preferred option differs
+/// from the recommended one — so strong disagreement is preserved rather than
+/// averaged away.
+///
+/// Option comparison ignores case and surrounding whitespace (the model
+/// restates option names). Reports are sorted by influence weight, highest
+/// first, with ties broken by stakeholder name for deterministic output; a
+/// stakeholder with no stated preference is not a dissenter.
+#[must_use]
+pub fn minority_reports(
+    stakeholders: &[Stakeholder],
+    recommendation: &BalancedRecommendation,
+) -> Vec<MinorityReport> {
+    let recommended = recommendation.option.trim();
+    let mut reports: Vec<MinorityReport> = stakeholders
+        .iter()
+        .filter(|s| {
+            let preferred = s.preferred_option.trim();
+            !preferred.is_empty() && !preferred.eq_ignore_ascii_case(recommended)
+        })
+        .map(|s| {
+            let preferred = s.preferred_option.trim();
+            let dissent = if s.concerns.is_empty() {
+                format!("Prefers '{preferred}' over '{recommended}'")
+            } else {
+                format!(
+                    "Prefers '{preferred}' over '{recommended}' — concerns: {}",
+                    s.concerns.join("; ")
+                )
+            };
+            MinorityReport {
+                stakeholder: s.name.clone(),
+                dissent,
+                strength: s.influence_level.weight(),
+            }
+        })
+        .collect();
+
+    reports.sort_by(|a, b| {
+        b.strength
+            .cmp(&a.strength)
+            .then_with(|| a.stakeholder.cmp(&b.stakeholder))
+    });
+    reports
+}
+
 /// Response from perspectives operation.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct PerspectivesResponse {
@@ -439,12 +733,23 @@ pub struct PerspectivesResponse {
     pub conflicts: Vec<Conflict>,
     /// Alignments identified.
     pub alignments: Vec<Alignment>,
+    /// Symmetric conflict intensity matrix derived from `conflicts`.
+    #[serde(default)]
+    pub conflict_matrix: ConflictMatrix,
     /// Balanced recommendation.
     pub balanced_recommendation: BalancedRecommendation,
+    /// Dissenting stakeholder views, strongest influence first (see
+    /// [`minority_reports`]). Derived from `stakeholders` and the
+    /// recommendation, not model-stated.
+    #[serde(default)]
+    pub minority_reports: Vec<MinorityReport>,
 }
 
 impl PerspectivesResponse {
     /// Create a new perspectives response.
+    ///
+    /// The conflict matrix and minority reports are derived from the parsed
+    /// components so callers never have to keep them in sync by hand.
     #[must_use]
     pub fn new(
         thought_id: impl Into<String>,
@@ -454,13 +759,17 @@ impl PerspectivesResponse {
         alignments: Vec<Alignment>,
         balanced_recommendation: BalancedRecommendation,
     ) -> Self {
+        let conflict_matrix = ConflictMatrix::from_conflicts(&conflicts, &stakeholders);
+        let minority_reports = minority_reports(&stakeholders, &balanced_recommendation);
         Self {
             thought_id: thought_id.into(),
             session_id: session_id.into(),
             stakeholders,
             conflicts,
             alignments,
+            conflict_matrix,
             balanced_recommendation,
+            minority_reports,
         }
     }
 }
@@ -500,4 +809,209 @@ mod tests {
             "\"high\""
         );
     }
+
+    fn stakeholder(name: &str) -> Stakeholder {
+        Stakeholder {
+            name: name.to_string(),
+            interests: vec![],
+            preferred_option: "A".to_string(),
+            concerns: vec![],
+            influence_level: InfluenceLevel::Medium,
+        }
+    }
+
+    fn conflict(between: &[&str], severity: ConflictSeverity) -> Conflict {
+        Conflict {
+            between: between.iter().map(ToString::to_string).collect(),
+            issue: "disagreement".to_string(),
+            severity,
+        }
+    }
+
+    #[test]
+    fn test_conflict_severity_intensity() {
+        assert_eq!(ConflictSeverity::High.intensity(), 3);
+        assert_eq!(ConflictSeverity::Medium.intensity(), 2);
+        assert_eq!(ConflictSeverity::Low.intensity(), 1);
+    }
+
+    #[test]
+    fn test_conflict_matrix_symmetry_and_accumulation() {
+        let stakeholders = vec![
+            stakeholder("Alice"),
+            stakeholder("Bob"),
+            stakeholder("Cara"),
+        ];
+        let conflicts = vec![
+            conflict(&["Alice", "Bob"], ConflictSeverity::High),
+            conflict(&["Alice", "Bob"], ConflictSeverity::Medium),
+            conflict(&["Bob", "Cara"], ConflictSeverity::Low),
+        ];
+
+        let matrix = ConflictMatrix::from_conflicts(&conflicts, &stakeholders);
+
+        // Every stakeholder has a row, even conflict-free pairs.
+        assert_eq!(matrix.intensities.len(), 3);
+
+        // Repeated conflicts accumulate: high (3) + medium (2) = 5.
+        assert_eq!(matrix.intensities["Alice"]["Bob"], 5);
+        assert_eq!(matrix.intensities["Bob"]["Cara"], 1);
+        assert!(!matrix.intensities["Alice"].contains_key("Cara"));
+
+        // Symmetric by construction.
+        for (a, row) in &matrix.intensities {
+            for (b, intensity) in row {
+                assert_eq!(matrix.intensities[b][a], *intensity);
+            }
+        }
+    }
+
+    #[test]
+    fn test_conflict_matrix_top_conflict_pair() {
+        let stakeholders = vec![
+            stakeholder("Alice"),
+            stakeholder("Bob"),
+            stakeholder("Cara"),
+        ];
+        let conflicts = vec![
+            conflict(&["Alice", "Bob"], ConflictSeverity::Medium),
+            conflict(&["Bob", "Cara"], ConflictSeverity::High),
+        ];
+
+        let matrix = ConflictMatrix::from_conflicts(&conflicts, &stakeholders);
+        let (a, b, intensity) = matrix.top_conflict_pair().expect("pair");
+        assert_eq!((a.as_str(), b.as_str()), ("Bob", "Cara"));
+        assert_eq!(intensity, 3);
+    }
+
+    #[test]
+    fn test_conflict_matrix_top_pair_none_without_conflicts() {
+        let matrix = ConflictMatrix::from_conflicts(&[], &[stakeholder("Alice")]);
+        assert!(matrix.top_conflict_pair().is_none());
+    }
+
+    #[test]
+    fn test_conflict_matrix_includes_unlisted_stakeholder() {
+        // "Dana" appears in a conflict but not in the stakeholders list; she
+        // must still get a symmetric row rather than being dropped.
+        let stakeholders = vec![stakeholder("Alice")];
+        let conflicts = vec![conflict(&["Alice", "Dana"], ConflictSeverity::Low)];
+
+        let matrix = ConflictMatrix::from_conflicts(&conflicts, &stakeholders);
+        assert_eq!(matrix.intensities["Alice"]["Dana"], 1);
+        assert_eq!(matrix.intensities["Dana"]["Alice"], 1);
+    }
+
+    #[test]
+    fn test_conflict_matrix_multiparty_conflict() {
+        // A three-way conflict adds intensity to all three pairs.
+        let stakeholders = vec![
+            stakeholder("Alice"),
+            stakeholder("Bob"),
+            stakeholder("Cara"),
+        ];
+        let conflicts = vec![conflict(
+            &["Alice", "Bob", "Cara"],
+            ConflictSeverity::Medium,
+        )];
+
+        let matrix = ConflictMatrix::from_conflicts(&conflicts, &stakeholders);
+        assert_eq!(matrix.intensities["Alice"]["Bob"], 2);
+        assert_eq!(matrix.intensities["Alice"]["Cara"], 2);
+        assert_eq!(matrix.intensities["Bob"]["Cara"], 2);
+    }
+
+    #[test]
+    fn test_perspectives_response_builds_conflict_matrix() {
+        let response = PerspectivesResponse::new(
+            "t-1",
+            "s-1",
+            vec![stakeholder("Alice"), stakeholder("Bob")],
+            vec![conflict(&["Alice", "Bob"], ConflictSeverity::High)],
+            vec![],
+            BalancedRecommendation {
+                option: "A".to_string(),
+                rationale: "balances interests".to_string(),
+                mitigation: "address concerns".to_string(),
+            },
+        );
+
+        assert_eq!(response.conflict_matrix.intensities["Alice"]["Bob"], 3);
+        let (a, b, intensity) = response.conflict_matrix.top_conflict_pair().expect("pair");
+        assert_eq!((a.as_str(), b.as_str()), ("Alice", "Bob"));
+        assert_eq!(intensity, 3);
+    }
+
+    fn recommendation(option: &str) -> BalancedRecommendation {
+        BalancedRecommendation {
+            option: option.to_string(),
+            rationale: "balances interests".to_string(),
+            mitigation: "address concerns".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_influence_level_weight() {
+        assert_eq!(InfluenceLevel::High.weight(), 3);
+        assert_eq!(InfluenceLevel::Medium.weight(), 2);
+        assert_eq!(InfluenceLevel::Low.weight(), 1);
+    }
+
+    #[test]
+    fn test_minority_reports_surface_high_influence_dissent_first() {
+        let mut ceo = stakeholder("CEO");
+        ceo.preferred_option = "B".to_string();
+        ceo.influence_level = InfluenceLevel::High;
+        ceo.concerns = vec!["migration cost".to_string(), "vendor lock-in".to_string()];
+        let mut intern = stakeholder("Intern");
+        intern.preferred_option = "C".to_string();
+        intern.influence_level = InfluenceLevel::Low;
+        let agrees = stakeholder("Ops"); // prefers "A", the recommendation
+
+        let reports = minority_reports(&[intern, agrees, ceo], &recommendation("A"));
+
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].stakeholder, "CEO");
+        assert_eq!(reports[0].strength, 3);
+        assert!(reports[0].dissent.contains("Prefers 'B' over 'A'"));
+        assert!(reports[0]
+            .dissent
+            .contains("migration cost; vendor lock-in"));
+        assert_eq!(reports[1].stakeholder, "Intern");
+        assert_eq!(reports[1].strength, 1);
+    }
+
+    #[test]
+    fn test_minority_reports_ignore_case_and_whitespace_in_option_names() {
+        let mut restated = stakeholder("Alice");
+        restated.preferred_option = "  option a ".to_string();
+        assert!(minority_reports(&[restated], &recommendation("Option A")).is_empty());
+    }
+
+    #[test]
+    fn test_minority_reports_skip_stakeholder_without_preference() {
+        let mut undecided = stakeholder("Bob");
+        undecided.preferred_option = "  ".to_string();
+        assert!(minority_reports(&[undecided], &recommendation("A")).is_empty());
+    }
+
+    #[test]
+    fn test_perspectives_response_builds_minority_reports() {
+        let mut dissenter = stakeholder("Bob");
+        dissenter.preferred_option = "B".to_string();
+        dissenter.influence_level = InfluenceLevel::High;
+
+        let response = PerspectivesResponse::new(
+            "t-1",
+            "s-1",
+            vec![stakeholder("Alice"), dissenter],
+            vec![],
+            vec![],
+            recommendation("A"),
+        );
+
+        assert_eq!(response.minority_reports.len(), 1);
+        assert_eq!(response.minority_reports[0].stakeholder, "Bob");
+        assert_eq!(response.minority_reports[0].strength, 3);
+    }
 }
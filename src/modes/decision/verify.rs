@@ -9,6 +9,7 @@
 use std::cmp::Ordering;
 use std::collections::HashMap;
 
+use super::normalize::{normalize_scores, Normalization};
 use super::types::{
     Criterion, DecisionValidation, PairwiseComparison, PairwiseRank, PreferenceResult,
     RankedOption, TopsisCreterion, TopsisDistances, TopsisRank,
@@ -31,11 +32,17 @@ fn by_score_desc(a: &(String, f64), b: &(String, f64)) -> Ordering {
 /// Recomputes each option's total as `Σ weightᵢ · scoreᵢ`, adopts the
 /// recomputed totals, and re-derives the ranking. `weighted_totals` and
 /// `ranking` are corrected in place.
+///
+/// With a normalization strategy other than `None`, the score matrix is
+/// rescaled in place first and the totals recompute from the normalized
+/// scores. The model's stated totals and ranking come from the raw scores,
+/// so they are replaced silently rather than flagged as inconsistent.
 pub(super) fn verify_weighted(
     criteria: &[Criterion],
-    scores: &HashMap<String, HashMap<String, f64>>,
+    scores: &mut HashMap<String, HashMap<String, f64>>,
     weighted_totals: &mut HashMap<String, f64>,
     ranking: &mut Vec<RankedOption>,
+    normalization: Normalization,
 ) -> DecisionValidation {
     let mut validation = DecisionValidation::default();
 
@@ -47,19 +54,24 @@ pub(super) fn verify_weighted(
             .push(format!("Criteria weights sum to {weight_sum:.3}, not 1.0"));
     }
 
+    let normalized = normalization != Normalization::None;
+    normalize_scores(scores, normalization);
+
     // Recompute each option's weighted total from its per-criterion scores.
     let mut recomputed: HashMap<String, f64> = HashMap::new();
-    for (option, option_scores) in scores {
+    for (option, option_scores) in scores.iter() {
         let total: f64 = criteria
             .iter()
             .map(|c| c.weight * option_scores.get(&c.name).copied().unwrap_or(0.0))
             .sum();
-        if let Some(&stated) = weighted_totals.get(option) {
-            if (stated - total).abs() > TOLERANCE {
-                validation.consistent = false;
-                validation.warnings.push(format!(
-                    "Weighted total for '{option}' was stated as {stated:.3} but recomputes to {total:.3}"
-                ));
+        if !normalized {
+            if let Some(&stated) = weighted_totals.get(option) {
+                if (stated - total).abs() > TOLERANCE {
+                    validation.consistent = false;
+                    validation.warnings.push(format!(
+                        "Weighted total for '{option}' was stated as {stated:.3} but recomputes to {total:.3}"
+                    ));
+                }
             }
         }
         recomputed.insert(option.clone(), total);
@@ -73,10 +85,12 @@ pub(super) fn verify_weighted(
         rank,
     });
 
-    if !same_order(
-        ranking.iter().map(|r| &r.option),
-        new_ranking.iter().map(|r| &r.option),
-    ) {
+    if !normalized
+        && !same_order(
+            ranking.iter().map(|r| &r.option),
+            new_ranking.iter().map(|r| &r.option),
+        )
+    {
         validation.consistent = false;
         validation.ranking_corrected = true;
         validation
@@ -275,6 +289,68 @@ fn same_order<'a>(
     a.eq(b)
 }
 
+/// Consistency ratio below which a pairwise ranking is flagged unreliable.
+pub(super) const MIN_RELIABLE_CONSISTENCY: f64 = 0.8;
+
+/// Fraction of fully-compared option triples whose strict preferences are
+/// transitive.
+///
+/// For every triple of options where all three head-to-head preferences are
+/// strict (no ties), the triple is consistent unless it forms a cycle
+/// (a beats b, b beats c, c beats a). Returns 1.0 when the preferences are
+/// perfectly transitive — or when no triple is fully compared, since there is
+/// then nothing to contradict — and approaches 0.0 as cycles dominate.
+pub(super) fn compute_pairwise_consistency(comparisons: &[PairwiseComparison]) -> f64 {
+    // Set of (winner, loser) strict preferences; ties carry no direction.
+    let mut beats: std::collections::HashSet<(&str, &str)> = std::collections::HashSet::new();
+    let mut options: Vec<&str> = Vec::new();
+    for cmp in comparisons {
+        for option in [cmp.option_a.as_str(), cmp.option_b.as_str()] {
+            if !options.contains(&option) {
+                options.push(option);
+            }
+        }
+        match cmp.preferred {
+            PreferenceResult::OptionA => {
+                beats.insert((&cmp.option_a, &cmp.option_b));
+            }
+            PreferenceResult::OptionB => {
+                beats.insert((&cmp.option_b, &cmp.option_a));
+            }
+            PreferenceResult::Tie => {}
+        }
+    }
+
+    let mut total = 0u32;
+    let mut consistent = 0u32;
+    for (i, a) in options.iter().enumerate() {
+        for (j, b) in options.iter().enumerate().skip(i + 1) {
+            for c in options.iter().skip(j + 1) {
+                let decided = |x: &str, y: &str| beats.contains(&(x, y)) || beats.contains(&(y, x));
+                if !(decided(a, b) && decided(b, c) && decided(a, c)) {
+                    continue;
+                }
+                total += 1;
+                // A triple is cyclic exactly when each option wins once.
+                let cyclic =
+                    (beats.contains(&(a, b)) && beats.contains(&(b, c)) && beats.contains(&(c, a)))
+                        || (beats.contains(&(b, a))
+                            && beats.contains(&(c, b))
+                            && beats.contains(&(a, c)));
+                if !cyclic {
+                    consistent += 1;
+                }
+            }
+        }
+    }
+
+    if total == 0 {
+        1.0
+    } else {
+        f64::from(consistent) / f64::from(total)
+    }
+}
+
 /// Find a 3-cycle (a beats b, b beats c, c beats a) in the strict-preference
 /// relation, if any. Returns the cycle as `(a, b, c)`.
 fn find_intransitivity(comparisons: &[PairwiseComparison]) -> Option<(String, String, String)> {
@@ -344,7 +420,13 @@ mod tests {
             },
         ];
 
-        let v = verify_weighted(&criteria, &scores, &mut totals, &mut ranking);
+        let v = verify_weighted(
+            &criteria,
+            &mut scores,
+            &mut totals,
+            &mut ranking,
+            Normalization::None,
+        );
 
         assert!(!v.consistent);
         assert!(v.ranking_corrected);
@@ -356,7 +438,7 @@ mod tests {
     #[test]
     fn weighted_consistent_when_arithmetic_matches() {
         let criteria = vec![crit("Cost", 0.5), crit("Speed", 0.5)];
-        let scores = HashMap::from([(
+        let mut scores = HashMap::from([(
             "A".to_string(),
             HashMap::from([("Cost".to_string(), 0.6), ("Speed".to_string(), 0.8)]),
         )]);
@@ -367,7 +449,13 @@ mod tests {
             rank: 1,
         }];
 
-        let v = verify_weighted(&criteria, &scores, &mut totals, &mut ranking);
+        let v = verify_weighted(
+            &criteria,
+            &mut scores,
+            &mut totals,
+            &mut ranking,
+            Normalization::None,
+        );
         assert!(v.consistent);
         assert!(!v.ranking_corrected);
         assert!(v.warnings.is_empty());
@@ -376,7 +464,7 @@ mod tests {
     #[test]
     fn weighted_flags_bad_weight_sum() {
         let criteria = vec![crit("Cost", 0.5), crit("Speed", 0.3)];
-        let scores = HashMap::from([(
+        let mut scores = HashMap::from([(
             "A".to_string(),
             HashMap::from([("Cost".to_string(), 0.5), ("Speed".to_string(), 0.5)]),
         )]);
@@ -387,11 +475,74 @@ mod tests {
             rank: 1,
         }];
 
-        let v = verify_weighted(&criteria, &scores, &mut totals, &mut ranking);
+        let v = verify_weighted(
+            &criteria,
+            &mut scores,
+            &mut totals,
+            &mut ranking,
+            Normalization::None,
+        );
         assert!(!v.consistent);
         assert!(v.warnings.iter().any(|w| w.contains("sum to 0.800")));
     }
 
+    #[test]
+    fn weighted_min_max_normalization_changes_ranking_on_disparate_scales() {
+        // Throughput is scored in raw requests/sec and dwarfs the 0-1
+        // usability scale, so without normalization it decides the outcome
+        // alone; with min-max both criteria contribute on [0, 1].
+        let criteria = vec![crit("Throughput", 0.4), crit("Usability", 0.6)];
+        let raw = HashMap::from([
+            (
+                "A".to_string(),
+                HashMap::from([
+                    ("Throughput".to_string(), 1000.0),
+                    ("Usability".to_string(), 0.1),
+                ]),
+            ),
+            (
+                "B".to_string(),
+                HashMap::from([
+                    ("Throughput".to_string(), 900.0),
+                    ("Usability".to_string(), 0.9),
+                ]),
+            ),
+        ]);
+
+        let mut scores = raw.clone();
+        let mut totals = HashMap::new();
+        let mut ranking = Vec::new();
+        verify_weighted(
+            &criteria,
+            &mut scores,
+            &mut totals,
+            &mut ranking,
+            Normalization::None,
+        );
+        assert_eq!(ranking[0].option, "A");
+
+        let mut scores = raw;
+        let mut totals = HashMap::new();
+        let mut ranking = Vec::new();
+        let v = verify_weighted(
+            &criteria,
+            &mut scores,
+            &mut totals,
+            &mut ranking,
+            Normalization::MinMax,
+        );
+        assert_eq!(ranking[0].option, "B");
+        assert!((totals["A"] - 0.4).abs() < 1e-9);
+        assert!((totals["B"] - 0.6).abs() < 1e-9);
+        // The scores in the result are the normalized matrix.
+        assert!((scores["A"]["Throughput"] - 1.0).abs() < 1e-9);
+        assert!((scores["B"]["Throughput"]).abs() < 1e-9);
+        // Replacing the model's raw-scale arithmetic is the point, not an
+        // inconsistency.
+        assert!(v.consistent);
+        assert!(!v.ranking_corrected);
+    }
+
     #[test]
     fn topsis_recomputes_closeness() {
         let criteria = vec![TopsisCreterion {
@@ -476,6 +627,82 @@ mod tests {
         assert!(v.warnings.iter().any(|w| w.contains("Win count for 'A'")));
     }
 
+    #[test]
+    fn pairwise_consistency_transitive_is_one() {
+        let cmp = |a: &str, b: &str, pref: PreferenceResult| PairwiseComparison {
+            option_a: a.to_string(),
+            option_b: b.to_string(),
+            preferred: pref,
+            strength: PreferenceStrength::Moderate,
+            reasoning: String::new(),
+        };
+        // A > B > C with A > C: the single triple is transitive.
+        let comparisons = vec![
+            cmp("A", "B", PreferenceResult::OptionA),
+            cmp("B", "C", PreferenceResult::OptionA),
+            cmp("A", "C", PreferenceResult::OptionA),
+        ];
+        assert_eq!(compute_pairwise_consistency(&comparisons), 1.0);
+    }
+
+    #[test]
+    fn pairwise_consistency_cycle_is_zero() {
+        let cmp = |a: &str, b: &str, pref: PreferenceResult| PairwiseComparison {
+            option_a: a.to_string(),
+            option_b: b.to_string(),
+            preferred: pref,
+            strength: PreferenceStrength::Moderate,
+            reasoning: String::new(),
+        };
+        // A > B > C > A: the single triple cycles.
+        let comparisons = vec![
+            cmp("A", "B", PreferenceResult::OptionA),
+            cmp("B", "C", PreferenceResult::OptionA),
+            cmp("A", "C", PreferenceResult::OptionB),
+        ];
+        assert_eq!(compute_pairwise_consistency(&comparisons), 0.0);
+    }
+
+    #[test]
+    fn pairwise_consistency_counts_fraction_of_triples() {
+        let cmp = |a: &str, b: &str, pref: PreferenceResult| PairwiseComparison {
+            option_a: a.to_string(),
+            option_b: b.to_string(),
+            preferred: pref,
+            strength: PreferenceStrength::Moderate,
+            reasoning: String::new(),
+        };
+        // A/B/C cycle; D loses to everyone, so the three triples through D
+        // are transitive. 3 of 4 fully-compared triples are consistent.
+        let comparisons = vec![
+            cmp("A", "B", PreferenceResult::OptionA),
+            cmp("B", "C", PreferenceResult::OptionA),
+            cmp("C", "A", PreferenceResult::OptionA),
+            cmp("A", "D", PreferenceResult::OptionA),
+            cmp("B", "D", PreferenceResult::OptionA),
+            cmp("C", "D", PreferenceResult::OptionA),
+        ];
+        assert_eq!(compute_pairwise_consistency(&comparisons), 0.75);
+    }
+
+    #[test]
+    fn pairwise_consistency_ignores_ties_and_missing_pairs() {
+        let cmp = |a: &str, b: &str, pref: PreferenceResult| PairwiseComparison {
+            option_a: a.to_string(),
+            option_b: b.to_string(),
+            preferred: pref,
+            strength: PreferenceStrength::Moderate,
+            reasoning: String::new(),
+        };
+        // No triple has all three strict preferences, so nothing contradicts.
+        let comparisons = vec![
+            cmp("A", "B", PreferenceResult::OptionA),
+            cmp("B", "C", PreferenceResult::Tie),
+        ];
+        assert_eq!(compute_pairwise_consistency(&comparisons), 1.0);
+        assert_eq!(compute_pairwise_consistency(&[]), 1.0);
+    }
+
     #[test]
     fn topsis_rationale_flags_near_tie() {
         let ranking = vec![
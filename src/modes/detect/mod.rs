@@ -1,9 +1,12 @@
 //! Bias and fallacy detection mode.
 //!
-//! This mode provides three operations:
+//! This mode provides five operations:
 //! - `biases`: Detect cognitive biases in reasoning
 //! - `fallacies`: Detect logical fallacies in arguments
 //! - `knowledge_gaps`: Find absent information that could change the conclusion
+//! - `counterargue`: Generate steelmanned counterarguments against a claim
+//! - `circular`: Find circular justification loops across a session's thoughts
+//!   (purely structural — no API call)
 //!
 //! # Output Schema
 //!
@@ -21,29 +24,45 @@
 //! - `gaps`: List of absent information items with category and investigation steps
 //! - `unchallenged_assumptions`: Premises taken as given without verification
 //! - `overall_assessment`: Summary including gap count and completeness score
+//!
+//! ## Counterargue Operation
+//! - `counterarguments`: Steelmanned opposing arguments with rationale and rebuttal assessment
+//! - `overall_assessment`: Summary including counterargument count and claim resilience
 
 mod parsing;
 mod types;
+mod verify;
 
 pub use types::{
-    ArgumentStructure, ArgumentValidity, BiasAssessment, BiasSeverity, BiasesResponse,
-    DetectedBias, DetectedFallacy, FallaciesResponse, FallacyAssessment, FallacyCategory,
-    FallacySeverity, GapCategory, KnowledgeGap, KnowledgeGapAssessment, KnowledgeGapsResponse,
+    bias_interactions, ArgumentStructure, ArgumentValidity, BiasAssessment, BiasInteraction,
+    BiasSeverity, BiasesResponse, CircularChain, CircularResponse, Counterargument,
+    CounterargumentAssessment, CounterargumentResponse, DetectedBias, DetectedFallacy,
+    FallaciesResponse, FallacyAssessment, FallacyCategory, FallacySeverity, GapCategory,
+    KnowledgeGap, KnowledgeGapAssessment, KnowledgeGapsResponse, PremiseAssessment,
+    PremiseVerification, RebuttalStrength,
 };
 
 use std::fmt::Write as _;
 
 use crate::error::ModeError;
-use crate::modes::{extract_json, generate_thought_id, validate_content};
-use crate::prompts::{detect_biases_prompt, detect_fallacies_prompt, detect_knowledge_gaps_prompt};
+use crate::modes::{
+    apply_memory_update, chunk_content, extract_json, generate_thought_id, language_instruction,
+    load_working_memory_block, reject_unknown_keys, validate_content, ContentChunk, ModeFlags,
+    CHUNK_MAX_BYTES,
+};
+use crate::prompts::{
+    detect_biases_prompt, detect_counterargue_prompt, detect_fallacies_prompt,
+    detect_knowledge_gaps_prompt, detect_premise_verification_prompt, detect_retry_addendum,
+};
 use crate::traits::{
     AnthropicClientTrait, CompletionConfig, Message, Session, StorageTrait, Thought,
 };
 
 use parsing::{
-    parse_argument_structure, parse_bias_assessment, parse_biases, parse_fallacies,
+    parse_argument_structure, parse_bias_assessment, parse_biases,
+    parse_counterargument_assessment, parse_counterarguments, parse_fallacies,
     parse_fallacy_assessment, parse_knowledge_gap_assessment, parse_knowledge_gaps,
-    parse_unchallenged_assumptions,
+    parse_premise_assessments, parse_unchallenged_assumptions,
 };
 
 // ============================================================================
@@ -75,6 +94,17 @@ where
 {
     storage: S,
     client: C,
+    /// Behavior flags injected from server configuration (all off by default).
+    flags: ModeFlags,
+    /// Effective output language (per-request override merged with the
+    /// configured default at the server boundary).
+    language: Option<String>,
+    /// When set, oversized content is split on paragraph boundaries and each
+    /// chunk analyzed separately, with findings merged and deduplicated.
+    chunking: bool,
+    /// When set, `fallacies` runs a follow-up scoring each identified premise
+    /// for plausibility and evidentiary support.
+    premise_verification: bool,
 }
 
 impl<S, C> DetectMode<S, C>
@@ -85,7 +115,53 @@ where
     /// Create a new detect mode instance.
     #[must_use]
     pub fn new(storage: S, client: C) -> Self {
-        Self { storage, client }
+        Self {
+            storage,
+            client,
+            flags: ModeFlags::default(),
+            language: None,
+            chunking: false,
+            premise_verification: false,
+        }
+    }
+
+    /// Set the output language for natural-language response fields. The
+    /// server passes the per-request override merged with the configured
+    /// `RESPONSE_LANGUAGE` default; `None` adds no language instruction.
+    #[must_use]
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Inject the server's behavior flags ([`ModeFlags`]). A mode built
+    /// without them — as in most tests — uses the all-off defaults.
+    #[must_use]
+    pub fn with_flags(mut self, flags: ModeFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Enable content chunking: content larger than [`CHUNK_MAX_BYTES`] is
+    /// split on paragraph boundaries, each chunk analyzed separately, and the
+    /// per-chunk findings merged (deduplicated, with cited passages mapped
+    /// back to their offset in the original content). Content that fits in a
+    /// single chunk takes the ordinary path regardless of this flag.
+    #[must_use]
+    pub const fn with_chunking(mut self, chunking: bool) -> Self {
+        self.chunking = chunking;
+        self
+    }
+
+    /// Enable premise verification for `fallacies`: after detection, a
+    /// follow-up call scores each premise in the argument structure for
+    /// plausibility and evidentiary support (chaining detect into an
+    /// evidence-style assessment). An argument with no clear premises gets
+    /// an empty result with an explanatory note, without a second call.
+    #[must_use]
+    pub const fn with_premise_verification(mut self, premise_verification: bool) -> Self {
+        self.premise_verification = premise_verification;
+        self
     }
 
     /// Detect cognitive biases in content.
@@ -112,37 +188,60 @@ where
     ) -> Result<BiasesResponse, ModeError> {
         validate_content(content)?;
 
+        if self.chunking {
+            let chunks = chunk_content(content, CHUNK_MAX_BYTES);
+            if chunks.len() > 1 {
+                return self.biases_chunked(&chunks, session_id).await;
+            }
+        }
+
         let has_prior_session = session_id.is_some();
         let session = self.get_or_create_session(session_id).await?;
 
         let prompt = detect_biases_prompt();
-        let user_message = self
-            .build_user_message(prompt, content, &session.id, has_prior_session)
-            .await;
-
-        let messages = vec![Message::user(user_message)];
-        let config = CompletionConfig::new()
-            .with_max_tokens(16384)
-            .with_temperature(0.3) // Lower temp for analytical tasks
-            .with_deep_thinking();
-
-        let response = self.client.complete(messages, config).await?;
-        let json = extract_json(&response.content)?;
-
-        // Parse biases_detected array
-        let biases_detected = parse_biases(&json)?;
+        let json = self
+            .detect_completion(prompt, content, &session.id, has_prior_session)
+            .await?;
+
+        // Parse biases_detected array, then cross-check each cited evidence
+        // string against the source content (hallucinated citations → verified: false).
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &["biases_detected", "overall_assessment"],
+        )?;
+        let mut biases_detected = parse_biases(&json)?;
+        verify::verify_biases(&mut biases_detected, content);
 
         // Parse overall_assessment
-        let overall_assessment = parse_bias_assessment(&json)?;
+        let mut overall_assessment = parse_bias_assessment(&json)?;
 
         // Parse debiased_version
-        let debiased_version = json
-            .get("debiased_version")
-            .and_then(serde_json::Value::as_str)
-            .ok_or_else(|| ModeError::MissingField {
-                field: "debiased_version".to_string(),
-            })?
-            .to_string();
+        let mut debiased_version = parse_debiased_version(&json)?;
+
+        // Opt-in degenerate-output gate (`RETRY_DEGENERATE`): a clean-parse,
+        // zero-finding result gets exactly one sharper rerun. A populated
+        // rerun wins; an empty or failed one keeps the original.
+        if biases_detected.is_empty() && self.flags.retry_degenerate {
+            tracing::info!("Bias detection found nothing — retrying once with a sharper prompt");
+            let retry_prompt = format!("{prompt}\n\n{}", detect_retry_addendum());
+            match self
+                .retry_biases(&retry_prompt, content, &session.id, has_prior_session)
+                .await
+            {
+                Ok(Some((biases, assessment, debiased))) => {
+                    biases_detected = biases;
+                    overall_assessment = assessment;
+                    debiased_version = debiased;
+                }
+                Ok(None) => {
+                    tracing::info!("Retry also found no biases — keeping the empty result");
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, "Degenerate-output retry failed — keeping the first result");
+                }
+            }
+        }
 
         // Save thought
         let thought_id = generate_thought_id();
@@ -167,6 +266,71 @@ where
         ))
     }
 
+    /// Chunked bias detection: analyze each chunk separately, then merge the
+    /// deduplicated findings with citation offsets mapped back to the
+    /// original content.
+    async fn biases_chunked(
+        &self,
+        chunks: &[ContentChunk],
+        session_id: Option<String>,
+    ) -> Result<BiasesResponse, ModeError> {
+        let has_prior_session = session_id.is_some();
+        let session = self.get_or_create_session(session_id).await?;
+        let prompt = detect_biases_prompt();
+
+        let mut all_biases = Vec::new();
+        let mut assessments = Vec::new();
+        let mut debiased_parts = Vec::new();
+        for chunk in chunks {
+            let json = self
+                .detect_completion(prompt, &chunk.text, &session.id, has_prior_session)
+                .await?;
+            reject_unknown_keys(
+                self.flags.strict_parsing,
+                &json,
+                &["biases_detected", "overall_assessment"],
+            )?;
+
+            let mut biases = parse_biases(&json)?;
+            verify::verify_biases(&mut biases, &chunk.text);
+            for bias in &mut biases {
+                bias.offset = chunk.locate(&bias.evidence);
+            }
+            all_biases.extend(biases);
+            assessments.push(parse_bias_assessment(&json)?);
+            debiased_parts.push(parse_debiased_version(&json)?);
+        }
+
+        let biases_detected = dedup_biases(all_biases);
+        let overall_assessment = merge_bias_assessments(&biases_detected, &assessments);
+        let debiased_version = debiased_parts.join("\n\n");
+
+        let thought_id = generate_thought_id();
+        let thought = Thought::new(
+            &thought_id,
+            &session.id,
+            format!(
+                "Bias detection: {} biases found across {} chunks",
+                biases_detected.len(),
+                chunks.len()
+            ),
+            "detect_biases",
+            overall_assessment.reasoning_quality,
+        );
+
+        if let Err(e) = self.storage.save_thought(&thought).await {
+            tracing::warn!(error = %e, "Storage write failed — reasoning result preserved, thought not persisted");
+        }
+
+        Ok(BiasesResponse::new(
+            thought_id,
+            session.id,
+            biases_detected,
+            overall_assessment,
+            debiased_version,
+        ))
+    }
+
     /// Detect logical fallacies in content.
     ///
     /// # Arguments
@@ -191,31 +355,64 @@ where
     ) -> Result<FallaciesResponse, ModeError> {
         validate_content(content)?;
 
+        if self.chunking {
+            let chunks = chunk_content(content, CHUNK_MAX_BYTES);
+            if chunks.len() > 1 {
+                return self.fallacies_chunked(&chunks, session_id).await;
+            }
+        }
+
         let has_prior_session = session_id.is_some();
         let session = self.get_or_create_session(session_id).await?;
 
         let prompt = detect_fallacies_prompt();
-        let user_message = self
-            .build_user_message(prompt, content, &session.id, has_prior_session)
-            .await;
-
-        let messages = vec![Message::user(user_message)];
-        let config = CompletionConfig::new()
-            .with_max_tokens(16384)
-            .with_temperature(0.3)
-            .with_deep_thinking();
-
-        let response = self.client.complete(messages, config).await?;
-        let json = extract_json(&response.content)?;
-
-        // Parse fallacies_detected array
-        let fallacies_detected = parse_fallacies(&json)?;
+        let json = self
+            .detect_completion(prompt, content, &session.id, has_prior_session)
+            .await?;
+
+        // Parse fallacies_detected array, then cross-check each cited passage
+        // against the source content (hallucinated citations → verified: false).
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &[
+                "fallacies_detected",
+                "argument_structure",
+                "overall_assessment",
+            ],
+        )?;
+        let mut fallacies_detected = parse_fallacies(&json)?;
+        verify::verify_fallacies(&mut fallacies_detected, content);
 
         // Parse argument_structure
-        let argument_structure = parse_argument_structure(&json)?;
+        let mut argument_structure = parse_argument_structure(&json)?;
 
         // Parse overall_assessment
-        let overall_assessment = parse_fallacy_assessment(&json)?;
+        let mut overall_assessment = parse_fallacy_assessment(&json)?;
+
+        // Opt-in degenerate-output gate (`RETRY_DEGENERATE`): a clean-parse,
+        // zero-finding result gets exactly one sharper rerun. A populated
+        // rerun wins; an empty or failed one keeps the original.
+        if fallacies_detected.is_empty() && self.flags.retry_degenerate {
+            tracing::info!("Fallacy detection found nothing — retrying once with a sharper prompt");
+            let retry_prompt = format!("{prompt}\n\n{}", detect_retry_addendum());
+            match self
+                .retry_fallacies(&retry_prompt, content, &session.id, has_prior_session)
+                .await
+            {
+                Ok(Some((fallacies, structure, assessment))) => {
+                    fallacies_detected = fallacies;
+                    argument_structure = structure;
+                    overall_assessment = assessment;
+                }
+                Ok(None) => {
+                    tracing::info!("Retry also found no fallacies — keeping the empty result");
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, "Degenerate-output retry failed — keeping the first result");
+                }
+            }
+        }
 
         // Save thought
         let thought_id = generate_thought_id();
@@ -234,13 +431,213 @@ where
             tracing::warn!(error = %e, "Storage write failed — reasoning result preserved, thought not persisted");
         }
 
-        Ok(FallaciesResponse::new(
+        let mut response = FallaciesResponse::new(
             thought_id,
             session.id,
             fallacies_detected,
             argument_structure,
             overall_assessment,
-        ))
+        );
+        if self.premise_verification {
+            response.premise_verification =
+                Some(self.verify_premises(&response.argument_structure).await?);
+        }
+        Ok(response)
+    }
+
+    /// Chunked fallacy detection: analyze each chunk separately, then merge
+    /// the deduplicated findings and unify the per-chunk argument structures.
+    async fn fallacies_chunked(
+        &self,
+        chunks: &[ContentChunk],
+        session_id: Option<String>,
+    ) -> Result<FallaciesResponse, ModeError> {
+        let has_prior_session = session_id.is_some();
+        let session = self.get_or_create_session(session_id).await?;
+        let prompt = detect_fallacies_prompt();
+
+        let mut all_fallacies = Vec::new();
+        let mut structures = Vec::new();
+        let mut assessments = Vec::new();
+        for chunk in chunks {
+            let json = self
+                .detect_completion(prompt, &chunk.text, &session.id, has_prior_session)
+                .await?;
+            reject_unknown_keys(
+                self.flags.strict_parsing,
+                &json,
+                &[
+                    "fallacies_detected",
+                    "argument_structure",
+                    "overall_assessment",
+                ],
+            )?;
+
+            let mut fallacies = parse_fallacies(&json)?;
+            verify::verify_fallacies(&mut fallacies, &chunk.text);
+            for fallacy in &mut fallacies {
+                fallacy.offset = chunk.locate(&fallacy.passage);
+            }
+            all_fallacies.extend(fallacies);
+            structures.push(parse_argument_structure(&json)?);
+            assessments.push(parse_fallacy_assessment(&json)?);
+        }
+
+        let fallacies_detected = dedup_fallacies(all_fallacies);
+        let argument_structure = merge_argument_structures(&structures);
+        let overall_assessment = merge_fallacy_assessments(&fallacies_detected, &assessments);
+
+        let thought_id = generate_thought_id();
+        let thought = Thought::new(
+            &thought_id,
+            &session.id,
+            format!(
+                "Fallacy detection: {} fallacies found across {} chunks",
+                fallacies_detected.len(),
+                chunks.len()
+            ),
+            "detect_fallacies",
+            overall_assessment.argument_strength,
+        );
+
+        if let Err(e) = self.storage.save_thought(&thought).await {
+            tracing::warn!(error = %e, "Storage write failed — reasoning result preserved, thought not persisted");
+        }
+
+        let mut response = FallaciesResponse::new(
+            thought_id,
+            session.id,
+            fallacies_detected,
+            argument_structure,
+            overall_assessment,
+        );
+        if self.premise_verification {
+            response.premise_verification =
+                Some(self.verify_premises(&response.argument_structure).await?);
+        }
+        Ok(response)
+    }
+
+    /// Single degenerate-output retry for `biases`: rerun the completion with
+    /// the sharper prompt and return the parsed result only when the rerun
+    /// actually found something.
+    async fn retry_biases(
+        &self,
+        prompt: &str,
+        content: &str,
+        session_id: &str,
+        has_prior_session: bool,
+    ) -> Result<Option<(Vec<DetectedBias>, BiasAssessment, String)>, ModeError> {
+        let json = self
+            .detect_completion(prompt, content, session_id, has_prior_session)
+            .await?;
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &["biases_detected", "overall_assessment"],
+        )?;
+        let mut biases = parse_biases(&json)?;
+        if biases.is_empty() {
+            return Ok(None);
+        }
+        verify::verify_biases(&mut biases, content);
+        let assessment = parse_bias_assessment(&json)?;
+        let debiased = parse_debiased_version(&json)?;
+        Ok(Some((biases, assessment, debiased)))
+    }
+
+    /// Single degenerate-output retry for `fallacies`: rerun the completion
+    /// with the sharper prompt and return the parsed result only when the
+    /// rerun actually found something.
+    async fn retry_fallacies(
+        &self,
+        prompt: &str,
+        content: &str,
+        session_id: &str,
+        has_prior_session: bool,
+    ) -> Result<Option<(Vec<DetectedFallacy>, ArgumentStructure, FallacyAssessment)>, ModeError>
+    {
+        let json = self
+            .detect_completion(prompt, content, session_id, has_prior_session)
+            .await?;
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &[
+                "fallacies_detected",
+                "argument_structure",
+                "overall_assessment",
+            ],
+        )?;
+        let mut fallacies = parse_fallacies(&json)?;
+        if fallacies.is_empty() {
+            return Ok(None);
+        }
+        verify::verify_fallacies(&mut fallacies, content);
+        let structure = parse_argument_structure(&json)?;
+        let assessment = parse_fallacy_assessment(&json)?;
+        Ok(Some((fallacies, structure, assessment)))
+    }
+
+    /// Premise verification follow-up: score each identified premise for
+    /// plausibility and evidentiary support. An argument with no clear
+    /// premises returns an empty result with an explanatory note, without
+    /// an API call.
+    async fn verify_premises(
+        &self,
+        structure: &ArgumentStructure,
+    ) -> Result<PremiseVerification, ModeError> {
+        if structure.premises.is_empty() {
+            return Ok(PremiseVerification {
+                assessments: vec![],
+                note: Some(
+                    "No clear premises were identified in the argument — nothing to verify."
+                        .to_string(),
+                ),
+            });
+        }
+
+        let prompt = detect_premise_verification_prompt();
+        let mut message = format!(
+            "{prompt}{}\n\nConclusion the premises support:\n{}\n\nPremises to verify:\n",
+            language_instruction(self.language.as_deref(), &structure.conclusion),
+            structure.conclusion,
+        );
+        for (idx, premise) in structure.premises.iter().enumerate() {
+            let _ = writeln!(message, "{}. {premise}", idx + 1);
+        }
+
+        let messages = vec![Message::user(message)];
+        let config = CompletionConfig::new()
+            .with_mode("detect")
+            .with_max_tokens(16384)
+            .with_temperature(0.3)
+            .with_deep_thinking()
+            // The verification prompt opens the message verbatim — cacheable.
+            .with_cached_prompt_len(prompt.len());
+
+        let response = self.client.complete(messages, config).await?;
+        let json = extract_json(&response.content)?;
+        reject_unknown_keys(self.flags.strict_parsing, &json, &["premise_assessments"])?;
+        let assessments = parse_premise_assessments(&json)?;
+
+        // Every premise must come back scored — a partial result would leave
+        // the caller unsure which premises were actually verified.
+        if assessments.len() != structure.premises.len() {
+            return Err(ModeError::InvalidValue {
+                field: "premise_assessments".to_string(),
+                reason: format!(
+                    "expected one assessment per premise ({} premises, got {})",
+                    structure.premises.len(),
+                    assessments.len()
+                ),
+            });
+        }
+
+        Ok(PremiseVerification {
+            assessments,
+            note: None,
+        })
     }
 
     /// Detect knowledge gaps — absent information that could change the conclusion.
@@ -276,20 +673,16 @@ where
         let session = self.get_or_create_session(session_id).await?;
 
         let prompt = detect_knowledge_gaps_prompt();
-        let user_message = self
-            .build_user_message(prompt, content, &session.id, has_prior_session)
-            .await;
-
-        let messages = vec![Message::user(user_message)];
-        let config = CompletionConfig::new()
-            .with_max_tokens(16384)
-            .with_temperature(0.3)
-            .with_deep_thinking();
-
-        let response = self.client.complete(messages, config).await?;
-        let json = extract_json(&response.content)?;
+        let json = self
+            .detect_completion(prompt, content, &session.id, has_prior_session)
+            .await?;
 
         // Parse gaps array
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &["gaps", "unchallenged_assumptions", "overall_assessment"],
+        )?;
         let gaps = parse_knowledge_gaps(&json)?;
 
         // Parse unchallenged assumptions (optional — returns empty vec if absent)
@@ -321,10 +714,173 @@ where
         ))
     }
 
+    /// Generate steelmanned counterarguments against a claim.
+    ///
+    /// Argues against the claim as its most capable opponent would: each
+    /// counterargument carries its supporting rationale, a strength score, and
+    /// an assessment of how well the original claim can rebut it. Distinct from
+    /// fallacy detection (which critiques the claim's own reasoning): this
+    /// constructs the **opposing** case.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The claim to argue against
+    /// * `session_id` - Optional session ID for context continuity
+    ///
+    /// # Returns
+    ///
+    /// A [`CounterargumentResponse`] containing counterarguments and assessment.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModeError`] if:
+    /// - Content is empty
+    /// - API call fails
+    /// - Response parsing fails
+    pub async fn counterargue(
+        &self,
+        content: &str,
+        session_id: Option<String>,
+    ) -> Result<CounterargumentResponse, ModeError> {
+        validate_content(content)?;
+
+        let has_prior_session = session_id.is_some();
+        let session = self.get_or_create_session(session_id).await?;
+
+        let prompt = detect_counterargue_prompt();
+        let json = self
+            .detect_completion(prompt, content, &session.id, has_prior_session)
+            .await?;
+
+        // Parse counterarguments array
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &["counterarguments", "overall_assessment"],
+        )?;
+        let counterarguments = parse_counterarguments(&json)?;
+
+        // Parse overall_assessment
+        let overall_assessment = parse_counterargument_assessment(&json)?;
+
+        // Save thought
+        let thought_id = generate_thought_id();
+        let thought = Thought::new(
+            &thought_id,
+            &session.id,
+            format!(
+                "Counterargument generation: {} counterarguments found",
+                counterarguments.len()
+            ),
+            "detect_counterargue",
+            overall_assessment.claim_resilience,
+        );
+
+        if let Err(e) = self.storage.save_thought(&thought).await {
+            tracing::warn!(error = %e, "Storage write failed — reasoning result preserved, thought not persisted");
+        }
+
+        Ok(CounterargumentResponse::new(
+            thought_id,
+            session.id,
+            counterarguments,
+            overall_assessment,
+        ))
+    }
+
+    /// Detect circular justification across a session's stored thoughts.
+    ///
+    /// Builds a justification graph — an edge from thought A to thought B
+    /// when A's content references B's id — and runs the same cycle
+    /// detection the graph mode uses for imports. Unlike the other detect
+    /// operations this is purely structural: no API call is made and
+    /// nothing is saved.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_id` - The session whose thoughts to analyze
+    ///
+    /// # Returns
+    ///
+    /// A [`CircularResponse`] listing each justification loop with the
+    /// participating thought ids; an empty `cycles` list means the session's
+    /// justification graph is acyclic.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModeError`] if the session id is empty or thoughts cannot
+    /// be loaded.
+    pub async fn circular(&self, session_id: &str) -> Result<CircularResponse, ModeError> {
+        if session_id.trim().is_empty() {
+            return Err(ModeError::InvalidValue {
+                field: "session_id".to_string(),
+                reason: "session_id must not be empty".to_string(),
+            });
+        }
+
+        let thoughts =
+            self.storage
+                .get_thoughts(session_id)
+                .await
+                .map_err(|e| ModeError::ApiUnavailable {
+                    message: format!("Failed to load thoughts: {e}"),
+                })?;
+
+        // Justification edges: A → B when A's content mentions B's id.
+        let ids: Vec<&str> = thoughts.iter().map(|t| t.id.as_str()).collect();
+        let mut edges: Vec<(&str, &str)> = Vec::new();
+        for thought in &thoughts {
+            for id in &ids {
+                if *id != thought.id && thought.content.contains(id) {
+                    edges.push((thought.id.as_str(), *id));
+                }
+            }
+        }
+        edges.sort_unstable();
+
+        let cyclic = crate::modes::graph::cyclic_ids(&ids, &edges);
+        let cycles = extract_cycles(&cyclic, &edges);
+
+        Ok(CircularResponse::new(
+            session_id,
+            thoughts.len(),
+            edges.len(),
+            cycles,
+        ))
+    }
+
     // ========================================================================
     // Private Helpers
     // ========================================================================
 
+    /// Run a single detect completion: build the user message, call the API,
+    /// extract the JSON payload, and apply any working-memory update.
+    async fn detect_completion(
+        &self,
+        prompt: &str,
+        content: &str,
+        session_id: &str,
+        has_prior_session: bool,
+    ) -> Result<serde_json::Value, ModeError> {
+        let user_message = self
+            .build_user_message(prompt, content, session_id, has_prior_session)
+            .await;
+
+        let messages = vec![Message::user(user_message)];
+        let config = CompletionConfig::new()
+            .with_mode("detect")
+            .with_max_tokens(16384)
+            .with_temperature(0.3) // Lower temp for analytical tasks
+            .with_deep_thinking()
+            // The operation prompt opens the message verbatim — cacheable.
+            .with_cached_prompt_len(prompt.len());
+
+        let response = self.client.complete(messages, config).await?;
+        let json = extract_json(&response.content)?;
+        apply_memory_update(&self.storage, session_id, &json).await;
+        Ok(json)
+    }
+
     async fn get_or_create_session(
         &self,
         session_id: Option<String>,
@@ -380,17 +936,27 @@ where
         session_id: &str,
         has_prior_session: bool,
     ) -> String {
-        let prior_context = if has_prior_session {
-            self.load_prior_context(session_id).await
+        let (prior_context, memory_block) = if has_prior_session {
+            (
+                self.load_prior_context(session_id).await,
+                load_working_memory_block(&self.storage, session_id).await,
+            )
         } else {
-            String::new()
+            (String::new(), String::new())
         };
 
-        if prior_context.is_empty() {
-            format!("{prompt}\n\nContent to analyze:\n{content}")
-        } else {
-            format!("{prompt}\n\n{prior_context}\nContent to analyze:\n{content}")
+        let mut message = format!(
+            "{prompt}{}\n",
+            language_instruction(self.language.as_deref(), content)
+        );
+        if !memory_block.is_empty() {
+            let _ = write!(message, "\n{memory_block}");
+        }
+        if !prior_context.is_empty() {
+            let _ = write!(message, "\n{prior_context}");
         }
+        let _ = write!(message, "\nContent to analyze:\n{content}");
+        message
     }
 }
 
@@ -399,6 +965,207 @@ const MAX_CONTEXT_THOUGHTS: usize = 5;
 /// Maximum characters per prior thought when building the context block.
 const MAX_CONTEXT_THOUGHT_CHARS: usize = 600;
 
+/// Parse the required `debiased_version` string from the response JSON.
+fn parse_debiased_version(json: &serde_json::Value) -> Result<String, ModeError> {
+    json.get("debiased_version")
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| ModeError::MissingField {
+            field: "debiased_version".to_string(),
+        })
+        .map(ToString::to_string)
+}
+
+/// Drop repeated findings across chunks, keyed on the bias name
+/// (case-insensitive) plus the cited evidence. First occurrence wins, so the
+/// earliest chunk's offset is kept.
+fn dedup_biases(biases: Vec<DetectedBias>) -> Vec<DetectedBias> {
+    let mut seen = std::collections::HashSet::new();
+    biases
+        .into_iter()
+        .filter(|b| seen.insert((b.bias.to_lowercase(), b.evidence.clone())))
+        .collect()
+}
+
+/// Drop repeated findings across chunks, keyed on the fallacy name
+/// (case-insensitive) plus the cited passage. First occurrence wins.
+fn dedup_fallacies(fallacies: Vec<DetectedFallacy>) -> Vec<DetectedFallacy> {
+    let mut seen = std::collections::HashSet::new();
+    fallacies
+        .into_iter()
+        .filter(|f| seen.insert((f.fallacy.to_lowercase(), f.passage.clone())))
+        .collect()
+}
+
+/// Merge per-chunk bias assessments: counts and severity ranking are
+/// recomputed from the merged findings; reasoning quality is averaged.
+#[allow(clippy::cast_precision_loss)]
+fn merge_bias_assessments(
+    biases: &[DetectedBias],
+    chunk_assessments: &[BiasAssessment],
+) -> BiasAssessment {
+    let most_severe = biases
+        .iter()
+        .max_by_key(|b| bias_severity_rank(&b.severity))
+        .map_or_else(|| "None".to_string(), |b| b.bias.clone());
+    let conclusion_altering: Vec<&str> = biases
+        .iter()
+        .filter(|b| b.changes_conclusion == "yes")
+        .map(|b| b.bias.as_str())
+        .collect();
+    let reasoning_quality = if chunk_assessments.is_empty() {
+        0.0
+    } else {
+        chunk_assessments
+            .iter()
+            .map(|a| a.reasoning_quality)
+            .sum::<f64>()
+            / chunk_assessments.len() as f64
+    };
+
+    BiasAssessment {
+        bias_count: u32::try_from(biases.len()).unwrap_or(u32::MAX),
+        most_severe,
+        conclusion_altering_biases: conclusion_altering.join(", "),
+        reasoning_quality,
+    }
+}
+
+/// Merge per-chunk argument structures: premises are concatenated without
+/// duplicates, the conclusion comes from the last chunk that stated one
+/// (conclusions typically close a document), and the overall validity is the
+/// weakest across chunks — one invalid section makes the argument invalid.
+fn merge_argument_structures(structures: &[ArgumentStructure]) -> ArgumentStructure {
+    let mut premises: Vec<String> = Vec::new();
+    for structure in structures {
+        for premise in &structure.premises {
+            if !premises.contains(premise) {
+                premises.push(premise.clone());
+            }
+        }
+    }
+    let conclusion = structures
+        .iter()
+        .rev()
+        .find(|s| !s.conclusion.is_empty())
+        .map(|s| s.conclusion.clone())
+        .unwrap_or_default();
+    let validity = structures
+        .iter()
+        .map(|s| &s.validity)
+        .min_by_key(|v| validity_rank(v))
+        .cloned()
+        .unwrap_or(ArgumentValidity::Valid);
+
+    ArgumentStructure {
+        premises,
+        conclusion,
+        validity,
+    }
+}
+
+/// Merge per-chunk fallacy assessments: counts and criticality are recomputed
+/// from the merged findings; argument strength is averaged.
+#[allow(clippy::cast_precision_loss)]
+fn merge_fallacy_assessments(
+    fallacies: &[DetectedFallacy],
+    chunk_assessments: &[FallacyAssessment],
+) -> FallacyAssessment {
+    let most_critical = fallacies
+        .iter()
+        .max_by_key(|f| fallacy_severity_rank(&f.severity))
+        .map_or_else(|| "None".to_string(), |f| f.fallacy.clone());
+    let argument_strength = if chunk_assessments.is_empty() {
+        0.0
+    } else {
+        chunk_assessments
+            .iter()
+            .map(|a| a.argument_strength)
+            .sum::<f64>()
+            / chunk_assessments.len() as f64
+    };
+
+    FallacyAssessment {
+        fallacy_count: u32::try_from(fallacies.len()).unwrap_or(u32::MAX),
+        argument_strength,
+        most_critical,
+    }
+}
+
+const fn bias_severity_rank(severity: &BiasSeverity) -> u8 {
+    match severity {
+        BiasSeverity::Low => 0,
+        BiasSeverity::Medium => 1,
+        BiasSeverity::High => 2,
+    }
+}
+
+const fn fallacy_severity_rank(severity: &FallacySeverity) -> u8 {
+    match severity {
+        FallacySeverity::Low => 0,
+        FallacySeverity::Medium => 1,
+        FallacySeverity::High => 2,
+    }
+}
+
+/// Extract distinct justification loops from the cyclic id set. From each
+/// unclaimed id (in sorted order), follow the first in-set outgoing edge
+/// until a node repeats; the walked segment from the repeat onward is one
+/// loop, rotated to start at its smallest id for deterministic output. Nodes
+/// that dead-end or run into an already-reported loop yield nothing.
+fn extract_cycles(cyclic: &[&str], edges: &[(&str, &str)]) -> Vec<CircularChain> {
+    let in_set: std::collections::HashSet<&str> = cyclic.iter().copied().collect();
+    let mut claimed: std::collections::HashSet<&str> = std::collections::HashSet::new();
+    let mut cycles = Vec::new();
+
+    for start in cyclic {
+        if claimed.contains(start) {
+            continue;
+        }
+        let mut path: Vec<&str> = vec![start];
+        let mut current = *start;
+        // Edges are sorted, so the first matching edge is deterministic; a
+        // dead end (fed by a cycle without being on one) exits the walk.
+        while let Some((_, next)) = edges
+            .iter()
+            .find(|(from, to)| *from == current && in_set.contains(to))
+        {
+            if claimed.contains(next) {
+                break; // Ran into an already-reported loop.
+            }
+            if let Some(pos) = path.iter().position(|id| id == next) {
+                let mut loop_ids: Vec<&str> = path[pos..].to_vec();
+                // Rotate so the loop starts at its smallest id.
+                if let Some(min_pos) = loop_ids
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, id)| **id)
+                    .map(|(i, _)| i)
+                {
+                    loop_ids.rotate_left(min_pos);
+                }
+                claimed.extend(path.iter().copied());
+                cycles.push(CircularChain::new(
+                    loop_ids.iter().map(ToString::to_string).collect(),
+                ));
+                break;
+            }
+            path.push(next);
+            current = next;
+        }
+    }
+
+    cycles
+}
+
+/// Lower rank = weaker validity, so `min_by_key` picks the weakest.
+const fn validity_rank(validity: &ArgumentValidity) -> u8 {
+    match validity {
+        ArgumentValidity::Invalid => 0,
+        ArgumentValidity::PartiallyValid => 1,
+        ArgumentValidity::Valid => 2,
+    }
+}
+
 /// Truncate a string to at most `max` characters (char-safe), appending an
 /// ellipsis when truncated.
 fn truncate_chars(s: &str, max: usize) -> String {
@@ -503,6 +1270,9 @@ mod tests {
         mock_storage.expect_save_thought().returning(|_| Ok(()));
         // A referenced session triggers a prior-thoughts lookup for context.
         mock_storage.expect_get_thoughts().returning(|_| Ok(vec![]));
+        mock_storage
+            .expect_get_working_memory()
+            .returning(|_| Ok(None));
 
         let response_json = mock_biases_response();
         mock_client.expect_complete().returning(move |_, _| {
@@ -703,6 +1473,9 @@ mod tests {
         });
         mock_storage.expect_save_thought().returning(|_| Ok(()));
         mock_storage.expect_get_thoughts().returning(|_| Ok(vec![]));
+        mock_storage
+            .expect_get_working_memory()
+            .returning(|_| Ok(None));
 
         let response_json = mock_fallacies_response();
         mock_client.expect_complete().returning(move |_, _| {
@@ -731,6 +1504,214 @@ mod tests {
             ArgumentValidity::Invalid
         );
         assert_eq!(response.overall_assessment.fallacy_count, 1);
+        // Premise verification is opt-in; the default path skips it.
+        assert!(response.premise_verification.is_none());
+    }
+
+    fn mock_premise_verification_response() -> String {
+        r#"{
+            "premise_assessments": [
+                {
+                    "premise": "Premise 1",
+                    "plausibility": 0.8,
+                    "evidentiary_support": 0.6,
+                    "assessment": "Widely accepted and partially documented"
+                },
+                {
+                    "premise": "Premise 2",
+                    "plausibility": 0.4,
+                    "evidentiary_support": 0.2,
+                    "assessment": "Contested and lacking direct evidence"
+                }
+            ]
+        }"#
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_fallacies_premise_verification_scores_each_premise() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("test-session")));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+
+        // First completion is the detection; the second is the premise
+        // follow-up and must list the identified premises.
+        let calls = AtomicUsize::new(0);
+        let fallacies_json = mock_fallacies_response();
+        let premises_json = mock_premise_verification_response();
+        mock_client
+            .expect_complete()
+            .times(2)
+            .returning(move |messages, _| {
+                let body = if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                    fallacies_json.clone()
+                } else {
+                    let content = &messages.first().expect("user message").content;
+                    assert!(content.contains("Premises to verify"));
+                    assert!(content.contains("1. Premise 1"));
+                    assert!(content.contains("2. Premise 2"));
+                    premises_json.clone()
+                };
+                Ok(CompletionResponse::new(body, Usage::new(100, 200)))
+            });
+
+        let mode = DetectMode::new(mock_storage, mock_client).with_premise_verification(true);
+        let response = mode.fallacies("Some flawed argument", None).await.unwrap();
+
+        let verification = response.premise_verification.expect("verification ran");
+        assert!(verification.note.is_none());
+        assert_eq!(verification.assessments.len(), 2);
+        // Every parsed premise gets its own plausibility score.
+        assert_eq!(verification.assessments[0].premise, "Premise 1");
+        assert!((verification.assessments[0].plausibility - 0.8).abs() < f64::EPSILON);
+        assert!((verification.assessments[0].evidentiary_support - 0.6).abs() < f64::EPSILON);
+        assert_eq!(verification.assessments[1].premise, "Premise 2");
+        assert!((verification.assessments[1].plausibility - 0.4).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_fallacies_premise_verification_no_premises_returns_note() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("test-session")));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+
+        // No clear premises → exactly one completion; the follow-up is skipped.
+        mock_client.expect_complete().times(1).returning(|_, _| {
+            Ok(CompletionResponse::new(
+                r#"{
+                    "fallacies_detected": [],
+                    "argument_structure": {
+                        "premises": [],
+                        "conclusion": "A bare assertion",
+                        "validity": "invalid"
+                    },
+                    "overall_assessment": {
+                        "fallacy_count": 0,
+                        "argument_strength": 0.2,
+                        "most_critical": "None"
+                    }
+                }"#,
+                Usage::new(100, 200),
+            ))
+        });
+
+        let mode = DetectMode::new(mock_storage, mock_client).with_premise_verification(true);
+        let response = mode.fallacies("Just trust me on this", None).await.unwrap();
+
+        let verification = response.premise_verification.expect("verification ran");
+        assert!(verification.assessments.is_empty());
+        assert!(verification
+            .note
+            .expect("note")
+            .contains("No clear premises"));
+    }
+
+    #[tokio::test]
+    async fn test_fallacies_premise_verification_partial_result_rejected() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("test-session")));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+
+        // Two premises identified, only one scored → the result is rejected
+        // rather than silently partial.
+        let calls = AtomicUsize::new(0);
+        let fallacies_json = mock_fallacies_response();
+        mock_client
+            .expect_complete()
+            .times(2)
+            .returning(move |_, _| {
+                let body = if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                    fallacies_json.clone()
+                } else {
+                    r#"{
+                        "premise_assessments": [
+                            {
+                                "premise": "Premise 1",
+                                "plausibility": 0.8,
+                                "evidentiary_support": 0.6,
+                                "assessment": "Fine"
+                            }
+                        ]
+                    }"#
+                    .to_string()
+                };
+                Ok(CompletionResponse::new(body, Usage::new(100, 200)))
+            });
+
+        let mode = DetectMode::new(mock_storage, mock_client).with_premise_verification(true);
+        let result = mode.fallacies("Some flawed argument", None).await;
+
+        assert!(matches!(
+            result,
+            Err(ModeError::InvalidValue { field, .. }) if field == "premise_assessments"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fallacies_verifies_passages_against_content() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("test-session")));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+
+        // One passage quotes the input; the other is fabricated.
+        mock_client.expect_complete().returning(|_, _| {
+            Ok(CompletionResponse::new(
+                r#"{
+                    "fallacies_detected": [
+                        {
+                            "fallacy": "Ad Hominem",
+                            "category": "informal",
+                            "passage": "you're wrong because you're stupid",
+                            "severity": "high",
+                            "confidence": 0.9,
+                            "explanation": "Attacks the person",
+                            "correction": "Address the argument"
+                        },
+                        {
+                            "fallacy": "Appeal to Popularity",
+                            "category": "informal",
+                            "passage": "everyone already agrees the plan is perfect",
+                            "severity": "medium",
+                            "confidence": 0.7,
+                            "explanation": "Popularity is not truth",
+                            "correction": "Cite evidence instead"
+                        }
+                    ],
+                    "argument_structure": {"premises": ["P"], "conclusion": "C", "validity": "invalid"},
+                    "overall_assessment": {"fallacy_count": 2, "argument_strength": 0.3, "most_critical": "Ad Hominem"}
+                }"#,
+                Usage::new(100, 200),
+            ))
+        });
+
+        let mode = DetectMode::new(mock_storage, mock_client);
+        let result = mode
+            .fallacies("You're wrong because you're stupid, so we reject it", None)
+            .await;
+
+        let response = result.unwrap();
+        assert!(response.fallacies_detected[0].verified);
+        assert!(!response.fallacies_detected[1].verified);
     }
 
     #[tokio::test]
@@ -929,6 +1910,9 @@ mod tests {
         });
         mock_storage.expect_save_thought().returning(|_| Ok(()));
         mock_storage.expect_get_thoughts().returning(|_| Ok(vec![]));
+        mock_storage
+            .expect_get_working_memory()
+            .returning(|_| Ok(None));
 
         let response_json = mock_knowledge_gaps_response();
         mock_client.expect_complete().returning(move |_, _| {
@@ -1016,6 +2000,175 @@ mod tests {
         );
     }
 
+    // ========================================================================
+    // Counterargue Operation Tests
+    // ========================================================================
+
+    fn mock_counterargue_response() -> String {
+        r#"{
+            "counterarguments": [
+                {
+                    "argument": "Migration cost exceeds projected savings",
+                    "rationale": "Industry surveys put overruns at 2-3x estimates",
+                    "strength": 0.85,
+                    "rebuttal": "Phased rollout caps exposure per quarter",
+                    "rebuttal_strength": "partial"
+                },
+                {
+                    "argument": "Vendor lock-in increases long-term risk",
+                    "rationale": "Proprietary APIs make a future exit expensive",
+                    "strength": 0.6,
+                    "rebuttal": "No credible mitigation identified",
+                    "rebuttal_strength": "weak"
+                }
+            ],
+            "overall_assessment": {
+                "counterargument_count": 2,
+                "strongest": "Migration cost exceeds projected savings",
+                "claim_resilience": 0.55
+            }
+        }"#
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_counterargue_success_multiple_counterarguments() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage.expect_get_or_create_session().returning(|id| {
+            Ok(Session::new(
+                id.unwrap_or_else(|| "test-session".to_string()),
+            ))
+        });
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        mock_storage.expect_get_thoughts().returning(|_| Ok(vec![]));
+        mock_storage
+            .expect_get_working_memory()
+            .returning(|_| Ok(None));
+
+        let response_json = mock_counterargue_response();
+        mock_client.expect_complete().returning(move |_, _| {
+            Ok(CompletionResponse::new(
+                response_json.clone(),
+                Usage::new(100, 200),
+            ))
+        });
+
+        let mode = DetectMode::new(mock_storage, mock_client);
+        let result = mode
+            .counterargue(
+                "We should migrate to the new stack",
+                Some("test-session".to_string()),
+            )
+            .await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.session_id, "test-session");
+        assert_eq!(response.counterarguments.len(), 2);
+        assert_eq!(
+            response.counterarguments[0].argument,
+            "Migration cost exceeds projected savings"
+        );
+        assert!((response.counterarguments[0].strength - 0.85).abs() < f64::EPSILON);
+        assert_eq!(
+            response.counterarguments[0].rebuttal_strength,
+            RebuttalStrength::Partial
+        );
+        assert_eq!(
+            response.counterarguments[1].rebuttal_strength,
+            RebuttalStrength::Weak
+        );
+        assert_eq!(response.overall_assessment.counterargument_count, 2);
+        assert!((response.overall_assessment.claim_resilience - 0.55).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_counterargue_missing_rationale_errors_cleanly() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("test-session")));
+
+        // First counterargument omits rationale → MissingField, not a panic.
+        mock_client.expect_complete().returning(|_, _| {
+            Ok(CompletionResponse::new(
+                r#"{
+                    "counterarguments": [{
+                        "argument": "The plan is too risky",
+                        "strength": 0.7,
+                        "rebuttal": "Risk is mitigated",
+                        "rebuttal_strength": "partial"
+                    }],
+                    "overall_assessment": {
+                        "counterargument_count": 1,
+                        "strongest": "The plan is too risky",
+                        "claim_resilience": 0.6
+                    }
+                }"#,
+                Usage::new(50, 100),
+            ))
+        });
+
+        let mode = DetectMode::new(mock_storage, mock_client);
+        let result = mode.counterargue("Test claim", None).await;
+
+        assert!(result.is_err());
+        assert!(matches!(result, Err(ModeError::MissingField { field }) if field == "rationale"));
+    }
+
+    #[tokio::test]
+    async fn test_counterargue_empty_content_returns_error() {
+        let mock_storage = MockStorageTrait::new();
+        let mock_client = MockAnthropicClientTrait::new();
+
+        let mode = DetectMode::new(mock_storage, mock_client);
+        let result = mode.counterargue("", None).await;
+        assert!(result.is_err());
+        assert!(matches!(result, Err(ModeError::MissingField { field }) if field == "content"));
+    }
+
+    #[tokio::test]
+    async fn test_counterargue_invalid_rebuttal_strength() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("test-session")));
+
+        mock_client.expect_complete().returning(|_, _| {
+            Ok(CompletionResponse::new(
+                r#"{
+                    "counterarguments": [{
+                        "argument": "A",
+                        "rationale": "R",
+                        "strength": 0.7,
+                        "rebuttal": "B",
+                        "rebuttal_strength": "crushing"
+                    }],
+                    "overall_assessment": {
+                        "counterargument_count": 1,
+                        "strongest": "A",
+                        "claim_resilience": 0.6
+                    }
+                }"#,
+                Usage::new(50, 100),
+            ))
+        });
+
+        let mode = DetectMode::new(mock_storage, mock_client);
+        let result = mode.counterargue("Test claim", None).await;
+
+        assert!(result.is_err());
+        assert!(
+            matches!(result, Err(ModeError::InvalidValue { field, .. }) if field == "rebuttal_strength")
+        );
+    }
+
     #[tokio::test]
     async fn test_biases_injects_prior_session_context() {
         use crate::traits::Thought;
@@ -1035,6 +2188,9 @@ mod tests {
                 0.7,
             )])
         });
+        mock_storage
+            .expect_get_working_memory()
+            .returning(|_| Ok(None));
         mock_storage.expect_save_thought().returning(|_| Ok(()));
 
         let response_json = mock_biases_response();
@@ -1099,6 +2255,183 @@ mod tests {
         assert_eq!(truncate_chars("123456789", 4), "1234…");
     }
 
+    // ========================================================================
+    // Chunked Path Tests
+    // ========================================================================
+
+    /// Content whose two paragraphs each fit a chunk but together exceed
+    /// `CHUNK_MAX_BYTES`, forcing a two-chunk split. Both halves contain the
+    /// literal "the shared pattern" so a finding duplicated across chunks can
+    /// be deduplicated.
+    fn two_chunk_content() -> String {
+        let mut first = "alpha evidence ".repeat(2400);
+        first.push_str("the shared pattern");
+        let mut second = "beta evidence ".repeat(2400);
+        second.push_str("the shared pattern");
+        format!("{first}\n\n{second}")
+    }
+
+    fn chunked_mode_storage() -> MockStorageTrait {
+        let mut mock_storage = MockStorageTrait::new();
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("chunk-session")));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        mock_storage
+    }
+
+    #[tokio::test]
+    async fn test_biases_chunked_aggregates_across_chunks_without_duplicates() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let content = two_chunk_content();
+        let first_chunk_len = content.find("\n\n").unwrap();
+
+        let mock_storage = chunked_mode_storage();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        // One response per chunk; "Framing" (same evidence) appears in both.
+        let calls = AtomicUsize::new(0);
+        mock_client.expect_complete().times(2).returning(move |_, _| {
+            let body = if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                r#"{
+                    "biases_detected": [
+                        {"bias": "Anchoring", "evidence": "alpha evidence", "severity": "medium", "confidence": 0.8, "impact": "I", "debiasing": "D"},
+                        {"bias": "Framing", "evidence": "the shared pattern", "severity": "low", "confidence": 0.6, "impact": "I", "debiasing": "D"}
+                    ],
+                    "overall_assessment": {"bias_count": 2, "most_severe": "Anchoring", "reasoning_quality": 0.6},
+                    "debiased_version": "First half fixed."
+                }"#
+            } else {
+                r#"{
+                    "biases_detected": [
+                        {"bias": "Availability Heuristic", "evidence": "beta evidence", "severity": "high", "confidence": 0.9, "impact": "I", "debiasing": "D"},
+                        {"bias": "Framing", "evidence": "the shared pattern", "severity": "low", "confidence": 0.6, "impact": "I", "debiasing": "D"}
+                    ],
+                    "overall_assessment": {"bias_count": 2, "most_severe": "Availability Heuristic", "reasoning_quality": 0.8},
+                    "debiased_version": "Second half fixed."
+                }"#
+            };
+            Ok(CompletionResponse::new(body, Usage::new(100, 200)))
+        });
+
+        let mode = DetectMode::new(mock_storage, mock_client).with_chunking(true);
+        let response = mode.biases(&content, None).await.unwrap();
+
+        // Findings from both chunks, with the duplicate collapsed.
+        let names: Vec<&str> = response
+            .biases_detected
+            .iter()
+            .map(|b| b.bias.as_str())
+            .collect();
+        assert_eq!(names, ["Anchoring", "Framing", "Availability Heuristic"]);
+        assert_eq!(response.overall_assessment.bias_count, 3);
+        assert_eq!(
+            response.overall_assessment.most_severe,
+            "Availability Heuristic"
+        );
+        assert!((response.overall_assessment.reasoning_quality - 0.7).abs() < f64::EPSILON);
+        assert_eq!(
+            response.debiased_version,
+            "First half fixed.\n\nSecond half fixed."
+        );
+
+        // Offsets map each citation back into the original content.
+        assert_eq!(response.biases_detected[0].offset, Some(0));
+        let shared_offset = response.biases_detected[1].offset.unwrap();
+        assert!(shared_offset < first_chunk_len);
+        let beta_offset = response.biases_detected[2].offset.unwrap();
+        assert!(beta_offset > first_chunk_len);
+        assert_eq!(&content[beta_offset..beta_offset + 13], "beta evidence");
+    }
+
+    #[tokio::test]
+    async fn test_fallacies_chunked_merges_structure_and_dedups() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let content = two_chunk_content();
+        let mock_storage = chunked_mode_storage();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        // "Ad Hominem" on the shared passage appears in both chunk results.
+        let calls = AtomicUsize::new(0);
+        mock_client.expect_complete().times(2).returning(move |_, _| {
+            let body = if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                r#"{
+                    "fallacies_detected": [
+                        {"fallacy": "Strawman", "category": "informal", "passage": "alpha evidence", "severity": "medium", "confidence": 0.8, "explanation": "E", "correction": "C"},
+                        {"fallacy": "Ad Hominem", "category": "informal", "passage": "the shared pattern", "severity": "high", "confidence": 0.9, "explanation": "E", "correction": "C"}
+                    ],
+                    "argument_structure": {"premises": ["P1"], "conclusion": "", "validity": "valid"},
+                    "overall_assessment": {"fallacy_count": 2, "argument_strength": 0.7, "most_critical": "Ad Hominem"}
+                }"#
+            } else {
+                r#"{
+                    "fallacies_detected": [
+                        {"fallacy": "Ad Hominem", "category": "informal", "passage": "the shared pattern", "severity": "high", "confidence": 0.9, "explanation": "E", "correction": "C"}
+                    ],
+                    "argument_structure": {"premises": ["P1", "P2"], "conclusion": "The final conclusion", "validity": "invalid"},
+                    "overall_assessment": {"fallacy_count": 1, "argument_strength": 0.3, "most_critical": "Ad Hominem"}
+                }"#
+            };
+            Ok(CompletionResponse::new(body, Usage::new(100, 200)))
+        });
+
+        let mode = DetectMode::new(mock_storage, mock_client).with_chunking(true);
+        let response = mode.fallacies(&content, None).await.unwrap();
+
+        let names: Vec<&str> = response
+            .fallacies_detected
+            .iter()
+            .map(|f| f.fallacy.as_str())
+            .collect();
+        assert_eq!(names, ["Strawman", "Ad Hominem"]);
+        assert_eq!(response.overall_assessment.fallacy_count, 2);
+        assert_eq!(response.overall_assessment.most_critical, "Ad Hominem");
+        assert!((response.overall_assessment.argument_strength - 0.5).abs() < f64::EPSILON);
+
+        // Premises merged without duplicates; conclusion from the last chunk
+        // that stated one; one invalid section makes the whole invalid.
+        assert_eq!(response.argument_structure.premises, ["P1", "P2"]);
+        assert_eq!(
+            response.argument_structure.conclusion,
+            "The final conclusion"
+        );
+        assert_eq!(
+            response.argument_structure.validity,
+            ArgumentValidity::Invalid
+        );
+        assert!(response.fallacies_detected[0].offset.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_biases_small_content_with_chunking_takes_single_path() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("test-session")));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+
+        let response_json = mock_biases_response();
+        // Content fits one chunk → exactly one completion, ordinary path.
+        mock_client
+            .expect_complete()
+            .times(1)
+            .returning(move |_, _| {
+                Ok(CompletionResponse::new(
+                    response_json.clone(),
+                    Usage::new(100, 200),
+                ))
+            });
+
+        let mode = DetectMode::new(mock_storage, mock_client).with_chunking(true);
+        let response = mode.biases("Some biased content", None).await.unwrap();
+        // The single-chunk path never sets offsets.
+        assert_eq!(response.biases_detected[0].offset, None);
+    }
+
     #[test]
     fn test_changes_conclusion_parsed_from_biases() {
         let json: serde_json::Value = serde_json::from_str(&mock_biases_with_changes()).unwrap();
@@ -1131,4 +2464,320 @@ mod tests {
         }"#
         .to_string()
     }
+
+    /// Build a stored thought whose content references the given thought ids.
+    fn justifying_thought(id: &str, references: &[&str]) -> Thought {
+        let content = if references.is_empty() {
+            format!("Standalone claim in thought {id}")
+        } else {
+            format!("This holds because of {}", references.join(" and "))
+        };
+        Thought::new(id, "sess-circ", content, "linear", 0.8)
+    }
+
+    #[tokio::test]
+    async fn test_circular_reports_justification_loop() {
+        let mut mock_storage = MockStorageTrait::new();
+        mock_storage.expect_get_thoughts().returning(|_| {
+            Ok(vec![
+                justifying_thought("thought-a", &["thought-b"]),
+                justifying_thought("thought-b", &["thought-a"]),
+                justifying_thought("thought-c", &[]),
+            ])
+        });
+
+        // Purely structural: the client must never be called.
+        let mode = DetectMode::new(mock_storage, MockAnthropicClientTrait::new());
+        let response = mode.circular("sess-circ").await.expect("circular");
+
+        assert_eq!(response.session_id, "sess-circ");
+        assert_eq!(response.thought_count, 3);
+        assert_eq!(response.reference_count, 2);
+        assert_eq!(response.cycles.len(), 1);
+        assert_eq!(
+            response.cycles[0].thought_ids,
+            vec!["thought-a".to_string(), "thought-b".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_circular_reports_longer_loop_in_justification_order() {
+        let mut mock_storage = MockStorageTrait::new();
+        // A justified by B, B by C, C by A — one three-thought loop.
+        mock_storage.expect_get_thoughts().returning(|_| {
+            Ok(vec![
+                justifying_thought("thought-a", &["thought-b"]),
+                justifying_thought("thought-b", &["thought-c"]),
+                justifying_thought("thought-c", &["thought-a"]),
+            ])
+        });
+
+        let mode = DetectMode::new(mock_storage, MockAnthropicClientTrait::new());
+        let response = mode.circular("sess-circ").await.expect("circular");
+
+        assert_eq!(response.cycles.len(), 1);
+        assert_eq!(
+            response.cycles[0].thought_ids,
+            vec![
+                "thought-a".to_string(),
+                "thought-b".to_string(),
+                "thought-c".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_circular_finds_nothing_in_acyclic_chain() {
+        let mut mock_storage = MockStorageTrait::new();
+        // A justified by B, B standalone — references but no loop.
+        mock_storage.expect_get_thoughts().returning(|_| {
+            Ok(vec![
+                justifying_thought("thought-a", &["thought-b"]),
+                justifying_thought("thought-b", &[]),
+            ])
+        });
+
+        let mode = DetectMode::new(mock_storage, MockAnthropicClientTrait::new());
+        let response = mode.circular("sess-circ").await.expect("circular");
+
+        assert_eq!(response.thought_count, 2);
+        assert_eq!(response.reference_count, 1);
+        assert!(response.cycles.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_circular_rejects_empty_session_id() {
+        let mode = DetectMode::new(MockStorageTrait::new(), MockAnthropicClientTrait::new());
+        let err = mode.circular("  ").await.expect_err("empty session id");
+        assert!(err.to_string().contains("session_id"), "{err}");
+    }
+
+    // ========================================================================
+    // Degenerate-Output Retry Tests
+    // ========================================================================
+
+    fn mock_empty_biases_response() -> String {
+        r#"{
+            "biases_detected": [],
+            "overall_assessment": {
+                "bias_count": 0,
+                "most_severe": "None",
+                "reasoning_quality": 0.9
+            },
+            "debiased_version": "The argument as given."
+        }"#
+        .to_string()
+    }
+
+    fn mock_empty_fallacies_response() -> String {
+        r#"{
+            "fallacies_detected": [],
+            "argument_structure": {
+                "premises": ["Premise 1"],
+                "conclusion": "The main conclusion",
+                "validity": "valid"
+            },
+            "overall_assessment": {
+                "fallacy_count": 0,
+                "argument_strength": 0.8,
+                "most_critical": "None"
+            }
+        }"#
+        .to_string()
+    }
+
+    fn retry_test_storage() -> MockStorageTrait {
+        let mut mock_storage = MockStorageTrait::new();
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("test-session")));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        mock_storage
+    }
+
+    #[tokio::test]
+    async fn test_biases_degenerate_retry_returns_populated_result() {
+        let mock_storage = retry_test_storage();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        // First pass: clean parse, zero findings.
+        mock_client
+            .expect_complete()
+            .withf(|messages, _| {
+                messages
+                    .first()
+                    .is_some_and(|m| !m.content.contains("reported no findings"))
+            })
+            .times(1)
+            .returning(|_, _| {
+                Ok(CompletionResponse::new(
+                    mock_empty_biases_response(),
+                    Usage::new(100, 200),
+                ))
+            });
+        // Retry carries the sharper addendum and finds the bias.
+        mock_client
+            .expect_complete()
+            .withf(|messages, _| {
+                messages
+                    .first()
+                    .is_some_and(|m| m.content.contains("reported no findings"))
+            })
+            .times(1)
+            .returning(|_, _| {
+                Ok(CompletionResponse::new(
+                    mock_biases_response(),
+                    Usage::new(100, 200),
+                ))
+            });
+
+        let mode = DetectMode::new(mock_storage, mock_client).with_flags(ModeFlags {
+            retry_degenerate: true,
+            ..ModeFlags::default()
+        });
+        let result = mode.biases("Some biased content", None).await;
+
+        let response = result.expect("retry should recover the findings");
+        assert_eq!(response.biases_detected.len(), 1);
+        assert_eq!(response.biases_detected[0].bias, "Confirmation Bias");
+        assert_eq!(response.overall_assessment.bias_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_biases_degenerate_retry_keeps_empty_when_retry_also_empty() {
+        let mock_storage = retry_test_storage();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        // Both passes come back empty: exactly two completions, empty kept.
+        mock_client.expect_complete().times(2).returning(|_, _| {
+            Ok(CompletionResponse::new(
+                mock_empty_biases_response(),
+                Usage::new(100, 200),
+            ))
+        });
+
+        let mode = DetectMode::new(mock_storage, mock_client).with_flags(ModeFlags {
+            retry_degenerate: true,
+            ..ModeFlags::default()
+        });
+        let result = mode.biases("Some content", None).await;
+
+        let response = result.expect("empty result should still succeed");
+        assert!(response.biases_detected.is_empty());
+        assert_eq!(response.overall_assessment.bias_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_biases_degenerate_retry_failure_keeps_first_result() {
+        let mock_storage = retry_test_storage();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_client
+            .expect_complete()
+            .withf(|messages, _| {
+                messages
+                    .first()
+                    .is_some_and(|m| !m.content.contains("reported no findings"))
+            })
+            .times(1)
+            .returning(|_, _| {
+                Ok(CompletionResponse::new(
+                    mock_empty_biases_response(),
+                    Usage::new(100, 200),
+                ))
+            });
+        // The retry fails outright — the original empty result survives.
+        mock_client
+            .expect_complete()
+            .withf(|messages, _| {
+                messages
+                    .first()
+                    .is_some_and(|m| m.content.contains("reported no findings"))
+            })
+            .times(1)
+            .returning(|_, _| {
+                Err(ModeError::ApiUnavailable {
+                    message: "API error".to_string(),
+                })
+            });
+
+        let mode = DetectMode::new(mock_storage, mock_client).with_flags(ModeFlags {
+            retry_degenerate: true,
+            ..ModeFlags::default()
+        });
+        let result = mode.biases("Some content", None).await;
+
+        let response = result.expect("retry failure should not surface");
+        assert!(response.biases_detected.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_biases_degenerate_retry_off_by_default() {
+        let mock_storage = retry_test_storage();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        // Exactly one completion: no rerun when the flag is off.
+        mock_client.expect_complete().times(1).returning(|_, _| {
+            Ok(CompletionResponse::new(
+                mock_empty_biases_response(),
+                Usage::new(100, 200),
+            ))
+        });
+
+        let mode = DetectMode::new(mock_storage, mock_client);
+        let result = mode.biases("Some content", None).await;
+
+        let response = result.expect("empty result should still succeed");
+        assert!(response.biases_detected.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fallacies_degenerate_retry_returns_populated_result() {
+        let mock_storage = retry_test_storage();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_client
+            .expect_complete()
+            .withf(|messages, _| {
+                messages
+                    .first()
+                    .is_some_and(|m| !m.content.contains("reported no findings"))
+            })
+            .times(1)
+            .returning(|_, _| {
+                Ok(CompletionResponse::new(
+                    mock_empty_fallacies_response(),
+                    Usage::new(100, 200),
+                ))
+            });
+        mock_client
+            .expect_complete()
+            .withf(|messages, _| {
+                messages
+                    .first()
+                    .is_some_and(|m| m.content.contains("reported no findings"))
+            })
+            .times(1)
+            .returning(|_, _| {
+                Ok(CompletionResponse::new(
+                    mock_fallacies_response(),
+                    Usage::new(100, 200),
+                ))
+            });
+
+        let mode = DetectMode::new(mock_storage, mock_client).with_flags(ModeFlags {
+            retry_degenerate: true,
+            ..ModeFlags::default()
+        });
+        let result = mode.fallacies("Some fallacious argument", None).await;
+
+        let response = result.expect("retry should recover the findings");
+        assert_eq!(response.fallacies_detected.len(), 1);
+        assert_eq!(response.fallacies_detected[0].fallacy, "Ad Hominem");
+        assert_eq!(response.overall_assessment.fallacy_count, 1);
+        assert_eq!(
+            response.argument_structure.validity,
+            ArgumentValidity::Invalid
+        );
+    }
 }
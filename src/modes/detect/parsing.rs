@@ -5,30 +5,18 @@
 //! fallacies, assessments). Returns ModeError::MissingField for absent fields.
 
 use crate::error::ModeError;
+use crate::modes::parse_probability;
 
 use super::types::{
-    ArgumentStructure, ArgumentValidity, BiasAssessment, BiasSeverity, DetectedBias,
-    DetectedFallacy, FallacyAssessment, FallacyCategory, FallacySeverity, GapCategory,
-    KnowledgeGap, KnowledgeGapAssessment,
+    ArgumentStructure, ArgumentValidity, BiasAssessment, BiasSeverity, Counterargument,
+    CounterargumentAssessment, DetectedBias, DetectedFallacy, FallacyAssessment, FallacyCategory,
+    FallacySeverity, GapCategory, KnowledgeGap, KnowledgeGapAssessment, PremiseAssessment,
+    RebuttalStrength,
 };
 
 /// Parse a required `confidence` field (0.0-1.0) from a detection item.
 fn parse_confidence(item: &serde_json::Value) -> Result<f64, ModeError> {
-    let confidence = item
-        .get("confidence")
-        .and_then(serde_json::Value::as_f64)
-        .ok_or_else(|| ModeError::MissingField {
-            field: "confidence".to_string(),
-        })?;
-
-    if !(0.0..=1.0).contains(&confidence) {
-        return Err(ModeError::InvalidValue {
-            field: "confidence".to_string(),
-            reason: format!("must be between 0.0 and 1.0, got {confidence}"),
-        });
-    }
-
-    Ok(confidence)
+    parse_probability(item, "confidence")
 }
 
 // ============================================================================
@@ -116,6 +104,9 @@ pub fn parse_biases(json: &serde_json::Value) -> Result<Vec<DetectedBias>, ModeE
                 changes_conclusion,
                 impact,
                 debiasing,
+                // Set by the post-parse verification step, not by the model.
+                verified: false,
+                offset: None,
             })
         })
         .collect()
@@ -154,19 +145,7 @@ pub fn parse_bias_assessment(json: &serde_json::Value) -> Result<BiasAssessment,
         .unwrap_or_default()
         .to_string();
 
-    let reasoning_quality = assessment
-        .get("reasoning_quality")
-        .and_then(serde_json::Value::as_f64)
-        .ok_or_else(|| ModeError::MissingField {
-            field: "reasoning_quality".to_string(),
-        })?;
-
-    if !(0.0..=1.0).contains(&reasoning_quality) {
-        return Err(ModeError::InvalidValue {
-            field: "reasoning_quality".to_string(),
-            reason: format!("must be between 0.0 and 1.0, got {reasoning_quality}"),
-        });
-    }
+    let reasoning_quality = parse_probability(assessment, "reasoning_quality")?;
 
     Ok(BiasAssessment {
         bias_count,
@@ -275,6 +254,9 @@ pub fn parse_fallacies(json: &serde_json::Value) -> Result<Vec<DetectedFallacy>,
                 confidence,
                 explanation,
                 correction,
+                // Set by the post-parse verification step, not by the model.
+                verified: false,
+                offset: None,
             })
         })
         .collect()
@@ -349,19 +331,7 @@ pub fn parse_fallacy_assessment(json: &serde_json::Value) -> Result<FallacyAsses
             field: "fallacy_count".to_string(),
         })? as u32;
 
-    let argument_strength = assessment
-        .get("argument_strength")
-        .and_then(serde_json::Value::as_f64)
-        .ok_or_else(|| ModeError::MissingField {
-            field: "argument_strength".to_string(),
-        })?;
-
-    if !(0.0..=1.0).contains(&argument_strength) {
-        return Err(ModeError::InvalidValue {
-            field: "argument_strength".to_string(),
-            reason: format!("must be between 0.0 and 1.0, got {argument_strength}"),
-        });
-    }
+    let argument_strength = parse_probability(assessment, "argument_strength")?;
 
     let most_critical = assessment
         .get("most_critical")
@@ -378,6 +348,50 @@ pub fn parse_fallacy_assessment(json: &serde_json::Value) -> Result<FallacyAsses
     })
 }
 
+/// Parses the `premise_assessments` array from the verification follow-up
+/// into a list of `PremiseAssessment` values.
+pub fn parse_premise_assessments(
+    json: &serde_json::Value,
+) -> Result<Vec<PremiseAssessment>, ModeError> {
+    let assessments_array = json
+        .get("premise_assessments")
+        .and_then(serde_json::Value::as_array)
+        .ok_or_else(|| ModeError::MissingField {
+            field: "premise_assessments".to_string(),
+        })?;
+
+    assessments_array
+        .iter()
+        .map(|a| {
+            let premise = a
+                .get("premise")
+                .and_then(serde_json::Value::as_str)
+                .ok_or_else(|| ModeError::MissingField {
+                    field: "premise".to_string(),
+                })?
+                .to_string();
+
+            let plausibility = parse_probability(a, "plausibility")?;
+            let evidentiary_support = parse_probability(a, "evidentiary_support")?;
+
+            let assessment = a
+                .get("assessment")
+                .and_then(serde_json::Value::as_str)
+                .ok_or_else(|| ModeError::MissingField {
+                    field: "assessment".to_string(),
+                })?
+                .to_string();
+
+            Ok(PremiseAssessment {
+                premise,
+                plausibility,
+                evidentiary_support,
+                assessment,
+            })
+        })
+        .collect()
+}
+
 // ============================================================================
 // Knowledge Gaps Parsing
 // ============================================================================
@@ -500,19 +514,7 @@ pub fn parse_knowledge_gap_assessment(
         })?
         .to_string();
 
-    let completeness_score = assessment
-        .get("completeness_score")
-        .and_then(serde_json::Value::as_f64)
-        .ok_or_else(|| ModeError::MissingField {
-            field: "completeness_score".to_string(),
-        })?;
-
-    if !(0.0..=1.0).contains(&completeness_score) {
-        return Err(ModeError::InvalidValue {
-            field: "completeness_score".to_string(),
-            reason: format!("must be between 0.0 and 1.0, got {completeness_score}"),
-        });
-    }
+    let completeness_score = parse_probability(assessment, "completeness_score")?;
 
     Ok(KnowledgeGapAssessment {
         gap_count,
@@ -521,6 +523,116 @@ pub fn parse_knowledge_gap_assessment(
     })
 }
 
+// ============================================================================
+// Counterargument Parsing
+// ============================================================================
+
+/// Parses the `counterarguments` array from LLM JSON into a list of `Counterargument` values.
+pub fn parse_counterarguments(json: &serde_json::Value) -> Result<Vec<Counterargument>, ModeError> {
+    let counterarguments_array = json
+        .get("counterarguments")
+        .and_then(serde_json::Value::as_array)
+        .ok_or_else(|| ModeError::MissingField {
+            field: "counterarguments".to_string(),
+        })?;
+
+    counterarguments_array
+        .iter()
+        .map(|c| {
+            let argument = c
+                .get("argument")
+                .and_then(serde_json::Value::as_str)
+                .ok_or_else(|| ModeError::MissingField {
+                    field: "argument".to_string(),
+                })?
+                .to_string();
+
+            let rationale = c
+                .get("rationale")
+                .and_then(serde_json::Value::as_str)
+                .ok_or_else(|| ModeError::MissingField {
+                    field: "rationale".to_string(),
+                })?
+                .to_string();
+
+            let strength = parse_probability(c, "strength")?;
+
+            let rebuttal = c
+                .get("rebuttal")
+                .and_then(serde_json::Value::as_str)
+                .ok_or_else(|| ModeError::MissingField {
+                    field: "rebuttal".to_string(),
+                })?
+                .to_string();
+
+            let rebuttal_strength_str = c
+                .get("rebuttal_strength")
+                .and_then(serde_json::Value::as_str)
+                .ok_or_else(|| ModeError::MissingField {
+                    field: "rebuttal_strength".to_string(),
+                })?;
+
+            let rebuttal_strength = match rebuttal_strength_str.to_lowercase().as_str() {
+                "weak" => RebuttalStrength::Weak,
+                "partial" => RebuttalStrength::Partial,
+                "strong" => RebuttalStrength::Strong,
+                _ => {
+                    return Err(ModeError::InvalidValue {
+                        field: "rebuttal_strength".to_string(),
+                        reason: format!(
+                            "must be weak, partial, or strong, got {rebuttal_strength_str}"
+                        ),
+                    })
+                }
+            };
+
+            Ok(Counterargument {
+                argument,
+                rationale,
+                strength,
+                rebuttal,
+                rebuttal_strength,
+            })
+        })
+        .collect()
+}
+
+/// Parses the `overall_assessment` object for counterarguments.
+pub fn parse_counterargument_assessment(
+    json: &serde_json::Value,
+) -> Result<CounterargumentAssessment, ModeError> {
+    let assessment = json
+        .get("overall_assessment")
+        .ok_or_else(|| ModeError::MissingField {
+            field: "overall_assessment".to_string(),
+        })?;
+
+    // Counterargument counts are small integers (typically < 10)
+    #[allow(clippy::cast_possible_truncation)]
+    let counterargument_count = assessment
+        .get("counterargument_count")
+        .and_then(serde_json::Value::as_u64)
+        .ok_or_else(|| ModeError::MissingField {
+            field: "counterargument_count".to_string(),
+        })? as u32;
+
+    let strongest = assessment
+        .get("strongest")
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| ModeError::MissingField {
+            field: "strongest".to_string(),
+        })?
+        .to_string();
+
+    let claim_resilience = parse_probability(assessment, "claim_resilience")?;
+
+    Ok(CounterargumentAssessment {
+        counterargument_count,
+        strongest,
+        claim_resilience,
+    })
+}
+
 #[cfg(test)]
 #[allow(
     clippy::unwrap_used,
@@ -1523,4 +1635,283 @@ mod tests {
             matches!(result, Err(ModeError::InvalidValue { field, .. }) if field == "completeness_score")
         );
     }
+
+    // ========================================================================
+    // parse_counterarguments tests
+    // ========================================================================
+
+    #[test]
+    fn test_parse_counterarguments_success_multiple() {
+        let json = json!({
+            "counterarguments": [
+                {
+                    "argument": "Migration cost exceeds projected savings",
+                    "rationale": "Industry surveys put average migration overruns at 2-3x estimates",
+                    "strength": 0.85,
+                    "rebuttal": "Phased rollout caps exposure per quarter",
+                    "rebuttal_strength": "partial"
+                },
+                {
+                    "argument": "The team lacks operational experience with the new stack",
+                    "rationale": "No one on the team has run it in production",
+                    "strength": 0.7,
+                    "rebuttal": "Managed offering removes most operational burden",
+                    "rebuttal_strength": "strong"
+                },
+                {
+                    "argument": "Vendor lock-in increases long-term risk",
+                    "rationale": "Proprietary APIs make a future exit expensive",
+                    "strength": 0.6,
+                    "rebuttal": "No credible mitigation identified",
+                    "rebuttal_strength": "weak"
+                }
+            ]
+        });
+
+        let result = parse_counterarguments(&json).unwrap();
+        assert_eq!(result.len(), 3);
+        assert_eq!(
+            result[0].argument,
+            "Migration cost exceeds projected savings"
+        );
+        assert!((result[0].strength - 0.85).abs() < f64::EPSILON);
+        assert!(matches!(
+            result[0].rebuttal_strength,
+            RebuttalStrength::Partial
+        ));
+        assert!(matches!(
+            result[1].rebuttal_strength,
+            RebuttalStrength::Strong
+        ));
+        assert!(matches!(
+            result[2].rebuttal_strength,
+            RebuttalStrength::Weak
+        ));
+    }
+
+    #[test]
+    fn test_parse_counterarguments_missing_counterarguments_field() {
+        let json = json!({});
+        let result = parse_counterarguments(&json);
+        assert!(
+            matches!(result, Err(ModeError::MissingField { field }) if field == "counterarguments")
+        );
+    }
+
+    #[test]
+    fn test_parse_counterarguments_missing_argument() {
+        let json = json!({
+            "counterarguments": [{
+                "rationale": "test",
+                "strength": 0.5,
+                "rebuttal": "test",
+                "rebuttal_strength": "weak"
+            }]
+        });
+        let result = parse_counterarguments(&json);
+        assert!(matches!(result, Err(ModeError::MissingField { field }) if field == "argument"));
+    }
+
+    #[test]
+    fn test_parse_counterarguments_missing_rationale() {
+        let json = json!({
+            "counterarguments": [{
+                "argument": "test",
+                "strength": 0.5,
+                "rebuttal": "test",
+                "rebuttal_strength": "weak"
+            }]
+        });
+        let result = parse_counterarguments(&json);
+        assert!(matches!(result, Err(ModeError::MissingField { field }) if field == "rationale"));
+    }
+
+    #[test]
+    fn test_parse_counterarguments_missing_strength() {
+        let json = json!({
+            "counterarguments": [{
+                "argument": "test",
+                "rationale": "test",
+                "rebuttal": "test",
+                "rebuttal_strength": "weak"
+            }]
+        });
+        let result = parse_counterarguments(&json);
+        assert!(matches!(result, Err(ModeError::MissingField { field }) if field == "strength"));
+    }
+
+    #[test]
+    fn test_parse_counterarguments_invalid_strength_too_high() {
+        let json = json!({
+            "counterarguments": [{
+                "argument": "test",
+                "rationale": "test",
+                "strength": 1.5,
+                "rebuttal": "test",
+                "rebuttal_strength": "weak"
+            }]
+        });
+        let result = parse_counterarguments(&json);
+        assert!(
+            matches!(result, Err(ModeError::InvalidValue { field, .. }) if field == "strength")
+        );
+    }
+
+    #[test]
+    fn test_parse_counterarguments_missing_rebuttal() {
+        let json = json!({
+            "counterarguments": [{
+                "argument": "test",
+                "rationale": "test",
+                "strength": 0.5,
+                "rebuttal_strength": "weak"
+            }]
+        });
+        let result = parse_counterarguments(&json);
+        assert!(matches!(result, Err(ModeError::MissingField { field }) if field == "rebuttal"));
+    }
+
+    #[test]
+    fn test_parse_counterarguments_missing_rebuttal_strength() {
+        let json = json!({
+            "counterarguments": [{
+                "argument": "test",
+                "rationale": "test",
+                "strength": 0.5,
+                "rebuttal": "test"
+            }]
+        });
+        let result = parse_counterarguments(&json);
+        assert!(
+            matches!(result, Err(ModeError::MissingField { field }) if field == "rebuttal_strength")
+        );
+    }
+
+    #[test]
+    fn test_parse_counterarguments_invalid_rebuttal_strength() {
+        let json = json!({
+            "counterarguments": [{
+                "argument": "test",
+                "rationale": "test",
+                "strength": 0.5,
+                "rebuttal": "test",
+                "rebuttal_strength": "devastating"
+            }]
+        });
+        let result = parse_counterarguments(&json);
+        assert!(
+            matches!(result, Err(ModeError::InvalidValue { field, .. }) if field == "rebuttal_strength")
+        );
+    }
+
+    #[test]
+    fn test_parse_counterargument_assessment_success() {
+        let json = json!({
+            "overall_assessment": {
+                "counterargument_count": 3,
+                "strongest": "Migration cost exceeds projected savings",
+                "claim_resilience": 0.55
+            }
+        });
+        let result = parse_counterargument_assessment(&json).unwrap();
+        assert_eq!(result.counterargument_count, 3);
+        assert_eq!(result.strongest, "Migration cost exceeds projected savings");
+        assert!((result.claim_resilience - 0.55).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_parse_counterargument_assessment_missing_overall() {
+        let json = json!({});
+        let result = parse_counterargument_assessment(&json);
+        assert!(
+            matches!(result, Err(ModeError::MissingField { field }) if field == "overall_assessment")
+        );
+    }
+
+    #[test]
+    fn test_parse_counterargument_assessment_missing_count() {
+        let json = json!({
+            "overall_assessment": {
+                "strongest": "test",
+                "claim_resilience": 0.5
+            }
+        });
+        let result = parse_counterargument_assessment(&json);
+        assert!(
+            matches!(result, Err(ModeError::MissingField { field }) if field == "counterargument_count")
+        );
+    }
+
+    #[test]
+    fn test_parse_counterargument_assessment_missing_strongest() {
+        let json = json!({
+            "overall_assessment": {
+                "counterargument_count": 1,
+                "claim_resilience": 0.5
+            }
+        });
+        let result = parse_counterargument_assessment(&json);
+        assert!(matches!(result, Err(ModeError::MissingField { field }) if field == "strongest"));
+    }
+
+    #[test]
+    fn test_parse_counterargument_assessment_invalid_resilience_too_high() {
+        let json = json!({
+            "overall_assessment": {
+                "counterargument_count": 1,
+                "strongest": "test",
+                "claim_resilience": 1.2
+            }
+        });
+        let result = parse_counterargument_assessment(&json);
+        assert!(
+            matches!(result, Err(ModeError::InvalidValue { field, .. }) if field == "claim_resilience")
+        );
+    }
+
+    #[test]
+    fn test_parse_premise_assessments_valid() {
+        let json = json!({
+            "premise_assessments": [
+                {
+                    "premise": "P1",
+                    "plausibility": 0.9,
+                    "evidentiary_support": 0.7,
+                    "assessment": "Well documented"
+                }
+            ]
+        });
+        let assessments = parse_premise_assessments(&json).unwrap();
+        assert_eq!(assessments.len(), 1);
+        assert_eq!(assessments[0].premise, "P1");
+        assert!((assessments[0].plausibility - 0.9).abs() < f64::EPSILON);
+        assert!((assessments[0].evidentiary_support - 0.7).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_parse_premise_assessments_missing_array() {
+        let json = json!({"other": []});
+        let result = parse_premise_assessments(&json);
+        assert!(
+            matches!(result, Err(ModeError::MissingField { field }) if field == "premise_assessments")
+        );
+    }
+
+    #[test]
+    fn test_parse_premise_assessments_plausibility_out_of_range() {
+        let json = json!({
+            "premise_assessments": [
+                {
+                    "premise": "P1",
+                    "plausibility": 1.4,
+                    "evidentiary_support": 0.7,
+                    "assessment": "Overconfident"
+                }
+            ]
+        });
+        let result = parse_premise_assessments(&json);
+        assert!(
+            matches!(result, Err(ModeError::InvalidValue { field, .. }) if field == "plausibility")
+        );
+    }
 }
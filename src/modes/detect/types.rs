@@ -29,6 +29,16 @@ pub struct DetectedBias {
     pub impact: String,
     /// Strategy to counteract.
     pub debiasing: String,
+    /// Whether the cited `evidence` was found (fuzzy match) in the analyzed
+    /// content. Set by post-parse verification, not by the model; `false`
+    /// flags a likely hallucinated citation.
+    #[serde(default)]
+    pub verified: bool,
+    /// Byte offset of the cited `evidence` in the original (pre-chunking)
+    /// content. Only set on the chunked path, and only when the citation
+    /// occurs verbatim.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
 }
 
 /// Severity level for a detected bias.
@@ -70,6 +80,153 @@ pub struct BiasAssessment {
     pub reasoning_quality: f64,
 }
 
+/// A pair of detected biases known to compound each other.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BiasInteraction {
+    /// First bias of the pair, named as detected.
+    pub bias_a: String,
+    /// Second bias of the pair, named as detected.
+    pub bias_b: String,
+    /// How the pair compounds beyond either bias alone.
+    pub combined_effect: String,
+}
+
+/// Canonical form of a bias name for interaction lookup: lowercased,
+/// punctuation collapsed to spaces, and a trailing "bias"/"effect"/"fallacy"
+/// dropped ("Confirmation Bias" and "confirmation" both map to
+/// "confirmation").
+fn canonical_bias_name(name: &str) -> String {
+    let mut words: Vec<String> = name
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    if words.len() > 1
+        && matches!(
+            words.last().map(String::as_str),
+            Some("bias" | "effect" | "fallacy")
+        )
+    {
+        words.pop();
+    }
+    words.join(" ")
+}
+
+/// Known compounding pairs, keyed by canonical bias names in either order.
+const BIAS_INTERACTION_TABLE: &[(&str, &str, &str)] = &[
+    (
+        "confirmation",
+        "anchoring",
+        "The anchor sets the initial position and confirmation bias then filters \
+         subsequent evidence to defend it, locking in the early estimate",
+    ),
+    (
+        "confirmation",
+        "availability",
+        "Easily recalled examples feel representative and confirmation bias \
+         over-weights exactly those, so a vivid anecdote crowds out base rates",
+    ),
+    (
+        "confirmation",
+        "overconfidence",
+        "Filtering out disconfirming evidence inflates apparent support, making \
+         the stated confidence higher than the evidence warrants",
+    ),
+    (
+        "confirmation",
+        "halo",
+        "A favorable overall impression biases which evidence is sought, and \
+         confirmation bias keeps the impression intact against counterevidence",
+    ),
+    (
+        "anchoring",
+        "availability",
+        "The most available figure becomes the anchor, so adjustment starts from \
+         a number chosen for recall ease rather than relevance",
+    ),
+    (
+        "anchoring",
+        "overconfidence",
+        "Insufficient adjustment from the anchor narrows the considered range, \
+         producing tighter confidence bounds than the evidence supports",
+    ),
+    (
+        "sunk cost",
+        "loss aversion",
+        "Past investment reframes stopping as a loss, so loss aversion amplifies \
+         the pull to continue a failing course",
+    ),
+    (
+        "sunk cost",
+        "commitment",
+        "Escalating commitment recruits past investment as justification, making \
+         each additional step harder to reverse than the last",
+    ),
+    (
+        "optimism",
+        "planning",
+        "Optimistic base assumptions feed directly into underestimated timelines \
+         and costs, compounding schedule risk",
+    ),
+    (
+        "groupthink",
+        "authority",
+        "Deference to the authority suppresses dissent and the group then \
+         converges on that view without independent evaluation",
+    ),
+    (
+        "hindsight",
+        "outcome",
+        "Judging the decision by its outcome looks reasonable once hindsight \
+         makes the outcome feel predictable, unfairly condemning sound process",
+    ),
+    (
+        "recency",
+        "availability",
+        "Recent events are also the most available ones, doubly over-weighting \
+         the latest data point against the longer record",
+    ),
+];
+
+/// Identify known compounding interactions among the detected biases.
+///
+/// Every unordered pair of detected biases is looked up in a fixed table of
+/// recognized interactions (names matched canonically, so "Confirmation Bias"
+/// pairs with "anchoring"). Each reported pair references the bias names
+/// exactly as detected; duplicate detections of the same bias produce one
+/// interaction. Fewer than two biases means no interactions.
+#[must_use]
+pub fn bias_interactions(biases: &[DetectedBias]) -> Vec<BiasInteraction> {
+    let mut interactions = Vec::new();
+    let mut seen: Vec<(String, String)> = Vec::new();
+    for (i, a) in biases.iter().enumerate() {
+        let canon_a = canonical_bias_name(&a.bias);
+        for b in biases.iter().skip(i + 1) {
+            let canon_b = canonical_bias_name(&b.bias);
+            let Some((_, _, effect)) = BIAS_INTERACTION_TABLE.iter().find(|(x, y, _)| {
+                (*x == canon_a && *y == canon_b) || (*x == canon_b && *y == canon_a)
+            }) else {
+                continue;
+            };
+            let key = if canon_a <= canon_b {
+                (canon_a.clone(), canon_b.clone())
+            } else {
+                (canon_b.clone(), canon_a.clone())
+            };
+            if seen.contains(&key) {
+                continue;
+            }
+            seen.push(key);
+            interactions.push(BiasInteraction {
+                bias_a: a.bias.clone(),
+                bias_b: b.bias.clone(),
+                combined_effect: (*effect).to_string(),
+            });
+        }
+    }
+    interactions
+}
+
 /// Response from bias detection operation.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct BiasesResponse {
@@ -83,10 +240,17 @@ pub struct BiasesResponse {
     pub overall_assessment: BiasAssessment,
     /// Debiased version of the argument.
     pub debiased_version: String,
+    /// Recognized compounding pairs among the detected biases (see
+    /// [`bias_interactions`]). Computed in Rust, not model-stated.
+    #[serde(default)]
+    pub interactions: Vec<BiasInteraction>,
 }
 
 impl BiasesResponse {
     /// Create a new biases response.
+    ///
+    /// The interaction list is derived from `biases_detected` so it can never
+    /// reference a bias that was not actually detected.
     #[must_use]
     pub fn new(
         thought_id: impl Into<String>,
@@ -95,12 +259,14 @@ impl BiasesResponse {
         overall_assessment: BiasAssessment,
         debiased_version: impl Into<String>,
     ) -> Self {
+        let interactions = bias_interactions(&biases_detected);
         Self {
             thought_id: thought_id.into(),
             session_id: session_id.into(),
             biases_detected,
             overall_assessment,
             debiased_version: debiased_version.into(),
+            interactions,
         }
     }
 }
@@ -127,6 +293,16 @@ pub struct DetectedFallacy {
     pub explanation: String,
     /// How to fix the argument.
     pub correction: String,
+    /// Whether the cited `passage` was found (fuzzy match) in the analyzed
+    /// content. Set by post-parse verification, not by the model; `false`
+    /// flags a likely hallucinated citation.
+    #[serde(default)]
+    pub verified: bool,
+    /// Byte offset of the cited `passage` in the original (pre-chunking)
+    /// content. Only set on the chunked path, and only when the citation
+    /// occurs verbatim.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
 }
 
 /// Severity level for a detected fallacy.
@@ -226,6 +402,30 @@ pub struct FallacyAssessment {
     pub most_critical: String,
 }
 
+/// Plausibility and support scores for a single premise.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PremiseAssessment {
+    /// The premise, as identified in the argument structure.
+    pub premise: String,
+    /// How likely the premise is to be true on its face (0.0-1.0).
+    pub plausibility: f64,
+    /// How well available evidence backs the premise (0.0-1.0).
+    pub evidentiary_support: f64,
+    /// Why the premise earned these scores.
+    pub assessment: String,
+}
+
+/// Result of the premise verification follow-up: per-premise scores, or a
+/// note explaining why there was nothing to verify.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PremiseVerification {
+    /// One assessment per identified premise, in argument order.
+    pub assessments: Vec<PremiseAssessment>,
+    /// Set when no clear premises were identified (nothing to verify).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
 /// Response from fallacy detection operation.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct FallaciesResponse {
@@ -239,6 +439,10 @@ pub struct FallaciesResponse {
     pub argument_structure: ArgumentStructure,
     /// Overall assessment.
     pub overall_assessment: FallacyAssessment,
+    /// Per-premise plausibility/support scores from the verification
+    /// follow-up. `None` unless premise verification was requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub premise_verification: Option<PremiseVerification>,
 }
 
 impl FallaciesResponse {
@@ -257,6 +461,7 @@ impl FallaciesResponse {
             fallacies_detected,
             argument_structure,
             overall_assessment,
+            premise_verification: None,
         }
     }
 }
@@ -356,6 +561,145 @@ impl KnowledgeGapsResponse {
     }
 }
 
+// ============================================================================
+// Response Types - Counterarguments
+// ============================================================================
+
+/// How well the original claim rebuts a counterargument.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RebuttalStrength {
+    /// The counterargument survives largely intact.
+    Weak,
+    /// The counterargument is blunted but not answered.
+    Partial,
+    /// The counterargument is fully answered.
+    Strong,
+}
+
+impl RebuttalStrength {
+    /// Returns the lowercase string representation.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Weak => "weak",
+            Self::Partial => "partial",
+            Self::Strong => "strong",
+        }
+    }
+}
+
+/// A single steelmanned counterargument against the claim.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Counterargument {
+    /// Concise statement of the opposing argument.
+    pub argument: String,
+    /// The evidence and reasoning that make this counterargument compelling.
+    pub rationale: String,
+    /// How compelling this counterargument is on its own merits (0.0-1.0),
+    /// before any rebuttal.
+    pub strength: f64,
+    /// The best answer the original claim has to this counterargument.
+    pub rebuttal: String,
+    /// How well the rebuttal answers the counterargument.
+    pub rebuttal_strength: RebuttalStrength,
+}
+
+/// Overall assessment of counterarguments against the claim.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CounterargumentAssessment {
+    /// Number of counterarguments generated.
+    pub counterargument_count: u32,
+    /// The counterargument the claim has most trouble answering.
+    pub strongest: String,
+    /// How well the claim holds up after weighing all counterarguments
+    /// and rebuttals (0.0 = refuted, 1.0 = unshaken).
+    pub claim_resilience: f64,
+}
+
+/// Response from counterargument generation operation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CounterargumentResponse {
+    /// Unique identifier for this thought.
+    pub thought_id: String,
+    /// Session this thought belongs to.
+    pub session_id: String,
+    /// List of steelmanned counterarguments, strongest first.
+    pub counterarguments: Vec<Counterargument>,
+    /// Overall assessment.
+    pub overall_assessment: CounterargumentAssessment,
+}
+
+impl CounterargumentResponse {
+    /// Create a new counterargument response.
+    #[must_use]
+    pub fn new(
+        thought_id: impl Into<String>,
+        session_id: impl Into<String>,
+        counterarguments: Vec<Counterargument>,
+        overall_assessment: CounterargumentAssessment,
+    ) -> Self {
+        Self {
+            thought_id: thought_id.into(),
+            session_id: session_id.into(),
+            counterarguments,
+            overall_assessment,
+        }
+    }
+}
+
+// ============================================================================
+// Circular Reasoning Types
+// ============================================================================
+
+/// One circular justification loop found in a session.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CircularChain {
+    /// Thought ids participating in the loop, in justification order (each
+    /// thought references the next; the last references the first).
+    pub thought_ids: Vec<String>,
+}
+
+impl CircularChain {
+    /// Create a new circular chain.
+    #[must_use]
+    pub const fn new(thought_ids: Vec<String>) -> Self {
+        Self { thought_ids }
+    }
+}
+
+/// Response from circular-reasoning detection over a session.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CircularResponse {
+    /// Session that was analyzed.
+    pub session_id: String,
+    /// Number of thoughts in the session.
+    pub thought_count: usize,
+    /// Number of justification references found between thoughts.
+    pub reference_count: usize,
+    /// Circular justification loops, if any. Empty means the session's
+    /// justification graph is acyclic.
+    pub cycles: Vec<CircularChain>,
+}
+
+impl CircularResponse {
+    /// Create a new circular-reasoning response.
+    #[must_use]
+    pub fn new(
+        session_id: impl Into<String>,
+        thought_count: usize,
+        reference_count: usize,
+        cycles: Vec<CircularChain>,
+    ) -> Self {
+        Self {
+            session_id: session_id.into(),
+            thought_count,
+            reference_count,
+            cycles,
+        }
+    }
+}
+
 #[cfg(test)]
 #[allow(
     clippy::unwrap_used,
@@ -418,11 +762,78 @@ mod tests {
             changes_conclusion: "yes".to_string(),
             impact: "I".to_string(),
             debiasing: "D".to_string(),
+            verified: false,
+            offset: None,
         };
         let cloned = bias.clone();
         assert_eq!(bias, cloned);
     }
 
+    fn detected(name: &str) -> DetectedBias {
+        DetectedBias {
+            bias: name.to_string(),
+            evidence: "E".to_string(),
+            severity: BiasSeverity::Medium,
+            confidence: 0.8,
+            changes_conclusion: "maybe".to_string(),
+            impact: "I".to_string(),
+            debiasing: "D".to_string(),
+            verified: false,
+            offset: None,
+        }
+    }
+
+    #[test]
+    fn test_bias_interactions_reports_known_pair_with_detected_names() {
+        let biases = vec![detected("Confirmation Bias"), detected("Anchoring Bias")];
+        let interactions = bias_interactions(&biases);
+
+        assert_eq!(interactions.len(), 1);
+        // References the biases exactly as detected.
+        assert_eq!(interactions[0].bias_a, "Confirmation Bias");
+        assert_eq!(interactions[0].bias_b, "Anchoring Bias");
+        assert!(interactions[0].combined_effect.contains("anchor"));
+    }
+
+    #[test]
+    fn test_bias_interactions_empty_for_single_or_unrelated_biases() {
+        assert!(bias_interactions(&[detected("Confirmation Bias")]).is_empty());
+        assert!(
+            bias_interactions(&[detected("Hindsight Bias"), detected("Sunk Cost Fallacy")])
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_bias_interactions_deduplicate_repeated_detections() {
+        let biases = vec![
+            detected("Confirmation Bias"),
+            detected("confirmation"),
+            detected("Anchoring"),
+        ];
+        assert_eq!(bias_interactions(&biases).len(), 1);
+    }
+
+    #[test]
+    fn test_biases_response_derives_interactions() {
+        let response = BiasesResponse::new(
+            "t-1",
+            "s-1",
+            vec![detected("Sunk Cost Bias"), detected("Loss Aversion")],
+            BiasAssessment {
+                bias_count: 2,
+                most_severe: "Sunk Cost Bias".to_string(),
+                conclusion_altering_biases: String::new(),
+                reasoning_quality: 0.5,
+            },
+            "debiased",
+        );
+
+        assert_eq!(response.interactions.len(), 1);
+        assert_eq!(response.interactions[0].bias_a, "Sunk Cost Bias");
+        assert_eq!(response.interactions[0].bias_b, "Loss Aversion");
+    }
+
     #[test]
     fn test_detected_fallacy_clone() {
         let fallacy = DetectedFallacy {
@@ -433,6 +844,8 @@ mod tests {
             confidence: 0.8,
             explanation: "E".to_string(),
             correction: "C".to_string(),
+            verified: false,
+            offset: None,
         };
         let cloned = fallacy.clone();
         assert_eq!(fallacy, cloned);
@@ -510,4 +923,48 @@ mod tests {
         assert_eq!(resp.gaps.len(), 1);
         assert_eq!(resp.unchallenged_assumptions.len(), 1);
     }
+
+    #[test]
+    fn test_rebuttal_strength_serialize() {
+        assert_eq!(
+            serde_json::to_string(&RebuttalStrength::Weak).unwrap(),
+            "\"weak\""
+        );
+        assert_eq!(
+            serde_json::to_string(&RebuttalStrength::Partial).unwrap(),
+            "\"partial\""
+        );
+        assert_eq!(
+            serde_json::to_string(&RebuttalStrength::Strong).unwrap(),
+            "\"strong\""
+        );
+    }
+
+    #[test]
+    fn test_rebuttal_strength_as_str() {
+        assert_eq!(RebuttalStrength::Weak.as_str(), "weak");
+        assert_eq!(RebuttalStrength::Partial.as_str(), "partial");
+        assert_eq!(RebuttalStrength::Strong.as_str(), "strong");
+    }
+
+    #[test]
+    fn test_counterargument_response_new() {
+        let counterarguments = vec![Counterargument {
+            argument: "Test objection".to_string(),
+            rationale: "Strong evidence against".to_string(),
+            strength: 0.8,
+            rebuttal: "Partial answer".to_string(),
+            rebuttal_strength: RebuttalStrength::Partial,
+        }];
+        let assessment = CounterargumentAssessment {
+            counterargument_count: 1,
+            strongest: "Test objection".to_string(),
+            claim_resilience: 0.6,
+        };
+        let resp = CounterargumentResponse::new("t1", "s1", counterarguments, assessment);
+        assert_eq!(resp.thought_id, "t1");
+        assert_eq!(resp.session_id, "s1");
+        assert_eq!(resp.counterarguments.len(), 1);
+        assert_eq!(resp.overall_assessment.claim_resilience, 0.6);
+    }
 }
@@ -0,0 +1,192 @@
+//! Post-parse citation verification for detect mode.
+//!
+//! The model sometimes cites a `passage`/`evidence` string that does not
+//! appear in the analyzed content — a hallucinated citation. These helpers
+//! cross-check every detection against the source text (fuzzy match) and set
+//! its `verified` flag, so callers can distinguish grounded findings from
+//! fabricated ones.
+
+use super::types::{DetectedBias, DetectedFallacy};
+
+/// Normalize text for quote-grounding comparison: collapse whitespace, lowercase.
+pub fn normalize_for_grounding(s: &str) -> String {
+    s.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Length of the longest run of words present contiguously in both slices.
+fn longest_common_word_run(a: &[&str], b: &[&str]) -> usize {
+    if a.is_empty() || b.is_empty() {
+        return 0;
+    }
+    let mut prev = vec![0usize; b.len() + 1];
+    let mut best = 0;
+    for ai in a {
+        let mut cur = vec![0usize; b.len() + 1];
+        for (j, bj) in b.iter().enumerate() {
+            if ai == bj {
+                cur[j + 1] = prev[j] + 1;
+                best = best.max(cur[j + 1]);
+            }
+        }
+        prev = cur;
+    }
+    best
+}
+
+/// True when the cited `quote` is grounded in `content`.
+///
+/// Models rarely quote verbatim — they embed the actual passage inside longer
+/// commentary (e.g. `"…never let me down - showing a preference for…"`). So
+/// rather than demand an exact substring, this accepts the quote when a
+/// substantial contiguous run of its words appears in the content: a run of ≥5
+/// words, or ≥half the quote (with a 3-word floor). An empty quote is grounded.
+pub fn quote_is_grounded(content_normalized: &str, quote: &str) -> bool {
+    let q = normalize_for_grounding(quote);
+    if q.is_empty() || content_normalized.contains(&q) {
+        return true;
+    }
+    let q_words: Vec<&str> = q.split(' ').collect();
+    let c_words: Vec<&str> = content_normalized.split(' ').collect();
+    let run = longest_common_word_run(&q_words, &c_words);
+    run >= 5 || (run >= 3 && run * 2 >= q_words.len())
+}
+
+/// Set each bias's `verified` flag from whether its cited `evidence` is
+/// grounded in the analyzed content.
+pub fn verify_biases(biases: &mut [DetectedBias], content: &str) {
+    let content_norm = normalize_for_grounding(content);
+    for bias in biases {
+        bias.verified = quote_is_grounded(&content_norm, &bias.evidence);
+    }
+}
+
+/// Set each fallacy's `verified` flag from whether its cited `passage` is
+/// grounded in the analyzed content.
+pub fn verify_fallacies(fallacies: &mut [DetectedFallacy], content: &str) {
+    let content_norm = normalize_for_grounding(content);
+    for fallacy in fallacies {
+        fallacy.verified = quote_is_grounded(&content_norm, &fallacy.passage);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::super::types::{BiasSeverity, FallacyCategory, FallacySeverity};
+    use super::*;
+
+    #[test]
+    fn test_normalize_collapses_whitespace_and_case() {
+        assert_eq!(
+            normalize_for_grounding("  The   QUICK\nBrown "),
+            "the quick brown"
+        );
+    }
+
+    #[test]
+    fn test_quote_grounded_when_present() {
+        let content = normalize_for_grounding("Our product is superior because customers say so");
+        assert!(quote_is_grounded(&content, "customers say so"));
+        // Whitespace/case differences still match.
+        assert!(quote_is_grounded(&content, "Customers   Say So"));
+    }
+
+    #[test]
+    fn test_quote_not_grounded_when_absent() {
+        let content = normalize_for_grounding("Our product is superior");
+        assert!(!quote_is_grounded(
+            &content,
+            "a paraphrased claim not present"
+        ));
+    }
+
+    #[test]
+    fn test_quote_grounded_when_embedded_in_commentary() {
+        // The real failure mode: the model quotes a passage then appends its own
+        // commentary, so the full evidence string is not a verbatim substring.
+        let content =
+            normalize_for_grounding("I've used them for years and they've never let me down");
+        let evidence =
+            "I've used them for years and they've never let me down - showing a preference \
+             for maintaining the current vendor relationship without objective evaluation";
+        assert!(quote_is_grounded(&content, evidence));
+    }
+
+    #[test]
+    fn test_single_shared_word_does_not_ground_long_quote() {
+        // A lone common word must not count as grounding.
+        let content = normalize_for_grounding("The deployment pipeline is fast and reliable");
+        assert!(!quote_is_grounded(
+            &content,
+            "the moon orbits a distant planet very quietly"
+        ));
+    }
+
+    #[test]
+    fn test_empty_quote_is_grounded() {
+        let content = normalize_for_grounding("anything");
+        assert!(quote_is_grounded(&content, "   "));
+    }
+
+    fn fallacy(passage: &str) -> DetectedFallacy {
+        DetectedFallacy {
+            fallacy: "Ad Hominem".to_string(),
+            category: FallacyCategory::Informal,
+            passage: passage.to_string(),
+            severity: FallacySeverity::Medium,
+            confidence: 0.8,
+            explanation: "E".to_string(),
+            correction: "C".to_string(),
+            verified: false,
+            offset: None,
+        }
+    }
+
+    #[test]
+    fn test_verify_fallacies_flags_fabricated_passage() {
+        let content = "You're wrong because you're stupid, so the plan fails";
+        let mut fallacies = vec![
+            fallacy("You're wrong because you're stupid"),
+            fallacy("Everyone agrees this plan is doomed to fail"),
+        ];
+        verify_fallacies(&mut fallacies, content);
+        assert!(fallacies[0].verified);
+        assert!(!fallacies[1].verified);
+    }
+
+    #[test]
+    fn test_verify_biases_flags_fabricated_evidence() {
+        let content = "Only citing supporting evidence from friendly analysts here";
+        let mut biases = vec![
+            DetectedBias {
+                bias: "Confirmation Bias".to_string(),
+                evidence: "Only citing supporting evidence from friendly analysts".to_string(),
+                severity: BiasSeverity::High,
+                confidence: 0.9,
+                changes_conclusion: "yes".to_string(),
+                impact: "I".to_string(),
+                debiasing: "D".to_string(),
+                verified: false,
+                offset: None,
+            },
+            DetectedBias {
+                bias: "Anchoring".to_string(),
+                evidence: "The first estimate of nine million dominated all later numbers"
+                    .to_string(),
+                severity: BiasSeverity::Low,
+                confidence: 0.5,
+                changes_conclusion: "no".to_string(),
+                impact: "I".to_string(),
+                debiasing: "D".to_string(),
+                verified: false,
+                offset: None,
+            },
+        ];
+        verify_biases(&mut biases, content);
+        assert!(biases[0].verified);
+        assert!(!biases[1].verified);
+    }
+}
@@ -10,17 +10,65 @@
 #![allow(clippy::similar_names)]
 #![allow(clippy::cast_precision_loss)]
 
+use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
 
 use crate::anthropic::StreamAccumulator;
 use crate::error::ModeError;
-use crate::modes::{extract_json, generate_thought_id, validate_content};
-use crate::prompts::{get_prompt_for_mode, Operation, ReasoningMode};
+use crate::modes::{
+    append_language_instruction, enforce_context_budget, extract_json, generate_thought_id,
+    reject_unknown_keys, validate_content, ModeFlags,
+};
+use crate::prompts::{
+    divergent_single_perspective_prompt, divergent_synthesis_prompt, get_prompt_for_mode,
+    Operation, ReasoningMode,
+};
 use crate::server::{ProgressMilestone, ProgressReporter};
 use crate::traits::{
     AnthropicClientTrait, CompletionConfig, Message, Session, StorageTrait, Thought,
 };
 
+/// Top-level keys the divergent response parser reads (strict parsing).
+const DIVERGENT_RESPONSE_KEYS: &[&str] = &[
+    "perspectives",
+    "contrarian_perspectives",
+    "assumptions_identified",
+    "challenged_assumptions",
+    "tensions",
+    "synergies",
+    "synthesis",
+    "strongest_challenge",
+];
+
+/// Top-level keys a single per-perspective completion may return.
+const SINGLE_PERSPECTIVE_KEYS: &[&str] = &[
+    "name",
+    "viewpoint",
+    "content",
+    "key_insight",
+    "novelty_score",
+    "blind_spots",
+];
+
+/// Top-level keys the per-perspective synthesis completion may return.
+const SYNTHESIS_RESPONSE_KEYS: &[&str] = &[
+    "tensions",
+    "synergies",
+    "synthesis",
+    "challenged_assumptions",
+    "assumptions_identified",
+];
+
+/// Analytical lenses assigned to per-perspective completions, in fan-out
+/// order. Five entries — one per perspective at the cap.
+const PERSPECTIVE_LENSES: &[&str] = &[
+    "opportunity-focused",
+    "risk-focused",
+    "contrarian",
+    "systems-level",
+    "first-principles",
+];
+
 /// A single perspective from divergent reasoning.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Perspective {
@@ -91,6 +139,10 @@ pub struct DivergentResponse {
     /// Unified synthesis from all perspectives.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub synthesis: Option<String>,
+    /// Raw extended-thinking trace from the model. Only present when the
+    /// caller opted in via `include_thinking`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<String>,
 }
 
 impl DivergentResponse {
@@ -109,6 +161,7 @@ impl DivergentResponse {
             tensions: None,
             synergies: None,
             synthesis: None,
+            thinking: None,
         }
     }
 
@@ -139,6 +192,13 @@ impl DivergentResponse {
         self.synthesis = Some(synthesis.into());
         self
     }
+
+    /// Attach the raw extended-thinking trace.
+    #[must_use]
+    pub fn with_thinking(mut self, thinking: impl Into<String>) -> Self {
+        self.thinking = Some(thinking.into());
+        self
+    }
 }
 
 /// Divergent reasoning mode.
@@ -151,6 +211,19 @@ where
 {
     storage: S,
     client: C,
+    /// Behavior flags injected from server configuration (all off by default).
+    flags: ModeFlags,
+    /// Effective output language (per-request override merged with the
+    /// configured default at the server boundary).
+    language: Option<String>,
+    /// Surface the raw extended-thinking trace in the response. Off by
+    /// default — the trace is a debugging aid, not part of the result.
+    include_thinking: bool,
+    /// Run one deep completion per perspective concurrently instead of
+    /// generating every perspective in a single call. Off by default.
+    per_perspective: bool,
+    /// Cap on concurrent per-perspective completions.
+    perspective_concurrency: usize,
 }
 
 impl<S, C> DivergentMode<S, C>
@@ -161,7 +234,57 @@ where
     /// Create a new divergent mode instance.
     #[must_use]
     pub fn new(storage: S, client: C) -> Self {
-        Self { storage, client }
+        Self {
+            storage,
+            client,
+            flags: ModeFlags::default(),
+            language: None,
+            include_thinking: false,
+            per_perspective: false,
+            perspective_concurrency: crate::config::DEFAULT_DIVERGENT_MAX_CONCURRENCY as usize,
+        }
+    }
+
+    /// Set the output language for natural-language response fields. The
+    /// server passes the per-request override merged with the configured
+    /// `RESPONSE_LANGUAGE` default; `None` adds no language instruction.
+    #[must_use]
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Inject the server's behavior flags ([`ModeFlags`]). A mode built
+    /// without them — as in most tests — uses the all-off defaults.
+    #[must_use]
+    pub fn with_flags(mut self, flags: ModeFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Opt in to returning the raw extended-thinking trace alongside the
+    /// parsed result (default: hidden).
+    #[must_use]
+    pub fn with_include_thinking(mut self, include_thinking: bool) -> Self {
+        self.include_thinking = include_thinking;
+        self
+    }
+
+    /// Opt in to per-perspective fan-out: one deep completion per perspective
+    /// run concurrently, aggregated with a synthesis pass (default: single
+    /// call). Rebellion requests keep the single-call contrarian prompt.
+    #[must_use]
+    pub fn with_per_perspective(mut self, per_perspective: bool) -> Self {
+        self.per_perspective = per_perspective;
+        self
+    }
+
+    /// Set the cap on concurrent per-perspective completions. Values below 1
+    /// are treated as 1.
+    #[must_use]
+    pub fn with_perspective_concurrency(mut self, concurrency: usize) -> Self {
+        self.perspective_concurrency = concurrency.max(1);
+        self
     }
 
     /// Generate multiple perspectives on the content.
@@ -197,13 +320,33 @@ where
             String::new()
         };
 
+        // Per-perspective fan-out replaces the single multi-perspective call.
+        // Rebellion keeps the single-call contrarian prompt: its output schema
+        // (assumptions, radical perspective) doesn't decompose per perspective.
+        if self.per_perspective && !force_rebellion {
+            return self
+                .process_per_perspective(
+                    content,
+                    &session,
+                    num_perspectives,
+                    challenge_assumptions,
+                    &prior_context,
+                    None,
+                )
+                .await;
+        }
+
         // Select prompt based on force_rebellion
         let operation = if force_rebellion {
             Some(Operation::ForceRebellion)
         } else {
             None
         };
-        let prompt = get_prompt_for_mode(ReasoningMode::Divergent, operation.as_ref());
+        let prompt = append_language_instruction(
+            get_prompt_for_mode(ReasoningMode::Divergent, operation.as_ref()),
+            self.language.as_deref(),
+            content,
+        );
 
         let user_message = if challenge_assumptions {
             format!(
@@ -213,15 +356,24 @@ where
             format!("{prompt}\n\n{prior_context}Generate {num_perspectives} perspectives for:\n{content}")
         };
 
-        let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("divergent")
             .with_max_tokens(16384)
             .with_temperature(0.9)
             .with_deep_thinking();
+        enforce_context_budget(&user_message, config.max_tokens)?;
+        let messages = vec![Message::user(user_message)];
 
         let response = self.client.complete(messages, config).await?;
+        let thinking = if self.include_thinking {
+            response.thinking.clone()
+        } else {
+            None
+        };
         let json = extract_json(&response.content)?;
 
+        reject_unknown_keys(self.flags.strict_parsing, &json, DIVERGENT_RESPONSE_KEYS)?;
+
         // Parse perspectives
         let perspectives = Self::parse_perspectives(&json, force_rebellion)?;
 
@@ -266,6 +418,9 @@ where
         if let Some(syn) = synthesis {
             response = response.with_synthesis(syn);
         }
+        if let Some(t) = thinking {
+            response = response.with_thinking(t);
+        }
 
         Ok(response)
     }
@@ -312,13 +467,32 @@ where
             String::new()
         };
 
+        // Per-perspective fan-out uses non-streaming completions per
+        // perspective; progress still advances through the coarse milestones.
+        if self.per_perspective && !force_rebellion {
+            return self
+                .process_per_perspective(
+                    content,
+                    &session,
+                    num_perspectives,
+                    challenge_assumptions,
+                    &prior_context,
+                    progress,
+                )
+                .await;
+        }
+
         // Select prompt based on force_rebellion
         let operation = if force_rebellion {
             Some(Operation::ForceRebellion)
         } else {
             None
         };
-        let prompt = get_prompt_for_mode(ReasoningMode::Divergent, operation.as_ref());
+        let prompt = append_language_instruction(
+            get_prompt_for_mode(ReasoningMode::Divergent, operation.as_ref()),
+            self.language.as_deref(),
+            content,
+        );
 
         let user_message = if challenge_assumptions {
             format!(
@@ -328,11 +502,13 @@ where
             format!("{prompt}\n\n{prior_context}Generate {num_perspectives} perspectives for:\n{content}")
         };
 
-        let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("divergent")
             .with_max_tokens(16384)
             .with_temperature(0.9)
             .with_deep_thinking();
+        enforce_context_budget(&user_message, config.max_tokens)?;
+        let messages = vec![Message::user(user_message)];
 
         if let Some(p) = progress {
             p.report_milestone(ProgressMilestone::ApiCallStarted);
@@ -357,8 +533,15 @@ where
         }
 
         let response_text = accumulator.text();
+        let thinking = if self.include_thinking {
+            accumulator.thinking().map(String::from)
+        } else {
+            None
+        };
         let json = extract_json(&response_text)?;
 
+        reject_unknown_keys(self.flags.strict_parsing, &json, DIVERGENT_RESPONSE_KEYS)?;
+
         // Parse perspectives
         let perspectives = Self::parse_perspectives(&json, force_rebellion)?;
 
@@ -391,6 +574,101 @@ where
         // Build response
         let mut response = DivergentResponse::new(&thought_id, &session.id, perspectives);
 
+        if let Some(assumptions) = challenged_assumptions {
+            response = response.with_challenged_assumptions(assumptions);
+        }
+        if let Some(t) = tensions {
+            response = response.with_tensions(t);
+        }
+        if let Some(s) = synergies {
+            response = response.with_synergies(s);
+        }
+        if let Some(syn) = synthesis {
+            response = response.with_synthesis(syn);
+        }
+        if let Some(t) = thinking {
+            response = response.with_thinking(t);
+        }
+
+        if let Some(p) = progress {
+            p.report_milestone(ProgressMilestone::Complete);
+        }
+
+        Ok(response)
+    }
+
+    /// Run the per-perspective fan-out: one deep completion per perspective
+    /// (at most `perspective_concurrency` in flight), then a synthesis pass
+    /// over the aggregated results.
+    ///
+    /// Each perspective is developed independently through its assigned lens,
+    /// so the viewpoints are richer than a single multi-perspective call can
+    /// produce. Results keep lens order.
+    async fn process_per_perspective(
+        &self,
+        content: &str,
+        session: &Session,
+        num_perspectives: u32,
+        challenge_assumptions: bool,
+        prior_context: &str,
+        progress: Option<&ProgressReporter>,
+    ) -> Result<DivergentResponse, ModeError> {
+        // Boxing the futures as `dyn Future` keeps the combined future `Send`
+        // through the nested async layers above this one (auto and
+        // confidence-route delegation).
+        type PerspectiveFuture<'a> = std::pin::Pin<
+            Box<dyn std::future::Future<Output = Result<Perspective, ModeError>> + Send + 'a>,
+        >;
+
+        if let Some(p) = progress {
+            p.report_milestone(ProgressMilestone::ApiCallStarted);
+        }
+
+        // `buffered` (not `buffer_unordered`) keeps results aligned with the
+        // lens order while still running up to the cap concurrently.
+        let mut futures: Vec<PerspectiveFuture<'_>> = Vec::with_capacity(num_perspectives as usize);
+        for lens in PERSPECTIVE_LENSES
+            .iter()
+            .copied()
+            .take(num_perspectives as usize)
+        {
+            futures.push(Box::pin(self.complete_one_perspective(
+                content,
+                prior_context,
+                lens,
+            )));
+        }
+        let completions = futures_util::stream::iter(futures)
+            .buffered(self.perspective_concurrency)
+            .collect::<Vec<_>>()
+            .await;
+
+        let mut perspectives = Vec::with_capacity(completions.len());
+        for completion in completions {
+            perspectives.push(completion?);
+        }
+
+        if let Some(p) = progress {
+            p.report_milestone(ProgressMilestone::ProcessingResponse);
+        }
+
+        let (challenged_assumptions, tensions, synergies, synthesis) = self
+            .synthesize_perspectives(content, &perspectives, challenge_assumptions)
+            .await?;
+
+        let thought_id = generate_thought_id();
+        let avg_novelty = if perspectives.is_empty() {
+            0.5
+        } else {
+            perspectives.iter().map(|p| p.novelty_score).sum::<f64>() / perspectives.len() as f64
+        };
+
+        let thought = Thought::new(&thought_id, &session.id, content, "divergent", avg_novelty);
+        if let Err(e) = self.storage.save_thought(&thought).await {
+            tracing::warn!(error = %e, "Storage write failed — reasoning result preserved, thought not persisted");
+        }
+
+        let mut response = DivergentResponse::new(&thought_id, &session.id, perspectives);
         if let Some(assumptions) = challenged_assumptions {
             response = response.with_challenged_assumptions(assumptions);
         }
@@ -411,6 +689,148 @@ where
         Ok(response)
     }
 
+    /// One deep completion developing a single perspective through `lens`.
+    async fn complete_one_perspective(
+        &self,
+        content: &str,
+        prior_context: &str,
+        lens: &str,
+    ) -> Result<Perspective, ModeError> {
+        let prompt = append_language_instruction(
+            divergent_single_perspective_prompt(),
+            self.language.as_deref(),
+            content,
+        );
+        let user_message = format!(
+            "{prompt}\n\n{prior_context}Assigned lens: {lens}\n\nDevelop exactly one perspective for:\n{content}"
+        );
+
+        let config = CompletionConfig::new()
+            .with_mode("divergent")
+            .with_max_tokens(16384)
+            .with_temperature(0.9)
+            .with_deep_thinking();
+        enforce_context_budget(&user_message, config.max_tokens)?;
+        let messages = vec![Message::user(user_message)];
+
+        let response = self.client.complete(messages, config).await?;
+        let json = extract_json(&response.content)?;
+        reject_unknown_keys(self.flags.strict_parsing, &json, SINGLE_PERSPECTIVE_KEYS)?;
+        Self::parse_single_perspective(&json, lens)
+    }
+
+    /// Parse the JSON object a single per-perspective completion returns.
+    /// Falls back to the assigned lens when the model omits a name.
+    fn parse_single_perspective(
+        json: &serde_json::Value,
+        lens: &str,
+    ) -> Result<Perspective, ModeError> {
+        let viewpoint = json
+            .get("name")
+            .and_then(|v| v.as_str())
+            .unwrap_or(lens)
+            .to_string();
+
+        let content = json
+            .get("viewpoint")
+            .or_else(|| json.get("content"))
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| ModeError::MissingField {
+                field: "viewpoint".to_string(),
+            })?
+            .to_string();
+
+        let novelty_score = json
+            .get("novelty_score")
+            .and_then(serde_json::Value::as_f64)
+            .unwrap_or(0.5)
+            .clamp(0.0, 1.0);
+
+        let key_insight = json
+            .get("key_insight")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+
+        let blind_spots = json.get("blind_spots").and_then(|v| {
+            v.as_array().map(|arr| {
+                arr.iter()
+                    .filter_map(|s| s.as_str().map(String::from))
+                    .collect()
+            })
+        });
+
+        let mut perspective = Perspective::new(viewpoint, content, novelty_score);
+        if let Some(insight) = key_insight {
+            perspective = perspective.with_key_insight(insight);
+        }
+        if let Some(spots) = blind_spots {
+            perspective = perspective.with_blind_spots(spots);
+        }
+        Ok(perspective)
+    }
+
+    /// Synthesis pass over independently generated perspectives. Returns
+    /// `(challenged_assumptions, tensions, synergies, synthesis)`.
+    #[allow(clippy::type_complexity)]
+    async fn synthesize_perspectives(
+        &self,
+        content: &str,
+        perspectives: &[Perspective],
+        challenge_assumptions: bool,
+    ) -> Result<
+        (
+            Option<Vec<String>>,
+            Option<Vec<String>>,
+            Option<Vec<String>>,
+            Option<String>,
+        ),
+        ModeError,
+    > {
+        let prompt = append_language_instruction(
+            divergent_synthesis_prompt(),
+            self.language.as_deref(),
+            content,
+        );
+        let perspective_block = perspectives
+            .iter()
+            .map(|p| format!("- {}: {}", p.viewpoint, p.content))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let user_message = if challenge_assumptions {
+            format!(
+                "{prompt}\n\nIMPORTANT: Also identify and challenge hidden assumptions.\n\nOriginal content:\n{content}\n\nIndependent perspectives:\n{perspective_block}"
+            )
+        } else {
+            format!(
+                "{prompt}\n\nOriginal content:\n{content}\n\nIndependent perspectives:\n{perspective_block}"
+            )
+        };
+
+        let config = CompletionConfig::new()
+            .with_mode("divergent")
+            .with_max_tokens(16384)
+            .with_temperature(0.9)
+            .with_deep_thinking();
+        enforce_context_budget(&user_message, config.max_tokens)?;
+        let messages = vec![Message::user(user_message)];
+
+        let response = self.client.complete(messages, config).await?;
+        let json = extract_json(&response.content)?;
+        reject_unknown_keys(self.flags.strict_parsing, &json, SYNTHESIS_RESPONSE_KEYS)?;
+
+        let challenged = Self::parse_string_array(&json, "challenged_assumptions")
+            .or_else(|| Self::parse_string_array(&json, "assumptions_identified"));
+        let tensions = Self::parse_string_array(&json, "tensions");
+        let synergies = Self::parse_string_array(&json, "synergies");
+        let synthesis = json
+            .get("synthesis")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+
+        Ok((challenged, tensions, synergies, synthesis))
+    }
+
     /// Parse perspectives from JSON response.
     fn parse_perspectives(
         json: &serde_json::Value,
@@ -743,6 +1163,72 @@ mod tests {
         assert!(response.synthesis.is_some());
     }
 
+    #[tokio::test]
+    async fn include_thinking_surfaces_trace_when_requested() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("s")));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+
+        mock_client.expect_complete().returning(|_, _| {
+            Ok(CompletionResponse::new(
+                r#"{"perspectives": [
+                    {"name": "A", "viewpoint": "v1", "novelty_score": 0.5},
+                    {"name": "B", "viewpoint": "v2", "novelty_score": 0.5}
+                ]}"#,
+                Usage::new(50, 100),
+            )
+            .with_thinking("Weighing angles before committing..."))
+        });
+
+        let mode = DivergentMode::new(mock_storage, mock_client).with_include_thinking(true);
+        let response = mode
+            .process("topic", None, Some(2), false, false)
+            .await
+            .unwrap();
+        assert_eq!(
+            response.thinking.as_deref(),
+            Some("Weighing angles before committing...")
+        );
+    }
+
+    #[tokio::test]
+    async fn thinking_stays_hidden_by_default() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("s")));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+
+        // The API returns a thinking block, but the caller did not opt in.
+        mock_client.expect_complete().returning(|_, _| {
+            Ok(CompletionResponse::new(
+                r#"{"perspectives": [
+                    {"name": "A", "viewpoint": "v1", "novelty_score": 0.5},
+                    {"name": "B", "viewpoint": "v2", "novelty_score": 0.5}
+                ]}"#,
+                Usage::new(50, 100),
+            )
+            .with_thinking("Weighing angles before committing..."))
+        });
+
+        let mode = DivergentMode::new(mock_storage, mock_client);
+        let response = mode
+            .process("topic", None, Some(2), false, false)
+            .await
+            .unwrap();
+        assert!(response.thinking.is_none());
+
+        // And the serialized response carries no trace of the hidden field.
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(!json.contains("thinking"));
+    }
+
     #[tokio::test]
     async fn test_divergent_parses_novelty_and_key_insight() {
         let mut mock_storage = MockStorageTrait::new();
@@ -1379,4 +1865,126 @@ mod tests {
             Err(ModeError::MissingField { field }) if field == "content"
         ));
     }
+
+    // Per-perspective fan-out tests
+    #[tokio::test]
+    async fn test_per_perspective_issues_one_completion_per_lens() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("fan")));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+
+        // Exactly one deep completion per requested perspective. The
+        // response omits "name" so the parsed viewpoint falls back to the
+        // assigned lens, proving each call carried a distinct lens.
+        mock_client
+            .expect_complete()
+            .withf(|messages, _| {
+                messages
+                    .first()
+                    .is_some_and(|m| m.content.contains("Develop exactly one perspective"))
+            })
+            .times(3)
+            .returning(|_, _| {
+                Ok(CompletionResponse::new(
+                    r#"{"viewpoint": "deep take", "key_insight": "it compounds", "novelty_score": 0.8}"#,
+                    Usage::new(50, 100),
+                ))
+            });
+        // Plus one synthesis pass over the collected perspectives.
+        mock_client
+            .expect_complete()
+            .withf(|messages, _| {
+                messages
+                    .first()
+                    .is_some_and(|m| m.content.contains("Independent perspectives:"))
+            })
+            .times(1)
+            .returning(|_, _| {
+                Ok(CompletionResponse::new(
+                    r#"{"tensions": ["speed vs safety"], "synergies": ["shared data"], "synthesis": "Combined insight"}"#,
+                    Usage::new(50, 100),
+                ))
+            });
+
+        let mode = DivergentMode::new(mock_storage, mock_client).with_per_perspective(true);
+        let response = mode
+            .process("topic", None, Some(3), false, false)
+            .await
+            .unwrap();
+
+        assert_eq!(response.perspectives.len(), 3);
+        assert_eq!(response.perspectives[0].viewpoint, "opportunity-focused");
+        assert_eq!(response.perspectives[1].viewpoint, "risk-focused");
+        assert_eq!(response.perspectives[2].viewpoint, "contrarian");
+        assert_eq!(
+            response.perspectives[0].key_insight.as_deref(),
+            Some("it compounds")
+        );
+        assert_eq!(response.synthesis.as_deref(), Some("Combined insight"));
+        assert_eq!(response.tensions.unwrap(), vec!["speed vs safety"]);
+        assert_eq!(response.synergies.unwrap(), vec!["shared data"]);
+    }
+
+    #[tokio::test]
+    async fn test_per_perspective_rebellion_keeps_single_call() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("reb")));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+
+        // force_rebellion overrides the fan-out: one contrarian call only.
+        let response_json = mock_rebellion_response();
+        mock_client
+            .expect_complete()
+            .times(1)
+            .returning(move |_, _| {
+                Ok(CompletionResponse::new(
+                    response_json.clone(),
+                    Usage::new(100, 200),
+                ))
+            });
+
+        let mode = DivergentMode::new(mock_storage, mock_client).with_per_perspective(true);
+        let response = mode
+            .process("topic", None, None, false, true)
+            .await
+            .unwrap();
+        assert_eq!(response.perspectives.len(), 1);
+        assert!(response.challenged_assumptions.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_per_perspective_completion_error_propagates() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("err")));
+
+        mock_client.expect_complete().returning(|_, _| {
+            Err(ModeError::ApiUnavailable {
+                message: "API error".to_string(),
+            })
+        });
+
+        let mode = DivergentMode::new(mock_storage, mock_client).with_per_perspective(true);
+        let result = mode.process("topic", None, Some(2), false, false).await;
+        assert!(matches!(result, Err(ModeError::ApiUnavailable { .. })));
+    }
+
+    #[test]
+    fn test_perspective_concurrency_floors_at_one() {
+        let mock_storage = MockStorageTrait::new();
+        let mock_client = MockAnthropicClientTrait::new();
+        let mode = DivergentMode::new(mock_storage, mock_client).with_perspective_concurrency(0);
+        assert_eq!(mode.perspective_concurrency, 1);
+    }
 }
@@ -0,0 +1,166 @@
+//! Escalation ladder policy for the `reasoning_solve` tool.
+//!
+//! "Just solve it well" callers don't want to pick a mode: the ladder starts
+//! cheap (`linear`) and escalates to progressively heavier modes (`tree`,
+//! then `graph`) until a rung's confidence clears the threshold or the ladder
+//! runs out. This module holds the policy — which rungs, in what order, and
+//! when to stop — as pure data; the server handler walks it.
+
+use crate::error::ModeError;
+
+/// One rung of the escalation ladder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscalationRung {
+    /// Single-pass sequential reasoning (cheapest).
+    Linear,
+    /// Branching exploration (tree create).
+    Tree,
+    /// Graph-of-Thoughts init + generate + finalize (heaviest).
+    Graph,
+}
+
+impl EscalationRung {
+    /// Parse a rung name as given in a tool call.
+    pub fn parse(name: &str) -> Result<Self, ModeError> {
+        match name.to_lowercase().as_str() {
+            "linear" => Ok(Self::Linear),
+            "tree" => Ok(Self::Tree),
+            "graph" => Ok(Self::Graph),
+            other => Err(ModeError::InvalidValue {
+                field: "ladder".to_string(),
+                reason: format!("Unknown rung '{other}'. Valid rungs: linear, tree, graph"),
+            }),
+        }
+    }
+
+    /// The rung's name, as accepted by [`Self::parse`].
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Linear => "linear",
+            Self::Tree => "tree",
+            Self::Graph => "graph",
+        }
+    }
+}
+
+impl std::fmt::Display for EscalationRung {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The escalation ladder: ordered rungs plus the stop threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EscalationPolicy {
+    /// Rungs to try, cheapest first.
+    pub rungs: Vec<EscalationRung>,
+    /// Confidence at or above which the ladder stops (clamped to [0.0, 1.0]).
+    pub confidence_threshold: f64,
+}
+
+impl EscalationPolicy {
+    /// Build a policy from explicit rungs. Fails on an empty ladder.
+    pub fn new(rungs: Vec<EscalationRung>, confidence_threshold: f64) -> Result<Self, ModeError> {
+        if rungs.is_empty() {
+            return Err(ModeError::InvalidValue {
+                field: "ladder".to_string(),
+                reason: "Ladder must contain at least one rung".to_string(),
+            });
+        }
+        Ok(Self {
+            rungs,
+            confidence_threshold: confidence_threshold.clamp(0.0, 1.0),
+        })
+    }
+
+    /// The built-in ladder: linear → tree → graph.
+    #[must_use]
+    pub fn default_ladder(confidence_threshold: f64) -> Self {
+        Self {
+            rungs: vec![
+                EscalationRung::Linear,
+                EscalationRung::Tree,
+                EscalationRung::Graph,
+            ],
+            confidence_threshold: confidence_threshold.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Build a policy from caller-supplied rung names (the configurable path).
+    pub fn from_names(names: &[String], confidence_threshold: f64) -> Result<Self, ModeError> {
+        let rungs = names
+            .iter()
+            .map(|name| EscalationRung::parse(name))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(rungs, confidence_threshold)
+    }
+
+    /// Whether a rung's confidence is good enough to stop the ladder.
+    #[must_use]
+    pub fn met(&self, confidence: f64) -> bool {
+        confidence >= self.confidence_threshold
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp,
+    clippy::approx_constant,
+    clippy::unreadable_literal
+)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_ladder_is_linear_tree_graph() {
+        let policy = EscalationPolicy::default_ladder(0.75);
+        assert_eq!(
+            policy.rungs,
+            vec![
+                EscalationRung::Linear,
+                EscalationRung::Tree,
+                EscalationRung::Graph
+            ]
+        );
+        assert_eq!(policy.confidence_threshold, 0.75);
+    }
+
+    #[test]
+    fn test_from_names_builds_custom_ladder() {
+        let names = vec!["tree".to_string(), "GRAPH".to_string()];
+        let policy = EscalationPolicy::from_names(&names, 0.6).expect("valid ladder");
+        assert_eq!(
+            policy.rungs,
+            vec![EscalationRung::Tree, EscalationRung::Graph]
+        );
+    }
+
+    #[test]
+    fn test_unknown_rung_rejected() {
+        let names = vec!["linear".to_string(), "mcts".to_string()];
+        let err = EscalationPolicy::from_names(&names, 0.6).expect_err("invalid rung");
+        assert!(err.to_string().contains("mcts"));
+    }
+
+    #[test]
+    fn test_empty_ladder_rejected() {
+        let err = EscalationPolicy::new(Vec::new(), 0.6).expect_err("empty ladder");
+        assert!(err.to_string().contains("at least one rung"));
+    }
+
+    #[test]
+    fn test_threshold_clamped_and_met() {
+        let policy = EscalationPolicy::default_ladder(1.5);
+        assert_eq!(policy.confidence_threshold, 1.0);
+        assert!(policy.met(1.0));
+        assert!(!policy.met(0.99));
+
+        let policy = EscalationPolicy::default_ladder(0.7);
+        assert!(policy.met(0.7));
+        assert!(!policy.met(0.69));
+    }
+}
@@ -1,8 +1,10 @@
 //! Evidence evaluation mode.
 //!
-//! This mode provides two operations:
+//! This mode provides three operations:
 //! - `assess`: Evaluate source credibility and evidence quality
 //! - `probabilistic`: Perform Bayesian belief updating
+//! - `synthesize`: Run both, feeding assessed credibility into the
+//!   likelihoods and recalibrating the posterior by evidence credibility
 //!
 //! # Output Schema
 //!
@@ -20,23 +22,28 @@ mod parsing;
 mod types;
 
 pub use types::{
-    AssessResponse, BeliefDirection, BeliefMagnitude, BeliefUpdate, Credibility, EvidenceAnalysis,
+    credible_interval, rank_value_of_information, AssessResponse, BeliefDirection, BeliefMagnitude,
+    BeliefUpdate, Credibility, CredibleInterval, EvidenceAnalysis, EvidenceConflict, EvidenceGap,
     EvidencePiece, EvidenceQuality, OverallEvidenceAssessment, Posterior, Prior,
-    ProbabilisticResponse, SourceType,
+    ProbabilisticResponse, SourceType, SynthesizeResponse, ValueOfInformation,
 };
 
 use std::fmt::Write as _;
 
 use crate::error::ModeError;
-use crate::modes::{extract_json, generate_thought_id, validate_content};
+use crate::modes::{
+    apply_memory_update, chunk_content, extract_json, generate_thought_id, language_instruction,
+    load_working_memory_block, parse_open_questions, persist_open_questions, reject_unknown_keys,
+    validate_content, ContentChunk, ModeFlags, CHUNK_MAX_BYTES,
+};
 use crate::prompts::{evidence_assess_prompt, evidence_probabilistic_prompt};
 use crate::traits::{
     AnthropicClientTrait, CompletionConfig, Message, Session, StorageTrait, Thought,
 };
 
 use parsing::{
-    parse_belief_update, parse_confidence, parse_evidence_analysis, parse_evidence_pieces,
-    parse_overall_assessment, parse_posterior, parse_prior,
+    parse_belief_update, parse_confidence, parse_evidence_analysis, parse_evidence_conflicts,
+    parse_evidence_pieces, parse_overall_assessment, parse_posterior, parse_prior,
 };
 
 // ============================================================================
@@ -53,6 +60,17 @@ where
 {
     storage: S,
     client: C,
+    /// Behavior flags injected from server configuration (all off by default).
+    flags: ModeFlags,
+    /// Effective output language (per-request override merged with the
+    /// configured default at the server boundary).
+    language: Option<String>,
+    /// When set, oversized content is split on paragraph boundaries and each
+    /// chunk assessed separately, with evidence pieces merged and deduplicated.
+    chunking: bool,
+    /// Half-width applied to likelihoods without a model-provided range when
+    /// computing the credible interval around a probabilistic posterior.
+    interval_width: f64,
 }
 
 impl<S, C> EvidenceMode<S, C>
@@ -63,7 +81,52 @@ where
     /// Create a new evidence mode instance.
     #[must_use]
     pub fn new(storage: S, client: C) -> Self {
-        Self { storage, client }
+        Self {
+            storage,
+            client,
+            flags: ModeFlags::default(),
+            language: None,
+            chunking: false,
+            interval_width: crate::config::DEFAULT_EVIDENCE_INTERVAL_WIDTH,
+        }
+    }
+
+    /// Set the output language for natural-language response fields. The
+    /// server passes the per-request override merged with the configured
+    /// `RESPONSE_LANGUAGE` default; `None` adds no language instruction.
+    #[must_use]
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Inject the server's behavior flags ([`ModeFlags`]). A mode built
+    /// without them — as in most tests — uses the all-off defaults.
+    #[must_use]
+    pub fn with_flags(mut self, flags: ModeFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Enable content chunking for `assess`: content larger than
+    /// [`CHUNK_MAX_BYTES`] is split on paragraph boundaries, each chunk
+    /// assessed separately, and the per-chunk evidence pieces merged without
+    /// duplicates. Content that fits in a single chunk takes the ordinary
+    /// path regardless of this flag; `probabilistic` is unaffected (a Bayesian
+    /// update needs the hypothesis and evidence together).
+    #[must_use]
+    pub const fn with_chunking(mut self, chunking: bool) -> Self {
+        self.chunking = chunking;
+        self
+    }
+
+    /// Set the default credible-interval half-width applied to likelihoods
+    /// the model did not bound with an explicit range (configured via
+    /// `EVIDENCE_INTERVAL_WIDTH`).
+    #[must_use]
+    pub const fn with_interval_width(mut self, interval_width: f64) -> Self {
+        self.interval_width = interval_width;
+        self
     }
 
     /// Assess evidence quality and credibility.
@@ -83,32 +146,35 @@ where
     ) -> Result<AssessResponse, ModeError> {
         validate_content(content)?;
 
+        if self.chunking {
+            let chunks = chunk_content(content, CHUNK_MAX_BYTES);
+            if chunks.len() > 1 {
+                return self.assess_chunked(&chunks, session_id).await;
+            }
+        }
+
         let has_prior_session = session_id.is_some();
         let session = self.get_or_create_session(session_id).await?;
 
         let prompt = evidence_assess_prompt();
-        let user_message = self
-            .build_user_message(
-                prompt,
-                content,
-                &session.id,
-                has_prior_session,
-                "Evidence to assess",
-            )
-            .await;
-
-        let messages = vec![Message::user(user_message)];
-        let config = CompletionConfig::new()
-            .with_max_tokens(16384)
-            .with_temperature(0.3)
-            .with_deep_thinking();
-
-        let response = self.client.complete(messages, config).await?;
-        let json = extract_json(&response.content)?;
-
+        let json = self
+            .assess_completion(prompt, content, &session.id, has_prior_session)
+            .await?;
+
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &[
+                "evidence_pieces",
+                "overall_assessment",
+                "confidence_in_conclusion",
+                "conflicts",
+            ],
+        )?;
         let evidence_pieces = parse_evidence_pieces(&json)?;
         let overall_assessment = parse_overall_assessment(&json)?;
         let confidence = parse_confidence(&json)?;
+        let conflicts = parse_evidence_conflicts(&json, evidence_pieces.len());
 
         let thought_id = generate_thought_id();
         let thought = Thought::new(
@@ -127,6 +193,73 @@ where
             tracing::warn!(error = %e, "Storage write failed — reasoning result preserved, thought not persisted");
         }
 
+        Ok(AssessResponse::new(
+            thought_id,
+            session.id,
+            evidence_pieces,
+            overall_assessment,
+            confidence,
+        )
+        .with_conflicts(conflicts))
+    }
+
+    /// Chunked evidence assessment: assess each chunk separately, then merge
+    /// the deduplicated evidence pieces and combine the per-chunk assessments.
+    async fn assess_chunked(
+        &self,
+        chunks: &[ContentChunk],
+        session_id: Option<String>,
+    ) -> Result<AssessResponse, ModeError> {
+        let has_prior_session = session_id.is_some();
+        let session = self.get_or_create_session(session_id).await?;
+        let prompt = evidence_assess_prompt();
+
+        let mut all_pieces = Vec::new();
+        let mut assessments = Vec::new();
+        let mut confidences = Vec::new();
+        for chunk in chunks {
+            let json = self
+                .assess_completion(prompt, &chunk.text, &session.id, has_prior_session)
+                .await?;
+            // "conflicts" is accepted but not merged: per-chunk indices are
+            // meaningless after cross-chunk dedup reorders the pieces.
+            reject_unknown_keys(
+                self.flags.strict_parsing,
+                &json,
+                &[
+                    "evidence_pieces",
+                    "overall_assessment",
+                    "confidence_in_conclusion",
+                    "conflicts",
+                ],
+            )?;
+            all_pieces.extend(parse_evidence_pieces(&json)?);
+            assessments.push(parse_overall_assessment(&json)?);
+            confidences.push(parse_confidence(&json)?);
+        }
+
+        let evidence_pieces = dedup_evidence_pieces(all_pieces);
+        let overall_assessment = merge_overall_assessments(&assessments);
+        let confidence = mean(&confidences);
+
+        let thought_id = generate_thought_id();
+        let thought = Thought::new(
+            &thought_id,
+            &session.id,
+            format!(
+                "Evidence assessment: {} pieces across {} chunks, confidence {:.2}",
+                evidence_pieces.len(),
+                chunks.len(),
+                confidence
+            ),
+            "evidence_assess",
+            confidence,
+        );
+
+        if let Err(e) = self.storage.save_thought(&thought).await {
+            tracing::warn!(error = %e, "Storage write failed — reasoning result preserved, thought not persisted");
+        }
+
         Ok(AssessResponse::new(
             thought_id,
             session.id,
@@ -136,6 +269,43 @@ where
         ))
     }
 
+    /// Run a single assess completion: build the user message, call the API,
+    /// extract the JSON payload, and apply any working-memory update.
+    async fn assess_completion(
+        &self,
+        prompt: &str,
+        content: &str,
+        session_id: &str,
+        has_prior_session: bool,
+    ) -> Result<serde_json::Value, ModeError> {
+        let user_message = self
+            .build_user_message(
+                prompt,
+                content,
+                session_id,
+                has_prior_session,
+                "Evidence to assess",
+            )
+            .await;
+
+        let messages = vec![Message::user(user_message)];
+        let config = CompletionConfig::new()
+            .with_mode("evidence")
+            .with_max_tokens(16384)
+            .with_temperature(0.3)
+            .with_deep_thinking()
+            // The assess prompt opens the message verbatim — cacheable.
+            .with_cached_prompt_len(prompt.len());
+
+        let response = self.client.complete(messages, config).await?;
+        let json = extract_json(&response.content)?;
+        apply_memory_update(&self.storage, session_id, &json).await;
+        // Persist open questions the assessment surfaced (best-effort, empty
+        // if absent) so reasoning_open_questions can aggregate them.
+        persist_open_questions(&self.storage, session_id, &parse_open_questions(&json)).await;
+        Ok(json)
+    }
+
     /// Perform Bayesian probability update.
     ///
     /// # Arguments
@@ -169,12 +339,19 @@ where
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("evidence")
             .with_max_tokens(16384)
             .with_temperature(0.3)
-            .with_deep_thinking();
+            .with_deep_thinking()
+            // The probabilistic prompt opens the message verbatim — cacheable.
+            .with_cached_prompt_len(prompt.len());
 
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
+        apply_memory_update(&self.storage, &session.id, &json).await;
+        // Persist open questions the update surfaced (best-effort, empty if
+        // absent) so reasoning_open_questions can aggregate them.
+        persist_open_questions(&self.storage, &session.id, &parse_open_questions(&json)).await;
 
         let hypothesis = json
             .get("hypothesis")
@@ -184,6 +361,18 @@ where
             })?
             .to_string();
 
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &[
+                "hypothesis",
+                "prior",
+                "evidence_analysis",
+                "posterior",
+                "belief_update",
+                "sensitivity",
+            ],
+        )?;
         let prior = parse_prior(&json)?;
         let evidence_analysis = parse_evidence_analysis(&json)?;
         let posterior = parse_posterior(&json)?;
@@ -222,6 +411,97 @@ where
             posterior,
             belief_update,
             sensitivity,
+            self.interval_width,
+        ))
+    }
+
+    /// Combine `assess` and `probabilistic` into one calibrated update.
+    ///
+    /// Runs `assess` first, restates the assessed credibility scores alongside
+    /// the content so the Bayesian pass grounds its likelihoods in them, then
+    /// scales the raw posterior shift by the mean assessed credibility: fully
+    /// credible evidence keeps the full shift, weak evidence keeps only part
+    /// of it, pulling the posterior back toward the prior.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The hypothesis and evidence to analyze
+    /// * `session_id` - Optional session ID (both passes share one session)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModeError`] if content is empty or either pass fails.
+    pub async fn synthesize(
+        &self,
+        content: &str,
+        session_id: Option<String>,
+    ) -> Result<SynthesizeResponse, ModeError> {
+        validate_content(content)?;
+
+        let assessment = self.assess(content, session_id).await?;
+        let session_id = assessment.session_id.clone();
+
+        let mut augmented = format!(
+            "{content}\n\nAssessed credibility of the evidence (0.0-1.0). \
+             Ground your likelihoods P(E|H) and P(E|not H) in these scores — \
+             low-credibility evidence should carry weaker likelihood ratios:\n"
+        );
+        for piece in &assessment.evidence_pieces {
+            let _ = writeln!(
+                augmented,
+                "- {} ({}): credibility {:.2}, quality {:.2}",
+                piece.summary,
+                piece.source_type.as_str(),
+                piece.credibility.overall,
+                piece.quality.overall,
+            );
+        }
+
+        let probabilistic = self
+            .probabilistic(&augmented, Some(session_id.clone()))
+            .await?;
+
+        let weight = credibility_weight(&assessment.evidence_pieces);
+        let calibrated = calibrate_posterior(
+            probabilistic.prior.probability,
+            probabilistic.posterior.probability,
+            weight,
+        );
+        let calibrated_posterior = Posterior {
+            probability: calibrated,
+            calculation: format!(
+                "prior {:.2} + (posterior {:.2} - prior {:.2}) x mean credibility {weight:.2}",
+                probabilistic.prior.probability,
+                probabilistic.posterior.probability,
+                probabilistic.prior.probability,
+            ),
+        };
+
+        let thought_id = generate_thought_id();
+        let thought = Thought::new(
+            &thought_id,
+            &session_id,
+            format!(
+                "Evidence synthesis: {} pieces, calibrated posterior {calibrated:.2} \
+                 (raw {:.2}, credibility weight {weight:.2})",
+                assessment.evidence_pieces.len(),
+                probabilistic.posterior.probability,
+            ),
+            "evidence_synthesize",
+            calibrated,
+        );
+
+        if let Err(e) = self.storage.save_thought(&thought).await {
+            tracing::warn!(error = %e, "Storage write failed — reasoning result preserved, thought not persisted");
+        }
+
+        Ok(SynthesizeResponse::new(
+            thought_id,
+            session_id,
+            assessment,
+            probabilistic,
+            weight,
+            calibrated_posterior,
         ))
     }
 
@@ -285,17 +565,27 @@ where
         has_prior_session: bool,
         content_label: &str,
     ) -> String {
-        let prior_context = if has_prior_session {
-            self.load_prior_context(session_id).await
+        let (prior_context, memory_block) = if has_prior_session {
+            (
+                self.load_prior_context(session_id).await,
+                load_working_memory_block(&self.storage, session_id).await,
+            )
         } else {
-            String::new()
+            (String::new(), String::new())
         };
 
-        if prior_context.is_empty() {
-            format!("{prompt}\n\n{content_label}:\n{content}")
-        } else {
-            format!("{prompt}\n\n{prior_context}\n{content_label}:\n{content}")
+        let mut message = format!(
+            "{prompt}{}\n",
+            language_instruction(self.language.as_deref(), content)
+        );
+        if !memory_block.is_empty() {
+            let _ = write!(message, "\n{memory_block}");
+        }
+        if !prior_context.is_empty() {
+            let _ = write!(message, "\n{prior_context}");
         }
+        let _ = write!(message, "\n{content_label}:\n{content}");
+        message
     }
 }
 
@@ -304,6 +594,88 @@ const MAX_CONTEXT_THOUGHTS: usize = 5;
 /// Maximum characters per prior thought when building the context block.
 const MAX_CONTEXT_THOUGHT_CHARS: usize = 600;
 
+/// Drop repeated evidence pieces across chunks, keyed on the summary
+/// (case-insensitive). First occurrence wins.
+fn dedup_evidence_pieces(pieces: Vec<EvidencePiece>) -> Vec<EvidencePiece> {
+    let mut seen = std::collections::HashSet::new();
+    pieces
+        .into_iter()
+        .filter(|p| seen.insert(p.summary.to_lowercase()))
+        .collect()
+}
+
+/// Merge per-chunk overall assessments: support is averaged, strengths,
+/// weaknesses, and gaps are concatenated without duplicates, and the pivot
+/// evidence comes from the first chunk that named one.
+fn merge_overall_assessments(
+    assessments: &[OverallEvidenceAssessment],
+) -> OverallEvidenceAssessment {
+    let mut key_strengths: Vec<String> = Vec::new();
+    let mut key_weaknesses: Vec<String> = Vec::new();
+    let mut gaps: Vec<EvidenceGap> = Vec::new();
+    for assessment in assessments {
+        for strength in &assessment.key_strengths {
+            if !key_strengths.contains(strength) {
+                key_strengths.push(strength.clone());
+            }
+        }
+        for weakness in &assessment.key_weaknesses {
+            if !key_weaknesses.contains(weakness) {
+                key_weaknesses.push(weakness.clone());
+            }
+        }
+        for gap in &assessment.gaps {
+            if !gaps.iter().any(|g| g.gap == gap.gap) {
+                gaps.push(gap.clone());
+            }
+        }
+    }
+    let pivot_evidence = assessments
+        .iter()
+        .find(|a| !a.pivot_evidence.is_empty())
+        .map(|a| a.pivot_evidence.clone())
+        .unwrap_or_default();
+    let supports: Vec<f64> = assessments.iter().map(|a| a.evidential_support).collect();
+
+    OverallEvidenceAssessment {
+        evidential_support: mean(&supports),
+        key_strengths,
+        key_weaknesses,
+        gaps,
+        pivot_evidence,
+    }
+}
+
+/// Mean overall credibility across assessed pieces; 0.5 (neutral) when the
+/// assessment identified none, so a pieceless synthesis still moves halfway.
+fn credibility_weight(pieces: &[EvidencePiece]) -> f64 {
+    if pieces.is_empty() {
+        0.5
+    } else {
+        let overalls: Vec<f64> = pieces.iter().map(|p| p.credibility.overall).collect();
+        mean(&overalls)
+    }
+}
+
+/// Scale the raw posterior shift by the credibility weight, clamped to a
+/// valid probability. Higher credibility keeps more of the Bayesian shift.
+fn calibrate_posterior(prior: f64, posterior: f64, weight: f64) -> f64 {
+    weight
+        .clamp(0.0, 1.0)
+        .mul_add(posterior - prior, prior)
+        .clamp(0.0, 1.0)
+}
+
+/// Arithmetic mean, 0.0 for an empty slice.
+#[allow(clippy::cast_precision_loss)]
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
 /// Truncate a string to at most `max` characters (char-safe), appending an
 /// ellipsis when truncated.
 fn truncate_chars(s: &str, max: usize) -> String {
@@ -420,6 +792,9 @@ mod tests {
         mock_storage.expect_save_thought().returning(|_| Ok(()));
 
         mock_storage.expect_get_thoughts().returning(|_| Ok(vec![]));
+        mock_storage
+            .expect_get_working_memory()
+            .returning(|_| Ok(None));
 
         let response_json = mock_assess_response();
         mock_client.expect_complete().returning(move |_, _| {
@@ -442,6 +817,112 @@ mod tests {
         assert!((response.confidence_in_conclusion - 0.75).abs() < f64::EPSILON);
     }
 
+    /// Two contradictory pieces plus a `conflicts` array mixing one valid
+    /// pair with entries citing a missing piece and a self-reference.
+    fn mock_conflicting_assess_response() -> String {
+        r#"{
+            "evidence_pieces": [
+                {
+                    "summary": "Trial reports the treatment works",
+                    "source_type": "primary",
+                    "credibility": {
+                        "expertise": 0.9, "objectivity": 0.8, "corroboration": 0.7,
+                        "recency": 0.9, "overall": 0.83
+                    },
+                    "quality": {
+                        "relevance": 0.9, "strength": 0.8,
+                        "representativeness": 0.7, "overall": 0.8
+                    }
+                },
+                {
+                    "summary": "Meta-analysis finds no effect",
+                    "source_type": "secondary",
+                    "credibility": {
+                        "expertise": 0.8, "objectivity": 0.9, "corroboration": 0.8,
+                        "recency": 0.7, "overall": 0.8
+                    },
+                    "quality": {
+                        "relevance": 0.9, "strength": 0.7,
+                        "representativeness": 0.8, "overall": 0.8
+                    }
+                }
+            ],
+            "overall_assessment": {
+                "evidential_support": 0.5,
+                "key_strengths": ["Both sources are credible"],
+                "key_weaknesses": ["Direct contradiction"],
+                "gaps": ["Need a third independent study"]
+            },
+            "confidence_in_conclusion": 0.4,
+            "conflicts": [
+                {"piece_a": 0, "piece_b": 1, "nature": "Trial claims efficacy; meta-analysis finds none"},
+                {"piece_a": 0, "piece_b": 5, "nature": "cites a piece that does not exist"},
+                {"piece_a": 1, "piece_b": 1, "nature": "a piece cannot conflict with itself"}
+            ]
+        }"#
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_assess_reports_conflicts_with_valid_indices() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("test-session")));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+
+        let response_json = mock_conflicting_assess_response();
+        mock_client.expect_complete().returning(move |_, _| {
+            Ok(CompletionResponse::new(
+                response_json.clone(),
+                Usage::new(100, 200),
+            ))
+        });
+
+        let mode = EvidenceMode::new(mock_storage, mock_client);
+        let response = mode
+            .assess("Trial says yes, meta-analysis says no", None)
+            .await
+            .expect("assess");
+
+        // Only the entry citing two distinct existing pieces survives.
+        assert_eq!(response.conflicts.len(), 1);
+        let conflict = &response.conflicts[0];
+        assert_eq!(conflict.piece_a, 0);
+        assert_eq!(conflict.piece_b, 1);
+        assert!(conflict.piece_a < response.evidence_pieces.len());
+        assert!(conflict.piece_b < response.evidence_pieces.len());
+        assert!(conflict.nature.contains("meta-analysis"));
+    }
+
+    #[tokio::test]
+    async fn test_assess_single_piece_has_no_conflicts() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("test-session")));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+
+        // One piece, no conflicts field at all.
+        let response_json = mock_assess_response();
+        mock_client.expect_complete().returning(move |_, _| {
+            Ok(CompletionResponse::new(
+                response_json.clone(),
+                Usage::new(100, 200),
+            ))
+        });
+
+        let mode = EvidenceMode::new(mock_storage, mock_client);
+        let response = mode.assess("A single source", None).await.expect("assess");
+
+        assert_eq!(response.evidence_pieces.len(), 1);
+        assert!(response.conflicts.is_empty());
+    }
+
     #[tokio::test]
     async fn test_assess_empty_content() {
         let mock_storage = MockStorageTrait::new();
@@ -565,6 +1046,9 @@ mod tests {
         mock_storage.expect_save_thought().returning(|_| Ok(()));
 
         mock_storage.expect_get_thoughts().returning(|_| Ok(vec![]));
+        mock_storage
+            .expect_get_working_memory()
+            .returning(|_| Ok(None));
 
         let response_json = mock_probabilistic_response();
         mock_client.expect_complete().returning(move |_, _| {
@@ -661,6 +1145,129 @@ mod tests {
         );
     }
 
+    // ========================================================================
+    // Synthesize Tests
+    // ========================================================================
+
+    #[tokio::test]
+    async fn test_synthesize_runs_both_operations_and_calibrates() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("synth-session")));
+        // One thought per pass plus the synthesis thought itself.
+        mock_storage
+            .expect_save_thought()
+            .times(3)
+            .returning(|_| Ok(()));
+        mock_storage.expect_get_thoughts().returning(|_| Ok(vec![]));
+        mock_storage
+            .expect_get_working_memory()
+            .returning(|_| Ok(None));
+
+        // First completion is the assess pass; the second is the Bayesian
+        // pass and must see the assessed credibility restated in its input.
+        let calls = AtomicUsize::new(0);
+        let assess_json = mock_assess_response();
+        let probabilistic_json = mock_probabilistic_response();
+        mock_client
+            .expect_complete()
+            .times(2)
+            .returning(move |messages, _| {
+                let body = if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                    assess_json.clone()
+                } else {
+                    let content = &messages.first().expect("user message").content;
+                    assert!(content.contains("Assessed credibility of the evidence"));
+                    assert!(content.contains("Research paper on topic (primary)"));
+                    assert!(content.contains("credibility 0.83"));
+                    probabilistic_json.clone()
+                };
+                Ok(CompletionResponse::new(body, Usage::new(100, 200)))
+            });
+
+        let mode = EvidenceMode::new(mock_storage, mock_client);
+        let response = mode
+            .synthesize("Hypothesis and evidence", None)
+            .await
+            .unwrap();
+
+        assert_eq!(response.session_id, "synth-session");
+        assert_eq!(response.assessment.evidence_pieces.len(), 1);
+        assert_eq!(
+            response.probabilistic.hypothesis,
+            "The treatment is effective"
+        );
+        // One piece at credibility 0.83 → weight 0.83; the raw 0.30 → 0.79
+        // shift is scaled to 0.30 + 0.49 x 0.83.
+        assert!((response.credibility_weight - 0.83).abs() < f64::EPSILON);
+        let expected = 0.83f64.mul_add(0.79 - 0.3, 0.3);
+        assert!((response.calibrated_posterior.probability - expected).abs() < f64::EPSILON);
+        assert!(response
+            .calibrated_posterior
+            .calculation
+            .contains("mean credibility 0.83"));
+    }
+
+    #[tokio::test]
+    async fn test_synthesize_empty_content() {
+        let mock_storage = MockStorageTrait::new();
+        let mock_client = MockAnthropicClientTrait::new();
+
+        let mode = EvidenceMode::new(mock_storage, mock_client);
+        let result = mode.synthesize("", None).await;
+
+        assert!(matches!(result, Err(ModeError::MissingField { field }) if field == "content"));
+    }
+
+    #[tokio::test]
+    async fn test_synthesize_assess_failure_skips_probabilistic() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("synth-session")));
+        // Only the assess pass runs; its failure stops the pipeline.
+        mock_client.expect_complete().times(1).returning(|_, _| {
+            Err(ModeError::ApiUnavailable {
+                message: "API error".to_string(),
+            })
+        });
+
+        let mode = EvidenceMode::new(mock_storage, mock_client);
+        let result = mode.synthesize("Test", None).await;
+
+        assert!(matches!(result, Err(ModeError::ApiUnavailable { .. })));
+    }
+
+    #[test]
+    fn test_credibility_weight_defaults_to_neutral() {
+        assert!((credibility_weight(&[]) - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_calibrate_posterior_scales_shift_by_credibility() {
+        // Higher assessed credibility keeps more of the raw posterior shift.
+        let weak = calibrate_posterior(0.3, 0.8, 0.4);
+        let strong = calibrate_posterior(0.3, 0.8, 0.9);
+        assert!(strong > weak);
+        assert!((weak - 0.5).abs() < f64::EPSILON);
+        assert!((strong - 0.75).abs() < f64::EPSILON);
+
+        // Full credibility keeps the full shift; zero keeps the prior.
+        assert!((calibrate_posterior(0.3, 0.8, 1.0) - 0.8).abs() < f64::EPSILON);
+        assert!((calibrate_posterior(0.3, 0.8, 0.0) - 0.3).abs() < f64::EPSILON);
+
+        // Downward shifts scale the same way, and results stay in [0, 1].
+        assert!((calibrate_posterior(0.8, 0.2, 0.5) - 0.5).abs() < f64::EPSILON);
+        assert!((0.0..=1.0).contains(&calibrate_posterior(0.0, 1.0, 1.0)));
+    }
+
     // ========================================================================
     // Response Type Tests
     // ========================================================================
@@ -713,6 +1320,7 @@ mod tests {
                 interpretation: "Interp".to_string(),
             },
             "Sensitivity",
+            0.1,
         );
         assert_eq!(response.hypothesis, "Hypothesis");
     }
@@ -736,6 +1344,9 @@ mod tests {
                 0.7,
             )])
         });
+        mock_storage
+            .expect_get_working_memory()
+            .returning(|_| Ok(None));
         mock_storage.expect_save_thought().returning(|_| Ok(()));
 
         let response_json = mock_assess_response();
@@ -818,4 +1429,121 @@ mod tests {
         let assessment = parse_overall_assessment(&json).unwrap();
         assert_eq!(assessment.pivot_evidence, "The eyewitness account");
     }
+
+    // ========================================================================
+    // Chunked Path Tests
+    // ========================================================================
+
+    #[tokio::test]
+    async fn test_assess_chunked_merges_pieces_without_duplicates() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // Two paragraphs that each fit a chunk but together exceed the limit.
+        let content = format!(
+            "{}\n\n{}",
+            "alpha finding ".repeat(2400),
+            "beta finding ".repeat(2400)
+        );
+
+        let mut mock_storage = MockStorageTrait::new();
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("chunk-session")));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+
+        let mut mock_client = MockAnthropicClientTrait::new();
+        // One response per chunk; "Shared survey" appears in both.
+        let calls = AtomicUsize::new(0);
+        mock_client.expect_complete().times(2).returning(move |_, _| {
+            let piece = |summary: &str| {
+                format!(
+                    r#"{{
+                        "summary": "{summary}",
+                        "source_type": "primary",
+                        "credibility": {{"expertise": 0.9, "objectivity": 0.8, "corroboration": 0.7, "recency": 0.9, "overall": 0.83}},
+                        "quality": {{"relevance": 0.9, "strength": 0.8, "representativeness": 0.7, "overall": 0.8}}
+                    }}"#
+                )
+            };
+            let body = if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                format!(
+                    r#"{{
+                        "evidence_pieces": [{}, {}],
+                        "overall_assessment": {{
+                            "evidential_support": 0.6,
+                            "key_strengths": ["S1"],
+                            "key_weaknesses": ["W1"],
+                            "gaps": ["Need replication"]
+                        }},
+                        "confidence_in_conclusion": 0.6
+                    }}"#,
+                    piece("Alpha study"),
+                    piece("Shared survey")
+                )
+            } else {
+                format!(
+                    r#"{{
+                        "evidence_pieces": [{}, {}],
+                        "overall_assessment": {{
+                            "evidential_support": 0.8,
+                            "key_strengths": ["S1", "S2"],
+                            "key_weaknesses": ["W2"],
+                            "gaps": ["Need replication"],
+                            "pivot_evidence": "Beta report"
+                        }},
+                        "confidence_in_conclusion": 0.8
+                    }}"#,
+                    piece("Beta report"),
+                    piece("Shared survey")
+                )
+            };
+            Ok(CompletionResponse::new(body, Usage::new(100, 200)))
+        });
+
+        let mode = EvidenceMode::new(mock_storage, mock_client).with_chunking(true);
+        let response = mode.assess(&content, None).await.unwrap();
+
+        // Pieces from both chunks, with the duplicate collapsed.
+        let summaries: Vec<&str> = response
+            .evidence_pieces
+            .iter()
+            .map(|p| p.summary.as_str())
+            .collect();
+        assert_eq!(summaries, ["Alpha study", "Shared survey", "Beta report"]);
+
+        let assessment = &response.overall_assessment;
+        assert!((assessment.evidential_support - 0.7).abs() < f64::EPSILON);
+        assert_eq!(assessment.key_strengths, ["S1", "S2"]);
+        assert_eq!(assessment.key_weaknesses, ["W1", "W2"]);
+        assert_eq!(assessment.gaps.len(), 1);
+        assert_eq!(assessment.pivot_evidence, "Beta report");
+        assert!((response.confidence_in_conclusion - 0.7).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_assess_small_content_with_chunking_takes_single_path() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|_| Ok(Session::new("test-session")));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+
+        let response_json = mock_assess_response();
+        // Content fits one chunk → exactly one completion, ordinary path.
+        mock_client
+            .expect_complete()
+            .times(1)
+            .returning(move |_, _| {
+                Ok(CompletionResponse::new(
+                    response_json.clone(),
+                    Usage::new(100, 200),
+                ))
+            });
+
+        let mode = EvidenceMode::new(mock_storage, mock_client).with_chunking(true);
+        let response = mode.assess("A short claim to assess", None).await.unwrap();
+        assert_eq!(response.evidence_pieces.len(), 1);
+    }
 }
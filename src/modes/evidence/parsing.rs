@@ -5,11 +5,14 @@
 //! assessments, Bayesian updates). Returns ModeError::MissingField for absent fields.
 
 use crate::error::ModeError;
+use crate::modes::parse_probability;
 
 use super::types::{
-    BeliefDirection, BeliefMagnitude, BeliefUpdate, Credibility, EvidenceAnalysis, EvidencePiece,
-    EvidenceQuality, OverallEvidenceAssessment, Posterior, Prior, SourceType,
+    BeliefDirection, BeliefMagnitude, BeliefUpdate, Credibility, EvidenceAnalysis,
+    EvidenceConflict, EvidenceGap, EvidencePiece, EvidenceQuality, OverallEvidenceAssessment,
+    Posterior, Prior, SourceType,
 };
+use crate::modes::reflection::Priority;
 
 // ============================================================================
 // Assess Parsing
@@ -60,11 +63,98 @@ pub fn parse_evidence_pieces(json: &serde_json::Value) -> Result<Vec<EvidencePie
             let credibility = parse_credibility(p)?;
             let quality = parse_quality(p)?;
 
+            let source_url = p
+                .get("source_url")
+                .and_then(serde_json::Value::as_str)
+                .filter(|u| !u.trim().is_empty())
+                .map(str::to_string);
+            let source_title = p
+                .get("source_title")
+                .and_then(serde_json::Value::as_str)
+                .filter(|t| !t.trim().is_empty())
+                .map(str::to_string);
+            let date = p
+                .get("date")
+                .and_then(serde_json::Value::as_str)
+                .filter(|d| !d.trim().is_empty())
+                .map(str::to_string);
+
+            // Provenance is best-effort: a malformed URL is flagged (and
+            // logged), never a parse failure.
+            let source_url_malformed = source_url
+                .as_deref()
+                .is_some_and(|url| !is_well_formed_url(url));
+            if source_url_malformed {
+                tracing::warn!(
+                    source_url = source_url.as_deref().unwrap_or_default(),
+                    "Evidence source URL is not a well-formed http(s) URL"
+                );
+            }
+
             Ok(EvidencePiece {
                 summary,
                 source_type,
                 credibility,
                 quality,
+                source_url,
+                source_title,
+                date,
+                source_url_malformed,
+            })
+        })
+        .collect()
+}
+
+/// Parses the optional `conflicts` array into validated [`EvidenceConflict`]
+/// pairs.
+///
+/// Conflict detection is best-effort, so this never fails: an absent or
+/// malformed array yields no conflicts, and each entry is validated against
+/// the parsed evidence list — indices out of range, self-references, and
+/// non-numeric indices are dropped (with a warning) rather than surfaced.
+/// With fewer than two pieces nothing can conflict.
+#[must_use]
+pub fn parse_evidence_conflicts(
+    json: &serde_json::Value,
+    piece_count: usize,
+) -> Vec<EvidenceConflict> {
+    let Some(entries) = json.get("conflicts").and_then(serde_json::Value::as_array) else {
+        return Vec::new();
+    };
+
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let piece_a = entry
+                .get("piece_a")
+                .and_then(serde_json::Value::as_u64)
+                .map(|i| i as usize);
+            let piece_b = entry
+                .get("piece_b")
+                .and_then(serde_json::Value::as_u64)
+                .map(|i| i as usize);
+            let (Some(piece_a), Some(piece_b)) = (piece_a, piece_b) else {
+                tracing::warn!("Dropping evidence conflict without numeric piece indices");
+                return None;
+            };
+            if piece_a >= piece_count || piece_b >= piece_count || piece_a == piece_b {
+                tracing::warn!(
+                    piece_a,
+                    piece_b,
+                    piece_count,
+                    "Dropping evidence conflict citing invalid piece indices"
+                );
+                return None;
+            }
+            let nature = entry
+                .get("nature")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            Some(EvidenceConflict {
+                piece_a,
+                piece_b,
+                nature,
             })
         })
         .collect()
@@ -78,11 +168,11 @@ pub fn parse_credibility(piece: &serde_json::Value) -> Result<Credibility, ModeE
             field: "credibility".to_string(),
         })?;
 
-    let expertise = get_f64(cred, "expertise")?;
-    let objectivity = get_f64(cred, "objectivity")?;
-    let corroboration = get_f64(cred, "corroboration")?;
-    let recency = get_f64(cred, "recency")?;
-    let overall = get_f64(cred, "overall")?;
+    let expertise = parse_probability(cred, "expertise")?;
+    let objectivity = parse_probability(cred, "objectivity")?;
+    let corroboration = parse_probability(cred, "corroboration")?;
+    let recency = parse_probability(cred, "recency")?;
+    let overall = parse_probability(cred, "overall")?;
 
     Ok(Credibility {
         expertise,
@@ -101,10 +191,10 @@ pub fn parse_quality(piece: &serde_json::Value) -> Result<EvidenceQuality, ModeE
             field: "quality".to_string(),
         })?;
 
-    let relevance = get_f64(qual, "relevance")?;
-    let strength = get_f64(qual, "strength")?;
-    let representativeness = get_f64(qual, "representativeness")?;
-    let overall = get_f64(qual, "overall")?;
+    let relevance = parse_probability(qual, "relevance")?;
+    let strength = parse_probability(qual, "strength")?;
+    let representativeness = parse_probability(qual, "representativeness")?;
+    let overall = parse_probability(qual, "overall")?;
 
     Ok(EvidenceQuality {
         relevance,
@@ -124,11 +214,11 @@ pub fn parse_overall_assessment(
             field: "overall_assessment".to_string(),
         })?;
 
-    let evidential_support = get_f64(assessment, "evidential_support")?;
+    let evidential_support = parse_probability(assessment, "evidential_support")?;
 
     let key_strengths = get_string_array(assessment, "key_strengths")?;
     let key_weaknesses = get_string_array(assessment, "key_weaknesses")?;
-    let gaps = get_string_array(assessment, "gaps")?;
+    let gaps = parse_gaps(assessment)?;
 
     // Optional: the most fragile link in the evidential chain. Absent on
     // older/edge responses, so default to empty rather than failing the parse.
@@ -147,23 +237,51 @@ pub fn parse_overall_assessment(
     })
 }
 
-/// Parses and validates the `confidence_in_conclusion` field (must be 0.0–1.0) from LLM JSON.
-pub fn parse_confidence(json: &serde_json::Value) -> Result<f64, ModeError> {
-    let confidence = json
-        .get("confidence_in_conclusion")
-        .and_then(serde_json::Value::as_f64)
+/// Parses the `gaps` array into structured [`EvidenceGap`] entries.
+///
+/// Accepts both the structured form (`{"gap", "suggested_action", "priority"}`)
+/// and the legacy bare-string form, which maps to a gap with no suggested
+/// action and medium priority. Entries without a recognizable gap text are
+/// skipped, mirroring [`get_string_array`]'s tolerance; a missing `gaps`
+/// array is still `ModeError::MissingField`.
+pub fn parse_gaps(assessment: &serde_json::Value) -> Result<Vec<EvidenceGap>, ModeError> {
+    Ok(assessment
+        .get("gaps")
+        .and_then(serde_json::Value::as_array)
         .ok_or_else(|| ModeError::MissingField {
-            field: "confidence_in_conclusion".to_string(),
-        })?;
-
-    if !(0.0..=1.0).contains(&confidence) {
-        return Err(ModeError::InvalidValue {
-            field: "confidence_in_conclusion".to_string(),
-            reason: format!("must be between 0.0 and 1.0, got {confidence}"),
-        });
-    }
+            field: "gaps".to_string(),
+        })?
+        .iter()
+        .filter_map(|entry| match entry {
+            serde_json::Value::String(s) => Some(EvidenceGap {
+                gap: s.clone(),
+                suggested_action: String::new(),
+                priority: Priority::default(),
+            }),
+            serde_json::Value::Object(obj) => {
+                let gap = obj.get("gap").and_then(serde_json::Value::as_str)?;
+                Some(EvidenceGap {
+                    gap: gap.to_string(),
+                    suggested_action: obj
+                        .get("suggested_action")
+                        .and_then(serde_json::Value::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    priority: obj
+                        .get("priority")
+                        .and_then(serde_json::Value::as_str)
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or_default(),
+                })
+            }
+            _ => None,
+        })
+        .collect())
+}
 
-    Ok(confidence)
+/// Parses and validates the `confidence_in_conclusion` field (must be 0.0–1.0) from LLM JSON.
+pub fn parse_confidence(json: &serde_json::Value) -> Result<f64, ModeError> {
+    parse_probability(json, "confidence_in_conclusion")
 }
 
 // ============================================================================
@@ -176,7 +294,7 @@ pub fn parse_prior(json: &serde_json::Value) -> Result<Prior, ModeError> {
         field: "prior".to_string(),
     })?;
 
-    let probability = get_f64(prior, "probability")?;
+    let probability = parse_probability(prior, "probability")?;
     let basis = prior
         .get("basis")
         .and_then(serde_json::Value::as_str)
@@ -210,20 +328,64 @@ pub fn parse_evidence_analysis(
                 })?
                 .to_string();
 
-            let likelihood_if_true = get_f64(a, "likelihood_if_true")?;
-            let likelihood_if_false = get_f64(a, "likelihood_if_false")?;
+            let likelihood_if_true = parse_probability(a, "likelihood_if_true")?;
+            let likelihood_if_false = parse_probability(a, "likelihood_if_false")?;
             let bayes_factor = get_f64(a, "bayes_factor")?;
+            let likelihood_if_true_range = parse_likelihood_range(a, "likelihood_if_true_range")?;
+            let likelihood_if_false_range = parse_likelihood_range(a, "likelihood_if_false_range")?;
 
             Ok(EvidenceAnalysis {
                 evidence,
                 likelihood_if_true,
                 likelihood_if_false,
                 bayes_factor,
+                likelihood_if_true_range,
+                likelihood_if_false_range,
             })
         })
         .collect()
 }
 
+/// Parses an optional `[low, high]` likelihood range. Absent or `null` is
+/// `None`; anything present must be a two-element array of probabilities.
+fn parse_likelihood_range(
+    value: &serde_json::Value,
+    field: &str,
+) -> Result<Option<[f64; 2]>, ModeError> {
+    let Some(raw) = value.get(field) else {
+        return Ok(None);
+    };
+    if raw.is_null() {
+        return Ok(None);
+    }
+
+    let invalid = |reason: String| ModeError::InvalidValue {
+        field: field.to_string(),
+        reason,
+    };
+    let arr = raw
+        .as_array()
+        .ok_or_else(|| invalid(format!("must be a [low, high] array, got {raw}")))?;
+    if arr.len() != 2 {
+        return Err(invalid(format!(
+            "must have exactly 2 elements, got {}",
+            arr.len()
+        )));
+    }
+
+    let mut bounds = [0.0_f64; 2];
+    for (slot, element) in bounds.iter_mut().zip(arr) {
+        let n = element
+            .as_f64()
+            .ok_or_else(|| invalid(format!("must contain numbers, got {element}")))?;
+        if !(0.0..=1.0).contains(&n) {
+            return Err(invalid(format!("must be between 0.0 and 1.0, got {n}")));
+        }
+        *slot = n;
+    }
+    Ok(Some(bounds))
+}
+
 /// Parses the `posterior` object from LLM JSON into a `Posterior` with probability and calculation.
 pub fn parse_posterior(json: &serde_json::Value) -> Result<Posterior, ModeError> {
     let post = json
@@ -232,7 +394,7 @@ pub fn parse_posterior(json: &serde_json::Value) -> Result<Posterior, ModeError>
             field: "posterior".to_string(),
         })?;
 
-    let probability = get_f64(post, "probability")?;
+    let probability = parse_probability(post, "probability")?;
     let calculation = post
         .get("calculation")
         .and_then(serde_json::Value::as_str)
@@ -312,6 +474,18 @@ pub fn parse_belief_update(json: &serde_json::Value) -> Result<BeliefUpdate, Mod
 // Utility Helpers
 // ============================================================================
 
+/// Whether a source URL looks well-formed: an http(s) scheme, a non-empty
+/// host, and no embedded whitespace. Deliberately lightweight — no URL crate
+/// dependency for a warn-only check.
+fn is_well_formed_url(url: &str) -> bool {
+    url.strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .is_some_and(|rest| {
+            let host = rest.split('/').next().unwrap_or_default();
+            !host.is_empty() && !url.chars().any(char::is_whitespace)
+        })
+}
+
 /// Extracts a named `f64` field from a JSON object, returning `ModeError::MissingField` if absent.
 pub fn get_f64(json: &serde_json::Value, field: &str) -> Result<f64, ModeError> {
     json.get(field)
@@ -536,6 +710,87 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_evidence_pieces_without_source_url() {
+        let json = json!({
+            "evidence_pieces": [{
+                "summary": "No provenance",
+                "source_type": "primary",
+                "credibility": {
+                    "expertise": 0.9, "objectivity": 0.8, "corroboration": 0.7, "recency": 0.9, "overall": 0.85
+                },
+                "quality": {
+                    "relevance": 0.9, "strength": 0.85, "representativeness": 0.8, "overall": 0.85
+                }
+            }]
+        });
+
+        let result = parse_evidence_pieces(&json).unwrap();
+        assert_eq!(result[0].source_url, None);
+        assert_eq!(result[0].source_title, None);
+        assert!(!result[0].source_url_malformed);
+    }
+
+    #[test]
+    fn test_parse_evidence_pieces_with_source_url() {
+        let json = json!({
+            "evidence_pieces": [{
+                "summary": "Cited study",
+                "source_type": "secondary",
+                "source_url": "https://example.com/study",
+                "source_title": "Example Study 2025",
+                "credibility": {
+                    "expertise": 0.9, "objectivity": 0.8, "corroboration": 0.7, "recency": 0.9, "overall": 0.85
+                },
+                "quality": {
+                    "relevance": 0.9, "strength": 0.85, "representativeness": 0.8, "overall": 0.85
+                }
+            }]
+        });
+
+        let result = parse_evidence_pieces(&json).unwrap();
+        assert_eq!(
+            result[0].source_url.as_deref(),
+            Some("https://example.com/study")
+        );
+        assert_eq!(
+            result[0].source_title.as_deref(),
+            Some("Example Study 2025")
+        );
+        assert!(!result[0].source_url_malformed);
+    }
+
+    #[test]
+    fn test_parse_evidence_pieces_malformed_url_flagged_not_fatal() {
+        let json = json!({
+            "evidence_pieces": [{
+                "summary": "Bad citation",
+                "source_type": "anecdotal",
+                "source_url": "not a url",
+                "credibility": {
+                    "expertise": 0.5, "objectivity": 0.4, "corroboration": 0.3, "recency": 0.9, "overall": 0.52
+                },
+                "quality": {
+                    "relevance": 0.6, "strength": 0.4, "representativeness": 0.3, "overall": 0.43
+                }
+            }]
+        });
+
+        let result = parse_evidence_pieces(&json).unwrap();
+        assert_eq!(result[0].source_url.as_deref(), Some("not a url"));
+        assert!(result[0].source_url_malformed);
+    }
+
+    #[test]
+    fn test_is_well_formed_url() {
+        assert!(is_well_formed_url("https://example.com"));
+        assert!(is_well_formed_url("http://example.com/path?q=1"));
+        assert!(!is_well_formed_url("ftp://example.com"));
+        assert!(!is_well_formed_url("https://"));
+        assert!(!is_well_formed_url("https://example.com/with space"));
+        assert!(!is_well_formed_url("example.com"));
+    }
+
     // ========================================================================
     // parse_credibility tests
     // ========================================================================
@@ -737,6 +992,59 @@ mod tests {
         assert_eq!(result.key_strengths.len(), 2);
         assert_eq!(result.key_weaknesses.len(), 1);
         assert_eq!(result.gaps.len(), 1);
+        // Legacy bare-string gaps keep the text and get neutral defaults.
+        assert_eq!(result.gaps[0].gap, "Missing longitudinal data");
+        assert_eq!(result.gaps[0].suggested_action, "");
+        assert_eq!(result.gaps[0].priority, Priority::Medium);
+    }
+
+    #[test]
+    fn test_parse_gaps_structured() {
+        let assessment = json!({
+            "gaps": [
+                {
+                    "gap": "No control group",
+                    "suggested_action": "Find a cohort study with matched controls",
+                    "priority": "high"
+                },
+                {"gap": "Dated sources"}
+            ]
+        });
+
+        let gaps = parse_gaps(&assessment).unwrap();
+        assert_eq!(gaps.len(), 2);
+        assert_eq!(gaps[0].gap, "No control group");
+        assert_eq!(
+            gaps[0].suggested_action,
+            "Find a cohort study with matched controls"
+        );
+        assert_eq!(gaps[0].priority, Priority::High);
+        assert_eq!(gaps[1].suggested_action, "");
+        assert_eq!(gaps[1].priority, Priority::Medium);
+    }
+
+    #[test]
+    fn test_parse_gaps_skips_malformed_entries() {
+        let assessment = json!({
+            "gaps": [
+                {"suggested_action": "no gap text"},
+                42,
+                {"gap": "Valid gap", "priority": "nonsense"}
+            ]
+        });
+
+        let gaps = parse_gaps(&assessment).unwrap();
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].gap, "Valid gap");
+        // Unrecognized priority strings fall back to the default.
+        assert_eq!(gaps[0].priority, Priority::Medium);
+    }
+
+    #[test]
+    fn test_parse_gaps_missing_array() {
+        let assessment = json!({});
+        let result = parse_gaps(&assessment);
+        assert!(matches!(result, Err(ModeError::MissingField { field }) if field == "gaps"));
     }
 
     #[test]
@@ -981,6 +1289,72 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_evidence_analysis_with_likelihood_ranges() {
+        let json = json!({
+            "evidence_analysis": [{
+                "evidence": "test",
+                "likelihood_if_true": 0.95,
+                "likelihood_if_false": 0.05,
+                "bayes_factor": 19.0,
+                "likelihood_if_true_range": [0.9, 0.99],
+                "likelihood_if_false_range": [0.01, 0.1]
+            }]
+        });
+        let result = parse_evidence_analysis(&json).unwrap();
+        assert_eq!(result[0].likelihood_if_true_range, Some([0.9, 0.99]));
+        assert_eq!(result[0].likelihood_if_false_range, Some([0.01, 0.1]));
+    }
+
+    #[test]
+    fn test_parse_evidence_analysis_null_range_is_absent() {
+        let json = json!({
+            "evidence_analysis": [{
+                "evidence": "test",
+                "likelihood_if_true": 0.95,
+                "likelihood_if_false": 0.05,
+                "bayes_factor": 19.0,
+                "likelihood_if_true_range": null
+            }]
+        });
+        let result = parse_evidence_analysis(&json).unwrap();
+        assert_eq!(result[0].likelihood_if_true_range, None);
+    }
+
+    #[test]
+    fn test_parse_evidence_analysis_rejects_malformed_range() {
+        let json = json!({
+            "evidence_analysis": [{
+                "evidence": "test",
+                "likelihood_if_true": 0.95,
+                "likelihood_if_false": 0.05,
+                "bayes_factor": 19.0,
+                "likelihood_if_true_range": [0.9]
+            }]
+        });
+        let result = parse_evidence_analysis(&json);
+        assert!(
+            matches!(result, Err(ModeError::InvalidValue { field, .. }) if field == "likelihood_if_true_range")
+        );
+    }
+
+    #[test]
+    fn test_parse_evidence_analysis_rejects_out_of_range_bound() {
+        let json = json!({
+            "evidence_analysis": [{
+                "evidence": "test",
+                "likelihood_if_true": 0.95,
+                "likelihood_if_false": 0.05,
+                "bayes_factor": 19.0,
+                "likelihood_if_false_range": [0.05, 1.5]
+            }]
+        });
+        let result = parse_evidence_analysis(&json);
+        assert!(
+            matches!(result, Err(ModeError::InvalidValue { field, .. }) if field == "likelihood_if_false_range")
+        );
+    }
+
     // ========================================================================
     // parse_posterior tests
     // ========================================================================
@@ -6,6 +6,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::modes::reflection::Priority;
+
 // ============================================================================
 // Response Types - Assess
 // ============================================================================
@@ -76,6 +78,36 @@ pub struct EvidencePiece {
     pub credibility: Credibility,
     /// Quality assessment.
     pub quality: EvidenceQuality,
+    /// URL of the source, when the model provided one. Kept as given so
+    /// reports can cite it; see `source_url_malformed` for well-formedness.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_url: Option<String>,
+    /// Title of the source, when the model provided one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_title: Option<String>,
+    /// When the evidence is from, when the model provided a date. Kept as
+    /// given (ISO date, year-month, or year); used to place evidence on a
+    /// timeline via [`evidence_timeline`](crate::modes::evidence_timeline()).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    /// True when `source_url` is present but not a well-formed http(s) URL.
+    /// A bad URL never fails the parse — it is flagged so a report can
+    /// caveat the citation instead of dropping the evidence.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub source_url_malformed: bool,
+}
+
+/// A missing piece of evidence paired with a concrete next step.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EvidenceGap {
+    /// What evidence is missing.
+    pub gap: String,
+    /// Concrete action to fill the gap (empty when the model gave none).
+    #[serde(default)]
+    pub suggested_action: String,
+    /// How urgently the gap should be filled.
+    #[serde(default)]
+    pub priority: Priority,
 }
 
 /// Overall assessment of evidence.
@@ -87,14 +119,51 @@ pub struct OverallEvidenceAssessment {
     pub key_strengths: Vec<String>,
     /// Key weaknesses.
     pub key_weaknesses: Vec<String>,
-    /// What evidence is missing.
-    pub gaps: Vec<String>,
+    /// What evidence is missing, each gap paired with a suggested action.
+    pub gaps: Vec<EvidenceGap>,
     /// The single existing piece of evidence that, if false, would most change
     /// the conclusion — the most fragile link in the evidential chain.
     #[serde(default)]
     pub pivot_evidence: String,
 }
 
+impl OverallEvidenceAssessment {
+    /// Gaps sorted by priority (high first), preserving the model's order
+    /// within a priority level. The natural worklist for a follow-up
+    /// `assess` call on new sources.
+    #[must_use]
+    pub fn gaps_by_priority(&self) -> Vec<EvidenceGap> {
+        let mut gaps = self.gaps.clone();
+        gaps.sort_by_key(|g| priority_rank(g.priority));
+        gaps
+    }
+}
+
+/// Sort rank for a gap priority: high before medium before low.
+const fn priority_rank(priority: Priority) -> u8 {
+    match priority {
+        Priority::High => 0,
+        Priority::Medium => 1,
+        Priority::Low => 2,
+    }
+}
+
+/// A pair of evidence pieces that contradict each other.
+///
+/// Indices are zero-based positions into `evidence_pieces` on the same
+/// response, validated during parsing — an entry citing a piece that does
+/// not exist (or itself) is dropped.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EvidenceConflict {
+    /// Index of the first conflicting piece.
+    pub piece_a: usize,
+    /// Index of the second conflicting piece.
+    pub piece_b: usize,
+    /// How the two pieces disagree.
+    #[serde(default)]
+    pub nature: String,
+}
+
 /// Response from assess operation.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AssessResponse {
@@ -108,6 +177,10 @@ pub struct AssessResponse {
     pub overall_assessment: OverallEvidenceAssessment,
     /// Confidence in the conclusion.
     pub confidence_in_conclusion: f64,
+    /// Pairs of evidence pieces that contradict each other (empty when the
+    /// sources agree or only one piece was found).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conflicts: Vec<EvidenceConflict>,
 }
 
 impl AssessResponse {
@@ -126,8 +199,16 @@ impl AssessResponse {
             evidence_pieces,
             overall_assessment,
             confidence_in_conclusion,
+            conflicts: Vec::new(),
         }
     }
+
+    /// Attach the detected evidence conflicts.
+    #[must_use]
+    pub fn with_conflicts(mut self, conflicts: Vec<EvidenceConflict>) -> Self {
+        self.conflicts = conflicts;
+        self
+    }
 }
 
 // ============================================================================
@@ -154,6 +235,15 @@ pub struct EvidenceAnalysis {
     pub likelihood_if_false: f64,
     /// Bayes factor = P(E|H) / P(E|¬H).
     pub bayes_factor: f64,
+    /// Optional `[low, high]` range for P(E|H) when the model is uncertain
+    /// about the likelihood itself. Feeds the credible interval; absent means
+    /// the configured default width is applied around the point estimate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub likelihood_if_true_range: Option<[f64; 2]>,
+    /// Optional `[low, high]` range for P(E|¬H). Same semantics as
+    /// `likelihood_if_true_range`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub likelihood_if_false_range: Option<[f64; 2]>,
 }
 
 /// Posterior probability with calculation explanation.
@@ -200,6 +290,152 @@ pub struct BeliefUpdate {
     pub interpretation: String,
 }
 
+/// Expected belief movement from one piece of hypothetical evidence.
+///
+/// Ranks "what would change my mind": treating the evidence's likelihood
+/// structure as a template for a future observation, how far the posterior
+/// would be expected to move once we learn whether that evidence holds.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ValueOfInformation {
+    /// Description of the hypothetical evidence.
+    pub evidence: String,
+    /// Expected absolute posterior movement (0.0-1.0) from learning whether
+    /// the evidence holds, weighted by how likely each outcome is under the
+    /// current belief. Higher diagnosticity (P(E|H) far from P(E|¬H)) means
+    /// a larger expected shift.
+    pub expected_posterior_shift: f64,
+}
+
+/// Rank hypothetical evidence by expected posterior movement, strongest
+/// first (ties broken by description for deterministic output).
+///
+/// For each evidence item with current belief `p`, `P(E|H)` and `P(E|¬H)`:
+/// the expected shift is `P(E)·|P(H|E) − p| + P(¬E)·|P(H|¬E) − p|`, where
+/// `P(E) = p·P(E|H) + (1−p)·P(E|¬H)`. Degenerate likelihoods that make an
+/// outcome impossible contribute no shift for that outcome.
+#[must_use]
+pub fn rank_value_of_information(
+    current_belief: f64,
+    evidence_analysis: &[EvidenceAnalysis],
+) -> Vec<ValueOfInformation> {
+    let p = current_belief.clamp(0.0, 1.0);
+    let mut ranked: Vec<ValueOfInformation> = evidence_analysis
+        .iter()
+        .map(|e| {
+            let l_true = e.likelihood_if_true.clamp(0.0, 1.0);
+            let l_false = e.likelihood_if_false.clamp(0.0, 1.0);
+            let p_observed = p * l_true + (1.0 - p) * l_false;
+            let p_absent = 1.0 - p_observed;
+
+            let shift_if_observed = if p_observed > f64::EPSILON {
+                (p * l_true / p_observed - p).abs()
+            } else {
+                0.0
+            };
+            let shift_if_absent = if p_absent > f64::EPSILON {
+                (p * (1.0 - l_true) / p_absent - p).abs()
+            } else {
+                0.0
+            };
+
+            ValueOfInformation {
+                evidence: e.evidence.clone(),
+                expected_posterior_shift: p_observed * shift_if_observed
+                    + p_absent * shift_if_absent,
+            }
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| {
+        b.expected_posterior_shift
+            .partial_cmp(&a.expected_posterior_shift)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.evidence.cmp(&b.evidence))
+    });
+    ranked
+}
+
+/// Credible interval around the posterior point estimate.
+///
+/// Computed in Rust by propagating likelihood uncertainty through a
+/// sequential Bayesian update (see [`credible_interval`]), not model-stated.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CredibleInterval {
+    /// The posterior point estimate the interval brackets (0.0-1.0).
+    pub posterior: f64,
+    /// Lower bound of the interval (0.0-1.0). Always `<= posterior`.
+    pub lower: f64,
+    /// Upper bound of the interval (0.0-1.0). Always `>= posterior`.
+    pub upper: f64,
+}
+
+/// Compute a credible interval around a posterior by propagating uncertainty
+/// in the likelihoods through a sequential Bayesian update from the prior.
+///
+/// Each evidence item contributes a `[low, high]` range per likelihood: the
+/// model-provided range when present (clamped and reordered if inverted),
+/// otherwise `±default_width` around the point estimate, clamped to `[0, 1]`.
+/// The update is monotone — the posterior rises with P(E|H) and falls with
+/// P(E|¬H) — so the lower bound uses every P(E|H) low with every P(E|¬H)
+/// high, and the upper bound the reverse. The bounds are then widened to
+/// include the model's own point estimate so the interval always brackets it.
+#[must_use]
+pub fn credible_interval(
+    prior: f64,
+    point_posterior: f64,
+    evidence_analysis: &[EvidenceAnalysis],
+    default_width: f64,
+) -> CredibleInterval {
+    let width = default_width.clamp(0.0, 1.0);
+    let bounds = |point: f64, range: Option<[f64; 2]>| -> (f64, f64) {
+        let point = point.clamp(0.0, 1.0);
+        range.map_or_else(
+            || ((point - width).max(0.0), (point + width).min(1.0)),
+            |[a, b]| {
+                let (low, high) = (a.clamp(0.0, 1.0), b.clamp(0.0, 1.0));
+                if low <= high {
+                    (low, high)
+                } else {
+                    (high, low)
+                }
+            },
+        )
+    };
+
+    let update = |mut p: f64, pick: &dyn Fn(&EvidenceAnalysis) -> (f64, f64)| -> f64 {
+        for e in evidence_analysis {
+            let (l_true, l_false) = pick(e);
+            let numerator = p * l_true;
+            let denominator = numerator + (1.0 - p) * l_false;
+            if denominator > f64::EPSILON {
+                p = numerator / denominator;
+            }
+        }
+        p
+    };
+
+    let p0 = prior.clamp(0.0, 1.0);
+    let posterior = point_posterior.clamp(0.0, 1.0);
+    let lower = update(p0, &|e| {
+        (
+            bounds(e.likelihood_if_true, e.likelihood_if_true_range).0,
+            bounds(e.likelihood_if_false, e.likelihood_if_false_range).1,
+        )
+    });
+    let upper = update(p0, &|e| {
+        (
+            bounds(e.likelihood_if_true, e.likelihood_if_true_range).1,
+            bounds(e.likelihood_if_false, e.likelihood_if_false_range).0,
+        )
+    });
+
+    CredibleInterval {
+        posterior,
+        lower: lower.min(posterior),
+        upper: upper.max(posterior),
+    }
+}
+
 /// Response from probabilistic operation.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ProbabilisticResponse {
@@ -219,6 +455,21 @@ pub struct ProbabilisticResponse {
     pub belief_update: BeliefUpdate,
     /// Sensitivity to prior assumptions.
     pub sensitivity: String,
+    /// Hypothetical evidence ranked by expected posterior movement, strongest
+    /// first. Computed in Rust from the likelihood structure (see
+    /// [`rank_value_of_information`]), not model-stated.
+    #[serde(default)]
+    pub value_of_information: Vec<ValueOfInformation>,
+    /// Description of the evidence with the highest expected posterior shift
+    /// — the single observation most worth pursuing. `None` when no evidence
+    /// was analyzed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_driver: Option<String>,
+    /// Credible interval bracketing the posterior, from propagating likelihood
+    /// uncertainty through the update (see [`credible_interval`]). Computed in
+    /// Rust, not model-stated.
+    #[serde(default)]
+    pub credible_interval: CredibleInterval,
 }
 
 impl ProbabilisticResponse {
@@ -235,7 +486,19 @@ impl ProbabilisticResponse {
         posterior: Posterior,
         belief_update: BeliefUpdate,
         sensitivity: impl Into<String>,
+        interval_width: f64,
     ) -> Self {
+        // Derived here rather than accepted from the caller, so the ranking
+        // always matches the likelihood structure it is computed from.
+        let value_of_information =
+            rank_value_of_information(posterior.probability, &evidence_analysis);
+        let top_driver = value_of_information.first().map(|v| v.evidence.clone());
+        let credible_interval = credible_interval(
+            prior.probability,
+            posterior.probability,
+            &evidence_analysis,
+            interval_width,
+        );
         Self {
             thought_id: thought_id.into(),
             session_id: session_id.into(),
@@ -245,6 +508,55 @@ impl ProbabilisticResponse {
             posterior,
             belief_update,
             sensitivity: sensitivity.into(),
+            value_of_information,
+            top_driver,
+            credible_interval,
+        }
+    }
+}
+
+// ============================================================================
+// Response Types - Synthesize
+// ============================================================================
+
+/// Response from the synthesize operation: an `assess` pass chained into a
+/// `probabilistic` pass, with the raw posterior recalibrated by how credible
+/// the assessed evidence actually was.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SynthesizeResponse {
+    /// Unique identifier for this thought.
+    pub thought_id: String,
+    /// Session this thought belongs to.
+    pub session_id: String,
+    /// The credibility/quality assessment of the evidence.
+    pub assessment: AssessResponse,
+    /// The Bayesian update, informed by the assessed credibility scores.
+    pub probabilistic: ProbabilisticResponse,
+    /// Mean overall credibility across assessed pieces (0.0-1.0), used as the
+    /// calibration weight; 0.5 (neutral) when no pieces were identified.
+    pub credibility_weight: f64,
+    /// Final posterior with the raw shift scaled by `credibility_weight`.
+    pub calibrated_posterior: Posterior,
+}
+
+impl SynthesizeResponse {
+    /// Create a new synthesize response.
+    #[must_use]
+    pub fn new(
+        thought_id: impl Into<String>,
+        session_id: impl Into<String>,
+        assessment: AssessResponse,
+        probabilistic: ProbabilisticResponse,
+        credibility_weight: f64,
+        calibrated_posterior: Posterior,
+    ) -> Self {
+        Self {
+            thought_id: thought_id.into(),
+            session_id: session_id.into(),
+            assessment,
+            probabilistic,
+            credibility_weight,
+            calibrated_posterior,
         }
     }
 }
@@ -273,6 +585,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_gaps_by_priority_sorts_high_first() {
+        let gap = |text: &str, priority: Priority| EvidenceGap {
+            gap: text.to_string(),
+            suggested_action: String::new(),
+            priority,
+        };
+        let assessment = OverallEvidenceAssessment {
+            evidential_support: 0.5,
+            key_strengths: vec![],
+            key_weaknesses: vec![],
+            gaps: vec![
+                gap("low", Priority::Low),
+                gap("high", Priority::High),
+                gap("medium-1", Priority::Medium),
+                gap("medium-2", Priority::Medium),
+            ],
+            pivot_evidence: String::new(),
+        };
+
+        let sorted = assessment.gaps_by_priority();
+        let order: Vec<&str> = sorted.iter().map(|g| g.gap.as_str()).collect();
+        // Stable sort: medium gaps keep the model's order.
+        assert_eq!(order, vec!["high", "medium-1", "medium-2", "low"]);
+    }
+
     #[test]
     fn test_belief_direction_serialize() {
         assert_eq!(
@@ -288,4 +626,168 @@ mod tests {
             "\"strong\""
         );
     }
+
+    fn analysis(
+        evidence: &str,
+        likelihood_if_true: f64,
+        likelihood_if_false: f64,
+    ) -> EvidenceAnalysis {
+        EvidenceAnalysis {
+            evidence: evidence.to_string(),
+            likelihood_if_true,
+            likelihood_if_false,
+            bayes_factor: likelihood_if_true / likelihood_if_false,
+            likelihood_if_true_range: None,
+            likelihood_if_false_range: None,
+        }
+    }
+
+    #[test]
+    fn test_value_of_information_ranks_diagnostic_evidence_first() {
+        // A decisive test (0.9 vs 0.1) against a near-useless one (0.55 vs 0.5).
+        let ranked = rank_value_of_information(
+            0.5,
+            &[
+                analysis("weak survey", 0.55, 0.5),
+                analysis("decisive lab test", 0.9, 0.1),
+            ],
+        );
+
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].evidence, "decisive lab test");
+        assert!(ranked[0].expected_posterior_shift > ranked[1].expected_posterior_shift * 5.0);
+    }
+
+    #[test]
+    fn test_value_of_information_shift_magnitude() {
+        // p = 0.5, P(E|H) = 0.9, P(E|¬H) = 0.1:
+        //   P(E) = 0.5, P(H|E) = 0.9, P(H|¬E) = 0.1
+        //   expected shift = 0.5·|0.9 − 0.5| + 0.5·|0.1 − 0.5| = 0.4
+        let ranked = rank_value_of_information(0.5, &[analysis("lab test", 0.9, 0.1)]);
+        assert!((ranked[0].expected_posterior_shift - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_value_of_information_nondiagnostic_evidence_shifts_nothing() {
+        // Equal likelihoods carry no information, whatever the belief.
+        let ranked = rank_value_of_information(0.7, &[analysis("coin flip", 0.5, 0.5)]);
+        assert!(ranked[0].expected_posterior_shift.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_probabilistic_response_computes_top_driver() {
+        let response = ProbabilisticResponse::new(
+            "t-1",
+            "s-1",
+            "Hypothesis",
+            Prior {
+                probability: 0.5,
+                basis: "Base".to_string(),
+            },
+            vec![
+                analysis("weak survey", 0.55, 0.5),
+                analysis("decisive lab test", 0.9, 0.1),
+            ],
+            Posterior {
+                probability: 0.5,
+                calculation: "Calc".to_string(),
+            },
+            BeliefUpdate {
+                direction: BeliefDirection::Unchanged,
+                magnitude: BeliefMagnitude::Slight,
+                interpretation: "Interp".to_string(),
+            },
+            "Sensitivity",
+            0.1,
+        );
+
+        assert_eq!(response.top_driver.as_deref(), Some("decisive lab test"));
+        assert_eq!(response.value_of_information.len(), 2);
+    }
+
+    #[test]
+    fn test_probabilistic_response_without_evidence_has_no_driver() {
+        let response = ProbabilisticResponse::new(
+            "t-1",
+            "s-1",
+            "Hypothesis",
+            Prior {
+                probability: 0.5,
+                basis: "Base".to_string(),
+            },
+            vec![],
+            Posterior {
+                probability: 0.5,
+                calculation: "Calc".to_string(),
+            },
+            BeliefUpdate {
+                direction: BeliefDirection::Unchanged,
+                magnitude: BeliefMagnitude::Slight,
+                interpretation: "Interp".to_string(),
+            },
+            "Sensitivity",
+            0.1,
+        );
+
+        assert!(response.value_of_information.is_empty());
+        assert_eq!(response.top_driver, None);
+    }
+
+    #[test]
+    fn test_credible_interval_brackets_point_estimate() {
+        let interval = credible_interval(0.5, 0.8, &[analysis("lab test", 0.9, 0.1)], 0.1);
+        assert!(interval.lower <= interval.posterior);
+        assert!(interval.upper >= interval.posterior);
+        assert_eq!(interval.posterior, 0.8);
+        assert!(interval.lower < interval.upper);
+    }
+
+    #[test]
+    fn test_credible_interval_widens_with_wider_default_width() {
+        let evidence = [analysis("survey", 0.7, 0.3)];
+        let narrow = credible_interval(0.5, 0.7, &evidence, 0.05);
+        let wide = credible_interval(0.5, 0.7, &evidence, 0.25);
+        assert!(wide.upper - wide.lower > narrow.upper - narrow.lower);
+    }
+
+    #[test]
+    fn test_credible_interval_widens_with_wider_model_ranges() {
+        let mut certain = analysis("lab test", 0.8, 0.2);
+        certain.likelihood_if_true_range = Some([0.78, 0.82]);
+        certain.likelihood_if_false_range = Some([0.18, 0.22]);
+        let mut vague = certain.clone();
+        vague.likelihood_if_true_range = Some([0.5, 0.95]);
+        vague.likelihood_if_false_range = Some([0.05, 0.5]);
+
+        let tight = credible_interval(0.5, 0.94, &[certain], 0.1);
+        let loose = credible_interval(0.5, 0.94, &[vague], 0.1);
+        assert!(loose.upper - loose.lower > tight.upper - tight.lower);
+    }
+
+    #[test]
+    fn test_credible_interval_model_range_overrides_default_width() {
+        // A provided range pins the bounds; the default width is ignored.
+        let mut exact = analysis("census", 0.6, 0.4);
+        exact.likelihood_if_true_range = Some([0.6, 0.6]);
+        exact.likelihood_if_false_range = Some([0.4, 0.4]);
+        let point = 0.6 / (0.6 + 0.4);
+        let interval = credible_interval(0.5, point, &[exact], 0.3);
+        assert!((interval.lower - point).abs() < 1e-9);
+        assert!((interval.upper - point).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_credible_interval_without_evidence_collapses_to_point() {
+        let interval = credible_interval(0.5, 0.5, &[], 0.2);
+        assert_eq!(interval.lower, 0.5);
+        assert_eq!(interval.upper, 0.5);
+    }
+
+    #[test]
+    fn test_credible_interval_reorders_inverted_range() {
+        let mut inverted = analysis("report", 0.7, 0.3);
+        inverted.likelihood_if_true_range = Some([0.9, 0.5]);
+        let interval = credible_interval(0.5, 0.7, &[inverted], 0.1);
+        assert!(interval.lower <= interval.upper);
+    }
 }
@@ -0,0 +1,271 @@
+//! Chronological view of assessed evidence over a timeline.
+//!
+//! Research workflows assess evidence in evidence mode and build event
+//! sequences in timeline mode; [`evidence_timeline`] bridges the two. Each
+//! dated [`EvidencePiece`] is matched to the [`TimelineEvent`] whose time is
+//! closest, the dated pieces are sorted chronologically, and a piece whose
+//! date disagrees with where its matched event sits in the established event
+//! order is flagged. The mapping is purely structural: no API calls and no
+//! storage access, so evidence and events from different sessions combine
+//! like any others.
+
+use serde::{Deserialize, Serialize};
+
+use super::evidence::EvidencePiece;
+use super::timeline::TimelineEvent;
+
+/// One dated piece of evidence placed on the timeline.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EvidenceTimelineEntry {
+    /// Zero-based index of the piece in the input evidence list.
+    pub evidence_index: usize,
+    /// Summary of the evidence, as assessed.
+    pub summary: String,
+    /// Date of the evidence, as given (ISO date, year-month, or year).
+    pub date: String,
+    /// Id of the nearest-dated event, when any event time is parseable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched_event_id: Option<String>,
+    /// True when this evidence's date places it after evidence matched to a
+    /// later event in the established order — the dates say the events
+    /// happened in a different sequence than the timeline claims.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub contradicts_event_order: bool,
+}
+
+/// Chronological evidence view produced by [`evidence_timeline`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EvidenceTimeline {
+    /// Dated evidence in chronological order (ties keep input order).
+    pub entries: Vec<EvidenceTimelineEntry>,
+    /// Summaries of evidence without a usable date, in input order. Undated
+    /// evidence cannot be placed and is never flagged.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub undated: Vec<String>,
+}
+
+/// Map assessed evidence onto timeline events chronologically.
+///
+/// The events list is taken as the established order (the timeline's causal
+/// or narrative sequence). Each dated piece is matched to the event with the
+/// nearest parseable time (earlier event wins a tie); walking the entries in
+/// date order, a piece matched to an event that precedes an already-seen
+/// event is flagged as contradicting that order.
+#[must_use]
+pub fn evidence_timeline(evidence: &[EvidencePiece], events: &[TimelineEvent]) -> EvidenceTimeline {
+    let event_keys: Vec<Option<i64>> = events.iter().map(|e| parse_date_key(&e.time)).collect();
+
+    let mut entries = Vec::new();
+    let mut undated = Vec::new();
+    for (index, piece) in evidence.iter().enumerate() {
+        let Some((date, key)) = piece
+            .date
+            .as_deref()
+            .and_then(|d| parse_date_key(d).map(|k| (d.to_string(), k)))
+        else {
+            undated.push(piece.summary.clone());
+            continue;
+        };
+        entries.push((
+            key,
+            EvidenceTimelineEntry {
+                evidence_index: index,
+                summary: piece.summary.clone(),
+                date,
+                matched_event_id: nearest_event(key, events, &event_keys),
+                contradicts_event_order: false,
+            },
+        ));
+    }
+    entries.sort_by_key(|(key, entry)| (*key, entry.evidence_index));
+
+    // Chronological evidence should visit the established event order
+    // front-to-back; a step backwards means the dates contradict it.
+    let mut latest_seen: Option<usize> = None;
+    for (_, entry) in &mut entries {
+        let Some(position) = entry
+            .matched_event_id
+            .as_deref()
+            .and_then(|id| events.iter().position(|e| e.id == id))
+        else {
+            continue;
+        };
+        if latest_seen.is_some_and(|seen| position < seen) {
+            entry.contradicts_event_order = true;
+        } else {
+            latest_seen = Some(position);
+        }
+    }
+
+    EvidenceTimeline {
+        entries: entries.into_iter().map(|(_, entry)| entry).collect(),
+        undated,
+    }
+}
+
+/// Find the id of the event whose parsed time is nearest `key`.
+///
+/// Events without a parseable time are skipped; ties go to the earlier event
+/// in the list. `None` when no event time parses at all.
+fn nearest_event(key: i64, events: &[TimelineEvent], event_keys: &[Option<i64>]) -> Option<String> {
+    events
+        .iter()
+        .zip(event_keys)
+        .filter_map(|(event, event_key)| event_key.map(|k| (event, (k - key).abs())))
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(event, _)| event.id.clone())
+}
+
+/// Parse a free-form time marker into an orderable day count.
+///
+/// Scans for the first four-digit year and any `-MM` / `-MM-DD` that follows
+/// it, so `"2024-06-15"`, `"2024-06"`, `"2024"`, and `"Q3 2024 launch"` all
+/// parse. The scale (372 days to a nominal year) only has to order and
+/// measure nearness, not match the calendar. `None` when no year is found.
+fn parse_date_key(time: &str) -> Option<i64> {
+    let bytes = time.as_bytes();
+    let year_start = (0..bytes.len().saturating_sub(3)).find(|&i| {
+        bytes[i..i + 4].iter().all(u8::is_ascii_digit)
+            && (i == 0 || !bytes[i - 1].is_ascii_digit())
+            && bytes.get(i + 4).is_none_or(|b| !b.is_ascii_digit())
+    })?;
+    let year: i64 = time.get(year_start..year_start + 4)?.parse().ok()?;
+
+    let rest = &bytes[year_start + 4..];
+    let month = parse_two_digit_component(rest);
+    let day = month.and_then(|_| parse_two_digit_component(&rest[3..]));
+    Some(year * 372 + (month.unwrap_or(1) - 1) * 31 + (day.unwrap_or(1) - 1))
+}
+
+/// Parse a leading `-NN` component, if the bytes start with one.
+fn parse_two_digit_component(bytes: &[u8]) -> Option<i64> {
+    match bytes {
+        [b'-', tens, ones, ..] if tens.is_ascii_digit() && ones.is_ascii_digit() => {
+            Some(i64::from((tens - b'0') * 10 + (ones - b'0')))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::modes::evidence::{Credibility, EvidenceQuality, SourceType};
+    use crate::modes::timeline::EventType;
+
+    fn piece(summary: &str, date: Option<&str>) -> EvidencePiece {
+        EvidencePiece {
+            summary: summary.to_string(),
+            source_type: SourceType::Primary,
+            credibility: Credibility {
+                expertise: 0.8,
+                objectivity: 0.8,
+                corroboration: 0.8,
+                recency: 0.8,
+                overall: 0.8,
+            },
+            quality: EvidenceQuality {
+                relevance: 0.8,
+                strength: 0.8,
+                representativeness: 0.8,
+                overall: 0.8,
+            },
+            source_url: None,
+            source_title: None,
+            date: date.map(str::to_string),
+            source_url_malformed: false,
+        }
+    }
+
+    fn event(id: &str, time: &str) -> TimelineEvent {
+        TimelineEvent {
+            id: id.to_string(),
+            description: format!("Event {id}"),
+            time: time.to_string(),
+            event_type: EventType::Event,
+            causes: vec![],
+            effects: vec![],
+        }
+    }
+
+    #[test]
+    fn test_dated_evidence_maps_to_nearest_events_chronologically() {
+        let events = vec![event("e1", "2024-01-10"), event("e2", "2024-06-20")];
+        let evidence = vec![
+            piece("Launch report", Some("2024-06-18")),
+            piece("Kickoff memo", Some("2024-01-12")),
+        ];
+
+        let timeline = evidence_timeline(&evidence, &events);
+
+        assert_eq!(timeline.entries.len(), 2);
+        assert_eq!(timeline.entries[0].summary, "Kickoff memo");
+        assert_eq!(timeline.entries[0].matched_event_id.as_deref(), Some("e1"));
+        assert_eq!(timeline.entries[1].summary, "Launch report");
+        assert_eq!(timeline.entries[1].matched_event_id.as_deref(), Some("e2"));
+        assert!(timeline
+            .entries
+            .iter()
+            .all(|entry| !entry.contradicts_event_order));
+        assert!(timeline.undated.is_empty());
+    }
+
+    #[test]
+    fn test_out_of_order_evidence_is_flagged() {
+        // The timeline claims e1 happened before e2, but the dated evidence
+        // says otherwise: the piece tied to e1 postdates the piece tied to e2.
+        let events = vec![event("e1", "2024-05"), event("e2", "2024-02")];
+        let evidence = vec![
+            piece("Supports the first event", Some("2024-05-01")),
+            piece("Supports the second event", Some("2024-02-01")),
+        ];
+
+        let timeline = evidence_timeline(&evidence, &events);
+
+        assert_eq!(timeline.entries[0].matched_event_id.as_deref(), Some("e2"));
+        assert!(!timeline.entries[0].contradicts_event_order);
+        assert_eq!(timeline.entries[1].matched_event_id.as_deref(), Some("e1"));
+        assert!(timeline.entries[1].contradicts_event_order);
+    }
+
+    #[test]
+    fn test_undated_evidence_is_listed_not_placed() {
+        let events = vec![event("e1", "2024-01")];
+        let evidence = vec![
+            piece("Dated", Some("2024-01-05")),
+            piece("Undated survey", None),
+            piece("Unparseable date", Some("last spring")),
+        ];
+
+        let timeline = evidence_timeline(&evidence, &events);
+
+        assert_eq!(timeline.entries.len(), 1);
+        assert_eq!(
+            timeline.undated,
+            vec!["Undated survey".to_string(), "Unparseable date".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_no_parseable_event_times_leaves_evidence_unmatched() {
+        let events = vec![event("e1", "after the merger")];
+        let evidence = vec![piece("Dated", Some("2024-03"))];
+
+        let timeline = evidence_timeline(&evidence, &events);
+
+        assert_eq!(timeline.entries.len(), 1);
+        assert!(timeline.entries[0].matched_event_id.is_none());
+        assert!(!timeline.entries[0].contradicts_event_order);
+    }
+
+    #[test]
+    fn test_date_key_parses_year_month_day_variants() {
+        assert!(parse_date_key("2024-06-15").unwrap() > parse_date_key("2024-06").unwrap());
+        assert!(parse_date_key("2024-06").unwrap() > parse_date_key("2024").unwrap());
+        assert_eq!(parse_date_key("Q3 2024 launch"), parse_date_key("2024"));
+        assert_eq!(parse_date_key("no date here"), None);
+        // A leading longer digit run is not mistaken for a year.
+        assert_eq!(parse_date_key("ticket 123456"), None);
+    }
+}
@@ -1,8 +1,10 @@
 //! Graph-of-Thoughts mode.
 //!
-//! This mode provides 8 graph operations:
+//! This mode provides 10 graph operations:
 //! - `init`: Create a graph with root node
+//! - `import`: Seed a graph from external JSON (nodes + edges)
 //! - `generate`: Generate child nodes
+//! - `advance`: Auto-expand the best frontier nodes in one call
 //! - `score`: Evaluate nodes
 //! - `aggregate`: Merge nodes into synthesis
 //! - `refine`: Improve nodes through self-critique
@@ -10,6 +12,10 @@
 //! - `finalize`: Extract conclusions
 //! - `state`: Get current graph state
 //!
+//! Plus graph-level snapshotting: `snapshot` captures the full node/edge set
+//! and `restore_snapshot` reinstates it (replace semantics), e.g. around an
+//! aggressive prune.
+//!
 //! # Module Structure
 //!
 //! - `types`: Response types for all operations
@@ -18,29 +24,64 @@
 #![allow(clippy::missing_const_for_fn)]
 
 mod parsing;
+mod rubric;
 pub mod types;
 
+// Shared with detect's circular-reasoning analysis, which runs the same
+// cycle detection over a session's justification graph.
+pub use parsing::cyclic_ids;
+pub use rubric::{quality_rubric, QualityRubric};
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+
+use futures_util::StreamExt;
+
 use crate::error::ModeError;
-use crate::modes::{extract_json, generate_thought_id, validate_content};
+use crate::modes::{
+    append_language_instruction, extract_json, generate_checkpoint_id, generate_thought_id,
+    reject_unknown_keys, validate_content, ModeFlags,
+};
 use crate::prompts::{
     graph_aggregate_prompt, graph_finalize_prompt, graph_generate_prompt, graph_init_prompt,
     graph_prune_prompt, graph_refine_prompt, graph_score_prompt, graph_state_prompt,
 };
-use crate::storage::{GraphEdgeType, GraphNodeType};
+use crate::storage::{content_similarity, GraphEdgeType, GraphNodeOrigin, GraphNodeType};
 use crate::traits::{
-    AnthropicClientTrait, CompletionConfig, Message, Session, StorageTrait, StoredGraphEdge,
-    StoredGraphNode, Thought,
+    AnthropicClientTrait, CompletionConfig, Message, Session, StorageTrait, StoredCheckpoint,
+    StoredGraphEdge, StoredGraphNode, Thought,
 };
 
 pub use types::{
-    AggregateResponse, ChildNode, ComplexityLevel, ExpansionDirection, FinalizeResponse,
-    FrontierNodeInfo, GenerateResponse, GraphConclusion, GraphMetadata, GraphMetrics, GraphPath,
-    GraphStructure, InitResponse, IntegrationNotes, NodeAssessment, NodeCritique,
-    NodeRecommendation, NodeRelationship, NodeScores, NodeType, PruneCandidate, PruneImpact,
-    PruneReason, PruneResponse, RefineResponse, RefinedNode, RootNode, ScoreResponse,
-    SessionQuality, StateResponse, SuggestedAction, SynthesisNode,
+    AdvanceResponse, AggregateResponse, ApplyPruneResponse, ChildNode, ComplexityLevel,
+    ExpandedFrontier, ExpansionDirection, FinalizeResponse, FrontierNodeInfo, GenerateResponse,
+    GraphConclusion, GraphMetadata, GraphMetrics, GraphPath, GraphStructure, ImportResponse,
+    ImportedEdge, ImportedNode, InitResponse, IntegrationNotes, MergeGraphsResponse,
+    MergedDuplicate, NodeAssessment, NodeCritique, NodeProvenance, NodeRecommendation,
+    NodeRelationship, NodeScores, NodeType, OmittedEdge, PathAdjustment, ProtectedNode,
+    PruneCandidate, PruneImpact, PruneReason, PruneResponse, RefineResponse, RefinedNode,
+    RestoreSnapshotResponse, RootNode, ScoreResponse, SessionQuality, SnapshotResponse,
+    StateResponse, SuggestedAction, SynthesisNode, TreeViewNode, TreeViewResponse,
 };
 
+/// Most frontier nodes a single `advance` call will expand, regardless of the
+/// caller's requested count.
+const MAX_ADVANCE_NODES: usize = 5;
+
+/// Cap on generate completions in flight during an advance, so a wide frontier
+/// cannot hold an unbounded number of API requests open at once.
+const MAX_CONCURRENT_ADVANCE: usize = 4;
+
+/// Callback [`GraphMode::advance_with_observer`] invokes with each expanded
+/// frontier as its expansion completes (and its children are persisted).
+pub type FrontierObserver<'a> = &'a (dyn Fn(&ExpandedFrontier) + Send + Sync);
+
+/// Default per-session ceiling on stored graph nodes. `generate` (and so
+/// `advance`) refuses to add nodes past it, protecting storage and prompt
+/// size; prune first to make room. The server overrides this per deployment
+/// via `Config::graph_max_nodes` (`GRAPH_MAX_NODES`).
+const MAX_GRAPH_NODES: usize = 200;
+
 // ============================================================================
 // GraphMode
 // ============================================================================
@@ -56,6 +97,17 @@ where
 {
     storage: S,
     client: C,
+    /// Behavior flags injected from server configuration (all off by default).
+    flags: ModeFlags,
+    max_graph_nodes: usize,
+    /// Cap on children returned by generate/advance (`None` = uncapped).
+    /// The cap only trims the response; every child is still persisted.
+    max_returned_children: Option<usize>,
+    /// Weight of the LLM self-report in the finalize quality rubric.
+    rubric_llm_weight: f64,
+    /// Effective output language (per-request override merged with the
+    /// configured default at the server boundary).
+    language: Option<String>,
 }
 
 impl<S, C> GraphMode<S, C>
@@ -66,7 +118,87 @@ where
     /// Create a new graph mode instance.
     #[must_use]
     pub fn new(storage: S, client: C) -> Self {
-        Self { storage, client }
+        Self {
+            storage,
+            client,
+            flags: ModeFlags::default(),
+            max_graph_nodes: MAX_GRAPH_NODES,
+            max_returned_children: None,
+            rubric_llm_weight: rubric::DEFAULT_LLM_WEIGHT,
+            language: None,
+        }
+    }
+
+    /// Set the output language for natural-language response fields. The
+    /// server passes the per-request override merged with the configured
+    /// `RESPONSE_LANGUAGE` default; `None` adds no language instruction.
+    #[must_use]
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Inject the server's behavior flags ([`ModeFlags`]). A mode built
+    /// without them — as in most tests — uses the all-off defaults.
+    #[must_use]
+    pub fn with_flags(mut self, flags: ModeFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Override the per-session node ceiling (default [`MAX_GRAPH_NODES`];
+    /// clamped to at least 1). The server passes
+    /// `Config::graph_max_nodes` here so operators can tune the cap.
+    #[must_use]
+    pub fn with_max_graph_nodes(mut self, max_graph_nodes: usize) -> Self {
+        self.max_graph_nodes = max_graph_nodes.max(1);
+        self
+    }
+
+    /// Cap the children generate (and so advance) returns at `max`,
+    /// top-scored first; `None` (and zero) means uncapped. The server passes
+    /// `Config::graph_max_returned_children` (`GRAPH_MAX_RETURNED_CHILDREN`)
+    /// here. The cap only trims the response — every generated child is
+    /// persisted regardless, and a trimmed response says how many were held
+    /// back.
+    #[must_use]
+    pub fn with_max_returned_children(mut self, max: Option<usize>) -> Self {
+        self.max_returned_children = max.filter(|&m| m > 0);
+        self
+    }
+
+    /// Set the weight of the LLM self-report in the finalize quality rubric
+    /// (clamped to `[0.0, 1.0]`; default 0.5). The server passes
+    /// `Config::rubric_llm_weight` (`QUALITY_RUBRIC_LLM_WEIGHT`) here.
+    #[must_use]
+    pub fn with_rubric_llm_weight(mut self, weight: f64) -> Self {
+        self.rubric_llm_weight = weight.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Error when the session's stored graph is already at the node ceiling.
+    ///
+    /// Checked before the API call so a rejected `generate` costs nothing.
+    async fn ensure_node_capacity(&self, session_id: &str) -> Result<(), ModeError> {
+        let node_count = self
+            .storage
+            .get_graph_nodes(session_id)
+            .await
+            .map_err(|e| ModeError::ApiUnavailable {
+                message: format!("Failed to get graph nodes: {e}"),
+            })?
+            .len();
+        if node_count >= self.max_graph_nodes {
+            return Err(ModeError::InvalidValue {
+                field: "graph".to_string(),
+                reason: format!(
+                    "node limit reached: session has {node_count} of {max} nodes — \
+                     run prune to remove low-quality nodes before generating more",
+                    max = self.max_graph_nodes,
+                ),
+            });
+        }
+        Ok(())
     }
 
     /// Initialize a graph with a root node.
@@ -88,11 +220,13 @@ where
 
         let session = self.get_or_create_session(session_id).await?;
 
-        let prompt = graph_init_prompt();
+        let prompt =
+            append_language_instruction(graph_init_prompt(), self.language.as_deref(), content);
         let user_message = format!("{prompt}\n\nTopic:\n{content}");
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("graph")
             .with_max_tokens(8192)
             .with_temperature(0.4)
             .with_standard_thinking();
@@ -100,6 +234,11 @@ where
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
 
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &["root", "expansion_directions", "graph_metadata"],
+        )?;
         let root = parsing::parse_root(&json)?;
         let expansion_directions = parsing::parse_expansion_directions(&json)?;
         let graph_metadata = parsing::parse_graph_metadata(&json)?;
@@ -126,6 +265,8 @@ where
             &root.content,
             root.score,
             GraphNodeType::Thought,
+            GraphNodeOrigin::Init,
+            &[],
         )
         .await;
 
@@ -156,13 +297,19 @@ where
         session_id: Option<String>,
     ) -> Result<GenerateResponse, ModeError> {
         let session = self.get_or_create_session(session_id).await?;
+        self.ensure_node_capacity(&session.id).await?;
         let resolved_content = self.resolve_content(content, node_id, &session.id).await?;
 
-        let prompt = graph_generate_prompt();
+        let prompt = append_language_instruction(
+            graph_generate_prompt(),
+            self.language.as_deref(),
+            &resolved_content,
+        );
         let user_message = format!("{prompt}\n\nParent node:\n{resolved_content}");
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("graph")
             .with_max_tokens(8192)
             .with_temperature(0.5)
             .with_standard_thinking();
@@ -170,6 +317,11 @@ where
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
 
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &["parent_id", "children", "generation_notes"],
+        )?;
         let parent_id = parsing::get_str(&json, "parent_id")?;
         let children = parsing::parse_children(&json)?;
         let generation_notes = parsing::get_str(&json, "generation_notes")?;
@@ -183,32 +335,59 @@ where
             0.7,
         );
 
-        if let Err(e) = self.storage.save_thought(&thought).await {
-            tracing::warn!(error = %e, "Storage write failed — reasoning result preserved, thought not persisted");
-        }
-
-        // Track how many of the child/edge writes failed so the caller can be
-        // told when the graph wasn't fully saved (e.g. generating from a parent
-        // node that was never persisted drops the edge under FK enforcement).
-        let mut persistence_failures: u32 = 0;
+        // Persist the thought, child nodes, and edges as one atomic batch:
+        // either the whole generation lands or nothing does, so a mid-write
+        // failure (e.g. an edge from a parent that was never persisted under
+        // FK enforcement) can't leave a partially-saved graph. Failures are
+        // still logged and counted rather than propagated, so a write error
+        // never discards a reasoning result already produced.
+        let mut nodes = Vec::with_capacity(children.len());
+        let mut edges = Vec::with_capacity(children.len());
         for child in &children {
-            if !self
-                .persist_node(
+            nodes.push(
+                StoredGraphNode::new(
+                    Self::namespaced_id(&session.id, &child.id),
                     &session.id,
-                    &child.id,
                     &child.content,
-                    child.score,
-                    GraphNodeType::Thought,
                 )
-                .await
-            {
-                persistence_failures += 1;
-            }
-            if !self
-                .persist_edge(&session.id, &parent_id, &child.id, GraphEdgeType::Continues)
-                .await
-            {
-                persistence_failures += 1;
+                .with_score(child.score)
+                .with_node_type(GraphNodeType::Thought)
+                .with_provenance(
+                    GraphNodeOrigin::Generate,
+                    vec![Self::namespaced_id(&session.id, &parent_id)],
+                ),
+            );
+            edges.push(
+                StoredGraphEdge::new(
+                    Self::namespaced_id(&session.id, &format!("{parent_id}->{}", child.id)),
+                    &session.id,
+                    Self::namespaced_id(&session.id, &parent_id),
+                    Self::namespaced_id(&session.id, &child.id),
+                )
+                .with_edge_type(GraphEdgeType::Continues),
+            );
+        }
+
+        let mut persistence_failures: u32 = 0;
+        if let Err(e) = self
+            .storage
+            .save_graph_batch(&thought, &nodes, &edges)
+            .await
+        {
+            persistence_failures = u32::try_from(nodes.len() + edges.len()).unwrap_or(u32::MAX);
+            tracing::warn!(error = %e, "Graph persistence rolled back — reasoning result preserved, nothing stored");
+        }
+
+        // The full child set is persisted above; the cap only trims what goes
+        // back to the client, keeping the top-scored children so a trimmed
+        // response still leads with the best continuations.
+        let mut returned_children = children;
+        let mut truncated_children = None;
+        if let Some(max) = self.max_returned_children {
+            if returned_children.len() > max {
+                truncated_children = Some(returned_children.len() - max);
+                returned_children.sort_by(|a, b| b.score.total_cmp(&a.score));
+                returned_children.truncate(max);
             }
         }
 
@@ -216,10 +395,11 @@ where
             thought_id,
             session.id,
             parent_id,
-            children,
+            returned_children,
             generation_notes,
         )
-        .with_persistence_failures(persistence_failures))
+        .with_persistence_failures(persistence_failures)
+        .with_truncated_children(truncated_children))
     }
 
     /// Score and evaluate a node.
@@ -242,11 +422,16 @@ where
         let session = self.get_or_create_session(session_id).await?;
         let resolved_content = self.resolve_content(content, node_id, &session.id).await?;
 
-        let prompt = graph_score_prompt();
+        let prompt = append_language_instruction(
+            graph_score_prompt(),
+            self.language.as_deref(),
+            &resolved_content,
+        );
         let user_message = format!("{prompt}\n\nNode to score:\n{resolved_content}");
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("graph")
             .with_max_tokens(8192)
             .with_temperature(0.3)
             .with_standard_thinking();
@@ -254,6 +439,11 @@ where
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
 
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &["node_id", "scores", "assessment"],
+        )?;
         let response_node_id = parsing::get_str(&json, "node_id")?;
         let scores = parsing::parse_node_scores(&json)?;
         let assessment = parsing::parse_node_assessment(&json)?;
@@ -302,11 +492,16 @@ where
 
         let session = self.get_or_create_session(session_id).await?;
 
-        let prompt = graph_aggregate_prompt();
+        let prompt = append_language_instruction(
+            graph_aggregate_prompt(),
+            self.language.as_deref(),
+            content,
+        );
         let user_message = format!("{prompt}\n\nNodes to aggregate:\n{content}");
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("graph")
             .with_max_tokens(8192)
             .with_temperature(0.4)
             .with_standard_thinking();
@@ -314,6 +509,11 @@ where
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
 
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &["input_node_ids", "synthesis", "integration_notes"],
+        )?;
         let input_node_ids = parsing::get_string_array(&json, "input_node_ids")?;
         let synthesis = parsing::parse_synthesis(&json)?;
         let integration_notes = parsing::parse_integration_notes(&json)?;
@@ -337,6 +537,8 @@ where
             &synthesis.content,
             synthesis.score,
             GraphNodeType::Aggregation,
+            GraphNodeOrigin::Aggregate,
+            &input_node_ids,
         )
         .await;
         for input_id in &input_node_ids {
@@ -377,11 +579,13 @@ where
 
         let session = self.get_or_create_session(session_id).await?;
 
-        let prompt = graph_refine_prompt();
+        let prompt =
+            append_language_instruction(graph_refine_prompt(), self.language.as_deref(), content);
         let user_message = format!("{prompt}\n\nNode to refine:\n{content}");
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("graph")
             .with_max_tokens(8192)
             .with_temperature(0.4)
             .with_standard_thinking();
@@ -389,6 +593,16 @@ where
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
 
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &[
+                "original_node_id",
+                "critique",
+                "refined_node",
+                "improvement_delta",
+            ],
+        )?;
         let original_node_id = parsing::get_str(&json, "original_node_id")?;
         let critique = parsing::parse_critique(&json)?;
         let refined_node = parsing::parse_refined_node(&json)?;
@@ -413,6 +627,8 @@ where
             &refined_node.content,
             refined_node.score,
             GraphNodeType::Refinement,
+            GraphNodeOrigin::Refine,
+            std::slice::from_ref(&original_node_id),
         )
         .await;
         self.persist_edge(
@@ -453,16 +669,23 @@ where
 
         let session = self.get_or_create_session(session_id).await?;
 
-        let prompt = graph_prune_prompt();
+        let prompt =
+            append_language_instruction(graph_prune_prompt(), self.language.as_deref(), content);
+        // Structural centrality prioritizes candidates: low centrality + low
+        // score = prune candidate. Best-effort — an empty/unreadable stored
+        // graph just omits the block.
+        let centrality = self.centrality_or_empty(&session.id).await;
+        let centrality_block = Self::centrality_prompt_block(&centrality);
         // Inject the (tunable) quality floor: nodes scoring below it are
         // pruning candidates. Caller/Config-supplied via `quality_floor`.
         let user_message = format!(
             "{prompt}\n\nTreat a node as a pruning candidate when its quality \
-             score is below {quality_floor:.2}.\n\nGraph state:\n{content}"
+             score is below {quality_floor:.2}.{centrality_block}\n\nGraph state:\n{content}"
         );
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("graph")
             .with_max_tokens(8192)
             .with_temperature(0.3)
             .with_standard_thinking();
@@ -470,6 +693,11 @@ where
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
 
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &["prune_candidates", "preserve_nodes", "pruning_strategy"],
+        )?;
         let prune_candidates = parsing::parse_prune_candidates(&json)?;
         let preserve_nodes = parsing::get_string_array(&json, "preserve_nodes")?;
         let pruning_strategy = parsing::get_str(&json, "pruning_strategy")?;
@@ -503,6 +731,122 @@ where
         ))
     }
 
+    /// Apply a prune: delete the given nodes and their edges from storage.
+    ///
+    /// Unlike [`Self::prune`], which asks the model for candidates, this takes
+    /// the caller's list and removes it — with guardrails. A graph root (a
+    /// node with no incoming edge) and any node on the current best path (the
+    /// same stored-score walk [`Self::finalize`] computes) are protected:
+    /// they are reported, not removed, unless `force` is true. Candidates
+    /// absent from the stored graph are reported as missing.
+    ///
+    /// Deterministic: reads and mutates the stored graph without an API call.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModeError`] if no graph is stored for the session or a
+    /// storage operation fails.
+    pub async fn apply_prune(
+        &self,
+        session_id: &str,
+        candidates: &[String],
+        force: bool,
+    ) -> Result<ApplyPruneResponse, ModeError> {
+        let storage_err = |context: &'static str| {
+            move |e: crate::error::StorageError| ModeError::ApiUnavailable {
+                message: format!("{context}: {e}"),
+            }
+        };
+
+        let nodes = self
+            .storage
+            .get_graph_nodes(session_id)
+            .await
+            .map_err(storage_err("Failed to get graph nodes"))?;
+        if nodes.is_empty() {
+            return Err(ModeError::InvalidValue {
+                field: "session_id".to_string(),
+                reason: format!(
+                    "No graph stored for session '{session_id}' — run init or import first"
+                ),
+            });
+        }
+        let edges = self
+            .storage
+            .get_graph_edges(session_id)
+            .await
+            .map_err(storage_err("Failed to get graph edges"))?;
+
+        let prefix = format!("{session_id}::");
+        let strip = |id: &str| -> String { id.strip_prefix(&prefix).unwrap_or(id).to_string() };
+        let known: std::collections::HashSet<String> = nodes.iter().map(|n| strip(&n.id)).collect();
+        let has_incoming: std::collections::HashSet<String> =
+            edges.iter().map(|e| strip(&e.to_node_id)).collect();
+        let best_path: std::collections::HashSet<String> =
+            Self::computed_best_path(&nodes, &edges, &prefix)
+                .map(|p| p.path.into_iter().collect())
+                .unwrap_or_default();
+
+        let mut removed = Vec::new();
+        let mut protected = Vec::new();
+        let mut missing = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        // An edge between two removed candidates must only be deleted once.
+        let mut deleted_edges = std::collections::HashSet::new();
+        for candidate in candidates {
+            if !seen.insert(candidate.clone()) {
+                continue;
+            }
+            if !known.contains(candidate) {
+                missing.push(candidate.clone());
+                continue;
+            }
+            if !force {
+                if !has_incoming.contains(candidate) {
+                    protected.push(ProtectedNode {
+                        node_id: candidate.clone(),
+                        reason: "graph root".to_string(),
+                    });
+                    continue;
+                }
+                if best_path.contains(candidate) {
+                    protected.push(ProtectedNode {
+                        node_id: candidate.clone(),
+                        reason: "on current best path".to_string(),
+                    });
+                    continue;
+                }
+            }
+
+            let storage_id = Self::namespaced_id(session_id, candidate);
+            for edge in edges
+                .iter()
+                .filter(|e| e.from_node_id == storage_id || e.to_node_id == storage_id)
+            {
+                if !deleted_edges.insert(edge.id.clone()) {
+                    continue;
+                }
+                self.storage
+                    .delete_graph_edge(&edge.id)
+                    .await
+                    .map_err(storage_err("Failed to delete graph edge"))?;
+            }
+            self.storage
+                .delete_graph_node(&storage_id)
+                .await
+                .map_err(storage_err("Failed to delete graph node"))?;
+            removed.push(candidate.clone());
+        }
+
+        Ok(ApplyPruneResponse {
+            session_id: session_id.to_string(),
+            removed,
+            protected,
+            missing,
+            forced: force,
+        })
+    }
+
     /// Finalize the graph and extract conclusions.
     ///
     /// # Arguments
@@ -522,11 +866,13 @@ where
 
         let session = self.get_or_create_session(session_id).await?;
 
-        let prompt = graph_finalize_prompt();
+        let prompt =
+            append_language_instruction(graph_finalize_prompt(), self.language.as_deref(), content);
         let user_message = format!("{prompt}\n\nGraph to finalize:\n{content}");
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("graph")
             .with_max_tokens(8192)
             .with_temperature(0.3)
             .with_standard_thinking();
@@ -534,11 +880,72 @@ where
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
 
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &[
+                "best_paths",
+                "conclusions",
+                "final_synthesis",
+                "session_quality",
+            ],
+        )?;
         let best_paths = parsing::parse_best_paths(&json)?;
         let conclusions = parsing::parse_conclusions(&json)?;
         let final_synthesis = parsing::get_str(&json, "final_synthesis")?;
         let session_quality = parsing::parse_session_quality(&json)?;
 
+        // Validate the model's paths against the stored graph: models
+        // occasionally reference node IDs that were never stored. When the
+        // graph is in storage, a path computed from stored scores is preferred
+        // and the model's (validated) paths follow as supplementary.
+        // Best-effort: a storage read failure keeps the model's paths
+        // unvalidated rather than discarding the paid result.
+        let (best_paths, path_adjustments, quality_rubric) = match self
+            .storage
+            .get_graph_nodes(&session.id)
+            .await
+        {
+            Ok(nodes) if !nodes.is_empty() => {
+                let prefix = format!("{}::", session.id);
+                let known: std::collections::HashSet<String> = nodes
+                    .iter()
+                    .map(|n| n.id.strip_prefix(&prefix).unwrap_or(&n.id).to_string())
+                    .collect();
+                let (mut validated, adjustments) = Self::validate_best_paths(best_paths, &known);
+                // The rubric is computed from whatever is readable: an edge
+                // read failure degrades path diversity, not the whole rubric.
+                let mut stored_edges = Vec::new();
+                match self.storage.get_graph_edges(&session.id).await {
+                    Ok(edges) => {
+                        if let Some(computed) = Self::computed_best_path(&nodes, &edges, &prefix) {
+                            if !validated.iter().any(|p| p.path == computed.path) {
+                                validated.insert(0, computed);
+                            }
+                        }
+                        stored_edges = edges;
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Failed to read graph edges — skipping computed best path");
+                    }
+                }
+                let rubric = quality_rubric(
+                    &session_quality,
+                    &nodes,
+                    &stored_edges,
+                    self.rubric_llm_weight,
+                );
+                (validated, adjustments, Some(rubric))
+            }
+            // No stored graph (content-only finalize): nothing to validate
+            // against or measure a rubric over.
+            Ok(_) => (best_paths, Vec::new(), None),
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to read graph nodes — returning model paths unvalidated");
+                (best_paths, Vec::new(), None)
+            }
+        };
+
         let thought_id = generate_thought_id();
         let thought = Thought::new(
             &thought_id,
@@ -563,7 +970,9 @@ where
             conclusions,
             final_synthesis,
             session_quality,
-        ))
+        )
+        .with_path_adjustments(path_adjustments)
+        .with_quality_rubric(quality_rubric))
     }
 
     /// Get the current state of the graph.
@@ -591,11 +1000,16 @@ where
             _ => self.build_graph_state_from_storage(session_id).await?,
         };
 
-        let prompt = graph_state_prompt();
+        let prompt = append_language_instruction(
+            graph_state_prompt(),
+            self.language.as_deref(),
+            &resolved_content,
+        );
         let user_message = format!("{prompt}\n\nGraph:\n{resolved_content}");
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("graph")
             .with_max_tokens(8192)
             .with_temperature(0.2)
             .with_standard_thinking();
@@ -603,6 +1017,11 @@ where
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
 
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &["structure", "frontiers", "metrics", "next_steps"],
+        )?;
         let structure = parsing::parse_structure(&json)?;
         let frontiers = parsing::parse_frontiers(&json)?;
         let metrics = parsing::parse_metrics(&json)?;
@@ -625,316 +1044,1416 @@ where
             tracing::warn!(error = %e, "Storage write failed — reasoning result preserved, thought not persisted");
         }
 
+        // Attach structural centrality and per-node provenance of the stored
+        // graph (both empty when nothing is stored, e.g. state over
+        // caller-supplied content only).
+        let centrality = self.centrality_or_empty(session_id).await;
+        let provenance = self.provenance_or_empty(session_id).await;
+
         Ok(StateResponse::new(
             thought_id, session.id, structure, frontiers, metrics, next_steps,
-        ))
+        )
+        .with_centrality(centrality)
+        .with_provenance(provenance))
     }
 
-    // ========================================================================
-    // Private Helpers
-    // ========================================================================
-
-    async fn get_or_create_session(
+    /// Import an externally-built graph (e.g. an existing argument map) into a
+    /// session, so subsequent `score`/`aggregate`/`finalize` can operate on it.
+    ///
+    /// Deterministic: validates and persists the supplied graph without an API
+    /// call. Unlike the best-effort writes of the reasoning operations, a
+    /// failed write here fails the import — a partially-persisted graph is the
+    /// only result, so there is no reasoning output worth preserving.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - JSON graph: `{"nodes": [{"id", "content", "score"?,
+    ///   "is_terminal"?}], "edges": [{"from", "to"}]}`
+    /// * `session_id` - Optional session ID
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModeError`] if the JSON is malformed, a node id is duplicated,
+    /// an edge references an unknown node, the graph is cyclic, or persistence
+    /// fails.
+    pub async fn import(
         &self,
+        content: &str,
         session_id: Option<String>,
-    ) -> Result<Session, ModeError> {
-        self.storage
-            .get_or_create_session(session_id)
-            .await
-            .map_err(|e| ModeError::ApiUnavailable {
-                message: format!("Failed to get or create session: {e}"),
-            })
-    }
+    ) -> Result<ImportResponse, ModeError> {
+        validate_content(content)?;
 
-    /// Resolve content from either direct content or node_id lookup.
-    ///
-    /// If content is provided and non-empty, uses it directly.
-    /// If node_id is provided, looks up the content from storage.
-    /// Returns an error if neither is provided.
-    async fn resolve_content(
-        &self,
-        content: Option<&str>,
-        node_id: Option<&str>,
-        session_id: &str,
-    ) -> Result<String, ModeError> {
-        // Check for direct content first
-        if let Some(c) = content {
-            if !c.trim().is_empty() {
-                return Ok(c.to_string());
+        let json = extract_json(content)?;
+        let (nodes, edges) = parsing::parse_import_graph(&json)?;
+
+        let session = self.get_or_create_session(session_id).await?;
+
+        let mut stored_nodes = Vec::with_capacity(nodes.len());
+        for node in &nodes {
+            // Imported nodes carry provenance too: the parents are whatever
+            // the supplied edges point at this node from.
+            let parent_ids: Vec<String> = edges
+                .iter()
+                .filter(|edge| edge.to == node.id)
+                .map(|edge| Self::namespaced_id(&session.id, &edge.from))
+                .collect();
+            let mut stored = StoredGraphNode::new(
+                Self::namespaced_id(&session.id, &node.id),
+                &session.id,
+                &node.content,
+            )
+            .with_provenance(GraphNodeOrigin::Import, parent_ids);
+            if let Some(score) = node.score {
+                stored = stored.with_score(score);
+            }
+            if node.is_terminal {
+                stored = stored.as_terminal();
             }
+            stored_nodes.push(stored);
         }
 
-        // Try to look up by node_id (stored under its session-namespaced key)
-        if let Some(nid) = node_id {
-            let storage_id = Self::namespaced_id(session_id, nid);
-            let node = self
-                .storage
-                .get_graph_node(&storage_id)
-                .await
-                .map_err(|e| ModeError::ApiUnavailable {
-                    message: format!("Failed to get graph node: {e}"),
-                })?
-                .ok_or_else(|| ModeError::InvalidValue {
-                    field: "node_id".to_string(),
-                    reason: format!("Node '{nid}' not found"),
-                })?;
-            return Ok(node.content);
-        }
+        let stored_edges: Vec<StoredGraphEdge> = edges
+            .iter()
+            .map(|edge| {
+                StoredGraphEdge::new(
+                    Self::namespaced_id(&session.id, &format!("{}->{}", edge.from, edge.to)),
+                    &session.id,
+                    Self::namespaced_id(&session.id, &edge.from),
+                    Self::namespaced_id(&session.id, &edge.to),
+                )
+            })
+            .collect();
 
-        // Neither provided
-        Err(ModeError::MissingField {
-            field: "content or node_id".to_string(),
-        })
+        let roots: Vec<String> = nodes
+            .iter()
+            .filter(|n| edges.iter().all(|e| e.to != n.id))
+            .map(|n| n.id.clone())
+            .collect();
+
+        let thought_id = generate_thought_id();
+        let thought = Thought::new(
+            &thought_id,
+            &session.id,
+            format!("Graph import: {} nodes, {} edges", nodes.len(), edges.len()),
+            "graph_import",
+            0.5,
+        );
+
+        // One transaction: the whole import lands or none of it, so a failed
+        // edge can't leave half a graph behind.
+        self.storage
+            .save_graph_batch(&thought, &stored_nodes, &stored_edges)
+            .await
+            .map_err(|e| ModeError::ApiUnavailable {
+                message: format!("Failed to persist imported graph: {e}"),
+            })?;
+
+        Ok(ImportResponse::new(
+            thought_id,
+            session.id,
+            nodes,
+            edges.len(),
+            roots,
+        ))
     }
 
-    /// Build a JSON representation of the graph from storage for the state operation.
-    async fn build_graph_state_from_storage(&self, session_id: &str) -> Result<String, ModeError> {
+    /// Snapshot the session's entire stored graph (nodes + edges) so it can be
+    /// reinstated later with [`Self::restore_snapshot`] — typically taken just
+    /// before an aggressive prune.
+    ///
+    /// Deterministic: serializes the stored graph into a checkpoint row
+    /// without an API call. The snapshot is immutable; graph changes made
+    /// after it do not touch it, and one snapshot can be restored repeatedly.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModeError`] if no graph is stored for the session or
+    /// persistence fails.
+    pub async fn snapshot(&self, session_id: &str) -> Result<SnapshotResponse, ModeError> {
+        let session = self
+            .get_or_create_session(Some(session_id.to_string()))
+            .await?;
+
         let nodes = self
             .storage
-            .get_graph_nodes(session_id)
+            .get_graph_nodes(&session.id)
             .await
             .map_err(|e| ModeError::ApiUnavailable {
                 message: format!("Failed to get graph nodes: {e}"),
             })?;
 
+        if nodes.is_empty() {
+            return Err(ModeError::InvalidValue {
+                field: "session_id".to_string(),
+                reason: format!(
+                    "No graph stored for session '{session_id}' — run init or import first"
+                ),
+            });
+        }
+
         let edges = self
             .storage
-            .get_graph_edges(session_id)
+            .get_graph_edges(&session.id)
             .await
             .map_err(|e| ModeError::ApiUnavailable {
                 message: format!("Failed to get graph edges: {e}"),
             })?;
 
-        // Build a JSON representation of the graph, stripping the session
-        // namespace prefix so the model sees the original short node IDs.
-        let prefix = format!("{session_id}::");
-        let strip = |id: &str| -> String { id.strip_prefix(&prefix).unwrap_or(id).to_string() };
-
-        let nodes_json: Vec<serde_json::Value> = nodes
-            .iter()
-            .map(|n| {
-                serde_json::json!({
-                    "id": strip(&n.id),
-                    "content": n.content,
-                    "score": n.score,
-                    "node_type": n.node_type.as_str(),
-                    "is_terminal": n.is_terminal
-                })
-            })
-            .collect();
-
-        let edges_json: Vec<serde_json::Value> = edges
-            .iter()
-            .map(|e| {
-                serde_json::json!({
-                    "from": strip(&e.from_node_id),
-                    "to": strip(&e.to_node_id),
-                    "type": e.edge_type.as_str()
-                })
-            })
-            .collect();
+        let state = serde_json::json!({ "nodes": nodes, "edges": edges });
+        let state_str = serde_json::to_string(&state).map_err(|e| ModeError::JsonParseFailed {
+            message: format!("Failed to serialize graph snapshot: {e}"),
+        })?;
 
-        let graph = serde_json::json!({
-            "nodes": nodes_json,
-            "edges": edges_json
-        });
+        let snapshot_id = generate_checkpoint_id();
+        let checkpoint = StoredCheckpoint::new(
+            &snapshot_id,
+            &session.id,
+            format!(
+                "graph-snapshot ({} nodes, {} edges)",
+                nodes.len(),
+                edges.len()
+            ),
+            state_str,
+        );
+        self.storage
+            .save_checkpoint(&checkpoint)
+            .await
+            .map_err(|e| ModeError::ApiUnavailable {
+                message: format!("Failed to persist graph snapshot: {e}"),
+            })?;
 
-        Ok(graph.to_string())
+        Ok(SnapshotResponse::new(
+            snapshot_id,
+            session.id,
+            nodes.len(),
+            edges.len(),
+        ))
     }
 
-    /// Namespace a model-supplied node ID with its session.
+    /// Reinstate a graph snapshot taken with [`Self::snapshot`].
     ///
-    /// The `graph_nodes` primary key is global, but models reuse short IDs
-    /// (`root`, `c1`, ...) across sessions. Prefixing with the session ID keeps
-    /// nodes from different sessions from colliding in storage.
-    fn namespaced_id(session_id: &str, node_id: &str) -> String {
-        format!("{session_id}::{node_id}")
-    }
-
-    /// Persist a graph node. Storage failures are logged, not propagated, so a
-    /// write error never discards a reasoning result already returned to the
-    /// caller. Returns `true` on success.
-    async fn persist_node(
+    /// Restore **replaces** rather than forks: the session's current graph —
+    /// including any nodes added after the snapshot — is deleted and the
+    /// snapshotted nodes and edges are written back, so the graph is exactly
+    /// as captured. The snapshot row itself is kept, so a restore can be
+    /// repeated (and re-restored after further experimentation).
+    ///
+    /// The replace is not transactional: current edges and nodes are deleted
+    /// before the snapshot is reinstated. A failure mid-restore can leave a
+    /// partial graph, but the snapshot still exists — rerunning the restore
+    /// recovers it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModeError`] if the snapshot does not exist, belongs to a
+    /// different session, is unreadable, or a storage operation fails.
+    pub async fn restore_snapshot(
         &self,
         session_id: &str,
-        node_id: &str,
-        content: &str,
-        score: f64,
-        node_type: GraphNodeType,
-    ) -> bool {
-        let node = StoredGraphNode::new(
-            Self::namespaced_id(session_id, node_id),
-            session_id,
-            content,
-        )
-        .with_score(score)
-        .with_node_type(node_type);
+        snapshot_id: &str,
+    ) -> Result<RestoreSnapshotResponse, ModeError> {
+        let checkpoint = self
+            .storage
+            .get_checkpoint(snapshot_id)
+            .await
+            .map_err(|e| ModeError::ApiUnavailable {
+                message: format!("Failed to get graph snapshot: {e}"),
+            })?
+            .ok_or_else(|| ModeError::InvalidValue {
+                field: "snapshot_id".to_string(),
+                reason: format!("Graph snapshot '{snapshot_id}' not found"),
+            })?;
 
-        if let Err(e) = self.storage.save_graph_node(&node).await {
-            tracing::warn!(error = %e, node_id, "Graph node persistence failed");
-            return false;
+        if checkpoint.session_id != session_id {
+            return Err(ModeError::InvalidValue {
+                field: "snapshot_id".to_string(),
+                reason: format!(
+                    "Graph snapshot '{snapshot_id}' belongs to session '{}', not '{session_id}'",
+                    checkpoint.session_id
+                ),
+            });
         }
-        true
-    }
 
-    /// Persist a directed graph edge between two (namespaced) nodes. Failures are
-    /// logged, not propagated. Returns `true` on success.
-    async fn persist_edge(
-        &self,
-        session_id: &str,
-        from_id: &str,
-        to_id: &str,
-        edge_type: GraphEdgeType,
-    ) -> bool {
-        let edge = StoredGraphEdge::new(
-            Self::namespaced_id(session_id, &format!("{from_id}->{to_id}")),
-            session_id,
-            Self::namespaced_id(session_id, from_id),
-            Self::namespaced_id(session_id, to_id),
+        let state: serde_json::Value =
+            serde_json::from_str(&checkpoint.state).map_err(|e| ModeError::JsonParseFailed {
+                message: format!("Graph snapshot '{snapshot_id}' is unreadable: {e}"),
+            })?;
+        let nodes: Vec<StoredGraphNode> = serde_json::from_value(
+            state.get("nodes").cloned().unwrap_or_default(),
         )
-        .with_edge_type(edge_type);
+        .map_err(|e| ModeError::JsonParseFailed {
+            message: format!("Graph snapshot '{snapshot_id}' has malformed nodes: {e}"),
+        })?;
+        let edges: Vec<StoredGraphEdge> = serde_json::from_value(
+            state.get("edges").cloned().unwrap_or_default(),
+        )
+        .map_err(|e| ModeError::JsonParseFailed {
+            message: format!("Graph snapshot '{snapshot_id}' has malformed edges: {e}"),
+        })?;
+
+        let storage_err = |action: &str| {
+            let action = action.to_string();
+            move |e: crate::error::StorageError| ModeError::ApiUnavailable {
+                message: format!("{action}: {e}"),
+            }
+        };
 
-        if let Err(e) = self.storage.save_graph_edge(&edge).await {
-            tracing::warn!(error = %e, from = from_id, to = to_id, "Graph edge persistence failed");
-            return false;
+        // Replace: clear the live graph first (edges before nodes, so no edge
+        // ever references a deleted node), then write the snapshot back.
+        let current_edges = self
+            .storage
+            .get_graph_edges(session_id)
+            .await
+            .map_err(storage_err("Failed to get graph edges"))?;
+        for edge in &current_edges {
+            self.storage
+                .delete_graph_edge(&edge.id)
+                .await
+                .map_err(storage_err("Failed to delete graph edge"))?;
         }
-        true
-    }
-
-    /// Update a node's score in storage. Failures are logged, not propagated.
-    /// Returns `true` on success.
-    async fn persist_score(&self, session_id: &str, node_id: &str, score: f64) -> bool {
-        let storage_id = Self::namespaced_id(session_id, node_id);
-        if let Err(e) = self
+        let current_nodes = self
             .storage
-            .update_graph_node_score(&storage_id, score)
+            .get_graph_nodes(session_id)
             .await
-        {
-            tracing::warn!(error = %e, node_id, "Graph node score update failed");
-            return false;
+            .map_err(storage_err("Failed to get graph nodes"))?;
+        for node in &current_nodes {
+            self.storage
+                .delete_graph_node(&node.id)
+                .await
+                .map_err(storage_err("Failed to delete graph node"))?;
         }
-        true
-    }
-}
 
-impl<S, C> std::fmt::Debug for GraphMode<S, C>
-where
-    S: StorageTrait,
-    C: AnthropicClientTrait,
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("GraphMode")
-            .field("storage", &"<StorageTrait>")
-            .field("client", &"<AnthropicClientTrait>")
-            .finish()
+        for node in &nodes {
+            self.storage
+                .save_graph_node(node)
+                .await
+                .map_err(storage_err("Failed to restore graph node"))?;
+        }
+        for edge in &edges {
+            self.storage
+                .save_graph_edge(edge)
+                .await
+                .map_err(storage_err("Failed to restore graph edge"))?;
+        }
+
+        Ok(RestoreSnapshotResponse::new(
+            session_id,
+            snapshot_id,
+            nodes.len(),
+            edges.len(),
+            current_nodes.len(),
+        ))
     }
-}
 
-#[cfg(test)]
-#[allow(
-    clippy::unwrap_used,
-    clippy::expect_used,
-    clippy::panic,
-    clippy::float_cmp,
-    clippy::approx_constant,
-    clippy::unreadable_literal
-)]
-mod tests {
-    use super::*;
-    use crate::error::StorageError;
-    use crate::traits::{CompletionResponse, MockAnthropicClientTrait, MockStorageTrait, Usage};
+    /// Merge two sessions' stored graphs into a target session.
+    ///
+    /// Nodes and edges from both sources are copied — the sources are left
+    /// untouched — with ids remapped into the target's namespace, preserving
+    /// scores, terminal flags, and provenance (parent ids follow the remap).
+    /// When `dedup_threshold` is set, a node whose content is at least that
+    /// similar (the same Jaccard word-overlap measure thought dedup uses) to
+    /// an already-merged node is folded into it: its edges follow the fold
+    /// and the kept node takes the higher score. Cross-links that would
+    /// duplicate an existing edge or close a cycle after a fold are omitted
+    /// and reported rather than written, so the merged graph stays acyclic
+    /// when the sources were.
+    ///
+    /// Deterministic: no API call; persistence is one transaction, so either
+    /// the whole combined graph lands in the target or none of it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModeError`] if a source has no stored graph, the target is
+    /// one of the sources, `dedup_threshold` is outside `[0.0, 1.0]`, the
+    /// target would exceed the node limit, or storage fails.
+    pub async fn merge_graphs(
+        &self,
+        source_a: &str,
+        source_b: &str,
+        target_session_id: Option<String>,
+        dedup_threshold: Option<f64>,
+    ) -> Result<MergeGraphsResponse, ModeError> {
+        if let Some(threshold) = dedup_threshold {
+            if !(0.0..=1.0).contains(&threshold) {
+                return Err(ModeError::InvalidValue {
+                    field: "dedup_threshold".to_string(),
+                    reason: format!("must be between 0.0 and 1.0, got {threshold}"),
+                });
+            }
+        }
+        // Checked before the target session row is created, so a bad target
+        // does not leave an empty session behind.
+        if let Some(target) = target_session_id.as_deref() {
+            if target == source_a || target == source_b {
+                return Err(ModeError::InvalidValue {
+                    field: "session_id".to_string(),
+                    reason: "target session must differ from both merge sources".to_string(),
+                });
+            }
+        }
 
-    /// Register permissive (`times(..)`) expectations for the best-effort graph
-    /// persistence writes so happy-path tests, which focus on API parsing, don't
-    /// panic when an operation also persists nodes/edges/scores.
-    fn expect_graph_writes(mock: &mut MockStorageTrait) {
-        mock.expect_save_graph_node()
-            .times(..)
-            .returning(|_| Ok(()));
-        mock.expect_save_graph_edge()
-            .times(..)
-            .returning(|_| Ok(()));
-        mock.expect_update_graph_node_score()
-            .times(..)
-            .returning(|_, _| Ok(()));
-        mock.expect_delete_graph_node()
-            .times(..)
-            .returning(|_| Ok(()));
-    }
+        let mut source_nodes = Vec::new();
+        let mut source_edges = Vec::new();
+        for source in [source_a, source_b] {
+            let nodes = self.storage.get_graph_nodes(source).await.map_err(|e| {
+                ModeError::ApiUnavailable {
+                    message: format!("Failed to get graph nodes: {e}"),
+                }
+            })?;
+            if nodes.is_empty() {
+                return Err(ModeError::InvalidValue {
+                    field: "session_id".to_string(),
+                    reason: format!(
+                        "No graph stored for session '{source}' — run init or import first"
+                    ),
+                });
+            }
+            let edges = self.storage.get_graph_edges(source).await.map_err(|e| {
+                ModeError::ApiUnavailable {
+                    message: format!("Failed to get graph edges: {e}"),
+                }
+            })?;
+            source_nodes.extend(nodes);
+            source_edges.extend(edges);
+        }
 
-    fn mock_init_response() -> String {
-        r#"{
-            "root": {"id": "root", "content": "Main topic", "score": 0.5, "type": "root"},
-            "expansion_directions": [
-                {"direction": "Direction A", "potential": 0.7}
-            ],
-            "graph_metadata": {"complexity": "medium", "estimated_depth": 3}
-        }"#
-        .to_string()
-    }
+        let session = self.get_or_create_session(target_session_id).await?;
+        self.ensure_node_capacity(&session.id).await?;
+
+        // Pass 1: decide each source node's id in the target. Source ids are
+        // globally unique, so the namespaced remap cannot collide; a near-
+        // duplicate (dedup enabled) folds onto the first matching kept node.
+        let mut kept: Vec<StoredGraphNode> = Vec::with_capacity(source_nodes.len());
+        let mut kept_parents: Vec<Vec<String>> = Vec::with_capacity(source_nodes.len());
+        let mut id_map: HashMap<String, String> = HashMap::new();
+        let mut deduplicated = Vec::new();
+        for node in &source_nodes {
+            let duplicate_of = dedup_threshold.and_then(|threshold| {
+                kept.iter().position(|existing| {
+                    content_similarity(&existing.content, &node.content) >= threshold
+                })
+            });
+            if let Some(index) = duplicate_of {
+                let existing = &mut kept[index];
+                existing.score = match (existing.score, node.score) {
+                    (Some(a), Some(b)) => Some(a.max(b)),
+                    (a, b) => a.or(b),
+                };
+                existing.is_terminal = existing.is_terminal || node.is_terminal;
+                deduplicated.push(MergedDuplicate {
+                    dropped_id: node.id.clone(),
+                    kept_id: existing.id.clone(),
+                });
+                id_map.insert(node.id.clone(), existing.id.clone());
+            } else {
+                let new_id = Self::namespaced_id(&session.id, &node.id);
+                let mut merged = StoredGraphNode::new(&new_id, &session.id, &node.content);
+                merged.node_type = node.node_type;
+                merged.score = node.score;
+                merged.is_terminal = node.is_terminal;
+                merged.origin = node.origin;
+                id_map.insert(node.id.clone(), new_id);
+                kept_parents.push(node.parent_ids.clone());
+                kept.push(merged);
+            }
+        }
+        // Pass 2: provenance parents follow the node remap (a parent that was
+        // folded away maps to the node it was folded into).
+        for (node, parents) in kept.iter_mut().zip(&kept_parents) {
+            node.parent_ids = parents
+                .iter()
+                .filter_map(|parent| id_map.get(parent).cloned())
+                .collect();
+        }
 
-    fn mock_generate_response() -> String {
-        r#"{
-            "parent_id": "root",
-            "children": [
-                {"id": "c1", "content": "Child 1", "score": 0.6, "type": "reasoning", "relationship": "elaborates"}
-            ],
-            "generation_notes": "Generated reasoning children"
-        }"#
-        .to_string()
-    }
+        // Edges: endpoints follow the remap. A fold can make two edges land
+        // on the same endpoint pair, point a node at itself, or close a cycle
+        // between the sources — those are omitted and reported, keeping the
+        // merged graph acyclic.
+        let mut merged_edges: Vec<StoredGraphEdge> = Vec::with_capacity(source_edges.len());
+        let mut seen: HashSet<(String, String)> = HashSet::new();
+        let mut adjacency: HashMap<String, Vec<String>> = HashMap::new();
+        let mut omitted_edges = Vec::new();
+        for edge in &source_edges {
+            let (Some(from), Some(to)) =
+                (id_map.get(&edge.from_node_id), id_map.get(&edge.to_node_id))
+            else {
+                // Dangling endpoint in the source; nothing to link.
+                continue;
+            };
+            if from == to
+                || !seen.insert((from.clone(), to.clone()))
+                || Self::reaches(&adjacency, to, from)
+            {
+                omitted_edges.push(OmittedEdge {
+                    from: from.clone(),
+                    to: to.clone(),
+                });
+                continue;
+            }
+            adjacency.entry(from.clone()).or_default().push(to.clone());
+            let mut merged = StoredGraphEdge::new(
+                Self::namespaced_id(&session.id, &edge.id),
+                &session.id,
+                from,
+                to,
+            );
+            merged.edge_type = edge.edge_type;
+            merged_edges.push(merged);
+        }
 
-    fn mock_score_response() -> String {
-        r#"{
-            "node_id": "c1",
-            "scores": {"relevance": 0.8, "coherence": 0.7, "depth": 0.6, "novelty": 0.5, "overall": 0.65},
-            "assessment": {"strengths": ["Clear"], "weaknesses": ["Shallow"], "recommendation": "expand"}
-        }"#
-        .to_string()
-    }
+        let thought_id = generate_thought_id();
+        let thought = Thought::new(
+            &thought_id,
+            &session.id,
+            format!(
+                "Graph merge of '{source_a}' + '{source_b}': {} nodes, {} edges \
+                 ({} deduplicated, {} edge(s) omitted)",
+                kept.len(),
+                merged_edges.len(),
+                deduplicated.len(),
+                omitted_edges.len(),
+            ),
+            "graph_merge",
+            0.5,
+        );
+        self.storage
+            .save_graph_batch(&thought, &kept, &merged_edges)
+            .await
+            .map_err(|e| ModeError::ApiUnavailable {
+                message: format!("Failed to persist merged graph: {e}"),
+            })?;
 
-    fn mock_aggregate_response() -> String {
-        r#"{
-            "input_node_ids": ["c1", "c2"],
-            "synthesis": {"id": "s1", "content": "Synthesis", "score": 0.75, "type": "synthesis"},
-            "integration_notes": {"common_themes": ["Theme"], "complementary_aspects": ["Aspect"], "resolved_contradictions": []}
-        }"#
-        .to_string()
+        Ok(MergeGraphsResponse::new(
+            thought_id,
+            session.id,
+            vec![source_a.to_string(), source_b.to_string()],
+            kept.len(),
+            merged_edges.len(),
+            deduplicated,
+            omitted_edges,
+        ))
     }
 
-    fn mock_refine_response() -> String {
-        r#"{
-            "original_node_id": "c1",
-            "critique": {"issues": ["Issue"], "missing_elements": ["Missing"], "unclear_aspects": []},
-            "refined_node": {"id": "r1", "content": "Refined", "score": 0.8, "type": "refined"},
-            "improvement_delta": 0.15
-        }"#
-        .to_string()
+    /// Whether `target` is reachable from `start` in the adjacency map.
+    fn reaches(adjacency: &HashMap<String, Vec<String>>, start: &str, target: &str) -> bool {
+        let mut stack = vec![start];
+        let mut visited: HashSet<&str> = HashSet::new();
+        while let Some(current) = stack.pop() {
+            if current == target {
+                return true;
+            }
+            if visited.insert(current) {
+                if let Some(next) = adjacency.get(current) {
+                    stack.extend(next.iter().map(String::as_str));
+                }
+            }
+        }
+        false
     }
 
-    fn mock_prune_response() -> String {
-        r#"{
-            "prune_candidates": [
-                {"node_id": "bad_node", "reason": "low_score", "confidence": 0.8, "impact": "minor"}
-            ],
-            "preserve_nodes": ["root", "c1"],
-            "pruning_strategy": "Remove low-quality nodes"
-        }"#
-        .to_string()
-    }
+    /// Convert the stored graph into a spanning forest for tree-only renderers.
+    ///
+    /// BFS from each root (a node with no incoming edge, in stored order): the
+    /// first edge to reach a node becomes its tree edge, and every other
+    /// stored edge is reported in `omitted_edges` so the non-tree
+    /// relationships are not silently lost. Multiple roots yield a forest;
+    /// a node reachable only through a cycle is promoted to a root of its own
+    /// tree rather than dropped.
+    ///
+    /// Deterministic: reads the stored graph without an API call and persists
+    /// nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModeError`] if no graph is stored for the session or reading
+    /// it fails.
+    pub async fn tree_view(&self, session_id: &str) -> Result<TreeViewResponse, ModeError> {
+        let session = self
+            .get_or_create_session(Some(session_id.to_string()))
+            .await?;
 
-    fn mock_finalize_response() -> String {
-        r#"{
-            "best_paths": [
-                {"path": ["root", "c1"], "path_quality": 0.85, "key_insight": "Main insight"}
-            ],
-            "conclusions": [
-                {"conclusion": "Key conclusion", "confidence": 0.8, "supporting_nodes": ["c1"]}
-            ],
-            "final_synthesis": "Final summary",
-            "session_quality": {"depth_achieved": 0.75, "breadth_achieved": 0.8, "coherence": 0.85, "overall": 0.8}
-        }"#
-        .to_string()
+        let nodes = self
+            .storage
+            .get_graph_nodes(&session.id)
+            .await
+            .map_err(|e| ModeError::ApiUnavailable {
+                message: format!("Failed to get graph nodes: {e}"),
+            })?;
+
+        if nodes.is_empty() {
+            return Err(ModeError::InvalidValue {
+                field: "session_id".to_string(),
+                reason: format!(
+                    "No graph stored for session '{session_id}' — run init or import first"
+                ),
+            });
+        }
+
+        let edges = self
+            .storage
+            .get_graph_edges(&session.id)
+            .await
+            .map_err(|e| ModeError::ApiUnavailable {
+                message: format!("Failed to get graph edges: {e}"),
+            })?;
+
+        let prefix = format!("{}::", session.id);
+        let (roots, tree_nodes, omitted_edges) = Self::spanning_forest(&nodes, &edges, &prefix);
+
+        Ok(TreeViewResponse::new(
+            session.id,
+            roots,
+            tree_nodes,
+            omitted_edges,
+        ))
+    }
+
+    /// Build a spanning forest over stored nodes and edges via BFS.
+    ///
+    /// Returns `(roots, nodes, omitted_edges)` with the session namespace
+    /// stripped from every ID. Edges whose endpoint is no longer stored
+    /// (e.g. already pruned) are skipped entirely, matching how centrality
+    /// treats them.
+    fn spanning_forest(
+        nodes: &[StoredGraphNode],
+        edges: &[StoredGraphEdge],
+        prefix: &str,
+    ) -> (Vec<String>, Vec<TreeViewNode>, Vec<OmittedEdge>) {
+        let strip = |id: &str| -> String { id.strip_prefix(prefix).unwrap_or(id).to_string() };
+
+        let node_by_id: HashMap<&str, &StoredGraphNode> =
+            nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+        let mut outgoing: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (index, edge) in edges.iter().enumerate() {
+            if node_by_id.contains_key(edge.from_node_id.as_str())
+                && node_by_id.contains_key(edge.to_node_id.as_str())
+            {
+                outgoing
+                    .entry(edge.from_node_id.as_str())
+                    .or_default()
+                    .push(index);
+            }
+        }
+        let has_incoming: std::collections::HashSet<&str> = edges
+            .iter()
+            .filter(|e| node_by_id.contains_key(e.from_node_id.as_str()))
+            .map(|e| e.to_node_id.as_str())
+            .collect();
+
+        let mut visited: std::collections::HashSet<&str> = std::collections::HashSet::new();
+        let mut tree_edges: std::collections::HashSet<usize> = std::collections::HashSet::new();
+        let mut roots = Vec::new();
+        let mut tree_nodes = Vec::new();
+
+        // Natural roots first, then anything left unvisited (a component only
+        // reachable through a cycle) promoted to a root of its own tree.
+        let seeds: Vec<&StoredGraphNode> = nodes
+            .iter()
+            .filter(|n| !has_incoming.contains(n.id.as_str()))
+            .chain(nodes.iter())
+            .collect();
+
+        for seed in seeds {
+            if !visited.insert(seed.id.as_str()) {
+                continue;
+            }
+            roots.push(strip(&seed.id));
+
+            let mut queue: std::collections::VecDeque<(&StoredGraphNode, Option<String>, u32)> =
+                std::collections::VecDeque::new();
+            queue.push_back((seed, None, 0));
+            while let Some((node, parent_id, depth)) = queue.pop_front() {
+                let node_id = strip(&node.id);
+                for &index in outgoing.get(node.id.as_str()).into_iter().flatten() {
+                    let target = edges[index].to_node_id.as_str();
+                    if visited.insert(target) {
+                        tree_edges.insert(index);
+                        if let Some(child) = node_by_id.get(target) {
+                            queue.push_back((child, Some(node_id.clone()), depth + 1));
+                        }
+                    }
+                }
+                tree_nodes.push(TreeViewNode {
+                    id: node_id,
+                    content: node.content.clone(),
+                    score: node.score,
+                    parent_id,
+                    depth,
+                });
+            }
+        }
+
+        let omitted_edges = edges
+            .iter()
+            .enumerate()
+            .filter(|(index, edge)| {
+                !tree_edges.contains(index)
+                    && node_by_id.contains_key(edge.from_node_id.as_str())
+                    && node_by_id.contains_key(edge.to_node_id.as_str())
+            })
+            .map(|(_, edge)| OmittedEdge {
+                from: strip(&edge.from_node_id),
+                to: strip(&edge.to_node_id),
+            })
+            .collect();
+
+        (roots, tree_nodes, omitted_edges)
+    }
+
+    /// Auto-advance the stored graph: expand its best frontier nodes in one call.
+    ///
+    /// Reads the persisted graph, picks the highest-scoring non-terminal nodes
+    /// with no outgoing edge (the frontier), and runs [`Self::generate`] on
+    /// each — at most [`MAX_CONCURRENT_ADVANCE`] completions in flight.
+    /// Children are persisted exactly as a manual `generate` would persist
+    /// them; failed writes are counted, not propagated.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_id` - Session whose stored graph to advance
+    /// * `max_nodes` - Frontier nodes to expand (clamped to 1..=[`MAX_ADVANCE_NODES`])
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModeError`] if no graph is stored for the session, the
+    /// frontier is empty (every leaf is terminal), an API call fails, or
+    /// parsing fails.
+    pub async fn advance(
+        &self,
+        session_id: &str,
+        max_nodes: usize,
+    ) -> Result<AdvanceResponse, ModeError> {
+        self.advance_with_observer(session_id, max_nodes, None)
+            .await
+    }
+
+    /// [`Self::advance`] with an observer called for each expanded frontier as
+    /// it completes, rather than only after every expansion finishes.
+    ///
+    /// Expansions run concurrently, so the observer sees frontiers in
+    /// completion order — a fast node reports before a slow higher-ranked one.
+    /// Each frontier's children are already persisted when the observer fires.
+    /// The returned response is unchanged: frontiers in ranked order,
+    /// independent of completion order.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::advance`]. When an expansion fails, the remaining
+    /// in-flight expansions still drain (and report) before the first error
+    /// is returned.
+    pub async fn advance_with_observer(
+        &self,
+        session_id: &str,
+        max_nodes: usize,
+        observer: Option<FrontierObserver<'_>>,
+    ) -> Result<AdvanceResponse, ModeError> {
+        let session = self
+            .get_or_create_session(Some(session_id.to_string()))
+            .await?;
+
+        let nodes = self
+            .storage
+            .get_graph_nodes(session_id)
+            .await
+            .map_err(|e| ModeError::ApiUnavailable {
+                message: format!("Failed to get graph nodes: {e}"),
+            })?;
+
+        if nodes.is_empty() {
+            return Err(ModeError::InvalidValue {
+                field: "session_id".to_string(),
+                reason: format!(
+                    "No graph stored for session '{session_id}' — run init or import first"
+                ),
+            });
+        }
+
+        let edges = self
+            .storage
+            .get_graph_edges(session_id)
+            .await
+            .map_err(|e| ModeError::ApiUnavailable {
+                message: format!("Failed to get graph edges: {e}"),
+            })?;
+
+        // Frontier: non-terminal nodes with no outgoing edge, best score first.
+        // Stored IDs carry the session namespace; strip it so generate's
+        // node_id lookup (which re-namespaces) resolves them.
+        let prefix = format!("{session_id}::");
+        let mut frontier: Vec<(String, f64)> = nodes
+            .iter()
+            .filter(|n| !n.is_terminal && edges.iter().all(|e| e.from_node_id != n.id))
+            .map(|n| {
+                let short = n.id.strip_prefix(&prefix).unwrap_or(&n.id).to_string();
+                (short, n.score.unwrap_or(0.5))
+            })
+            .collect();
+
+        if frontier.is_empty() {
+            return Err(ModeError::InvalidValue {
+                field: "session_id".to_string(),
+                reason: "No non-terminal frontier nodes to expand — the graph is complete"
+                    .to_string(),
+            });
+        }
+
+        frontier.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        frontier.truncate(max_nodes.clamp(1, MAX_ADVANCE_NODES));
+        let selected: Vec<String> = frontier.into_iter().map(|(node_id, _)| node_id).collect();
+
+        // `buffer_unordered` so the observer hears about each frontier the
+        // moment it completes; the response is re-ranked below so callers
+        // still get deterministic order. Built with a loop rather than a
+        // closure: a closure borrowing the frontier here trips rustc's
+        // "FnOnce is not general enough" lifetime bug once the future flows
+        // through the server's tool macro.
+        let mut expansions = Vec::with_capacity(selected.len());
+        for node_id in &selected {
+            expansions.push(self.advance_one(session_id, node_id));
+        }
+        let mut generations =
+            futures_util::stream::iter(expansions).buffer_unordered(MAX_CONCURRENT_ADVANCE);
+
+        let mut expanded = Vec::with_capacity(selected.len());
+        let mut nodes_added: u32 = 0;
+        let mut persistence_failures: u32 = 0;
+        let mut first_error: Option<ModeError> = None;
+        while let Some((node_id, result)) = generations.next().await {
+            match result {
+                Ok(generation) => {
+                    nodes_added += generation.children.len() as u32;
+                    persistence_failures += generation.persistence_failures;
+                    let frontier = ExpandedFrontier {
+                        node_id,
+                        children: generation.children,
+                    };
+                    if let Some(observer) = observer {
+                        observer(&frontier);
+                    }
+                    expanded.push(frontier);
+                }
+                Err(e) => {
+                    if first_error.is_none() {
+                        first_error = Some(e);
+                    }
+                }
+            }
+        }
+        if let Some(e) = first_error {
+            return Err(e);
+        }
+
+        // Restore the ranked order for the response: completion order is an
+        // observer-only detail.
+        expanded.sort_by_key(|f| {
+            selected
+                .iter()
+                .position(|id| id == &f.node_id)
+                .unwrap_or(usize::MAX)
+        });
+
+        let thought_id = generate_thought_id();
+        let thought = Thought::new(
+            &thought_id,
+            &session.id,
+            format!(
+                "Graph advance: expanded {} frontier node(s), {nodes_added} children added",
+                expanded.len()
+            ),
+            "graph_advance",
+            0.7,
+        );
+        if let Err(e) = self.storage.save_thought(&thought).await {
+            tracing::warn!(error = %e, "Storage write failed — reasoning result preserved, thought not persisted");
+        }
+
+        Ok(AdvanceResponse::new(
+            thought_id,
+            session.id,
+            expanded,
+            nodes_added,
+            persistence_failures,
+        ))
+    }
+
+    /// Expand a single frontier node for [`Self::advance`], pairing the result
+    /// with the node it came from so failures stay attributable.
+    async fn advance_one(
+        &self,
+        session_id: &str,
+        node_id: &str,
+    ) -> (String, Result<GenerateResponse, ModeError>) {
+        let result = self
+            .generate(None, Some(node_id), Some(session_id.to_string()))
+            .await;
+        (node_id.to_string(), result)
+    }
+
+    // ========================================================================
+    // Private Helpers
+    // ========================================================================
+
+    async fn get_or_create_session(
+        &self,
+        session_id: Option<String>,
+    ) -> Result<Session, ModeError> {
+        crate::modes::enforce_session_required(
+            self.flags.require_session_id,
+            "graph",
+            session_id.as_deref(),
+        )?;
+        self.storage
+            .get_or_create_session(session_id)
+            .await
+            .map_err(|e| ModeError::ApiUnavailable {
+                message: format!("Failed to get or create session: {e}"),
+            })
+    }
+
+    /// Resolve content from either direct content or node_id lookup.
+    ///
+    /// If content is provided and non-empty, uses it directly.
+    /// If node_id is provided, looks up the content from storage.
+    /// Returns an error if neither is provided.
+    async fn resolve_content(
+        &self,
+        content: Option<&str>,
+        node_id: Option<&str>,
+        session_id: &str,
+    ) -> Result<String, ModeError> {
+        // Check for direct content first
+        if let Some(c) = content {
+            if !c.trim().is_empty() {
+                return Ok(c.to_string());
+            }
+        }
+
+        // Try to look up by node_id (stored under its session-namespaced key)
+        if let Some(nid) = node_id {
+            let storage_id = Self::namespaced_id(session_id, nid);
+            let node = self
+                .storage
+                .get_graph_node(&storage_id)
+                .await
+                .map_err(|e| ModeError::ApiUnavailable {
+                    message: format!("Failed to get graph node: {e}"),
+                })?
+                .ok_or_else(|| ModeError::InvalidValue {
+                    field: "node_id".to_string(),
+                    reason: format!("Node '{nid}' not found"),
+                })?;
+            return Ok(node.content);
+        }
+
+        // Neither provided
+        Err(ModeError::MissingField {
+            field: "content or node_id".to_string(),
+        })
+    }
+
+    /// Build a JSON representation of the graph from storage for the state operation.
+    async fn build_graph_state_from_storage(&self, session_id: &str) -> Result<String, ModeError> {
+        let nodes = self
+            .storage
+            .get_graph_nodes(session_id)
+            .await
+            .map_err(|e| ModeError::ApiUnavailable {
+                message: format!("Failed to get graph nodes: {e}"),
+            })?;
+
+        let edges = self
+            .storage
+            .get_graph_edges(session_id)
+            .await
+            .map_err(|e| ModeError::ApiUnavailable {
+                message: format!("Failed to get graph edges: {e}"),
+            })?;
+
+        // Build a JSON representation of the graph, stripping the session
+        // namespace prefix so the model sees the original short node IDs.
+        let prefix = format!("{session_id}::");
+        let strip = |id: &str| -> String { id.strip_prefix(&prefix).unwrap_or(id).to_string() };
+
+        let nodes_json: Vec<serde_json::Value> = nodes
+            .iter()
+            .map(|n| {
+                serde_json::json!({
+                    "id": strip(&n.id),
+                    "content": n.content,
+                    "score": n.score,
+                    "node_type": n.node_type.as_str(),
+                    "is_terminal": n.is_terminal
+                })
+            })
+            .collect();
+
+        let edges_json: Vec<serde_json::Value> = edges
+            .iter()
+            .map(|e| {
+                serde_json::json!({
+                    "from": strip(&e.from_node_id),
+                    "to": strip(&e.to_node_id),
+                    "type": e.edge_type.as_str()
+                })
+            })
+            .collect();
+
+        let graph = serde_json::json!({
+            "nodes": nodes_json,
+            "edges": edges_json
+        });
+
+        Ok(graph.to_string())
+    }
+
+    /// Compute structural centrality for every stored node in a session.
+    ///
+    /// Degree centrality over the stored edges, treated as undirected: a
+    /// node's degree divided by the maximum possible degree (`n - 1`), so
+    /// scores are 0.0-1.0 and comparable across graphs. A hub every branch
+    /// passes through scores near 1.0; leaves score low; a node in a
+    /// disconnected component is scored within the same scale (an isolated
+    /// node scores 0.0). Keys are the original short node IDs with the
+    /// session namespace stripped.
+    ///
+    /// Low centrality combined with a low quality score marks a prune
+    /// candidate — `prune` feeds these scores into its prompt and `state`
+    /// surfaces them in its response.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModeError::ApiUnavailable`] if reading nodes or edges from
+    /// storage fails.
+    pub async fn compute_centrality(
+        &self,
+        session_id: &str,
+    ) -> Result<HashMap<String, f64>, ModeError> {
+        let nodes = self
+            .storage
+            .get_graph_nodes(session_id)
+            .await
+            .map_err(|e| ModeError::ApiUnavailable {
+                message: format!("Failed to get graph nodes: {e}"),
+            })?;
+
+        let edges = self
+            .storage
+            .get_graph_edges(session_id)
+            .await
+            .map_err(|e| ModeError::ApiUnavailable {
+                message: format!("Failed to get graph edges: {e}"),
+            })?;
+
+        let prefix = format!("{session_id}::");
+        let strip = |id: &str| -> String { id.strip_prefix(&prefix).unwrap_or(id).to_string() };
+
+        let mut degrees: HashMap<String, f64> = nodes.iter().map(|n| (strip(&n.id), 0.0)).collect();
+        for edge in &edges {
+            for endpoint in [strip(&edge.from_node_id), strip(&edge.to_node_id)] {
+                // Edges to nodes no longer stored (e.g. already pruned) are skipped
+                // rather than resurrected as zero-content entries.
+                if let Some(degree) = degrees.get_mut(&endpoint) {
+                    *degree += 1.0;
+                }
+            }
+        }
+
+        // A graph of 0 or 1 nodes has no structure to rank.
+        let max_degree = degrees.len().saturating_sub(1);
+        if max_degree > 0 {
+            #[allow(clippy::cast_precision_loss)]
+            let scale = max_degree as f64;
+            for degree in degrees.values_mut() {
+                *degree /= scale;
+            }
+        }
+
+        Ok(degrees)
+    }
+
+    /// Best-effort centrality for prompt/response enrichment: an unreadable
+    /// graph degrades to "no centrality" (with a warning) rather than failing
+    /// an operation whose real work is the API call.
+    async fn centrality_or_empty(&self, session_id: &str) -> HashMap<String, f64> {
+        match self.compute_centrality(session_id).await {
+            Ok(centrality) => centrality,
+            Err(e) => {
+                tracing::warn!(error = %e, "Centrality computation failed — continuing without it");
+                HashMap::new()
+            }
+        }
+    }
+
+    /// Best-effort per-node provenance for response enrichment: an unreadable
+    /// graph degrades to "no provenance" (with a warning) rather than failing
+    /// the call. Nodes without recorded provenance (rows predating migration
+    /// 018) are omitted.
+    async fn provenance_or_empty(&self, session_id: &str) -> HashMap<String, NodeProvenance> {
+        let nodes = match self.storage.get_graph_nodes(session_id).await {
+            Ok(nodes) => nodes,
+            Err(e) => {
+                tracing::warn!(error = %e, "Graph node read failed — continuing without provenance");
+                return HashMap::new();
+            }
+        };
+        nodes
+            .into_iter()
+            .filter_map(|node| {
+                let origin = node.origin?;
+                Some((
+                    node.id,
+                    NodeProvenance {
+                        origin: origin.as_str().to_string(),
+                        parent_ids: node.parent_ids,
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    /// Render centrality scores as a prompt block (highest first, ties by ID
+    /// for determinism), or an empty string when there are no scores.
+    fn centrality_prompt_block(centrality: &HashMap<String, f64>) -> String {
+        if centrality.is_empty() {
+            return String::new();
+        }
+        let mut ranked: Vec<(&str, f64)> = centrality
+            .iter()
+            .map(|(id, score)| (id.as_str(), *score))
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+        let mut block =
+            String::from("\n\nStructural centrality of stored nodes (0 = peripheral, 1 = hub):\n");
+        for (id, score) in ranked {
+            let _ = writeln!(block, "- {id}: {score:.2}");
+        }
+        block.push_str(
+            "Prefer pruning nodes that combine a quality score below the floor \
+             with low centrality; avoid pruning high-centrality nodes other \
+             branches depend on.",
+        );
+        block
+    }
+
+    /// Namespace a model-supplied node ID with its session.
+    ///
+    /// The `graph_nodes` primary key is global, but models reuse short IDs
+    /// (`root`, `c1`, ...) across sessions. Prefixing with the session ID keeps
+    /// nodes from different sessions from colliding in storage.
+    fn namespaced_id(session_id: &str, node_id: &str) -> String {
+        format!("{session_id}::{node_id}")
+    }
+
+    /// Validate finalize `best_paths` against the stored node IDs.
+    ///
+    /// Unknown node IDs are filtered out of a path and recorded as an
+    /// adjustment; a path left with no stored nodes is dropped outright.
+    /// Paths that reference only stored nodes pass through untouched.
+    fn validate_best_paths(
+        paths: Vec<GraphPath>,
+        known: &std::collections::HashSet<String>,
+    ) -> (Vec<GraphPath>, Vec<PathAdjustment>) {
+        let mut validated = Vec::new();
+        let mut adjustments = Vec::new();
+        for (path_index, mut path) in paths.into_iter().enumerate() {
+            let unknown_nodes: Vec<String> = path
+                .path
+                .iter()
+                .filter(|id| !known.contains(*id))
+                .cloned()
+                .collect();
+            if unknown_nodes.is_empty() {
+                validated.push(path);
+                continue;
+            }
+            path.path.retain(|id| known.contains(id));
+            let dropped = path.path.is_empty();
+            adjustments.push(PathAdjustment {
+                path_index,
+                unknown_nodes,
+                dropped,
+            });
+            if !dropped {
+                validated.push(path);
+            }
+        }
+        (validated, adjustments)
+    }
+
+    /// Compute the best path through the stored graph by greedy descent on
+    /// node scores: start at the highest-scoring root (a node with no incoming
+    /// edge) and repeatedly follow the highest-scoring unvisited child. Path
+    /// quality is the mean score along the path; unscored nodes count as 0.5.
+    /// Returns `None` when the graph has no edge to walk — a single node is
+    /// not a path.
+    fn computed_best_path(
+        nodes: &[StoredGraphNode],
+        edges: &[StoredGraphEdge],
+        prefix: &str,
+    ) -> Option<GraphPath> {
+        let strip = |id: &str| -> String { id.strip_prefix(prefix).unwrap_or(id).to_string() };
+        let score_of = |id: &str| -> f64 {
+            nodes
+                .iter()
+                .find(|n| strip(&n.id) == id)
+                .and_then(|n| n.score)
+                .unwrap_or(0.5)
+        };
+
+        let mut children: std::collections::HashMap<String, Vec<String>> =
+            std::collections::HashMap::new();
+        let mut has_incoming: std::collections::HashSet<String> = std::collections::HashSet::new();
+        for edge in edges {
+            children
+                .entry(strip(&edge.from_node_id))
+                .or_default()
+                .push(strip(&edge.to_node_id));
+            has_incoming.insert(strip(&edge.to_node_id));
+        }
+
+        let start = nodes
+            .iter()
+            .map(|n| strip(&n.id))
+            .filter(|id| !has_incoming.contains(id))
+            .max_by(|a, b| score_of(a).total_cmp(&score_of(b)))?;
+
+        let mut path = vec![start];
+        let mut visited: std::collections::HashSet<String> = path.iter().cloned().collect();
+        while let Some(next) = path.last().and_then(|current| {
+            children.get(current).and_then(|c| {
+                c.iter()
+                    .filter(|id| !visited.contains(*id))
+                    .max_by(|a, b| score_of(a).total_cmp(&score_of(b)))
+            })
+        }) {
+            let next = next.clone();
+            visited.insert(next.clone());
+            path.push(next);
+        }
+        if path.len() < 2 {
+            return None;
+        }
+
+        let path_quality = path.iter().map(|id| score_of(id)).sum::<f64>() / path.len() as f64;
+        let key_insight = format!(
+            "Computed from stored node scores: highest-scoring walk from '{}' to '{}'",
+            path.first().map_or("", String::as_str),
+            path.last().map_or("", String::as_str),
+        );
+        Some(GraphPath {
+            path,
+            path_quality,
+            key_insight,
+        })
+    }
+
+    /// Persist a graph node with its provenance: which operation produced it
+    /// and from which parent(s) (raw ids, namespaced here to match stored node
+    /// ids). Storage failures are logged, not propagated, so a write error
+    /// never discards a reasoning result already returned to the caller.
+    /// Returns `true` on success.
+    #[allow(clippy::too_many_arguments)]
+    async fn persist_node(
+        &self,
+        session_id: &str,
+        node_id: &str,
+        content: &str,
+        score: f64,
+        node_type: GraphNodeType,
+        origin: GraphNodeOrigin,
+        parent_ids: &[String],
+    ) -> bool {
+        let namespaced_parents = parent_ids
+            .iter()
+            .map(|parent| Self::namespaced_id(session_id, parent))
+            .collect();
+        let node = StoredGraphNode::new(
+            Self::namespaced_id(session_id, node_id),
+            session_id,
+            content,
+        )
+        .with_score(score)
+        .with_node_type(node_type)
+        .with_provenance(origin, namespaced_parents);
+
+        if let Err(e) = self.storage.save_graph_node(&node).await {
+            tracing::warn!(error = %e, node_id, "Graph node persistence failed");
+            return false;
+        }
+        true
+    }
+
+    /// Persist a directed graph edge between two (namespaced) nodes. Failures are
+    /// logged, not propagated. Returns `true` on success.
+    async fn persist_edge(
+        &self,
+        session_id: &str,
+        from_id: &str,
+        to_id: &str,
+        edge_type: GraphEdgeType,
+    ) -> bool {
+        let edge = StoredGraphEdge::new(
+            Self::namespaced_id(session_id, &format!("{from_id}->{to_id}")),
+            session_id,
+            Self::namespaced_id(session_id, from_id),
+            Self::namespaced_id(session_id, to_id),
+        )
+        .with_edge_type(edge_type);
+
+        if let Err(e) = self.storage.save_graph_edge(&edge).await {
+            tracing::warn!(error = %e, from = from_id, to = to_id, "Graph edge persistence failed");
+            return false;
+        }
+        true
+    }
+
+    /// Update a node's score in storage. Failures are logged, not propagated.
+    /// Returns `true` on success.
+    async fn persist_score(&self, session_id: &str, node_id: &str, score: f64) -> bool {
+        let storage_id = Self::namespaced_id(session_id, node_id);
+        if let Err(e) = self
+            .storage
+            .update_graph_node_score(&storage_id, score)
+            .await
+        {
+            tracing::warn!(error = %e, node_id, "Graph node score update failed");
+            return false;
+        }
+        true
+    }
+}
+
+impl<S, C> std::fmt::Debug for GraphMode<S, C>
+where
+    S: StorageTrait,
+    C: AnthropicClientTrait,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GraphMode")
+            .field("storage", &"<StorageTrait>")
+            .field("client", &"<AnthropicClientTrait>")
+            .finish()
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp,
+    clippy::approx_constant,
+    clippy::unreadable_literal
+)]
+mod tests {
+    use super::*;
+    use crate::error::StorageError;
+    use crate::traits::{CompletionResponse, MockAnthropicClientTrait, MockStorageTrait, Usage};
+
+    /// Register permissive (`times(..)`) expectations for the best-effort graph
+    /// persistence writes so happy-path tests, which focus on API parsing, don't
+    /// panic when an operation also persists nodes/edges/scores.
+    fn expect_graph_writes(mock: &mut MockStorageTrait) {
+        // generate checks the node ceiling (an empty graph is far below it);
+        // prune/state read nodes and edges back for centrality.
+        mock.expect_get_graph_nodes()
+            .times(..)
+            .returning(|_| Ok(Vec::new()));
+        mock.expect_get_graph_edges()
+            .times(..)
+            .returning(|_| Ok(Vec::new()));
+        mock.expect_save_graph_node()
+            .times(..)
+            .returning(|_| Ok(()));
+        mock.expect_save_graph_edge()
+            .times(..)
+            .returning(|_| Ok(()));
+        mock.expect_save_graph_batch()
+            .times(..)
+            .returning(|_, _, _| Ok(()));
+        mock.expect_update_graph_node_score()
+            .times(..)
+            .returning(|_, _| Ok(()));
+        mock.expect_delete_graph_node()
+            .times(..)
+            .returning(|_| Ok(()));
+    }
+
+    fn mock_init_response() -> String {
+        r#"{
+            "root": {"id": "root", "content": "Main topic", "score": 0.5, "type": "root"},
+            "expansion_directions": [
+                {"direction": "Direction A", "potential": 0.7}
+            ],
+            "graph_metadata": {"complexity": "medium", "estimated_depth": 3}
+        }"#
+        .to_string()
+    }
+
+    fn mock_generate_response() -> String {
+        r#"{
+            "parent_id": "root",
+            "children": [
+                {"id": "c1", "content": "Child 1", "score": 0.6, "type": "reasoning", "relationship": "elaborates"}
+            ],
+            "generation_notes": "Generated reasoning children"
+        }"#
+        .to_string()
+    }
+
+    fn mock_score_response() -> String {
+        r#"{
+            "node_id": "c1",
+            "scores": {"relevance": 0.8, "coherence": 0.7, "depth": 0.6, "novelty": 0.5, "overall": 0.65},
+            "assessment": {"strengths": ["Clear"], "weaknesses": ["Shallow"], "recommendation": "expand"}
+        }"#
+        .to_string()
+    }
+
+    fn mock_aggregate_response() -> String {
+        r#"{
+            "input_node_ids": ["c1", "c2"],
+            "synthesis": {"id": "s1", "content": "Synthesis", "score": 0.75, "type": "synthesis"},
+            "integration_notes": {"common_themes": ["Theme"], "complementary_aspects": ["Aspect"], "resolved_contradictions": []}
+        }"#
+        .to_string()
+    }
+
+    fn mock_refine_response() -> String {
+        r#"{
+            "original_node_id": "c1",
+            "critique": {"issues": ["Issue"], "missing_elements": ["Missing"], "unclear_aspects": []},
+            "refined_node": {"id": "r1", "content": "Refined", "score": 0.8, "type": "refined"},
+            "improvement_delta": 0.15
+        }"#
+        .to_string()
+    }
+
+    fn mock_prune_response() -> String {
+        r#"{
+            "prune_candidates": [
+                {"node_id": "bad_node", "reason": "low_score", "confidence": 0.8, "impact": "minor"}
+            ],
+            "preserve_nodes": ["root", "c1"],
+            "pruning_strategy": "Remove low-quality nodes"
+        }"#
+        .to_string()
+    }
+
+    fn mock_finalize_response() -> String {
+        r#"{
+            "best_paths": [
+                {"path": ["root", "c1"], "path_quality": 0.85, "key_insight": "Main insight"}
+            ],
+            "conclusions": [
+                {"conclusion": "Key conclusion", "confidence": 0.8, "supporting_nodes": ["c1"]}
+            ],
+            "final_synthesis": "Final summary",
+            "session_quality": {"depth_achieved": 0.75, "breadth_achieved": 0.8, "coherence": 0.85, "overall": 0.8}
+        }"#
+        .to_string()
     }
 
     fn mock_state_response() -> String {
@@ -943,45 +2462,639 @@ mod tests {
             "frontiers": [
                 {"node_id": "f1", "potential": 0.7, "suggested_action": "expand"}
             ],
-            "metrics": {"average_score": 0.65, "max_score": 0.9, "coverage": 0.6},
-            "next_steps": ["Expand f1"]
-        }"#
-        .to_string()
+            "metrics": {"average_score": 0.65, "max_score": 0.9, "coverage": 0.6},
+            "next_steps": ["Expand f1"]
+        }"#
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_init_success() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        expect_graph_writes(&mut mock_storage);
+
+        let resp = mock_init_response();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = GraphMode::new(mock_storage, mock_client);
+        let result = mode.init("Topic", Some("test".to_string())).await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.root.id, "root");
+    }
+
+    #[tokio::test]
+    async fn test_init_requires_session_id_under_strict_config() {
+        let strict = ModeFlags {
+            require_session_id: true,
+            ..ModeFlags::default()
+        };
+
+        // Without a session id the call is rejected before touching storage.
+        let mode = GraphMode::new(MockStorageTrait::new(), MockAnthropicClientTrait::new())
+            .with_flags(strict);
+        let rejected = mode.init("Topic", None).await;
+
+        // With one, the operation runs normally.
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        expect_graph_writes(&mut mock_storage);
+        let resp = mock_init_response();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+        let mode = GraphMode::new(mock_storage, mock_client).with_flags(strict);
+        let accepted = mode.init("Topic", Some("test".to_string())).await;
+
+        assert!(matches!(rejected, Err(ModeError::SessionRequired)));
+        assert!(accepted.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_generate_success() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        expect_graph_writes(&mut mock_storage);
+
+        let resp = mock_generate_response();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = GraphMode::new(mock_storage, mock_client);
+        let result = mode.generate(Some("Parent"), None, None).await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.children.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_score_success() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        expect_graph_writes(&mut mock_storage);
+
+        let resp = mock_score_response();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = GraphMode::new(mock_storage, mock_client);
+        let result = mode.score(Some("Node"), None, None).await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert!((response.scores.overall - 0.65).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_success() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        expect_graph_writes(&mut mock_storage);
+
+        let resp = mock_aggregate_response();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = GraphMode::new(mock_storage, mock_client);
+        let result = mode.aggregate("Nodes", None).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_refine_success() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        expect_graph_writes(&mut mock_storage);
+
+        let resp = mock_refine_response();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = GraphMode::new(mock_storage, mock_client);
+        let result = mode.refine("Node", None).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_prune_success() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        expect_graph_writes(&mut mock_storage);
+
+        let resp = mock_prune_response();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = GraphMode::new(mock_storage, mock_client);
+        let result = mode.prune("Graph", None, 0.3).await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.prune_candidates.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_finalize_success() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        expect_graph_writes(&mut mock_storage);
+
+        let resp = mock_finalize_response();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = GraphMode::new(mock_storage, mock_client);
+        let result = mode.finalize("Graph", None).await;
+
+        // No stored graph: the model's paths pass through unvalidated.
+        let response = result.expect("finalize");
+        assert_eq!(response.best_paths.len(), 1);
+        assert!(response.path_adjustments.is_empty());
+    }
+
+    /// Stored nodes `root`/`c1`/`c2` (namespaced) with edges root→c1, root→c2.
+    fn stored_graph(session_id: &str) -> (Vec<StoredGraphNode>, Vec<StoredGraphEdge>) {
+        let node = |id: &str, score: f64| {
+            StoredGraphNode::new(format!("{session_id}::{id}"), session_id, "Content")
+                .with_score(score)
+        };
+        let edge = |from: &str, to: &str| {
+            StoredGraphEdge::new(
+                format!("{session_id}::{from}->{to}"),
+                session_id,
+                format!("{session_id}::{from}"),
+                format!("{session_id}::{to}"),
+            )
+        };
+        (
+            vec![node("root", 0.9), node("c1", 0.7), node("c2", 0.4)],
+            vec![edge("root", "c1"), edge("root", "c2")],
+        )
+    }
+
+    #[tokio::test]
+    async fn test_finalize_flags_path_with_unknown_node() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        mock_storage
+            .expect_get_graph_nodes()
+            .returning(|_| Ok(stored_graph("test").0));
+        mock_storage
+            .expect_get_graph_edges()
+            .returning(|_| Ok(stored_graph("test").1));
+
+        // One valid path, one referencing a hallucinated node, one entirely
+        // hallucinated.
+        let resp = r#"{
+            "best_paths": [
+                {"path": ["root", "c1"], "path_quality": 0.85, "key_insight": "Valid"},
+                {"path": ["root", "ghost"], "path_quality": 0.7, "key_insight": "Partial"},
+                {"path": ["ghost1", "ghost2"], "path_quality": 0.6, "key_insight": "Bogus"}
+            ],
+            "conclusions": [
+                {"conclusion": "Key conclusion", "confidence": 0.8, "supporting_nodes": ["c1"]}
+            ],
+            "final_synthesis": "Final summary",
+            "session_quality": {"depth_achieved": 0.75, "breadth_achieved": 0.8, "coherence": 0.85, "overall": 0.8}
+        }"#.to_string();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = GraphMode::new(mock_storage, mock_client);
+        let response = mode
+            .finalize("Graph", Some("test".to_string()))
+            .await
+            .expect("finalize");
+
+        // The fully valid path survives untouched (it matches the computed
+        // walk, so no duplicate is prepended), the partial path is filtered
+        // down to its stored node, and the bogus path is dropped.
+        let paths: Vec<&[String]> = response.best_paths.iter().map(|p| &p.path[..]).collect();
+        assert_eq!(paths, vec![&["root", "c1"][..], &["root"][..]]);
+
+        assert_eq!(response.path_adjustments.len(), 2);
+        assert_eq!(response.path_adjustments[0].path_index, 1);
+        assert_eq!(response.path_adjustments[0].unknown_nodes, vec!["ghost"]);
+        assert!(!response.path_adjustments[0].dropped);
+        assert_eq!(response.path_adjustments[1].path_index, 2);
+        assert_eq!(
+            response.path_adjustments[1].unknown_nodes,
+            vec!["ghost1", "ghost2"]
+        );
+        assert!(response.path_adjustments[1].dropped);
+    }
+
+    #[tokio::test]
+    async fn test_finalize_prefers_computed_path() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        mock_storage
+            .expect_get_graph_nodes()
+            .returning(|_| Ok(stored_graph("test").0));
+        mock_storage
+            .expect_get_graph_edges()
+            .returning(|_| Ok(stored_graph("test").1));
+
+        // The model picks the weaker branch; the computed walk (root → c1 by
+        // stored scores) is preferred and the model's path follows.
+        let resp = r#"{
+            "best_paths": [
+                {"path": ["root", "c2"], "path_quality": 0.5, "key_insight": "Model's pick"}
+            ],
+            "conclusions": [
+                {"conclusion": "Key conclusion", "confidence": 0.8, "supporting_nodes": ["c2"]}
+            ],
+            "final_synthesis": "Final summary",
+            "session_quality": {"depth_achieved": 0.75, "breadth_achieved": 0.8, "coherence": 0.85, "overall": 0.8}
+        }"#.to_string();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = GraphMode::new(mock_storage, mock_client);
+        let response = mode
+            .finalize("Graph", Some("test".to_string()))
+            .await
+            .expect("finalize");
+
+        assert_eq!(response.best_paths.len(), 2);
+        assert_eq!(response.best_paths[0].path, vec!["root", "c1"]);
+        assert!(response.best_paths[0]
+            .key_insight
+            .contains("Computed from stored node scores"));
+        assert!((response.best_paths[0].path_quality - 0.8).abs() < 1e-9);
+        assert_eq!(response.best_paths[1].path, vec!["root", "c2"]);
+        assert!(response.path_adjustments.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_finalize_attaches_quality_rubric_for_stored_graph() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        mock_storage
+            .expect_get_graph_nodes()
+            .returning(|_| Ok(stored_graph("test").0));
+        mock_storage
+            .expect_get_graph_edges()
+            .returning(|_| Ok(stored_graph("test").1));
+
+        let resp = r#"{
+            "best_paths": [
+                {"path": ["root", "c1"], "path_quality": 0.8, "key_insight": "Strong"}
+            ],
+            "conclusions": [
+                {"conclusion": "Key conclusion", "confidence": 0.8, "supporting_nodes": ["c1"]}
+            ],
+            "final_synthesis": "Final summary",
+            "session_quality": {"depth_achieved": 0.75, "breadth_achieved": 0.8, "coherence": 0.85, "overall": 0.8}
+        }"#.to_string();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = GraphMode::new(mock_storage, mock_client);
+        let response = mode
+            .finalize("Graph", Some("test".to_string()))
+            .await
+            .expect("finalize");
+
+        // stored_graph: 3 nodes (scores 0.9/0.7/0.4), leaves c1 and c2. The
+        // weight-independent components match a hand computation.
+        let rubric = response.quality_rubric.expect("rubric for stored graph");
+        assert!((rubric.llm_overall - 0.8).abs() < 1e-9);
+        assert!((rubric.node_count_component - 0.3).abs() < 1e-9);
+        assert!((rubric.average_score_component - 2.0 / 3.0).abs() < 1e-9);
+        assert!((rubric.path_diversity_component - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_computed_best_path_needs_an_edge() {
+        let (nodes, _) = stored_graph("test");
+        let computed = GraphMode::<MockStorageTrait, MockAnthropicClientTrait>::computed_best_path(
+            &nodes,
+            &[],
+            "test::",
+        );
+        assert!(computed.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_state_success() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        expect_graph_writes(&mut mock_storage);
+
+        let resp = mock_state_response();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = GraphMode::new(mock_storage, mock_client);
+        let result = mode.state(Some("Graph"), "test").await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.structure.total_nodes, 10);
+    }
+
+    #[tokio::test]
+    async fn test_init_empty_content() {
+        let mock_storage = MockStorageTrait::new();
+        let mock_client = MockAnthropicClientTrait::new();
+
+        let mode = GraphMode::new(mock_storage, mock_client);
+        let result = mode.init("", None).await;
+
+        assert!(matches!(result, Err(ModeError::MissingField { field }) if field == "content"));
+    }
+
+    #[tokio::test]
+    async fn test_storage_error() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage.expect_get_or_create_session().returning(|_| {
+            Err(StorageError::ConnectionFailed {
+                message: "DB error".to_string(),
+            })
+        });
+
+        let mode = GraphMode::new(mock_storage, mock_client);
+        let result = mode.init("Test", None).await;
+
+        assert!(matches!(result, Err(ModeError::ApiUnavailable { .. })));
+    }
+
+    #[test]
+    fn test_graph_mode_debug() {
+        let mock_storage = MockStorageTrait::new();
+        let mock_client = MockAnthropicClientTrait::new();
+        let mode = GraphMode::new(mock_storage, mock_client);
+        let debug = format!("{mode:?}");
+        assert!(debug.contains("GraphMode"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_with_node_id_lookup() {
+        use crate::storage::StoredGraphNode;
+
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        expect_graph_writes(&mut mock_storage);
+
+        // Mock the node lookup
+        mock_storage.expect_get_graph_node().returning(|_id| {
+            Ok(Some(StoredGraphNode::new(
+                "node-1",
+                "test",
+                "Stored node content",
+            )))
+        });
+
+        let resp = mock_generate_response();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = GraphMode::new(mock_storage, mock_client);
+        // Use node_id instead of content
+        let result = mode.generate(None, Some("node-1"), None).await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.children.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_generate_missing_content_and_node_id() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mock_client = MockAnthropicClientTrait::new();
+
+        // Need to mock session creation as it happens before content resolution
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage
+            .expect_get_graph_nodes()
+            .returning(|_| Ok(Vec::new()));
+
+        let mode = GraphMode::new(mock_storage, mock_client);
+        let result = mode.generate(None, None, None).await;
+
+        assert!(
+            matches!(result, Err(ModeError::MissingField { field }) if field == "content or node_id")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_node_id_not_found() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mock_client = MockAnthropicClientTrait::new();
+
+        // Need to mock session creation as it happens before content resolution
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage
+            .expect_get_graph_nodes()
+            .returning(|_| Ok(Vec::new()));
+
+        mock_storage
+            .expect_get_graph_node()
+            .returning(|_id| Ok(None));
+
+        let mode = GraphMode::new(mock_storage, mock_client);
+        let result = mode.generate(None, Some("nonexistent"), None).await;
+
+        assert!(matches!(result, Err(ModeError::InvalidValue { field, .. }) if field == "node_id"));
+    }
+
+    #[tokio::test]
+    async fn test_state_from_storage() {
+        use crate::storage::{StoredGraphEdge, StoredGraphNode};
+
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        expect_graph_writes(&mut mock_storage);
+
+        // Mock the graph retrieval from storage
+        mock_storage
+            .expect_get_graph_nodes()
+            .returning(|_session_id| {
+                Ok(vec![
+                    StoredGraphNode::new("n1", "test", "Node 1"),
+                    StoredGraphNode::new("n2", "test", "Node 2"),
+                ])
+            });
+        mock_storage
+            .expect_get_graph_edges()
+            .returning(|_session_id| Ok(vec![StoredGraphEdge::new("e1", "test", "n1", "n2")]));
+
+        let resp = mock_state_response();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = GraphMode::new(mock_storage, mock_client);
+        // Call state without content - should retrieve from storage
+        let result = mode.state(None, "test").await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.structure.total_nodes, 10);
+    }
+
+    // ========================================================================
+    // Additional Coverage Tests for Error Paths
+    // ========================================================================
+
+    #[tokio::test]
+    async fn test_init_save_thought_error() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage.expect_save_thought().returning(|_| {
+            Err(StorageError::QueryFailed {
+                query: "INSERT".to_string(),
+                message: "Save failed".to_string(),
+            })
+        });
+        expect_graph_writes(&mut mock_storage);
+
+        let resp = mock_init_response();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = GraphMode::new(mock_storage, mock_client);
+        let result = mode.init("Topic", None).await;
+
+        assert!(result.is_ok());
     }
 
     #[tokio::test]
-    async fn test_init_success() {
+    async fn test_init_api_error() {
         let mut mock_storage = MockStorageTrait::new();
         let mut mock_client = MockAnthropicClientTrait::new();
 
         mock_storage
             .expect_get_or_create_session()
             .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
-        mock_storage.expect_save_thought().returning(|_| Ok(()));
-        expect_graph_writes(&mut mock_storage);
 
-        let resp = mock_init_response();
-        mock_client
-            .expect_complete()
-            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+        mock_client.expect_complete().returning(|_, _| {
+            Err(ModeError::ApiUnavailable {
+                message: "API error".to_string(),
+            })
+        });
 
         let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.init("Topic", Some("test".to_string())).await;
+        let result = mode.init("Topic", None).await;
 
-        assert!(result.is_ok());
-        let response = result.unwrap();
-        assert_eq!(response.root.id, "root");
+        assert!(matches!(result, Err(ModeError::ApiUnavailable { .. })));
     }
 
     #[tokio::test]
-    async fn test_generate_success() {
+    async fn test_generate_batch_write_error() {
         let mut mock_storage = MockStorageTrait::new();
         let mut mock_client = MockAnthropicClientTrait::new();
 
         mock_storage
             .expect_get_or_create_session()
             .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
-        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        // Registered before the permissive defaults so it takes precedence:
+        // the transactional write fails.
+        mock_storage.expect_save_graph_batch().returning(|_, _, _| {
+            Err(StorageError::QueryFailed {
+                query: "INSERT".to_string(),
+                message: "Save failed".to_string(),
+            })
+        });
         expect_graph_writes(&mut mock_storage);
 
         let resp = mock_generate_response();
@@ -992,20 +3105,26 @@ mod tests {
         let mode = GraphMode::new(mock_storage, mock_client);
         let result = mode.generate(Some("Parent"), None, None).await;
 
+        // The reasoning result survives a rolled-back write; the failure is
+        // reported as every write in the batch (1 node + 1 edge).
         assert!(result.is_ok());
-        let response = result.unwrap();
-        assert_eq!(response.children.len(), 1);
+        assert_eq!(result.unwrap().persistence_failures, 2);
     }
 
     #[tokio::test]
-    async fn test_score_success() {
+    async fn test_score_save_thought_error() {
         let mut mock_storage = MockStorageTrait::new();
         let mut mock_client = MockAnthropicClientTrait::new();
 
         mock_storage
             .expect_get_or_create_session()
             .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
-        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        mock_storage.expect_save_thought().returning(|_| {
+            Err(StorageError::QueryFailed {
+                query: "INSERT".to_string(),
+                message: "Save failed".to_string(),
+            })
+        });
         expect_graph_writes(&mut mock_storage);
 
         let resp = mock_score_response();
@@ -1017,19 +3136,33 @@ mod tests {
         let result = mode.score(Some("Node"), None, None).await;
 
         assert!(result.is_ok());
-        let response = result.unwrap();
-        assert!((response.scores.overall - 0.65).abs() < 0.01);
     }
 
     #[tokio::test]
-    async fn test_aggregate_success() {
+    async fn test_aggregate_empty_content() {
+        let mock_storage = MockStorageTrait::new();
+        let mock_client = MockAnthropicClientTrait::new();
+
+        let mode = GraphMode::new(mock_storage, mock_client);
+        let result = mode.aggregate("", None).await;
+
+        assert!(matches!(result, Err(ModeError::MissingField { field }) if field == "content"));
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_save_thought_error() {
         let mut mock_storage = MockStorageTrait::new();
         let mut mock_client = MockAnthropicClientTrait::new();
 
         mock_storage
             .expect_get_or_create_session()
             .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
-        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        mock_storage.expect_save_thought().returning(|_| {
+            Err(StorageError::QueryFailed {
+                query: "INSERT".to_string(),
+                message: "Save failed".to_string(),
+            })
+        });
         expect_graph_writes(&mut mock_storage);
 
         let resp = mock_aggregate_response();
@@ -1044,14 +3177,30 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_refine_success() {
+    async fn test_refine_empty_content() {
+        let mock_storage = MockStorageTrait::new();
+        let mock_client = MockAnthropicClientTrait::new();
+
+        let mode = GraphMode::new(mock_storage, mock_client);
+        let result = mode.refine("", None).await;
+
+        assert!(matches!(result, Err(ModeError::MissingField { field }) if field == "content"));
+    }
+
+    #[tokio::test]
+    async fn test_refine_save_thought_error() {
         let mut mock_storage = MockStorageTrait::new();
         let mut mock_client = MockAnthropicClientTrait::new();
 
         mock_storage
             .expect_get_or_create_session()
             .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
-        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        mock_storage.expect_save_thought().returning(|_| {
+            Err(StorageError::QueryFailed {
+                query: "INSERT".to_string(),
+                message: "Save failed".to_string(),
+            })
+        });
         expect_graph_writes(&mut mock_storage);
 
         let resp = mock_refine_response();
@@ -1066,14 +3215,30 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_prune_success() {
+    async fn test_prune_empty_content() {
+        let mock_storage = MockStorageTrait::new();
+        let mock_client = MockAnthropicClientTrait::new();
+
+        let mode = GraphMode::new(mock_storage, mock_client);
+        let result = mode.prune("", None, 0.3).await;
+
+        assert!(matches!(result, Err(ModeError::MissingField { field }) if field == "content"));
+    }
+
+    #[tokio::test]
+    async fn test_prune_save_thought_error() {
         let mut mock_storage = MockStorageTrait::new();
         let mut mock_client = MockAnthropicClientTrait::new();
 
         mock_storage
             .expect_get_or_create_session()
             .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
-        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        mock_storage.expect_save_thought().returning(|_| {
+            Err(StorageError::QueryFailed {
+                query: "INSERT".to_string(),
+                message: "Save failed".to_string(),
+            })
+        });
         expect_graph_writes(&mut mock_storage);
 
         let resp = mock_prune_response();
@@ -1085,19 +3250,33 @@ mod tests {
         let result = mode.prune("Graph", None, 0.3).await;
 
         assert!(result.is_ok());
-        let response = result.unwrap();
-        assert_eq!(response.prune_candidates.len(), 1);
     }
 
     #[tokio::test]
-    async fn test_finalize_success() {
+    async fn test_finalize_empty_content() {
+        let mock_storage = MockStorageTrait::new();
+        let mock_client = MockAnthropicClientTrait::new();
+
+        let mode = GraphMode::new(mock_storage, mock_client);
+        let result = mode.finalize("", None).await;
+
+        assert!(matches!(result, Err(ModeError::MissingField { field }) if field == "content"));
+    }
+
+    #[tokio::test]
+    async fn test_finalize_save_thought_error() {
         let mut mock_storage = MockStorageTrait::new();
         let mut mock_client = MockAnthropicClientTrait::new();
 
         mock_storage
             .expect_get_or_create_session()
             .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
-        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        mock_storage.expect_save_thought().returning(|_| {
+            Err(StorageError::QueryFailed {
+                query: "INSERT".to_string(),
+                message: "Save failed".to_string(),
+            })
+        });
         expect_graph_writes(&mut mock_storage);
 
         let resp = mock_finalize_response();
@@ -1112,14 +3291,19 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_state_success() {
+    async fn test_state_save_thought_error() {
         let mut mock_storage = MockStorageTrait::new();
         let mut mock_client = MockAnthropicClientTrait::new();
 
         mock_storage
             .expect_get_or_create_session()
             .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
-        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        mock_storage.expect_save_thought().returning(|_| {
+            Err(StorageError::QueryFailed {
+                query: "INSERT".to_string(),
+                message: "Save failed".to_string(),
+            })
+        });
         expect_graph_writes(&mut mock_storage);
 
         let resp = mock_state_response();
@@ -1131,49 +3315,85 @@ mod tests {
         let result = mode.state(Some("Graph"), "test").await;
 
         assert!(result.is_ok());
-        let response = result.unwrap();
-        assert_eq!(response.structure.total_nodes, 10);
     }
 
     #[tokio::test]
-    async fn test_init_empty_content() {
-        let mock_storage = MockStorageTrait::new();
+    async fn test_generate_node_lookup_storage_error() {
+        let mut mock_storage = MockStorageTrait::new();
         let mock_client = MockAnthropicClientTrait::new();
 
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage
+            .expect_get_graph_nodes()
+            .returning(|_| Ok(Vec::new()));
+
+        mock_storage.expect_get_graph_node().returning(|_id| {
+            Err(StorageError::QueryFailed {
+                query: "SELECT".to_string(),
+                message: "Lookup failed".to_string(),
+            })
+        });
+
         let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.init("", None).await;
+        let result = mode.generate(None, Some("node-1"), None).await;
 
-        assert!(matches!(result, Err(ModeError::MissingField { field }) if field == "content"));
+        assert!(matches!(result, Err(ModeError::ApiUnavailable { .. })));
     }
 
     #[tokio::test]
-    async fn test_storage_error() {
+    async fn test_state_get_nodes_storage_error() {
         let mut mock_storage = MockStorageTrait::new();
         let mock_client = MockAnthropicClientTrait::new();
 
-        mock_storage.expect_get_or_create_session().returning(|_| {
-            Err(StorageError::ConnectionFailed {
-                message: "DB error".to_string(),
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+
+        mock_storage.expect_get_graph_nodes().returning(|_| {
+            Err(StorageError::QueryFailed {
+                query: "SELECT".to_string(),
+                message: "Get nodes failed".to_string(),
             })
         });
 
         let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.init("Test", None).await;
+        let result = mode.state(None, "test").await;
 
         assert!(matches!(result, Err(ModeError::ApiUnavailable { .. })));
     }
 
-    #[test]
-    fn test_graph_mode_debug() {
-        let mock_storage = MockStorageTrait::new();
+    #[tokio::test]
+    async fn test_state_get_edges_storage_error() {
+        use crate::storage::StoredGraphNode;
+
+        let mut mock_storage = MockStorageTrait::new();
         let mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+
+        mock_storage
+            .expect_get_graph_nodes()
+            .returning(|_| Ok(vec![StoredGraphNode::new("n1", "test", "Node 1")]));
+
+        mock_storage.expect_get_graph_edges().returning(|_| {
+            Err(StorageError::QueryFailed {
+                query: "SELECT".to_string(),
+                message: "Get edges failed".to_string(),
+            })
+        });
+
         let mode = GraphMode::new(mock_storage, mock_client);
-        let debug = format!("{mode:?}");
-        assert!(debug.contains("GraphMode"));
+        let result = mode.state(None, "test").await;
+
+        assert!(matches!(result, Err(ModeError::ApiUnavailable { .. })));
     }
 
     #[tokio::test]
-    async fn test_generate_with_node_id_lookup() {
+    async fn test_score_with_node_id_lookup() {
         use crate::storage::StoredGraphNode;
 
         let mut mock_storage = MockStorageTrait::new();
@@ -1185,7 +3405,6 @@ mod tests {
         mock_storage.expect_save_thought().returning(|_| Ok(()));
         expect_graph_writes(&mut mock_storage);
 
-        // Mock the node lookup
         mock_storage.expect_get_graph_node().returning(|_id| {
             Ok(Some(StoredGraphNode::new(
                 "node-1",
@@ -1194,32 +3413,28 @@ mod tests {
             )))
         });
 
-        let resp = mock_generate_response();
+        let resp = mock_score_response();
         mock_client
             .expect_complete()
             .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
 
         let mode = GraphMode::new(mock_storage, mock_client);
-        // Use node_id instead of content
-        let result = mode.generate(None, Some("node-1"), None).await;
+        let result = mode.score(None, Some("node-1"), None).await;
 
         assert!(result.is_ok());
-        let response = result.unwrap();
-        assert_eq!(response.children.len(), 1);
     }
 
     #[tokio::test]
-    async fn test_generate_missing_content_and_node_id() {
+    async fn test_score_missing_content_and_node_id() {
         let mut mock_storage = MockStorageTrait::new();
         let mock_client = MockAnthropicClientTrait::new();
 
-        // Need to mock session creation as it happens before content resolution
         mock_storage
             .expect_get_or_create_session()
             .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
 
         let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.generate(None, None, None).await;
+        let result = mode.score(None, None, None).await;
 
         assert!(
             matches!(result, Err(ModeError::MissingField { field }) if field == "content or node_id")
@@ -1227,27 +3442,40 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_generate_node_id_not_found() {
+    async fn test_generate_empty_content_with_node_id() {
+        use crate::storage::StoredGraphNode;
+
         let mut mock_storage = MockStorageTrait::new();
-        let mock_client = MockAnthropicClientTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
 
-        // Need to mock session creation as it happens before content resolution
         mock_storage
             .expect_get_or_create_session()
             .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+        expect_graph_writes(&mut mock_storage);
 
-        mock_storage
-            .expect_get_graph_node()
-            .returning(|_id| Ok(None));
+        // Providing empty content should fall back to node_id lookup
+        mock_storage.expect_get_graph_node().returning(|_id| {
+            Ok(Some(StoredGraphNode::new(
+                "node-1",
+                "test",
+                "Stored node content",
+            )))
+        });
+
+        let resp = mock_generate_response();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
 
         let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.generate(None, Some("nonexistent"), None).await;
+        let result = mode.generate(Some("  "), Some("node-1"), None).await;
 
-        assert!(matches!(result, Err(ModeError::InvalidValue { field, .. }) if field == "node_id"));
+        assert!(result.is_ok());
     }
 
     #[tokio::test]
-    async fn test_state_from_storage() {
+    async fn test_state_with_empty_content() {
         use crate::storage::{StoredGraphEdge, StoredGraphNode};
 
         let mut mock_storage = MockStorageTrait::new();
@@ -1259,18 +3487,13 @@ mod tests {
         mock_storage.expect_save_thought().returning(|_| Ok(()));
         expect_graph_writes(&mut mock_storage);
 
-        // Mock the graph retrieval from storage
+        // Providing empty content should retrieve from storage
         mock_storage
             .expect_get_graph_nodes()
-            .returning(|_session_id| {
-                Ok(vec![
-                    StoredGraphNode::new("n1", "test", "Node 1"),
-                    StoredGraphNode::new("n2", "test", "Node 2"),
-                ])
-            });
+            .returning(|_| Ok(vec![StoredGraphNode::new("n1", "test", "Node 1")]));
         mock_storage
             .expect_get_graph_edges()
-            .returning(|_session_id| Ok(vec![StoredGraphEdge::new("e1", "test", "n1", "n2")]));
+            .returning(|_| Ok(vec![StoredGraphEdge::new("e1", "test", "n1", "n2")]));
 
         let resp = mock_state_response();
         mock_client
@@ -1278,751 +3501,1456 @@ mod tests {
             .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
 
         let mode = GraphMode::new(mock_storage, mock_client);
-        // Call state without content - should retrieve from storage
-        let result = mode.state(None, "test").await;
+        let result = mode.state(Some("  "), "test").await;
 
         assert!(result.is_ok());
-        let response = result.unwrap();
-        assert_eq!(response.structure.total_nodes, 10);
     }
 
     // ========================================================================
-    // Additional Coverage Tests for Error Paths
+    // End-to-end persistence tests (real in-memory storage)
+    //
+    // These verify the graph is actually written to and read back from storage,
+    // with per-session node-ID namespacing, which the mocked tests above cannot.
     // ========================================================================
 
-    #[tokio::test]
-    async fn test_init_save_thought_error() {
-        let mut mock_storage = MockStorageTrait::new();
-        let mut mock_client = MockAnthropicClientTrait::new();
+    use std::sync::Arc;
 
-        mock_storage
-            .expect_get_or_create_session()
-            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
-        mock_storage.expect_save_thought().returning(|_| {
-            Err(StorageError::QueryFailed {
-                query: "INSERT".to_string(),
-                message: "Save failed".to_string(),
-            })
-        });
-        expect_graph_writes(&mut mock_storage);
+    use crate::storage::SqliteStorage;
 
-        let resp = mock_init_response();
-        mock_client
+    /// Build a mock client that returns `resp` for every completion call.
+    fn fixed_client(resp: String) -> MockAnthropicClientTrait {
+        let mut client = MockAnthropicClientTrait::new();
+        client
             .expect_complete()
             .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+        client
+    }
 
-        let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.init("Topic", None).await;
+    async fn in_memory_storage() -> Arc<SqliteStorage> {
+        Arc::new(
+            SqliteStorage::new_in_memory()
+                .await
+                .expect("create in-memory storage"),
+        )
+    }
 
-        assert!(result.is_ok());
+    /// Create the session row (foreign keys are enforced, so nodes/edges need it).
+    async fn seed_session(storage: &Arc<SqliteStorage>, session_id: &str) {
+        storage
+            .get_or_create_session(Some(session_id.to_string()))
+            .await
+            .expect("create session");
+    }
+
+    /// Persist a node under its session-namespaced key so edges can reference it.
+    async fn seed_node(storage: &Arc<SqliteStorage>, session_id: &str, node_id: &str) {
+        storage
+            .save_graph_node(&StoredGraphNode::new(
+                format!("{session_id}::{node_id}"),
+                session_id,
+                "seed content",
+            ))
+            .await
+            .expect("seed node");
     }
 
     #[tokio::test]
-    async fn test_init_api_error() {
-        let mut mock_storage = MockStorageTrait::new();
-        let mut mock_client = MockAnthropicClientTrait::new();
+    async fn test_init_persists_root_node() {
+        let storage = in_memory_storage().await;
+        let mode = GraphMode::new(Arc::clone(&storage), fixed_client(mock_init_response()));
 
-        mock_storage
-            .expect_get_or_create_session()
-            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        let resp = mode
+            .init("Topic", Some("sess-init".to_string()))
+            .await
+            .expect("init succeeds");
+        assert_eq!(resp.root.id, "root");
 
-        mock_client.expect_complete().returning(|_, _| {
-            Err(ModeError::ApiUnavailable {
-                message: "API error".to_string(),
-            })
-        });
+        let nodes = storage
+            .get_graph_nodes("sess-init")
+            .await
+            .expect("read nodes");
+        assert_eq!(nodes.len(), 1);
+        // Stored under its session-namespaced key, not the bare model ID.
+        assert_eq!(nodes[0].id, "sess-init::root");
+        assert_eq!(nodes[0].content, "Main topic");
+        assert!((nodes[0].score.unwrap() - 0.5).abs() < f64::EPSILON);
+    }
 
-        let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.init("Topic", None).await;
+    #[tokio::test]
+    async fn test_generate_persists_children_and_edges() {
+        let storage = in_memory_storage().await;
+        seed_session(&storage, "sess-gen").await;
+        // Parent must exist before the child edge can reference it (FK enforced).
+        seed_node(&storage, "sess-gen", "root").await;
+        let mode = GraphMode::new(Arc::clone(&storage), fixed_client(mock_generate_response()));
 
-        assert!(matches!(result, Err(ModeError::ApiUnavailable { .. })));
+        let resp = mode
+            .generate(Some("Parent"), None, Some("sess-gen".to_string()))
+            .await
+            .expect("generate succeeds");
+        // Parent exists, so everything persisted.
+        assert_eq!(resp.persistence_failures, 0);
+
+        let nodes = storage.get_graph_nodes("sess-gen").await.expect("nodes");
+        assert!(nodes.iter().any(|n| n.id == "sess-gen::c1"));
+
+        let edges = storage.get_graph_edges("sess-gen").await.expect("edges");
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].from_node_id, "sess-gen::root");
+        assert_eq!(edges[0].to_node_id, "sess-gen::c1");
     }
 
     #[tokio::test]
-    async fn test_generate_save_thought_error() {
-        let mut mock_storage = MockStorageTrait::new();
-        let mut mock_client = MockAnthropicClientTrait::new();
+    async fn test_generate_reports_persistence_failure_for_missing_parent() {
+        let storage = in_memory_storage().await;
+        seed_session(&storage, "sess-orphan").await;
+        // Deliberately do NOT seed the parent "root" node, so the child→parent
+        // edge violates the foreign key and the whole batch rolls back.
+        let mode = GraphMode::new(Arc::clone(&storage), fixed_client(mock_generate_response()));
 
-        mock_storage
-            .expect_get_or_create_session()
-            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
-        mock_storage.expect_save_thought().returning(|_| {
-            Err(StorageError::QueryFailed {
-                query: "INSERT".to_string(),
-                message: "Save failed".to_string(),
-            })
-        });
-        expect_graph_writes(&mut mock_storage);
+        let resp = mode
+            .generate(Some("Parent"), None, Some("sess-orphan".to_string()))
+            .await
+            .expect("generate still returns the reasoning result");
 
-        let resp = mock_generate_response();
-        mock_client
-            .expect_complete()
-            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+        // The write is atomic: the failed edge takes the child node and the
+        // thought down with it, so no partial graph is left behind.
+        assert_eq!(resp.persistence_failures, 2);
+        let edges = storage.get_graph_edges("sess-orphan").await.expect("edges");
+        assert!(
+            edges.is_empty(),
+            "edge to a missing parent must not persist"
+        );
+        let nodes = storage.get_graph_nodes("sess-orphan").await.expect("nodes");
+        assert!(nodes.is_empty(), "rolled-back child node must not persist");
+        let thoughts = storage.get_thoughts("sess-orphan").await.expect("thoughts");
+        assert!(thoughts.is_empty(), "rolled-back thought must not persist");
+    }
 
-        let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.generate(Some("Parent"), None, None).await;
+    #[tokio::test]
+    async fn test_score_updates_persisted_node() {
+        let storage = in_memory_storage().await;
+        seed_session(&storage, "sess-score").await;
+        // Seed the node the score response refers to ("c1") under the session.
+        storage
+            .save_graph_node(
+                &StoredGraphNode::new("sess-score::c1", "sess-score", "Node").with_score(0.1),
+            )
+            .await
+            .expect("seed node");
 
-        assert!(result.is_ok());
+        let mode = GraphMode::new(Arc::clone(&storage), fixed_client(mock_score_response()));
+        mode.score(Some("Node"), None, Some("sess-score".to_string()))
+            .await
+            .expect("score succeeds");
+
+        let node = storage
+            .get_graph_node("sess-score::c1")
+            .await
+            .expect("read node")
+            .expect("node exists");
+        assert!((node.score.unwrap() - 0.65).abs() < 0.01);
     }
 
     #[tokio::test]
-    async fn test_score_save_thought_error() {
-        let mut mock_storage = MockStorageTrait::new();
-        let mut mock_client = MockAnthropicClientTrait::new();
+    async fn test_aggregate_persists_synthesis_and_edges() {
+        let storage = in_memory_storage().await;
+        seed_session(&storage, "sess-agg").await;
+        // Input nodes must exist before aggregation edges can reference them.
+        seed_node(&storage, "sess-agg", "c1").await;
+        seed_node(&storage, "sess-agg", "c2").await;
+        let mode = GraphMode::new(
+            Arc::clone(&storage),
+            fixed_client(mock_aggregate_response()),
+        );
 
-        mock_storage
-            .expect_get_or_create_session()
-            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
-        mock_storage.expect_save_thought().returning(|_| {
-            Err(StorageError::QueryFailed {
-                query: "INSERT".to_string(),
-                message: "Save failed".to_string(),
-            })
-        });
-        expect_graph_writes(&mut mock_storage);
+        mode.aggregate("Nodes", Some("sess-agg".to_string()))
+            .await
+            .expect("aggregate succeeds");
 
-        let resp = mock_score_response();
-        mock_client
-            .expect_complete()
-            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+        let synthesis = storage
+            .get_graph_node("sess-agg::s1")
+            .await
+            .expect("read synthesis")
+            .expect("synthesis exists");
+        assert_eq!(synthesis.node_type, GraphNodeType::Aggregation);
+
+        // One edge from each input node ("c1", "c2") into the synthesis.
+        let edges = storage.get_graph_edges("sess-agg").await.expect("edges");
+        assert_eq!(edges.len(), 2);
+        assert!(edges.iter().all(|e| e.to_node_id == "sess-agg::s1"));
+    }
+
+    #[tokio::test]
+    async fn test_refine_persists_refined_node() {
+        let storage = in_memory_storage().await;
+        seed_session(&storage, "sess-refine").await;
+        // Original node must exist before the refinement edge can reference it.
+        seed_node(&storage, "sess-refine", "c1").await;
+        let mode = GraphMode::new(Arc::clone(&storage), fixed_client(mock_refine_response()));
+
+        mode.refine("Node", Some("sess-refine".to_string()))
+            .await
+            .expect("refine succeeds");
+
+        let refined = storage
+            .get_graph_node("sess-refine::r1")
+            .await
+            .expect("read refined")
+            .expect("refined exists");
+        assert_eq!(refined.node_type, GraphNodeType::Refinement);
+
+        let edges = storage.get_graph_edges("sess-refine").await.expect("edges");
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].from_node_id, "sess-refine::c1");
+        assert_eq!(edges[0].to_node_id, "sess-refine::r1");
+    }
+
+    #[tokio::test]
+    async fn test_generate_records_generate_provenance_with_parent() {
+        use crate::storage::GraphNodeOrigin;
+
+        let storage = in_memory_storage().await;
+        seed_session(&storage, "sess-gen-prov").await;
+        seed_node(&storage, "sess-gen-prov", "root").await;
+        let mode = GraphMode::new(Arc::clone(&storage), fixed_client(mock_generate_response()));
+
+        mode.generate(Some("Parent"), None, Some("sess-gen-prov".to_string()))
+            .await
+            .expect("generate succeeds");
+
+        let child = storage
+            .get_graph_node("sess-gen-prov::c1")
+            .await
+            .expect("read child")
+            .expect("child exists");
+        assert_eq!(child.origin, Some(GraphNodeOrigin::Generate));
+        assert_eq!(child.parent_ids, vec!["sess-gen-prov::root".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_records_aggregate_provenance_with_inputs() {
+        use crate::storage::GraphNodeOrigin;
+
+        let storage = in_memory_storage().await;
+        seed_session(&storage, "sess-agg-prov").await;
+        seed_node(&storage, "sess-agg-prov", "c1").await;
+        seed_node(&storage, "sess-agg-prov", "c2").await;
+        let mode = GraphMode::new(
+            Arc::clone(&storage),
+            fixed_client(mock_aggregate_response()),
+        );
 
-        let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.score(Some("Node"), None, None).await;
+        mode.aggregate("Nodes", Some("sess-agg-prov".to_string()))
+            .await
+            .expect("aggregate succeeds");
 
-        assert!(result.is_ok());
+        let synthesis = storage
+            .get_graph_node("sess-agg-prov::s1")
+            .await
+            .expect("read synthesis")
+            .expect("synthesis exists");
+        assert_eq!(synthesis.origin, Some(GraphNodeOrigin::Aggregate));
+        assert_eq!(
+            synthesis.parent_ids,
+            vec![
+                "sess-agg-prov::c1".to_string(),
+                "sess-agg-prov::c2".to_string()
+            ]
+        );
     }
 
     #[tokio::test]
-    async fn test_aggregate_empty_content() {
-        let mock_storage = MockStorageTrait::new();
-        let mock_client = MockAnthropicClientTrait::new();
+    async fn test_prune_deletes_persisted_node() {
+        let storage = in_memory_storage().await;
+        seed_session(&storage, "sess-prune").await;
+        storage
+            .save_graph_node(&StoredGraphNode::new(
+                "sess-prune::bad_node",
+                "sess-prune",
+                "Weak node",
+            ))
+            .await
+            .expect("seed node");
 
-        let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.aggregate("", None).await;
+        let mode = GraphMode::new(Arc::clone(&storage), fixed_client(mock_prune_response()));
+        mode.prune("Graph", Some("sess-prune".to_string()), 0.3)
+            .await
+            .expect("prune succeeds");
 
-        assert!(matches!(result, Err(ModeError::MissingField { field }) if field == "content"));
+        let gone = storage
+            .get_graph_node("sess-prune::bad_node")
+            .await
+            .expect("read node");
+        assert!(gone.is_none(), "pruned node should be deleted from storage");
+    }
+
+    /// Seed a star graph: `hub` connected to three leaves, plus an isolated
+    /// node in its own (disconnected) component.
+    async fn seed_star_graph(storage: &Arc<SqliteStorage>, session_id: &str) {
+        seed_session(storage, session_id).await;
+        for node_id in ["hub", "l1", "l2", "l3", "iso"] {
+            seed_node(storage, session_id, node_id).await;
+        }
+        for leaf in ["l1", "l2", "l3"] {
+            seed_edge(storage, session_id, "hub", leaf).await;
+        }
     }
 
     #[tokio::test]
-    async fn test_aggregate_save_thought_error() {
-        let mut mock_storage = MockStorageTrait::new();
-        let mut mock_client = MockAnthropicClientTrait::new();
+    async fn test_compute_centrality_hub_highest_leaves_lowest() {
+        let storage = in_memory_storage().await;
+        seed_star_graph(&storage, "sess-central").await;
+        let mode = GraphMode::new(Arc::clone(&storage), MockAnthropicClientTrait::new());
 
-        mock_storage
-            .expect_get_or_create_session()
-            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
-        mock_storage.expect_save_thought().returning(|_| {
-            Err(StorageError::QueryFailed {
-                query: "INSERT".to_string(),
-                message: "Save failed".to_string(),
-            })
-        });
-        expect_graph_writes(&mut mock_storage);
+        let centrality = mode
+            .compute_centrality("sess-central")
+            .await
+            .expect("centrality");
 
-        let resp = mock_aggregate_response();
-        mock_client
-            .expect_complete()
-            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+        // 5 nodes → max degree 4. Hub: 3/4; each leaf: 1/4; isolated: 0.
+        assert!((centrality["hub"] - 0.75).abs() < f64::EPSILON);
+        for leaf in ["l1", "l2", "l3"] {
+            assert!((centrality[leaf] - 0.25).abs() < f64::EPSILON);
+        }
+        assert!(
+            centrality["iso"].abs() < f64::EPSILON,
+            "disconnected node scores 0.0"
+        );
+    }
 
-        let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.aggregate("Nodes", None).await;
+    #[tokio::test]
+    async fn test_compute_centrality_empty_and_single_node_graphs() {
+        let storage = in_memory_storage().await;
+        let mode = GraphMode::new(Arc::clone(&storage), MockAnthropicClientTrait::new());
 
-        assert!(result.is_ok());
+        // No stored graph at all → empty map, not an error.
+        let empty = mode
+            .compute_centrality("sess-none")
+            .await
+            .expect("centrality");
+        assert!(empty.is_empty());
+
+        // A single node has no possible connections — scored 0.0, not NaN.
+        seed_session(&storage, "sess-one").await;
+        seed_node(&storage, "sess-one", "only").await;
+        let single = mode
+            .compute_centrality("sess-one")
+            .await
+            .expect("centrality");
+        assert!(single["only"].abs() < f64::EPSILON);
     }
 
     #[tokio::test]
-    async fn test_refine_empty_content() {
-        let mock_storage = MockStorageTrait::new();
-        let mock_client = MockAnthropicClientTrait::new();
+    async fn test_state_surfaces_centrality() {
+        let storage = in_memory_storage().await;
+        seed_star_graph(&storage, "sess-state-central").await;
+        let mode = GraphMode::new(Arc::clone(&storage), fixed_client(mock_state_response()));
 
-        let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.refine("", None).await;
+        let resp = mode
+            .state(Some("Graph"), "sess-state-central")
+            .await
+            .expect("state succeeds");
 
-        assert!(matches!(result, Err(ModeError::MissingField { field }) if field == "content"));
+        assert_eq!(resp.centrality.len(), 5);
+        assert!(resp.centrality["hub"] > resp.centrality["l1"]);
     }
 
     #[tokio::test]
-    async fn test_refine_save_thought_error() {
-        let mut mock_storage = MockStorageTrait::new();
-        let mut mock_client = MockAnthropicClientTrait::new();
-
-        mock_storage
-            .expect_get_or_create_session()
-            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
-        mock_storage.expect_save_thought().returning(|_| {
-            Err(StorageError::QueryFailed {
-                query: "INSERT".to_string(),
-                message: "Save failed".to_string(),
-            })
-        });
-        expect_graph_writes(&mut mock_storage);
+    async fn test_prune_prompt_includes_centrality_ranking() {
+        let storage = in_memory_storage().await;
+        seed_star_graph(&storage, "sess-prune-central").await;
 
-        let resp = mock_refine_response();
-        mock_client
+        // The outgoing prompt must rank stored nodes by centrality, hub first.
+        let mut client = MockAnthropicClientTrait::new();
+        let resp = mock_prune_response();
+        client
             .expect_complete()
+            .withf(|messages, _| {
+                messages.first().is_some_and(|m| {
+                    m.content.contains("Structural centrality")
+                        && m.content.contains("- hub: 0.75")
+                        && m.content.contains("- iso: 0.00")
+                })
+            })
             .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
 
-        let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.refine("Node", None).await;
-
-        assert!(result.is_ok());
+        let mode = GraphMode::new(Arc::clone(&storage), client);
+        mode.prune("Graph", Some("sess-prune-central".to_string()), 0.3)
+            .await
+            .expect("prune succeeds");
     }
 
     #[tokio::test]
-    async fn test_prune_empty_content() {
-        let mock_storage = MockStorageTrait::new();
-        let mock_client = MockAnthropicClientTrait::new();
-
-        let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.prune("", None, 0.3).await;
+    async fn test_node_id_lookup_uses_namespaced_key() {
+        let storage = in_memory_storage().await;
+        seed_session(&storage, "sess-lookup").await;
+        // Persist a node, then drive generate by node_id (not content).
+        storage
+            .save_graph_node(&StoredGraphNode::new(
+                "sess-lookup::n1",
+                "sess-lookup",
+                "Stored parent content",
+            ))
+            .await
+            .expect("seed node");
 
-        assert!(matches!(result, Err(ModeError::MissingField { field }) if field == "content"));
+        let mode = GraphMode::new(Arc::clone(&storage), fixed_client(mock_generate_response()));
+        // Bare "n1" must resolve via the session-namespaced key "sess-lookup::n1".
+        let resp = mode
+            .generate(None, Some("n1"), Some("sess-lookup".to_string()))
+            .await
+            .expect("generate by node_id succeeds");
+        assert_eq!(resp.children.len(), 1);
     }
 
     #[tokio::test]
-    async fn test_prune_save_thought_error() {
-        let mut mock_storage = MockStorageTrait::new();
-        let mut mock_client = MockAnthropicClientTrait::new();
+    async fn test_state_reads_persisted_graph_with_stripped_ids() {
+        let storage = in_memory_storage().await;
+        // init then generate to build a small persisted graph.
+        GraphMode::new(Arc::clone(&storage), fixed_client(mock_init_response()))
+            .init("Topic", Some("sess-state".to_string()))
+            .await
+            .expect("init");
+        GraphMode::new(Arc::clone(&storage), fixed_client(mock_generate_response()))
+            .generate(Some("Parent"), None, Some("sess-state".to_string()))
+            .await
+            .expect("generate");
 
-        mock_storage
-            .expect_get_or_create_session()
-            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
-        mock_storage.expect_save_thought().returning(|_| {
-            Err(StorageError::QueryFailed {
-                query: "INSERT".to_string(),
-                message: "Save failed".to_string(),
-            })
-        });
-        expect_graph_writes(&mut mock_storage);
+        // build_graph_state_from_storage strips the namespace prefix for the model.
+        let json = GraphMode::new(Arc::clone(&storage), fixed_client(mock_state_response()))
+            .build_graph_state_from_storage("sess-state")
+            .await
+            .expect("build state");
+        assert!(json.contains("\"id\":\"root\""), "state JSON: {json}");
+        assert!(json.contains("\"id\":\"c1\""), "state JSON: {json}");
+        assert!(
+            !json.contains("sess-state::"),
+            "namespace prefix should be stripped: {json}"
+        );
+    }
 
-        let resp = mock_prune_response();
-        mock_client
-            .expect_complete()
-            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+    #[tokio::test]
+    async fn test_import_persists_nodes_and_edges() {
+        let storage = in_memory_storage().await;
+        // Import is deterministic, so the client is never called.
+        let mode = GraphMode::new(Arc::clone(&storage), MockAnthropicClientTrait::new());
+
+        let graph = r#"{
+            "nodes": [
+                {"id": "claim", "content": "Main claim", "score": 0.8},
+                {"id": "ev1", "content": "Supporting evidence"},
+                {"id": "concl", "content": "Conclusion", "is_terminal": true}
+            ],
+            "edges": [
+                {"from": "claim", "to": "ev1"},
+                {"from": "ev1", "to": "concl"}
+            ]
+        }"#;
 
-        let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.prune("Graph", None, 0.3).await;
+        let resp = mode
+            .import(graph, Some("sess-import".to_string()))
+            .await
+            .expect("import succeeds");
+        assert_eq!(resp.nodes.len(), 3);
+        assert_eq!(resp.edge_count, 2);
+        assert_eq!(resp.roots, vec!["claim".to_string()]);
 
-        assert!(result.is_ok());
+        // Stored under session-namespaced keys, with score/terminal preserved.
+        let nodes = storage
+            .get_graph_nodes("sess-import")
+            .await
+            .expect("read nodes");
+        assert_eq!(nodes.len(), 3);
+        let claim = nodes
+            .iter()
+            .find(|n| n.id == "sess-import::claim")
+            .expect("claim stored");
+        assert!((claim.score.unwrap() - 0.8).abs() < f64::EPSILON);
+        let concl = nodes
+            .iter()
+            .find(|n| n.id == "sess-import::concl")
+            .expect("conclusion stored");
+        assert!(concl.is_terminal);
+
+        let edges = storage
+            .get_graph_edges("sess-import")
+            .await
+            .expect("read edges");
+        assert_eq!(edges.len(), 2);
     }
 
     #[tokio::test]
-    async fn test_finalize_empty_content() {
-        let mock_storage = MockStorageTrait::new();
-        let mock_client = MockAnthropicClientTrait::new();
+    async fn test_import_rejects_dangling_edge() {
+        let storage = in_memory_storage().await;
+        let mode = GraphMode::new(Arc::clone(&storage), MockAnthropicClientTrait::new());
 
-        let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.finalize("", None).await;
+        let graph = r#"{
+            "nodes": [{"id": "a", "content": "A"}],
+            "edges": [{"from": "a", "to": "missing"}]
+        }"#;
 
-        assert!(matches!(result, Err(ModeError::MissingField { field }) if field == "content"));
+        let err = mode
+            .import(graph, Some("sess-dangling".to_string()))
+            .await
+            .expect_err("dangling edge rejected");
+        assert!(err.to_string().contains("unknown node 'missing'"), "{err}");
+
+        // Validation happens before persistence: nothing was written.
+        let nodes = storage
+            .get_graph_nodes("sess-dangling")
+            .await
+            .expect("read nodes");
+        assert!(nodes.is_empty());
     }
 
     #[tokio::test]
-    async fn test_finalize_save_thought_error() {
-        let mut mock_storage = MockStorageTrait::new();
-        let mut mock_client = MockAnthropicClientTrait::new();
-
-        mock_storage
-            .expect_get_or_create_session()
-            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
-        mock_storage.expect_save_thought().returning(|_| {
-            Err(StorageError::QueryFailed {
-                query: "INSERT".to_string(),
-                message: "Save failed".to_string(),
-            })
-        });
-        expect_graph_writes(&mut mock_storage);
+    async fn test_import_rejects_duplicate_node_ids() {
+        let mode = GraphMode::new(in_memory_storage().await, MockAnthropicClientTrait::new());
 
-        let resp = mock_finalize_response();
-        mock_client
-            .expect_complete()
-            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
-
-        let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.finalize("Graph", None).await;
+        let graph = r#"{
+            "nodes": [
+                {"id": "a", "content": "First"},
+                {"id": "a", "content": "Second"}
+            ],
+            "edges": []
+        }"#;
 
-        assert!(result.is_ok());
+        let err = mode
+            .import(graph, Some("sess-dup".to_string()))
+            .await
+            .expect_err("duplicate id rejected");
+        assert!(err.to_string().contains("duplicate node id 'a'"), "{err}");
     }
 
     #[tokio::test]
-    async fn test_state_save_thought_error() {
-        let mut mock_storage = MockStorageTrait::new();
-        let mut mock_client = MockAnthropicClientTrait::new();
+    async fn test_import_rejects_cycle() {
+        let mode = GraphMode::new(in_memory_storage().await, MockAnthropicClientTrait::new());
+
+        let graph = r#"{
+            "nodes": [
+                {"id": "a", "content": "A"},
+                {"id": "b", "content": "B"},
+                {"id": "c", "content": "C"}
+            ],
+            "edges": [
+                {"from": "a", "to": "b"},
+                {"from": "b", "to": "c"},
+                {"from": "c", "to": "a"}
+            ]
+        }"#;
+
+        let err = mode
+            .import(graph, Some("sess-cycle".to_string()))
+            .await
+            .expect_err("cycle rejected");
+        assert!(err.to_string().contains("cycle"), "{err}");
+    }
 
-        mock_storage
-            .expect_get_or_create_session()
-            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
-        mock_storage.expect_save_thought().returning(|_| {
-            Err(StorageError::QueryFailed {
-                query: "INSERT".to_string(),
-                message: "Save failed".to_string(),
-            })
-        });
-        expect_graph_writes(&mut mock_storage);
+    /// Persist a scored (optionally terminal) node under its namespaced key.
+    async fn seed_scored_node(
+        storage: &Arc<SqliteStorage>,
+        session_id: &str,
+        node_id: &str,
+        score: f64,
+        terminal: bool,
+    ) {
+        let mut node = StoredGraphNode::new(
+            format!("{session_id}::{node_id}"),
+            session_id,
+            format!("content {node_id}"),
+        )
+        .with_score(score);
+        if terminal {
+            node = node.as_terminal();
+        }
+        storage.save_graph_node(&node).await.expect("seed node");
+    }
 
-        let resp = mock_state_response();
-        mock_client
-            .expect_complete()
-            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+    /// Persist a Continues edge between two seeded nodes.
+    async fn seed_edge(storage: &Arc<SqliteStorage>, session_id: &str, from: &str, to: &str) {
+        storage
+            .save_graph_edge(&StoredGraphEdge::new(
+                format!("{session_id}::{from}->{to}"),
+                session_id,
+                format!("{session_id}::{from}"),
+                format!("{session_id}::{to}"),
+            ))
+            .await
+            .expect("seed edge");
+    }
 
-        let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.state(Some("Graph"), "test").await;
+    /// Build a client whose generate response is keyed off the parent content
+    /// in the request, so concurrent advance expansions stay distinguishable
+    /// regardless of completion order.
+    fn advance_client() -> MockAnthropicClientTrait {
+        let mut client = MockAnthropicClientTrait::new();
+        client.expect_complete().returning(|messages, _| {
+            let parent = if messages[0].content.contains("content f1") {
+                "f1"
+            } else {
+                "f2"
+            };
+            let resp = format!(
+                r#"{{
+                    "parent_id": "{parent}",
+                    "children": [
+                        {{"id": "{parent}-child", "content": "Child of {parent}", "score": 0.6, "type": "reasoning", "relationship": "elaborates"}}
+                    ],
+                    "generation_notes": "Expanded frontier"
+                }}"#
+            );
+            Ok(CompletionResponse::new(resp, Usage::new(100, 200)))
+        });
+        client
+    }
 
-        assert!(result.is_ok());
+    /// Seed root → {f1, f2, t1}: f1 and f2 are the non-terminal frontier
+    /// (scores 0.9 and 0.4), t1 is terminal and must never be expanded.
+    async fn seed_advance_graph(storage: &Arc<SqliteStorage>, session_id: &str) {
+        seed_session(storage, session_id).await;
+        seed_scored_node(storage, session_id, "root", 0.5, false).await;
+        seed_scored_node(storage, session_id, "f1", 0.9, false).await;
+        seed_scored_node(storage, session_id, "f2", 0.4, false).await;
+        seed_scored_node(storage, session_id, "t1", 0.95, true).await;
+        seed_edge(storage, session_id, "root", "f1").await;
+        seed_edge(storage, session_id, "root", "f2").await;
+        seed_edge(storage, session_id, "root", "t1").await;
     }
 
     #[tokio::test]
-    async fn test_generate_node_lookup_storage_error() {
-        let mut mock_storage = MockStorageTrait::new();
-        let mock_client = MockAnthropicClientTrait::new();
-
-        mock_storage
-            .expect_get_or_create_session()
-            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+    async fn test_advance_expands_top_frontier_only() {
+        let storage = in_memory_storage().await;
+        seed_advance_graph(&storage, "sess-adv").await;
+        let mode = GraphMode::new(Arc::clone(&storage), advance_client());
 
-        mock_storage.expect_get_graph_node().returning(|_id| {
-            Err(StorageError::QueryFailed {
-                query: "SELECT".to_string(),
-                message: "Lookup failed".to_string(),
-            })
-        });
+        let resp = mode.advance("sess-adv", 1).await.expect("advance succeeds");
 
-        let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.generate(None, Some("node-1"), None).await;
+        // Only the best-scoring non-terminal frontier is expanded — not the
+        // lower-scored f2 and not the higher-scored but terminal t1.
+        assert_eq!(resp.expanded.len(), 1);
+        assert_eq!(resp.expanded[0].node_id, "f1");
+        assert_eq!(resp.expanded[0].children[0].id, "f1-child");
+        assert_eq!(resp.nodes_added, 1);
+        assert_eq!(resp.persistence_failures, 0);
 
-        assert!(matches!(result, Err(ModeError::ApiUnavailable { .. })));
+        // The child was persisted under its namespaced key, linked to f1.
+        let nodes = storage.get_graph_nodes("sess-adv").await.expect("nodes");
+        assert!(nodes.iter().any(|n| n.id == "sess-adv::f1-child"));
+        assert!(!nodes.iter().any(|n| n.id == "sess-adv::f2-child"));
+        let edges = storage.get_graph_edges("sess-adv").await.expect("edges");
+        assert!(edges
+            .iter()
+            .any(|e| e.from_node_id == "sess-adv::f1" && e.to_node_id == "sess-adv::f1-child"));
     }
 
     #[tokio::test]
-    async fn test_state_get_nodes_storage_error() {
-        let mut mock_storage = MockStorageTrait::new();
-        let mock_client = MockAnthropicClientTrait::new();
-
-        mock_storage
-            .expect_get_or_create_session()
-            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+    async fn test_advance_expands_frontiers_ranked_by_score() {
+        let storage = in_memory_storage().await;
+        seed_advance_graph(&storage, "sess-adv2").await;
+        let mode = GraphMode::new(Arc::clone(&storage), advance_client());
 
-        mock_storage.expect_get_graph_nodes().returning(|_| {
-            Err(StorageError::QueryFailed {
-                query: "SELECT".to_string(),
-                message: "Get nodes failed".to_string(),
-            })
-        });
+        // max_nodes above the frontier size expands the whole frontier; the
+        // clamp to MAX_ADVANCE_NODES keeps the request bounded.
+        let resp = mode
+            .advance("sess-adv2", 50)
+            .await
+            .expect("advance succeeds");
 
-        let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.state(None, "test").await;
+        assert_eq!(resp.expanded.len(), 2);
+        assert_eq!(resp.expanded[0].node_id, "f1");
+        assert_eq!(resp.expanded[1].node_id, "f2");
+        assert_eq!(resp.nodes_added, 2);
 
-        assert!(matches!(result, Err(ModeError::ApiUnavailable { .. })));
+        let nodes = storage.get_graph_nodes("sess-adv2").await.expect("nodes");
+        assert!(nodes.iter().any(|n| n.id == "sess-adv2::f1-child"));
+        assert!(nodes.iter().any(|n| n.id == "sess-adv2::f2-child"));
     }
 
     #[tokio::test]
-    async fn test_state_get_edges_storage_error() {
-        use crate::storage::StoredGraphNode;
+    async fn test_advance_errors_without_stored_graph() {
+        let storage = in_memory_storage().await;
+        let mode = GraphMode::new(Arc::clone(&storage), MockAnthropicClientTrait::new());
 
-        let mut mock_storage = MockStorageTrait::new();
-        let mock_client = MockAnthropicClientTrait::new();
+        let err = mode
+            .advance("sess-empty", 3)
+            .await
+            .expect_err("empty graph rejected");
+        assert!(matches!(err, ModeError::InvalidValue { .. }));
+        assert!(err.to_string().contains("init or import"), "{err}");
+    }
 
-        mock_storage
-            .expect_get_or_create_session()
-            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+    #[tokio::test]
+    async fn test_advance_errors_when_frontier_all_terminal() {
+        let storage = in_memory_storage().await;
+        seed_session(&storage, "sess-done").await;
+        seed_scored_node(&storage, "sess-done", "root", 0.5, false).await;
+        seed_scored_node(&storage, "sess-done", "leaf", 0.8, true).await;
+        seed_edge(&storage, "sess-done", "root", "leaf").await;
+        let mode = GraphMode::new(Arc::clone(&storage), MockAnthropicClientTrait::new());
+
+        let err = mode
+            .advance("sess-done", 3)
+            .await
+            .expect_err("complete graph rejected");
+        assert!(err.to_string().contains("frontier"), "{err}");
+    }
 
-        mock_storage
-            .expect_get_graph_nodes()
-            .returning(|_| Ok(vec![StoredGraphNode::new("n1", "test", "Node 1")]));
+    /// Client whose generate completion is delayed per parent node, so
+    /// concurrent advance expansions complete in a known order that differs
+    /// from the ranked order.
+    struct DelayedAdvanceClient;
+
+    #[async_trait::async_trait]
+    impl AnthropicClientTrait for DelayedAdvanceClient {
+        async fn complete(
+            &self,
+            messages: Vec<Message>,
+            _config: CompletionConfig,
+        ) -> Result<CompletionResponse, ModeError> {
+            // Highest-ranked frontier is the slowest: ranked order f1, f2, f3
+            // must complete as f3, f2, f1.
+            let (parent, delay_ms) = if messages[0].content.contains("content f1") {
+                ("f1", 60)
+            } else if messages[0].content.contains("content f2") {
+                ("f2", 30)
+            } else {
+                ("f3", 5)
+            };
+            tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+            let resp = format!(
+                r#"{{
+                    "parent_id": "{parent}",
+                    "children": [
+                        {{"id": "{parent}-child", "content": "Child of {parent}", "score": 0.6, "type": "reasoning", "relationship": "elaborates"}}
+                    ],
+                    "generation_notes": "Expanded frontier"
+                }}"#
+            );
+            Ok(CompletionResponse::new(resp, Usage::new(100, 200)))
+        }
 
-        mock_storage.expect_get_graph_edges().returning(|_| {
-            Err(StorageError::QueryFailed {
-                query: "SELECT".to_string(),
-                message: "Get edges failed".to_string(),
+        async fn complete_streaming(
+            &self,
+            _messages: Vec<Message>,
+            _config: CompletionConfig,
+        ) -> Result<
+            tokio::sync::mpsc::Receiver<Result<crate::anthropic::StreamEvent, ModeError>>,
+            ModeError,
+        > {
+            Err(ModeError::ApiUnavailable {
+                message: "streaming not mocked".to_string(),
             })
-        });
+        }
+    }
 
-        let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.state(None, "test").await;
+    #[tokio::test]
+    async fn test_advance_observer_sees_frontiers_in_completion_order() {
+        let storage = in_memory_storage().await;
+        seed_session(&storage, "sess-obs").await;
+        seed_scored_node(&storage, "sess-obs", "root", 0.5, false).await;
+        seed_scored_node(&storage, "sess-obs", "f1", 0.9, false).await;
+        seed_scored_node(&storage, "sess-obs", "f2", 0.6, false).await;
+        seed_scored_node(&storage, "sess-obs", "f3", 0.3, false).await;
+        seed_edge(&storage, "sess-obs", "root", "f1").await;
+        seed_edge(&storage, "sess-obs", "root", "f2").await;
+        seed_edge(&storage, "sess-obs", "root", "f3").await;
+        let mode = GraphMode::new(Arc::clone(&storage), DelayedAdvanceClient);
+
+        let emissions: std::sync::Mutex<Vec<(String, usize)>> = std::sync::Mutex::new(Vec::new());
+        let observer = |frontier: &ExpandedFrontier| {
+            emissions
+                .lock()
+                .expect("emissions lock")
+                .push((frontier.node_id.clone(), frontier.children.len()));
+        };
 
-        assert!(matches!(result, Err(ModeError::ApiUnavailable { .. })));
+        let resp = mode
+            .advance_with_observer("sess-obs", 3, Some(&observer))
+            .await
+            .expect("advance succeeds");
+
+        // Observer heard each frontier as it finished: fastest first.
+        let seen = emissions.lock().expect("emissions lock").clone();
+        assert_eq!(
+            seen,
+            vec![
+                ("f3".to_string(), 1),
+                ("f2".to_string(), 1),
+                ("f1".to_string(), 1)
+            ]
+        );
+
+        // The response itself stays in ranked order regardless.
+        let order: Vec<&str> = resp.expanded.iter().map(|f| f.node_id.as_str()).collect();
+        assert_eq!(order, ["f1", "f2", "f3"]);
+        assert_eq!(resp.nodes_added, 3);
+
+        // Each frontier's children were persisted before its emission.
+        let nodes = storage.get_graph_nodes("sess-obs").await.expect("nodes");
+        for child in ["f1-child", "f2-child", "f3-child"] {
+            assert!(nodes.iter().any(|n| n.id == format!("sess-obs::{child}")));
+        }
     }
 
     #[tokio::test]
-    async fn test_score_with_node_id_lookup() {
-        use crate::storage::StoredGraphNode;
+    async fn test_advance_without_observer_unchanged() {
+        let storage = in_memory_storage().await;
+        seed_advance_graph(&storage, "sess-noobs").await;
+        let mode = GraphMode::new(Arc::clone(&storage), advance_client());
 
-        let mut mock_storage = MockStorageTrait::new();
-        let mut mock_client = MockAnthropicClientTrait::new();
+        let resp = mode
+            .advance_with_observer("sess-noobs", 2, None)
+            .await
+            .expect("advance succeeds");
 
-        mock_storage
-            .expect_get_or_create_session()
-            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
-        mock_storage.expect_save_thought().returning(|_| Ok(()));
-        expect_graph_writes(&mut mock_storage);
+        assert_eq!(resp.expanded.len(), 2);
+        assert_eq!(resp.expanded[0].node_id, "f1");
+        assert_eq!(resp.expanded[1].node_id, "f2");
+    }
 
-        mock_storage.expect_get_graph_node().returning(|_id| {
-            Ok(Some(StoredGraphNode::new(
-                "node-1",
-                "test",
-                "Stored node content",
-            )))
-        });
+    // ========================================================================
+    // Node-limit tests (end-to-end with in-memory SQLite)
+    // ========================================================================
 
-        let resp = mock_score_response();
-        mock_client
-            .expect_complete()
-            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+    #[tokio::test]
+    async fn test_generate_rejected_at_node_limit_until_prune() {
+        let storage = in_memory_storage().await;
+        seed_session(&storage, "sess-full").await;
+        seed_node(&storage, "sess-full", "root").await;
+        seed_node(&storage, "sess-full", "bad_node").await;
 
-        let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.score(None, Some("node-1"), None).await;
+        let mode = GraphMode::new(Arc::clone(&storage), fixed_client(mock_generate_response()))
+            .with_max_graph_nodes(2);
 
-        assert!(result.is_ok());
+        // At the ceiling: generate is rejected before any API call.
+        let err = mode
+            .generate(Some("Parent content"), None, Some("sess-full".to_string()))
+            .await
+            .expect_err("node limit rejects generate");
+        match &err {
+            ModeError::InvalidValue { field, reason } => {
+                assert_eq!(field, "graph");
+                assert!(reason.contains("node limit reached"), "{reason}");
+                assert!(reason.contains("prune"), "{reason}");
+            }
+            other => panic!("expected InvalidValue, got {other:?}"),
+        }
+
+        // Prune removes the low-quality node (the mock names `bad_node`)...
+        let prune_mode = GraphMode::new(Arc::clone(&storage), fixed_client(mock_prune_response()));
+        prune_mode
+            .prune("Graph state", Some("sess-full".to_string()), 0.3)
+            .await
+            .expect("prune succeeds");
+        let nodes = storage.get_graph_nodes("sess-full").await.expect("nodes");
+        assert_eq!(nodes.len(), 1);
+
+        // ...and generate is accepted again.
+        let resp = mode
+            .generate(Some("Parent content"), None, Some("sess-full".to_string()))
+            .await
+            .expect("generate succeeds below the limit");
+        assert_eq!(resp.children.len(), 1);
     }
 
     #[tokio::test]
-    async fn test_score_missing_content_and_node_id() {
-        let mut mock_storage = MockStorageTrait::new();
-        let mock_client = MockAnthropicClientTrait::new();
+    async fn test_generate_allowed_below_node_limit() {
+        let storage = in_memory_storage().await;
+        seed_session(&storage, "sess-room").await;
+        seed_node(&storage, "sess-room", "root").await;
 
-        mock_storage
-            .expect_get_or_create_session()
-            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        let mode = GraphMode::new(Arc::clone(&storage), fixed_client(mock_generate_response()))
+            .with_max_graph_nodes(5);
 
-        let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.score(None, None, None).await;
+        let resp = mode
+            .generate(Some("Parent content"), None, Some("sess-room".to_string()))
+            .await
+            .expect("generate succeeds");
+        assert_eq!(resp.children.len(), 1);
+    }
 
-        assert!(
-            matches!(result, Err(ModeError::MissingField { field }) if field == "content or node_id")
+    // ========================================================================
+    // Snapshot / Restore Tests (real storage: restore rewrites the graph)
+    // ========================================================================
+
+    /// In-memory storage seeded with a session and a three-node, two-edge graph.
+    async fn seeded_graph_storage(
+        session_id: &str,
+    ) -> std::sync::Arc<crate::storage::SqliteStorage> {
+        let storage = std::sync::Arc::new(
+            crate::storage::SqliteStorage::new_in_memory()
+                .await
+                .expect("create storage"),
         );
+        storage
+            .get_or_create_session(Some(session_id.to_string()))
+            .await
+            .expect("create session");
+        for node_id in ["n1", "n2", "n3"] {
+            let node = StoredGraphNode::new(
+                format!("{session_id}::{node_id}"),
+                session_id,
+                format!("Node {node_id}"),
+            )
+            .with_score(0.5);
+            storage.save_graph_node(&node).await.expect("save node");
+        }
+        for (from, to) in [("n1", "n2"), ("n2", "n3")] {
+            let edge = StoredGraphEdge::new(
+                format!("{session_id}::{from}->{to}"),
+                session_id,
+                format!("{session_id}::{from}"),
+                format!("{session_id}::{to}"),
+            );
+            storage.save_graph_edge(&edge).await.expect("save edge");
+        }
+        storage
     }
 
     #[tokio::test]
-    async fn test_generate_empty_content_with_node_id() {
-        use crate::storage::StoredGraphNode;
+    async fn test_snapshot_and_restore_brings_pruned_nodes_back() {
+        let session_id = "snap-session";
+        let storage = seeded_graph_storage(session_id).await;
+        let mode = GraphMode::new(
+            std::sync::Arc::clone(&storage),
+            MockAnthropicClientTrait::new(),
+        );
 
-        let mut mock_storage = MockStorageTrait::new();
-        let mut mock_client = MockAnthropicClientTrait::new();
+        let snapshot = mode.snapshot(session_id).await.expect("snapshot succeeds");
+        assert_eq!(snapshot.session_id, session_id);
+        assert_eq!(snapshot.node_count, 3);
+        assert_eq!(snapshot.edge_count, 2);
+
+        // Simulate an aggressive prune: drop both edges and two of three nodes.
+        for edge_id in [
+            format!("{session_id}::n1->n2"),
+            format!("{session_id}::n2->n3"),
+        ] {
+            storage
+                .delete_graph_edge(&edge_id)
+                .await
+                .expect("delete edge");
+        }
+        for node_id in [format!("{session_id}::n2"), format!("{session_id}::n3")] {
+            storage
+                .delete_graph_node(&node_id)
+                .await
+                .expect("delete node");
+        }
+        assert_eq!(storage.get_graph_nodes(session_id).await.unwrap().len(), 1);
 
-        mock_storage
-            .expect_get_or_create_session()
-            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
-        mock_storage.expect_save_thought().returning(|_| Ok(()));
-        expect_graph_writes(&mut mock_storage);
+        let restored = mode
+            .restore_snapshot(session_id, &snapshot.snapshot_id)
+            .await
+            .expect("restore succeeds");
+        assert_eq!(restored.restored_nodes, 3);
+        assert_eq!(restored.restored_edges, 2);
+        assert_eq!(restored.removed_nodes, 1);
+
+        let nodes = storage.get_graph_nodes(session_id).await.unwrap();
+        let ids: Vec<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+        assert!(ids.contains(&format!("{session_id}::n2").as_str()));
+        assert!(ids.contains(&format!("{session_id}::n3").as_str()));
+        assert_eq!(storage.get_graph_edges(session_id).await.unwrap().len(), 2);
+    }
 
-        // Providing empty content should fall back to node_id lookup
-        mock_storage.expect_get_graph_node().returning(|_id| {
-            Ok(Some(StoredGraphNode::new(
-                "node-1",
-                "test",
-                "Stored node content",
-            )))
-        });
+    #[tokio::test]
+    async fn test_restore_replaces_nodes_added_after_snapshot() {
+        let session_id = "snap-replace";
+        let storage = seeded_graph_storage(session_id).await;
+        let mode = GraphMode::new(
+            std::sync::Arc::clone(&storage),
+            MockAnthropicClientTrait::new(),
+        );
 
-        let resp = mock_generate_response();
-        mock_client
-            .expect_complete()
-            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+        let snapshot = mode.snapshot(session_id).await.expect("snapshot succeeds");
 
-        let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.generate(Some("  "), Some("node-1"), None).await;
+        let late_node = StoredGraphNode::new(format!("{session_id}::n4"), session_id, "Late node");
+        storage
+            .save_graph_node(&late_node)
+            .await
+            .expect("save node");
 
-        assert!(result.is_ok());
+        let restored = mode
+            .restore_snapshot(session_id, &snapshot.snapshot_id)
+            .await
+            .expect("restore succeeds");
+        assert_eq!(restored.removed_nodes, 4);
+
+        let nodes = storage.get_graph_nodes(session_id).await.unwrap();
+        assert_eq!(nodes.len(), 3);
+        assert!(!nodes.iter().any(|n| n.id.ends_with("::n4")));
     }
 
     #[tokio::test]
-    async fn test_state_with_empty_content() {
-        use crate::storage::{StoredGraphEdge, StoredGraphNode};
+    async fn test_snapshot_without_graph_rejected() {
+        let storage = std::sync::Arc::new(
+            crate::storage::SqliteStorage::new_in_memory()
+                .await
+                .expect("create storage"),
+        );
+        let mode = GraphMode::new(storage, MockAnthropicClientTrait::new());
+        let result = mode.snapshot("empty-session").await;
+        assert!(
+            matches!(result, Err(ModeError::InvalidValue { field, .. }) if field == "session_id")
+        );
+    }
 
-        let mut mock_storage = MockStorageTrait::new();
-        let mut mock_client = MockAnthropicClientTrait::new();
+    #[tokio::test]
+    async fn test_restore_unknown_snapshot_rejected() {
+        let session_id = "snap-missing";
+        let storage = seeded_graph_storage(session_id).await;
+        let mode = GraphMode::new(storage, MockAnthropicClientTrait::new());
+        let result = mode.restore_snapshot(session_id, "cp_nonexistent").await;
+        assert!(
+            matches!(result, Err(ModeError::InvalidValue { field, .. }) if field == "snapshot_id")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_rejects_snapshot_from_other_session() {
+        let session_id = "snap-owner";
+        let storage = seeded_graph_storage(session_id).await;
+        let mode = GraphMode::new(
+            std::sync::Arc::clone(&storage),
+            MockAnthropicClientTrait::new(),
+        );
+        let snapshot = mode.snapshot(session_id).await.expect("snapshot succeeds");
+
+        let result = mode
+            .restore_snapshot("some-other-session", &snapshot.snapshot_id)
+            .await;
+        assert!(
+            matches!(result, Err(ModeError::InvalidValue { field, .. }) if field == "snapshot_id")
+        );
+    }
+
+    /// Mock storage serving the given namespaced nodes and edges for `test`.
+    fn tree_view_storage(node_ids: &[&str], edge_pairs: &[(&str, &str)]) -> MockStorageTrait {
+        let nodes: Vec<StoredGraphNode> = node_ids
+            .iter()
+            .map(|id| StoredGraphNode::new(format!("test::{id}"), "test", format!("Node {id}")))
+            .collect();
+        let edges: Vec<StoredGraphEdge> = edge_pairs
+            .iter()
+            .map(|(from, to)| {
+                StoredGraphEdge::new(
+                    format!("test::{from}->{to}"),
+                    "test",
+                    format!("test::{from}"),
+                    format!("test::{to}"),
+                )
+            })
+            .collect();
 
+        let mut mock_storage = MockStorageTrait::new();
         mock_storage
             .expect_get_or_create_session()
             .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
-        mock_storage.expect_save_thought().returning(|_| Ok(()));
-        expect_graph_writes(&mut mock_storage);
-
-        // Providing empty content should retrieve from storage
         mock_storage
             .expect_get_graph_nodes()
-            .returning(|_| Ok(vec![StoredGraphNode::new("n1", "test", "Node 1")]));
+            .returning(move |_| Ok(nodes.clone()));
         mock_storage
             .expect_get_graph_edges()
-            .returning(|_| Ok(vec![StoredGraphEdge::new("e1", "test", "n1", "n2")]));
-
-        let resp = mock_state_response();
-        mock_client
-            .expect_complete()
-            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
-
-        let mode = GraphMode::new(mock_storage, mock_client);
-        let result = mode.state(Some("  "), "test").await;
-
-        assert!(result.is_ok());
+            .returning(move |_| Ok(edges.clone()));
+        mock_storage
     }
 
-    // ========================================================================
-    // End-to-end persistence tests (real in-memory storage)
-    //
-    // These verify the graph is actually written to and read back from storage,
-    // with per-session node-ID namespacing, which the mocked tests above cannot.
-    // ========================================================================
+    #[tokio::test]
+    async fn test_tree_view_diamond_drops_redundant_edge() {
+        // Diamond: a → b, a → c, b → d, c → d. BFS keeps the first edge into
+        // d (b → d) and reports c → d as the one omitted relationship.
+        let storage = tree_view_storage(
+            &["a", "b", "c", "d"],
+            &[("a", "b"), ("a", "c"), ("b", "d"), ("c", "d")],
+        );
+        let mode = GraphMode::new(storage, MockAnthropicClientTrait::new());
+
+        let response = mode.tree_view("test").await.expect("tree view");
+
+        assert_eq!(response.roots, vec!["a"]);
+        assert_eq!(response.nodes.len(), 4);
+        let find = |id: &str| {
+            response
+                .nodes
+                .iter()
+                .find(|n| n.id == id)
+                .unwrap_or_else(|| panic!("node {id} missing"))
+        };
+        assert_eq!(find("a").depth, 0);
+        assert_eq!(find("a").parent_id, None);
+        assert_eq!(find("b").parent_id.as_deref(), Some("a"));
+        assert_eq!(find("c").parent_id.as_deref(), Some("a"));
+        assert_eq!(find("d").parent_id.as_deref(), Some("b"));
+        assert_eq!(find("d").depth, 2);
+        assert_eq!(
+            response.omitted_edges,
+            vec![OmittedEdge {
+                from: "c".to_string(),
+                to: "d".to_string(),
+            }]
+        );
+    }
 
-    use std::sync::Arc;
+    #[tokio::test]
+    async fn test_tree_view_forest_with_multiple_roots() {
+        // Two disconnected components: every edge fits, no omissions.
+        let storage = tree_view_storage(&["a", "b", "c", "d"], &[("a", "b"), ("c", "d")]);
+        let mode = GraphMode::new(storage, MockAnthropicClientTrait::new());
 
-    use crate::storage::SqliteStorage;
+        let response = mode.tree_view("test").await.expect("tree view");
 
-    /// Build a mock client that returns `resp` for every completion call.
-    fn fixed_client(resp: String) -> MockAnthropicClientTrait {
-        let mut client = MockAnthropicClientTrait::new();
-        client
-            .expect_complete()
-            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
-        client
+        assert_eq!(response.roots, vec!["a", "c"]);
+        assert!(response.omitted_edges.is_empty());
+        assert_eq!(response.nodes.len(), 4);
     }
 
-    async fn in_memory_storage() -> Arc<SqliteStorage> {
-        Arc::new(
-            SqliteStorage::new_in_memory()
+    #[tokio::test]
+    async fn test_tree_view_promotes_cycle_component_to_root() {
+        // x ⇄ y has no natural root; x (stored first) is promoted, and the
+        // back edge y → x is reported rather than rendered.
+        let storage = tree_view_storage(&["x", "y"], &[("x", "y"), ("y", "x")]);
+        let mode = GraphMode::new(storage, MockAnthropicClientTrait::new());
+
+        let response = mode.tree_view("test").await.expect("tree view");
+
+        assert_eq!(response.roots, vec!["x"]);
+        assert_eq!(response.nodes.len(), 2);
+        assert_eq!(
+            response.omitted_edges,
+            vec![OmittedEdge {
+                from: "y".to_string(),
+                to: "x".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tree_view_without_graph_rejected() {
+        let storage = std::sync::Arc::new(
+            crate::storage::SqliteStorage::new_in_memory()
                 .await
-                .expect("create in-memory storage"),
-        )
+                .expect("create storage"),
+        );
+        let mode = GraphMode::new(storage, MockAnthropicClientTrait::new());
+        let result = mode.tree_view("empty-session").await;
+        assert!(
+            matches!(result, Err(ModeError::InvalidValue { field, .. }) if field == "session_id")
+        );
     }
 
-    /// Create the session row (foreign keys are enforced, so nodes/edges need it).
-    async fn seed_session(storage: &Arc<SqliteStorage>, session_id: &str) {
-        storage
-            .get_or_create_session(Some(session_id.to_string()))
-            .await
-            .expect("create session");
+    fn apply_prune_storage() -> MockStorageTrait {
+        let mut mock = MockStorageTrait::new();
+        mock.expect_get_graph_nodes()
+            .returning(|_| Ok(stored_graph("test").0));
+        mock.expect_get_graph_edges()
+            .returning(|_| Ok(stored_graph("test").1));
+        mock
     }
 
-    /// Persist a node under its session-namespaced key so edges can reference it.
-    async fn seed_node(storage: &Arc<SqliteStorage>, session_id: &str, node_id: &str) {
-        storage
-            .save_graph_node(&StoredGraphNode::new(
-                format!("{session_id}::{node_id}"),
-                session_id,
-                "seed content",
-            ))
+    #[tokio::test]
+    async fn test_apply_prune_protects_best_path_node() {
+        // Best path by stored scores is root -> c1; c1 must survive a
+        // non-forced prune and nothing may be deleted.
+        let mut storage = apply_prune_storage();
+        storage.expect_delete_graph_edge().times(0);
+        storage.expect_delete_graph_node().times(0);
+        let mode = GraphMode::new(storage, MockAnthropicClientTrait::new());
+
+        let response = mode
+            .apply_prune("test", &["c1".to_string()], false)
             .await
-            .expect("seed node");
+            .expect("apply prune");
+
+        assert!(response.removed.is_empty());
+        assert_eq!(response.protected.len(), 1);
+        assert_eq!(response.protected[0].node_id, "c1");
+        assert_eq!(response.protected[0].reason, "on current best path");
+        assert!(!response.forced);
     }
 
     #[tokio::test]
-    async fn test_init_persists_root_node() {
-        let storage = in_memory_storage().await;
-        let mode = GraphMode::new(Arc::clone(&storage), fixed_client(mock_init_response()));
+    async fn test_apply_prune_force_removes_best_path_node() {
+        let mut storage = apply_prune_storage();
+        storage
+            .expect_delete_graph_edge()
+            .withf(|id| id == "test::root->c1")
+            .times(1)
+            .returning(|_| Ok(()));
+        storage
+            .expect_delete_graph_node()
+            .withf(|id| id == "test::c1")
+            .times(1)
+            .returning(|_| Ok(()));
+        let mode = GraphMode::new(storage, MockAnthropicClientTrait::new());
 
-        let resp = mode
-            .init("Topic", Some("sess-init".to_string()))
+        let response = mode
+            .apply_prune("test", &["c1".to_string()], true)
             .await
-            .expect("init succeeds");
-        assert_eq!(resp.root.id, "root");
+            .expect("apply prune");
 
-        let nodes = storage
-            .get_graph_nodes("sess-init")
-            .await
-            .expect("read nodes");
-        assert_eq!(nodes.len(), 1);
-        // Stored under its session-namespaced key, not the bare model ID.
-        assert_eq!(nodes[0].id, "sess-init::root");
-        assert_eq!(nodes[0].content, "Main topic");
-        assert!((nodes[0].score.unwrap() - 0.5).abs() < f64::EPSILON);
+        assert_eq!(response.removed, vec!["c1"]);
+        assert!(response.protected.is_empty());
+        assert!(response.forced);
     }
 
     #[tokio::test]
-    async fn test_generate_persists_children_and_edges() {
-        let storage = in_memory_storage().await;
-        seed_session(&storage, "sess-gen").await;
-        // Parent must exist before the child edge can reference it (FK enforced).
-        seed_node(&storage, "sess-gen", "root").await;
-        let mode = GraphMode::new(Arc::clone(&storage), fixed_client(mock_generate_response()));
+    async fn test_apply_prune_protects_root_and_reports_missing() {
+        // root is a graph root (no incoming edge) and protected; c2 is off
+        // the best path and removable; ghost is not in the graph at all.
+        let mut storage = apply_prune_storage();
+        storage
+            .expect_delete_graph_edge()
+            .withf(|id| id == "test::root->c2")
+            .times(1)
+            .returning(|_| Ok(()));
+        storage
+            .expect_delete_graph_node()
+            .withf(|id| id == "test::c2")
+            .times(1)
+            .returning(|_| Ok(()));
+        let mode = GraphMode::new(storage, MockAnthropicClientTrait::new());
 
-        let resp = mode
-            .generate(Some("Parent"), None, Some("sess-gen".to_string()))
+        let response = mode
+            .apply_prune(
+                "test",
+                &["root".to_string(), "c2".to_string(), "ghost".to_string()],
+                false,
+            )
             .await
-            .expect("generate succeeds");
-        // Parent exists, so everything persisted.
-        assert_eq!(resp.persistence_failures, 0);
-
-        let nodes = storage.get_graph_nodes("sess-gen").await.expect("nodes");
-        assert!(nodes.iter().any(|n| n.id == "sess-gen::c1"));
+            .expect("apply prune");
 
-        let edges = storage.get_graph_edges("sess-gen").await.expect("edges");
-        assert_eq!(edges.len(), 1);
-        assert_eq!(edges[0].from_node_id, "sess-gen::root");
-        assert_eq!(edges[0].to_node_id, "sess-gen::c1");
+        assert_eq!(response.removed, vec!["c2"]);
+        assert_eq!(response.protected.len(), 1);
+        assert_eq!(response.protected[0].node_id, "root");
+        assert_eq!(response.protected[0].reason, "graph root");
+        assert_eq!(response.missing, vec!["ghost"]);
     }
 
     #[tokio::test]
-    async fn test_generate_reports_persistence_failure_for_missing_parent() {
-        let storage = in_memory_storage().await;
-        seed_session(&storage, "sess-orphan").await;
-        // Deliberately do NOT seed the parent "root" node, so the child→parent
-        // edge violates the foreign key and is dropped.
-        let mode = GraphMode::new(Arc::clone(&storage), fixed_client(mock_generate_response()));
-
-        let resp = mode
-            .generate(Some("Parent"), None, Some("sess-orphan".to_string()))
-            .await
-            .expect("generate still returns the reasoning result");
+    async fn test_apply_prune_without_graph_rejected() {
+        let mut storage = MockStorageTrait::new();
+        storage.expect_get_graph_nodes().returning(|_| Ok(vec![]));
+        let mode = GraphMode::new(storage, MockAnthropicClientTrait::new());
 
-        // The child node persists, but the edge to the missing parent fails.
-        assert_eq!(resp.persistence_failures, 1);
-        let edges = storage.get_graph_edges("sess-orphan").await.expect("edges");
+        let result = mode.apply_prune("empty", &["c1".to_string()], false).await;
         assert!(
-            edges.is_empty(),
-            "edge to a missing parent must not persist"
+            matches!(result, Err(ModeError::InvalidValue { field, .. }) if field == "session_id")
         );
     }
 
     #[tokio::test]
-    async fn test_score_updates_persisted_node() {
+    async fn test_merge_graphs_combines_both_sources() {
         let storage = in_memory_storage().await;
-        seed_session(&storage, "sess-score").await;
-        // Seed the node the score response refers to ("c1") under the session.
-        storage
-            .save_graph_node(
-                &StoredGraphNode::new("sess-score::c1", "sess-score", "Node").with_score(0.1),
-            )
-            .await
-            .expect("seed node");
-
-        let mode = GraphMode::new(Arc::clone(&storage), fixed_client(mock_score_response()));
-        mode.score(Some("Node"), None, Some("sess-score".to_string()))
-            .await
-            .expect("score succeeds");
+        seed_session(&storage, "sess-ma").await;
+        seed_session(&storage, "sess-mb").await;
+        for node in [
+            StoredGraphNode::new("a-root", "sess-ma", "Root question").with_score(0.9),
+            StoredGraphNode::new("a-1", "sess-ma", "Alpha branch insight")
+                .with_score(0.7)
+                .with_provenance(GraphNodeOrigin::Generate, vec!["a-root".to_string()]),
+            StoredGraphNode::new("b-root", "sess-mb", "Parallel root").with_score(0.8),
+            StoredGraphNode::new("b-1", "sess-mb", "Beta branch insight").with_score(0.5),
+        ] {
+            storage.save_graph_node(&node).await.expect("seed node");
+        }
+        for edge in [
+            StoredGraphEdge::new("e-a1", "sess-ma", "a-root", "a-1"),
+            StoredGraphEdge::new("e-b1", "sess-mb", "b-root", "b-1"),
+        ] {
+            storage.save_graph_edge(&edge).await.expect("seed edge");
+        }
+        let mode = GraphMode::new(Arc::clone(&storage), MockAnthropicClientTrait::new());
 
-        let node = storage
-            .get_graph_node("sess-score::c1")
+        let response = mode
+            .merge_graphs("sess-ma", "sess-mb", Some("sess-mt".to_string()), None)
             .await
-            .expect("read node")
-            .expect("node exists");
-        assert!((node.score.unwrap() - 0.65).abs() < 0.01);
-    }
+            .expect("merge succeeds");
 
-    #[tokio::test]
-    async fn test_aggregate_persists_synthesis_and_edges() {
-        let storage = in_memory_storage().await;
-        seed_session(&storage, "sess-agg").await;
-        // Input nodes must exist before aggregation edges can reference them.
-        seed_node(&storage, "sess-agg", "c1").await;
-        seed_node(&storage, "sess-agg", "c2").await;
-        let mode = GraphMode::new(
-            Arc::clone(&storage),
-            fixed_client(mock_aggregate_response()),
-        );
+        assert_eq!(response.node_count, 4);
+        assert_eq!(response.edge_count, 2);
+        assert!(response.deduplicated.is_empty());
+        assert!(response.omitted_edges.is_empty());
 
-        mode.aggregate("Nodes", Some("sess-agg".to_string()))
+        let nodes = storage
+            .get_graph_nodes("sess-mt")
             .await
-            .expect("aggregate succeeds");
-
-        let synthesis = storage
-            .get_graph_node("sess-agg::s1")
+            .expect("read nodes");
+        let edges = storage
+            .get_graph_edges("sess-mt")
             .await
-            .expect("read synthesis")
-            .expect("synthesis exists");
-        assert_eq!(synthesis.node_type, GraphNodeType::Aggregation);
-
-        // One edge from each input node ("c1", "c2") into the synthesis.
-        let edges = storage.get_graph_edges("sess-agg").await.expect("edges");
+            .expect("read edges");
+        assert_eq!(nodes.len(), 4);
         assert_eq!(edges.len(), 2);
-        assert!(edges.iter().all(|e| e.to_node_id == "sess-agg::s1"));
+        // Ids are remapped into the target namespace; scores and provenance
+        // (with remapped parents) come along.
+        let merged = nodes
+            .iter()
+            .find(|n| n.id == "sess-mt::a-1")
+            .expect("remapped a-1");
+        assert!((merged.score.unwrap() - 0.7).abs() < f64::EPSILON);
+        assert_eq!(merged.origin, Some(GraphNodeOrigin::Generate));
+        assert_eq!(merged.parent_ids, vec!["sess-mt::a-root"]);
+        // The sources are left untouched.
+        assert_eq!(
+            storage
+                .get_graph_nodes("sess-ma")
+                .await
+                .expect("read source")
+                .len(),
+            2
+        );
     }
 
     #[tokio::test]
-    async fn test_refine_persists_refined_node() {
+    async fn test_merge_graphs_dedups_near_identical_nodes_and_omits_cycle() {
+        // A holds x → y; B holds the same two nodes (modulo case) linked the
+        // other way. With dedup on, B folds onto A and its reversed edge
+        // would close a cycle — so it is omitted, not written.
         let storage = in_memory_storage().await;
-        seed_session(&storage, "sess-refine").await;
-        // Original node must exist before the refinement edge can reference it.
-        seed_node(&storage, "sess-refine", "c1").await;
-        let mode = GraphMode::new(Arc::clone(&storage), fixed_client(mock_refine_response()));
+        seed_session(&storage, "sess-da").await;
+        seed_session(&storage, "sess-db").await;
+        for node in [
+            StoredGraphNode::new("a-x", "sess-da", "The cache layer is stale").with_score(0.6),
+            StoredGraphNode::new("a-y", "sess-da", "Invalidate on write").with_score(0.7),
+            StoredGraphNode::new("b-x", "sess-db", "the cache layer is stale").with_score(0.9),
+            StoredGraphNode::new("b-y", "sess-db", "invalidate on write").with_score(0.4),
+        ] {
+            storage.save_graph_node(&node).await.expect("seed node");
+        }
+        for edge in [
+            StoredGraphEdge::new("e-a", "sess-da", "a-x", "a-y"),
+            StoredGraphEdge::new("e-b", "sess-db", "b-y", "b-x"),
+        ] {
+            storage.save_graph_edge(&edge).await.expect("seed edge");
+        }
+        let mode = GraphMode::new(Arc::clone(&storage), MockAnthropicClientTrait::new());
 
-        mode.refine("Node", Some("sess-refine".to_string()))
+        let response = mode
+            .merge_graphs("sess-da", "sess-db", Some("sess-dt".to_string()), Some(0.9))
             .await
-            .expect("refine succeeds");
+            .expect("merge succeeds");
+
+        assert_eq!(response.node_count, 2);
+        assert_eq!(response.edge_count, 1);
+        assert_eq!(response.deduplicated.len(), 2);
+        assert_eq!(
+            response.omitted_edges,
+            vec![OmittedEdge {
+                from: "sess-dt::a-y".to_string(),
+                to: "sess-dt::a-x".to_string(),
+            }]
+        );
 
-        let refined = storage
-            .get_graph_node("sess-refine::r1")
+        let nodes = storage
+            .get_graph_nodes("sess-dt")
             .await
-            .expect("read refined")
-            .expect("refined exists");
-        assert_eq!(refined.node_type, GraphNodeType::Refinement);
-
-        let edges = storage.get_graph_edges("sess-refine").await.expect("edges");
+            .expect("read nodes");
+        // The fold keeps the higher score from either source.
+        let kept = nodes
+            .iter()
+            .find(|n| n.id == "sess-dt::a-x")
+            .expect("kept node");
+        assert!((kept.score.unwrap() - 0.9).abs() < f64::EPSILON);
+        let edges = storage
+            .get_graph_edges("sess-dt")
+            .await
+            .expect("read edges");
         assert_eq!(edges.len(), 1);
-        assert_eq!(edges[0].from_node_id, "sess-refine::c1");
-        assert_eq!(edges[0].to_node_id, "sess-refine::r1");
+        assert_eq!(edges[0].from_node_id, "sess-dt::a-x");
+        assert_eq!(edges[0].to_node_id, "sess-dt::a-y");
     }
 
     #[tokio::test]
-    async fn test_prune_deletes_persisted_node() {
+    async fn test_merge_graphs_rejects_bad_inputs() {
         let storage = in_memory_storage().await;
-        seed_session(&storage, "sess-prune").await;
-        storage
-            .save_graph_node(&StoredGraphNode::new(
-                "sess-prune::bad_node",
-                "sess-prune",
-                "Weak node",
-            ))
-            .await
-            .expect("seed node");
+        seed_session(&storage, "sess-g").await;
+        seed_node(&storage, "sess-g", "root").await;
+        let mode = GraphMode::new(Arc::clone(&storage), MockAnthropicClientTrait::new());
 
-        let mode = GraphMode::new(Arc::clone(&storage), fixed_client(mock_prune_response()));
-        mode.prune("Graph", Some("sess-prune".to_string()), 0.3)
-            .await
-            .expect("prune succeeds");
+        // Target colliding with a source.
+        let result = mode
+            .merge_graphs("sess-g", "sess-other", Some("sess-g".to_string()), None)
+            .await;
+        assert!(
+            matches!(result, Err(ModeError::InvalidValue { field, .. }) if field == "session_id")
+        );
 
-        let gone = storage
-            .get_graph_node("sess-prune::bad_node")
-            .await
-            .expect("read node");
-        assert!(gone.is_none(), "pruned node should be deleted from storage");
+        // Out-of-range dedup threshold.
+        let result = mode
+            .merge_graphs("sess-g", "sess-other", None, Some(1.5))
+            .await;
+        assert!(
+            matches!(result, Err(ModeError::InvalidValue { field, .. }) if field == "dedup_threshold")
+        );
+
+        // A source without a stored graph.
+        let result = mode.merge_graphs("sess-g", "sess-empty", None, None).await;
+        assert!(
+            matches!(result, Err(ModeError::InvalidValue { field, .. }) if field == "session_id")
+        );
+    }
+
+    fn mock_generate_many_children_response() -> String {
+        r#"{
+            "parent_id": "root",
+            "children": [
+                {"id": "c1", "content": "Child 1", "score": 0.3, "type": "reasoning", "relationship": "elaborates"},
+                {"id": "c2", "content": "Child 2", "score": 0.9, "type": "reasoning", "relationship": "elaborates"},
+                {"id": "c3", "content": "Child 3", "score": 0.5, "type": "reasoning", "relationship": "elaborates"},
+                {"id": "c4", "content": "Child 4", "score": 0.7, "type": "reasoning", "relationship": "elaborates"},
+                {"id": "c5", "content": "Child 5", "score": 0.1, "type": "reasoning", "relationship": "elaborates"}
+            ],
+            "generation_notes": "Generated reasoning children"
+        }"#
+        .to_string()
     }
 
     #[tokio::test]
-    async fn test_node_id_lookup_uses_namespaced_key() {
+    async fn test_generate_caps_returned_children_but_stores_all() {
         let storage = in_memory_storage().await;
-        seed_session(&storage, "sess-lookup").await;
-        // Persist a node, then drive generate by node_id (not content).
-        storage
-            .save_graph_node(&StoredGraphNode::new(
-                "sess-lookup::n1",
-                "sess-lookup",
-                "Stored parent content",
-            ))
-            .await
-            .expect("seed node");
+        seed_session(&storage, "sess-cap").await;
+        seed_node(&storage, "sess-cap", "root").await;
+        let mode = GraphMode::new(
+            Arc::clone(&storage),
+            fixed_client(mock_generate_many_children_response()),
+        )
+        .with_max_returned_children(Some(2));
 
-        let mode = GraphMode::new(Arc::clone(&storage), fixed_client(mock_generate_response()));
-        // Bare "n1" must resolve via the session-namespaced key "sess-lookup::n1".
         let resp = mode
-            .generate(None, Some("n1"), Some("sess-lookup".to_string()))
+            .generate(Some("Parent"), None, Some("sess-cap".to_string()))
             .await
-            .expect("generate by node_id succeeds");
-        assert_eq!(resp.children.len(), 1);
+            .expect("generate succeeds");
+
+        // Top-2 by score come back, with the held-back count flagged.
+        assert_eq!(resp.children.len(), 2);
+        assert_eq!(resp.children[0].id, "c2");
+        assert_eq!(resp.children[1].id, "c4");
+        assert_eq!(resp.truncated_children, Some(3));
+
+        // The cap only trimmed the response: all five children persisted.
+        let nodes = storage
+            .get_graph_nodes("sess-cap")
+            .await
+            .expect("read nodes");
+        assert_eq!(nodes.len(), 6); // seeded root + 5 children
     }
 
     #[tokio::test]
-    async fn test_state_reads_persisted_graph_with_stripped_ids() {
+    async fn test_generate_under_cap_is_not_flagged() {
         let storage = in_memory_storage().await;
-        // init then generate to build a small persisted graph.
-        GraphMode::new(Arc::clone(&storage), fixed_client(mock_init_response()))
-            .init("Topic", Some("sess-state".to_string()))
-            .await
-            .expect("init");
-        GraphMode::new(Arc::clone(&storage), fixed_client(mock_generate_response()))
-            .generate(Some("Parent"), None, Some("sess-state".to_string()))
-            .await
-            .expect("generate");
+        seed_session(&storage, "sess-uncap").await;
+        seed_node(&storage, "sess-uncap", "root").await;
+        let mode = GraphMode::new(Arc::clone(&storage), fixed_client(mock_generate_response()))
+            .with_max_returned_children(Some(2));
 
-        // build_graph_state_from_storage strips the namespace prefix for the model.
-        let json = GraphMode::new(Arc::clone(&storage), fixed_client(mock_state_response()))
-            .build_graph_state_from_storage("sess-state")
+        let resp = mode
+            .generate(Some("Parent"), None, Some("sess-uncap".to_string()))
             .await
-            .expect("build state");
-        assert!(json.contains("\"id\":\"root\""), "state JSON: {json}");
-        assert!(json.contains("\"id\":\"c1\""), "state JSON: {json}");
-        assert!(
-            !json.contains("sess-state::"),
-            "namespace prefix should be stripped: {json}"
-        );
+            .expect("generate succeeds");
+
+        assert_eq!(resp.children.len(), 1);
+        assert_eq!(resp.truncated_children, None);
     }
 }
@@ -3,13 +3,14 @@
 //! Parsing functions for graph operation responses.
 
 use crate::error::ModeError;
+use crate::modes::parse_probability;
 
 use super::types::{
     ChildNode, ComplexityLevel, ExpansionDirection, FrontierNodeInfo, GraphConclusion,
-    GraphMetadata, GraphMetrics, GraphPath, GraphStructure, IntegrationNotes, NodeAssessment,
-    NodeCritique, NodeRecommendation, NodeRelationship, NodeScores, NodeType, PruneCandidate,
-    PruneImpact, PruneReason, RefinedNode, RootNode, SessionQuality, SuggestedAction,
-    SynthesisNode,
+    GraphMetadata, GraphMetrics, GraphPath, GraphStructure, ImportedEdge, ImportedNode,
+    IntegrationNotes, NodeAssessment, NodeCritique, NodeRecommendation, NodeRelationship,
+    NodeScores, NodeType, PruneCandidate, PruneImpact, PruneReason, RefinedNode, RootNode,
+    SessionQuality, SuggestedAction, SynthesisNode,
 };
 
 // ============================================================================
@@ -177,7 +178,7 @@ pub fn parse_root(json: &serde_json::Value) -> Result<RootNode, ModeError> {
     Ok(RootNode {
         id: get_str(r, "id")?,
         content: get_str(r, "content")?,
-        score: get_f64(r, "score")?,
+        score: parse_probability(r, "score")?,
         node_type,
     })
 }
@@ -197,7 +198,7 @@ pub fn parse_expansion_directions(
         .map(|d| {
             Ok(ExpansionDirection {
                 direction: get_str(d, "direction")?,
-                potential: get_f64(d, "potential")?,
+                potential: parse_probability(d, "potential")?,
             })
         })
         .collect()
@@ -244,7 +245,7 @@ pub fn parse_children(json: &serde_json::Value) -> Result<Vec<ChildNode>, ModeEr
             Ok(ChildNode {
                 id: get_str(c, "id")?,
                 content: get_str(c, "content")?,
-                score: get_f64(c, "score")?,
+                score: parse_probability(c, "score")?,
                 node_type,
                 relationship,
             })
@@ -263,11 +264,11 @@ pub fn parse_node_scores(json: &serde_json::Value) -> Result<NodeScores, ModeErr
     })?;
 
     Ok(NodeScores {
-        relevance: get_f64(s, "relevance")?,
-        coherence: get_f64(s, "coherence")?,
-        depth: get_f64(s, "depth")?,
-        novelty: get_f64(s, "novelty")?,
-        overall: get_f64(s, "overall")?,
+        relevance: parse_probability(s, "relevance")?,
+        coherence: parse_probability(s, "coherence")?,
+        depth: parse_probability(s, "depth")?,
+        novelty: parse_probability(s, "novelty")?,
+        overall: parse_probability(s, "overall")?,
     })
 }
 
@@ -307,7 +308,7 @@ pub fn parse_synthesis(json: &serde_json::Value) -> Result<SynthesisNode, ModeEr
     Ok(SynthesisNode {
         id: get_str(s, "id")?,
         content: get_str(s, "content")?,
-        score: get_f64(s, "score")?,
+        score: parse_probability(s, "score")?,
         node_type,
     })
 }
@@ -360,7 +361,7 @@ pub fn parse_refined_node(json: &serde_json::Value) -> Result<RefinedNode, ModeE
     Ok(RefinedNode {
         id: get_str(r, "id")?,
         content: get_str(r, "content")?,
-        score: get_f64(r, "score")?,
+        score: parse_probability(r, "score")?,
         node_type,
     })
 }
@@ -389,7 +390,7 @@ pub fn parse_prune_candidates(json: &serde_json::Value) -> Result<Vec<PruneCandi
             Ok(PruneCandidate {
                 node_id: get_str(c, "node_id")?,
                 reason,
-                confidence: get_f64(c, "confidence")?,
+                confidence: parse_probability(c, "confidence")?,
                 impact,
             })
         })
@@ -413,7 +414,7 @@ pub fn parse_best_paths(json: &serde_json::Value) -> Result<Vec<GraphPath>, Mode
         .map(|p| {
             Ok(GraphPath {
                 path: get_string_array(p, "path")?,
-                path_quality: get_f64(p, "path_quality")?,
+                path_quality: parse_probability(p, "path_quality")?,
                 key_insight: get_str(p, "key_insight")?,
             })
         })
@@ -433,7 +434,7 @@ pub fn parse_conclusions(json: &serde_json::Value) -> Result<Vec<GraphConclusion
         .map(|c| {
             Ok(GraphConclusion {
                 conclusion: get_str(c, "conclusion")?,
-                confidence: get_f64(c, "confidence")?,
+                confidence: parse_probability(c, "confidence")?,
                 supporting_nodes: get_string_array(c, "supporting_nodes")?,
             })
         })
@@ -449,10 +450,10 @@ pub fn parse_session_quality(json: &serde_json::Value) -> Result<SessionQuality,
         })?;
 
     Ok(SessionQuality {
-        depth_achieved: get_f64(q, "depth_achieved")?,
-        breadth_achieved: get_f64(q, "breadth_achieved")?,
-        coherence: get_f64(q, "coherence")?,
-        overall: get_f64(q, "overall")?,
+        depth_achieved: parse_probability(q, "depth_achieved")?,
+        breadth_achieved: parse_probability(q, "breadth_achieved")?,
+        coherence: parse_probability(q, "coherence")?,
+        overall: parse_probability(q, "overall")?,
     })
 }
 
@@ -492,7 +493,7 @@ pub fn parse_frontiers(json: &serde_json::Value) -> Result<Vec<FrontierNodeInfo>
 
             Ok(FrontierNodeInfo {
                 node_id: get_str(f, "node_id")?,
-                potential: get_f64(f, "potential")?,
+                potential: parse_probability(f, "potential")?,
                 suggested_action,
             })
         })
@@ -506,12 +507,156 @@ pub fn parse_metrics(json: &serde_json::Value) -> Result<GraphMetrics, ModeError
     })?;
 
     Ok(GraphMetrics {
-        average_score: get_f64(m, "average_score")?,
-        max_score: get_f64(m, "max_score")?,
-        coverage: get_f64(m, "coverage")?,
+        average_score: parse_probability(m, "average_score")?,
+        max_score: parse_probability(m, "max_score")?,
+        coverage: parse_probability(m, "coverage")?,
     })
 }
 
+/// Parses and validates an externally-supplied graph for the import operation.
+///
+/// Expects `{"nodes": [{"id", "content", "score"?, "is_terminal"?}], "edges":
+/// [{"from", "to"}]}`. Rejects duplicate node IDs, edges referencing unknown
+/// nodes, out-of-range scores, and cyclic graphs — an imported argument map
+/// must be a DAG for downstream `score`/`aggregate`/`finalize` to make sense.
+pub fn parse_import_graph(
+    json: &serde_json::Value,
+) -> Result<(Vec<ImportedNode>, Vec<ImportedEdge>), ModeError> {
+    let node_arr = json
+        .get("nodes")
+        .and_then(serde_json::Value::as_array)
+        .ok_or_else(|| ModeError::MissingField {
+            field: "nodes".to_string(),
+        })?;
+    if node_arr.is_empty() {
+        return Err(ModeError::InvalidValue {
+            field: "nodes".to_string(),
+            reason: "imported graph must contain at least one node".to_string(),
+        });
+    }
+
+    let mut nodes = Vec::with_capacity(node_arr.len());
+    let mut seen = std::collections::HashSet::new();
+    for n in node_arr {
+        let id = get_str(n, "id")?;
+        if id.trim().is_empty() {
+            return Err(ModeError::InvalidValue {
+                field: "nodes".to_string(),
+                reason: "node id must be non-empty".to_string(),
+            });
+        }
+        if !seen.insert(id.clone()) {
+            return Err(ModeError::InvalidValue {
+                field: "nodes".to_string(),
+                reason: format!("duplicate node id '{id}'"),
+            });
+        }
+        let score = n.get("score").and_then(serde_json::Value::as_f64);
+        if let Some(s) = score {
+            if !(0.0..=1.0).contains(&s) {
+                return Err(ModeError::InvalidValue {
+                    field: "nodes".to_string(),
+                    reason: format!("score {s} for node '{id}' is outside 0.0-1.0"),
+                });
+            }
+        }
+        nodes.push(ImportedNode {
+            id,
+            content: get_str(n, "content")?,
+            score,
+            is_terminal: n
+                .get("is_terminal")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false),
+        });
+    }
+
+    let edge_arr = json
+        .get("edges")
+        .and_then(serde_json::Value::as_array)
+        .ok_or_else(|| ModeError::MissingField {
+            field: "edges".to_string(),
+        })?;
+
+    let mut edges = Vec::with_capacity(edge_arr.len());
+    for e in edge_arr {
+        let from = get_str(e, "from")?;
+        let to = get_str(e, "to")?;
+        for endpoint in [&from, &to] {
+            if !seen.contains(endpoint.as_str()) {
+                return Err(ModeError::InvalidValue {
+                    field: "edges".to_string(),
+                    reason: format!("edge references unknown node '{endpoint}'"),
+                });
+            }
+        }
+        edges.push(ImportedEdge { from, to });
+    }
+
+    ensure_acyclic(&nodes, &edges)?;
+
+    Ok((nodes, edges))
+}
+
+/// Rejects an imported graph containing a directed cycle.
+fn ensure_acyclic(nodes: &[ImportedNode], edges: &[ImportedEdge]) -> Result<(), ModeError> {
+    let ids: Vec<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+    let edge_pairs: Vec<(&str, &str)> = edges
+        .iter()
+        .map(|e| (e.from.as_str(), e.to.as_str()))
+        .collect();
+    let cyclic = cyclic_ids(&ids, &edge_pairs);
+    if cyclic.is_empty() {
+        Ok(())
+    } else {
+        Err(ModeError::InvalidValue {
+            field: "edges".to_string(),
+            reason: format!("graph contains a cycle involving: {}", cyclic.join(", ")),
+        })
+    }
+}
+
+/// Find the node ids that lie on (or are fed only by) a directed cycle, using
+/// Kahn's algorithm: iteratively peel zero-in-degree nodes; whatever cannot be
+/// peeled retains an incoming path from a cycle. Returns the leftover ids
+/// sorted; an empty result means the graph is acyclic. Edges whose endpoints
+/// are not in `ids` are ignored.
+pub fn cyclic_ids<'a>(ids: &[&'a str], edges: &[(&'a str, &'a str)]) -> Vec<&'a str> {
+    let mut in_degree: std::collections::HashMap<&str, usize> =
+        ids.iter().map(|id| (*id, 0)).collect();
+    for (from, to) in edges {
+        if in_degree.contains_key(from) {
+            if let Some(d) = in_degree.get_mut(to) {
+                *d += 1;
+            }
+        }
+    }
+
+    let mut queue: Vec<&str> = in_degree
+        .iter()
+        .filter(|(_, d)| **d == 0)
+        .map(|(id, _)| *id)
+        .collect();
+    while let Some(id) = queue.pop() {
+        for (_, to) in edges.iter().filter(|(from, _)| *from == id) {
+            if let Some(d) = in_degree.get_mut(to) {
+                *d -= 1;
+                if *d == 0 {
+                    queue.push(to);
+                }
+            }
+        }
+    }
+
+    let mut cyclic: Vec<&'a str> = ids
+        .iter()
+        .filter(|id| in_degree.get(**id).copied().unwrap_or(0) > 0)
+        .copied()
+        .collect();
+    cyclic.sort_unstable();
+    cyclic
+}
+
 #[cfg(test)]
 #[allow(
     clippy::unwrap_used,
@@ -802,6 +947,22 @@ mod tests {
         assert!(matches!(result.node_type, NodeType::Root));
     }
 
+    #[test]
+    fn test_parse_root_rejects_out_of_range_score() {
+        let json = json!({
+            "root": {
+                "id": "node-1",
+                "content": "Main problem",
+                "score": 1.5,
+                "type": "root"
+            }
+        });
+        assert!(matches!(
+            parse_root(&json),
+            Err(ModeError::InvalidValue { field, .. }) if field == "score"
+        ));
+    }
+
     #[test]
     fn test_parse_root_missing() {
         let json = json!({});
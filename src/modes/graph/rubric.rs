@@ -0,0 +1,190 @@
+//! Transparent quality rubric for finalize.
+//!
+//! The model's [`SessionQuality`] self-report is useful but unverifiable on
+//! its own. This rubric blends it with metrics computed from the stored graph
+//! — node count, average node score, and path diversity — into a single
+//! score surfaced alongside the self-report, so a glowing `overall` over a
+//! three-node graph is visibly at odds with what was actually explored. The
+//! blend weight comes from `Config::rubric_llm_weight`
+//! (`QUALITY_RUBRIC_LLM_WEIGHT`), injected via
+//! [`GraphMode::with_rubric_llm_weight`](super::GraphMode::with_rubric_llm_weight).
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use super::types::SessionQuality;
+use crate::storage::{StoredGraphEdge, StoredGraphNode};
+
+/// Stored node count at which the node-count component saturates at 1.0.
+const NODE_COUNT_TARGET: f64 = 10.0;
+
+/// Distinct leaf count at which the path-diversity component saturates at 1.0.
+const PATH_DIVERSITY_TARGET: f64 = 3.0;
+
+/// Default weight of the LLM self-report in the blended score (matches
+/// `crate::config::DEFAULT_RUBRIC_LLM_WEIGHT`).
+pub(super) const DEFAULT_LLM_WEIGHT: f64 = 0.5;
+
+/// Computed quality rubric blending the LLM self-report with structural
+/// metrics, with every component exposed so the blend can be checked by hand.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QualityRubric {
+    /// Blended score: `llm_weight * llm_overall + (1 - llm_weight) *`
+    /// the mean of the three structural components.
+    pub computed_overall: f64,
+    /// The model's own `overall` from [`SessionQuality`], for comparison.
+    pub llm_overall: f64,
+    /// Node-count component: stored nodes over the saturation target,
+    /// capped at 1.0.
+    pub node_count_component: f64,
+    /// Mean score of stored nodes that carry one (0.0 when none do).
+    pub average_score_component: f64,
+    /// Path-diversity component: distinct leaf nodes over the saturation
+    /// target, capped at 1.0. More independent endpoints means more
+    /// alternatives were actually explored.
+    pub path_diversity_component: f64,
+    /// Weight applied to the LLM self-report in the blend.
+    pub llm_weight: f64,
+}
+
+/// Compute the quality rubric for a stored graph and the model's self-report.
+///
+/// `llm_weight` is clamped to `[0.0, 1.0]`; the structural remainder is the
+/// unweighted mean of the three computed components.
+#[must_use]
+pub fn quality_rubric(
+    quality: &SessionQuality,
+    nodes: &[StoredGraphNode],
+    edges: &[StoredGraphEdge],
+    llm_weight: f64,
+) -> QualityRubric {
+    let llm_weight = llm_weight.clamp(0.0, 1.0);
+
+    #[allow(clippy::cast_precision_loss)]
+    let node_count_component = (nodes.len() as f64 / NODE_COUNT_TARGET).min(1.0);
+
+    let scores: Vec<f64> = nodes.iter().filter_map(|node| node.score).collect();
+    #[allow(clippy::cast_precision_loss)]
+    let average_score_component = if scores.is_empty() {
+        0.0
+    } else {
+        scores.iter().sum::<f64>() / scores.len() as f64
+    };
+
+    // A leaf is a stored node with no outgoing edge.
+    let sources: HashSet<&str> = edges
+        .iter()
+        .map(|edge| edge.from_node_id.as_str())
+        .collect();
+    let leaf_count = nodes
+        .iter()
+        .filter(|node| !sources.contains(node.id.as_str()))
+        .count();
+    #[allow(clippy::cast_precision_loss)]
+    let path_diversity_component = (leaf_count as f64 / PATH_DIVERSITY_TARGET).min(1.0);
+
+    let structural =
+        (node_count_component + average_score_component + path_diversity_component) / 3.0;
+    let computed_overall =
+        (llm_weight * quality.overall + (1.0 - llm_weight) * structural).clamp(0.0, 1.0);
+
+    QualityRubric {
+        computed_overall,
+        llm_overall: quality.overall,
+        node_count_component,
+        average_score_component,
+        path_diversity_component,
+        llm_weight,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::storage::GraphEdgeType;
+
+    fn report(overall: f64) -> SessionQuality {
+        SessionQuality {
+            depth_achieved: 0.7,
+            breadth_achieved: 0.7,
+            coherence: 0.7,
+            overall,
+        }
+    }
+
+    fn node(id: &str, score: f64) -> StoredGraphNode {
+        StoredGraphNode::new(id, "sess-1", "Content").with_score(score)
+    }
+
+    fn edge(id: &str, from: &str, to: &str) -> StoredGraphEdge {
+        StoredGraphEdge::new(id, "sess-1", from, to).with_edge_type(GraphEdgeType::Continues)
+    }
+
+    #[test]
+    fn test_rubric_matches_hand_computation() {
+        // Graph: root -> a, root -> b; a -> c. Leaves: b and c (2 of them).
+        let nodes = vec![
+            node("root", 0.8),
+            node("a", 0.6),
+            node("b", 0.7),
+            node("c", 0.9),
+        ];
+        let edges = vec![
+            edge("e1", "root", "a"),
+            edge("e2", "root", "b"),
+            edge("e3", "a", "c"),
+        ];
+
+        let rubric = quality_rubric(&report(0.8), &nodes, &edges, 0.5);
+
+        // By hand: nodes 4/10 = 0.4; scores (0.8+0.6+0.7+0.9)/4 = 0.75;
+        // leaves 2/3; structural (0.4 + 0.75 + 2/3)/3 = 0.60556;
+        // blended 0.5*0.8 + 0.5*0.60556 = 0.70278.
+        assert!((rubric.node_count_component - 0.4).abs() < 1e-9);
+        assert!((rubric.average_score_component - 0.75).abs() < 1e-9);
+        assert!((rubric.path_diversity_component - 2.0 / 3.0).abs() < 1e-9);
+        assert!((rubric.computed_overall - 0.702_777_8).abs() < 1e-6);
+        assert_eq!(rubric.llm_overall, 0.8);
+        assert_eq!(rubric.llm_weight, 0.5);
+    }
+
+    #[test]
+    fn test_rubric_components_saturate_at_one() {
+        // 12 disconnected nodes: count and diversity both cap at 1.0.
+        let nodes: Vec<StoredGraphNode> = (0..12).map(|i| node(&format!("n{i}"), 1.0)).collect();
+        let rubric = quality_rubric(&report(1.0), &nodes, &[], 0.5);
+
+        assert_eq!(rubric.node_count_component, 1.0);
+        assert_eq!(rubric.path_diversity_component, 1.0);
+        assert_eq!(rubric.computed_overall, 1.0);
+    }
+
+    #[test]
+    fn test_rubric_weight_extremes_and_clamping() {
+        let nodes = vec![node("root", 0.0)];
+        let quality = report(1.0);
+
+        // Weight 1.0: pure self-report. Weight 0.0: pure structure.
+        assert_eq!(
+            quality_rubric(&quality, &nodes, &[], 1.0).computed_overall,
+            1.0
+        );
+        let structural_only = quality_rubric(&quality, &nodes, &[], 0.0);
+        assert!(structural_only.computed_overall < 0.2);
+
+        // Out-of-range weights clamp rather than extrapolate.
+        assert_eq!(quality_rubric(&quality, &nodes, &[], 7.5).llm_weight, 1.0);
+        assert_eq!(quality_rubric(&quality, &nodes, &[], -1.0).llm_weight, 0.0);
+    }
+
+    #[test]
+    fn test_rubric_empty_graph_scores_zero_structure() {
+        let rubric = quality_rubric(&report(0.9), &[], &[], 0.5);
+        assert_eq!(rubric.node_count_component, 0.0);
+        assert_eq!(rubric.average_score_component, 0.0);
+        assert_eq!(rubric.path_diversity_component, 0.0);
+        assert!((rubric.computed_overall - 0.45).abs() < 1e-9);
+    }
+}
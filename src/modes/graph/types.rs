@@ -2,8 +2,12 @@
 //!
 //! Shared types for graph-of-thoughts operations.
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
+use super::rubric::QualityRubric;
+
 // ============================================================================
 // Common Types
 // ============================================================================
@@ -212,6 +216,12 @@ pub struct GenerateResponse {
     /// Zero in the normal case; non-zero means the graph wasn't fully saved.
     #[serde(default)]
     pub persistence_failures: u32,
+    /// Number of generated children omitted from the response because the
+    /// per-mode cap was hit. Every child is persisted regardless — retrieve
+    /// the full graph with the `state` operation. `None` when nothing was
+    /// trimmed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truncated_children: Option<usize>,
 }
 
 impl GenerateResponse {
@@ -231,6 +241,7 @@ impl GenerateResponse {
             children,
             generation_notes: generation_notes.into(),
             persistence_failures: 0,
+            truncated_children: None,
         }
     }
 
@@ -240,6 +251,13 @@ impl GenerateResponse {
         self.persistence_failures = failures;
         self
     }
+
+    /// Record how many children the response cap held back.
+    #[must_use]
+    pub fn with_truncated_children(mut self, truncated_children: Option<usize>) -> Self {
+        self.truncated_children = truncated_children;
+        self
+    }
 }
 
 // ============================================================================
@@ -458,6 +476,31 @@ pub struct PruneCandidate {
     pub impact: PruneImpact,
 }
 
+/// A node an apply-prune refused to remove, and why.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProtectedNode {
+    /// The protected node's ID.
+    pub node_id: String,
+    /// Why it was protected (e.g. "graph root" or "on current best path").
+    pub reason: String,
+}
+
+/// Response from the apply-prune operation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ApplyPruneResponse {
+    /// Session whose graph was pruned.
+    pub session_id: String,
+    /// Node IDs actually removed (with their edges).
+    pub removed: Vec<String>,
+    /// Candidates refused because removing them would orphan the graph or
+    /// break the current best path. Empty when `force` was set.
+    pub protected: Vec<ProtectedNode>,
+    /// Candidates not present in the stored graph.
+    pub missing: Vec<String>,
+    /// Whether protections were overridden.
+    pub forced: bool,
+}
+
 /// Response from prune operation.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PruneResponse {
@@ -532,6 +575,21 @@ pub struct SessionQuality {
     pub overall: f64,
 }
 
+/// How a finalize `best_paths` entry was adjusted during validation.
+///
+/// Models occasionally reference node IDs that were never stored; finalize
+/// checks each returned path against the stored graph and records what it
+/// changed here rather than silently passing bad references through.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PathAdjustment {
+    /// Index of the path in the model's original `best_paths` order.
+    pub path_index: usize,
+    /// Node IDs the model referenced that are not in the stored graph.
+    pub unknown_nodes: Vec<String>,
+    /// Whether the path was dropped outright (no stored nodes remained).
+    pub dropped: bool,
+}
+
 /// Response from finalize operation.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct FinalizeResponse {
@@ -539,7 +597,9 @@ pub struct FinalizeResponse {
     pub thought_id: String,
     /// Session this thought belongs to.
     pub session_id: String,
-    /// Best paths through the graph.
+    /// Best paths through the graph. When the session's graph is in storage,
+    /// a path computed from stored node scores comes first and the model's
+    /// (validated) paths follow as supplementary.
     pub best_paths: Vec<GraphPath>,
     /// Conclusions.
     pub conclusions: Vec<GraphConclusion>,
@@ -547,6 +607,17 @@ pub struct FinalizeResponse {
     pub final_synthesis: String,
     /// Session quality.
     pub session_quality: SessionQuality,
+    /// Adjustments made while validating `best_paths` against the stored
+    /// graph. Empty when every path referenced only stored nodes, or when no
+    /// graph is stored for the session to validate against.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub path_adjustments: Vec<PathAdjustment>,
+    /// Computed quality rubric blending the model's self-report with
+    /// structural metrics from the stored graph, surfaced alongside
+    /// `session_quality` so the two can be compared. `None` for a
+    /// content-only finalize with no stored graph to measure.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality_rubric: Option<QualityRubric>,
 }
 
 impl FinalizeResponse {
@@ -569,8 +640,24 @@ impl FinalizeResponse {
             conclusions,
             final_synthesis: final_synthesis.into(),
             session_quality,
+            path_adjustments: Vec::new(),
+            quality_rubric: None,
         }
     }
+
+    /// Attach the adjustments made while validating `best_paths`.
+    #[must_use]
+    pub fn with_path_adjustments(mut self, path_adjustments: Vec<PathAdjustment>) -> Self {
+        self.path_adjustments = path_adjustments;
+        self
+    }
+
+    /// Attach the computed quality rubric.
+    #[must_use]
+    pub fn with_quality_rubric(mut self, quality_rubric: Option<QualityRubric>) -> Self {
+        self.quality_rubric = quality_rubric;
+        self
+    }
 }
 
 // ============================================================================
@@ -627,6 +714,16 @@ pub struct StateResponse {
     pub metrics: GraphMetrics,
     /// Suggested next steps.
     pub next_steps: Vec<String>,
+    /// Structural centrality per stored node (0.0 = peripheral, 1.0 = hub),
+    /// computed from the stored edges. Empty when nothing is stored for the
+    /// session. Low centrality plus a low quality score marks a prune candidate.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub centrality: HashMap<String, f64>,
+    /// Provenance per stored node: which operation produced it and from which
+    /// parent(s). Empty when nothing is stored for the session; nodes
+    /// persisted before provenance was recorded are omitted.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub provenance: HashMap<String, NodeProvenance>,
 }
 
 impl StateResponse {
@@ -647,6 +744,335 @@ impl StateResponse {
             frontiers,
             metrics,
             next_steps,
+            centrality: HashMap::new(),
+            provenance: HashMap::new(),
+        }
+    }
+
+    /// Attach structural centrality scores.
+    #[must_use]
+    pub fn with_centrality(mut self, centrality: HashMap<String, f64>) -> Self {
+        self.centrality = centrality;
+        self
+    }
+
+    /// Attach per-node provenance.
+    #[must_use]
+    pub fn with_provenance(mut self, provenance: HashMap<String, NodeProvenance>) -> Self {
+        self.provenance = provenance;
+        self
+    }
+}
+
+/// How a stored graph node arose: the producing operation and its inputs.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NodeProvenance {
+    /// Operation that produced the node (init/generate/aggregate/refine/import).
+    pub origin: String,
+    /// Parent node id(s) the operation consumed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parent_ids: Vec<String>,
+}
+
+// ============================================================================
+// Import Types
+// ============================================================================
+
+/// A node supplied to the import operation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ImportedNode {
+    /// Node identifier (unique within the imported graph).
+    pub id: String,
+    /// Node content.
+    pub content: String,
+    /// Optional quality score (0.0-1.0).
+    pub score: Option<f64>,
+    /// Whether this node is terminal.
+    pub is_terminal: bool,
+}
+
+/// An edge supplied to the import operation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ImportedEdge {
+    /// Source node identifier.
+    pub from: String,
+    /// Target node identifier.
+    pub to: String,
+}
+
+/// Response from import operation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ImportResponse {
+    /// Unique identifier for this thought.
+    pub thought_id: String,
+    /// Session this thought belongs to.
+    pub session_id: String,
+    /// Imported nodes.
+    pub nodes: Vec<ImportedNode>,
+    /// Number of imported edges.
+    pub edge_count: usize,
+    /// Nodes with no incoming edge (entry points for further operations).
+    pub roots: Vec<String>,
+}
+
+impl ImportResponse {
+    /// Create a new import response.
+    #[must_use]
+    pub fn new(
+        thought_id: impl Into<String>,
+        session_id: impl Into<String>,
+        nodes: Vec<ImportedNode>,
+        edge_count: usize,
+        roots: Vec<String>,
+    ) -> Self {
+        Self {
+            thought_id: thought_id.into(),
+            session_id: session_id.into(),
+            nodes,
+            edge_count,
+            roots,
+        }
+    }
+}
+
+// ============================================================================
+// Snapshot Types
+// ============================================================================
+
+/// Response from the snapshot operation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SnapshotResponse {
+    /// Identifier of the persisted snapshot (pass to `restore_snapshot`).
+    pub snapshot_id: String,
+    /// Session whose graph was snapshotted.
+    pub session_id: String,
+    /// Number of nodes captured.
+    pub node_count: usize,
+    /// Number of edges captured.
+    pub edge_count: usize,
+}
+
+impl SnapshotResponse {
+    /// Create a new snapshot response.
+    #[must_use]
+    pub fn new(
+        snapshot_id: impl Into<String>,
+        session_id: impl Into<String>,
+        node_count: usize,
+        edge_count: usize,
+    ) -> Self {
+        Self {
+            snapshot_id: snapshot_id.into(),
+            session_id: session_id.into(),
+            node_count,
+            edge_count,
+        }
+    }
+}
+
+/// Response from the restore-snapshot operation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RestoreSnapshotResponse {
+    /// Session whose graph was restored.
+    pub session_id: String,
+    /// Snapshot that was reinstated.
+    pub snapshot_id: String,
+    /// Number of nodes reinstated from the snapshot.
+    pub restored_nodes: usize,
+    /// Number of edges reinstated from the snapshot.
+    pub restored_edges: usize,
+    /// Nodes of the live graph removed by the replace (including any added
+    /// after the snapshot was taken).
+    pub removed_nodes: usize,
+}
+
+impl RestoreSnapshotResponse {
+    /// Create a new restore-snapshot response.
+    #[must_use]
+    pub fn new(
+        session_id: impl Into<String>,
+        snapshot_id: impl Into<String>,
+        restored_nodes: usize,
+        restored_edges: usize,
+        removed_nodes: usize,
+    ) -> Self {
+        Self {
+            session_id: session_id.into(),
+            snapshot_id: snapshot_id.into(),
+            restored_nodes,
+            restored_edges,
+            removed_nodes,
+        }
+    }
+}
+
+// ============================================================================
+// Merge Types
+// ============================================================================
+
+/// A source node folded into an already-merged near-duplicate during a graph
+/// merge, so callers can see which ids were collapsed and where they went.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MergedDuplicate {
+    /// Source node id that was folded away (its edges follow the fold).
+    pub dropped_id: String,
+    /// Merged node id in the target session it now maps to.
+    pub kept_id: String,
+}
+
+/// Response from merging two sessions' graphs into a target session.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MergeGraphsResponse {
+    /// Generated thought ID recording the merge.
+    pub thought_id: String,
+    /// Target session now holding the combined graph.
+    pub session_id: String,
+    /// The two sessions whose graphs were merged (left untouched).
+    pub source_session_ids: Vec<String>,
+    /// Number of nodes written to the target.
+    pub node_count: usize,
+    /// Number of edges written to the target.
+    pub edge_count: usize,
+    /// Near-duplicate nodes folded away, when deduplication was requested.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deduplicated: Vec<MergedDuplicate>,
+    /// Cross-links not written because they would duplicate an existing edge
+    /// or close a cycle after deduplication folded their endpoints together.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub omitted_edges: Vec<OmittedEdge>,
+}
+
+impl MergeGraphsResponse {
+    /// Create a new merge response.
+    #[must_use]
+    pub fn new(
+        thought_id: impl Into<String>,
+        session_id: impl Into<String>,
+        source_session_ids: Vec<String>,
+        node_count: usize,
+        edge_count: usize,
+        deduplicated: Vec<MergedDuplicate>,
+        omitted_edges: Vec<OmittedEdge>,
+    ) -> Self {
+        Self {
+            thought_id: thought_id.into(),
+            session_id: session_id.into(),
+            source_session_ids,
+            node_count,
+            edge_count,
+            deduplicated,
+            omitted_edges,
+        }
+    }
+}
+
+// ============================================================================
+// Tree View Types
+// ============================================================================
+
+/// A node in the spanning-tree view of the stored graph.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TreeViewNode {
+    /// Node identifier (session namespace stripped).
+    pub id: String,
+    /// Node content.
+    pub content: String,
+    /// Quality score, when one is stored.
+    pub score: Option<f64>,
+    /// Parent in the spanning tree (`None` for a root).
+    pub parent_id: Option<String>,
+    /// Depth in the spanning tree (roots are 0).
+    pub depth: u32,
+}
+
+/// A stored edge dropped from the spanning tree (a cross or back edge whose
+/// target is already reached via another path).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OmittedEdge {
+    /// Source node identifier.
+    pub from: String,
+    /// Target node identifier.
+    pub to: String,
+}
+
+/// Response from the tree-view conversion.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TreeViewResponse {
+    /// Session whose graph was converted.
+    pub session_id: String,
+    /// Root node IDs, one per tree in the forest.
+    pub roots: Vec<String>,
+    /// Every stored node in breadth-first order (parents precede children).
+    pub nodes: Vec<TreeViewNode>,
+    /// Stored edges not representable in the tree, so the non-tree
+    /// relationships are reported rather than silently lost.
+    pub omitted_edges: Vec<OmittedEdge>,
+}
+
+impl TreeViewResponse {
+    /// Create a new tree-view response.
+    #[must_use]
+    pub fn new(
+        session_id: impl Into<String>,
+        roots: Vec<String>,
+        nodes: Vec<TreeViewNode>,
+        omitted_edges: Vec<OmittedEdge>,
+    ) -> Self {
+        Self {
+            session_id: session_id.into(),
+            roots,
+            nodes,
+            omitted_edges,
+        }
+    }
+}
+
+// ============================================================================
+// Advance Types
+// ============================================================================
+
+/// A frontier node expanded by the advance operation, with its generated
+/// children.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExpandedFrontier {
+    /// Frontier node that was expanded.
+    pub node_id: String,
+    /// Children generated from it.
+    pub children: Vec<ChildNode>,
+}
+
+/// Response from advance operation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AdvanceResponse {
+    /// Unique identifier for this thought.
+    pub thought_id: String,
+    /// Session this thought belongs to.
+    pub session_id: String,
+    /// Expanded frontiers, best-ranked first.
+    pub expanded: Vec<ExpandedFrontier>,
+    /// Total child nodes added across all expansions.
+    pub nodes_added: u32,
+    /// Number of child-node / edge writes that failed to persist to storage.
+    #[serde(default)]
+    pub persistence_failures: u32,
+}
+
+impl AdvanceResponse {
+    /// Create a new advance response.
+    #[must_use]
+    pub fn new(
+        thought_id: impl Into<String>,
+        session_id: impl Into<String>,
+        expanded: Vec<ExpandedFrontier>,
+        nodes_added: u32,
+        persistence_failures: u32,
+    ) -> Self {
+        Self {
+            thought_id: thought_id.into(),
+            session_id: session_id.into(),
+            expanded,
+            nodes_added,
+            persistence_failures,
         }
     }
 }
@@ -0,0 +1,206 @@
+//! Lightweight content-language detection.
+//!
+//! Backs `RESPONSE_LANGUAGE=auto`: a cheap, dependency-free heuristic guesses
+//! the language of incoming content so the output-language instruction can
+//! match it. Non-Latin scripts are identified by character ranges; Latin-script
+//! languages by stopword counts. The heuristic deliberately prefers "don't
+//! know" (`None`) over a wrong guess — an uncertain detection falls back to
+//! the configured default rather than steering the model to the wrong
+//! language.
+
+/// Fraction of alphabetic characters a single script must reach before the
+/// content is attributed to it.
+const SCRIPT_DOMINANCE_THRESHOLD: f64 = 0.2;
+
+/// Minimum stopword hits before a Latin-script guess counts as confident.
+const MIN_STOPWORD_HITS: usize = 3;
+
+/// Stopword lists for the Latin-script languages the heuristic can tell apart.
+/// Shared words (es/fr/pt articles, etc.) are fine: a tie reads as uncertain.
+const STOPWORDS: &[(&str, &[&str])] = &[
+    (
+        "English",
+        &[
+            "the", "is", "and", "of", "to", "in", "that", "it", "for", "with", "was", "this",
+        ],
+    ),
+    (
+        "Spanish",
+        &[
+            "el", "la", "los", "las", "es", "de", "que", "y", "en", "un", "una", "por", "para",
+            "con", "no", "se",
+        ],
+    ),
+    (
+        "French",
+        &[
+            "le", "la", "les", "est", "de", "que", "et", "en", "un", "une", "pour", "dans", "ce",
+            "pas", "qui", "sur",
+        ],
+    ),
+    (
+        "German",
+        &[
+            "der", "die", "das", "ist", "und", "nicht", "ein", "eine", "mit", "für", "auf", "zu",
+            "den", "von",
+        ],
+    ),
+    (
+        "Portuguese",
+        &[
+            "o", "os", "as", "é", "de", "que", "e", "em", "um", "uma", "para", "com", "não", "do",
+            "da",
+        ],
+    ),
+    (
+        "Italian",
+        &[
+            "il", "lo", "gli", "è", "di", "che", "e", "in", "un", "una", "per", "con", "non",
+            "del", "della",
+        ],
+    ),
+];
+
+/// Guess the language of `content`, or `None` when uncertain.
+///
+/// Non-Latin scripts are attributed by character range (Cyrillic → Russian,
+/// kana → Japanese, Hangul → Korean, and so on; CJK ideographs without kana
+/// read as Chinese). Latin-script text is attributed to the language with the
+/// most stopword hits, requiring at least [`MIN_STOPWORD_HITS`] and a strict
+/// lead over the runner-up.
+#[must_use]
+pub fn detect_language(content: &str) -> Option<&'static str> {
+    if let Some(language) = detect_by_script(content) {
+        return Some(language);
+    }
+    detect_by_stopwords(content)
+}
+
+/// Attribute content to a language by dominant non-Latin script, if any.
+fn detect_by_script(content: &str) -> Option<&'static str> {
+    let mut alphabetic = 0usize;
+    let mut cyrillic = 0usize;
+    let mut greek = 0usize;
+    let mut arabic = 0usize;
+    let mut hebrew = 0usize;
+    let mut devanagari = 0usize;
+    let mut hangul = 0usize;
+    let mut kana = 0usize;
+    let mut cjk = 0usize;
+
+    for ch in content.chars().filter(|c| c.is_alphabetic()) {
+        alphabetic += 1;
+        match ch {
+            '\u{0400}'..='\u{04FF}' => cyrillic += 1,
+            '\u{0370}'..='\u{03FF}' => greek += 1,
+            '\u{0600}'..='\u{06FF}' => arabic += 1,
+            '\u{0590}'..='\u{05FF}' => hebrew += 1,
+            '\u{0900}'..='\u{097F}' => devanagari += 1,
+            '\u{AC00}'..='\u{D7AF}' => hangul += 1,
+            '\u{3040}'..='\u{30FF}' => kana += 1,
+            '\u{4E00}'..='\u{9FFF}' => cjk += 1,
+            _ => {}
+        }
+    }
+    if alphabetic == 0 {
+        return None;
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let dominant = |count: usize| count as f64 / alphabetic as f64 >= SCRIPT_DOMINANCE_THRESHOLD;
+
+    // Kana distinguishes Japanese from Chinese even when ideographs dominate.
+    if dominant(kana) || (kana > 0 && dominant(kana + cjk)) {
+        return Some("Japanese");
+    }
+    if dominant(cjk) {
+        return Some("Chinese");
+    }
+    [
+        (cyrillic, "Russian"),
+        (greek, "Greek"),
+        (arabic, "Arabic"),
+        (hebrew, "Hebrew"),
+        (devanagari, "Hindi"),
+        (hangul, "Korean"),
+    ]
+    .into_iter()
+    .find(|&(count, _)| dominant(count))
+    .map(|(_, language)| language)
+}
+
+/// Attribute Latin-script content to a language by stopword counts.
+fn detect_by_stopwords(content: &str) -> Option<&'static str> {
+    let words: Vec<String> = content
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    if words.is_empty() {
+        return None;
+    }
+
+    let mut best: Option<(&'static str, usize)> = None;
+    let mut runner_up = 0usize;
+    for &(language, stopwords) in STOPWORDS {
+        let hits = words
+            .iter()
+            .filter(|word| stopwords.contains(&word.as_str()))
+            .count();
+        match best {
+            Some((_, best_hits)) if hits > best_hits => {
+                runner_up = best_hits;
+                best = Some((language, hits));
+            }
+            Some((_, best_hits)) => runner_up = runner_up.max(hits.min(best_hits)),
+            None => best = Some((language, hits)),
+        }
+    }
+
+    best.and_then(|(language, hits)| {
+        (hits >= MIN_STOPWORD_HITS && hits > runner_up).then_some(language)
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_spanish_by_stopwords() {
+        let content = "El problema es que la base de datos no responde a las consultas";
+        assert_eq!(detect_language(content), Some("Spanish"));
+    }
+
+    #[test]
+    fn test_detects_english_by_stopwords() {
+        let content = "The cache is stale and the update is not applied to the index";
+        assert_eq!(detect_language(content), Some("English"));
+    }
+
+    #[test]
+    fn test_detects_russian_by_script() {
+        assert_eq!(
+            detect_language("Почему сервер не отвечает на запросы"),
+            Some("Russian")
+        );
+    }
+
+    #[test]
+    fn test_detects_japanese_over_chinese_when_kana_present() {
+        assert_eq!(
+            detect_language("この問題を分析してください"),
+            Some("Japanese")
+        );
+        assert_eq!(detect_language("分析这个问题的根本原因"), Some("Chinese"));
+    }
+
+    #[test]
+    fn test_uncertain_input_detects_nothing() {
+        // Too short / no stopword evidence either way.
+        assert_eq!(detect_language("foo bar baz qux"), None);
+        assert_eq!(detect_language("12345 !!!"), None);
+        assert_eq!(detect_language(""), None);
+    }
+}
@@ -33,7 +33,10 @@ use std::fmt::Write as _;
 
 use crate::anthropic::StreamAccumulator;
 use crate::error::ModeError;
-use crate::modes::{extract_json, generate_thought_id, validate_content};
+use crate::modes::{
+    apply_memory_update, extract_json, generate_thought_id, language_instruction,
+    load_working_memory_block, reject_unknown_keys, validate_content, ModeFlags,
+};
 use crate::prompts::{mcts_backtrack_prompt, mcts_explore_prompt};
 use crate::server::{ProgressMilestone, ProgressReporter};
 use crate::traits::{
@@ -56,6 +59,11 @@ where
 {
     storage: S,
     client: C,
+    /// Behavior flags injected from server configuration (all off by default).
+    flags: ModeFlags,
+    /// Effective output language (per-request override merged with the
+    /// configured default at the server boundary).
+    language: Option<String>,
 }
 
 impl<S, C> MctsMode<S, C>
@@ -66,7 +74,29 @@ where
     /// Create a new MCTS mode instance.
     #[must_use]
     pub fn new(storage: S, client: C) -> Self {
-        Self { storage, client }
+        Self {
+            storage,
+            client,
+            flags: ModeFlags::default(),
+            language: None,
+        }
+    }
+
+    /// Set the output language for natural-language response fields. The
+    /// server passes the per-request override merged with the configured
+    /// `RESPONSE_LANGUAGE` default; `None` adds no language instruction.
+    #[must_use]
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Inject the server's behavior flags ([`ModeFlags`]). A mode built
+    /// without them — as in most tests — uses the all-off defaults.
+    #[must_use]
+    pub fn with_flags(mut self, flags: ModeFlags) -> Self {
+        self.flags = flags;
+        self
     }
 
     /// Perform MCTS exploration step.
@@ -102,13 +132,26 @@ where
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("mcts")
             .with_max_tokens(32768)
             .with_temperature(0.5)
             .with_maximum_thinking();
 
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
-
+        apply_memory_update(&self.storage, &session.id, &json).await;
+
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &[
+                "frontier_evaluation",
+                "selected_node",
+                "expansion",
+                "backpropagation",
+                "search_status",
+            ],
+        )?;
         let frontier_evaluation = parse_frontier(&json)?;
         let selected_node = parse_selected(&json)?;
         let expansion = parse_expansion(&json)?;
@@ -176,13 +219,25 @@ where
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("mcts")
             .with_max_tokens(32768)
             .with_temperature(0.3)
             .with_maximum_thinking();
 
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
-
+        apply_memory_update(&self.storage, &session.id, &json).await;
+
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &[
+                "quality_assessment",
+                "backtrack_decision",
+                "alternative_actions",
+                "recommendation",
+            ],
+        )?;
         let quality_assessment = parse_quality_assessment(&json)?;
         let backtrack_decision = parse_backtrack_decision(&json)?;
         let alternative_actions = parse_alternatives(&json)?;
@@ -261,6 +316,7 @@ where
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("mcts")
             .with_max_tokens(32768)
             .with_temperature(0.5)
             .with_thinking_budget(thinking_budget);
@@ -287,7 +343,19 @@ where
 
         let response_text = accumulator.text();
         let json = extract_json(&response_text)?;
-
+        apply_memory_update(&self.storage, &session.id, &json).await;
+
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &[
+                "frontier_evaluation",
+                "selected_node",
+                "expansion",
+                "backpropagation",
+                "search_status",
+            ],
+        )?;
         let frontier_evaluation = parse_frontier(&json)?;
         let selected_node = parse_selected(&json)?;
         let expansion = parse_expansion(&json)?;
@@ -368,6 +436,7 @@ where
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("mcts")
             .with_max_tokens(32768)
             .with_temperature(0.3)
             .with_thinking_budget(thinking_budget);
@@ -394,7 +463,18 @@ where
 
         let response_text = accumulator.text();
         let json = extract_json(&response_text)?;
-
+        apply_memory_update(&self.storage, &session.id, &json).await;
+
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &[
+                "quality_assessment",
+                "backtrack_decision",
+                "alternative_actions",
+                "recommendation",
+            ],
+        )?;
         let quality_assessment = parse_quality_assessment(&json)?;
         let backtrack_decision = parse_backtrack_decision(&json)?;
         let alternative_actions = parse_alternatives(&json)?;
@@ -492,17 +572,27 @@ where
         has_prior_session: bool,
         content_label: &str,
     ) -> String {
-        let prior_context = if has_prior_session {
-            self.load_prior_context(session_id).await
+        let (prior_context, memory_block) = if has_prior_session {
+            (
+                self.load_prior_context(session_id).await,
+                load_working_memory_block(&self.storage, session_id).await,
+            )
         } else {
-            String::new()
+            (String::new(), String::new())
         };
 
-        if prior_context.is_empty() {
-            format!("{prompt}\n\n{content_label}:\n{content}")
-        } else {
-            format!("{prompt}\n\n{prior_context}\n{content_label}:\n{content}")
+        let mut message = format!(
+            "{prompt}{}\n",
+            language_instruction(self.language.as_deref(), content)
+        );
+        if !memory_block.is_empty() {
+            let _ = write!(message, "\n{memory_block}");
+        }
+        if !prior_context.is_empty() {
+            let _ = write!(message, "\n{prior_context}");
         }
+        let _ = write!(message, "\n{content_label}:\n{content}");
+        message
     }
 }
 
@@ -626,6 +716,9 @@ mod tests {
         });
         mock_storage.expect_save_thought().returning(|_| Ok(()));
         mock_storage.expect_get_thoughts().returning(|_| Ok(vec![]));
+        mock_storage
+            .expect_get_working_memory()
+            .returning(|_| Ok(None));
 
         let response_json = mock_explore_response();
         mock_client.expect_complete().returning(move |_, _| {
@@ -671,6 +764,9 @@ mod tests {
         });
         mock_storage.expect_save_thought().returning(|_| Ok(()));
         mock_storage.expect_get_thoughts().returning(|_| Ok(vec![]));
+        mock_storage
+            .expect_get_working_memory()
+            .returning(|_| Ok(None));
 
         let response_json = mock_backtrack_response();
         mock_client.expect_complete().returning(move |_, _| {
@@ -1057,6 +1153,9 @@ mod tests {
                 0.7,
             )])
         });
+        mock_storage
+            .expect_get_working_memory()
+            .returning(|_| Ok(None));
         mock_storage.expect_save_thought().returning(|_| Ok(()));
 
         let response_json = mock_explore_response();
@@ -7,6 +7,7 @@
 use std::collections::HashMap;
 
 use crate::error::ModeError;
+use crate::modes::parse_probability;
 
 use super::types::{
     AlternativeAction, AlternativeOption, Backpropagation, BacktrackDecision, Expansion,
@@ -285,13 +286,7 @@ pub fn parse_recommendation(json: &serde_json::Value) -> Result<Recommendation,
         }
     };
 
-    let confidence = get_f64(r, "confidence")?;
-    if !(0.0..=1.0).contains(&confidence) {
-        return Err(ModeError::InvalidValue {
-            field: "confidence".to_string(),
-            reason: format!("must be between 0.0 and 1.0, got {confidence}"),
-        });
-    }
+    let confidence = parse_probability(r, "confidence")?;
 
     Ok(Recommendation {
         action,
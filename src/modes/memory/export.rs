@@ -0,0 +1,381 @@
+//! Session export in multiple formats.
+//!
+//! One dispatch behind `reasoning_export`: the same session renders as a
+//! JSON document (session row, thoughts, and any graph structure), a Markdown
+//! report, or a Graphviz DOT digraph of its Graph-of-Thoughts structure. The
+//! format is validated up front and unsupported combinations — `dot` on a
+//! session with no graph data — fail with a clear error rather than an empty
+//! document.
+
+use serde::Serialize;
+
+use crate::error::ModeError;
+use crate::storage::{SqliteStorage, StoredGraphEdge, StoredGraphNode, StoredSession};
+
+/// Output format for a session export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Full structured dump: session, thoughts, and graph structure.
+    Json,
+    /// Human-readable report of the reasoning trace.
+    Markdown,
+    /// Graphviz DOT digraph of the session's Graph-of-Thoughts structure.
+    Dot,
+}
+
+impl ExportFormat {
+    /// Parse a format name as given in a tool request.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModeError::InvalidValue`] naming the accepted formats when
+    /// the string is none of them.
+    pub fn parse(format: &str) -> Result<Self, ModeError> {
+        match format.trim().to_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "markdown" => Ok(Self::Markdown),
+            "dot" => Ok(Self::Dot),
+            other => Err(ModeError::InvalidValue {
+                field: "format".to_string(),
+                reason: format!("must be json, markdown, or dot, got {other}"),
+            }),
+        }
+    }
+
+    /// The canonical lowercase name of the format.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Markdown => "markdown",
+            Self::Dot => "dot",
+        }
+    }
+}
+
+/// JSON export document shape.
+#[derive(Serialize)]
+struct ExportDocument<'a> {
+    session: &'a StoredSession,
+    thoughts: &'a [crate::storage::StoredThought],
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    graph_nodes: &'a [StoredGraphNode],
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    graph_edges: &'a [StoredGraphEdge],
+}
+
+/// Export a session in the requested format.
+///
+/// # Errors
+///
+/// Returns [`ModeError::StorageError`] when the session does not exist or a
+/// read fails, and [`ModeError::InvalidValue`] for `dot` on a session with no
+/// graph data (only graph-mode sessions have a structure to draw — use `json`
+/// or `markdown` for the rest).
+pub async fn export_session(
+    storage: &SqliteStorage,
+    session_id: &str,
+    format: ExportFormat,
+) -> Result<String, ModeError> {
+    let session = storage
+        .get_stored_session(session_id)
+        .await
+        .map_err(|e| ModeError::StorageError {
+            message: format!("Failed to read session: {e}"),
+        })?
+        .ok_or_else(|| ModeError::StorageError {
+            message: format!("Session '{session_id}' not found"),
+        })?;
+    let thoughts =
+        storage
+            .get_stored_thoughts(session_id)
+            .await
+            .map_err(|e| ModeError::StorageError {
+                message: format!("Failed to read thoughts: {e}"),
+            })?;
+    let nodes = storage
+        .get_graph_nodes(session_id)
+        .await
+        .map_err(|e| ModeError::StorageError {
+            message: format!("Failed to read graph nodes: {e}"),
+        })?;
+    let edges = storage
+        .get_graph_edges(session_id)
+        .await
+        .map_err(|e| ModeError::StorageError {
+            message: format!("Failed to read graph edges: {e}"),
+        })?;
+
+    match format {
+        ExportFormat::Json => {
+            let document = ExportDocument {
+                session: &session,
+                thoughts: &thoughts,
+                graph_nodes: &nodes,
+                graph_edges: &edges,
+            };
+            serde_json::to_string_pretty(&document).map_err(|e| ModeError::JsonParseFailed {
+                message: format!("Failed to serialize export: {e}"),
+            })
+        }
+        ExportFormat::Markdown => Ok(render_markdown(&session, &thoughts, &nodes)),
+        ExportFormat::Dot => {
+            if nodes.is_empty() {
+                return Err(ModeError::InvalidValue {
+                    field: "format".to_string(),
+                    reason: format!(
+                        "session '{session_id}' has no graph data to export as dot; \
+                         use json or markdown, or run reasoning_graph in this session first"
+                    ),
+                });
+            }
+            Ok(render_dot(session_id, &nodes, &edges))
+        }
+    }
+}
+
+/// Render the session as a Markdown report.
+fn render_markdown(
+    session: &StoredSession,
+    thoughts: &[crate::storage::StoredThought],
+    nodes: &[StoredGraphNode],
+) -> String {
+    use std::fmt::Write as _;
+
+    let mut out = format!("# Session {}\n\n", session.id);
+    let _ = writeln!(out, "- Created: {}", session.created_at.to_rfc3339());
+    let _ = writeln!(out, "- Updated: {}", session.updated_at.to_rfc3339());
+    let _ = writeln!(out, "- Thoughts: {}", thoughts.len());
+    if !nodes.is_empty() {
+        let _ = writeln!(out, "- Graph nodes: {}", nodes.len());
+    }
+
+    if !thoughts.is_empty() {
+        out.push_str("\n## Thoughts\n");
+        for (index, thought) in thoughts.iter().enumerate() {
+            let _ = write!(
+                out,
+                "\n### {}. {} (confidence {:.2})\n\n{}\n",
+                index + 1,
+                thought.mode,
+                thought.confidence,
+                thought.content.trim()
+            );
+        }
+    }
+    out
+}
+
+/// Render the session's graph structure as a Graphviz DOT digraph.
+fn render_dot(session_id: &str, nodes: &[StoredGraphNode], edges: &[StoredGraphEdge]) -> String {
+    use std::fmt::Write as _;
+
+    let mut out = String::from("digraph reasoning {\n");
+    let _ = writeln!(out, "  label={};", dot_quote(session_id));
+    for node in nodes {
+        let _ = write!(
+            out,
+            "  {} [label={}, shape={}",
+            dot_quote(&node.id),
+            dot_quote(&node_label(node)),
+            match node.node_type {
+                crate::storage::GraphNodeType::Thought => "box",
+                crate::storage::GraphNodeType::Aggregation => "diamond",
+                crate::storage::GraphNodeType::Refinement => "ellipse",
+            }
+        );
+        // Provenance rides along as a tooltip so rendered output can show how
+        // the node arose; nodes without recorded provenance get none.
+        if let Some(provenance) = provenance_text(node) {
+            let _ = write!(out, ", tooltip={}", dot_quote(&provenance));
+        }
+        out.push_str("];\n");
+    }
+    for edge in edges {
+        let _ = writeln!(
+            out,
+            "  {} -> {} [label={}];",
+            dot_quote(&edge.from_node_id),
+            dot_quote(&edge.to_node_id),
+            dot_quote(edge.edge_type.as_str())
+        );
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Cap on graph node label length in DOT output.
+const MAX_DOT_LABEL_CHARS: usize = 60;
+
+/// Build a node label: truncated content plus score, when present.
+fn node_label(node: &StoredGraphNode) -> String {
+    use std::fmt::Write as _;
+
+    let mut label: String = node.content.chars().take(MAX_DOT_LABEL_CHARS).collect();
+    if node.content.chars().count() > MAX_DOT_LABEL_CHARS {
+        label.push('…');
+    }
+    if let Some(score) = node.score {
+        let _ = write!(label, " [{score:.2}]");
+    }
+    label
+}
+
+/// Describe a node's provenance — producing operation plus parent id(s) — or
+/// `None` when the node predates provenance recording.
+fn provenance_text(node: &StoredGraphNode) -> Option<String> {
+    let origin = node.origin?;
+    if node.parent_ids.is_empty() {
+        Some(origin.as_str().to_string())
+    } else {
+        Some(format!(
+            "{} from {}",
+            origin.as_str(),
+            node.parent_ids.join(", ")
+        ))
+    }
+}
+
+/// Quote a string as a DOT double-quoted ID, escaping quotes and backslashes.
+fn dot_quote(value: &str) -> String {
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{}\"", escaped.replace('\n', "\\n"))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::storage::{GraphEdgeType, GraphNodeOrigin, StoredThought};
+    use serial_test::serial;
+
+    async fn storage_with_session() -> SqliteStorage {
+        let storage = SqliteStorage::new_in_memory()
+            .await
+            .expect("create storage");
+        storage
+            .create_session_with_id("sess-export")
+            .await
+            .expect("create session");
+        for (id, mode, content, confidence) in [
+            ("t-1", "linear", "First step", 0.6),
+            ("t-2", "linear", "Second step", 0.8),
+        ] {
+            storage
+                .save_stored_thought(&StoredThought::new(
+                    id,
+                    "sess-export",
+                    mode,
+                    content,
+                    confidence,
+                ))
+                .await
+                .expect("save thought");
+        }
+        storage
+    }
+
+    async fn add_graph(storage: &SqliteStorage) {
+        storage
+            .save_graph_node(&StoredGraphNode::new("n-1", "sess-export", "Root \"idea\""))
+            .await
+            .expect("save node");
+        storage
+            .save_graph_node(
+                &StoredGraphNode::new("n-2", "sess-export", "Refined idea")
+                    .with_provenance(GraphNodeOrigin::Refine, vec!["n-1".to_string()]),
+            )
+            .await
+            .expect("save node");
+        storage
+            .save_graph_edge(
+                &StoredGraphEdge::new("e-1", "sess-export", "n-1", "n-2")
+                    .with_edge_type(GraphEdgeType::Refines),
+            )
+            .await
+            .expect("save edge");
+    }
+
+    #[test]
+    fn test_format_parse_accepts_known_names_case_insensitively() {
+        assert_eq!(ExportFormat::parse("json").unwrap(), ExportFormat::Json);
+        assert_eq!(
+            ExportFormat::parse(" Markdown ").unwrap(),
+            ExportFormat::Markdown
+        );
+        assert_eq!(ExportFormat::parse("DOT").unwrap(), ExportFormat::Dot);
+        let err = ExportFormat::parse("yaml").expect_err("unknown format");
+        assert!(err.to_string().contains("json, markdown, or dot"));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_json_export_is_well_formed() {
+        let storage = storage_with_session().await;
+        let json = export_session(&storage, "sess-export", ExportFormat::Json)
+            .await
+            .expect("export");
+
+        let parsed: serde_json::Value = serde_json::from_str(&json).expect("valid JSON");
+        assert_eq!(parsed["session"]["id"], "sess-export");
+        assert_eq!(parsed["thoughts"].as_array().expect("thoughts").len(), 2);
+        // No graph data — the graph arrays are omitted, not empty.
+        assert!(parsed.get("graph_nodes").is_none());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_markdown_export_is_well_formed() {
+        let storage = storage_with_session().await;
+        let markdown = export_session(&storage, "sess-export", ExportFormat::Markdown)
+            .await
+            .expect("export");
+
+        assert!(markdown.starts_with("# Session sess-export\n"));
+        assert!(markdown.contains("## Thoughts"));
+        assert!(markdown.contains("### 1. linear (confidence 0.60)"));
+        assert!(markdown.contains("Second step"));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_dot_export_is_well_formed_and_escaped() {
+        let storage = storage_with_session().await;
+        add_graph(&storage).await;
+        let dot = export_session(&storage, "sess-export", ExportFormat::Dot)
+            .await
+            .expect("export");
+
+        assert!(dot.starts_with("digraph reasoning {\n"));
+        assert!(dot.trim_end().ends_with('}'));
+        assert!(dot.contains("\"n-1\" -> \"n-2\" [label=\"refines\"];"));
+        // Quotes inside node content are escaped, not emitted raw.
+        assert!(dot.contains("Root \\\"idea\\\""));
+        // Recorded provenance rides along as a tooltip.
+        assert!(dot.contains("tooltip=\"refine from n-1\""));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_dot_export_without_graph_data_is_a_clear_error() {
+        let storage = storage_with_session().await;
+        let err = export_session(&storage, "sess-export", ExportFormat::Dot)
+            .await
+            .expect_err("no graph data");
+
+        assert!(err.to_string().contains("no graph data"));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_export_unknown_session_is_a_clear_error() {
+        let storage = SqliteStorage::new_in_memory()
+            .await
+            .expect("create storage");
+        let err = export_session(&storage, "missing", ExportFormat::Json)
+            .await
+            .expect_err("missing session");
+
+        assert!(err.to_string().contains("not found"));
+    }
+}
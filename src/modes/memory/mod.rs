@@ -5,11 +5,16 @@
 //! - `resume_session` - Load full context from a past session
 //! - `search_sessions` - Semantic search over reasoning history
 //! - `relate_sessions` - Show relationships between sessions
+//! - `session_quality` - Recency-weighted session quality and its trend
+//! - `open_questions` - Unknowns accumulated across a session
 
 mod cluster;
 mod embed_worker;
 mod embeddings;
+mod export;
 mod list;
+mod open_questions;
+mod quality;
 mod relate;
 mod resume;
 mod search;
@@ -17,7 +22,10 @@ mod similarity;
 mod types;
 
 pub use embed_worker::{process_pending_batch, run_embed_worker, EmbedBatchOutcome};
+pub use export::{export_session, ExportFormat};
 pub use list::list_sessions;
+pub use open_questions::{session_open_questions, SessionOpenQuestions};
+pub use quality::{recency_weighted_quality, session_quality, SessionQuality};
 pub use relate::relate_sessions;
 pub use resume::resume_session;
 pub use search::search_sessions;
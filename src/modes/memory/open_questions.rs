@@ -0,0 +1,159 @@
+//! Accumulated open questions for a session.
+//!
+//! Modes that opt in (linear and evidence so far) persist the questions a
+//! reasoning step surfaced but could not settle as `open_question` thoughts
+//! (see `persist_open_questions`). This module aggregates them across a
+//! session so accumulated unknowns stay visible: questions are returned
+//! oldest first, deduplicated exactly.
+
+use crate::error::ModeError;
+use crate::modes::OPEN_QUESTION_PREFIX;
+use crate::storage::SqliteStorage;
+
+/// The open questions accumulated across a session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionOpenQuestions {
+    /// Session the questions were collected from.
+    pub session_id: String,
+    /// Deduplicated open questions, oldest first.
+    pub open_questions: Vec<String>,
+}
+
+/// Collect the open questions accumulated across a session.
+///
+/// # Errors
+///
+/// Returns [`ModeError::NotFound`] when the session does not exist, or
+/// [`ModeError::StorageError`] when a read fails.
+pub async fn session_open_questions(
+    storage: &SqliteStorage,
+    session_id: &str,
+) -> Result<SessionOpenQuestions, ModeError> {
+    storage
+        .get_stored_session(session_id)
+        .await
+        .map_err(|e| ModeError::StorageError {
+            message: format!("Failed to get session: {e}"),
+        })?
+        .ok_or_else(|| ModeError::NotFound {
+            message: format!("Session not found: {session_id}"),
+        })?;
+
+    let thoughts =
+        storage
+            .get_stored_thoughts(session_id)
+            .await
+            .map_err(|e| ModeError::StorageError {
+                message: format!("Failed to get thoughts: {e}"),
+            })?;
+
+    let mut seen = std::collections::HashSet::new();
+    let open_questions = thoughts
+        .iter()
+        .filter(|t| t.mode == "open_question")
+        .map(|t| {
+            t.content
+                .strip_prefix(OPEN_QUESTION_PREFIX)
+                .unwrap_or(&t.content)
+                .to_string()
+        })
+        .filter(|question| seen.insert(question.clone()))
+        .collect();
+
+    Ok(SessionOpenQuestions {
+        session_id: session_id.to_string(),
+        open_questions,
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::storage::StoredThought;
+
+    async fn save_open_question(storage: &SqliteStorage, session_id: &str, id: &str, q: &str) {
+        let thought = StoredThought::new(
+            id,
+            session_id,
+            "open_question",
+            format!("{OPEN_QUESTION_PREFIX}{q}"),
+            0.5,
+        );
+        storage.save_stored_thought(&thought).await.expect("save");
+    }
+
+    #[tokio::test]
+    async fn test_open_questions_aggregate_across_thoughts() {
+        let storage = SqliteStorage::new_in_memory()
+            .await
+            .expect("create storage");
+        storage
+            .create_session_with_id("sess-oq")
+            .await
+            .expect("create session");
+
+        // Questions surfaced by separate reasoning steps in one session.
+        save_open_question(&storage, "sess-oq", "t1", "What is the churn rate?").await;
+        let analysis =
+            StoredThought::new("t2", "sess-oq", "linear", "Step two of the analysis", 0.8);
+        storage.save_stored_thought(&analysis).await.expect("save");
+        save_open_question(&storage, "sess-oq", "t3", "Does the contract auto-renew?").await;
+
+        let report = session_open_questions(&storage, "sess-oq")
+            .await
+            .expect("aggregate");
+        assert_eq!(
+            report.open_questions,
+            vec!["What is the churn rate?", "Does the contract auto-renew?"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_open_questions_deduplicate_repeats() {
+        let storage = SqliteStorage::new_in_memory()
+            .await
+            .expect("create storage");
+        storage
+            .create_session_with_id("sess-dup")
+            .await
+            .expect("create session");
+
+        save_open_question(&storage, "sess-dup", "t1", "What is the churn rate?").await;
+        save_open_question(&storage, "sess-dup", "t2", "What is the churn rate?").await;
+
+        let report = session_open_questions(&storage, "sess-dup")
+            .await
+            .expect("aggregate");
+        assert_eq!(report.open_questions, vec!["What is the churn rate?"]);
+    }
+
+    #[tokio::test]
+    async fn test_session_without_open_questions_is_empty() {
+        let storage = SqliteStorage::new_in_memory()
+            .await
+            .expect("create storage");
+        storage
+            .create_session_with_id("sess-none")
+            .await
+            .expect("create session");
+        let analysis = StoredThought::new("t1", "sess-none", "linear", "An analysis", 0.8);
+        storage.save_stored_thought(&analysis).await.expect("save");
+
+        let report = session_open_questions(&storage, "sess-none")
+            .await
+            .expect("aggregate");
+        assert!(report.open_questions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_missing_session_is_not_found() {
+        let storage = SqliteStorage::new_in_memory()
+            .await
+            .expect("create storage");
+        let err = session_open_questions(&storage, "nope")
+            .await
+            .expect_err("error");
+        assert!(matches!(err, ModeError::NotFound { .. }), "{err}");
+    }
+}
@@ -0,0 +1,234 @@
+//! Session-level aggregate quality.
+//!
+//! A session's quality is a recency-weighted aggregate of its thought
+//! confidence scores, folded in by storage on every thought write (see
+//! `update_session_quality`). This module reads the stored aggregate,
+//! recomputes it when a pre-migration session has thoughts but no stored
+//! value (the aggregate is derived data and self-heals), and classifies the
+//! trend by comparing the recency-weighted value against the session's
+//! unweighted mean.
+
+use crate::error::ModeError;
+use crate::storage::{SqliteStorage, SESSION_QUALITY_RECENCY_WEIGHT};
+
+/// Trend margin: the recency-weighted quality must differ from the unweighted
+/// mean by more than this before the trend reads as anything but stable.
+const TREND_EPSILON: f64 = 0.05;
+
+/// A session's current quality aggregate and its trend.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionQuality {
+    /// Session the quality was computed for.
+    pub session_id: String,
+    /// Recency-weighted quality aggregate. `None` when the session has no
+    /// scored thoughts yet.
+    pub quality: Option<f64>,
+    /// Trend versus the session's unweighted mean confidence: "improving",
+    /// "declining", or "stable".
+    pub trend: String,
+    /// Number of thoughts in the session.
+    pub thought_count: u32,
+}
+
+/// Fold a sequence of scores (oldest first) into the recency-weighted
+/// aggregate storage maintains: the first score seeds the aggregate, each
+/// later score carries [`SESSION_QUALITY_RECENCY_WEIGHT`].
+#[must_use]
+pub fn recency_weighted_quality(scores: &[f64]) -> Option<f64> {
+    let (first, rest) = scores.split_first()?;
+    Some(rest.iter().fold(*first, |aggregate, score| {
+        score * SESSION_QUALITY_RECENCY_WEIGHT + aggregate * (1.0 - SESSION_QUALITY_RECENCY_WEIGHT)
+    }))
+}
+
+/// Get a session's current quality aggregate and trend.
+///
+/// # Errors
+///
+/// Returns [`ModeError::NotFound`] when the session does not exist, or
+/// [`ModeError::StorageError`] when a read fails.
+pub async fn session_quality(
+    storage: &SqliteStorage,
+    session_id: &str,
+) -> Result<SessionQuality, ModeError> {
+    storage
+        .get_stored_session(session_id)
+        .await
+        .map_err(|e| ModeError::StorageError {
+            message: format!("Failed to get session: {e}"),
+        })?
+        .ok_or_else(|| ModeError::NotFound {
+            message: format!("Session not found: {session_id}"),
+        })?;
+
+    let thoughts =
+        storage
+            .get_stored_thoughts(session_id)
+            .await
+            .map_err(|e| ModeError::StorageError {
+                message: format!("Failed to get thoughts: {e}"),
+            })?;
+    let scores: Vec<f64> = thoughts.iter().map(|t| t.confidence).collect();
+
+    let stored =
+        storage
+            .get_session_quality(session_id)
+            .await
+            .map_err(|e| ModeError::StorageError {
+                message: format!("Failed to get session quality: {e}"),
+            })?;
+    // Sessions written before the quality column existed have thoughts but no
+    // stored aggregate: recompute from the thought chain.
+    let quality = stored.or_else(|| recency_weighted_quality(&scores));
+
+    let trend = quality.map_or("stable", |quality| {
+        let mean = scores.iter().sum::<f64>() / scores.len().max(1) as f64;
+        if quality > mean + TREND_EPSILON {
+            "improving"
+        } else if quality < mean - TREND_EPSILON {
+            "declining"
+        } else {
+            "stable"
+        }
+    });
+
+    Ok(SessionQuality {
+        session_id: session_id.to_string(),
+        quality,
+        trend: trend.to_string(),
+        thought_count: scores.len() as u32,
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::storage::StoredThought;
+
+    async fn session_with_scores(storage: &SqliteStorage, id: &str, scores: &[f64]) {
+        storage
+            .create_session_with_id(id)
+            .await
+            .expect("create session");
+        for (i, score) in scores.iter().enumerate() {
+            let thought = StoredThought::new(
+                format!("{id}-t{i}"),
+                id,
+                "linear",
+                format!("Thought {i} of {id}"),
+                *score,
+            );
+            storage.save_stored_thought(&thought).await.expect("save");
+        }
+    }
+
+    #[test]
+    fn test_recency_weighted_quality_matches_hand_computation() {
+        assert_eq!(recency_weighted_quality(&[]), None);
+        // First thought: quality equals its score.
+        assert_eq!(recency_weighted_quality(&[0.5]), Some(0.5));
+        // 0.3 * 0.9 + 0.7 * 0.5 = 0.62, then 0.3 * 0.7 + 0.7 * 0.62 = 0.644.
+        let quality = recency_weighted_quality(&[0.5, 0.9, 0.7]).expect("aggregate");
+        assert!((quality - 0.644).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_save_thought_updates_stored_quality() {
+        let storage = SqliteStorage::new_in_memory()
+            .await
+            .expect("create storage");
+        session_with_scores(&storage, "sess-q", &[0.5, 0.9, 0.7]).await;
+
+        let stored = storage
+            .get_session_quality("sess-q")
+            .await
+            .expect("get quality")
+            .expect("quality set");
+        assert!((stored - 0.644).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_first_thought_quality_equals_its_score() {
+        let storage = SqliteStorage::new_in_memory()
+            .await
+            .expect("create storage");
+        session_with_scores(&storage, "sess-first", &[0.8]).await;
+
+        let report = session_quality(&storage, "sess-first")
+            .await
+            .expect("quality");
+        assert_eq!(report.quality, Some(0.8));
+        assert_eq!(report.trend, "stable");
+        assert_eq!(report.thought_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_improving_sequence_reads_improving() {
+        let storage = SqliteStorage::new_in_memory()
+            .await
+            .expect("create storage");
+        session_with_scores(&storage, "sess-up", &[0.2, 0.2, 0.2, 0.9, 0.9]).await;
+
+        let report = session_quality(&storage, "sess-up").await.expect("quality");
+        assert_eq!(report.trend, "improving");
+        assert_eq!(report.thought_count, 5);
+    }
+
+    #[tokio::test]
+    async fn test_declining_sequence_reads_declining() {
+        let storage = SqliteStorage::new_in_memory()
+            .await
+            .expect("create storage");
+        session_with_scores(&storage, "sess-down", &[0.9, 0.9, 0.9, 0.2, 0.2]).await;
+
+        let report = session_quality(&storage, "sess-down")
+            .await
+            .expect("quality");
+        assert_eq!(report.trend, "declining");
+    }
+
+    #[tokio::test]
+    async fn test_empty_session_has_no_quality() {
+        let storage = SqliteStorage::new_in_memory()
+            .await
+            .expect("create storage");
+        session_with_scores(&storage, "sess-empty", &[]).await;
+
+        let report = session_quality(&storage, "sess-empty")
+            .await
+            .expect("quality");
+        assert_eq!(report.quality, None);
+        assert_eq!(report.trend, "stable");
+        assert_eq!(report.thought_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_missing_session_is_not_found() {
+        let storage = SqliteStorage::new_in_memory()
+            .await
+            .expect("create storage");
+        let err = session_quality(&storage, "nope").await.expect_err("error");
+        assert!(matches!(err, ModeError::NotFound { .. }), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_pre_migration_session_recomputes_from_thoughts() {
+        let storage = SqliteStorage::new_in_memory()
+            .await
+            .expect("create storage");
+        session_with_scores(&storage, "sess-old", &[0.5, 0.9]).await;
+
+        // Simulate a session written before the quality column existed.
+        sqlx::query("UPDATE sessions SET quality = NULL WHERE id = 'sess-old'")
+            .execute(&storage.get_pool())
+            .await
+            .expect("clear quality");
+
+        let report = session_quality(&storage, "sess-old")
+            .await
+            .expect("quality");
+        // 0.3 * 0.9 + 0.7 * 0.5 = 0.62, rebuilt from the thought chain.
+        assert!((report.quality.expect("recomputed") - 0.62).abs() < 1e-9);
+    }
+}
@@ -165,6 +165,7 @@ async fn compress_session<C: AnthropicClientTrait>(
 
     let messages = vec![Message::user(&prompt)];
     let config = CompletionConfig::new()
+        .with_mode("memory")
         .with_max_tokens(600)
         .with_temperature(0.3); // Low temperature for factual summarization
 
@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::error::ModeError;
 use crate::metrics::{MetricsCollector, ToolEffectiveness, TransitionStats};
-use crate::modes::{extract_json, validate_content};
+use crate::modes::{extract_json, reject_unknown_keys, validate_content, ModeFlags};
 use crate::traits::{AnthropicClientTrait, CompletionConfig, Message, StorageTrait};
 
 /// Problem type classification result.
@@ -47,6 +47,8 @@ where
     #[allow(dead_code)]
     storage: S,
     client: C,
+    /// Behavior flags injected from server configuration (all off by default).
+    flags: ModeFlags,
 }
 
 impl<S, C> MetaMode<S, C>
@@ -56,7 +58,19 @@ where
 {
     /// Create a new meta-reasoning mode.
     pub fn new(storage: S, client: C) -> Self {
-        Self { storage, client }
+        Self {
+            storage,
+            client,
+            flags: ModeFlags::default(),
+        }
+    }
+
+    /// Inject the server's behavior flags ([`ModeFlags`]). A mode built
+    /// without them — as in most tests — uses the all-off defaults.
+    #[must_use]
+    pub fn with_flags(mut self, flags: ModeFlags) -> Self {
+        self.flags = flags;
+        self
     }
 
     /// Route to optimal tool based on problem classification and effectiveness data.
@@ -253,6 +267,7 @@ Respond: {{"problem_type": "category", "reasoning": "one sentence why"}}"#
 
         let messages = vec![Message::user(prompt)];
         let config = CompletionConfig::default()
+            .with_mode("meta")
             .with_max_tokens(256)
             .with_temperature(0.2);
 
@@ -264,6 +279,11 @@ Respond: {{"problem_type": "category", "reasoning": "one sentence why"}}"#
 
         let json = extract_json(&response.content)?;
 
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &["problem_type", "reasoning"],
+        )?;
         let problem_type = json
             .get("problem_type")
             .and_then(|v| v.as_str())
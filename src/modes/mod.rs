@@ -30,61 +30,86 @@
 
 mod auto;
 mod checkpoint;
+mod chunking;
 mod core;
 mod counterfactual;
 mod decision;
 mod detect;
 mod divergent;
+mod escalation;
 mod evidence;
+mod evidence_timeline;
 mod graph;
+mod language;
 mod linear;
 mod mcts;
 pub mod memory;
 pub mod meta;
 mod reflection;
+mod thought_diff;
 mod timeline;
 mod tree;
 
-pub use auto::{AlternativeMode, AutoMode, AutoResponse};
+pub use auto::{AlternativeMode, AutoMode, AutoResponse, HistoryRecommendation};
 pub use checkpoint::{
     CheckpointContext, CheckpointMode, CheckpointSummary, CreateResponse, ListResponse,
     RestoreResponse, RestoredState,
 };
+pub use chunking::{chunk_content, ContentChunk, CHUNK_MAX_BYTES};
 pub use core::{
+    append_language_instruction, apply_memory_update, build_correction_message,
+    correction_eligible, enforce_context_budget, enforce_session_required, estimate_tokens,
     extract_json, generate_branch_id, generate_checkpoint_id, generate_node_id,
-    generate_session_id, generate_thought_id, serialize_for_log, validate_confidence,
-    validate_content, ModeCore,
+    generate_session_id, generate_thought_id, language_instruction, load_working_memory_block,
+    parse_assumptions, parse_open_questions, parse_probability, persist_assumptions,
+    persist_open_questions, persist_raw_io, reject_unknown_keys, serialize_for_log,
+    validate_confidence, validate_content, Assumption, ModeCore, ModeFlags, RawExchange,
+    MODEL_CONTEXT_WINDOW_TOKENS, OPEN_QUESTION_PREFIX,
 };
 pub use counterfactual::{
     AssociationLevel, CausalAnalysis, CausalConclusions, CausalEdge, CausalModel, CausalQuestion,
-    CausalStrength, CausalVariables, CounterfactualLevel, CounterfactualMode,
+    CausalStrength, CausalVariables, ConsequenceChain, CounterfactualLevel, CounterfactualMode,
     CounterfactualResponse, EdgeType, InterventionLevel, LadderRung,
 };
 pub use decision::{
-    Alignment, BalancedRecommendation, Conflict, ConflictSeverity, Criterion, CriterionType,
-    DecisionMode, DecisionValidation, InfluenceLevel, PairwiseComparison, PairwiseRank,
-    PairwiseResponse, PerspectivesResponse, PreferenceResult, PreferenceStrength, RankedOption,
-    Stakeholder, TopsisCreterion, TopsisDistances, TopsisRank, TopsisResponse, WeightedResponse,
+    diff_decisions, minority_reports, Alignment, BalancedRecommendation, ChallengeResponse,
+    Conflict, ConflictMatrix, ConflictSeverity, Criterion, CriterionJustification, CriterionType,
+    CriterionWeightChange, DecisionDiff, DecisionMode, DecisionValidation, InfluenceLevel,
+    MinorityReport, Normalization, OptionDiff, PairwiseComparison, PairwiseRank, PairwiseResponse,
+    PerspectivesResponse, PreferenceResult, PreferenceStrength, RankedOption, Stakeholder,
+    TopsisCreterion, TopsisDistances, TopsisRank, TopsisResponse, WeightChallenge,
+    WeightedResponse,
 };
 pub use detect::{
-    ArgumentStructure, ArgumentValidity, BiasAssessment, BiasSeverity, BiasesResponse, DetectMode,
-    DetectedBias, DetectedFallacy, FallaciesResponse, FallacyAssessment, FallacyCategory,
-    FallacySeverity, GapCategory, KnowledgeGap, KnowledgeGapAssessment, KnowledgeGapsResponse,
+    bias_interactions, ArgumentStructure, ArgumentValidity, BiasAssessment, BiasInteraction,
+    BiasSeverity, BiasesResponse, CircularChain, CircularResponse, Counterargument,
+    CounterargumentAssessment, CounterargumentResponse, DetectMode, DetectedBias, DetectedFallacy,
+    FallaciesResponse, FallacyAssessment, FallacyCategory, FallacySeverity, GapCategory,
+    KnowledgeGap, KnowledgeGapAssessment, KnowledgeGapsResponse, PremiseAssessment,
+    PremiseVerification, RebuttalStrength,
 };
 pub use divergent::{DivergentMode, DivergentResponse, Perspective};
+pub use escalation::{EscalationPolicy, EscalationRung};
 pub use evidence::{
-    AssessResponse, BeliefDirection, BeliefMagnitude, BeliefUpdate, Credibility, EvidenceAnalysis,
+    credible_interval, rank_value_of_information, AssessResponse, BeliefDirection, BeliefMagnitude,
+    BeliefUpdate, Credibility, CredibleInterval, EvidenceAnalysis, EvidenceConflict, EvidenceGap,
     EvidenceMode, EvidencePiece, EvidenceQuality, OverallEvidenceAssessment, Posterior, Prior,
-    ProbabilisticResponse, SourceType,
+    ProbabilisticResponse, SourceType, SynthesizeResponse, ValueOfInformation,
 };
+pub use evidence_timeline::{evidence_timeline, EvidenceTimeline, EvidenceTimelineEntry};
 pub use graph::{
-    AggregateResponse, ChildNode, ComplexityLevel, ExpansionDirection, FinalizeResponse,
-    FrontierNodeInfo, GenerateResponse, GraphConclusion, GraphMetadata, GraphMetrics, GraphMode,
-    GraphPath, GraphStructure, InitResponse, IntegrationNotes, NodeAssessment, NodeCritique,
-    NodeRecommendation, NodeRelationship, NodeScores, NodeType, PruneCandidate, PruneImpact,
-    PruneReason, PruneResponse, RefineResponse, RefinedNode, RootNode, ScoreResponse,
-    SessionQuality, StateResponse, SuggestedAction, SynthesisNode,
+    quality_rubric, AdvanceResponse, AggregateResponse, ApplyPruneResponse, ChildNode,
+    ComplexityLevel, ExpandedFrontier, ExpansionDirection, FinalizeResponse, FrontierNodeInfo,
+    FrontierObserver, GenerateResponse, GraphConclusion, GraphMetadata, GraphMetrics, GraphMode,
+    GraphPath, GraphStructure, ImportResponse, ImportedEdge, ImportedNode, InitResponse,
+    IntegrationNotes, MergeGraphsResponse, MergedDuplicate, NodeAssessment, NodeCritique,
+    NodeProvenance, NodeRecommendation, NodeRelationship, NodeScores, NodeType, OmittedEdge,
+    PathAdjustment, ProtectedNode, PruneCandidate, PruneImpact, PruneReason, PruneResponse,
+    QualityRubric, RefineResponse, RefinedNode, RestoreSnapshotResponse, RootNode, ScoreResponse,
+    SessionQuality, SnapshotResponse, StateResponse, SuggestedAction, SynthesisNode, TreeViewNode,
+    TreeViewResponse,
 };
+pub use language::detect_language;
 pub use linear::{LinearMode, LinearResponse};
 pub use mcts::{
     AlternativeAction, AlternativeOption, Backpropagation, BacktrackDecision, BacktrackResponse,
@@ -95,11 +120,14 @@ pub use reflection::{
     EvaluateResponse, Improvement, Priority, ProcessResponse, ReasoningAnalysis, ReflectionMode,
     SessionAssessment,
 };
+pub use thought_diff::{diff_thoughts, FieldChange, SimilaritySummary, ThoughtDiff};
 pub use timeline::{
-    BranchComparison, BranchDifference, BranchEvent, BranchPoint, BranchResponse, CommonPattern,
-    CompareRecommendation, CompareResponse, CreateTimelineResponse, DecisionPoint, EventType,
-    FragileStrategy, MergeResponse, OpportunityAssessment, RiskAssessment, RobustStrategy,
-    TemporalStructure, TimelineBranch, TimelineEvent, TimelineMode,
+    branch_path_probability, branch_sensitivity_sweep, BranchComparison, BranchDifference,
+    BranchEvent, BranchPoint, BranchResponse, CommonPattern, CompareRecommendation,
+    CompareResponse, CreateTimelineResponse, DecisionPoint, EventType, FragileStrategy,
+    MergeRecommendation, MergeResponse, OpportunityAssessment, RiskAssessment, RobustStrategy,
+    SensitivitySweep, TemporalStructure, TimelineBranch, TimelineEvent, TimelineMode,
+    LEGACY_RECOMMENDATION_CONFIDENCE, SENSITIVITY_SWEEP_STEP,
 };
 pub use tree::{Branch, BranchStatus, TreeMode, TreeResponse};
 
@@ -15,8 +15,13 @@ pub use types::{
 
 use crate::anthropic::StreamAccumulator;
 use crate::error::ModeError;
-use crate::modes::{extract_json, generate_thought_id, validate_content};
-use crate::prompts::{get_prompt_for_mode, Operation, ReasoningMode};
+use crate::modes::{
+    append_language_instruction, extract_json, generate_thought_id, reject_unknown_keys,
+    validate_content, ModeFlags,
+};
+use crate::prompts::{
+    get_prompt_for_mode, reflection_batch_summary_prompt, Operation, ReasoningMode,
+};
 use crate::server::{ProgressMilestone, ProgressReporter};
 use crate::traits::{
     AnthropicClientTrait, CompletionConfig, Message, Session, StorageTrait, Thought,
@@ -27,6 +32,28 @@ use parsing::{parse_analysis, parse_improvements, parse_session_assessment, pars
 /// Maximum number of refinement passes the process loop will perform.
 const MAX_ITERATIONS: u32 = 5;
 
+/// Default character cap for the evaluate context. Above it, older thoughts
+/// are summarized in batches instead of concatenated verbatim, so evaluating
+/// a long session cannot blow the prompt.
+const MAX_EVALUATE_CONTEXT_CHARS: usize = 24_000;
+
+/// Thoughts kept verbatim at the tail of an over-cap evaluate context; the
+/// evaluation still sees the session's latest reasoning word for word.
+const RECENT_THOUGHTS_VERBATIM: usize = 5;
+
+/// Older thoughts condensed per summarization completion.
+const SUMMARY_BATCH_SIZE: usize = 10;
+
+/// Top-level keys the evaluate response parser reads (strict parsing).
+const EVALUATE_RESPONSE_KEYS: &[&str] = &[
+    "session_assessment",
+    "strongest_elements",
+    "areas_for_improvement",
+    "key_insights",
+    "recommendations",
+    "meta_observations",
+];
+
 /// Parsed result of a single reflection pass, before persistence/iteration.
 struct ReflectionPass {
     analysis: ReasoningAnalysis,
@@ -46,6 +73,14 @@ where
 {
     storage: S,
     client: C,
+    /// Behavior flags injected from server configuration (all off by default).
+    flags: ModeFlags,
+    /// Effective output language (per-request override merged with the
+    /// configured default at the server boundary).
+    language: Option<String>,
+    /// Character cap for the evaluate context; above it older thoughts are
+    /// summarized in batches before evaluation.
+    max_evaluate_context_chars: usize,
 }
 
 impl<S, C> ReflectionMode<S, C>
@@ -56,7 +91,105 @@ where
     /// Create a new reflection mode instance.
     #[must_use]
     pub fn new(storage: S, client: C) -> Self {
-        Self { storage, client }
+        Self {
+            storage,
+            client,
+            flags: ModeFlags::default(),
+            language: None,
+            max_evaluate_context_chars: MAX_EVALUATE_CONTEXT_CHARS,
+        }
+    }
+
+    /// Override the evaluate context cap (default
+    /// [`MAX_EVALUATE_CONTEXT_CHARS`]; clamped to at least 1,000 characters so
+    /// the context cannot be squeezed into uselessness).
+    #[must_use]
+    pub fn with_max_evaluate_context_chars(mut self, max_evaluate_context_chars: usize) -> Self {
+        self.max_evaluate_context_chars = max_evaluate_context_chars.max(1_000);
+        self
+    }
+
+    /// Set the output language for natural-language response fields. The
+    /// server passes the per-request override merged with the configured
+    /// `RESPONSE_LANGUAGE` default; `None` adds no language instruction.
+    #[must_use]
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Inject the server's behavior flags ([`ModeFlags`]). A mode built
+    /// without them — as in most tests — uses the all-off defaults.
+    #[must_use]
+    pub fn with_flags(mut self, flags: ModeFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Build the evaluate context from a session's thoughts.
+    ///
+    /// Short sessions concatenate every thought verbatim — the long-standing
+    /// behavior. When the concatenation would exceed the context cap, older
+    /// thoughts are first condensed in batches of [`SUMMARY_BATCH_SIZE`] (one
+    /// completion per batch) and only the last [`RECENT_THOUGHTS_VERBATIM`]
+    /// thoughts are kept word for word, so session-wide evaluation keeps
+    /// working on long sessions without blowing the prompt. The cap is
+    /// absolute: the result is truncated to it even after summarization.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModeError`] when a summarization completion fails.
+    async fn build_evaluate_context(&self, thoughts: &[Thought]) -> Result<String, ModeError> {
+        let formatted: Vec<String> = thoughts
+            .iter()
+            .map(|t| format!("[{}] {}", t.mode, t.content))
+            .collect();
+        let full = formatted.join("\n\n");
+        if full.chars().count() <= self.max_evaluate_context_chars {
+            return Ok(full);
+        }
+
+        let split = formatted.len().saturating_sub(RECENT_THOUGHTS_VERBATIM);
+        let (older, recent) = formatted.split_at(split);
+        if older.is_empty() {
+            // A handful of giant thoughts: nothing older to condense, so the
+            // cap is enforced by truncation alone.
+            return Ok(full.chars().take(self.max_evaluate_context_chars).collect());
+        }
+
+        tracing::info!(
+            chars = full.len(),
+            cap = self.max_evaluate_context_chars,
+            summarized_thoughts = older.len(),
+            "Evaluate context over cap — summarizing older thoughts in batches"
+        );
+
+        let prompt = reflection_batch_summary_prompt();
+        let mut summaries = Vec::new();
+        for batch in older.chunks(SUMMARY_BATCH_SIZE) {
+            let user_message = format!("{prompt}\n\nReasoning steps:\n{}", batch.join("\n\n"));
+            let config = CompletionConfig::new()
+                .with_mode("reflection")
+                .with_max_tokens(1024)
+                .with_temperature(0.3)
+                // The summary prompt opens the message verbatim — cacheable.
+                .with_cached_prompt_len(prompt.len());
+            let response = self
+                .client
+                .complete(vec![Message::user(user_message)], config)
+                .await?;
+            summaries.push(response.content.trim().to_string());
+        }
+
+        let context = format!(
+            "Earlier reasoning (summarized):\n{}\n\nMost recent reasoning (verbatim):\n{}",
+            summaries.join("\n\n"),
+            recent.join("\n\n")
+        );
+        Ok(context
+            .chars()
+            .take(self.max_evaluate_context_chars)
+            .collect())
     }
 
     /// Process reasoning for improvement.
@@ -124,6 +257,11 @@ where
         session_id: &str,
         summary: Option<&str>,
     ) -> Result<EvaluateResponse, ModeError> {
+        crate::modes::enforce_session_required(
+            self.flags.require_session_id,
+            "reflection",
+            Some(session_id),
+        )?;
         let session = self
             .storage
             .get_session(session_id)
@@ -161,17 +299,18 @@ where
                 ],
             ));
         } else {
-            thoughts
-                .iter()
-                .map(|t| format!("[{}] {}", t.mode, t.content))
-                .collect::<Vec<_>>()
-                .join("\n\n")
+            self.build_evaluate_context(&thoughts).await?
         };
 
-        let prompt = get_prompt_for_mode(ReasoningMode::Reflection, Some(&Operation::Evaluate));
+        let prompt = append_language_instruction(
+            get_prompt_for_mode(ReasoningMode::Reflection, Some(&Operation::Evaluate)),
+            self.language.as_deref(),
+            &context,
+        );
         let user_message = format!("{prompt}\n\nEvaluate this reasoning session:\n{context}");
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("reflection")
             .with_max_tokens(16384)
             .with_temperature(0.7)
             .with_deep_thinking();
@@ -179,6 +318,8 @@ where
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
 
+        reject_unknown_keys(self.flags.strict_parsing, &json, EVALUATE_RESPONSE_KEYS)?;
+
         // Parse session assessment
         let session_assessment = parse_session_assessment(&json)?;
 
@@ -319,6 +460,11 @@ where
         summary: Option<&str>,
         progress: Option<&ProgressReporter>,
     ) -> Result<EvaluateResponse, ModeError> {
+        crate::modes::enforce_session_required(
+            self.flags.require_session_id,
+            "reflection",
+            Some(session_id),
+        )?;
         if let Some(p) = progress {
             p.report_milestone(ProgressMilestone::RequestPrepared);
         }
@@ -360,17 +506,18 @@ where
                 ],
             ));
         } else {
-            thoughts
-                .iter()
-                .map(|t| format!("[{}] {}", t.mode, t.content))
-                .collect::<Vec<_>>()
-                .join("\n\n")
+            self.build_evaluate_context(&thoughts).await?
         };
 
-        let prompt = get_prompt_for_mode(ReasoningMode::Reflection, Some(&Operation::Evaluate));
+        let prompt = append_language_instruction(
+            get_prompt_for_mode(ReasoningMode::Reflection, Some(&Operation::Evaluate)),
+            self.language.as_deref(),
+            &context,
+        );
         let user_message = format!("{prompt}\n\nEvaluate this reasoning session:\n{context}");
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("reflection")
             .with_max_tokens(16384)
             .with_temperature(0.7)
             .with_deep_thinking();
@@ -398,6 +545,8 @@ where
         let response_text = accumulator.text();
         let json = extract_json(&response_text)?;
 
+        reject_unknown_keys(self.flags.strict_parsing, &json, EVALUATE_RESPONSE_KEYS)?;
+
         // Parse session assessment
         let session_assessment = parse_session_assessment(&json)?;
 
@@ -480,7 +629,19 @@ where
     }
 
     /// Parse a single reflection pass from an LLM JSON response.
-    fn parse_pass(json: &serde_json::Value) -> Result<ReflectionPass, ModeError> {
+    fn parse_pass(&self, json: &serde_json::Value) -> Result<ReflectionPass, ModeError> {
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            json,
+            &[
+                "analysis",
+                "improvements",
+                "refined_reasoning",
+                "quality_score",
+                "confidence_improvement",
+            ],
+        )?;
+
         let analysis = parse_analysis(json)?;
         let improvements = parse_improvements(json)?;
 
@@ -521,18 +682,23 @@ where
         content: &str,
         prior_context: &str,
     ) -> Result<ReflectionPass, ModeError> {
-        let prompt = get_prompt_for_mode(ReasoningMode::Reflection, Some(&Operation::Process));
+        let prompt = append_language_instruction(
+            get_prompt_for_mode(ReasoningMode::Reflection, Some(&Operation::Process)),
+            self.language.as_deref(),
+            content,
+        );
         let user_message =
             format!("{prompt}\n\n{prior_context}Analyze and improve this reasoning:\n{content}");
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("reflection")
             .with_max_tokens(16384)
             .with_temperature(0.7)
             .with_deep_thinking();
 
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
-        Self::parse_pass(&json)
+        self.parse_pass(&json)
     }
 
     /// Run one streaming reflection pass over `content`, reporting progress.
@@ -542,11 +708,16 @@ where
         prior_context: &str,
         progress: Option<&ProgressReporter>,
     ) -> Result<ReflectionPass, ModeError> {
-        let prompt = get_prompt_for_mode(ReasoningMode::Reflection, Some(&Operation::Process));
+        let prompt = append_language_instruction(
+            get_prompt_for_mode(ReasoningMode::Reflection, Some(&Operation::Process)),
+            self.language.as_deref(),
+            content,
+        );
         let user_message =
             format!("{prompt}\n\n{prior_context}Analyze and improve this reasoning:\n{content}");
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("reflection")
             .with_max_tokens(16384)
             .with_temperature(0.7)
             .with_deep_thinking();
@@ -570,7 +741,7 @@ where
         }
         let response_text = accumulator.text();
         let json = extract_json(&response_text)?;
-        Self::parse_pass(&json)
+        self.parse_pass(&json)
     }
 
     /// Persist the reflection thought (using the assessed quality as confidence)
@@ -926,6 +1097,113 @@ mod tests {
         assert!(response.meta_observations.is_some());
     }
 
+    #[tokio::test]
+    async fn test_reflection_evaluate_long_session_summarizes_older_thoughts() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_session()
+            .returning(|id| Ok(Some(Session::new(id))));
+        // 20 thoughts of ~330 characters each: well over a 4,000-char cap.
+        mock_storage.expect_get_thoughts().returning(|_| {
+            Ok((0..20)
+                .map(|i| {
+                    let content = format!("Thought number {i:02}. {}", "padding text ".repeat(24));
+                    Thought::new(format!("t-{i}"), "s-long", content, "linear", 0.8)
+                })
+                .collect())
+        });
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+
+        // 15 older thoughts in batches of 10 → two summarization completions.
+        mock_client
+            .expect_complete()
+            .withf(|messages, _| {
+                messages[0]
+                    .content
+                    .contains("Summarize the following reasoning steps")
+            })
+            .times(2)
+            .returning(|_, _| {
+                Ok(CompletionResponse::new(
+                    "BATCH-SUMMARY.",
+                    Usage::new(10, 20),
+                ))
+            });
+
+        // The evaluate completion sees a context under the cap that keeps the
+        // recent thoughts verbatim and replaces older ones with summaries.
+        let response_json = mock_evaluate_response();
+        mock_client
+            .expect_complete()
+            .withf(|messages, _| {
+                let context = messages[0]
+                    .content
+                    .split("Evaluate this reasoning session:\n")
+                    .nth(1)
+                    .unwrap_or("");
+                context.chars().count() <= 4_000
+                    && context.contains("BATCH-SUMMARY.")
+                    && context.contains("Thought number 19")
+                    && !context.contains("Thought number 03")
+            })
+            .times(1)
+            .returning(move |_, _| {
+                Ok(CompletionResponse::new(
+                    response_json.clone(),
+                    Usage::new(100, 200),
+                ))
+            });
+
+        let mode =
+            ReflectionMode::new(mock_storage, mock_client).with_max_evaluate_context_chars(4_000);
+        let result = mode.evaluate("s-long", None).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reflection_evaluate_short_session_stays_verbatim() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_session()
+            .returning(|id| Ok(Some(Session::new(id))));
+        mock_storage.expect_get_thoughts().returning(|_| {
+            Ok(vec![
+                Thought::new("t-1", "s-short", "First short thought", "linear", 0.8),
+                Thought::new("t-2", "s-short", "Second short thought", "linear", 0.7),
+            ])
+        });
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+
+        // Under the cap: a single evaluate completion with every thought
+        // verbatim and no summarization pass.
+        let response_json = mock_evaluate_response();
+        mock_client
+            .expect_complete()
+            .withf(|messages, _| {
+                let content = &messages[0].content;
+                content.contains("[linear] First short thought")
+                    && content.contains("[linear] Second short thought")
+                    && !content.contains("Earlier reasoning (summarized)")
+            })
+            .times(1)
+            .returning(move |_, _| {
+                Ok(CompletionResponse::new(
+                    response_json.clone(),
+                    Usage::new(100, 200),
+                ))
+            });
+
+        let mode = ReflectionMode::new(mock_storage, mock_client);
+        let result = mode.evaluate("s-short", None).await;
+
+        assert!(result.is_ok());
+    }
+
     #[tokio::test]
     async fn test_reflection_evaluate_with_summary() {
         let mut mock_storage = MockStorageTrait::new();
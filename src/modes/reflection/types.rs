@@ -23,6 +23,18 @@ pub enum Priority {
     Low,
 }
 
+impl Priority {
+    /// Returns the lowercase string representation.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::High => "high",
+            Self::Medium => "medium",
+            Self::Low => "low",
+        }
+    }
+}
+
 impl std::str::FromStr for Priority {
     type Err = ();
 
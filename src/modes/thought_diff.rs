@@ -0,0 +1,409 @@
+//! Structured diffing of two arbitrary stored thoughts.
+//!
+//! [`diff_decisions`] compares two weighted decision analyses; this module
+//! generalizes the idea to any pair of thought ids. Same-mode pairs get a
+//! field-level diff of their stored typed payloads (weighted decisions reuse
+//! the specialized decision diff), while cross-mode pairs get a textual
+//! similarity summary instead — payloads of different shapes have no fields
+//! worth lining up. The comparison is purely structural: no API calls and no
+//! storage access, so thoughts from different sessions diff like any others.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+
+use super::decision::{diff_decisions, DecisionDiff, WeightedResponse};
+use crate::storage::StoredThought;
+
+/// Mode string of thoughts carrying a full weighted decision analysis.
+const WEIGHTED_MODE: &str = "decision_weighted";
+
+/// Cap on the shared vocabulary sample in a similarity summary.
+const MAX_SHARED_TERMS: usize = 10;
+
+/// One field-level difference between two same-mode stored payloads.
+///
+/// A side is `None` when the field is absent from that payload, so added and
+/// removed fields still get an entry with their known half filled.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FieldChange {
+    /// Dotted path of the field (array elements by index, e.g. `ranking.0.score`).
+    pub path: String,
+    /// Value in the first thought's payload, if the field exists there.
+    pub value_a: Option<serde_json::Value>,
+    /// Value in the second thought's payload, if the field exists there.
+    pub value_b: Option<serde_json::Value>,
+}
+
+/// Textual similarity summary for thoughts whose types do not line up.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SimilaritySummary {
+    /// Jaccard word overlap of the two contents in [0.0, 1.0].
+    pub similarity: f64,
+    /// Up to [`MAX_SHARED_TERMS`] shared content words, alphabetical.
+    pub shared_terms: Vec<String>,
+    /// One-line human-readable summary of the comparison.
+    pub summary: String,
+}
+
+/// Structured comparison of two stored thoughts.
+///
+/// Exactly one of `decision_diff`, `field_changes`, and `similarity` is
+/// populated: the specialized decision diff when both thoughts are weighted
+/// decision analyses, a field-level diff for other same-mode pairs with
+/// stored payloads, and the similarity summary otherwise.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ThoughtDiff {
+    /// Mode of the first thought.
+    pub mode_a: String,
+    /// Mode of the second thought.
+    pub mode_b: String,
+    /// True when both thoughts have the same mode.
+    pub same_type: bool,
+    /// True when the thoughts come from different sessions.
+    pub cross_session: bool,
+    /// Confidence movement (`confidence_b - confidence_a`).
+    pub confidence_delta: f64,
+    /// Specialized diff when both thoughts are weighted decision analyses.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decision_diff: Option<DecisionDiff>,
+    /// Field-level changes for other same-mode pairs with stored payloads.
+    /// Unchanged fields are omitted; an empty list means identical payloads.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field_changes: Option<Vec<FieldChange>>,
+    /// Similarity summary for cross-mode pairs, and for same-mode pairs
+    /// without comparable stored payloads.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub similarity: Option<SimilaritySummary>,
+}
+
+/// Compare two stored thoughts and report what differs.
+#[must_use]
+pub fn diff_thoughts(a: &StoredThought, b: &StoredThought) -> ThoughtDiff {
+    let same_type = a.mode == b.mode;
+    let mut diff = ThoughtDiff {
+        mode_a: a.mode.clone(),
+        mode_b: b.mode.clone(),
+        same_type,
+        cross_session: a.session_id != b.session_id,
+        confidence_delta: b.confidence - a.confidence,
+        decision_diff: None,
+        field_changes: None,
+        similarity: None,
+    };
+
+    if same_type {
+        if a.mode == WEIGHTED_MODE {
+            if let (Some(weighted_a), Some(weighted_b)) = (weighted_payload(a), weighted_payload(b))
+            {
+                diff.decision_diff = Some(diff_decisions(&weighted_a, &weighted_b));
+                return diff;
+            }
+        }
+        if let (Some(payload_a), Some(payload_b)) = (object_payload(a), object_payload(b)) {
+            let mut changes = Vec::new();
+            collect_field_changes("", &payload_a, &payload_b, &mut changes);
+            diff.field_changes = Some(changes);
+            return diff;
+        }
+    }
+
+    diff.similarity = Some(similarity_summary(a, b));
+    diff
+}
+
+/// The full weighted analysis persisted on a decision thought, if readable.
+fn weighted_payload(thought: &StoredThought) -> Option<WeightedResponse> {
+    serde_json::from_str(thought.metadata.as_deref()?).ok()
+}
+
+/// The thought's stored payload as a JSON object, if readable.
+fn object_payload(thought: &StoredThought) -> Option<serde_json::Value> {
+    serde_json::from_str(thought.metadata.as_deref()?)
+        .ok()
+        .filter(serde_json::Value::is_object)
+}
+
+/// Recursively collect field-level changes between two JSON values.
+///
+/// Objects recurse key-wise (union of keys, sorted for determinism), arrays
+/// recurse index-wise, and anything else is compared wholesale. Equal values
+/// produce no entry.
+fn collect_field_changes(
+    path: &str,
+    a: &serde_json::Value,
+    b: &serde_json::Value,
+    out: &mut Vec<FieldChange>,
+) {
+    if a == b {
+        return;
+    }
+    match (a, b) {
+        (serde_json::Value::Object(map_a), serde_json::Value::Object(map_b)) => {
+            let keys: BTreeSet<&String> = map_a.keys().chain(map_b.keys()).collect();
+            for key in keys {
+                let child = join_path(path, key);
+                match (map_a.get(key.as_str()), map_b.get(key.as_str())) {
+                    (Some(value_a), Some(value_b)) => {
+                        collect_field_changes(&child, value_a, value_b, out);
+                    }
+                    (value_a, value_b) => out.push(FieldChange {
+                        path: child,
+                        value_a: value_a.cloned(),
+                        value_b: value_b.cloned(),
+                    }),
+                }
+            }
+        }
+        (serde_json::Value::Array(items_a), serde_json::Value::Array(items_b)) => {
+            for i in 0..items_a.len().max(items_b.len()) {
+                let child = join_path(path, &i.to_string());
+                match (items_a.get(i), items_b.get(i)) {
+                    (Some(value_a), Some(value_b)) => {
+                        collect_field_changes(&child, value_a, value_b, out);
+                    }
+                    (value_a, value_b) => out.push(FieldChange {
+                        path: child,
+                        value_a: value_a.cloned(),
+                        value_b: value_b.cloned(),
+                    }),
+                }
+            }
+        }
+        _ => out.push(FieldChange {
+            path: path.to_string(),
+            value_a: Some(a.clone()),
+            value_b: Some(b.clone()),
+        }),
+    }
+}
+
+/// Join a dotted path with the next segment.
+fn join_path(path: &str, segment: &str) -> String {
+    if path.is_empty() {
+        segment.to_string()
+    } else {
+        format!("{path}.{segment}")
+    }
+}
+
+/// Word-overlap similarity of the two contents, with a one-line summary.
+///
+/// Mirrors the dedup similarity in the storage layer: cheap, local Jaccard
+/// overlap — a structural comparison must not cost an embedding call.
+fn similarity_summary(a: &StoredThought, b: &StoredThought) -> SimilaritySummary {
+    let words = |text: &str| -> HashSet<String> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(str::to_lowercase)
+            .collect()
+    };
+    let words_a = words(&a.content);
+    let words_b = words(&b.content);
+
+    let shared: BTreeSet<&String> = words_a.intersection(&words_b).collect();
+    let union = words_a.union(&words_b).count();
+    let similarity = if union == 0 {
+        0.0
+    } else {
+        shared.len() as f64 / union as f64
+    };
+    let shared_terms: Vec<String> = shared
+        .iter()
+        .take(MAX_SHARED_TERMS)
+        .map(|term| (*term).clone())
+        .collect();
+
+    let summary = if a.mode == b.mode {
+        format!(
+            "Both thoughts are '{}' but carry no comparable stored payloads; \
+             contents share {:.0}% of their vocabulary",
+            a.mode,
+            similarity * 100.0
+        )
+    } else {
+        format!(
+            "Different result types ('{}' vs '{}') — no fields to line up; \
+             contents share {:.0}% of their vocabulary",
+            a.mode,
+            b.mode,
+            similarity * 100.0
+        )
+    };
+
+    SimilaritySummary {
+        similarity,
+        shared_terms,
+        summary,
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp,
+    clippy::approx_constant,
+    clippy::unreadable_literal
+)]
+mod tests {
+    use super::*;
+    use crate::modes::{Criterion, RankedOption};
+    use std::collections::HashMap;
+
+    fn weighted_thought(
+        thought_id: &str,
+        session_id: &str,
+        ranking: &[(&str, f64, u32)],
+        criteria: &[(&str, f64)],
+    ) -> StoredThought {
+        let response = WeightedResponse::new(
+            thought_id,
+            session_id,
+            ranking.iter().map(|(o, _, _)| (*o).to_string()).collect(),
+            criteria
+                .iter()
+                .map(|(name, weight)| Criterion {
+                    name: (*name).to_string(),
+                    weight: *weight,
+                    description: String::new(),
+                })
+                .collect(),
+            HashMap::new(),
+            HashMap::new(),
+            ranking
+                .iter()
+                .map(|(option, score, rank)| RankedOption {
+                    option: (*option).to_string(),
+                    score: *score,
+                    rank: *rank,
+                })
+                .collect(),
+            "notes",
+        );
+        StoredThought::new(
+            thought_id,
+            session_id,
+            WEIGHTED_MODE,
+            "Decision weighted: seeded",
+            ranking.first().map_or(0.0, |(_, score, _)| *score),
+        )
+        .with_metadata(serde_json::to_string(&response).expect("serialize analysis"))
+    }
+
+    #[test]
+    fn test_diff_two_weighted_decisions_reports_rank_and_score_movements() {
+        let a = weighted_thought(
+            "t-a",
+            "sess-1",
+            &[("Alpha", 0.8, 1), ("Beta", 0.6, 2)],
+            &[("cost", 0.5), ("speed", 0.5)],
+        );
+        let b = weighted_thought(
+            "t-b",
+            "sess-2",
+            &[("Beta", 0.75, 1), ("Alpha", 0.7, 2)],
+            &[("cost", 0.3), ("speed", 0.7)],
+        );
+
+        let diff = diff_thoughts(&a, &b);
+        assert!(diff.same_type);
+        assert!(diff.cross_session);
+        assert!(diff.field_changes.is_none());
+        assert!(diff.similarity.is_none());
+
+        let decision = diff.decision_diff.expect("decision diff");
+        assert!(decision.winner_changed);
+        assert_eq!(decision.options[0].option, "Beta");
+        assert_eq!(decision.options[0].rank_delta, Some(1));
+        assert_eq!(decision.options[0].score_delta, Some(0.75 - 0.6));
+        assert_eq!(decision.options[1].option, "Alpha");
+        assert_eq!(decision.options[1].rank_delta, Some(-1));
+        assert_eq!(decision.criteria_changes[0].name, "cost");
+    }
+
+    #[test]
+    fn test_same_type_field_level_diff_omits_unchanged_fields() {
+        let a = StoredThought::new("t-a", "sess-1", "evidence_assess", "Assessment", 0.7)
+            .with_metadata(r#"{"quality": "high", "gaps": ["sample size"], "pieces": 3}"#);
+        let b = StoredThought::new("t-b", "sess-1", "evidence_assess", "Assessment", 0.9)
+            .with_metadata(
+                r#"{"quality": "high", "gaps": ["sample size", "recency"], "pieces": 4}"#,
+            );
+
+        let diff = diff_thoughts(&a, &b);
+        assert!(diff.same_type);
+        assert!(!diff.cross_session);
+        assert!((diff.confidence_delta - 0.2).abs() < 1e-9);
+
+        let changes = diff.field_changes.expect("field changes");
+        assert_eq!(
+            changes,
+            vec![
+                FieldChange {
+                    path: "gaps.1".to_string(),
+                    value_a: None,
+                    value_b: Some(serde_json::json!("recency")),
+                },
+                FieldChange {
+                    path: "pieces".to_string(),
+                    value_a: Some(serde_json::json!(3)),
+                    value_b: Some(serde_json::json!(4)),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cross_mode_pair_gets_similarity_summary() {
+        let a = StoredThought::new(
+            "t-a",
+            "sess-1",
+            "linear",
+            "Use an index on the database table",
+            0.8,
+        );
+        let b = StoredThought::new(
+            "t-b",
+            "sess-2",
+            WEIGHTED_MODE,
+            "Index the database table or shard it",
+            0.9,
+        );
+
+        let diff = diff_thoughts(&a, &b);
+        assert!(!diff.same_type);
+        assert!(diff.decision_diff.is_none());
+        assert!(diff.field_changes.is_none());
+
+        let similarity = diff.similarity.expect("similarity summary");
+        // Shared: index, the, database, table (4); union of 10 distinct words.
+        assert!((similarity.similarity - 0.4).abs() < 1e-9);
+        assert!(similarity.shared_terms.contains(&"database".to_string()));
+        assert!(similarity.summary.contains("Different result types"));
+    }
+
+    #[test]
+    fn test_same_mode_without_payloads_falls_back_to_similarity() {
+        let a = StoredThought::new("t-a", "sess-1", "linear", "Plan the sprint", 0.8);
+        let b = StoredThought::new("t-b", "sess-1", "linear", "Plan the release", 0.8);
+
+        let diff = diff_thoughts(&a, &b);
+        assert!(diff.same_type);
+        assert!(diff.field_changes.is_none());
+        let similarity = diff.similarity.expect("similarity summary");
+        assert!(similarity.summary.contains("no comparable stored payloads"));
+    }
+
+    #[test]
+    fn test_identical_payloads_yield_empty_field_changes() {
+        let a = StoredThought::new("t-a", "sess-1", "evidence_assess", "Assessment", 0.7)
+            .with_metadata(r#"{"quality": "high"}"#);
+        let b = StoredThought::new("t-b", "sess-1", "evidence_assess", "Assessment", 0.7)
+            .with_metadata(r#"{"quality": "high"}"#);
+
+        let diff = diff_thoughts(&a, &b);
+        assert_eq!(diff.field_changes, Some(Vec::new()));
+        assert!((diff.confidence_delta).abs() < 1e-9);
+    }
+}
@@ -25,22 +25,28 @@
 //! ## Merge Operation
 //! - `common_patterns`: Patterns across branches
 //! - `robust_strategies`: Strategies that work across scenarios
-//! - `recommendations`: Actionable next steps
+//! - `recommendations`: Actionable next steps with supporting branches and
+//!   per-recommendation confidence (legacy plain strings still parse)
 
 mod parsing;
 mod types;
 
 pub use types::{
-    BranchComparison, BranchDifference, BranchEvent, BranchPoint, BranchResponse, CommonPattern,
-    CompareRecommendation, CompareResponse, CreateTimelineResponse, DecisionPoint, EventType,
-    FragileStrategy, MergeResponse, OpportunityAssessment, RiskAssessment, RobustStrategy,
-    TemporalStructure, TimelineBranch, TimelineEvent,
+    branch_path_probability, branch_sensitivity_sweep, BranchComparison, BranchDifference,
+    BranchEvent, BranchPoint, BranchResponse, CommonPattern, CompareRecommendation,
+    CompareResponse, CreateTimelineResponse, DecisionPoint, EventType, FragileStrategy,
+    MergeRecommendation, MergeResponse, OpportunityAssessment, RiskAssessment, RobustStrategy,
+    SensitivitySweep, TemporalStructure, TimelineBranch, TimelineEvent,
+    LEGACY_RECOMMENDATION_CONFIDENCE, SENSITIVITY_SWEEP_STEP,
 };
 
 use std::fmt::Write as _;
 
 use crate::error::ModeError;
-use crate::modes::{extract_json, generate_thought_id, validate_content};
+use crate::modes::{
+    apply_memory_update, extract_json, generate_thought_id, language_instruction,
+    load_working_memory_block, reject_unknown_keys, validate_content, ModeFlags,
+};
 use crate::prompts::{
     timeline_branch_prompt, timeline_compare_prompt, timeline_create_prompt, timeline_merge_prompt,
 };
@@ -52,7 +58,8 @@ use parsing::{
     get_str, get_string_array, parse_branch_comparison, parse_branch_point, parse_branches,
     parse_common_patterns, parse_compare_recommendation, parse_decision_points, parse_events,
     parse_fragile_strategies, parse_key_differences, parse_opportunity_assessment,
-    parse_risk_assessment, parse_robust_strategies, parse_temporal_structure,
+    parse_recommendations, parse_risk_assessment, parse_robust_strategies,
+    parse_temporal_structure,
 };
 
 // ============================================================================
@@ -69,6 +76,11 @@ where
 {
     storage: S,
     client: C,
+    /// Behavior flags injected from server configuration (all off by default).
+    flags: ModeFlags,
+    /// Effective output language (per-request override merged with the
+    /// configured default at the server boundary).
+    language: Option<String>,
 }
 
 impl<S, C> TimelineMode<S, C>
@@ -79,7 +91,29 @@ where
     /// Create a new timeline mode instance.
     #[must_use]
     pub fn new(storage: S, client: C) -> Self {
-        Self { storage, client }
+        Self {
+            storage,
+            client,
+            flags: ModeFlags::default(),
+            language: None,
+        }
+    }
+
+    /// Set the output language for natural-language response fields. The
+    /// server passes the per-request override merged with the configured
+    /// `RESPONSE_LANGUAGE` default; `None` adds no language instruction.
+    #[must_use]
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Inject the server's behavior flags ([`ModeFlags`]). A mode built
+    /// without them — as in most tests — uses the all-off defaults.
+    #[must_use]
+    pub fn with_flags(mut self, flags: ModeFlags) -> Self {
+        self.flags = flags;
+        self
     }
 
     /// Create a new timeline.
@@ -109,13 +143,25 @@ where
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("timeline")
             .with_max_tokens(16384)
             .with_temperature(0.4)
             .with_deep_thinking();
 
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
-
+        apply_memory_update(&self.storage, &session.id, &json).await;
+
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &[
+                "timeline_id",
+                "events",
+                "decision_points",
+                "temporal_structure",
+            ],
+        )?;
         let timeline_id = get_str(&json, "timeline_id")?;
         let events = parse_events(&json)?;
         let decision_points = parse_decision_points(&json)?;
@@ -184,15 +230,26 @@ where
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("timeline")
             .with_max_tokens(16384)
             .with_temperature(0.5)
             .with_deep_thinking();
 
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
+        apply_memory_update(&self.storage, &session.id, &json).await;
 
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &["branch_point", "branches", "comparison"],
+        )?;
         let branch_point = parse_branch_point(&json)?;
-        let branches = parse_branches(&json)?;
+        let mut branches = parse_branches(&json)?;
+        // Rank by joint plausibility: the branch whose full event chain is
+        // most likely to actually play out comes first, regardless of the
+        // order the model emitted them in.
+        branches.sort_by(|a, b| b.joint_probability.total_cmp(&a.joint_probability));
         let comparison = parse_branch_comparison(&json)?;
 
         let thought_id = generate_thought_id();
@@ -253,13 +310,27 @@ where
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("timeline")
             .with_max_tokens(16384)
             .with_temperature(0.3)
             .with_deep_thinking();
 
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
-
+        apply_memory_update(&self.storage, &session.id, &json).await;
+
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &[
+                "branches_compared",
+                "divergence_point",
+                "key_differences",
+                "risk_assessment",
+                "opportunity_assessment",
+                "recommendation",
+            ],
+        )?;
         let branches_compared = get_string_array(&json, "branches_compared")?;
         let divergence_point = get_str(&json, "divergence_point")?;
         let key_differences = parse_key_differences(&json)?;
@@ -328,19 +399,33 @@ where
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("timeline")
             .with_max_tokens(16384)
             .with_temperature(0.3)
             .with_deep_thinking();
 
         let response = self.client.complete(messages, config).await?;
         let json = extract_json(&response.content)?;
-
+        apply_memory_update(&self.storage, &session.id, &json).await;
+
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &[
+                "branches_merged",
+                "common_patterns",
+                "robust_strategies",
+                "fragile_strategies",
+                "synthesis",
+                "recommendations",
+            ],
+        )?;
         let branches_merged = get_string_array(&json, "branches_merged")?;
         let common_patterns = parse_common_patterns(&json)?;
         let robust_strategies = parse_robust_strategies(&json)?;
         let fragile_strategies = parse_fragile_strategies(&json)?;
         let synthesis = get_str(&json, "synthesis")?;
-        let recommendations = get_string_array(&json, "recommendations")?;
+        let recommendations = parse_recommendations(&json)?;
 
         let thought_id = generate_thought_id();
         let thought = Thought::new(
@@ -382,6 +467,11 @@ where
         &self,
         session_id: Option<String>,
     ) -> Result<Session, ModeError> {
+        crate::modes::enforce_session_required(
+            self.flags.require_session_id,
+            "timeline",
+            session_id.as_deref(),
+        )?;
         self.storage
             .get_or_create_session(session_id)
             .await
@@ -434,17 +524,27 @@ where
         has_prior_session: bool,
         content_label: &str,
     ) -> String {
-        let prior_context = if has_prior_session {
-            self.load_prior_context(session_id).await
+        let (prior_context, memory_block) = if has_prior_session {
+            (
+                self.load_prior_context(session_id).await,
+                load_working_memory_block(&self.storage, session_id).await,
+            )
         } else {
-            String::new()
+            (String::new(), String::new())
         };
 
-        if prior_context.is_empty() {
-            format!("{prompt}\n\n{content_label}:\n{content}")
-        } else {
-            format!("{prompt}\n\n{prior_context}\n{content_label}:\n{content}")
+        let mut message = format!(
+            "{prompt}{}\n",
+            language_instruction(self.language.as_deref(), content)
+        );
+        if !memory_block.is_empty() {
+            let _ = write!(message, "\n{memory_block}");
+        }
+        if !prior_context.is_empty() {
+            let _ = write!(message, "\n{prior_context}");
         }
+        let _ = write!(message, "\n{content_label}:\n{content}");
+        message
     }
 }
 
@@ -604,6 +704,9 @@ mod tests {
             .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
         mock_storage.expect_save_thought().returning(|_| Ok(()));
         mock_storage.expect_get_thoughts().returning(|_| Ok(vec![]));
+        mock_storage
+            .expect_get_working_memory()
+            .returning(|_| Ok(None));
 
         let resp = mock_create_response();
         mock_client
@@ -640,6 +743,63 @@ mod tests {
         assert!(result.is_ok());
         let response = result.unwrap();
         assert_eq!(response.branches.len(), 1);
+        assert!((response.branches[0].joint_probability - 0.8).abs() < 1e-12);
+    }
+
+    #[tokio::test]
+    async fn test_branch_ranks_by_joint_probability() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage
+            .expect_get_or_create_session()
+            .returning(|id| Ok(Session::new(id.unwrap_or_else(|| "test".to_string()))));
+        mock_storage.expect_save_thought().returning(|_| Ok(()));
+
+        // b_low arrives first but its three-event chain multiplies out to
+        // 0.125; b_high's single 0.9 event should rank it first.
+        let resp = r#"{
+            "branch_point": {"event_id": "d1", "description": "Choose path"},
+            "branches": [
+                {
+                    "id": "b_low",
+                    "choice": "Option A",
+                    "events": [
+                        {"id": "e1", "description": "E1", "probability": 0.5, "time_offset": "+1d"},
+                        {"id": "e2", "description": "E2", "probability": 0.5, "time_offset": "+2d"},
+                        {"id": "e3", "description": "E3", "probability": 0.5, "time_offset": "+3d"}
+                    ],
+                    "plausibility": 0.9,
+                    "outcome_quality": 0.6
+                },
+                {
+                    "id": "b_high",
+                    "choice": "Option B",
+                    "events": [
+                        {"id": "e4", "description": "E4", "probability": 0.9, "time_offset": "+1d"}
+                    ],
+                    "plausibility": 0.5,
+                    "outcome_quality": 0.6
+                }
+            ],
+            "comparison": {
+                "most_likely_good_outcome": "b_high",
+                "highest_risk": "b_low",
+                "key_differences": ["Chain length"]
+            }
+        }"#
+        .to_string();
+        mock_client
+            .expect_complete()
+            .returning(move |_, _| Ok(CompletionResponse::new(resp.clone(), Usage::new(100, 200))));
+
+        let mode = TimelineMode::new(mock_storage, mock_client);
+        let response = mode.branch("Decision", None).await.unwrap();
+
+        assert_eq!(response.branches[0].id, "b_high");
+        assert!((response.branches[0].joint_probability - 0.9).abs() < 1e-12);
+        assert_eq!(response.branches[1].id, "b_low");
+        assert!((response.branches[1].joint_probability - 0.125).abs() < 1e-12);
     }
 
     #[tokio::test]
@@ -686,6 +846,15 @@ mod tests {
         assert!(result.is_ok());
         let response = result.unwrap();
         assert_eq!(response.robust_strategies.len(), 1);
+        // Legacy plain-string recommendations are kept at neutral confidence
+        // with no supporting-branch links.
+        assert_eq!(response.recommendations.len(), 2);
+        assert_eq!(response.recommendations[0].action, "Start small");
+        assert_eq!(
+            response.recommendations[0].confidence,
+            LEGACY_RECOMMENDATION_CONFIDENCE
+        );
+        assert!(response.recommendations[0].supporting_branches.is_empty());
     }
 
     #[tokio::test]
@@ -769,6 +938,9 @@ mod tests {
                 0.7,
             )])
         });
+        mock_storage
+            .expect_get_working_memory()
+            .returning(|_| Ok(None));
         mock_storage.expect_save_thought().returning(|_| Ok(()));
 
         let resp = mock_create_response();
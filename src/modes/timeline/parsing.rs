@@ -6,11 +6,13 @@
 //! All parsers return ModeError::MissingField for absent or malformed fields.
 
 use crate::error::ModeError;
+use crate::modes::parse_probability;
 
 use super::types::{
-    BranchComparison, BranchDifference, BranchEvent, BranchPoint, CommonPattern,
-    CompareRecommendation, DecisionPoint, EventType, FragileStrategy, OpportunityAssessment,
-    RiskAssessment, RobustStrategy, TemporalStructure, TimelineBranch, TimelineEvent,
+    branch_path_probability, BranchComparison, BranchDifference, BranchEvent, BranchPoint,
+    CommonPattern, CompareRecommendation, DecisionPoint, EventType, FragileStrategy,
+    MergeRecommendation, OpportunityAssessment, RiskAssessment, RobustStrategy, TemporalStructure,
+    TimelineBranch, TimelineEvent,
 };
 
 // ============================================================================
@@ -119,14 +121,25 @@ pub fn parse_branches(json: &serde_json::Value) -> Result<Vec<TimelineBranch>, M
     arr.iter()
         .map(|b| {
             let events = parse_branch_events(b)?;
-
-            Ok(TimelineBranch {
+            // Flag events that omitted a probability: parse_branch_events
+            // defaulted them to 1.0, so the joint probability is only an
+            // upper bound for this branch.
+            let missing_probabilities = b
+                .get("events")
+                .and_then(serde_json::Value::as_array)
+                .is_some_and(|evs| evs.iter().any(|e| e.get("probability").is_none()));
+
+            let mut branch = TimelineBranch {
                 id: get_str(b, "id")?,
                 choice: get_str(b, "choice")?,
                 events,
-                plausibility: get_f64(b, "plausibility")?,
-                outcome_quality: get_f64(b, "outcome_quality")?,
-            })
+                plausibility: parse_probability(b, "plausibility")?,
+                outcome_quality: parse_probability(b, "outcome_quality")?,
+                joint_probability: 1.0,
+                missing_probabilities,
+            };
+            branch.joint_probability = branch_path_probability(&branch);
+            Ok(branch)
         })
         .collect()
 }
@@ -145,7 +158,16 @@ pub fn parse_branch_events(branch: &serde_json::Value) -> Result<Vec<BranchEvent
             Ok(BranchEvent {
                 id: get_str(e, "id")?,
                 description: get_str(e, "description")?,
-                probability: get_f64(e, "probability")?,
+                // Missing probabilities default to 1.0 rather than failing the
+                // parse; the branch carries a flag so the joint probability is
+                // read as an upper bound.
+                // Optional with a certain-outcome default, but a present value
+                // must still be a valid probability.
+                probability: if e.get("probability").is_some() {
+                    parse_probability(e, "probability")?
+                } else {
+                    1.0
+                },
                 time_offset: get_str(e, "time_offset")?,
             })
         })
@@ -256,7 +278,7 @@ pub fn parse_common_patterns(json: &serde_json::Value) -> Result<Vec<CommonPatte
         .map(|p| {
             Ok(CommonPattern {
                 pattern: get_str(p, "pattern")?,
-                frequency: get_f64(p, "frequency")?,
+                frequency: parse_probability(p, "frequency")?,
                 implications: get_str(p, "implications")?,
             })
         })
@@ -276,7 +298,7 @@ pub fn parse_robust_strategies(json: &serde_json::Value) -> Result<Vec<RobustStr
         .map(|s| {
             Ok(RobustStrategy {
                 strategy: get_str(s, "strategy")?,
-                effectiveness: get_f64(s, "effectiveness")?,
+                effectiveness: parse_probability(s, "effectiveness")?,
                 conditions: get_str(s, "conditions")?,
             })
         })
@@ -304,6 +326,42 @@ pub fn parse_fragile_strategies(
         .collect()
 }
 
+/// Parses the `recommendations` array from a merge response into a list of
+/// `MergeRecommendation` values.
+///
+/// Structured entries (`{action, supporting_branches, confidence}`) are parsed
+/// as-is; `supporting_branches` may be omitted. Legacy plain-string entries are
+/// kept via [`MergeRecommendation::from_legacy`] rather than failing the parse.
+pub fn parse_recommendations(
+    json: &serde_json::Value,
+) -> Result<Vec<MergeRecommendation>, ModeError> {
+    let arr = json
+        .get("recommendations")
+        .and_then(serde_json::Value::as_array)
+        .ok_or_else(|| ModeError::MissingField {
+            field: "recommendations".to_string(),
+        })?;
+
+    arr.iter()
+        .map(|r| {
+            if let Some(action) = r.as_str() {
+                return Ok(MergeRecommendation::from_legacy(action));
+            }
+            if !r.is_object() {
+                return Err(ModeError::InvalidValue {
+                    field: "recommendations".to_string(),
+                    reason: format!("entries must be objects or strings, got {r}"),
+                });
+            }
+            Ok(MergeRecommendation {
+                action: get_str(r, "action")?,
+                supporting_branches: get_string_array(r, "supporting_branches").unwrap_or_default(),
+                confidence: parse_probability(r, "confidence")?,
+            })
+        })
+        .collect()
+}
+
 // ============================================================================
 // Utility Helpers
 // ============================================================================
@@ -318,15 +376,6 @@ pub fn get_str(json: &serde_json::Value, field: &str) -> Result<String, ModeErro
         })
 }
 
-/// Extracts an f64 field from a JSON object, returning `MissingField` if absent or not a number.
-pub fn get_f64(json: &serde_json::Value, field: &str) -> Result<f64, ModeError> {
-    json.get(field)
-        .and_then(serde_json::Value::as_f64)
-        .ok_or_else(|| ModeError::MissingField {
-            field: field.to_string(),
-        })
-}
-
 /// Extracts a string array field from a JSON object, filtering out non-string elements.
 pub fn get_string_array(json: &serde_json::Value, field: &str) -> Result<Vec<String>, ModeError> {
     Ok(json
@@ -384,36 +433,6 @@ mod tests {
         assert!(result.is_err());
     }
 
-    #[test]
-    fn test_get_f64_success() {
-        let json = json!({"value": 0.85});
-        let result = get_f64(&json, "value");
-        assert!(result.is_ok());
-        assert!((result.unwrap() - 0.85).abs() < f64::EPSILON);
-    }
-
-    #[test]
-    fn test_get_f64_integer() {
-        let json = json!({"value": 42});
-        let result = get_f64(&json, "value");
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), 42.0);
-    }
-
-    #[test]
-    fn test_get_f64_missing() {
-        let json = json!({"other": 1.0});
-        let result = get_f64(&json, "value");
-        assert!(result.is_err());
-    }
-
-    #[test]
-    fn test_get_f64_not_number() {
-        let json = json!({"value": "not a number"});
-        let result = get_f64(&json, "value");
-        assert!(result.is_err());
-    }
-
     #[test]
     fn test_get_string_array_success() {
         let json = json!({"items": ["a", "b", "c"]});
@@ -635,6 +654,91 @@ mod tests {
         assert!((branches[0].plausibility - 0.7).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn test_parse_branches_computes_joint_probability() {
+        let json = json!({
+            "branches": [
+                {
+                    "id": "b1",
+                    "choice": "Option A",
+                    "events": [
+                        {"id": "be1", "description": "E1", "probability": 0.8, "time_offset": "+1d"},
+                        {"id": "be2", "description": "E2", "probability": 0.5, "time_offset": "+2d"},
+                        {"id": "be3", "description": "E3", "probability": 0.5, "time_offset": "+3d"}
+                    ],
+                    "plausibility": 0.7,
+                    "outcome_quality": 0.85
+                }
+            ]
+        });
+        let branches = parse_branches(&json).unwrap();
+        assert!((branches[0].joint_probability - 0.2).abs() < 1e-12);
+        assert!(!branches[0].missing_probabilities);
+    }
+
+    #[test]
+    fn test_parse_branches_missing_probability_defaults_and_flags() {
+        let json = json!({
+            "branches": [
+                {
+                    "id": "b1",
+                    "choice": "Option A",
+                    "events": [
+                        {"id": "be1", "description": "E1", "probability": 0.4, "time_offset": "+1d"},
+                        {"id": "be2", "description": "E2", "time_offset": "+2d"}
+                    ],
+                    "plausibility": 0.7,
+                    "outcome_quality": 0.85
+                }
+            ]
+        });
+        let branches = parse_branches(&json).unwrap();
+        // Missing probability treated as 1.0: joint is the remaining 0.4.
+        assert_eq!(branches[0].events[1].probability, 1.0);
+        assert!((branches[0].joint_probability - 0.4).abs() < 1e-12);
+        assert!(branches[0].missing_probabilities);
+    }
+
+    #[test]
+    fn test_parse_branches_rejects_out_of_range_plausibility() {
+        let json = json!({
+            "branches": [
+                {
+                    "id": "b1",
+                    "choice": "Option A",
+                    "events": [],
+                    "plausibility": 1.2,
+                    "outcome_quality": 0.85
+                }
+            ]
+        });
+        assert!(matches!(
+            parse_branches(&json),
+            Err(ModeError::InvalidValue { field, .. }) if field == "plausibility"
+        ));
+    }
+
+    #[test]
+    fn test_parse_branches_rejects_out_of_range_event_probability() {
+        let json = json!({
+            "branches": [
+                {
+                    "id": "b1",
+                    "choice": "Option A",
+                    "events": [
+                        {"id": "be1", "description": "E1", "probability": 1.5, "time_offset": "+1d"}
+                    ],
+                    "plausibility": 0.7,
+                    "outcome_quality": 0.85
+                }
+            ]
+        });
+        assert!(matches!(
+            parse_branches(&json),
+            Err(ModeError::InvalidValue { field, .. }) if field == "probability"
+        ));
+    }
+
     #[test]
     fn test_parse_branches_missing() {
         let json = json!({"other": []});
@@ -866,6 +970,74 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // Parse Recommendations Tests
+    #[test]
+    fn test_parse_recommendations_structured() {
+        let json = json!({
+            "recommendations": [
+                {
+                    "action": "Ship the iterative plan",
+                    "supporting_branches": ["b1", "b2"],
+                    "confidence": 0.9
+                },
+                {
+                    "action": "Defer the rewrite",
+                    "confidence": 0.6
+                }
+            ]
+        });
+        let recs = parse_recommendations(&json).unwrap();
+        assert_eq!(recs.len(), 2);
+        assert_eq!(recs[0].action, "Ship the iterative plan");
+        assert_eq!(recs[0].supporting_branches, vec!["b1", "b2"]);
+        assert!((recs[0].confidence - 0.9).abs() < f64::EPSILON);
+        // supporting_branches may be omitted.
+        assert!(recs[1].supporting_branches.is_empty());
+    }
+
+    #[test]
+    fn test_parse_recommendations_legacy_strings() {
+        let json = json!({"recommendations": ["Start small", "Iterate often"]});
+        let recs = parse_recommendations(&json).unwrap();
+        assert_eq!(recs.len(), 2);
+        assert_eq!(recs[0].action, "Start small");
+        assert!(recs[0].supporting_branches.is_empty());
+        assert_eq!(
+            recs[0].confidence,
+            super::super::types::LEGACY_RECOMMENDATION_CONFIDENCE
+        );
+    }
+
+    #[test]
+    fn test_parse_recommendations_mixed_legacy_and_structured() {
+        let json = json!({
+            "recommendations": [
+                "Keep the option open",
+                {"action": "Invest in QA", "supporting_branches": ["b1"], "confidence": 0.8}
+            ]
+        });
+        let recs = parse_recommendations(&json).unwrap();
+        assert_eq!(recs.len(), 2);
+        assert_eq!(recs[1].supporting_branches, vec!["b1"]);
+    }
+
+    #[test]
+    fn test_parse_recommendations_invalid_entry() {
+        let json = json!({"recommendations": [42]});
+        let result = parse_recommendations(&json);
+        assert!(matches!(
+            result,
+            Err(ModeError::InvalidValue { field, .. }) if field == "recommendations"
+        ));
+    }
+
+    #[test]
+    fn test_parse_recommendations_missing() {
+        let json = json!({"other": []});
+        let result = parse_recommendations(&json);
+        assert!(result.is_err());
+    }
+
     // Edge Cases
     #[test]
     fn test_parse_events_empty_array() {
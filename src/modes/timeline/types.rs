@@ -146,6 +146,130 @@ pub struct TimelineBranch {
     pub plausibility: f64,
     /// Quality of the outcome.
     pub outcome_quality: f64,
+    /// Joint probability of the full event chain (see
+    /// [`branch_path_probability`]).
+    #[serde(default = "default_joint_probability")]
+    pub joint_probability: f64,
+    /// True when any event lacked an explicit probability, which
+    /// [`branch_path_probability`] treats as 1.0 — the joint probability is
+    /// then an upper bound, not an estimate.
+    #[serde(default)]
+    pub missing_probabilities: bool,
+}
+
+const fn default_joint_probability() -> f64 {
+    1.0
+}
+
+/// Joint probability of a branch's full event chain: the product of its
+/// per-event probabilities.
+///
+/// Events along a branch are treated as independent — the per-event
+/// probabilities the model emits are already conditioned on the branch being
+/// taken, and no cross-event correlation structure is available, so the
+/// product is the best available estimate of the chain occurring end to end.
+/// A branch with no events has joint probability 1.0 (nothing needs to
+/// happen).
+#[must_use]
+pub fn branch_path_probability(branch: &TimelineBranch) -> f64 {
+    branch.events.iter().map(|e| e.probability).product()
+}
+
+/// Granularity of the assumption-sensitivity sweep: per-event probabilities
+/// are perturbed in steps of this size.
+pub const SENSITIVITY_SWEEP_STEP: f64 = 0.01;
+
+/// Result of the assumption-sensitivity sweep over branch event
+/// probabilities.
+///
+/// Answers "how wrong can the event probabilities be before the recommended
+/// branch changes?": the smallest uniform per-event perturbation — shrinking
+/// every event probability of the recommended branch while growing every
+/// challenger's — at which a challenger's joint probability overtakes it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SensitivitySweep {
+    /// Branch recommended at the unperturbed probabilities (highest joint
+    /// probability).
+    pub recommended_branch: String,
+    /// Smallest per-event probability perturbation that flips the
+    /// recommendation, or `None` when no perturbation up to 1.0 does (the
+    /// recommendation is robust to any uniform probability error).
+    pub flip_delta: Option<f64>,
+    /// Branch the recommendation flips toward at `flip_delta`. `None`
+    /// exactly when `flip_delta` is.
+    pub flips_to: Option<String>,
+    /// Sweep granularity the threshold was found at.
+    pub step: f64,
+}
+
+/// Sweep per-event probability perturbations and find the threshold at
+/// which the recommended branch changes.
+///
+/// The recommended branch is the one with the highest joint probability
+/// (ties broken by branch id, matching the stable ranking order). For each
+/// perturbation `delta` in steps of `step`, every event probability of the
+/// recommended branch is lowered by `delta` and every challenger's raised
+/// by `delta` (both clamped to 0.0–1.0) — the worst case for the incumbent.
+/// The first `delta` where a challenger's perturbed joint probability
+/// strictly exceeds the incumbent's is reported, along with the strongest
+/// such challenger.
+///
+/// Returns `None` when there are no branches to recommend from; a single
+/// branch yields a sweep that can never flip.
+#[must_use]
+pub fn branch_sensitivity_sweep(
+    branches: &[TimelineBranch],
+    step: f64,
+) -> Option<SensitivitySweep> {
+    if branches.is_empty() || step <= 0.0 {
+        return None;
+    }
+
+    let joint = |branch: &TimelineBranch, delta: f64| -> f64 {
+        branch
+            .events
+            .iter()
+            .map(|e| (e.probability + delta).clamp(0.0, 1.0))
+            .product()
+    };
+
+    let incumbent = branches.iter().max_by(|a, b| {
+        joint(a, 0.0)
+            .total_cmp(&joint(b, 0.0))
+            .then_with(|| b.id.cmp(&a.id))
+    })?;
+
+    // Integer-counted sweep: delta is recomputed from the grid index each
+    // iteration, so no float error accumulates across steps.
+    for i in 1_u16.. {
+        let delta = f64::from(i) * step;
+        if delta > 1.0 {
+            break;
+        }
+        let incumbent_joint = joint(incumbent, -delta);
+        let challenger = branches
+            .iter()
+            .filter(|b| b.id != incumbent.id)
+            .map(|b| (b, joint(b, delta)))
+            .max_by(|(a, aj), (b, bj)| aj.total_cmp(bj).then_with(|| b.id.cmp(&a.id)));
+        if let Some((challenger, challenger_joint)) = challenger {
+            if challenger_joint > incumbent_joint {
+                return Some(SensitivitySweep {
+                    recommended_branch: incumbent.id.clone(),
+                    flip_delta: Some(delta),
+                    flips_to: Some(challenger.id.clone()),
+                    step,
+                });
+            }
+        }
+    }
+
+    Some(SensitivitySweep {
+        recommended_branch: incumbent.id.clone(),
+        flip_delta: None,
+        flips_to: None,
+        step,
+    })
 }
 
 /// Branch comparison summary.
@@ -172,10 +296,19 @@ pub struct BranchResponse {
     pub branches: Vec<TimelineBranch>,
     /// Comparison summary.
     pub comparison: BranchComparison,
+    /// Assumption-sensitivity sweep over the branch event probabilities
+    /// (see [`branch_sensitivity_sweep`]). `None` only when there are no
+    /// branches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sensitivity: Option<SensitivitySweep>,
 }
 
 impl BranchResponse {
     /// Create a new branch response.
+    ///
+    /// The sensitivity sweep is derived here from the branches rather than
+    /// accepted from the caller, so it always reflects the joint
+    /// probabilities actually reported.
     #[must_use]
     pub fn new(
         thought_id: impl Into<String>,
@@ -184,12 +317,14 @@ impl BranchResponse {
         branches: Vec<TimelineBranch>,
         comparison: BranchComparison,
     ) -> Self {
+        let sensitivity = branch_sensitivity_sweep(&branches, SENSITIVITY_SWEEP_STEP);
         Self {
             thought_id: thought_id.into(),
             session_id: session_id.into(),
             branch_point,
             branches,
             comparison,
+            sensitivity,
         }
     }
 }
@@ -324,6 +459,37 @@ pub struct FragileStrategy {
     pub failure_modes: String,
 }
 
+/// Confidence assigned to a legacy plain-string recommendation.
+///
+/// Older merge responses return `recommendations` as bare strings with no
+/// confidence; they are kept (the parse stays tolerant) at this neutral value
+/// so a threshold filter neither always includes nor always drops them.
+pub const LEGACY_RECOMMENDATION_CONFIDENCE: f64 = 0.5;
+
+/// An actionable recommendation from merging branches.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MergeRecommendation {
+    /// The recommended action.
+    pub action: String,
+    /// IDs of the merged branches that support this recommendation.
+    pub supporting_branches: Vec<String>,
+    /// How confident the synthesis is in this recommendation (0.0-1.0).
+    pub confidence: f64,
+}
+
+impl MergeRecommendation {
+    /// Wrap a legacy plain-string recommendation: no supporting-branch links
+    /// and [`LEGACY_RECOMMENDATION_CONFIDENCE`].
+    #[must_use]
+    pub fn from_legacy(action: impl Into<String>) -> Self {
+        Self {
+            action: action.into(),
+            supporting_branches: Vec::new(),
+            confidence: LEGACY_RECOMMENDATION_CONFIDENCE,
+        }
+    }
+}
+
 /// Response from merge operation.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct MergeResponse {
@@ -342,7 +508,7 @@ pub struct MergeResponse {
     /// Overall synthesis.
     pub synthesis: String,
     /// Actionable recommendations.
-    pub recommendations: Vec<String>,
+    pub recommendations: Vec<MergeRecommendation>,
 }
 
 impl MergeResponse {
@@ -358,7 +524,7 @@ impl MergeResponse {
         robust_strategies: Vec<RobustStrategy>,
         fragile_strategies: Vec<FragileStrategy>,
         synthesis: impl Into<String>,
-        recommendations: Vec<String>,
+        recommendations: Vec<MergeRecommendation>,
     ) -> Self {
         Self {
             thought_id: thought_id.into(),
@@ -371,6 +537,22 @@ impl MergeResponse {
             recommendations,
         }
     }
+
+    /// Recommendations whose confidence meets `threshold`, strongest first.
+    #[must_use]
+    pub fn recommendations_above(&self, threshold: f64) -> Vec<&MergeRecommendation> {
+        let mut above: Vec<&MergeRecommendation> = self
+            .recommendations
+            .iter()
+            .filter(|r| r.confidence >= threshold)
+            .collect();
+        above.sort_by(|a, b| {
+            b.confidence
+                .partial_cmp(&a.confidence)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        above
+    }
 }
 
 #[cfg(test)]
@@ -385,6 +567,115 @@ impl MergeResponse {
 mod tests {
     use super::*;
 
+    fn branch_with_probabilities(probabilities: &[f64]) -> TimelineBranch {
+        TimelineBranch {
+            id: "b1".to_string(),
+            choice: "Option A".to_string(),
+            events: probabilities
+                .iter()
+                .enumerate()
+                .map(|(i, p)| BranchEvent {
+                    id: format!("e{i}"),
+                    description: format!("Event {i}"),
+                    probability: *p,
+                    time_offset: "+1d".to_string(),
+                })
+                .collect(),
+            plausibility: 0.7,
+            outcome_quality: 0.6,
+            joint_probability: 1.0,
+            missing_probabilities: false,
+        }
+    }
+
+    #[test]
+    fn test_branch_path_probability_multiplies_events() {
+        let branch = branch_with_probabilities(&[0.8, 0.5, 0.5]);
+        assert!((branch_path_probability(&branch) - 0.2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_branch_path_probability_empty_is_one() {
+        let branch = branch_with_probabilities(&[]);
+        assert_eq!(branch_path_probability(&branch), 1.0);
+    }
+
+    fn named_branch(id: &str, probabilities: &[f64]) -> TimelineBranch {
+        let mut branch = branch_with_probabilities(probabilities);
+        branch.id = id.to_string();
+        branch.joint_probability = branch_path_probability(&branch);
+        branch
+    }
+
+    #[test]
+    fn test_sensitivity_sweep_finds_flip_threshold_for_close_branches() {
+        // a: joint 0.6, b: joint 0.55. The flip needs 0.55 + d > 0.6 - d,
+        // i.e. d > 0.025 — first grid point at step 0.01 is 0.03.
+        let branches = vec![named_branch("a", &[0.6]), named_branch("b", &[0.55])];
+        let sweep = branch_sensitivity_sweep(&branches, 0.01).expect("sweep");
+
+        assert_eq!(sweep.recommended_branch, "a");
+        assert!((sweep.flip_delta.expect("flips") - 0.03).abs() < 1e-12);
+        assert_eq!(sweep.flips_to.as_deref(), Some("b"));
+        assert!((sweep.step - 0.01).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_sensitivity_sweep_flips_toward_strongest_challenger() {
+        // Both challengers overtake eventually, but c is closer: the sweep
+        // must report the one that wins at the flip point. The crossover is
+        // at d = 0.055, so the first grid point that flips is 0.06.
+        let branches = vec![
+            named_branch("a", &[0.6]),
+            named_branch("b", &[0.3]),
+            named_branch("c", &[0.49]),
+        ];
+        let sweep = branch_sensitivity_sweep(&branches, 0.01).expect("sweep");
+
+        assert_eq!(sweep.recommended_branch, "a");
+        assert!((sweep.flip_delta.expect("flips") - 0.06).abs() < 1e-12);
+        assert_eq!(sweep.flips_to.as_deref(), Some("c"));
+    }
+
+    #[test]
+    fn test_sensitivity_sweep_robust_recommendation_never_flips() {
+        // A lone branch has no challenger, so no perturbation can flip it.
+        let branches = vec![named_branch("a", &[0.6])];
+        let sweep = branch_sensitivity_sweep(&branches, 0.01).expect("sweep");
+
+        assert_eq!(sweep.recommended_branch, "a");
+        assert!(sweep.flip_delta.is_none());
+        assert!(sweep.flips_to.is_none());
+    }
+
+    #[test]
+    fn test_sensitivity_sweep_empty_branches_is_none() {
+        assert!(branch_sensitivity_sweep(&[], 0.01).is_none());
+    }
+
+    #[test]
+    fn test_branch_response_populates_sensitivity() {
+        let response = BranchResponse::new(
+            "t-1",
+            "s-1",
+            BranchPoint {
+                event_id: "e1".to_string(),
+                description: "decision".to_string(),
+            },
+            vec![named_branch("a", &[0.6]), named_branch("b", &[0.55])],
+            BranchComparison {
+                most_likely_good_outcome: "a".to_string(),
+                highest_risk: "b".to_string(),
+                key_differences: vec![],
+            },
+        );
+
+        let sweep = response.sensitivity.expect("sensitivity computed");
+        assert_eq!(sweep.recommended_branch, "a");
+        assert!((sweep.flip_delta.expect("flips") - 0.03).abs() < 1e-12);
+        assert!((sweep.step - SENSITIVITY_SWEEP_STEP).abs() < f64::EPSILON);
+    }
+
     #[test]
     fn test_event_type_serialize() {
         assert_eq!(
@@ -396,4 +687,39 @@ mod tests {
             "\"decision_point\""
         );
     }
+
+    #[test]
+    fn test_recommendations_above_filters_and_sorts() {
+        let rec = |action: &str, confidence: f64| MergeRecommendation {
+            action: action.to_string(),
+            supporting_branches: vec!["b1".to_string()],
+            confidence,
+        };
+        let response = MergeResponse::new(
+            "t-1",
+            "s-1",
+            vec!["b1".to_string(), "b2".to_string()],
+            vec![],
+            vec![],
+            vec![],
+            "synthesis",
+            vec![
+                rec("medium", 0.6),
+                rec("low", 0.3),
+                rec("high", 0.9),
+                MergeRecommendation::from_legacy("legacy"),
+            ],
+        );
+
+        let above = response.recommendations_above(0.6);
+        assert_eq!(above.len(), 2);
+        // Strongest first; the legacy entry (0.5) and the 0.3 one are dropped.
+        assert_eq!(above[0].action, "high");
+        assert_eq!(above[1].action, "medium");
+        assert_eq!(above[0].supporting_branches, vec!["b1"]);
+
+        // A threshold at the legacy confidence keeps legacy entries.
+        let above = response.recommendations_above(LEGACY_RECOMMENDATION_CONFIDENCE);
+        assert_eq!(above.len(), 3);
+    }
 }
@@ -10,13 +10,24 @@
 #![allow(clippy::missing_const_for_fn)]
 #![allow(clippy::unused_async)]
 
+use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
 
 use crate::error::ModeError;
-use crate::modes::{extract_json, generate_branch_id, validate_content};
+use crate::modes::{
+    append_language_instruction, enforce_context_budget, extract_json, generate_branch_id,
+    generate_thought_id, reject_unknown_keys, validate_content, ModeFlags,
+};
 use crate::prompts::{get_prompt_for_mode, Operation, ReasoningMode};
 use crate::storage::{BranchStatus as StoredBranchStatus, StoredBranch};
-use crate::traits::{AnthropicClientTrait, CompletionConfig, Message, Session, StorageTrait};
+use crate::traits::{
+    AnthropicClientTrait, CompletionConfig, Message, Session, StorageTrait, Thought,
+};
+
+/// Global cap on completions in flight during a parallel branch expansion.
+/// Additional branches queue behind it so a wide `expand_branches` call cannot
+/// hold an unbounded number of API requests open at once.
+const MAX_CONCURRENT_EXPANSIONS: usize = 4;
 
 /// Branch status.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -284,9 +295,14 @@ where
 {
     storage: S,
     client: C,
+    /// Behavior flags injected from server configuration (all off by default).
+    flags: ModeFlags,
     /// Opt-in self-heal detection sink (spec 001): records parse failures of
     /// this mode's own output when set.
     defect_sink: Option<crate::self_improvement::heal::DefectSink>,
+    /// Effective output language (per-request override merged with the
+    /// configured default at the server boundary).
+    language: Option<String>,
 }
 
 impl<S, C> TreeMode<S, C>
@@ -300,10 +316,29 @@ where
         Self {
             storage,
             client,
+            flags: ModeFlags::default(),
             defect_sink: None,
+            language: None,
         }
     }
 
+    /// Set the output language for natural-language response fields. The
+    /// server passes the per-request override merged with the configured
+    /// `RESPONSE_LANGUAGE` default; `None` adds no language instruction.
+    #[must_use]
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Inject the server's behavior flags ([`ModeFlags`]). A mode built
+    /// without them — as in most tests — uses the all-off defaults.
+    #[must_use]
+    pub fn with_flags(mut self, flags: ModeFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
     /// Attach a self-heal detection sink so this mode records its own parse
     /// failures (spec 001). Opt-in: absent by default.
     #[must_use]
@@ -338,7 +373,11 @@ where
             String::new()
         };
 
-        let prompt = get_prompt_for_mode(ReasoningMode::Tree, Some(&Operation::Create));
+        let prompt = append_language_instruction(
+            get_prompt_for_mode(ReasoningMode::Tree, Some(&Operation::Create)),
+            self.language.as_deref(),
+            content,
+        );
         let user_message = if existing.is_empty() {
             format!("{prompt}\n\nGenerate {num_branches} branches for:\n{content}")
         } else {
@@ -348,10 +387,12 @@ where
             )
         };
 
-        let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("tree")
             .with_max_tokens(4096)
             .with_temperature(0.8);
+        enforce_context_budget(&user_message, config.max_tokens)?;
+        let messages = vec![Message::user(user_message)];
 
         let response = self.client.complete(messages, config).await?;
         let json = match extract_json(&response.content) {
@@ -364,6 +405,8 @@ where
             }
         };
 
+        reject_unknown_keys(self.flags.strict_parsing, &json, &["branches"])?;
+
         // Parse branches from response
         let branches_json = json
             .get("branches")
@@ -463,7 +506,11 @@ where
 
         let branch = Branch::from_stored(&stored_branch);
 
-        let prompt = get_prompt_for_mode(ReasoningMode::Tree, Some(&Operation::Focus));
+        let prompt = append_language_instruction(
+            get_prompt_for_mode(ReasoningMode::Tree, Some(&Operation::Focus)),
+            self.language.as_deref(),
+            &branch.content,
+        );
         let user_message = format!(
             "{prompt}\n\nBranch to explore:\nTitle: {}\nContent: {}",
             branch.title, branch.content
@@ -471,6 +518,7 @@ where
 
         let messages = vec![Message::user(user_message)];
         let config = CompletionConfig::new()
+            .with_mode("tree")
             .with_max_tokens(4096)
             .with_temperature(0.7);
 
@@ -485,6 +533,12 @@ where
             }
         };
 
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &["exploration", "insights", "confidence"],
+        )?;
+
         let exploration = json
             .get("exploration")
             .and_then(|v| v.as_str())
@@ -674,7 +728,11 @@ where
             })
             .collect();
 
-        let prompt = get_prompt_for_mode(ReasoningMode::Tree, Some(&Operation::Summarize));
+        let prompt = append_language_instruction(
+            get_prompt_for_mode(ReasoningMode::Tree, Some(&Operation::Summarize)),
+            self.language.as_deref(),
+            branches.first().map_or("", |b| b.content.as_str()),
+        );
         let user_message = format!(
             "{prompt}\n\nBranches to synthesize:\n\n{}",
             branch_summaries.join("\n\n---\n\n")
@@ -682,6 +740,7 @@ where
 
         let messages = vec![crate::traits::Message::user(user_message)];
         let config = crate::traits::CompletionConfig::new()
+            .with_mode("tree")
             .with_max_tokens(4096)
             .with_temperature(0.7);
 
@@ -696,6 +755,12 @@ where
             }
         };
 
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &["key_findings", "best_insights", "synthesis", "unresolved"],
+        )?;
+
         let key_findings: Vec<String> = json
             .get("key_findings")
             .and_then(|v| v.as_array())
@@ -747,6 +812,174 @@ where
         Ok(result)
     }
 
+    /// Expand several branches in parallel, one completion per branch.
+    ///
+    /// Fans out one focus-style completion per branch with at most
+    /// [`MAX_CONCURRENT_EXPANSIONS`] requests in flight, persists each result
+    /// as a thought linked to its branch, and returns the expanded branches
+    /// with their re-assessed scores. Results keep the order of `branch_ids`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModeError`] if `branch_ids` is empty, a branch is missing or
+    /// belongs to another session, the API fails, or parsing fails.
+    pub async fn expand_branches(
+        &mut self,
+        session_id: &str,
+        branch_ids: &[String],
+    ) -> Result<TreeResponse, ModeError> {
+        if branch_ids.is_empty() {
+            return Err(ModeError::MissingField {
+                field: "branch_ids".to_string(),
+            });
+        }
+
+        // `buffered` (not `buffer_unordered`) keeps results aligned with the
+        // input order while still running up to the cap concurrently.
+        let expansions = futures_util::stream::iter(
+            branch_ids
+                .iter()
+                .map(|branch_id| self.expand_one(session_id, branch_id)),
+        )
+        .buffered(MAX_CONCURRENT_EXPANSIONS)
+        .collect::<Vec<_>>()
+        .await;
+
+        let mut branches = Vec::with_capacity(branch_ids.len());
+        let mut insights = Vec::new();
+        for expansion in expansions {
+            let (branch, branch_insights) = expansion?;
+            branches.push(branch);
+            insights.extend(branch_insights);
+        }
+
+        let recommendation = branches
+            .iter()
+            .max_by(|a, b| {
+                a.score
+                    .partial_cmp(&b.score)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .map(|b| format!("Recommend exploring '{}' (score: {:.2})", b.title, b.score));
+
+        let mut response = TreeResponse::new(session_id)
+            .with_branches(branches)
+            .with_insights(insights);
+        if let Some(rec) = recommendation {
+            response = response.with_recommendation(rec);
+        }
+
+        Ok(response)
+    }
+
+    /// Expand a single branch: one completion, persisted as a thought linked
+    /// to the branch. Returns the branch with its re-assessed score plus the
+    /// insights gained.
+    async fn expand_one(
+        &self,
+        session_id: &str,
+        branch_id: &str,
+    ) -> Result<(Branch, Vec<String>), ModeError> {
+        let stored_branch = self
+            .storage
+            .get_branch(branch_id)
+            .await
+            .map_err(|e| ModeError::ApiUnavailable {
+                message: format!("Failed to get branch: {e}"),
+            })?
+            .ok_or_else(|| ModeError::InvalidValue {
+                field: "branch_id".to_string(),
+                reason: format!("Branch {branch_id} not found"),
+            })?;
+
+        if stored_branch.session_id != session_id {
+            return Err(ModeError::InvalidValue {
+                field: "branch_id".to_string(),
+                reason: format!("Branch {branch_id} not found in session {session_id}"),
+            });
+        }
+
+        let branch = Branch::from_stored(&stored_branch);
+
+        let prompt = append_language_instruction(
+            get_prompt_for_mode(ReasoningMode::Tree, Some(&Operation::Focus)),
+            self.language.as_deref(),
+            &branch.content,
+        );
+        let user_message = format!(
+            "{prompt}\n\nBranch to explore:\nTitle: {}\nContent: {}",
+            branch.title, branch.content
+        );
+
+        let messages = vec![Message::user(user_message)];
+        let config = CompletionConfig::new()
+            .with_mode("tree")
+            .with_max_tokens(4096)
+            .with_temperature(0.7);
+
+        let response = self.client.complete(messages, config).await?;
+        let json = match extract_json(&response.content) {
+            Ok(j) => j,
+            Err(e) => {
+                if let Some(sink) = &self.defect_sink {
+                    sink.parse_failure(&response.content);
+                }
+                return Err(e);
+            }
+        };
+
+        reject_unknown_keys(
+            self.flags.strict_parsing,
+            &json,
+            &["exploration", "insights", "confidence"],
+        )?;
+
+        let exploration = json
+            .get("exploration")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+
+        let insights: Vec<String> = json
+            .get("insights")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let confidence = json
+            .get("confidence")
+            .and_then(serde_json::Value::as_f64)
+            .filter(|c| (0.0..=1.0).contains(c))
+            .unwrap_or(branch.score);
+
+        // Persist the expansion as a thought linked to its branch so the
+        // session history records which branch each result came from.
+        let thought = Thought::new(
+            generate_thought_id(),
+            session_id,
+            format!(
+                "Branch '{}' ({branch_id}) expansion:\n{exploration}",
+                branch.title
+            ),
+            "tree",
+            confidence,
+        );
+        self.storage
+            .save_thought(&thought)
+            .await
+            .map_err(|e| ModeError::ApiUnavailable {
+                message: format!("Failed to save expansion thought: {e}"),
+            })?;
+
+        let mut expanded = branch;
+        expanded.score = confidence;
+        Ok((expanded, insights))
+    }
+
     /// Get or create a session.
     async fn get_or_create_session(
         &self,
@@ -1608,7 +1841,10 @@ mod tests {
                 usage: Usage {
                     input_tokens: 100,
                     output_tokens: 150,
+                    cache_read_input_tokens: 0,
                 },
+                thinking: None,
+                structured: None,
             })
         });
 
@@ -1899,4 +2135,153 @@ mod tests {
         // Empty unresolved → no recommendation
         assert!(resp.recommendation.is_none());
     }
+
+    #[tokio::test]
+    async fn test_tree_expand_branches_success() {
+        use std::sync::{Arc, Mutex};
+
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage.expect_get_branch().returning(|id| {
+            Ok(Some(StoredBranch::new(
+                id,
+                "test-session",
+                format!(r#"{{"title":"Branch {id}","content":"Content for {id}"}}"#),
+            )))
+        });
+
+        // Capture every persisted thought so linkage can be asserted.
+        let saved_thoughts: Arc<Mutex<Vec<Thought>>> = Arc::new(Mutex::new(Vec::new()));
+        let saved_clone = saved_thoughts.clone();
+        mock_storage
+            .expect_save_thought()
+            .returning(move |thought| {
+                saved_clone.lock().unwrap().push(thought.clone());
+                Ok(())
+            });
+
+        let focus_response = mock_focus_response();
+        mock_client
+            .expect_complete()
+            .times(3)
+            .returning(move |_, _| {
+                Ok(CompletionResponse::new(
+                    focus_response.clone(),
+                    Usage::new(100, 200),
+                ))
+            });
+
+        let mut mode = TreeMode::new(mock_storage, mock_client);
+        let branch_ids = vec!["b-1".to_string(), "b-2".to_string(), "b-3".to_string()];
+        let result = mode.expand_branches("test-session", &branch_ids).await;
+
+        assert!(result.is_ok());
+        let response = result.unwrap();
+        assert_eq!(response.session_id, "test-session");
+
+        // One expanded branch per input id, in input order, with the
+        // re-assessed score (0.85) from each completion.
+        let branches = response.branches.unwrap();
+        assert_eq!(branches.len(), 3);
+        for (branch, id) in branches.iter().zip(&branch_ids) {
+            assert_eq!(&branch.id, id);
+            assert!((branch.score - 0.85).abs() < f64::EPSILON);
+        }
+
+        // Insights aggregate across all branches (2 per mock response).
+        assert_eq!(response.insights.unwrap().len(), 6);
+        assert!(response.recommendation.is_some());
+
+        // Three thoughts persisted, each linked to its branch.
+        let saved = saved_thoughts.lock().unwrap();
+        assert_eq!(saved.len(), 3);
+        for id in &branch_ids {
+            let thought = saved
+                .iter()
+                .find(|t| t.content.contains(&format!("({id})")))
+                .expect("thought linked to branch");
+            assert_eq!(thought.session_id, "test-session");
+            assert_eq!(thought.mode, "tree");
+            assert!((thought.confidence - 0.85).abs() < f64::EPSILON);
+            assert!(thought.content.contains("Deep exploration of this branch"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tree_expand_branches_empty_ids() {
+        let mock_storage = MockStorageTrait::new();
+        let mock_client = MockAnthropicClientTrait::new();
+
+        let mut mode = TreeMode::new(mock_storage, mock_client);
+        let result = mode.expand_branches("test-session", &[]).await;
+
+        assert!(result.is_err());
+        assert!(matches!(
+            result,
+            Err(ModeError::MissingField { field }) if field == "branch_ids"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_tree_expand_branches_session_mismatch() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage.expect_get_branch().returning(|id| {
+            Ok(Some(StoredBranch::new(
+                id,
+                "other-session",
+                r#"{"title":"Branch","content":"Test"}"#,
+            )))
+        });
+
+        let mut mode = TreeMode::new(mock_storage, mock_client);
+        let result = mode
+            .expand_branches("test-session", &["b-1".to_string()])
+            .await;
+
+        assert!(result.is_err());
+        assert!(matches!(
+            result,
+            Err(ModeError::InvalidValue { field, reason })
+            if field == "branch_id" && reason.contains("not found in session")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_tree_expand_branches_save_thought_error() {
+        let mut mock_storage = MockStorageTrait::new();
+        let mut mock_client = MockAnthropicClientTrait::new();
+
+        mock_storage.expect_get_branch().returning(|id| {
+            Ok(Some(StoredBranch::new(
+                id,
+                "test-session",
+                r#"{"title":"Branch","content":"Test"}"#,
+            )))
+        });
+        mock_storage.expect_save_thought().returning(|_| {
+            Err(StorageError::QueryFailed {
+                query: "INSERT".to_string(),
+                message: "DB error".to_string(),
+            })
+        });
+
+        let focus_response = mock_focus_response();
+        mock_client.expect_complete().returning(move |_, _| {
+            Ok(CompletionResponse::new(
+                focus_response.clone(),
+                Usage::new(100, 200),
+            ))
+        });
+
+        let mut mode = TreeMode::new(mock_storage, mock_client);
+        let result = mode
+            .expand_branches("test-session", &["b-1".to_string()])
+            .await;
+
+        assert!(result.is_err());
+        assert!(matches!(result, Err(ModeError::ApiUnavailable { .. })));
+    }
 }
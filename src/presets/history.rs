@@ -0,0 +1,267 @@
+//! Preset run history: recording runs and diffing two of them.
+//!
+//! Teams rerunning the same preset want to track how outputs change across
+//! runs. [`record_preset_run`] persists a [`PresetResult`] (tagged by preset
+//! id and timestamp) to the `preset_runs` table, and [`diff_preset_runs`]
+//! compares two recorded runs step by step, highlighting steps whose outcome
+//! changed (success/failure flips) and score deltas where steps report a
+//! numeric `confidence`/`score`. Surfaced by the `reasoning_preset_history`
+//! tool.
+
+use serde::{Deserialize, Serialize};
+
+use super::{PresetResult, StepResult};
+use crate::error::StorageError;
+use crate::storage::{SqliteStorage, StoredPresetRun};
+
+/// Structured comparison of two preset runs.
+///
+/// Only steps whose outcome or score changed appear in `step_changes`;
+/// steps identical across both runs are omitted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PresetRunDiff {
+    /// Overall success of the earlier run.
+    pub success_a: bool,
+    /// Overall success of the later run.
+    pub success_b: bool,
+    /// Whether overall success flipped between the runs.
+    pub success_changed: bool,
+    /// Per-step changes, in step order.
+    pub step_changes: Vec<StepOutcomeChange>,
+}
+
+/// One step whose outcome or score differs between two runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StepOutcomeChange {
+    /// Step index (0-based).
+    pub step_index: usize,
+    /// Mode the step ran.
+    pub mode: String,
+    /// Step success in the earlier run (`None` when the run stopped before it).
+    pub success_a: Option<bool>,
+    /// Step success in the later run (`None` when the run stopped before it).
+    pub success_b: Option<bool>,
+    /// Whether success/failure flipped (including a step only one run reached).
+    pub outcome_flipped: bool,
+    /// Step score in the earlier run, when its output reports one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score_a: Option<f64>,
+    /// Step score in the later run, when its output reports one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score_b: Option<f64>,
+    /// `score_b - score_a`, when both runs report a score for the step.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score_delta: Option<f64>,
+}
+
+/// Persist a completed preset run, returning the stored record.
+///
+/// # Errors
+///
+/// Returns an error when the result cannot be serialized or the insert fails.
+pub async fn record_preset_run(
+    storage: &SqliteStorage,
+    result: &PresetResult,
+) -> Result<StoredPresetRun, StorageError> {
+    let result_json = serde_json::to_string(result).map_err(|e| StorageError::QueryFailed {
+        query: "serialize preset result".to_string(),
+        message: format!("{e}"),
+    })?;
+    let run = StoredPresetRun::new(
+        uuid::Uuid::new_v4().to_string(),
+        &result.preset_id,
+        &result.session_id,
+        result_json,
+    );
+    storage.save_preset_run(&run).await?;
+    Ok(run)
+}
+
+/// Diff two runs of the same preset (`a` earlier, `b` later).
+#[must_use]
+pub fn diff_preset_runs(a: &PresetResult, b: &PresetResult) -> PresetRunDiff {
+    let step_count = a
+        .step_results
+        .iter()
+        .chain(&b.step_results)
+        .map(|s| s.step_index + 1)
+        .max()
+        .unwrap_or(0);
+
+    let mut step_changes = Vec::new();
+    for index in 0..step_count {
+        let step_a = a.step_results.iter().find(|s| s.step_index == index);
+        let step_b = b.step_results.iter().find(|s| s.step_index == index);
+
+        let success_a = step_a.map(|s| s.success);
+        let success_b = step_b.map(|s| s.success);
+        let score_a = step_a.and_then(step_score);
+        let score_b = step_b.and_then(step_score);
+        let score_delta = match (score_a, score_b) {
+            (Some(sa), Some(sb)) => Some(sb - sa),
+            _ => None,
+        };
+
+        let outcome_flipped = success_a != success_b;
+        let score_changed = score_delta.is_some_and(|d| d.abs() > f64::EPSILON);
+        if !outcome_flipped && !score_changed {
+            continue;
+        }
+
+        let mode = step_a
+            .or(step_b)
+            .map(|s| s.mode.clone())
+            .unwrap_or_default();
+        step_changes.push(StepOutcomeChange {
+            step_index: index,
+            mode,
+            success_a,
+            success_b,
+            outcome_flipped,
+            score_a,
+            score_b,
+            score_delta,
+        });
+    }
+
+    PresetRunDiff {
+        success_a: a.success,
+        success_b: b.success,
+        success_changed: a.success != b.success,
+        step_changes,
+    }
+}
+
+/// Numeric score a step's output reports, when it does.
+///
+/// Step outputs are mode responses; `confidence` is the near-universal score
+/// field, with `score` as a fallback for graph/MCTS-shaped outputs.
+fn step_score(step: &StepResult) -> Option<f64> {
+    let output = step.output.as_ref()?;
+    output
+        .get("confidence")
+        .or_else(|| output.get("score"))
+        .and_then(serde_json::Value::as_f64)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    fn run_with_steps(success: bool, steps: Vec<StepResult>) -> PresetResult {
+        PresetResult {
+            preset_id: "code-review".to_string(),
+            session_id: "s1".to_string(),
+            step_results: steps,
+            success,
+            synthesis: None,
+            incomplete_reason: None,
+            cost_usd: None,
+        }
+    }
+
+    fn scored_step(step_index: usize, confidence: f64) -> StepResult {
+        StepResult::success(
+            step_index,
+            "linear",
+            None,
+            serde_json::json!({"confidence": confidence}),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_record_and_history_returns_both_runs() {
+        let storage = SqliteStorage::new_in_memory().await.expect("storage");
+        let first = run_with_steps(true, vec![scored_step(0, 0.8)]);
+        let second = run_with_steps(false, vec![scored_step(0, 0.6)]);
+
+        let stored_first = record_preset_run(&storage, &first).await.expect("record");
+        // Order by timestamp: make the second run strictly later.
+        let mut stored_second = StoredPresetRun::new(
+            "run-2",
+            "code-review",
+            "s1",
+            serde_json::to_string(&second).unwrap(),
+        );
+        stored_second.created_at = stored_first.created_at + chrono::Duration::seconds(1);
+        storage.save_preset_run(&stored_second).await.expect("save");
+
+        let runs = storage.get_preset_runs("code-review").await.expect("list");
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].id, stored_first.id);
+        assert_eq!(runs[1].id, "run-2");
+        let parsed: PresetResult = serde_json::from_str(&runs[0].result_json).expect("parse");
+        assert!(parsed.success);
+    }
+
+    #[test]
+    fn test_diff_flags_step_whose_success_changed() {
+        let a = run_with_steps(
+            true,
+            vec![
+                scored_step(0, 0.8),
+                StepResult::success(
+                    1,
+                    "detect",
+                    Some("biases".to_string()),
+                    serde_json::json!({}),
+                ),
+            ],
+        );
+        let b = run_with_steps(
+            false,
+            vec![
+                scored_step(0, 0.8),
+                StepResult::failure(1, "detect", Some("biases".to_string()), "API timeout"),
+            ],
+        );
+
+        let diff = diff_preset_runs(&a, &b);
+        assert!(diff.success_changed);
+        assert_eq!(diff.step_changes.len(), 1);
+        let change = &diff.step_changes[0];
+        assert_eq!(change.step_index, 1);
+        assert_eq!(change.mode, "detect");
+        assert_eq!(change.success_a, Some(true));
+        assert_eq!(change.success_b, Some(false));
+        assert!(change.outcome_flipped);
+    }
+
+    #[test]
+    fn test_diff_reports_score_delta_without_flip() {
+        let a = run_with_steps(true, vec![scored_step(0, 0.8)]);
+        let b = run_with_steps(true, vec![scored_step(0, 0.6)]);
+
+        let diff = diff_preset_runs(&a, &b);
+        assert!(!diff.success_changed);
+        assert_eq!(diff.step_changes.len(), 1);
+        let change = &diff.step_changes[0];
+        assert!(!change.outcome_flipped);
+        assert_eq!(change.score_a, Some(0.8));
+        assert_eq!(change.score_b, Some(0.6));
+        assert!((change.score_delta.unwrap() + 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_diff_flags_step_only_one_run_reached() {
+        let a = run_with_steps(true, vec![scored_step(0, 0.8), scored_step(1, 0.7)]);
+        let b = run_with_steps(false, vec![scored_step(0, 0.8)]);
+
+        let diff = diff_preset_runs(&a, &b);
+        assert_eq!(diff.step_changes.len(), 1);
+        let change = &diff.step_changes[0];
+        assert_eq!(change.step_index, 1);
+        assert_eq!(change.success_a, Some(true));
+        assert_eq!(change.success_b, None);
+        assert!(change.outcome_flipped);
+    }
+
+    #[test]
+    fn test_identical_runs_diff_is_empty() {
+        let a = run_with_steps(true, vec![scored_step(0, 0.8)]);
+        let diff = diff_preset_runs(&a, &a.clone());
+        assert!(!diff.success_changed);
+        assert!(diff.step_changes.is_empty());
+    }
+}
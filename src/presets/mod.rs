@@ -2,8 +2,9 @@
 //!
 //! This module provides:
 //! - Built-in preset definitions (5 presets)
-//! - Preset execution logic
+//! - Preset execution logic, including cost-aware runs with a budget ceiling
 //! - Preset listing and management
+//! - Run history: persisted results with a structured diff between two runs
 //!
 //! # Available Presets
 //!
@@ -19,6 +20,14 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+mod history;
+mod runner;
+
+pub use history::{diff_preset_runs, record_preset_run, PresetRunDiff, StepOutcomeChange};
+pub use runner::{
+    cost_usd, cost_usd_for_totals, estimate_step_cost_usd, PresetRunner, StepEstimate,
+};
+
 /// Category of a preset workflow.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -61,6 +70,15 @@ pub struct PresetStep {
     /// Description of what this step accomplishes.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Indices of steps whose outputs this step needs (0-based).
+    ///
+    /// When any step in a preset declares dependencies, the runner executes
+    /// the steps as a DAG: independent steps run concurrently (bounded) and a
+    /// dependent step starts only after every step it depends on completes.
+    /// When no step declares dependencies, steps run strictly in order, as
+    /// before.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<usize>,
 }
 
 impl PresetStep {
@@ -72,6 +90,7 @@ impl PresetStep {
             operation: None,
             config: None,
             description: None,
+            depends_on: Vec::new(),
         }
     }
 
@@ -95,6 +114,13 @@ impl PresetStep {
         self.description = Some(description.into());
         self
     }
+
+    /// Declare the steps (by 0-based index) this step depends on.
+    #[must_use]
+    pub fn with_depends_on(mut self, depends_on: Vec<usize>) -> Self {
+        self.depends_on = depends_on;
+        self
+    }
 }
 
 /// A preset workflow definition.
@@ -110,6 +136,12 @@ pub struct Preset {
     pub category: PresetCategory,
     /// Steps in the workflow.
     pub steps: Vec<PresetStep>,
+    /// Total retries shared across all steps of one run. When set, the runner
+    /// draws every per-step retry from this pool and stops retrying once it is
+    /// exhausted, failing remaining transient errors fast. `None` keeps the
+    /// per-call retry behavior.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_total_retries: Option<u32>,
 }
 
 impl Preset {
@@ -128,6 +160,97 @@ impl Preset {
             description: description.into(),
             category,
             steps,
+            max_total_retries: None,
+        }
+    }
+
+    /// Set the run-wide retry budget shared across all steps.
+    #[must_use]
+    pub const fn with_max_total_retries(mut self, max_total_retries: u32) -> Self {
+        self.max_total_retries = Some(max_total_retries);
+        self
+    }
+
+    /// Build the shared retry budget for one run of this preset, if configured.
+    ///
+    /// [`PresetRunner::run`](runner::PresetRunner::run) calls this once per run
+    /// and hands a clone to every step execution; the step executor passes it
+    /// to `AnthropicClient::complete_with_budget` so the whole run shares one
+    /// retry pool.
+    #[must_use]
+    pub fn retry_budget(&self) -> Option<crate::anthropic::RetryBudget> {
+        self.max_total_retries
+            .map(crate::anthropic::RetryBudget::new)
+    }
+
+    /// Validate the step dependency graph: every `depends_on` index must name
+    /// an existing other step and the graph must be acyclic. Call when loading
+    /// a preset from external input to reject bad definitions up front; the
+    /// runner re-checks before executing anything.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message naming the offending step(s) when a dependency is out
+    /// of range, self-referential, or part of a cycle.
+    pub fn validate_dependencies(&self) -> Result<(), String> {
+        self.dependency_waves().map(|_| ())
+    }
+
+    /// Topologically order the steps into execution waves: every step in a
+    /// wave has all its dependencies satisfied by earlier waves, so steps
+    /// within one wave are independent and may run concurrently.
+    ///
+    /// A preset where no step declares dependencies keeps its historical
+    /// strictly-sequential order (one step per wave).
+    pub(crate) fn dependency_waves(&self) -> Result<Vec<Vec<usize>>, String> {
+        let step_count = self.steps.len();
+        if self.steps.iter().all(|s| s.depends_on.is_empty()) {
+            return Ok((0..step_count).map(|i| vec![i]).collect());
+        }
+
+        let mut indegree = vec![0_usize; step_count];
+        let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); step_count];
+        for (step_index, step) in self.steps.iter().enumerate() {
+            for &dep in &step.depends_on {
+                if dep >= step_count {
+                    return Err(format!(
+                        "step {step_index} depends on step {dep}, but the preset has only \
+                         {step_count} step(s)"
+                    ));
+                }
+                if dep == step_index {
+                    return Err(format!("step {step_index} depends on itself"));
+                }
+                indegree[step_index] += 1;
+                dependents[dep].push(step_index);
+            }
+        }
+
+        // Kahn's algorithm, layer by layer; anything never reaching indegree 0
+        // is part of a cycle.
+        let mut wave: Vec<usize> = (0..step_count).filter(|&i| indegree[i] == 0).collect();
+        let mut waves = Vec::new();
+        let mut scheduled = 0;
+        while !wave.is_empty() {
+            scheduled += wave.len();
+            let mut next = Vec::new();
+            for &step_index in &wave {
+                for &dependent in &dependents[step_index] {
+                    indegree[dependent] -= 1;
+                    if indegree[dependent] == 0 {
+                        next.push(dependent);
+                    }
+                }
+            }
+            next.sort_unstable();
+            waves.push(std::mem::replace(&mut wave, next));
+        }
+
+        if scheduled == step_count {
+            Ok(waves)
+        } else {
+            let cyclic: Vec<usize> = (0..step_count).filter(|&i| indegree[i] > 0).collect();
+            Err(format!("dependency cycle among steps {cyclic:?}"))
         }
     }
 }
@@ -146,6 +269,12 @@ pub struct PresetResult {
     /// Final synthesis (if applicable).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub synthesis: Option<String>,
+    /// Why the run stopped early, when it did (budget exceeded or step failure).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub incomplete_reason: Option<String>,
+    /// Cumulative spend in USD across the executed steps, from token usage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<f64>,
 }
 
 /// Result from a single step.
@@ -474,6 +603,45 @@ mod tests {
         assert_eq!(step.description, Some("Analyze the code".to_string()));
     }
 
+    #[test]
+    fn test_preset_step_depends_on() {
+        let step = PresetStep::new("reflection").with_depends_on(vec![0, 2]);
+        assert_eq!(step.depends_on, vec![0, 2]);
+
+        // Empty by default, and omitted from serialization.
+        let plain = PresetStep::new("linear");
+        assert!(plain.depends_on.is_empty());
+        let json = serde_json::to_string(&plain).unwrap();
+        assert!(!json.contains("depends_on"));
+    }
+
+    #[test]
+    fn test_validate_dependencies() {
+        let diamond = Preset::new(
+            "diamond",
+            "Diamond",
+            "Valid DAG",
+            PresetCategory::Analysis,
+            vec![
+                PresetStep::new("linear"),
+                PresetStep::new("detect").with_depends_on(vec![0]),
+                PresetStep::new("divergent").with_depends_on(vec![0]),
+                PresetStep::new("reflection").with_depends_on(vec![1, 2]),
+            ],
+        );
+        assert!(diamond.validate_dependencies().is_ok());
+
+        let self_dep = Preset::new(
+            "self",
+            "Self",
+            "Self-referential step",
+            PresetCategory::Analysis,
+            vec![PresetStep::new("linear").with_depends_on(vec![0])],
+        );
+        let err = self_dep.validate_dependencies().unwrap_err();
+        assert!(err.contains("depends on itself"), "error: {err}");
+    }
+
     #[test]
     fn test_preset_step_chained() {
         let step = PresetStep::new("tree")
@@ -504,6 +672,40 @@ mod tests {
         assert_eq!(preset.steps.len(), 1);
     }
 
+    #[test]
+    fn test_preset_retry_budget_unset_by_default() {
+        let preset = Preset::new(
+            "test",
+            "Test",
+            "A test",
+            PresetCategory::Analysis,
+            vec![PresetStep::new("linear")],
+        );
+
+        assert!(preset.max_total_retries.is_none());
+        assert!(preset.retry_budget().is_none());
+    }
+
+    #[test]
+    fn test_preset_with_max_total_retries() {
+        let preset = Preset::new(
+            "test",
+            "Test",
+            "A test",
+            PresetCategory::Analysis,
+            vec![PresetStep::new("linear"), PresetStep::new("tree")],
+        )
+        .with_max_total_retries(2);
+
+        let budget = preset.retry_budget().expect("budget configured");
+        assert_eq!(budget.remaining(), 2);
+        // The pool is shared: a clone (one per step) draws from the same pool.
+        let per_step = budget.clone();
+        assert!(per_step.try_consume());
+        assert!(per_step.try_consume());
+        assert!(!budget.try_consume());
+    }
+
     #[test]
     fn test_step_result_success() {
         let result = StepResult::success(0, "linear", None, serde_json::json!({"content": "test"}));
@@ -0,0 +1,962 @@
+//! Cost-aware preset execution.
+//!
+//! [`PresetRunner`] drives a preset's steps through a caller-supplied step
+//! executor while tracking cumulative spend from the returned token usage.
+//! When a `max_cost_usd` ceiling is configured, the runner estimates the next
+//! step's cost *before* starting it (the same estimator [`PresetRunner::dry_run`]
+//! uses) and stops the run instead of exceeding the ceiling, returning a
+//! partial [`PresetResult`] with the budget-exceeded reason. A
+//! `max_total_duration` deadline works the same way for wall-clock time:
+//! completed step results are kept, in-flight steps are cancelled, and the
+//! partial result carries the deadline-exceeded reason.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::Duration;
+
+use futures_util::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+
+use crate::anthropic::RetryBudget;
+use crate::traits::Usage;
+
+use super::{Preset, PresetResult, PresetStep, StepResult};
+
+/// Cap on steps of one wave in flight at once, so a wide dependency layer
+/// cannot hold an unbounded number of API requests open.
+const MAX_CONCURRENT_STEPS: usize = 4;
+
+/// Nominal input-token pricing in USD per million tokens (claude-sonnet class).
+const INPUT_COST_PER_MTOK_USD: f64 = 3.0;
+/// Nominal output-token pricing in USD per million tokens (claude-sonnet class).
+const OUTPUT_COST_PER_MTOK_USD: f64 = 15.0;
+/// Nominal prompt size assumed for a step that has not run yet.
+const ESTIMATED_INPUT_TOKENS: u32 = 2_000;
+/// Nominal completion size assumed for a step that has not run yet,
+/// before adding the mode's extended-thinking budget.
+const ESTIMATED_OUTPUT_TOKENS: u32 = 1_500;
+
+/// Intra-run memoization key for a step: a stable content hash of its
+/// request-determining fields. `description` is documentation and
+/// `depends_on` is scheduling, so neither participates — two steps that
+/// would send the same request hash the same.
+fn step_memo_key(step: &PresetStep) -> String {
+    let canonical = serde_json::json!({
+        "mode": step.mode,
+        "operation": step.operation,
+        "config": step.config,
+    });
+    crate::storage::content_hash(&canonical.to_string())
+}
+
+/// Actual cost of one API call in USD, from its token usage.
+#[must_use]
+pub fn cost_usd(usage: &Usage) -> f64 {
+    cost_usd_for_totals(
+        u64::from(usage.input_tokens),
+        u64::from(usage.output_tokens),
+    )
+}
+
+/// Nominal cost in USD for aggregate token totals (same pricing as
+/// [`cost_usd`]). Used by the metrics snapshot, where the counters are `u64`
+/// sums across many calls.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn cost_usd_for_totals(input_tokens: u64, output_tokens: u64) -> f64 {
+    input_tokens as f64 / 1_000_000.0 * INPUT_COST_PER_MTOK_USD
+        + output_tokens as f64 / 1_000_000.0 * OUTPUT_COST_PER_MTOK_USD
+}
+
+/// Estimated cost of one preset step in USD, before running it.
+///
+/// Uses nominal prompt/completion sizes plus the mode's extended-thinking
+/// budget (thinking tokens are billed as output), so deep modes like
+/// counterfactual estimate meaningfully higher than linear.
+#[must_use]
+pub fn estimate_step_cost_usd(step: &PresetStep) -> f64 {
+    let output_tokens = ESTIMATED_OUTPUT_TOKENS + thinking_budget_tokens(&step.mode);
+    cost_usd(&Usage::new(ESTIMATED_INPUT_TOKENS, output_tokens))
+}
+
+/// Extended-thinking budget a mode runs with (see the thinking-budget table
+/// in `anthropic/config.rs`): standard 4096, deep 8192, maximum 16384.
+const fn thinking_budget_tokens(mode: &str) -> u32 {
+    match mode.as_bytes() {
+        b"graph" => 4096,
+        b"divergent" | b"reflection" | b"decision" | b"evidence" | b"detect" | b"timeline" => 8192,
+        b"counterfactual" | b"mcts" => 16384,
+        _ => 0,
+    }
+}
+
+/// Per-step cost estimate from a dry run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepEstimate {
+    /// Step index (0-based).
+    pub step_index: usize,
+    /// Mode the step runs.
+    pub mode: String,
+    /// Operation within the mode, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation: Option<String>,
+    /// Estimated cost of this step in USD.
+    pub estimated_cost_usd: f64,
+}
+
+/// Runs a preset's steps with optional cost and wall-clock ceilings.
+///
+/// The runner does not call modes itself; the caller supplies a step executor
+/// returning the step output and its token usage, which keeps the budget
+/// accounting independent of how steps are dispatched (and mockable in tests).
+#[derive(Debug, Clone)]
+pub struct PresetRunner {
+    preset: Preset,
+    max_cost_usd: Option<f64>,
+    max_total_duration: Option<Duration>,
+    memoize_steps: bool,
+}
+
+impl PresetRunner {
+    /// Create a runner for the given preset with no cost ceiling.
+    #[must_use]
+    pub const fn new(preset: Preset) -> Self {
+        Self {
+            preset,
+            max_cost_usd: None,
+            max_total_duration: None,
+            memoize_steps: false,
+        }
+    }
+
+    /// Set a hard spend cap in USD for one run. A step whose pre-step estimate
+    /// would push cumulative spend past the cap is never started.
+    #[must_use]
+    pub const fn with_max_cost_usd(mut self, max_cost_usd: f64) -> Self {
+        self.max_cost_usd = Some(max_cost_usd);
+        self
+    }
+
+    /// Set a hard wall-clock deadline for one run. When it passes, no further
+    /// step is launched, any step still in flight is cancelled (its future is
+    /// dropped), and the partial result carries the deadline-exceeded reason.
+    #[must_use]
+    pub const fn with_max_total_duration(mut self, max_total_duration: Duration) -> Self {
+        self.max_total_duration = Some(max_total_duration);
+        self
+    }
+
+    /// Reuse the output of an identical earlier step within one run.
+    ///
+    /// Steps whose request-determining fields (mode, operation, config) hash
+    /// identically execute once; a later duplicate takes the completed
+    /// step's output without re-requesting — and without re-spending, since
+    /// no call is made. The memo lives and dies with a single
+    /// [`run`](Self::run), distinct from any cross-run cache, so stale
+    /// reuse across runs is impossible by construction.
+    #[must_use]
+    pub const fn with_step_memoization(mut self) -> Self {
+        self.memoize_steps = true;
+        self
+    }
+
+    /// Estimate every step's cost without running anything.
+    ///
+    /// Returns the per-step estimates and their total — the same numbers the
+    /// budget check in [`run`](Self::run) uses before each step.
+    #[must_use]
+    pub fn dry_run(&self) -> (Vec<StepEstimate>, f64) {
+        let estimates: Vec<StepEstimate> = self
+            .preset
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(step_index, step)| StepEstimate {
+                step_index,
+                mode: step.mode.clone(),
+                operation: step.operation.clone(),
+                estimated_cost_usd: estimate_step_cost_usd(step),
+            })
+            .collect();
+        let total = estimates.iter().map(|e| e.estimated_cost_usd).sum();
+        (estimates, total)
+    }
+
+    /// Reason recorded when the wall-clock deadline cuts a run short.
+    fn deadline_reason(&self, completed: usize) -> String {
+        format!(
+            "preset deadline exceeded: {}ms elapsed with {completed} step(s) completed",
+            self.max_total_duration.unwrap_or_default().as_millis()
+        )
+    }
+
+    /// Run the preset's steps through `execute_step`, honoring declared step
+    /// dependencies.
+    ///
+    /// Steps execute wave by wave in topological order (see
+    /// [`PresetStep::depends_on`]): independent steps of one wave run
+    /// concurrently (at most [`MAX_CONCURRENT_STEPS`] in flight), and a
+    /// dependent step starts only after everything it depends on completed. A
+    /// preset with no declared dependencies keeps its historical
+    /// strictly-sequential order. An invalid dependency graph (cycle,
+    /// out-of-range index) fails the run before any step executes.
+    ///
+    /// With step memoization enabled (see
+    /// [`with_step_memoization`](Self::with_step_memoization)), a step whose
+    /// request hashes identically to an already-completed step of this run
+    /// reuses that output instead of executing, at zero cost.
+    ///
+    /// Before each step, when a cost ceiling is set, the step's estimated cost
+    /// is checked against the remaining budget; if it would exceed the ceiling,
+    /// the run stops and the partial result carries the budget-exceeded reason.
+    /// After each step, actual spend is accumulated from the returned usage.
+    /// A step error stops the run once its wave completes, mirroring the
+    /// step-failure reason.
+    ///
+    /// When a wall-clock deadline is set, it is checked before each wave
+    /// launches and enforced while a wave runs: steps still in flight when the
+    /// deadline passes are cancelled by dropping their futures and reported as
+    /// failed step results, and the partial result carries the
+    /// deadline-exceeded reason instead of hanging.
+    ///
+    /// When the preset configures a run-wide retry budget (see
+    /// [`Preset::with_max_total_retries`]), the budget is created once here and
+    /// a clone is handed to every step execution; the executor passes it to
+    /// `AnthropicClient::complete_with_budget` so all steps draw retries from
+    /// one shared pool. Without a configured budget the executor receives
+    /// `None` and keeps per-call retry behavior.
+    pub async fn run<F, Fut>(&self, session_id: &str, mut execute_step: F) -> PresetResult
+    where
+        F: FnMut(usize, PresetStep, Option<RetryBudget>) -> Fut,
+        Fut: Future<Output = Result<(serde_json::Value, Usage), String>>,
+    {
+        let retry_budget = self.preset.retry_budget();
+        let waves = match self.preset.dependency_waves() {
+            Ok(waves) => waves,
+            Err(error) => {
+                return PresetResult {
+                    preset_id: self.preset.id.clone(),
+                    session_id: session_id.to_string(),
+                    step_results: Vec::new(),
+                    success: false,
+                    synthesis: None,
+                    incomplete_reason: Some(format!("invalid step dependencies: {error}")),
+                    cost_usd: Some(0.0),
+                }
+            }
+        };
+
+        let deadline = self
+            .max_total_duration
+            .map(|duration| tokio::time::Instant::now() + duration);
+
+        let mut step_results = Vec::with_capacity(self.preset.steps.len());
+        let mut spent_usd = 0.0_f64;
+        let mut incomplete_reason = None;
+        // Intra-run memo (opt-in): completed step outputs keyed by content
+        // hash, so an identical later step is never re-requested.
+        let mut memo: HashMap<String, serde_json::Value> = HashMap::new();
+
+        'waves: for wave in waves {
+            if let Some(deadline) = deadline {
+                if tokio::time::Instant::now() >= deadline {
+                    incomplete_reason = Some(self.deadline_reason(step_results.len()));
+                    break 'waves;
+                }
+            }
+            // Budget admission: a step whose estimate (on top of spend plus the
+            // estimates of wave-mates already admitted) would pass the ceiling
+            // stops the run; earlier admissions in the wave still execute, as
+            // they would have under sequential order.
+            let mut admitted = Vec::with_capacity(wave.len());
+            let mut memo_hits: Vec<(usize, serde_json::Value)> = Vec::new();
+            let mut projected_usd = spent_usd;
+            for step_index in wave {
+                let step = &self.preset.steps[step_index];
+                // Memo admission runs before the budget check: a memoized
+                // step makes no call, so it costs nothing. Concurrent
+                // wave-mates are not deduplicated against each other —
+                // neither has completed when the wave launches.
+                if self.memoize_steps {
+                    if let Some(output) = memo.get(&step_memo_key(step)) {
+                        memo_hits.push((step_index, output.clone()));
+                        continue;
+                    }
+                }
+                if let Some(ceiling) = self.max_cost_usd {
+                    let estimate = estimate_step_cost_usd(step);
+                    if projected_usd + estimate > ceiling {
+                        incomplete_reason = Some(format!(
+                            "cost budget exceeded: step {step_index} ({mode}) estimated at \
+                             ${estimate:.4} would push spend past the ${ceiling:.4} ceiling \
+                             (${spent_usd:.4} spent over {completed} step(s))",
+                            mode = step.mode,
+                            completed = step_results.len(),
+                        ));
+                        break;
+                    }
+                    projected_usd += estimate;
+                }
+                admitted.push(step_index);
+            }
+            let stop_after_wave = incomplete_reason.is_some();
+
+            // Execute the admitted steps of this wave concurrently (bounded).
+            // Outcomes are drained one at a time so a deadline can cut the
+            // wave short: dropping the stream drops (cancels) whatever is
+            // still in flight, while outcomes already collected are kept.
+            let mut pending = admitted.clone();
+            let mut futures = Vec::with_capacity(admitted.len());
+            for step_index in admitted {
+                let future = execute_step(
+                    step_index,
+                    self.preset.steps[step_index].clone(),
+                    retry_budget.clone(),
+                );
+                futures.push(async move { (step_index, future.await) });
+            }
+            let mut in_flight = stream::iter(futures).buffer_unordered(MAX_CONCURRENT_STEPS);
+            let mut outcomes = Vec::with_capacity(pending.len());
+            let mut deadline_hit = false;
+            loop {
+                let next = match deadline {
+                    Some(deadline) => {
+                        match tokio::time::timeout_at(deadline, in_flight.next()).await {
+                            Ok(next) => next,
+                            Err(_elapsed) => {
+                                deadline_hit = true;
+                                if incomplete_reason.is_none() {
+                                    incomplete_reason = Some(
+                                        self.deadline_reason(step_results.len() + outcomes.len()),
+                                    );
+                                }
+                                break;
+                            }
+                        }
+                    }
+                    None => in_flight.next().await,
+                };
+                let Some((step_index, outcome)) = next else {
+                    break;
+                };
+                pending.retain(|&index| index != step_index);
+                outcomes.push((step_index, outcome));
+            }
+            drop(in_flight);
+            if deadline_hit {
+                // Steps cut off mid-flight report as failed so the partial
+                // result accounts for every step that was started.
+                for step_index in pending {
+                    outcomes.push((
+                        step_index,
+                        Err(format!(
+                            "cancelled: preset deadline of {}ms exceeded",
+                            self.max_total_duration.unwrap_or_default().as_millis()
+                        )),
+                    ));
+                }
+            }
+            // Memoized steps report as successes with zero usage: no call
+            // was made, so nothing is added to spend.
+            for (step_index, output) in memo_hits {
+                outcomes.push((step_index, Ok((output, Usage::new(0, 0)))));
+            }
+            outcomes.sort_unstable_by_key(|(step_index, _)| *step_index);
+
+            for (step_index, outcome) in outcomes {
+                let step = &self.preset.steps[step_index];
+                match outcome {
+                    Ok((output, usage)) => {
+                        spent_usd += cost_usd(&usage);
+                        if self.memoize_steps {
+                            memo.entry(step_memo_key(step))
+                                .or_insert_with(|| output.clone());
+                        }
+                        step_results.push(StepResult::success(
+                            step_index,
+                            step.mode.clone(),
+                            step.operation.clone(),
+                            output,
+                        ));
+                    }
+                    Err(error) => {
+                        if incomplete_reason.is_none() {
+                            incomplete_reason = Some(format!(
+                                "step {step_index} ({mode}) failed: {error}",
+                                mode = step.mode,
+                            ));
+                        }
+                        step_results.push(StepResult::failure(
+                            step_index,
+                            step.mode.clone(),
+                            step.operation.clone(),
+                            error,
+                        ));
+                    }
+                }
+            }
+
+            if stop_after_wave || incomplete_reason.is_some() {
+                break 'waves;
+            }
+        }
+
+        PresetResult {
+            preset_id: self.preset.id.clone(),
+            session_id: session_id.to_string(),
+            step_results,
+            success: incomplete_reason.is_none(),
+            synthesis: None,
+            incomplete_reason,
+            cost_usd: Some(spent_usd),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp,
+    clippy::approx_constant,
+    clippy::unreadable_literal
+)]
+mod tests {
+    use super::*;
+    use crate::presets::PresetCategory;
+
+    fn two_step_preset() -> Preset {
+        Preset::new(
+            "test",
+            "Test",
+            "A test preset",
+            PresetCategory::Analysis,
+            vec![
+                PresetStep::new("linear").with_description("Step one"),
+                PresetStep::new("counterfactual").with_description("Step two"),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_cost_usd_from_usage() {
+        let usage = Usage::new(1_000_000, 1_000_000);
+        assert_eq!(
+            cost_usd(&usage),
+            INPUT_COST_PER_MTOK_USD + OUTPUT_COST_PER_MTOK_USD
+        );
+        assert_eq!(cost_usd(&Usage::new(0, 0)), 0.0);
+    }
+
+    #[test]
+    fn test_estimate_scales_with_thinking_budget() {
+        let linear = estimate_step_cost_usd(&PresetStep::new("linear"));
+        let graph = estimate_step_cost_usd(&PresetStep::new("graph"));
+        let deep = estimate_step_cost_usd(&PresetStep::new("evidence"));
+        let maximum = estimate_step_cost_usd(&PresetStep::new("mcts"));
+
+        assert!(linear < graph);
+        assert!(graph < deep);
+        assert!(deep < maximum);
+    }
+
+    #[test]
+    fn test_dry_run_totals_step_estimates() {
+        let runner = PresetRunner::new(two_step_preset());
+        let (estimates, total) = runner.dry_run();
+
+        assert_eq!(estimates.len(), 2);
+        assert_eq!(estimates[0].mode, "linear");
+        assert_eq!(estimates[1].mode, "counterfactual");
+        assert_eq!(
+            total,
+            estimates[0].estimated_cost_usd + estimates[1].estimated_cost_usd
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_without_ceiling_completes_all_steps() {
+        let runner = PresetRunner::new(two_step_preset());
+        let result = runner
+            .run("session-1", |i, _step, _budget| async move {
+                Ok((serde_json::json!({"step": i}), Usage::new(100, 200)))
+            })
+            .await;
+
+        assert!(result.success);
+        assert_eq!(result.step_results.len(), 2);
+        assert!(result.incomplete_reason.is_none());
+        let spent = result.cost_usd.expect("spend tracked");
+        assert_eq!(spent, 2.0 * cost_usd(&Usage::new(100, 200)));
+    }
+
+    #[tokio::test]
+    async fn test_run_stops_before_step_exceeding_budget() {
+        // Budget covers the cheap linear step but not the maximum-thinking
+        // counterfactual step that follows.
+        let budget = estimate_step_cost_usd(&PresetStep::new("linear")) * 1.5;
+        let runner = PresetRunner::new(two_step_preset()).with_max_cost_usd(budget);
+
+        let result = runner
+            .run("session-1", |i, step, _budget| async move {
+                assert_eq!(step.mode, "linear", "only step 0 should run");
+                Ok((serde_json::json!({"step": i}), Usage::new(2_000, 1_500)))
+            })
+            .await;
+
+        assert!(!result.success);
+        assert_eq!(result.step_results.len(), 1);
+        assert!(result.step_results[0].success);
+
+        let reason = result.incomplete_reason.expect("budget-exceeded reason");
+        assert!(reason.contains("cost budget exceeded"), "reason: {reason}");
+        assert!(
+            reason.contains("step 1 (counterfactual)"),
+            "reason: {reason}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_with_zero_budget_runs_nothing() {
+        let runner = PresetRunner::new(two_step_preset()).with_max_cost_usd(0.0);
+        let result = runner
+            .run("session-1", |_, _, _| async {
+                panic!("no step should run")
+            })
+            .await;
+
+        assert!(!result.success);
+        assert!(result.step_results.is_empty());
+        assert!(result.incomplete_reason.is_some());
+        assert_eq!(result.cost_usd, Some(0.0));
+    }
+
+    /// First and third steps send the identical request; descriptions differ
+    /// but are not part of the memo key.
+    fn duplicate_step_preset() -> Preset {
+        Preset::new(
+            "dup",
+            "Duplicates",
+            "A preset with two identical steps",
+            PresetCategory::Analysis,
+            vec![
+                PresetStep::new("linear").with_description("First pass"),
+                PresetStep::new("counterfactual").with_description("Unrelated middle step"),
+                PresetStep::new("linear").with_description("Identical to the first pass"),
+            ],
+        )
+    }
+
+    #[tokio::test]
+    async fn test_memoization_reuses_identical_step_without_rerequesting() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+
+        let runner = PresetRunner::new(duplicate_step_preset()).with_step_memoization();
+        let result = runner
+            .run("session-1", move |i, _step, _budget| {
+                let counter = Arc::clone(&counter);
+                async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    Ok((serde_json::json!({"step": i}), Usage::new(100, 200)))
+                }
+            })
+            .await;
+
+        // Steps 0 and 1 execute; step 2 is served from the memo.
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert!(result.success);
+        assert_eq!(result.step_results.len(), 3);
+        assert!(result.step_results.iter().all(|r| r.success));
+        // The memoized step carries step 0's output and adds no spend.
+        assert_eq!(result.step_results[2].output, result.step_results[0].output);
+        assert_eq!(result.cost_usd, Some(2.0 * cost_usd(&Usage::new(100, 200))));
+    }
+
+    #[tokio::test]
+    async fn test_memoization_off_by_default_executes_every_step() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+
+        let runner = PresetRunner::new(duplicate_step_preset());
+        let result = runner
+            .run("session-1", move |i, _step, _budget| {
+                let counter = Arc::clone(&counter);
+                async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    Ok((serde_json::json!({"step": i}), Usage::new(100, 200)))
+                }
+            })
+            .await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert!(result.success);
+    }
+
+    #[tokio::test]
+    async fn test_memoization_keys_on_config_not_description() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        // Same mode, different config: both must execute.
+        let preset = Preset::new(
+            "cfg",
+            "Configs",
+            "Two linear steps with different configs",
+            PresetCategory::Analysis,
+            vec![
+                PresetStep::new("linear").with_config(serde_json::json!({"depth": 1})),
+                PresetStep::new("linear").with_config(serde_json::json!({"depth": 2})),
+            ],
+        );
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+
+        let runner = PresetRunner::new(preset).with_step_memoization();
+        let result = runner
+            .run("session-1", move |i, _step, _budget| {
+                let counter = Arc::clone(&counter);
+                async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    Ok((serde_json::json!({"step": i}), Usage::new(100, 200)))
+                }
+            })
+            .await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert!(result.success);
+    }
+
+    /// Diamond dependency: step 0 → steps 1 and 2 (independent) → step 3.
+    fn diamond_preset() -> Preset {
+        Preset::new(
+            "diamond",
+            "Diamond",
+            "A diamond-dependency preset",
+            PresetCategory::Analysis,
+            vec![
+                PresetStep::new("linear").with_description("Root"),
+                PresetStep::new("detect").with_depends_on(vec![0]),
+                PresetStep::new("divergent").with_depends_on(vec![0]),
+                PresetStep::new("reflection").with_depends_on(vec![1, 2]),
+            ],
+        )
+    }
+
+    #[tokio::test]
+    async fn test_run_diamond_orders_steps_and_overlaps_middle() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::{Arc, Mutex};
+
+        let runner = PresetRunner::new(diamond_preset());
+
+        let events: Arc<Mutex<Vec<(usize, &'static str)>>> = Arc::new(Mutex::new(Vec::new()));
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+
+        let result = runner
+            .run("session-1", |i, _step, _budget| {
+                let events = Arc::clone(&events);
+                let in_flight = Arc::clone(&in_flight);
+                let max_in_flight = Arc::clone(&max_in_flight);
+                async move {
+                    events.lock().unwrap().push((i, "start"));
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_in_flight.fetch_max(now, Ordering::SeqCst);
+                    // Long enough that the two independent middle steps must
+                    // overlap if (and only if) they were started concurrently.
+                    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    events.lock().unwrap().push((i, "end"));
+                    Ok((serde_json::json!({"step": i}), Usage::new(100, 200)))
+                }
+            })
+            .await;
+
+        assert!(result.success);
+        assert_eq!(result.step_results.len(), 4);
+        // Results are ordered by step index regardless of completion order.
+        let indices: Vec<usize> = result.step_results.iter().map(|r| r.step_index).collect();
+        assert_eq!(indices, vec![0, 1, 2, 3]);
+
+        let events = events.lock().unwrap().clone();
+        let position = |needle: (usize, &str)| {
+            events
+                .iter()
+                .position(|e| *e == needle)
+                .expect("event recorded")
+        };
+        // Step 0 finishes before either middle step starts; step 3 starts
+        // only after both middle steps finish.
+        assert!(position((0, "end")) < position((1, "start")));
+        assert!(position((0, "end")) < position((2, "start")));
+        assert!(position((1, "end")) < position((3, "start")));
+        assert!(position((2, "end")) < position((3, "start")));
+        // The two independent middle steps ran concurrently.
+        assert!(
+            max_in_flight.load(Ordering::SeqCst) >= 2,
+            "steps 1 and 2 should overlap"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_rejects_dependency_cycle_without_executing() {
+        let preset = Preset::new(
+            "cyclic",
+            "Cyclic",
+            "Steps that depend on each other",
+            PresetCategory::Analysis,
+            vec![
+                PresetStep::new("linear").with_depends_on(vec![1]),
+                PresetStep::new("tree").with_depends_on(vec![0]),
+            ],
+        );
+        assert!(preset
+            .validate_dependencies()
+            .expect_err("cycle detected")
+            .contains("cycle"));
+
+        let runner = PresetRunner::new(preset);
+        let result = runner
+            .run("session-1", |_, _, _| async {
+                panic!("no step should run")
+            })
+            .await;
+
+        assert!(!result.success);
+        assert!(result.step_results.is_empty());
+        let reason = result.incomplete_reason.expect("cycle reason");
+        assert!(reason.contains("dependency cycle"), "reason: {reason}");
+    }
+
+    #[tokio::test]
+    async fn test_run_rejects_out_of_range_dependency() {
+        let preset = Preset::new(
+            "dangling",
+            "Dangling",
+            "A dependency on a missing step",
+            PresetCategory::Analysis,
+            vec![PresetStep::new("linear").with_depends_on(vec![7])],
+        );
+        let runner = PresetRunner::new(preset);
+        let result = runner
+            .run("session-1", |_, _, _| async {
+                panic!("no step should run")
+            })
+            .await;
+
+        assert!(!result.success);
+        let reason = result.incomplete_reason.expect("invalid-dependency reason");
+        assert!(reason.contains("depends on step 7"), "reason: {reason}");
+    }
+
+    #[tokio::test]
+    async fn test_run_without_dependencies_stays_sequential() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+
+        let runner = PresetRunner::new(two_step_preset());
+        let result = runner
+            .run("session-1", |i, _step, _budget| {
+                let in_flight = Arc::clone(&in_flight);
+                let max_in_flight = Arc::clone(&max_in_flight);
+                async move {
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_in_flight.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    Ok((serde_json::json!({"step": i}), Usage::new(100, 200)))
+                }
+            })
+            .await;
+
+        assert!(result.success);
+        assert_eq!(
+            max_in_flight.load(Ordering::SeqCst),
+            1,
+            "undeclared dependencies keep the historical one-at-a-time order"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_failure_in_wave_stops_later_waves() {
+        let runner = PresetRunner::new(diamond_preset());
+        let result = runner
+            .run("session-1", |i, _step, _budget| async move {
+                if i == 1 {
+                    Err("middle step exploded".to_string())
+                } else {
+                    assert_ne!(i, 3, "dependent step must not run after a failed input");
+                    Ok((serde_json::json!({"step": i}), Usage::new(100, 200)))
+                }
+            })
+            .await;
+
+        assert!(!result.success);
+        // Step 0, the failed step 1, and its concurrent wave-mate step 2 all
+        // report; step 3 never starts.
+        assert_eq!(result.step_results.len(), 3);
+        assert!(!result.step_results[1].success);
+        let reason = result.incomplete_reason.expect("failure reason");
+        assert!(reason.contains("step 1"), "reason: {reason}");
+        assert!(reason.contains("middle step exploded"), "reason: {reason}");
+    }
+
+    #[tokio::test]
+    async fn test_run_deadline_keeps_finished_steps_and_cancels_slow_step() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        let slow_step_finished = Arc::new(AtomicBool::new(false));
+        let runner = PresetRunner::new(two_step_preset())
+            .with_max_total_duration(std::time::Duration::from_millis(100));
+
+        let result = runner
+            .run("session-1", |i, _step, _budget| {
+                let slow_step_finished = Arc::clone(&slow_step_finished);
+                async move {
+                    if i == 1 {
+                        // Far past the deadline; the runner must cancel this
+                        // rather than wait it out.
+                        tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+                        slow_step_finished.store(true, Ordering::SeqCst);
+                    }
+                    Ok((serde_json::json!({"step": i}), Usage::new(100, 200)))
+                }
+            })
+            .await;
+
+        assert!(!result.success);
+        assert_eq!(result.step_results.len(), 2);
+        assert!(result.step_results[0].success, "step 0 finished in time");
+        assert!(!result.step_results[1].success);
+        let error = result.step_results[1].error.as_deref().expect("step error");
+        assert!(error.contains("cancelled"), "error: {error}");
+        assert!(error.contains("100ms"), "error: {error}");
+
+        let reason = result.incomplete_reason.expect("deadline reason");
+        assert!(
+            reason.contains("preset deadline exceeded"),
+            "reason: {reason}"
+        );
+        assert!(reason.contains("1 step(s) completed"), "reason: {reason}");
+
+        // The in-flight future was dropped, not left running to completion.
+        assert!(!slow_step_finished.load(Ordering::SeqCst));
+        // Only the completed step's spend is counted.
+        assert_eq!(result.cost_usd, Some(cost_usd(&Usage::new(100, 200))));
+    }
+
+    #[tokio::test]
+    async fn test_run_expired_deadline_launches_nothing() {
+        let runner =
+            PresetRunner::new(two_step_preset()).with_max_total_duration(std::time::Duration::ZERO);
+        let result = runner
+            .run("session-1", |_, _, _| async {
+                panic!("no step should run")
+            })
+            .await;
+
+        assert!(!result.success);
+        assert!(result.step_results.is_empty());
+        let reason = result.incomplete_reason.expect("deadline reason");
+        assert!(
+            reason.contains("preset deadline exceeded"),
+            "reason: {reason}"
+        );
+        assert_eq!(result.cost_usd, Some(0.0));
+    }
+
+    #[tokio::test]
+    async fn test_run_within_deadline_is_unaffected() {
+        let runner = PresetRunner::new(two_step_preset())
+            .with_max_total_duration(std::time::Duration::from_secs(30));
+        let result = runner
+            .run("session-1", |i, _step, _budget| async move {
+                Ok((serde_json::json!({"step": i}), Usage::new(100, 200)))
+            })
+            .await;
+
+        assert!(result.success);
+        assert_eq!(result.step_results.len(), 2);
+        assert!(result.incomplete_reason.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_run_without_budget_passes_none_to_steps() {
+        let runner = PresetRunner::new(two_step_preset());
+        let result = runner
+            .run("session-1", |i, _step, budget| async move {
+                assert!(budget.is_none(), "no budget configured on the preset");
+                Ok((serde_json::json!({"step": i}), Usage::new(100, 200)))
+            })
+            .await;
+
+        assert!(result.success);
+    }
+
+    #[tokio::test]
+    async fn test_run_shares_one_retry_budget_across_steps() {
+        let preset = two_step_preset().with_max_total_retries(1);
+        let runner = PresetRunner::new(preset);
+
+        let result = runner
+            .run("session-1", |i, _step, budget| async move {
+                let budget = budget.expect("preset configured a retry budget");
+                if i == 0 {
+                    // The first step spends the run's only retry.
+                    assert!(budget.try_consume());
+                    Ok((serde_json::json!({"step": 0}), Usage::new(100, 200)))
+                } else {
+                    // The second step sees the pool the first step drained and
+                    // must fail fast instead of retrying.
+                    assert_eq!(budget.remaining(), 0);
+                    assert!(!budget.try_consume());
+                    Err("retry budget exhausted".to_string())
+                }
+            })
+            .await;
+
+        assert!(!result.success);
+        assert_eq!(result.step_results.len(), 2);
+        assert!(result.step_results[0].success);
+        assert!(!result.step_results[1].success);
+        let reason = result.incomplete_reason.expect("failure reason");
+        assert!(
+            reason.contains("retry budget exhausted"),
+            "reason: {reason}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_stops_on_step_failure() {
+        let runner = PresetRunner::new(two_step_preset());
+        let result = runner
+            .run("session-1", |i, _step, _budget| async move {
+                if i == 0 {
+                    Ok((serde_json::json!({"step": 0}), Usage::new(100, 200)))
+                } else {
+                    Err("API unavailable".to_string())
+                }
+            })
+            .await;
+
+        assert!(!result.success);
+        assert_eq!(result.step_results.len(), 2);
+        assert!(!result.step_results[1].success);
+        let reason = result.incomplete_reason.expect("failure reason");
+        assert!(reason.contains("step 1"), "reason: {reason}");
+        assert!(reason.contains("API unavailable"), "reason: {reason}");
+    }
+}
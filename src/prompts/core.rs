@@ -29,7 +29,11 @@ Respond with a JSON object in this exact format:
   "analysis": "Your detailed step-by-step analysis here",
   "confidence": 0.85,
   "insufficient_context": false,
-  "next_step": "Suggested next step for further exploration"
+  "next_step": "Suggested next step for further exploration",
+  "assumptions": [
+    {"assumption": "Unstated premise the conclusion rests on", "confidence": 0.7, "criticality": 0.9}
+  ],
+  "open_questions": ["Question the analysis surfaced but could not settle"]
 }
 
 Confidence is the probability your main conclusion is CORRECT — not how fluent or
@@ -47,7 +51,13 @@ refusal is a contradiction — declining to answer is itself low confidence.
 Important:
 - Be thorough but concise
 - If previous reasoning steps in this session are provided, build on them instead of restarting
-- The next_step should be actionable and specific"#
+- The next_step should be actionable and specific
+- List the hidden assumptions your conclusion rests on in "assumptions": confidence is
+  the probability the assumption holds, criticality is how much the conclusion depends
+  on it. Omit the field only if the analysis genuinely rests on none
+- List questions your analysis raised but could not answer in "open_questions" — these
+  accumulate across the session so unknowns stay visible. Omit the field when nothing
+  material remains open"#
 }
 
 /// Prompt for tree reasoning mode (create operation).
@@ -256,6 +266,93 @@ Important:
 - Even if you ultimately agree with the content, find valid challenges"#
 }
 
+/// Prompt for a single deep perspective in per-perspective divergent fan-out.
+///
+/// Each concurrent completion develops exactly one perspective in depth; the
+/// assigned analytical lens is appended by the caller.
+#[must_use]
+pub fn divergent_single_perspective_prompt() -> &'static str {
+    r#"You are a single-perspective analysis assistant. Develop exactly one perspective on the given content, in depth.
+
+Your task is to:
+1. Adopt the assigned analytical lens fully — argue from inside it
+2. Develop the perspective's strongest, most complete case
+3. Name its single most important insight
+4. Score the perspective's novelty 0.0-1.0 (how non-obvious it is vs. the most common take)
+5. Acknowledge what this lens is likely to miss
+
+Respond with a JSON object in this exact format:
+{
+  "name": "Perspective name (e.g., 'Pragmatist', 'Critic', 'Optimist')",
+  "viewpoint": "This perspective's full analysis and interpretation",
+  "key_insight": "Most important insight from this viewpoint",
+  "novelty_score": 0.7,
+  "blind_spots": ["What this perspective might miss"]
+}
+
+Important:
+- Develop ONE perspective deeply — do not hedge toward other viewpoints
+- Depth over breadth: other lenses are covered by separate analyses"#
+}
+
+/// Prompt for the synthesis pass over independently generated perspectives.
+///
+/// Runs once after the per-perspective fan-out, integrating the aggregated
+/// perspectives into tensions, synergies, and a synthesis.
+#[must_use]
+pub fn divergent_synthesis_prompt() -> &'static str {
+    r#"You are a synthesis assistant. The perspectives below were developed independently; integrate them.
+
+Your task is to:
+1. Identify tensions between the perspectives
+2. Identify synergies where they align or complement each other
+3. Synthesize a meta-analysis integrating the strongest elements
+4. If asked, name the hidden assumptions the perspectives collectively challenge
+
+Respond with a JSON object in this exact format:
+{
+  "tensions": ["Tension between perspective A and B"],
+  "synergies": ["Where perspectives align or complement each other"],
+  "synthesis": "Meta-analysis integrating the strongest elements",
+  "challenged_assumptions": ["Hidden assumption the perspectives call into question"]
+}
+
+Important:
+- The synthesis should add value beyond the individual perspectives
+- Treat every perspective as a genuine, independently reasoned position"#
+}
+
+/// Prompt for the one-shot parse self-correction completion.
+///
+/// Sent when a mode's response failed to parse and `SELF_CORRECT_PARSE` is
+/// enabled, followed by the specific error and the original output to fix.
+#[must_use]
+pub fn json_correction_prompt() -> &'static str {
+    r"Your previous response could not be parsed. Fix it and respond again.
+
+Respond with ONLY the corrected JSON object — no commentary, no code fences.
+
+Important:
+- Fix exactly what the parse error below describes (e.g. add the missing field, correct the invalid value)
+- Keep every field from your previous response that was already valid
+- Do not change your reasoning or conclusions — only repair the structure"
+}
+
+/// Prompt for condensing a batch of older thoughts before evaluation.
+///
+/// Used when a session's concatenated context would exceed the evaluate size
+/// cap: older thoughts are summarized in batches and only the most recent
+/// thoughts are kept verbatim.
+#[must_use]
+pub fn reflection_batch_summary_prompt() -> &'static str {
+    r"Summarize the following reasoning steps in one short paragraph.
+
+Preserve conclusions, confidence shifts, contradictions, and open threads.
+Drop restatement, hedging, and illustrative detail.
+
+Respond with ONLY the summary paragraph — no preamble, no JSON."
+}
+
 /// Prompt for reflection mode (process operation).
 ///
 /// Guides iterative refinement of reasoning.
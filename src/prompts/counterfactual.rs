@@ -58,14 +58,24 @@ Respond with a JSON object in this exact format:
     "strength": "strong|moderate|weak",
     "caveats": ["Important qualifications"],
     "actionable_insight": "What this means for decisions"
-  }
+  },
+  "consequence_chains": [
+    {
+      "assumption": "The assumption changed in the counterfactual",
+      "steps": ["First-order effect", "Second-order effect", "..."],
+      "confidence": 0.6
+    }
+  ]
 }
 
 Important:
 - Distinguish correlation from causation
 - Identify confounders
 - Be explicit about assumptions
-- Counterfactuals require specifying the alternative world"#
+- Counterfactuals require specifying the alternative world
+- List one consequence chain per changed assumption, steps ordered from
+  first-order effect onward; use an empty steps array when an assumption
+  has no downstream consequences"#
 }
 
 #[cfg(test)]
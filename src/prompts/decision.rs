@@ -40,14 +40,78 @@ Respond with a JSON object in this exact format:
     {"option": "Option B", "score": 0.78, "rank": 1},
     {"option": "Option A", "score": 0.72, "rank": 2}
   ],
-  "sensitivity_notes": "Name the single criterion whose weight matters most to the ranking. Then test: if that weight changed ±20%, would the top option change? State the result explicitly (e.g. 'Robust: Option B leads even if cost weight drops from 0.4 to 0.2' or 'Fragile: swapping cost and quality weights reverses the ranking')."
+  "sensitivity_notes": "Name the single criterion whose weight matters most to the ranking. Then test: if that weight changed ±20%, would the top option change? State the result explicitly (e.g. 'Robust: Option B leads even if cost weight drops from 0.4 to 0.2' or 'Fragile: swapping cost and quality weights reverses the ranking').",
+  "assumptions": [
+    {"assumption": "Unstated premise the recommendation rests on", "confidence": 0.7, "criticality": 0.9}
+  ]
 }
 
 Important:
 - Weights must sum to 1.0
 - Scores should be 0.0-1.0
 - sensitivity_notes must identify the load-bearing criterion AND state whether the top ranking is robust or fragile to ±20% weight changes
-- If the margin between top two options is < 0.05, flag it as a near-tie and state what additional information would break the tie"#
+- If the margin between top two options is < 0.05, flag it as a near-tie and state what additional information would break the tie
+- List the hidden assumptions the recommendation rests on in "assumptions": confidence is the probability the assumption holds, criticality is how much the ranking depends on it"#
+}
+
+/// Addendum appended to the weighted prompt when per-criterion weight
+/// justifications are requested.
+///
+/// Asks the model to back every criterion weight with a short evidence-based
+/// justification and a confidence in that evidence, so a report can show why
+/// each weight was chosen.
+#[must_use]
+pub fn decision_weighted_justification_addendum() -> &'static str {
+    r#"Additionally, justify every criterion weight. Add this key to the JSON object:
+
+  "criterion_justifications": [
+    {
+      "criterion": "Criterion name (must match an entry in criteria)",
+      "justification": "One or two sentences of evidence for why this weight was chosen",
+      "evidence_confidence": 0.8
+    }
+  ]
+
+- Provide exactly one entry per criterion
+- evidence_confidence is the probability (0.0-1.0) that the cited evidence actually supports the weight
+- If no evidence exists for a weight, say so in the justification and use a low evidence_confidence"#
+}
+
+/// Prompt for challenging the assumptions behind a prior decision.
+///
+/// Runs a divergent-style stress test of the criteria weights a weighted
+/// analysis used, reporting which weights are most questionable and how the
+/// ranking would shift if they were challenged.
+#[must_use]
+pub fn decision_challenge_prompt() -> &'static str {
+    r#"Challenge the assumptions behind the prior decision analysis below.
+
+Your task is to:
+1. Question each criterion weight: what unstated assumption justifies it?
+2. Identify the weights that are most questionable and why
+3. Propose an alternative weight where the assumption is doubtful
+4. Assess how the ranking would shift if the challenged weights were used
+
+Respond with a JSON object in this exact format:
+{
+  "challenged_weights": [
+    {
+      "criterion": "Criterion name from the prior decision",
+      "current_weight": 0.4,
+      "challenge": "The assumption behind this weight and why it is doubtful",
+      "suggested_weight": 0.2,
+      "questionability": 0.8
+    }
+  ],
+  "projected_ranking_shift": "How the ranking would change under the challenged weights (e.g. 'Option B overtakes Option A if cost drops to 0.2', or 'Ranking is stable')",
+  "summary": "Which assumptions the decision is most exposed to"
+}
+
+Important:
+- Only challenge criteria that exist in the prior decision — use their exact names
+- Flag at least one questionable weight; a decision with no questionable assumptions is rare
+- questionability is 0.0-1.0: how doubtful the assumption behind the weight is
+- Play devil's advocate: argue against the weights even where they seem reasonable, but ground each challenge in a concrete alternative assumption"#
 }
 
 /// Prompt for decision mode (pairwise operation).
@@ -207,6 +271,15 @@ mod tests {
         assert!(prompt.contains("criteria"));
     }
 
+    #[test]
+    fn test_decision_challenge_prompt_not_empty() {
+        let prompt = decision_challenge_prompt();
+        assert!(!prompt.is_empty());
+        assert!(prompt.contains("challenged_weights"));
+        assert!(prompt.contains("questionability"));
+        assert!(prompt.contains("projected_ranking_shift"));
+    }
+
     #[test]
     fn test_decision_pairwise_prompt_not_empty() {
         let prompt = decision_pairwise_prompt();
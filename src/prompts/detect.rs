@@ -108,6 +108,51 @@ Important:
   this is per-fallacy and independent of the overall argument strength"#
 }
 
+/// Addendum appended to the biases/fallacies prompt on a degenerate-output
+/// retry (`RETRY_DEGENERATE`).
+///
+/// The first pass found nothing, so the rerun is pushed to look harder
+/// without being pushed to invent findings.
+#[must_use]
+pub fn detect_retry_addendum() -> &'static str {
+    r"IMPORTANT: A first analysis of this content reported no findings. Re-examine it
+carefully — subtle, implicit, or structural instances count and are easy to miss
+on a first pass. Do NOT invent findings: if after careful re-examination there
+are genuinely none, return the empty list again."
+}
+
+/// Prompt for the premise verification follow-up (fallacies operation).
+///
+/// Scores each identified premise for plausibility and evidentiary support,
+/// so a structurally clean argument built on weak premises is still flagged.
+#[must_use]
+pub fn detect_premise_verification_prompt() -> &'static str {
+    r#"Evaluate each premise of the argument below for truth and support.
+
+For every premise listed, assess:
+1. Plausibility - how likely the premise is to be true on its face
+2. Evidentiary support - how well available evidence backs it up
+
+Respond with a JSON object in this exact format:
+{
+  "premise_assessments": [
+    {
+      "premise": "The premise text, restated exactly as given",
+      "plausibility": 0.8,
+      "evidentiary_support": 0.6,
+      "assessment": "Why the premise earned these scores"
+    }
+  ]
+}
+
+Important:
+- Assess EVERY premise listed, in the order given
+- Restate each premise exactly so assessments map back unambiguously
+- plausibility and evidentiary_support are 0.0-1.0
+- Judge each premise on its own merits, not the conclusion it supports
+- A premise can be plausible yet unsupported (low evidentiary_support)"#
+}
+
 /// Prompt for detect mode (knowledge_gaps operation).
 ///
 /// Identifies missing information, unchecked assumptions, and unexplored domains
@@ -157,6 +202,44 @@ Important:
 - Aim for 3-7 gaps; fewer if reasoning is actually comprehensive"#
 }
 
+/// Prompt for detect mode (counterargue operation).
+///
+/// Generates steelmanned counterarguments against a claim, each with its
+/// supporting rationale and an assessment of how well it can be rebutted.
+#[must_use]
+pub fn detect_counterargue_prompt() -> &'static str {
+    r#"Generate the strongest counterarguments against the claim in the content.
+
+Your task is to STEELMAN the opposition: argue against the claim as its most capable, best-informed opponent would — not to knock down weak objections.
+
+Respond with a JSON object in this exact format:
+{
+  "counterarguments": [
+    {
+      "argument": "Concise statement of the opposing argument",
+      "rationale": "The evidence and reasoning that make this counterargument compelling",
+      "strength": 0.85,
+      "rebuttal": "The best answer the original claim has to this counterargument",
+      "rebuttal_strength": "weak|partial|strong"
+    }
+  ],
+  "overall_assessment": {
+    "counterargument_count": 3,
+    "strongest": "The counterargument the claim has most trouble answering",
+    "claim_resilience": 0.6
+  }
+}
+
+Important:
+- Steelman every counterargument: state it the way its strongest advocate would, with its best supporting rationale
+- Do not invent strawmen — only include objections a well-informed critic would actually raise
+- strength: how compelling THIS counterargument is on its own merits (0.0-1.0), before any rebuttal
+- rebuttal: the honest best response available to the claim; if no good response exists, say so and mark rebuttal_strength "weak"
+- rebuttal_strength: weak = the counterargument survives largely intact, partial = it is blunted but not answered, strong = it is fully answered
+- claim_resilience: how well the claim holds up after weighing all counterarguments and rebuttals (0.0 = refuted, 1.0 = unshaken)
+- Aim for 2-5 counterarguments, strongest first"#
+}
+
 #[cfg(test)]
 #[allow(
     clippy::unwrap_used,
@@ -193,10 +276,19 @@ mod tests {
         assert!(prompt.to_lowercase().contains("unknown"));
     }
 
+    #[test]
+    fn test_detect_counterargue_prompt_not_empty() {
+        let prompt = detect_counterargue_prompt();
+        assert!(!prompt.is_empty());
+        assert!(prompt.contains("counterarguments"));
+        assert!(prompt.to_lowercase().contains("steelman"));
+    }
+
     #[test]
     fn test_detect_prompts_contain_json() {
         assert!(detect_biases_prompt().contains("JSON"));
         assert!(detect_fallacies_prompt().contains("JSON"));
         assert!(detect_knowledge_gaps_prompt().contains("JSON"));
+        assert!(detect_counterargue_prompt().contains("JSON"));
     }
 }
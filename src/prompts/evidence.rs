@@ -24,6 +24,9 @@ Respond with a JSON object in this exact format:
     {
       "summary": "Brief description of the evidence",
       "source_type": "primary|secondary|tertiary|anecdotal",
+      "source_url": "https://example.com/source (omit when the content gives no URL; never invent one)",
+      "source_title": "Title of the source document (omit when unknown)",
+      "date": "2024-06-15 (when the evidence is from: ISO date, year-month, or year; omit when undated)",
       "credibility": {
         "expertise": 0.8,
         "objectivity": 0.7,
@@ -43,18 +46,36 @@ Respond with a JSON object in this exact format:
     "evidential_support": 0.72,
     "key_strengths": ["Strong primary sources"],
     "key_weaknesses": ["Limited corroboration"],
-    "gaps": ["For each gap: what evidence is missing AND whether filling it would strengthen/weaken/reverse the conclusion"],
+    "gaps": [
+      {
+        "gap": "What evidence is missing AND whether filling it would strengthen/weaken/reverse the conclusion",
+        "suggested_action": "Concrete next step to obtain the missing evidence",
+        "priority": "high|medium|low"
+      }
+    ],
     "pivot_evidence": "Which single existing piece of evidence, if it turned out to be false, would most change the conclusion? Name it and state the direction of impact."
   },
-  "confidence_in_conclusion": 0.7
+  "confidence_in_conclusion": 0.7,
+  "conflicts": [
+    {
+      "piece_a": 0,
+      "piece_b": 1,
+      "nature": "How the two pieces contradict each other"
+    }
+  ],
+  "open_questions": ["Question the evidence raised but could not answer"]
 }
 
 Important:
 - Be rigorous about source credibility
-- Note conflicts between evidence pieces
+- Flag contradicting evidence pairs in "conflicts" using zero-based indices
+  into evidence_pieces; omit the field when the sources agree
 - gaps must state impact direction (strengthen/weaken/reverse) for each missing piece
+- each gap needs a suggested_action (how to fill it) and a priority (how urgently)
 - pivot_evidence is required: name the most fragile assumption in the evidential chain
-- Distinguish absence of evidence from evidence of absence"#
+- Distinguish absence of evidence from evidence of absence
+- List questions the evidence raised but could not answer in "open_questions"; omit
+  the field when nothing material remains open"#
 }
 
 /// Prompt for evidence mode (probabilistic operation).
@@ -82,7 +103,9 @@ Respond with a JSON object in this exact format:
       "evidence": "Description of evidence",
       "likelihood_if_true": 0.8,
       "likelihood_if_false": 0.2,
-      "bayes_factor": 4.0
+      "bayes_factor": 4.0,
+      "likelihood_if_true_range": [0.7, 0.9],
+      "likelihood_if_false_range": [0.1, 0.3]
     }
   ],
   "posterior": {
@@ -94,13 +117,19 @@ Respond with a JSON object in this exact format:
     "magnitude": "strong|moderate|slight",
     "interpretation": "What this means in plain language"
   },
-  "sensitivity": "How sensitive is the posterior to prior assumptions"
+  "sensitivity": "How sensitive is the posterior to prior assumptions",
+  "open_questions": ["Question the update raised but could not answer"]
 }
 
 Important:
 - Be explicit about prior assumptions
 - Bayes factor = P(E|H) / P(E|¬H)
-- Note where estimates are uncertain"#
+- Note where estimates are uncertain
+- When a likelihood estimate is uncertain, provide its plausible [low, high]
+  range in "likelihood_if_true_range"/"likelihood_if_false_range"; omit the
+  range fields when the point estimate is solid
+- List questions the update raised but could not answer in "open_questions"; omit
+  the field when nothing material remains open"#
 }
 
 #[cfg(test)]
@@ -36,25 +36,32 @@ mod detect;
 mod evidence;
 mod graph;
 mod mcts;
+mod registry;
 mod timeline;
 
 pub use core::{
     auto_select_prompt, checkpoint_create_prompt, divergent_prompt, divergent_rebellion_prompt,
-    linear_prompt, reflection_evaluate_prompt, reflection_process_prompt, tree_complete_prompt,
-    tree_create_prompt, tree_focus_prompt, tree_list_prompt,
+    divergent_single_perspective_prompt, divergent_synthesis_prompt, json_correction_prompt,
+    linear_prompt, reflection_batch_summary_prompt, reflection_evaluate_prompt,
+    reflection_process_prompt, tree_complete_prompt, tree_create_prompt, tree_focus_prompt,
+    tree_list_prompt,
 };
 pub use counterfactual::counterfactual_prompt;
 pub use decision::{
-    decision_pairwise_prompt, decision_perspectives_prompt, decision_topsis_prompt,
-    decision_weighted_prompt,
+    decision_challenge_prompt, decision_pairwise_prompt, decision_perspectives_prompt,
+    decision_topsis_prompt, decision_weighted_justification_addendum, decision_weighted_prompt,
+};
+pub use detect::{
+    detect_biases_prompt, detect_counterargue_prompt, detect_fallacies_prompt,
+    detect_knowledge_gaps_prompt, detect_premise_verification_prompt, detect_retry_addendum,
 };
-pub use detect::{detect_biases_prompt, detect_fallacies_prompt, detect_knowledge_gaps_prompt};
 pub use evidence::{evidence_assess_prompt, evidence_probabilistic_prompt};
 pub use graph::{
     graph_aggregate_prompt, graph_finalize_prompt, graph_generate_prompt, graph_init_prompt,
     graph_prune_prompt, graph_refine_prompt, graph_score_prompt, graph_state_prompt,
 };
 pub use mcts::{mcts_backtrack_prompt, mcts_explore_prompt};
+pub use registry::{PromptRegistry, ResolvedPrompt};
 pub use timeline::{
     timeline_branch_prompt, timeline_compare_prompt, timeline_create_prompt, timeline_merge_prompt,
 };
@@ -129,6 +136,111 @@ impl ReasoningMode {
         }
     }
 
+    /// Returns a one-line description of the mode, suitable for help output.
+    #[must_use]
+    pub const fn description(&self) -> &'static str {
+        match self {
+            Self::Linear => "Sequential step-by-step analysis of a single thought",
+            Self::Tree => "Branching exploration: create branches, focus one, synthesize",
+            Self::Divergent => "Multi-perspective analysis that challenges assumptions",
+            Self::Reflection => "Meta-cognitive critique of prior reasoning or a whole session",
+            Self::Checkpoint => "Save and restore reasoning state snapshots",
+            Self::Auto => "Selects the best reasoning mode for the content",
+            Self::Graph => "Graph-of-Thoughts: generate, score, and refine a node graph",
+            Self::Detect => "Detects cognitive biases and logical fallacies",
+            Self::Decision => {
+                "Multi-criteria decision analysis (weighted, pairwise, TOPSIS, perspectives)"
+            }
+            Self::Evidence => "Evidence credibility assessment and Bayesian belief updates",
+            Self::Timeline => "Temporal reasoning over branching event sequences",
+            Self::Mcts => "Monte Carlo Tree Search with UCB1-guided exploration",
+            Self::Counterfactual => "Causal analysis across Pearl's Ladder of Causation",
+        }
+    }
+
+    /// Returns the operations the prompt router distinguishes for this mode.
+    ///
+    /// Must stay in sync with the match arms of [`get_prompt_for_mode`]: every
+    /// operation listed here selects a prompt for the mode, and a mode with an
+    /// empty list uses a single prompt regardless of operation.
+    #[must_use]
+    pub const fn operations(&self) -> &'static [Operation] {
+        match self {
+            Self::Linear | Self::Checkpoint | Self::Auto | Self::Counterfactual => &[],
+            Self::Tree => &[
+                Operation::Create,
+                Operation::Focus,
+                Operation::List,
+                Operation::Complete,
+                Operation::Summarize,
+            ],
+            Self::Divergent => &[Operation::ForceRebellion],
+            Self::Reflection => &[Operation::Process, Operation::Evaluate],
+            Self::Graph => &[
+                Operation::Init,
+                Operation::Generate,
+                Operation::Score,
+                Operation::Aggregate,
+                Operation::Refine,
+                Operation::Prune,
+                Operation::Finalize,
+                Operation::State,
+            ],
+            Self::Detect => &[
+                Operation::Biases,
+                Operation::Fallacies,
+                Operation::Counterargue,
+            ],
+            Self::Decision => &[
+                Operation::Weighted,
+                Operation::Pairwise,
+                Operation::Topsis,
+                Operation::Perspectives,
+            ],
+            Self::Evidence => &[Operation::Assess, Operation::Probabilistic],
+            Self::Timeline => &[
+                Operation::TimelineCreate,
+                Operation::Branch,
+                Operation::Compare,
+                Operation::Merge,
+            ],
+            Self::Mcts => &[Operation::Explore, Operation::AutoBacktrack],
+        }
+    }
+
+    /// Returns the operation that runs when a request omits one.
+    ///
+    /// Must stay in sync with the `None` arms of [`get_prompt_for_mode`]: this
+    /// is the same default the prompt router quietly picks, made explicit so
+    /// the server can report which operation actually ran. `None` means the
+    /// mode has no operations (or, for divergent, that its bare form is not an
+    /// operation at all).
+    #[must_use]
+    pub const fn default_operation(&self) -> Option<Operation> {
+        match self {
+            Self::Linear
+            | Self::Divergent
+            | Self::Checkpoint
+            | Self::Auto
+            | Self::Counterfactual => None,
+            Self::Tree => Some(Operation::Create),
+            Self::Reflection => Some(Operation::Process),
+            Self::Graph => Some(Operation::Init),
+            Self::Detect => Some(Operation::Biases),
+            Self::Decision => Some(Operation::Weighted),
+            Self::Evidence => Some(Operation::Assess),
+            Self::Timeline => Some(Operation::TimelineCreate),
+            Self::Mcts => Some(Operation::Explore),
+        }
+    }
+
+    /// Resolves the operation string a request actually runs: the requested
+    /// operation when given, otherwise this mode's [`Self::default_operation`].
+    #[must_use]
+    pub fn resolve_operation<'a>(&self, requested: Option<&'a str>) -> Option<&'a str> {
+        requested.or_else(|| self.default_operation().map(|op| op.as_str()))
+    }
+
     /// Returns the mode name as a string.
     #[must_use]
     pub const fn as_str(&self) -> &'static str {
@@ -290,6 +402,8 @@ pub enum Operation {
     Biases,
     /// Detect: Find fallacies.
     Fallacies,
+    /// Detect: Generate steelmanned counterarguments.
+    Counterargue,
     /// Decision: Weighted scoring.
     Weighted,
     /// Decision: Pairwise comparison.
@@ -339,6 +453,7 @@ impl Operation {
             Self::State => "state",
             Self::Biases => "biases",
             Self::Fallacies => "fallacies",
+            Self::Counterargue => "counterargue",
             Self::Weighted => "weighted",
             Self::Pairwise => "pairwise",
             Self::Topsis => "topsis",
@@ -421,6 +536,7 @@ pub fn get_prompt_for_mode(mode: ReasoningMode, operation: Option<&Operation>) -
 
         // Detect mode
         (ReasoningMode::Detect, Some(Operation::Fallacies)) => detect_fallacies_prompt(),
+        (ReasoningMode::Detect, Some(Operation::Counterargue)) => detect_counterargue_prompt(),
         (ReasoningMode::Detect, _) => detect_biases_prompt(),
 
         // Decision mode
@@ -612,6 +728,142 @@ mod tests {
         assert_eq!(ReasoningMode::Mcts.thinking_budget(), Some(16384));
     }
 
+    #[test]
+    fn test_reasoning_mode_description_not_empty() {
+        for mode in ReasoningMode::all() {
+            assert!(
+                !mode.description().is_empty(),
+                "description missing for {mode}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_reasoning_mode_operations_match_router() {
+        // The operation lists must mirror the `get_prompt_for_mode` match arms.
+        fn names(mode: ReasoningMode) -> Vec<&'static str> {
+            mode.operations().iter().map(Operation::as_str).collect()
+        }
+
+        assert!(names(ReasoningMode::Linear).is_empty());
+        assert!(names(ReasoningMode::Checkpoint).is_empty());
+        assert!(names(ReasoningMode::Auto).is_empty());
+        assert!(names(ReasoningMode::Counterfactual).is_empty());
+        assert_eq!(
+            names(ReasoningMode::Tree),
+            ["create", "focus", "list", "complete", "summarize"]
+        );
+        assert_eq!(names(ReasoningMode::Divergent), ["force_rebellion"]);
+        assert_eq!(names(ReasoningMode::Reflection), ["process", "evaluate"]);
+        assert_eq!(
+            names(ReasoningMode::Graph),
+            [
+                "init",
+                "generate",
+                "score",
+                "aggregate",
+                "refine",
+                "prune",
+                "finalize",
+                "state"
+            ]
+        );
+        assert_eq!(
+            names(ReasoningMode::Detect),
+            ["biases", "fallacies", "counterargue"]
+        );
+        assert_eq!(
+            names(ReasoningMode::Decision),
+            ["weighted", "pairwise", "topsis", "perspectives"]
+        );
+        assert_eq!(names(ReasoningMode::Evidence), ["assess", "probabilistic"]);
+        assert_eq!(
+            names(ReasoningMode::Timeline),
+            ["create", "branch", "compare", "merge"]
+        );
+        assert_eq!(names(ReasoningMode::Mcts), ["explore", "auto_backtrack"]);
+    }
+
+    #[test]
+    fn test_reasoning_mode_operations_route_to_prompts() {
+        for mode in ReasoningMode::all() {
+            for op in mode.operations() {
+                let prompt = get_prompt_for_mode(*mode, Some(op));
+                assert!(!prompt.is_empty(), "no prompt for {mode}/{op}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_default_operation_per_mode() {
+        assert_eq!(ReasoningMode::Linear.default_operation(), None);
+        assert_eq!(
+            ReasoningMode::Tree.default_operation(),
+            Some(Operation::Create)
+        );
+        assert_eq!(ReasoningMode::Divergent.default_operation(), None);
+        assert_eq!(
+            ReasoningMode::Reflection.default_operation(),
+            Some(Operation::Process)
+        );
+        assert_eq!(ReasoningMode::Checkpoint.default_operation(), None);
+        assert_eq!(ReasoningMode::Auto.default_operation(), None);
+        assert_eq!(
+            ReasoningMode::Graph.default_operation(),
+            Some(Operation::Init)
+        );
+        assert_eq!(
+            ReasoningMode::Detect.default_operation(),
+            Some(Operation::Biases)
+        );
+        assert_eq!(
+            ReasoningMode::Decision.default_operation(),
+            Some(Operation::Weighted)
+        );
+        assert_eq!(
+            ReasoningMode::Evidence.default_operation(),
+            Some(Operation::Assess)
+        );
+        assert_eq!(
+            ReasoningMode::Timeline.default_operation(),
+            Some(Operation::TimelineCreate)
+        );
+        assert_eq!(
+            ReasoningMode::Mcts.default_operation(),
+            Some(Operation::Explore)
+        );
+        assert_eq!(ReasoningMode::Counterfactual.default_operation(), None);
+    }
+
+    #[test]
+    fn test_default_operation_matches_router_default() {
+        // The explicit default must select the same prompt the router picks
+        // when no operation is given.
+        for mode in ReasoningMode::all() {
+            if let Some(default) = mode.default_operation() {
+                assert_eq!(
+                    get_prompt_for_mode(*mode, Some(&default)),
+                    get_prompt_for_mode(*mode, None),
+                    "default for {mode} diverges from the router's None arm"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_resolve_operation_prefers_requested() {
+        assert_eq!(
+            ReasoningMode::Decision.resolve_operation(Some("topsis")),
+            Some("topsis")
+        );
+        assert_eq!(
+            ReasoningMode::Decision.resolve_operation(None),
+            Some("weighted")
+        );
+        assert_eq!(ReasoningMode::Tree.resolve_operation(None), Some("create"));
+        assert_eq!(ReasoningMode::Linear.resolve_operation(None), None);
+    }
+
     // ParseModeError tests
     #[test]
     fn test_parse_mode_error_display() {
@@ -653,6 +905,7 @@ mod tests {
         assert_eq!(Operation::State.as_str(), "state");
         assert_eq!(Operation::Biases.as_str(), "biases");
         assert_eq!(Operation::Fallacies.as_str(), "fallacies");
+        assert_eq!(Operation::Counterargue.as_str(), "counterargue");
         assert_eq!(Operation::Weighted.as_str(), "weighted");
         assert_eq!(Operation::Pairwise.as_str(), "pairwise");
         assert_eq!(Operation::Topsis.as_str(), "topsis");
@@ -770,6 +1023,9 @@ mod tests {
 
         let prompt = get_prompt_for_mode(ReasoningMode::Detect, Some(&Operation::Fallacies));
         assert!(prompt.contains("fallacies"));
+
+        let prompt = get_prompt_for_mode(ReasoningMode::Detect, Some(&Operation::Counterargue));
+        assert!(prompt.contains("counterarguments"));
     }
 
     #[test]
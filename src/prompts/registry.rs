@@ -0,0 +1,228 @@
+//! Named prompt versions for A/B testing alternative phrasings.
+//!
+//! The built-in prompts in this module's siblings are the defaults; the
+//! registry lets maintainers register alternative named versions per
+//! mode/operation and select one per call or process-wide (`PROMPT_VERSION`
+//! config). The version actually applied is recorded on the produced thought
+//! so result quality can be compared across phrasings later.
+
+use std::borrow::Cow;
+use std::sync::Mutex;
+
+use super::{get_prompt_for_mode, Operation, ReasoningMode};
+
+/// A registered prompt version: `key` scopes it to one mode/operation.
+struct VersionEntry {
+    key: String,
+    name: String,
+    text: String,
+}
+
+/// Registered prompt versions. A `Vec` scanned linearly: the registry holds a
+/// handful of hand-registered entries, resolved once per tool call.
+static VERSIONS: Mutex<Vec<VersionEntry>> = Mutex::new(Vec::new());
+
+/// Process-wide default version (`PROMPT_VERSION` config), consulted when a
+/// call does not request a version explicitly.
+static DEFAULT_VERSION: Mutex<Option<String>> = Mutex::new(None);
+
+/// A prompt resolved through the registry.
+///
+/// `version` is the name of the registered version that was applied, or
+/// `None` when the built-in prompt was used — the value modes record on the
+/// produced thought.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedPrompt {
+    /// The prompt text to use.
+    pub text: Cow<'static, str>,
+    /// Name of the applied registered version; `None` for the built-in.
+    pub version: Option<String>,
+}
+
+/// Process-wide registry of named prompt versions per mode/operation.
+///
+/// The registry is static state rather than a threaded parameter because
+/// prompts are built by free functions ([`get_prompt_for_mode`] and its
+/// siblings) with no constructed object to hang state on — threading a
+/// registry handle through every prompt-building call would touch every
+/// mode for the sake of a maintainer-only A/B hook. It is populated once at
+/// server startup, before any tool call, and read-only afterwards.
+pub struct PromptRegistry;
+
+impl PromptRegistry {
+    /// Scope key for one mode/operation pair.
+    fn key(mode: ReasoningMode, operation: Option<&Operation>) -> String {
+        operation.map_or_else(|| mode.to_string(), |op| format!("{mode}/{op}"))
+    }
+
+    /// Register (or replace) a named prompt version for a mode/operation.
+    pub fn register(
+        mode: ReasoningMode,
+        operation: Option<&Operation>,
+        name: &str,
+        text: impl Into<String>,
+    ) {
+        let key = Self::key(mode, operation);
+        let mut versions = VERSIONS
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(entry) = versions.iter_mut().find(|e| e.key == key && e.name == name) {
+            entry.text = text.into();
+        } else {
+            versions.push(VersionEntry {
+                key,
+                name: name.to_string(),
+                text: text.into(),
+            });
+        }
+    }
+
+    /// Set the process-wide default version, normally once at server startup
+    /// from the `PROMPT_VERSION` config. `None` (the default) selects the
+    /// built-in prompts.
+    pub fn set_default_version(version: Option<String>) {
+        *DEFAULT_VERSION
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = version;
+    }
+
+    /// Resolve the prompt for a mode/operation.
+    ///
+    /// A per-call `requested` version wins over the process-wide default. A
+    /// selected version with no registered entry for this mode/operation
+    /// falls back to the built-in prompt (with a warning) rather than
+    /// failing the call, and is not recorded as applied.
+    #[must_use]
+    pub fn resolve(
+        mode: ReasoningMode,
+        operation: Option<&Operation>,
+        requested: Option<&str>,
+    ) -> ResolvedPrompt {
+        let selected = requested.map(str::to_string).or_else(|| {
+            DEFAULT_VERSION
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .clone()
+        });
+
+        if let Some(name) = selected.filter(|n| !n.trim().is_empty()) {
+            let key = Self::key(mode, operation);
+            let registered = {
+                let versions = VERSIONS
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner);
+                versions
+                    .iter()
+                    .find(|e| e.key == key && e.name == name)
+                    .map(|e| e.text.clone())
+            };
+            if let Some(text) = registered {
+                return ResolvedPrompt {
+                    text: Cow::Owned(text),
+                    version: Some(name),
+                };
+            }
+            tracing::warn!(
+                version = %name,
+                mode = %mode,
+                "Prompt version not registered for this mode — using the built-in prompt"
+            );
+        }
+
+        ResolvedPrompt {
+            text: Cow::Borrowed(get_prompt_for_mode(mode, operation)),
+            version: None,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp,
+    clippy::approx_constant,
+    clippy::unreadable_literal
+)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_without_selection_uses_builtin() {
+        let resolved = PromptRegistry::resolve(ReasoningMode::Linear, None, None);
+        assert_eq!(resolved.version, None);
+        assert_eq!(
+            resolved.text,
+            get_prompt_for_mode(ReasoningMode::Linear, None)
+        );
+    }
+
+    #[test]
+    fn test_resolve_requested_version_wins() {
+        PromptRegistry::register(
+            ReasoningMode::Linear,
+            None,
+            "registry-test-terse",
+            "Terse variant.",
+        );
+        let resolved =
+            PromptRegistry::resolve(ReasoningMode::Linear, None, Some("registry-test-terse"));
+        assert_eq!(resolved.version.as_deref(), Some("registry-test-terse"));
+        assert_eq!(resolved.text, "Terse variant.");
+    }
+
+    #[test]
+    fn test_resolve_unknown_version_falls_back_to_builtin() {
+        let resolved =
+            PromptRegistry::resolve(ReasoningMode::Linear, None, Some("registry-test-missing"));
+        assert_eq!(resolved.version, None);
+        assert_eq!(
+            resolved.text,
+            get_prompt_for_mode(ReasoningMode::Linear, None)
+        );
+    }
+
+    #[test]
+    fn test_register_replaces_same_name() {
+        PromptRegistry::register(ReasoningMode::Linear, None, "registry-test-replace", "One.");
+        PromptRegistry::register(ReasoningMode::Linear, None, "registry-test-replace", "Two.");
+        let resolved =
+            PromptRegistry::resolve(ReasoningMode::Linear, None, Some("registry-test-replace"));
+        assert_eq!(resolved.text, "Two.");
+    }
+
+    #[test]
+    fn test_versions_are_scoped_per_operation() {
+        PromptRegistry::register(
+            ReasoningMode::Tree,
+            Some(&Operation::Create),
+            "registry-test-scoped",
+            "Tree create variant.",
+        );
+        // The same name resolves nothing for a different operation.
+        let resolved = PromptRegistry::resolve(
+            ReasoningMode::Tree,
+            Some(&Operation::List),
+            Some("registry-test-scoped"),
+        );
+        assert_eq!(resolved.version, None);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_default_version_applies_when_call_omits_one() {
+        PromptRegistry::register(
+            ReasoningMode::Linear,
+            None,
+            "registry-test-default",
+            "Default variant.",
+        );
+        PromptRegistry::set_default_version(Some("registry-test-default".to_string()));
+        let resolved = PromptRegistry::resolve(ReasoningMode::Linear, None, None);
+        PromptRegistry::set_default_version(None);
+
+        assert_eq!(resolved.version.as_deref(), Some("registry-test-default"));
+        assert_eq!(resolved.text, "Default variant.");
+    }
+}
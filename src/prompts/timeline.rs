@@ -185,7 +185,13 @@ Respond with a JSON object in this exact format:
     }
   ],
   "synthesis": "Overall conclusions from timeline exploration",
-  "recommendations": ["Actionable next steps"]
+  "recommendations": [
+    {
+      "action": "Actionable next step",
+      "supporting_branches": ["IDs of merged branches that support it"],
+      "confidence": 0.8
+    }
+  ]
 }
 
 Important:
@@ -0,0 +1,508 @@
+//! Replay recorded API interactions through the modes.
+//!
+//! Reproducing a user-reported issue usually needs the exact model output that
+//! triggered it, not a fresh (nondeterministic) completion. This module makes
+//! that possible without network access:
+//!
+//! - [`RecordingClient`] wraps any [`AnthropicClientTrait`] and captures every
+//!   completion it returns, exportable as a JSONL log;
+//! - [`ReplayClient`] implements [`AnthropicClientTrait`] by serving a recorded
+//!   log back in order, so the same mode calls re-run deterministically;
+//! - [`replay_sequence`] runs an ordered list of completion calls against any
+//!   client and collects the responses.
+//!
+//! # Log format
+//!
+//! The log is self-contained: one JSON object per line, mirroring
+//! [`CompletionResponse`]:
+//!
+//! ```json
+//! {"content": "{\"analysis\": \"...\", \"confidence\": 0.8}", "input_tokens": 100, "output_tokens": 50}
+//! ```
+//!
+//! Token fields default to zero, so hand-written logs only need `content`.
+//! Blank lines are skipped. Order matters — responses are served strictly in
+//! log order, one per `complete` call, and a call past the end of the log is an
+//! error rather than a silent fallback (a replay that invents output would no
+//! longer reproduce anything).
+//!
+//! Streaming calls are supported on replay (the next record is synthesized into
+//! a start/delta/stop event sequence) but are **not** captured by
+//! [`RecordingClient`], whose recording hook sits on the accumulated response —
+//! stream events are consumed by the caller, not the wrapper.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::mpsc;
+
+use crate::anthropic::{ApiUsage, StreamEvent};
+use crate::error::ModeError;
+use crate::traits::{AnthropicClientTrait, CompletionConfig, CompletionResponse, Message, Usage};
+
+/// One recorded completion: a single line of the JSONL log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayRecord {
+    /// The completion text the API returned.
+    pub content: String,
+    /// Input tokens reported for the call (zero when unknown).
+    #[serde(default)]
+    pub input_tokens: u32,
+    /// Output tokens reported for the call (zero when unknown).
+    #[serde(default)]
+    pub output_tokens: u32,
+}
+
+impl From<&CompletionResponse> for ReplayRecord {
+    fn from(response: &CompletionResponse) -> Self {
+        Self {
+            content: response.content.clone(),
+            input_tokens: response.usage.input_tokens,
+            output_tokens: response.usage.output_tokens,
+        }
+    }
+}
+
+impl From<ReplayRecord> for CompletionResponse {
+    fn from(record: ReplayRecord) -> Self {
+        Self::new(
+            record.content,
+            Usage::new(record.input_tokens, record.output_tokens),
+        )
+    }
+}
+
+/// Errors raised while loading a replay log.
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    /// A line failed to parse as a [`ReplayRecord`]. `line` is 1-based.
+    #[error("replay log line {line}: {source}")]
+    Parse {
+        /// 1-based line number of the offending record.
+        line: usize,
+        /// The underlying JSON error.
+        source: serde_json::Error,
+    },
+    /// The log file could not be read.
+    #[error("failed to read replay log: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// A stub [`AnthropicClientTrait`] that serves recorded responses in order.
+///
+/// Each `complete` (or `complete_streaming`) call consumes the next recorded
+/// response; a call after the log is exhausted returns
+/// [`ModeError::ApiUnavailable`] naming how many responses the log held.
+#[derive(Debug)]
+pub struct ReplayClient {
+    responses: Mutex<VecDeque<CompletionResponse>>,
+    recorded_len: usize,
+}
+
+impl ReplayClient {
+    /// Build a replay client from responses already in memory (e.g. captured by
+    /// a [`RecordingClient`] in the same test).
+    #[must_use]
+    pub fn from_responses(responses: Vec<CompletionResponse>) -> Self {
+        let recorded_len = responses.len();
+        Self {
+            responses: Mutex::new(responses.into()),
+            recorded_len,
+        }
+    }
+
+    /// Parse a JSONL log: one [`ReplayRecord`] per non-blank line.
+    ///
+    /// Returns [`ReplayError::Parse`] (with the 1-based line number) on the
+    /// first malformed record. An empty log is valid — it just means any
+    /// completion call will report exhaustion.
+    pub fn from_jsonl(log: &str) -> Result<Self, ReplayError> {
+        let mut responses = Vec::new();
+        for (idx, line) in log.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            let record: ReplayRecord =
+                serde_json::from_str(trimmed).map_err(|source| ReplayError::Parse {
+                    line: idx + 1,
+                    source,
+                })?;
+            responses.push(record.into());
+        }
+        Ok(Self::from_responses(responses))
+    }
+
+    /// Load and parse a JSONL log file.
+    pub fn from_jsonl_file(path: impl AsRef<std::path::Path>) -> Result<Self, ReplayError> {
+        let log = std::fs::read_to_string(path)?;
+        Self::from_jsonl(&log)
+    }
+
+    /// Number of recorded responses not yet served.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.responses
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    fn next_response(&self) -> Result<CompletionResponse, ModeError> {
+        self.responses
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pop_front()
+            .ok_or_else(|| ModeError::ApiUnavailable {
+                message: format!(
+                    "replay log exhausted: all {} recorded responses served",
+                    self.recorded_len
+                ),
+            })
+    }
+}
+
+#[async_trait]
+impl AnthropicClientTrait for ReplayClient {
+    async fn complete(
+        &self,
+        _messages: Vec<Message>,
+        _config: CompletionConfig,
+    ) -> Result<CompletionResponse, ModeError> {
+        self.next_response()
+    }
+
+    async fn complete_streaming(
+        &self,
+        _messages: Vec<Message>,
+        _config: CompletionConfig,
+    ) -> Result<mpsc::Receiver<Result<StreamEvent, ModeError>>, ModeError> {
+        // Synthesize the minimal event sequence a consumer accumulates back
+        // into the recorded response: start, one text delta, stop with usage.
+        let response = self.next_response()?;
+        let (tx, rx) = mpsc::channel(4);
+        tokio::spawn(async move {
+            let _ = tx
+                .send(Ok(StreamEvent::MessageStart {
+                    message_id: "replay_msg".to_string(),
+                }))
+                .await;
+            let _ = tx
+                .send(Ok(StreamEvent::TextDelta {
+                    index: 0,
+                    text: response.content,
+                }))
+                .await;
+            let _ = tx
+                .send(Ok(StreamEvent::MessageStop {
+                    stop_reason: "end_turn".to_string(),
+                    usage: ApiUsage::new(response.usage.input_tokens, response.usage.output_tokens),
+                }))
+                .await;
+        });
+        Ok(rx)
+    }
+}
+
+/// A pass-through [`AnthropicClientTrait`] that records every completion.
+///
+/// Wrap a real (or mock) client, drive the modes as usual, then export the
+/// captured interactions with [`RecordingClient::to_jsonl`] or replay them
+/// directly via [`RecordingClient::replay_client`].
+///
+/// Clones share one log (it is behind an [`Arc`]), so a clone can be moved
+/// into a mode while the original keeps access to what was recorded.
+#[derive(Debug, Clone)]
+pub struct RecordingClient<C> {
+    inner: C,
+    log: Arc<Mutex<Vec<CompletionResponse>>>,
+}
+
+impl<C> RecordingClient<C> {
+    /// Wrap `inner`, recording each completion it returns.
+    #[must_use]
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            log: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// The completions recorded so far, in call order.
+    #[must_use]
+    pub fn recorded(&self) -> Vec<CompletionResponse> {
+        self.log
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Serialize the recorded completions as a JSONL log, one
+    /// [`ReplayRecord`] per line.
+    pub fn to_jsonl(&self) -> Result<String, ModeError> {
+        let mut out = String::new();
+        for response in self.recorded() {
+            let line = serde_json::to_string(&ReplayRecord::from(&response)).map_err(|e| {
+                ModeError::JsonParseFailed {
+                    message: format!("failed to serialize replay record: {e}"),
+                }
+            })?;
+            out.push_str(&line);
+            out.push('\n');
+        }
+        Ok(out)
+    }
+
+    /// Build a [`ReplayClient`] that serves the recorded completions in order.
+    #[must_use]
+    pub fn replay_client(&self) -> ReplayClient {
+        ReplayClient::from_responses(self.recorded())
+    }
+}
+
+#[async_trait]
+impl<C: AnthropicClientTrait> AnthropicClientTrait for RecordingClient<C> {
+    async fn complete(
+        &self,
+        messages: Vec<Message>,
+        config: CompletionConfig,
+    ) -> Result<CompletionResponse, ModeError> {
+        let response = self.inner.complete(messages, config).await?;
+        self.log
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(response.clone());
+        Ok(response)
+    }
+
+    async fn complete_streaming(
+        &self,
+        messages: Vec<Message>,
+        config: CompletionConfig,
+    ) -> Result<mpsc::Receiver<Result<StreamEvent, ModeError>>, ModeError> {
+        // Forwarded without recording: the event stream is consumed by the
+        // caller, so there is no accumulated response to capture here.
+        self.inner.complete_streaming(messages, config).await
+    }
+}
+
+/// Run an ordered sequence of completion calls against `client`, collecting
+/// the responses. Stops at the first error — against a [`ReplayClient`] that
+/// includes running past the end of the log.
+pub async fn replay_sequence<C: AnthropicClientTrait>(
+    client: &C,
+    calls: Vec<(Vec<Message>, CompletionConfig)>,
+) -> Result<Vec<CompletionResponse>, ModeError> {
+    let mut responses = Vec::with_capacity(calls.len());
+    for (messages, config) in calls {
+        responses.push(client.complete(messages, config).await?);
+    }
+    Ok(responses)
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp,
+    clippy::approx_constant,
+    clippy::unreadable_literal
+)]
+mod tests {
+    use super::*;
+    use crate::doctest_helpers::{MockClient, MockStorage};
+    use crate::modes::LinearMode;
+
+    fn response(content: &str, input: u32, output: u32) -> CompletionResponse {
+        CompletionResponse::new(content, Usage::new(input, output))
+    }
+
+    #[tokio::test]
+    async fn test_replay_client_serves_in_order() {
+        let client =
+            ReplayClient::from_responses(vec![response("first", 10, 5), response("second", 20, 8)]);
+        assert_eq!(client.remaining(), 2);
+
+        let first = client
+            .complete(vec![Message::user("a")], CompletionConfig::new())
+            .await
+            .expect("first replay");
+        assert_eq!(first.content, "first");
+        assert_eq!(first.usage.input_tokens, 10);
+
+        let second = client
+            .complete(vec![Message::user("b")], CompletionConfig::new())
+            .await
+            .expect("second replay");
+        assert_eq!(second.content, "second");
+        assert_eq!(client.remaining(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_replay_client_exhausted_is_error() {
+        let client = ReplayClient::from_responses(vec![response("only", 1, 1)]);
+        client
+            .complete(vec![Message::user("a")], CompletionConfig::new())
+            .await
+            .expect("recorded response");
+
+        let err = client
+            .complete(vec![Message::user("b")], CompletionConfig::new())
+            .await
+            .expect_err("log exhausted");
+        assert!(
+            err.to_string().contains("replay log exhausted"),
+            "unexpected error: {err}"
+        );
+        assert!(err.to_string().contains('1'));
+    }
+
+    #[test]
+    fn test_from_jsonl_skips_blanks_and_defaults_usage() {
+        let log = concat!(
+            r#"{"content": "hello", "input_tokens": 7, "output_tokens": 3}"#,
+            "\n\n",
+            r#"{"content": "tokenless"}"#,
+            "\n"
+        );
+        let client = ReplayClient::from_jsonl(log).expect("parse log");
+        assert_eq!(client.remaining(), 2);
+
+        let responses = client
+            .responses
+            .lock()
+            .expect("lock")
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(responses[0], response("hello", 7, 3));
+        assert_eq!(responses[1], response("tokenless", 0, 0));
+    }
+
+    #[test]
+    fn test_from_jsonl_reports_line_number() {
+        let log = concat!(r#"{"content": "ok"}"#, "\n", "not json\n");
+        let err = ReplayClient::from_jsonl(log).expect_err("malformed line");
+        assert!(matches!(err, ReplayError::Parse { line: 2, .. }));
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[tokio::test]
+    async fn test_recording_round_trips_through_jsonl() {
+        let recorder = RecordingClient::new(MockClient::with_response("recorded body"));
+        recorder
+            .complete(vec![Message::user("a")], CompletionConfig::new())
+            .await
+            .expect("record first");
+        recorder
+            .complete(vec![Message::user("b")], CompletionConfig::new())
+            .await
+            .expect("record second");
+
+        let log = recorder.to_jsonl().expect("serialize log");
+        assert_eq!(log.lines().count(), 2);
+
+        let replay = ReplayClient::from_jsonl(&log).expect("parse log");
+        let replayed = replay
+            .complete(vec![Message::user("a")], CompletionConfig::new())
+            .await
+            .expect("replay first");
+        assert_eq!(replayed, recorder.recorded()[0]);
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay_identical_mode_output() {
+        let model_output =
+            r#"{"analysis": "Step one, then two.", "confidence": 0.82, "next_step": "Validate"}"#;
+
+        // Record a linear run against a mock client; the clone moved into the
+        // mode shares the recorder's log.
+        let recorder = RecordingClient::new(MockClient::with_response(model_output));
+        let recorded_mode = LinearMode::new(MockStorage::new(), recorder.clone());
+        let original = recorded_mode
+            .process("Analyze this", Some("sess-replay".to_string()), None)
+            .await
+            .expect("recorded run");
+
+        // Replay the captured log through a fresh mode instance.
+        let replay = recorder.replay_client();
+        let replayed_mode = LinearMode::new(MockStorage::new(), replay);
+        let replayed = replayed_mode
+            .process("Analyze this", Some("sess-replay".to_string()), None)
+            .await
+            .expect("replayed run");
+
+        // Thought IDs are freshly generated per run; everything parsed from the
+        // model output must match exactly.
+        assert_eq!(replayed.session_id, original.session_id);
+        assert_eq!(replayed.content, original.content);
+        assert_eq!(replayed.confidence, original.confidence);
+        assert_eq!(replayed.next_step, original.next_step);
+    }
+
+    #[tokio::test]
+    async fn test_replay_sequence_collects_in_order() {
+        let client =
+            ReplayClient::from_responses(vec![response("one", 1, 1), response("two", 2, 2)]);
+        let calls = vec![
+            (vec![Message::user("a")], CompletionConfig::new()),
+            (vec![Message::user("b")], CompletionConfig::new()),
+        ];
+        let responses = replay_sequence(&client, calls).await.expect("sequence");
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0].content, "one");
+        assert_eq!(responses[1].content, "two");
+    }
+
+    #[tokio::test]
+    async fn test_replay_sequence_stops_on_exhaustion() {
+        let client = ReplayClient::from_responses(vec![response("one", 1, 1)]);
+        let calls = vec![
+            (vec![Message::user("a")], CompletionConfig::new()),
+            (vec![Message::user("b")], CompletionConfig::new()),
+        ];
+        let err = replay_sequence(&client, calls)
+            .await
+            .expect_err("second call exhausts the log");
+        assert!(err.to_string().contains("replay log exhausted"));
+    }
+
+    #[tokio::test]
+    async fn test_replay_streaming_synthesizes_events() {
+        let client = ReplayClient::from_responses(vec![response("streamed body", 12, 6)]);
+        let mut rx = client
+            .complete_streaming(vec![Message::user("a")], CompletionConfig::new())
+            .await
+            .expect("streaming replay");
+
+        let start = rx.recv().await.expect("start event").expect("ok");
+        assert!(matches!(start, StreamEvent::MessageStart { .. }));
+
+        let delta = rx.recv().await.expect("delta event").expect("ok");
+        match delta {
+            StreamEvent::TextDelta { text, .. } => assert_eq!(text, "streamed body"),
+            other => panic!("expected text delta, got {other:?}"),
+        }
+
+        let stop = rx.recv().await.expect("stop event").expect("ok");
+        match stop {
+            StreamEvent::MessageStop { usage, .. } => {
+                assert_eq!(usage.input_tokens, 12);
+                assert_eq!(usage.output_tokens, 6);
+            }
+            other => panic!("expected message stop, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_replay_record_round_trip() {
+        let original = response("body", 3, 4);
+        let record = ReplayRecord::from(&original);
+        let restored: CompletionResponse = record.into();
+        assert_eq!(restored, original);
+    }
+}
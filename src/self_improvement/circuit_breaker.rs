@@ -1,8 +1,16 @@
 //! Circuit breaker for self-improvement safety.
 //!
-//! Prevents runaway changes by halting after consecutive failures.
+//! Prevents runaway changes by halting after consecutive failures. A
+//! configurable sliding window (`FAILURE_WINDOW_SECS`) expires stale
+//! consecutive-failure counts, so a transient blip hours ago doesn't leave the
+//! breaker one failure away from tripping forever.
 
-use std::time::{Duration, Instant};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::traits::{RealTimeProvider, TimeProvider};
 
 /// Circuit breaker state.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -24,6 +32,13 @@ pub struct CircuitBreakerConfig {
     pub cooldown_duration: Duration,
     /// Number of successes in half-open state to close.
     pub success_threshold: u32,
+    /// Sliding window after which a stale consecutive-failure count expires.
+    ///
+    /// When the last failure is older than this, the count resets before the
+    /// next failure is recorded, so transient blips don't accumulate toward a
+    /// trip indefinitely. `None` (the default) keeps counts until a success or
+    /// manual reset.
+    pub failure_window: Option<Duration>,
 }
 
 impl Default for CircuitBreakerConfig {
@@ -32,28 +47,70 @@ impl Default for CircuitBreakerConfig {
             failure_threshold: 3,
             cooldown_duration: Duration::from_mins(5), // 5 minutes
             success_threshold: 2,
+            failure_window: None,
+        }
+    }
+}
+
+impl CircuitBreakerConfig {
+    /// Build the default configuration with the sliding failure window taken
+    /// from `FAILURE_WINDOW_SECS` (whole seconds; unset, unparsable, or zero
+    /// leaves the window off).
+    #[must_use]
+    pub fn from_env() -> Self {
+        let failure_window = std::env::var("FAILURE_WINDOW_SECS")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs);
+        Self {
+            failure_window,
+            ..Self::default()
         }
     }
 }
 
 /// Circuit breaker for the self-improvement system.
-#[derive(Debug)]
 pub struct CircuitBreaker {
     config: CircuitBreakerConfig,
     state: CircuitState,
     consecutive_failures: u32,
     consecutive_successes: u32,
-    last_failure: Option<Instant>,
+    last_failure: Option<DateTime<Utc>>,
     total_failures: u64,
     total_successes: u64,
     trips: u64,
     divergence_trips: u64,
+    time: Arc<dyn TimeProvider>,
+}
+
+impl std::fmt::Debug for CircuitBreaker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CircuitBreaker")
+            .field("config", &self.config)
+            .field("state", &self.state)
+            .field("consecutive_failures", &self.consecutive_failures)
+            .field("consecutive_successes", &self.consecutive_successes)
+            .field("last_failure", &self.last_failure)
+            .field("total_failures", &self.total_failures)
+            .field("total_successes", &self.total_successes)
+            .field("trips", &self.trips)
+            .field("divergence_trips", &self.divergence_trips)
+            .finish_non_exhaustive()
+    }
 }
 
 impl CircuitBreaker {
-    /// Create a new circuit breaker.
+    /// Create a new circuit breaker on the system clock.
     #[must_use]
     pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self::with_time_provider(config, Arc::new(RealTimeProvider))
+    }
+
+    /// Create a circuit breaker with an injected clock, so tests can advance
+    /// time past the cooldown or failure window deterministically.
+    #[must_use]
+    pub fn with_time_provider(config: CircuitBreakerConfig, time: Arc<dyn TimeProvider>) -> Self {
         Self {
             config,
             state: CircuitState::Closed,
@@ -64,6 +121,7 @@ impl CircuitBreaker {
             total_successes: 0,
             trips: 0,
             divergence_trips: 0,
+            time,
         }
     }
 
@@ -83,11 +141,14 @@ impl CircuitBreaker {
     #[must_use]
     pub fn is_allowed(&mut self) -> bool {
         match self.state {
-            CircuitState::Closed => true,
+            CircuitState::Closed => {
+                self.expire_stale_failures();
+                true
+            }
             CircuitState::Open => {
                 // Check if cooldown has elapsed
                 if let Some(last_failure) = self.last_failure {
-                    if last_failure.elapsed() >= self.config.cooldown_duration {
+                    if self.elapsed_since(last_failure) >= self.config.cooldown_duration {
                         self.transition_to(CircuitState::HalfOpen);
                         true
                     } else {
@@ -125,10 +186,13 @@ impl CircuitBreaker {
 
     /// Record a failed operation.
     pub fn record_failure(&mut self) {
+        // A failure landing after the window expires the stale count first, so
+        // it starts a fresh run rather than extending one from long ago.
+        self.expire_stale_failures();
         self.total_failures += 1;
         self.consecutive_failures += 1;
         self.consecutive_successes = 0;
-        self.last_failure = Some(Instant::now());
+        self.last_failure = Some(self.time.now());
 
         match self.state {
             CircuitState::Closed => {
@@ -177,7 +241,7 @@ impl CircuitBreaker {
     /// Force the circuit open.
     pub fn trip(&mut self) {
         self.transition_to(CircuitState::Open);
-        self.last_failure = Some(Instant::now());
+        self.last_failure = Some(self.time.now());
         self.trips += 1;
     }
 
@@ -192,7 +256,7 @@ impl CircuitBreaker {
             total_successes: self.total_successes,
             trips: self.trips,
             divergence_trips: self.divergence_trips,
-            time_in_state: self.last_failure.map(|t| t.elapsed()),
+            time_in_state: self.last_failure.map(|t| self.elapsed_since(t)),
         }
     }
 
@@ -203,8 +267,31 @@ impl CircuitBreaker {
             return None;
         }
 
-        self.last_failure
-            .map(|last| self.config.cooldown_duration.saturating_sub(last.elapsed()))
+        self.last_failure.map(|last| {
+            self.config
+                .cooldown_duration
+                .saturating_sub(self.elapsed_since(last))
+        })
+    }
+
+    /// Expire a stale consecutive-failure count: once the sliding window has
+    /// passed since the last failure, old failures no longer count toward
+    /// tripping. Only applies while closed — an open circuit's `last_failure`
+    /// drives the cooldown instead.
+    fn expire_stale_failures(&mut self) {
+        let (Some(window), Some(last)) = (self.config.failure_window, self.last_failure) else {
+            return;
+        };
+        if self.state == CircuitState::Closed && self.elapsed_since(last) >= window {
+            self.consecutive_failures = 0;
+            self.last_failure = None;
+        }
+    }
+
+    /// Wall-clock time since `instant` per the injected clock, saturating to
+    /// zero if the clock moved backwards.
+    fn elapsed_since(&self, instant: DateTime<Utc>) -> Duration {
+        (self.time.now() - instant).to_std().unwrap_or_default()
     }
 
     fn transition_to(&mut self, new_state: CircuitState) {
@@ -264,12 +351,26 @@ pub struct CircuitBreakerStats {
 )]
 mod tests {
     use super::*;
+    use crate::traits::MockTimeProvider;
+    use serial_test::serial;
+    use std::sync::Mutex;
+
+    /// A mock clock the test can advance by writing through the returned handle.
+    fn advancing_clock(start: DateTime<Utc>) -> (Arc<Mutex<DateTime<Utc>>>, Arc<MockTimeProvider>) {
+        let now = Arc::new(Mutex::new(start));
+        let handle = Arc::clone(&now);
+        let mut mock = MockTimeProvider::new();
+        mock.expect_now()
+            .returning(move || *handle.lock().expect("clock lock"));
+        (now, Arc::new(mock))
+    }
 
     fn create_fast_config() -> CircuitBreakerConfig {
         CircuitBreakerConfig {
             failure_threshold: 2,
             cooldown_duration: Duration::from_millis(100),
             success_threshold: 1,
+            failure_window: None,
         }
     }
 
@@ -331,6 +432,7 @@ mod tests {
             failure_threshold: 1,
             cooldown_duration: Duration::from_millis(10),
             success_threshold: 1,
+            failure_window: None,
         };
         let mut cb = CircuitBreaker::new(config);
 
@@ -351,6 +453,7 @@ mod tests {
             failure_threshold: 1,
             cooldown_duration: Duration::from_millis(10),
             success_threshold: 2,
+            failure_window: None,
         };
         let mut cb = CircuitBreaker::new(config);
 
@@ -421,6 +524,7 @@ mod tests {
             failure_threshold: 1,
             cooldown_duration: Duration::from_millis(10),
             success_threshold: 1,
+            failure_window: None,
         };
         let mut cb = CircuitBreaker::new(config);
 
@@ -458,6 +562,7 @@ mod tests {
             failure_threshold: 1,
             cooldown_duration: Duration::from_millis(10),
             success_threshold: 3,
+            failure_window: None,
         };
         let mut cb = CircuitBreaker::new(config);
 
@@ -481,6 +586,90 @@ mod tests {
         assert_eq!(config.failure_threshold, 3);
         assert_eq!(config.cooldown_duration, Duration::from_mins(5));
         assert_eq!(config.success_threshold, 2);
+        assert!(config.failure_window.is_none());
+    }
+
+    #[test]
+    fn test_failure_window_resets_stale_count() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 3,
+            failure_window: Some(Duration::from_mins(1)),
+            ..Default::default()
+        };
+        let start = Utc::now();
+        let (clock, time) = advancing_clock(start);
+        let mut cb = CircuitBreaker::with_time_provider(config, time);
+
+        cb.record_failure();
+        cb.record_failure();
+        assert_eq!(cb.stats().consecutive_failures, 2);
+
+        // Advance past the window: the stale count expires and normal
+        // behavior resumes — the next failure starts a fresh run instead of
+        // tripping the breaker.
+        *clock.lock().expect("clock lock") = start + chrono::Duration::seconds(61);
+        assert!(cb.is_allowed());
+        assert_eq!(cb.stats().consecutive_failures, 0);
+
+        cb.record_failure();
+        assert_eq!(cb.state(), CircuitState::Closed);
+        assert_eq!(cb.stats().consecutive_failures, 1);
+    }
+
+    #[test]
+    fn test_failure_window_keeps_count_within_window() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 3,
+            failure_window: Some(Duration::from_mins(1)),
+            ..Default::default()
+        };
+        let start = Utc::now();
+        let (clock, time) = advancing_clock(start);
+        let mut cb = CircuitBreaker::with_time_provider(config, time);
+
+        cb.record_failure();
+        cb.record_failure();
+        // Still inside the window: the third failure trips as usual.
+        *clock.lock().expect("clock lock") = start + chrono::Duration::seconds(30);
+        cb.record_failure();
+        assert_eq!(cb.state(), CircuitState::Open);
+    }
+
+    #[test]
+    fn test_no_failure_window_keeps_count_indefinitely() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 3,
+            ..Default::default()
+        };
+        let start = Utc::now();
+        let (clock, time) = advancing_clock(start);
+        let mut cb = CircuitBreaker::with_time_provider(config, time);
+
+        cb.record_failure();
+        cb.record_failure();
+        // Without a window, failures from arbitrarily long ago still count.
+        *clock.lock().expect("clock lock") = start + chrono::Duration::days(7);
+        cb.record_failure();
+        assert_eq!(cb.state(), CircuitState::Open);
+    }
+
+    #[test]
+    #[serial]
+    fn test_failure_window_from_env() {
+        std::env::set_var("FAILURE_WINDOW_SECS", "120");
+        assert_eq!(
+            CircuitBreakerConfig::from_env().failure_window,
+            Some(Duration::from_mins(2))
+        );
+
+        // Zero and garbage both leave the window off.
+        std::env::set_var("FAILURE_WINDOW_SECS", "0");
+        assert!(CircuitBreakerConfig::from_env().failure_window.is_none());
+        std::env::set_var("FAILURE_WINDOW_SECS", "soon");
+        assert!(CircuitBreakerConfig::from_env().failure_window.is_none());
+
+        std::env::remove_var("FAILURE_WINDOW_SECS");
+        assert!(CircuitBreakerConfig::from_env().failure_window.is_none());
     }
 
     #[test]
@@ -499,6 +688,7 @@ mod tests {
             failure_threshold: 1,
             cooldown_duration: Duration::from_millis(10),
             success_threshold: 2,
+            failure_window: None,
         };
         let mut cb = CircuitBreaker::new(config);
 
@@ -552,7 +552,8 @@ impl<C: AnthropicClientTrait + Send + 'static> SelfImprovementManager<C> {
             max_actions_per_cycle: config.max_actions_per_cycle as usize,
             circuit_breaker: super::circuit_breaker::CircuitBreakerConfig {
                 failure_threshold: config.circuit_breaker_threshold,
-                ..Default::default()
+                // Picks up the FAILURE_WINDOW_SECS sliding reset when set.
+                ..super::circuit_breaker::CircuitBreakerConfig::from_env()
             },
             ..Default::default()
         };
@@ -1653,12 +1654,12 @@ mod tests {
     }
 
     // Build a single-successful-action cycle for outcome tests.
-    fn cycle_with_one_success(action: SelfImprovementAction) -> Result<CycleResult, ModeError> {
+    fn cycle_with_one_success(action: SelfImprovementAction) -> CycleResult {
         use super::super::analyzer::AnalysisResult;
         use super::super::executor::ExecutionResult;
         use super::super::monitor::MonitorResult;
         use super::super::types::SystemMetrics;
-        Ok(CycleResult {
+        CycleResult {
             monitor_result: MonitorResult {
                 metrics: SystemMetrics::new(0.9, 50.0, 12, std::collections::HashMap::new()),
                 triggers: vec![],
@@ -1678,7 +1679,7 @@ mod tests {
             learning_results: vec![],
             blocked: false,
             error: None,
-        })
+        }
     }
 
     #[tokio::test]
@@ -1700,7 +1701,7 @@ mod tests {
         let action = SelfImprovementAction::new("a1", ActionType::ConfigAdjust, "d", "r", 0.1)
             .with_parameters(serde_json::json!({ "request_timeout_ms": 45000 }));
         manager
-            .persist_cycle_audit(&cycle_with_one_success(action))
+            .persist_cycle_audit(&Ok(cycle_with_one_success(action)))
             .await
             .expect("persist");
 
@@ -1735,7 +1736,7 @@ mod tests {
 
         let action = SelfImprovementAction::new("a1", ActionType::LogObservation, "d", "r", 0.1);
         manager
-            .persist_cycle_audit(&cycle_with_one_success(action))
+            .persist_cycle_audit(&Ok(cycle_with_one_success(action)))
             .await
             .expect("persist");
 
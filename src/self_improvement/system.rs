@@ -580,7 +580,7 @@ mod tests {
             .await
             .unwrap();
 
-        let prompts = prompts.lock().expect("lock prompts");
+        let prompts = prompts.lock().expect("lock prompts").clone();
         assert_eq!(prompts.len(), 2, "expected one analyzer call per cycle");
         assert!(
             !prompts[0].contains("What past self-improvement actions taught us"),
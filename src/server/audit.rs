@@ -0,0 +1,292 @@
+//! Tool-call audit trail with argument redaction.
+//!
+//! Regulated deployments need a record of who called what with which
+//! arguments, without persisting sensitive content. Each dispatched tool call
+//! produces an [`AuditRecord`] capturing the tool name, timestamp, session id,
+//! the request's correlation id, the argument *field names* (never their
+//! values), a SHA-256 digest of the full argument object, and the call
+//! outcome. Records go to a configurable
+//! [`AuditSink`]: an append-only JSONL file or an in-process callback.
+//!
+//! The audit path is best-effort by design: a sink failure is logged to stderr
+//! and never fails the tool call itself.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// One audited tool call.
+///
+/// Argument values are never stored: `argument_fields` lists the top-level
+/// field names present in the call, and `arguments_sha256` is a one-way digest
+/// of the canonical argument JSON (enough to prove two calls carried the same
+/// payload without revealing it).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// Tool that was called (e.g. `reasoning_linear`).
+    pub tool: String,
+    /// RFC 3339 timestamp of the dispatch.
+    pub timestamp: String,
+    /// Session id from the call arguments, when present.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Correlation id the request's log lines carry (see
+    /// [`super::correlation`]).
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub correlation_id: String,
+    /// Top-level argument field names (sorted; values are never recorded).
+    pub argument_fields: Vec<String>,
+    /// SHA-256 hex digest of the canonical argument JSON.
+    pub arguments_sha256: String,
+    /// Whether the call completed successfully.
+    pub success: bool,
+}
+
+impl AuditRecord {
+    /// Build a record from a dispatched call's arguments and outcome.
+    ///
+    /// `arguments` is the raw JSON object the client sent; only its field
+    /// names and a digest are retained. The `session_id` field is treated as
+    /// an identifier (not content) and is recorded verbatim when it is a
+    /// string.
+    #[must_use]
+    pub fn new(
+        tool: impl Into<String>,
+        correlation_id: impl Into<String>,
+        arguments: Option<&serde_json::Map<String, serde_json::Value>>,
+        success: bool,
+    ) -> Self {
+        let mut argument_fields: Vec<String> =
+            arguments.map_or_else(Vec::new, |args| args.keys().cloned().collect());
+        argument_fields.sort_unstable();
+
+        let session_id = arguments
+            .and_then(|args| args.get("session_id"))
+            .and_then(|v| v.as_str())
+            .map(ToString::to_string);
+
+        // serde_json preserves object key order; hashing the serialized form
+        // of the map as sent keeps the digest stable for identical payloads.
+        let canonical = arguments.map_or_else(
+            || "null".to_string(),
+            |args| serde_json::Value::Object(args.clone()).to_string(),
+        );
+        let arguments_sha256 = format!("{:x}", Sha256::digest(canonical.as_bytes()));
+
+        Self {
+            tool: tool.into(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            session_id,
+            correlation_id: correlation_id.into(),
+            argument_fields,
+            arguments_sha256,
+            success,
+        }
+    }
+}
+
+/// Callback sink signature. Receives each record as it is produced.
+pub type AuditCallback = Arc<dyn Fn(AuditRecord) + Send + Sync>;
+
+/// Where audit records are delivered.
+#[derive(Clone)]
+pub enum AuditSink {
+    /// Append each record as one JSON line to the file at this path.
+    File(PathBuf),
+    /// Invoke a callback with each record (tests, embedded collectors).
+    Callback(AuditCallback),
+}
+
+impl std::fmt::Debug for AuditSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::File(path) => f.debug_tuple("File").field(path).finish(),
+            Self::Callback(_) => f.debug_tuple("Callback").field(&"<fn>").finish(),
+        }
+    }
+}
+
+/// The audit log attached to the server's dispatch path.
+#[derive(Debug)]
+pub struct AuditLog {
+    sink: AuditSink,
+    /// Serializes file appends so concurrent tool calls cannot interleave lines.
+    file_lock: Mutex<()>,
+}
+
+impl AuditLog {
+    /// Create an audit log writing to `sink`.
+    #[must_use]
+    pub fn new(sink: AuditSink) -> Self {
+        Self {
+            sink,
+            file_lock: Mutex::new(()),
+        }
+    }
+
+    /// Build the audit log from the environment, if configured.
+    ///
+    /// Reads `AUDIT_LOG_PATH`; when set and non-empty, records are appended as
+    /// JSONL to that file. Unset means auditing is off (the default).
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        std::env::var("AUDIT_LOG_PATH")
+            .ok()
+            .filter(|p| !p.trim().is_empty())
+            .map(|p| Self::new(AuditSink::File(PathBuf::from(p))))
+    }
+
+    /// Record one dispatched tool call. Never fails the call: sink errors are
+    /// logged and swallowed.
+    pub fn record(
+        &self,
+        tool: &str,
+        correlation_id: &str,
+        arguments: Option<&serde_json::Map<String, serde_json::Value>>,
+        success: bool,
+    ) {
+        let record = AuditRecord::new(tool, correlation_id, arguments, success);
+        match &self.sink {
+            AuditSink::File(path) => {
+                let line = match serde_json::to_string(&record) {
+                    Ok(line) => line,
+                    Err(e) => {
+                        tracing::error!(error = %e, "Failed to serialize audit record");
+                        return;
+                    }
+                };
+                let _guard = self.file_lock.lock();
+                let result = std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .and_then(|mut f| writeln!(f, "{line}"));
+                if let Err(e) = result {
+                    tracing::error!(error = %e, path = %path.display(), "Failed to write audit record");
+                }
+            }
+            AuditSink::Callback(callback) => callback(record),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex as StdMutex;
+
+    fn args(json: &serde_json::Value) -> serde_json::Map<String, serde_json::Value> {
+        json.as_object().expect("object").clone()
+    }
+
+    #[test]
+    fn test_record_captures_tool_and_field_names() {
+        let arguments = args(&serde_json::json!({
+            "content": "super secret business plan",
+            "session_id": "sess-1",
+            "mode": "linear"
+        }));
+        let record = AuditRecord::new("reasoning_linear", "corr-1", Some(&arguments), true);
+
+        assert_eq!(record.tool, "reasoning_linear");
+        assert_eq!(record.session_id, Some("sess-1".to_string()));
+        assert_eq!(
+            record.argument_fields,
+            vec!["content", "mode", "session_id"]
+        );
+        assert!(record.success);
+        assert!(!record.timestamp.is_empty());
+    }
+
+    #[test]
+    fn test_record_hashes_content_not_plain() {
+        let arguments = args(&serde_json::json!({
+            "content": "super secret business plan"
+        }));
+        let record = AuditRecord::new("reasoning_linear", "corr-1", Some(&arguments), true);
+
+        // The content value must appear nowhere in the serialized record —
+        // only field names and a fixed-width hex digest.
+        let serialized = serde_json::to_string(&record).unwrap();
+        assert!(!serialized.contains("super secret business plan"));
+        assert_eq!(record.arguments_sha256.len(), 64);
+        assert!(record
+            .arguments_sha256
+            .chars()
+            .all(|c| c.is_ascii_hexdigit()));
+    }
+
+    #[test]
+    fn test_record_digest_is_stable_and_payload_sensitive() {
+        let a = args(&serde_json::json!({"content": "one"}));
+        let b = args(&serde_json::json!({"content": "two"}));
+
+        let first = AuditRecord::new("t", "corr-1", Some(&a), true);
+        let again = AuditRecord::new("t", "corr-1", Some(&a), false);
+        let other = AuditRecord::new("t", "corr-1", Some(&b), true);
+
+        assert_eq!(first.arguments_sha256, again.arguments_sha256);
+        assert_ne!(first.arguments_sha256, other.arguments_sha256);
+    }
+
+    #[test]
+    fn test_record_without_arguments() {
+        let record = AuditRecord::new("reasoning_metrics", "corr-2", None, false);
+        assert!(record.argument_fields.is_empty());
+        assert!(record.session_id.is_none());
+        assert!(!record.success);
+    }
+
+    #[test]
+    fn test_callback_sink_receives_records() {
+        let seen: Arc<StdMutex<Vec<AuditRecord>>> = Arc::new(StdMutex::new(Vec::new()));
+        let seen_clone = Arc::clone(&seen);
+        let log = AuditLog::new(AuditSink::Callback(Arc::new(move |record| {
+            seen_clone.lock().unwrap().push(record);
+        })));
+
+        let arguments = args(&serde_json::json!({"content": "hidden", "session_id": "s1"}));
+        log.record("reasoning_tree", "corr-3", Some(&arguments), true);
+
+        let seen = seen.lock().unwrap().clone();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(seen[0].tool, "reasoning_tree");
+        assert_eq!(seen[0].argument_fields, vec!["content", "session_id"]);
+    }
+
+    #[test]
+    fn test_file_sink_appends_jsonl() {
+        let dir = std::env::temp_dir().join(format!("audit-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("audit.jsonl");
+        let log = AuditLog::new(AuditSink::File(path.clone()));
+
+        let arguments = args(&serde_json::json!({"content": "classified"}));
+        log.record("reasoning_linear", "corr-4", Some(&arguments), true);
+        log.record("reasoning_linear", "corr-4", Some(&arguments), false);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let first: AuditRecord = serde_json::from_str(lines[0]).unwrap();
+        assert!(first.success);
+        assert!(!contents.contains("classified"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_from_env_unset_is_off() {
+        // The variable is not set in the test environment.
+        assert!(AuditLog::from_env().is_none());
+    }
+
+    #[test]
+    fn test_sink_debug_redacts_callback() {
+        let sink = AuditSink::Callback(Arc::new(|_| {}));
+        assert!(format!("{sink:?}").contains("<fn>"));
+    }
+}
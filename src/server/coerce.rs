@@ -0,0 +1,169 @@
+//! Pre-dispatch argument coercion for `tools/call`.
+//!
+//! Clients sometimes send numbers as JSON strings (`"num_branches": "3"`) or
+//! spell out omitted optionals as explicit nulls. Both would be rejected by
+//! strict deserialization even though the intent is unambiguous, so
+//! [`coerce_arguments`] normalizes them against the tool's own input schema
+//! before dispatch:
+//!
+//! - a string argument whose schema property is numeric is parsed into a
+//!   number (left untouched when it doesn't parse, so deserialization still
+//!   rejects it with a clear type error);
+//! - an explicit `null` for an optional property is removed, exactly as if
+//!   the client had omitted the field (`null` for a *required* property is
+//!   kept, so the caller is told the field is wrong rather than missing).
+//!
+//! Like `format`/`select`/sticky sessions, this runs in `call_tool` (in
+//! [`super::tools`]) as a uniform transformation of the raw arguments —
+//! genuinely wrong types still reach the deserializer and fail with its
+//! message; nothing here invents values the client didn't send.
+
+use rmcp::model::JsonObject;
+use serde_json::Value;
+
+/// Normalize common client argument mismatches in place, guided by the tool's
+/// input schema.
+pub fn coerce_arguments(schema: &JsonObject, args: &mut JsonObject) {
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return;
+    };
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    // null → absent for optional properties.
+    args.retain(|key, value| !value.is_null() || required.contains(&key.as_str()));
+
+    // string → number where the schema says the property is numeric.
+    for (key, value) in args.iter_mut() {
+        let Some(property) = properties.get(key) else {
+            continue;
+        };
+        if !property_is_numeric(property) {
+            continue;
+        }
+        if let Value::String(text) = value {
+            if let Ok(parsed) = text.trim().parse::<serde_json::Number>() {
+                tracing::debug!(argument = %key, "Coerced string argument to number");
+                *value = Value::Number(parsed);
+            }
+        }
+    }
+}
+
+/// Whether a schema property accepts a numeric value, looking through the
+/// `type` keyword (string or array form) and `anyOf`/`oneOf` alternatives
+/// (how schemars encodes `Option<f64>` and friends).
+fn property_is_numeric(property: &Value) -> bool {
+    match property.get("type") {
+        Some(Value::String(ty)) => return ty == "number" || ty == "integer",
+        Some(Value::Array(types))
+            if types
+                .iter()
+                .filter_map(Value::as_str)
+                .any(|ty| ty == "number" || ty == "integer") =>
+        {
+            return true;
+        }
+        _ => {}
+    }
+    ["anyOf", "oneOf"]
+        .iter()
+        .filter_map(|key| property.get(key))
+        .filter_map(Value::as_array)
+        .flatten()
+        .any(property_is_numeric)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn tree_schema() -> JsonObject {
+        serde_json::from_value(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "operation": {"type": "string"},
+                "content": {"type": ["string", "null"]},
+                "session_id": {"type": ["string", "null"]},
+                "num_branches": {"type": ["integer", "null"], "format": "uint32"},
+                "confidence": {"anyOf": [{"type": "number"}, {"type": "null"}]}
+            },
+            "required": ["operation"]
+        }))
+        .expect("valid schema")
+    }
+
+    fn args(value: serde_json::Value) -> JsonObject {
+        serde_json::from_value(value).expect("object args")
+    }
+
+    #[test]
+    fn test_string_number_is_coerced() {
+        let mut arguments = args(serde_json::json!({
+            "operation": "create",
+            "num_branches": "3"
+        }));
+        coerce_arguments(&tree_schema(), &mut arguments);
+        assert_eq!(arguments["num_branches"], serde_json::json!(3));
+    }
+
+    #[test]
+    fn test_anyof_numeric_property_is_coerced() {
+        let mut arguments = args(serde_json::json!({
+            "operation": "create",
+            "confidence": "0.75"
+        }));
+        coerce_arguments(&tree_schema(), &mut arguments);
+        assert_eq!(arguments["confidence"], serde_json::json!(0.75));
+    }
+
+    #[test]
+    fn test_unparseable_string_is_left_for_the_deserializer() {
+        let mut arguments = args(serde_json::json!({
+            "operation": "create",
+            "num_branches": "lots"
+        }));
+        coerce_arguments(&tree_schema(), &mut arguments);
+        assert_eq!(arguments["num_branches"], serde_json::json!("lots"));
+    }
+
+    #[test]
+    fn test_string_property_is_never_touched() {
+        let mut arguments = args(serde_json::json!({
+            "operation": "create",
+            "content": "42"
+        }));
+        coerce_arguments(&tree_schema(), &mut arguments);
+        assert_eq!(arguments["content"], serde_json::json!("42"));
+    }
+
+    #[test]
+    fn test_null_optional_is_removed() {
+        let mut arguments = args(serde_json::json!({
+            "operation": "create",
+            "session_id": null
+        }));
+        coerce_arguments(&tree_schema(), &mut arguments);
+        assert!(!arguments.contains_key("session_id"));
+    }
+
+    #[test]
+    fn test_null_required_is_kept_for_rejection() {
+        let mut arguments = args(serde_json::json!({
+            "operation": null
+        }));
+        coerce_arguments(&tree_schema(), &mut arguments);
+        assert!(arguments["operation"].is_null());
+    }
+
+    #[test]
+    fn test_schema_without_properties_is_a_no_op() {
+        let mut arguments = args(serde_json::json!({"anything": "3"}));
+        coerce_arguments(&JsonObject::new(), &mut arguments);
+        assert_eq!(arguments["anything"], serde_json::json!("3"));
+    }
+}
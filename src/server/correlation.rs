@@ -0,0 +1,134 @@
+//! Per-request correlation ids for log tracing.
+//!
+//! Debugging a single request across the async tasks it spawns needs one id
+//! that every log line shares. Each dispatched tool call derives a correlation
+//! id from its JSON-RPC request id (or generates one when the id is unusable)
+//! and enters a `tracing` span carrying it, so every log line emitted while
+//! the request is handled — including those from the Anthropic client calls it
+//! makes — can be grepped by the same id. The audit log records it too.
+
+use rmcp::model::NumberOrString;
+
+/// Derive the correlation id for a request from its JSON-RPC id.
+///
+/// A numeric id becomes `req-<n>`; a string id is used verbatim (a compliant
+/// client already keeps it unique per in-flight request). A blank string id —
+/// legal JSON-RPC but useless for correlation — falls back to a generated
+/// UUID.
+#[must_use]
+pub fn correlation_id(request_id: &NumberOrString) -> String {
+    match request_id {
+        NumberOrString::Number(n) => format!("req-{n}"),
+        NumberOrString::String(s) if !s.trim().is_empty() => s.to_string(),
+        NumberOrString::String(_) => uuid::Uuid::new_v4().to_string(),
+    }
+}
+
+/// Build the span a tool call is dispatched inside.
+///
+/// Carries the tool name and the request's correlation id as span fields, so
+/// a `tracing` subscriber that prints span context stamps both onto every log
+/// line emitted within the call.
+#[must_use]
+pub fn request_span(tool: &str, request_id: &NumberOrString) -> tracing::Span {
+    tracing::info_span!(
+        "request",
+        tool = %tool,
+        correlation_id = %correlation_id(request_id)
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_numeric_id_maps_to_req_prefix() {
+        assert_eq!(correlation_id(&NumberOrString::Number(7)), "req-7");
+    }
+
+    #[test]
+    fn test_string_id_used_verbatim() {
+        assert_eq!(
+            correlation_id(&NumberOrString::String("abc-123".into())),
+            "abc-123"
+        );
+    }
+
+    #[test]
+    fn test_blank_string_id_generates_uuid() {
+        let id = correlation_id(&NumberOrString::String("  ".into()));
+        assert!(uuid::Uuid::parse_str(&id).is_ok());
+    }
+
+    /// `MakeWriter` capturing formatted log output for assertions.
+    #[derive(Clone)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for Capture {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    fn capturing_subscriber(buffer: &Arc<Mutex<Vec<u8>>>) -> impl tracing::Subscriber {
+        tracing_subscriber::fmt()
+            .with_writer(Capture(Arc::clone(buffer)))
+            .with_ansi(false)
+            .finish()
+    }
+
+    #[test]
+    fn test_logs_inside_request_span_carry_correlation_id() {
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        tracing::subscriber::with_default(capturing_subscriber(&buffer), || {
+            let span = request_span("reasoning_linear", &NumberOrString::Number(42));
+            let _guard = span.enter();
+            tracing::info!("handling request");
+        });
+
+        let output = String::from_utf8(buffer.lock().unwrap().clone()).unwrap();
+        assert!(output.contains("handling request"));
+        assert!(output.contains("correlation_id=req-42"));
+        assert!(output.contains("tool=reasoning_linear"));
+    }
+
+    #[tokio::test]
+    async fn test_instrumented_future_propagates_correlation_id_across_awaits() {
+        use tracing::Instrument;
+
+        // A nested helper logging after an await point, the shape of an
+        // Anthropic client call made from a handler.
+        async fn inner_call() {
+            tokio::task::yield_now().await;
+            tracing::info!("client call completed");
+        }
+
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let _default = tracing::subscriber::set_default(capturing_subscriber(&buffer));
+        // Created after the subscriber is installed — a span built against the
+        // no-op global dispatcher would record nothing.
+        let span = request_span("reasoning_tree", &NumberOrString::String("corr-9".into()));
+        inner_call().instrument(span).await;
+
+        let output = String::from_utf8(buffer.lock().unwrap().clone()).unwrap();
+        assert!(output.contains("client call completed"));
+        assert!(output.contains("correlation_id=corr-9"));
+    }
+}
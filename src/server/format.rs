@@ -0,0 +1,534 @@
+//! Response format rendering (JSON vs. readable text).
+//!
+//! Every tool accepts an optional top-level `format` argument: `"json"` (the
+//! default) returns the serialized response struct unchanged, `"text"` renders
+//! it into readable prose for clients that display results to humans directly.
+//! `"adr"` renders a decision analysis (weighted/TOPSIS) as an Architecture
+//! Decision Record in Markdown; responses without a decision ranking pass
+//! through as JSON.
+//!
+//! Formatting is applied in the result serialization layer (`call_tool` in
+//! [`super::tools`]), not in the modes or handlers: handlers always produce
+//! typed responses, and the text rendering is a uniform transformation of the
+//! serialized JSON. Request structs tolerate the extra `format` key (none of
+//! them deny unknown fields), so no per-tool parameter plumbing is needed.
+
+use std::fmt::Write as _;
+
+use rmcp::model::{CallToolResult, ContentBlock, JsonObject};
+use serde_json::Value;
+
+/// How a tool result should be rendered to the client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResponseFormat {
+    /// Serialized response struct (pretty-printed JSON). The default.
+    #[default]
+    Json,
+    /// Readable prose rendered from the response fields.
+    Text,
+    /// Architecture Decision Record in Markdown, rendered from a decision
+    /// analysis ranking. Non-decision responses pass through as JSON.
+    Adr,
+}
+
+impl ResponseFormat {
+    /// Read the requested format from a tool call's raw arguments.
+    ///
+    /// Absent, non-string, or unrecognized values fall back to [`Self::Json`]
+    /// so a typo can never hide the structured result.
+    #[must_use]
+    pub fn from_args(arguments: Option<&JsonObject>) -> Self {
+        match arguments
+            .and_then(|args| args.get("format"))
+            .and_then(Value::as_str)
+        {
+            Some("text") => Self::Text,
+            Some("adr") => Self::Adr,
+            _ => Self::Json,
+        }
+    }
+
+    /// Apply this format to a tool result.
+    ///
+    /// JSON format returns the result unchanged. Text format re-renders each
+    /// JSON text block as prose; blocks that aren't JSON (or aren't text) pass
+    /// through untouched. ADR format re-renders blocks that carry a decision
+    /// ranking; anything else passes through as JSON so the structured result
+    /// is never hidden.
+    #[must_use]
+    pub fn apply(self, mut result: CallToolResult) -> CallToolResult {
+        if self == Self::Json {
+            return result;
+        }
+
+        result.content = std::mem::take(&mut result.content)
+            .into_iter()
+            .map(|block| {
+                let rendered = block
+                    .as_text()
+                    .and_then(|text| serde_json::from_str::<Value>(&text.text).ok())
+                    .and_then(|value| match self {
+                        Self::Json => None,
+                        Self::Text => Some(ContentBlock::text(render_text(&value))),
+                        Self::Adr => render_adr(&value).map(ContentBlock::text),
+                    });
+                rendered.unwrap_or(block)
+            })
+            .collect();
+
+        result
+    }
+}
+
+/// Render a serialized response as readable prose.
+///
+/// Objects become `Key: value` lines (keys title-cased, nulls skipped), arrays
+/// become bullet lists, and nested structures are indented. The renderer is
+/// generic over the JSON shape so every tool shares one implementation.
+#[must_use]
+pub fn render_text(value: &Value) -> String {
+    let mut out = String::new();
+    render_value(&mut out, value, 0);
+    // Scalars at the top level have no trailing newline; normalize to one.
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+/// Render a serialized decision response as an Architecture Decision Record.
+///
+/// Returns `None` unless the response carries a recommendation and a
+/// non-empty ranking (i.e. a weighted or TOPSIS decision analysis), so other
+/// tools' responses are left untouched by the `adr` format.
+#[must_use]
+pub fn render_adr(value: &Value) -> Option<String> {
+    let recommendation = value.get("recommendation").and_then(Value::as_str)?;
+    let rankings = value.get("rankings").and_then(Value::as_array)?;
+    if rankings.is_empty() {
+        return None;
+    }
+
+    let mut ranked: Vec<&Value> = rankings.iter().collect();
+    ranked.sort_by_key(|r| r.get("rank").and_then(Value::as_u64).unwrap_or(u64::MAX));
+
+    let mut out = String::from("# Architecture Decision Record\n\n## Status\n\nProposed\n");
+
+    out.push_str("\n## Context\n\n");
+    match value.get("rationale").and_then(Value::as_str) {
+        Some(rationale) if !rationale.is_empty() => {
+            let _ = writeln!(out, "{rationale}");
+        }
+        _ => {
+            let _ = writeln!(
+                out,
+                "Structured decision analysis across {} option(s).",
+                ranked.len()
+            );
+        }
+    }
+
+    out.push_str(
+        "\n## Options Considered\n\n| Rank | Option | Score |\n|------|--------|-------|\n",
+    );
+    for entry in &ranked {
+        let _ = writeln!(
+            out,
+            "| {} | {} | {:.2} |",
+            entry.get("rank").and_then(Value::as_u64).unwrap_or(0),
+            entry.get("option").and_then(Value::as_str).unwrap_or("?"),
+            entry.get("score").and_then(Value::as_f64).unwrap_or(0.0),
+        );
+    }
+
+    if let Some(criteria) = adr_criteria(value) {
+        out.push_str("\n### Criteria\n\n");
+        out.push_str(&criteria);
+    }
+
+    out.push_str("\n## Decision\n\n");
+    let _ = writeln!(out, "{recommendation}");
+
+    out.push_str("\n## Consequences\n\n");
+    if let Some(runner_up) = ranked.get(1) {
+        let _ = writeln!(
+            out,
+            "Runner-up: {} (score {:.2}) remains the fallback if the decision is revisited.",
+            runner_up
+                .get("option")
+                .and_then(Value::as_str)
+                .unwrap_or("?"),
+            runner_up
+                .get("score")
+                .and_then(Value::as_f64)
+                .unwrap_or(0.0),
+        );
+    }
+    if let Some(validation) = value.get("validation") {
+        if validation.get("consistent").and_then(Value::as_bool) == Some(false) {
+            out.push_str("Arithmetic verification found discrepancies:\n");
+            if let Some(warnings) = validation.get("warnings").and_then(Value::as_array) {
+                for warning in warnings {
+                    let _ = writeln!(out, "- {}", scalar_text(warning));
+                }
+            }
+        }
+    }
+
+    Some(out)
+}
+
+/// Render the criteria list (with weights) from a weighted or TOPSIS
+/// breakdown as Markdown bullets. `None` when the response has neither.
+fn adr_criteria(value: &Value) -> Option<String> {
+    let breakdown = value.get("breakdown")?;
+    let criteria = breakdown
+        .get("weighted")
+        .or_else(|| breakdown.get("topsis"))?
+        .get("criteria")
+        .and_then(Value::as_array)?;
+
+    let mut out = String::new();
+    for criterion in criteria {
+        let name = criterion.get("name").and_then(Value::as_str).unwrap_or("?");
+        let weight = criterion
+            .get("weight")
+            .and_then(Value::as_f64)
+            .unwrap_or(0.0);
+        // TOPSIS criteria carry a benefit/cost type; weighted ones a description.
+        let detail = criterion
+            .get("criterion_type")
+            .or_else(|| criterion.get("description"))
+            .and_then(Value::as_str)
+            .unwrap_or("");
+        if detail.is_empty() {
+            let _ = writeln!(out, "- {name} (weight {weight:.2})");
+        } else {
+            let _ = writeln!(out, "- {name} (weight {weight:.2}): {detail}");
+        }
+    }
+    Some(out)
+}
+
+/// Append `value` to `out` at the given indent depth.
+fn render_value(out: &mut String, value: &Value, depth: usize) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map {
+                if v.is_null() {
+                    continue;
+                }
+                indent(out, depth);
+                if is_scalar(v) {
+                    let _ = writeln!(out, "{}: {}", prettify_key(key), scalar_text(v));
+                } else {
+                    let _ = writeln!(out, "{}:", prettify_key(key));
+                    render_value(out, v, depth + 1);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                indent(out, depth);
+                if is_scalar(item) {
+                    let _ = writeln!(out, "- {}", scalar_text(item));
+                } else {
+                    out.push_str("-\n");
+                    render_value(out, item, depth + 1);
+                }
+            }
+        }
+        scalar => out.push_str(&scalar_text(scalar)),
+    }
+}
+
+/// Whether a value renders on a single line.
+fn is_scalar(value: &Value) -> bool {
+    !(value.is_object() || value.is_array())
+}
+
+/// Render a scalar JSON value without quotes around strings.
+fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Turn a snake_case field name into a readable label (`thought_id` → `Thought id`).
+fn prettify_key(key: &str) -> String {
+    let spaced = key.replace('_', " ");
+    let mut chars = spaced.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().collect::<String>() + chars.as_str()
+    })
+}
+
+/// Append two spaces per indent level.
+fn indent(out: &mut String, depth: usize) {
+    for _ in 0..depth {
+        out.push_str("  ");
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp,
+    clippy::approx_constant,
+    clippy::unreadable_literal
+)]
+mod tests {
+    use super::*;
+    use crate::server::responses::{DetectResponse, Detection};
+    use rmcp::model::IntoContents;
+    use serde_json::json;
+
+    fn biases_response() -> DetectResponse {
+        DetectResponse {
+            detections: vec![
+                Detection {
+                    detection_type: "confirmation bias".to_string(),
+                    category: None,
+                    severity: "high".to_string(),
+                    confidence: 0.9,
+                    evidence: "only supporting sources cited".to_string(),
+                    explanation: "Contradicting data was not considered".to_string(),
+                    remediation: Some("seek disconfirming evidence".to_string()),
+                    changes_conclusion: Some("yes".to_string()),
+                    grounded: Some(true),
+                },
+                Detection {
+                    detection_type: "anchoring bias".to_string(),
+                    category: None,
+                    severity: "medium".to_string(),
+                    confidence: 0.7,
+                    evidence: "first estimate reused throughout".to_string(),
+                    explanation: "Later figures never revisited the anchor".to_string(),
+                    remediation: None,
+                    changes_conclusion: Some("maybe".to_string()),
+                    grounded: Some(true),
+                },
+            ],
+            summary: Some("Two biases detected".to_string()),
+            overall_quality: Some(0.6),
+            debiased_version: None,
+            argument_structure: None,
+            unchallenged_assumptions: None,
+            conclusion_altering_biases: None,
+            validation: None,
+            metadata: None,
+        }
+    }
+
+    fn biases_result() -> CallToolResult {
+        CallToolResult::success(biases_response().into_contents())
+    }
+
+    #[test]
+    fn test_from_args_defaults_to_json() {
+        assert_eq!(ResponseFormat::from_args(None), ResponseFormat::Json);
+
+        let args = json!({"content": "analyze this"});
+        let args = args.as_object().unwrap();
+        assert_eq!(ResponseFormat::from_args(Some(args)), ResponseFormat::Json);
+    }
+
+    #[test]
+    fn test_from_args_text() {
+        let args = json!({"content": "analyze this", "format": "text"});
+        let args = args.as_object().unwrap();
+        assert_eq!(ResponseFormat::from_args(Some(args)), ResponseFormat::Text);
+    }
+
+    #[test]
+    fn test_from_args_unrecognized_falls_back_to_json() {
+        let args = json!({"format": "yaml"});
+        let args = args.as_object().unwrap();
+        assert_eq!(ResponseFormat::from_args(Some(args)), ResponseFormat::Json);
+
+        let args = json!({"format": 42});
+        let args = args.as_object().unwrap();
+        assert_eq!(ResponseFormat::from_args(Some(args)), ResponseFormat::Json);
+    }
+
+    #[test]
+    fn test_detect_biases_json_format_is_structured() {
+        // Json is the default: the result passes through as valid structured output.
+        let result = ResponseFormat::Json.apply(biases_result());
+
+        let text = result.content[0].as_text().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&text.text).expect("valid JSON");
+        assert_eq!(value["detections"][0]["type"], "confirmation bias");
+        assert_eq!(value["detections"][1]["type"], "anchoring bias");
+        assert_eq!(value["summary"], "Two biases detected");
+    }
+
+    #[test]
+    fn test_detect_biases_text_format_is_prose() {
+        let result = ResponseFormat::Text.apply(biases_result());
+
+        let text = &result.content[0].as_text().unwrap().text;
+        // The bias names survive the rendering...
+        assert!(text.contains("confirmation bias"));
+        assert!(text.contains("anchoring bias"));
+        assert!(text.contains("Two biases detected"));
+        // ...as readable labels, not serialized JSON.
+        assert!(text.contains("Summary: Two biases detected"));
+        assert!(!text.contains('{'));
+        assert!(!text.contains("\"detections\""));
+    }
+
+    #[test]
+    fn test_text_format_leaves_non_json_content_untouched() {
+        let result = CallToolResult::success(vec![ContentBlock::text("plain prose, not JSON")]);
+        let rendered = ResponseFormat::Text.apply(result);
+        assert_eq!(
+            rendered.content[0].as_text().unwrap().text,
+            "plain prose, not JSON"
+        );
+    }
+
+    #[test]
+    fn test_render_text_nested_structures() {
+        let value = json!({
+            "session_id": "s-1",
+            "nested": {"inner_key": "inner value"},
+            "items": ["first", "second"],
+            "skipped": null,
+        });
+
+        let text = render_text(&value);
+        assert!(text.contains("Session id: s-1"));
+        assert!(text.contains("Nested:\n  Inner key: inner value"));
+        assert!(text.contains("Items:\n  - first\n  - second"));
+        assert!(!text.contains("skipped"));
+    }
+
+    #[test]
+    fn test_render_text_array_of_objects() {
+        let value = json!({"detections": [{"type": "strawman", "severity": "low"}]});
+        let text = render_text(&value);
+        assert!(text.contains("Detections:"));
+        assert!(text.contains("Type: strawman"));
+        assert!(text.contains("Severity: low"));
+    }
+
+    #[test]
+    fn test_render_text_scalar() {
+        assert_eq!(render_text(&json!("hello")), "hello\n");
+        assert_eq!(render_text(&json!(3)), "3\n");
+    }
+
+    #[test]
+    fn test_prettify_key() {
+        assert_eq!(prettify_key("thought_id"), "Thought id");
+        assert_eq!(prettify_key("summary"), "Summary");
+        assert_eq!(prettify_key(""), "");
+    }
+
+    // ========================================================================
+    // ADR Format Tests
+    // ========================================================================
+
+    fn weighted_decision_json() -> Value {
+        json!({
+            "recommendation": "Adopt PostgreSQL",
+            "rankings": [
+                {"option": "MongoDB", "score": 0.61, "rank": 2},
+                {"option": "Adopt PostgreSQL", "score": 0.87, "rank": 1},
+                {"option": "DynamoDB", "score": 0.44, "rank": 3}
+            ],
+            "rationale": "Relational integrity outweighed horizontal scaling needs.",
+            "breakdown": {
+                "weighted": {
+                    "criteria": [
+                        {"name": "Consistency", "weight": 0.4, "description": "Transactional guarantees"},
+                        {"name": "Operational cost", "weight": 0.35, "description": "Hosting and maintenance"},
+                        {"name": "Scalability", "weight": 0.25, "description": "Growth headroom"}
+                    ],
+                    "scores": {},
+                    "weighted_totals": {}
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_from_args_adr() {
+        let args = json!({"question": "Which database?", "format": "adr"});
+        let args = args.as_object().unwrap();
+        assert_eq!(ResponseFormat::from_args(Some(args)), ResponseFormat::Adr);
+    }
+
+    #[test]
+    fn test_adr_renders_weighted_decision() {
+        let adr = render_adr(&weighted_decision_json()).unwrap();
+
+        // The chosen option appears in the Decision section.
+        assert!(adr.contains("## Decision\n\nAdopt PostgreSQL"));
+        // Options are ranked best-first despite the unsorted input.
+        let best = adr.find("| 1 | Adopt PostgreSQL | 0.87 |").unwrap();
+        let second = adr.find("| 2 | MongoDB | 0.61 |").unwrap();
+        assert!(best < second);
+        // Criteria carry their weights.
+        assert!(adr.contains("- Consistency (weight 0.40): Transactional guarantees"));
+        assert!(adr.contains("- Operational cost (weight 0.35)"));
+        // The runner-up is called out in the consequences.
+        assert!(adr.contains("Runner-up: MongoDB (score 0.61)"));
+        assert!(adr.contains("Relational integrity outweighed"));
+    }
+
+    #[test]
+    fn test_adr_renders_topsis_criteria_with_types() {
+        let value = json!({
+            "recommendation": "Option A",
+            "rankings": [
+                {"option": "Option A", "score": 0.9, "rank": 1},
+                {"option": "Option B", "score": 0.5, "rank": 2}
+            ],
+            "breakdown": {
+                "topsis": {
+                    "criteria": [
+                        {"name": "Throughput", "criterion_type": "benefit", "weight": 0.6},
+                        {"name": "Latency", "criterion_type": "cost", "weight": 0.4}
+                    ],
+                    "closeness": {},
+                    "distances": {}
+                }
+            }
+        });
+
+        let adr = render_adr(&value).unwrap();
+        assert!(adr.contains("- Throughput (weight 0.60): benefit"));
+        assert!(adr.contains("- Latency (weight 0.40): cost"));
+        assert!(adr.contains("Runner-up: Option B (score 0.50)"));
+    }
+
+    #[test]
+    fn test_adr_surfaces_validation_warnings() {
+        let mut value = weighted_decision_json();
+        value["validation"] = json!({
+            "consistent": false,
+            "warnings": ["weighted total for MongoDB off by 0.12"],
+            "ranking_corrected": true
+        });
+
+        let adr = render_adr(&value).unwrap();
+        assert!(adr.contains("Arithmetic verification found discrepancies"));
+        assert!(adr.contains("- weighted total for MongoDB off by 0.12"));
+    }
+
+    #[test]
+    fn test_adr_format_passes_non_decision_result_through() {
+        // A detect response has no decision ranking — the structured JSON
+        // must come back unchanged rather than a half-empty ADR.
+        let result = ResponseFormat::Adr.apply(biases_result());
+        let text = result.content[0].as_text().unwrap().text.clone();
+        let value: Value = serde_json::from_str(&text).unwrap();
+        assert!(value.get("detections").is_some());
+        assert!(render_adr(&value).is_none());
+    }
+}
@@ -0,0 +1,240 @@
+//! Response post-processing hooks (middleware).
+//!
+//! A [`ResponseHook`] transforms the serialized tool response after the
+//! handler has produced it and before `select`/`format` rendering, so a
+//! selection or text rendering sees the transformed value. Hooks are
+//! registered at server construction
+//! ([`ReasoningServer::with_response_hook`](super::ReasoningServer::with_response_hook))
+//! and applied in registration order.
+//!
+//! Like `format` and `select`, hooks operate in the result serialization
+//! layer (`call_tool` in [`super::tools`]), not in the modes or handlers:
+//! handlers always produce full typed responses, and a hook is a uniform
+//! transformation of the serialized JSON. Content blocks that aren't JSON
+//! (or aren't text) pass through untouched.
+//!
+//! One built-in hook is provided: [`UsageStampHook`], which stamps the
+//! call's latency and token usage into the response envelope under an
+//! `envelope` key. It is registered in `run_stdio` when
+//! `RESPONSE_USAGE_STAMP=true`.
+
+use rmcp::model::CallToolResult;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Per-call context passed to each hook alongside the serialized response.
+#[derive(Debug, Clone)]
+pub struct HookContext {
+    /// Name of the tool that produced the response.
+    pub tool: String,
+    /// Wall-clock time the dispatch took, in milliseconds.
+    pub elapsed_ms: u64,
+    /// Uncached input tokens the call consumed (0 for tools that never hit
+    /// the API).
+    pub input_tokens: u64,
+    /// Input tokens the call served from the prompt cache.
+    pub cache_read_tokens: u64,
+}
+
+/// A response post-processing hook.
+///
+/// Implementations must be pure transformations of the serialized response:
+/// returning the value unchanged is always valid, and a hook that cannot
+/// apply (e.g. the response is not an object) should pass the value through
+/// rather than fail the call.
+pub trait ResponseHook: Send + Sync {
+    /// Short stable name, used in logs.
+    fn name(&self) -> &'static str;
+
+    /// Transform the serialized response.
+    fn apply(&self, context: &HookContext, response: Value) -> Value;
+}
+
+/// Built-in hook that stamps latency and token usage into the response
+/// envelope, under an `envelope` key no response type uses.
+///
+/// Lets clients see per-call cost without a follow-up `reasoning_metrics`
+/// query. Non-object responses pass through untouched.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UsageStampHook;
+
+impl ResponseHook for UsageStampHook {
+    fn name(&self) -> &'static str {
+        "usage_stamp"
+    }
+
+    fn apply(&self, context: &HookContext, mut response: Value) -> Value {
+        if let Some(object) = response.as_object_mut() {
+            object.insert(
+                "envelope".to_string(),
+                serde_json::json!({
+                    "tool": context.tool,
+                    "latency_ms": context.elapsed_ms,
+                    "input_tokens": context.input_tokens,
+                    "cache_read_tokens": context.cache_read_tokens,
+                }),
+            );
+        }
+        response
+    }
+}
+
+/// Apply the registered hooks, in order, to each JSON text block of a tool
+/// result.
+///
+/// Each text block that parses as JSON is folded through the hooks and
+/// re-serialized (pretty-printed, matching the default rendering); other
+/// blocks pass through untouched. With no hooks registered the result is
+/// returned unchanged.
+#[must_use]
+pub fn apply_hooks(
+    hooks: &[Arc<dyn ResponseHook>],
+    context: &HookContext,
+    mut result: CallToolResult,
+) -> CallToolResult {
+    if hooks.is_empty() {
+        return result;
+    }
+
+    result.content = std::mem::take(&mut result.content)
+        .into_iter()
+        .map(|block| {
+            let Some(value) = block
+                .as_text()
+                .and_then(|text| serde_json::from_str::<Value>(&text.text).ok())
+            else {
+                return block;
+            };
+            let transformed = hooks
+                .iter()
+                .fold(value, |value, hook| hook.apply(context, value));
+            let rendered = serde_json::to_string_pretty(&transformed)
+                .unwrap_or_else(|_| transformed.to_string());
+            rmcp::model::ContentBlock::text(rendered)
+        })
+        .collect();
+
+    result
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp,
+    clippy::approx_constant,
+    clippy::unreadable_literal
+)]
+mod tests {
+    use super::*;
+    use rmcp::model::ContentBlock;
+    use serde_json::json;
+
+    fn test_context() -> HookContext {
+        HookContext {
+            tool: "reasoning_linear".to_string(),
+            elapsed_ms: 1234,
+            input_tokens: 100,
+            cache_read_tokens: 40,
+        }
+    }
+
+    fn linear_result() -> CallToolResult {
+        let response = json!({
+            "content": "Step-by-step analysis",
+            "session_id": "s-1",
+            "confidence": 0.8,
+        });
+        CallToolResult::success(vec![ContentBlock::text(response.to_string())])
+    }
+
+    /// Hook that removes a named field from the response.
+    struct RedactHook(&'static str);
+
+    impl ResponseHook for RedactHook {
+        fn name(&self) -> &'static str {
+            "redact"
+        }
+
+        fn apply(&self, _context: &HookContext, mut response: Value) -> Value {
+            if let Some(object) = response.as_object_mut() {
+                object.remove(self.0);
+            }
+            response
+        }
+    }
+
+    #[test]
+    fn test_no_hooks_returns_result_unchanged() {
+        let result = linear_result();
+        let original = result.content[0].as_text().unwrap().text.clone();
+        let applied = apply_hooks(&[], &test_context(), result);
+        assert_eq!(applied.content[0].as_text().unwrap().text, original);
+    }
+
+    #[test]
+    fn test_redact_hook_removes_field_from_response() {
+        let hooks: Vec<Arc<dyn ResponseHook>> = vec![Arc::new(RedactHook("content"))];
+        let applied = apply_hooks(&hooks, &test_context(), linear_result());
+
+        let text = &applied.content[0].as_text().unwrap().text;
+        let value: Value = serde_json::from_str(text).expect("valid JSON");
+        assert!(value.get("content").is_none());
+        assert_eq!(value["session_id"], "s-1");
+    }
+
+    #[test]
+    fn test_usage_stamp_hook_adds_envelope() {
+        let hooks: Vec<Arc<dyn ResponseHook>> = vec![Arc::new(UsageStampHook)];
+        let applied = apply_hooks(&hooks, &test_context(), linear_result());
+
+        let text = &applied.content[0].as_text().unwrap().text;
+        let value: Value = serde_json::from_str(text).expect("valid JSON");
+        let envelope = &value["envelope"];
+        assert_eq!(envelope["tool"], "reasoning_linear");
+        assert_eq!(envelope["latency_ms"], 1234);
+        assert_eq!(envelope["input_tokens"], 100);
+        assert_eq!(envelope["cache_read_tokens"], 40);
+        // The response's own fields survive alongside the stamp.
+        assert_eq!(value["session_id"], "s-1");
+    }
+
+    #[test]
+    fn test_hooks_apply_in_registration_order() {
+        // The stamp runs first, then the redaction removes it again — order
+        // reversed, the envelope would survive.
+        let hooks: Vec<Arc<dyn ResponseHook>> =
+            vec![Arc::new(UsageStampHook), Arc::new(RedactHook("envelope"))];
+        let applied = apply_hooks(&hooks, &test_context(), linear_result());
+
+        let text = &applied.content[0].as_text().unwrap().text;
+        let value: Value = serde_json::from_str(text).expect("valid JSON");
+        assert!(value.get("envelope").is_none());
+    }
+
+    #[test]
+    fn test_hooks_leave_non_json_content_untouched() {
+        let hooks: Vec<Arc<dyn ResponseHook>> = vec![Arc::new(UsageStampHook)];
+        let result = CallToolResult::success(vec![ContentBlock::text("plain prose, not JSON")]);
+        let applied = apply_hooks(&hooks, &test_context(), result);
+
+        assert_eq!(
+            applied.content[0].as_text().unwrap().text,
+            "plain prose, not JSON"
+        );
+    }
+
+    #[test]
+    fn test_usage_stamp_passes_non_object_through() {
+        let hook = UsageStampHook;
+        let value = hook.apply(&test_context(), json!(["a", "b"]));
+        assert_eq!(value, json!(["a", "b"]));
+    }
+
+    #[test]
+    fn test_hook_names() {
+        assert_eq!(UsageStampHook.name(), "usage_stamp");
+        assert_eq!(RedactHook("x").name(), "redact");
+    }
+}
@@ -49,8 +49,43 @@ impl McpServer {
     /// - Server encounters a runtime error
     #[cfg_attr(coverage_nightly, coverage(off))]
     pub async fn run_stdio(&self) -> Result<(), AppError> {
-        // Initialize storage
-        let storage = SqliteStorage::new(&self.config.database_path).await?;
+        // Initialize storage. Pragmas default to WAL + NORMAL synchronous;
+        // SQLITE_* overrides are validated here so a typo fails at startup
+        // instead of silently running with a weaker durability setting.
+        let pragmas = crate::storage::StoragePragmas::from_env()?;
+        if pragmas != crate::storage::StoragePragmas::new() {
+            tracing::info!(?pragmas, "Applying non-default SQLite pragmas");
+        }
+        let storage = SqliteStorage::new_with_pragmas(
+            &self.config.database_path,
+            self.config.database_max_connections,
+            pragmas,
+        )
+        .await?;
+
+        // Deduplicate near-identical thoughts when configured (THOUGHT_DEDUP).
+        // Off by default; the skip-vs-link strategy and similarity threshold
+        // come from the environment.
+        let storage = match crate::storage::ThoughtDedupConfig::from_env() {
+            Some(dedup) => {
+                tracing::info!(
+                    strategy = ?dedup.strategy,
+                    threshold = dedup.threshold,
+                    "Thought deduplication ENABLED"
+                );
+                storage.with_thought_dedup(dedup)
+            }
+            None => storage,
+        };
+        // Sample thought persistence when configured (THOUGHT_SAMPLE_RATE).
+        // Off by default; important thoughts are always kept.
+        let storage = match crate::storage::ThoughtSamplingConfig::from_env() {
+            Some(sampling) => {
+                tracing::info!(rate = sampling.rate, "Thought persistence sampling ENABLED");
+                storage.with_thought_sampling(sampling)
+            }
+            None => storage,
+        };
         let si_storage = Arc::new(SelfImprovementStorage::new(storage.pool.clone()));
 
         // Initialize self-improvement system (ALWAYS enabled - core feature)
@@ -73,22 +108,67 @@ impl McpServer {
             }
         }
 
+        // Fail fast on a misspelled ENABLED_TOOLS/DISABLED_TOOLS entry before
+        // anything else spins up — the names must match the real tool set.
+        ReasoningServer::validate_tool_filter(&config)?;
+
         // Initialize metrics collector (shared between MCP tools and self-improvement)
         let metrics = Arc::new(MetricsCollector::new());
 
+        // Deterministic offline stub for demos/CI without a real key
+        // (OFFLINE_MODE). Loudly flagged so canned output is never mistaken
+        // for real reasoning; wired into every Anthropic client created below.
+        let offline_mode = std::env::var("OFFLINE_MODE").is_ok_and(|v| v.to_lowercase() == "true");
+        if offline_mode {
+            tracing::warn!(
+                "OFFLINE_MODE ENABLED — every completion is served from the deterministic \
+                 offline stub; NO Anthropic API calls will be made and all reasoning output \
+                 is canned placeholder content"
+            );
+        }
+
         // Create Anthropic client for MCP tools. It records each call's pinned
         // model identifier into metrics so a model-version change is detected
         // (spec 001, FR-017) and the drift classifier can use it.
         let client_config = ClientConfig::default()
             .with_timeout_ms(config.request_timeout_maximum_ms) // Use maximum timeout for deep thinking modes
-            .with_max_retries(config.max_retries);
+            .with_max_retries(config.max_retries)
+            .with_prompt_caching(config.prompt_caching);
         let client = AnthropicClient::new(config.api_key.expose(), client_config)?
-            .with_metrics(Arc::clone(&metrics));
+            .with_metrics(Arc::clone(&metrics))
+            .with_offline(offline_mode);
+
+        // Auto-tag thoughts by mode and topic when configured
+        // (AUTO_TAG_THOUGHTS). Off by default; LLM-derived topics
+        // (AUTO_TAG_LLM_TOPICS) get a dedicated client so tagging traffic
+        // stays out of the main client's metrics.
+        let storage = match crate::storage::ThoughtAutoTagConfig::from_env() {
+            Some(auto_tag) => {
+                tracing::info!(
+                    llm_topics = auto_tag.llm_topics,
+                    "Thought auto-tagging ENABLED"
+                );
+                let storage = storage.with_auto_tagging(auto_tag);
+                if auto_tag.llm_topics {
+                    let tag_client_config = ClientConfig::default()
+                        .with_timeout_ms(config.request_timeout_ms)
+                        .with_max_retries(config.max_retries);
+                    let tag_client =
+                        AnthropicClient::new(config.api_key.expose(), tag_client_config)?
+                            .with_offline(offline_mode);
+                    storage.with_topic_tag_client(Arc::new(tag_client))
+                } else {
+                    storage
+                }
+            }
+            None => storage,
+        };
 
         let si_client_config = ClientConfig::default()
             .with_timeout_ms(config.request_timeout_maximum_ms) // Use maximum timeout for deep thinking modes
             .with_max_retries(config.max_retries);
-        let si_client = AnthropicClient::new(config.api_key.expose(), si_client_config)?;
+        let si_client = AnthropicClient::new(config.api_key.expose(), si_client_config)?
+            .with_offline(offline_mode);
 
         let (si_manager, si_handle) = SelfImprovementManager::new(
             si_config.clone(),
@@ -124,6 +204,10 @@ impl McpServer {
         )
         .with_metrics(Arc::clone(&metrics));
 
+        // Default prompt version for the registry: calls that do not request
+        // a version resolve this one (built-in prompts when unset).
+        crate::prompts::PromptRegistry::set_default_version(config.prompt_version.clone());
+
         // Create the progress broadcast bus. The sender lives in AppState so modes
         // can emit milestones; the per-call MCP forwarder (tools/progress_bridge.rs)
         // subscribes its own receiver for each streaming tool call, so this startup
@@ -131,7 +215,7 @@ impl McpServer {
         let (progress_tx, _progress_rx) = super::progress::create_progress_channel();
 
         // Create app state with shared metrics and self-improvement handle
-        let state = AppState::new(
+        let mut state = AppState::new(
             storage,
             client,
             config.clone(),
@@ -141,6 +225,14 @@ impl McpServer {
             progress_tx,
         );
 
+        // Attach the tool-call audit trail when configured (AUDIT_LOG_PATH).
+        // Off by default; records are redacted (field names + digest, never
+        // argument values).
+        if let Some(audit) = super::audit::AuditLog::from_env() {
+            tracing::info!("Tool-call audit log ENABLED");
+            state = state.with_audit(audit);
+        }
+
         // Spawn the self-heal propose loop ONLY when explicitly enabled AND a
         // workspace is configured (Constitution IV: default-off, operator opt-in).
         // This is the ONLY path that opens PRs against the repo; it never merges.
@@ -154,7 +246,8 @@ impl McpServer {
                     .with_timeout_ms(config.request_timeout_maximum_ms)
                     .with_max_retries(config.max_retries);
                 let heal_client =
-                    AnthropicClient::new(config.api_key.expose(), heal_client_config)?;
+                    AnthropicClient::new(config.api_key.expose(), heal_client_config)?
+                        .with_offline(offline_mode);
                 let heal_manager = crate::self_improvement::heal_manager::HealManager::new(
                     heal_client,
                     crate::self_improvement::repair::SystemCommandRunner,
@@ -228,6 +321,33 @@ impl McpServer {
             });
         }
 
+        // Spawn the periodic JSON metrics snapshot writer when
+        // METRICS_SNAPSHOT_PATH is set (off by default). Writes are atomic
+        // (temp + rename), so readers never see a partial file. Shares the
+        // self-improvement shutdown signal.
+        if let Ok(snapshot_path) = std::env::var("METRICS_SNAPSHOT_PATH") {
+            if !snapshot_path.trim().is_empty() {
+                let interval_secs = std::env::var("METRICS_SNAPSHOT_INTERVAL_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(60);
+                let writer_metrics = Arc::clone(&state.metrics);
+                let writer_shutdown = shutdown_tx.subscribe();
+                let path = std::path::PathBuf::from(snapshot_path);
+                tokio::spawn(async move {
+                    tracing::info!(path = %path.display(), interval_secs, "Metrics snapshot writer started");
+                    crate::metrics::run_snapshot_writer(
+                        writer_metrics,
+                        path,
+                        std::time::Duration::from_secs(interval_secs),
+                        writer_shutdown,
+                    )
+                    .await;
+                    tracing::info!("Metrics snapshot writer stopped");
+                });
+            }
+        }
+
         // Spawn the real-time dashboard sidecar ONLY when built with the
         // `dashboard` feature AND enabled at runtime via MCP_DASHBOARD (off by
         // default, mirroring SELF_HEAL_*). Read-only, loopback-bound; it rides
@@ -260,7 +380,14 @@ impl McpServer {
         }
 
         // Create reasoning server
-        let server = ReasoningServer::new(Arc::new(state));
+        let mut server = ReasoningServer::new(Arc::new(state));
+
+        // Opt-in built-in response hook: stamp latency + token usage into
+        // every response envelope. See `crate::server::hooks`.
+        if std::env::var("RESPONSE_USAGE_STAMP").is_ok_and(|v| v.to_lowercase() == "true") {
+            tracing::info!("Response usage stamping enabled (RESPONSE_USAGE_STAMP)");
+            server = server.with_response_hook(Arc::new(crate::server::hooks::UsageStampHook));
+        }
 
         // Run with stdio transport
         let transport = StdioTransport::new();
@@ -327,8 +454,10 @@ mod tests {
 
     fn test_config() -> Config {
         Config {
+            response_language: None,
             api_key: SecretString::new("test-key"),
             database_path: ":memory:".to_string(),
+            database_max_connections: 5,
             log_level: "info".to_string(),
             request_timeout_ms: 30000,
             request_timeout_deep_ms: 60000,
@@ -342,9 +471,55 @@ mod tests {
             reflection_quality_threshold: 0.8,
             mcts_quality_threshold: 0.5,
             graph_prune_threshold: 0.3,
+            graph_max_nodes: 200,
+            graph_max_returned_children: None,
+            rubric_llm_weight: 0.5,
+            evidence_interval_width: 0.1,
+            sticky_session: false,
+            detect_filter_unverified: false,
+            strict_parsing: false,
+            require_session_id: false,
+            self_correct_parse: false,
+            structured_output: false,
+            confidence_floor: None,
+            prompt_caching: false,
+            enabled_tools: None,
+            disabled_tools: vec![],
+            divergent_per_perspective: false,
+            divergent_max_concurrency: 3,
+            linear_store_raw_io: false,
+            max_pending_requests: 32,
+            auto_history_bias: false,
+            retry_degenerate: false,
+            prompt_version: None,
         }
     }
 
+    #[test]
+    fn test_validate_tool_filter() {
+        let mut config = test_config();
+        assert!(ReasoningServer::validate_tool_filter(&config).is_ok());
+
+        // Real names pass, whichever list they appear in.
+        config.enabled_tools = Some(vec!["reasoning_linear".to_string()]);
+        config.disabled_tools = vec!["reasoning_mcts".to_string()];
+        assert!(ReasoningServer::validate_tool_filter(&config).is_ok());
+
+        // A misspelled name fails and says which variable carried it.
+        config.enabled_tools = Some(vec!["reasoning_liner".to_string()]);
+        assert!(matches!(
+            ReasoningServer::validate_tool_filter(&config),
+            Err(crate::error::ConfigError::InvalidValue { var, .. }) if var == "ENABLED_TOOLS"
+        ));
+
+        config.enabled_tools = None;
+        config.disabled_tools = vec!["not_a_tool".to_string()];
+        assert!(matches!(
+            ReasoningServer::validate_tool_filter(&config),
+            Err(crate::error::ConfigError::InvalidValue { var, .. }) if var == "DISABLED_TOOLS"
+        ));
+    }
+
     #[test]
     fn test_mcp_server_new() {
         let config = test_config();
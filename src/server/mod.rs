@@ -8,17 +8,18 @@
 //!
 //! # Architecture
 //!
-//! The server is built on the rmcp SDK and provides 35 tools
-//! (17 core reasoning + 7 self-improvement + 4 session + 7 agent/team):
+//! The server is built on the rmcp SDK and provides 49 tools
+//! (24 core reasoning + 7 self-improvement + 11 session + 7 agent/team):
 //!
 //! - **Core reasoning**: linear, tree, divergent, reflection, checkpoint, auto,
-//!   meta, confidence_route
+//!   auto_stats, meta, confidence_route, solve
 //! - **Graph**: graph (8 operations)
-//! - **Analysis**: detect, decision, evidence
+//! - **Analysis**: detect, decision, decision_diff, decision_challenge, evidence
 //! - **Advanced**: timeline, mcts, counterfactual
-//! - **Infrastructure**: preset, metrics
+//! - **Infrastructure**: preset, preset_history, metrics, help, next
 //! - **Self-improvement**: si_status/diagnoses/overrides/approve/reject/trigger/rollback
-//! - **Session**: list_sessions, resume, search, relate
+//! - **Session**: list_sessions, resume, search, relate, undo, inspect_thought,
+//!   diff, export, merge_sessions, session_quality, open_questions
 //! - **Agent/team**: agent_invoke/list/metrics, skill_run, team_run/list, crew_invoke
 //!
 //! # Example
@@ -38,6 +39,7 @@
 //! let config = Config {
 //!     api_key: SecretString::new("sk-ant-xxx"),
 //!     database_path: "./data/reasoning.db".to_string(),
+//!     database_max_connections: 5,
 //!     log_level: "info".to_string(),
 //!     request_timeout_ms: 30000,
 //!     request_timeout_deep_ms: 60000,
@@ -51,6 +53,28 @@
 //!     reflection_quality_threshold: 0.8,
 //!     mcts_quality_threshold: 0.5,
 //!     graph_prune_threshold: 0.3,
+//!     graph_max_nodes: 200,
+//!     graph_max_returned_children: None,
+//!     rubric_llm_weight: 0.5,
+//!     evidence_interval_width: 0.1,
+//!     sticky_session: false,
+//!     detect_filter_unverified: false,
+//!     strict_parsing: false,
+//!     require_session_id: false,
+//!     self_correct_parse: false,
+//!     structured_output: false,
+//!     confidence_floor: None,
+//!     prompt_caching: false,
+//!     response_language: None,
+//!     enabled_tools: None,
+//!     disabled_tools: vec![],
+//!     divergent_per_perspective: false,
+//!     divergent_max_concurrency: 3,
+//!     linear_store_raw_io: false,
+//!     max_pending_requests: 32,
+//!     auto_history_bias: false,
+//!     retry_degenerate: false,
+//!     prompt_version: None,
 //! };
 //! let metrics = Arc::new(MetricsCollector::new());
 //! let si_handle = ManagerHandle::for_testing(); // In production, use SelfImprovementManager::new()
@@ -65,15 +89,26 @@
 //! # }
 //! ```
 
+mod audit;
+mod coerce;
+mod correlation;
+mod format;
+mod hooks;
 mod mcp;
 mod metadata_builders;
 mod progress;
 mod requests;
 mod responses;
+mod select;
+mod sticky;
 mod tools;
 mod transport;
 mod types;
 
+pub use audit::{AuditCallback, AuditLog, AuditRecord, AuditSink};
+pub use correlation::{correlation_id, request_span};
+pub use format::{render_text, ResponseFormat};
+pub use hooks::{HookContext, ResponseHook, UsageStampHook};
 pub use mcp::McpServer;
 pub use progress::{create_progress_channel, ProgressEvent, ProgressMilestone, ProgressReporter};
 pub use requests::{
@@ -82,7 +117,7 @@ pub use requests::{
     DetectRequest, DivergentRequest, EvidenceRequest, GraphRequest, LinearRequest, MctsRequest,
     MetricsRequest, PresetRequest, ReflectionRequest, SiApproveRequest, SiDiagnosesRequest,
     SiRejectRequest, SiRollbackRequest, SiStatusRequest, SiTriggerRequest, SkillRunRequest,
-    TeamListRequest, TeamRunRequest, TimelineRequest, TreeRequest,
+    TeamListRequest, TeamRunRequest, TimelineRequest, TreeRequest, UndoRequest,
 };
 pub use responses::{
     AgentInvokeResponse, AgentListResponse, AgentMetricsResponse, AutoResponse,
@@ -94,8 +129,10 @@ pub use responses::{
     RankedOption, ReflectionResponse, SiApproveResponse, SiDiagnosesResponse, SiExecutionSummary,
     SiLearningSummary, SiPendingDiagnosis, SiRejectResponse, SiRollbackResponse, SiStatusResponse,
     SiTriggerResponse, SkillRunResponse, StakeholderMap, TeamListResponse, TeamRunResponse,
-    TimelineBranch, TimelineResponse, TreeResponse,
+    TimelineBranch, TimelineResponse, TreeResponse, UndoResponse,
 };
+pub use select::ResponseSelect;
+pub use sticky::StickySession;
 pub use tools::ReasoningServer;
 pub use transport::{StdioTransport, TransportConfig};
 pub use types::AppState;
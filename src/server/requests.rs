@@ -79,6 +79,21 @@ pub struct LinearRequest {
     /// Per-request timeout override in milliseconds. Overrides server default when set.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub timeout_ms: Option<u64>,
+    /// Output language for natural-language response fields (e.g. "Spanish").
+    /// Overrides the server-wide `RESPONSE_LANGUAGE` default; JSON keys and
+    /// enumeration values stay in English either way.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Completion profile: "fast" (default), "balanced", or "deep". Selects
+    /// the sampling and thinking settings for this call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(example = &"fast", example = &"balanced", example = &"deep")]
+    pub profile: Option<String>,
+    /// Named prompt version registered in the prompt registry to use for this
+    /// call, overriding the server-wide `PROMPT_VERSION` default. An
+    /// unregistered name falls back to the built-in prompt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_version: Option<String>,
 }
 
 /// Request for tree reasoning.
@@ -99,6 +114,11 @@ pub struct TreeRequest {
     pub num_branches: Option<u32>,
     /// Mark as completed.
     pub completed: Option<bool>,
+    /// Output language for natural-language response fields (e.g. "Spanish").
+    /// Overrides the server-wide `RESPONSE_LANGUAGE` default; JSON keys and
+    /// enumeration values stay in English either way.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
 }
 
 /// Request for divergent reasoning.
@@ -114,8 +134,18 @@ pub struct DivergentRequest {
     pub challenge_assumptions: Option<bool>,
     /// Force maximum divergence.
     pub force_rebellion: Option<bool>,
+    /// Include the raw extended-thinking trace in the response (default false).
+    /// A debugging aid for inspecting reasoning quality; the trace is not part
+    /// of the parsed result.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_thinking: Option<bool>,
     /// Progress token for streaming notifications (auto-generated if not provided).
     pub progress_token: Option<String>,
+    /// Output language for natural-language response fields (e.g. "Spanish").
+    /// Overrides the server-wide `RESPONSE_LANGUAGE` default; JSON keys and
+    /// enumeration values stay in English either way.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
 }
 
 /// Request for reflection.
@@ -138,6 +168,11 @@ pub struct ReflectionRequest {
     pub quality_threshold: Option<f64>,
     /// Progress token for streaming notifications (auto-generated if not provided).
     pub progress_token: Option<String>,
+    /// Output language for natural-language response fields (e.g. "Spanish").
+    /// Overrides the server-wide `RESPONSE_LANGUAGE` default; JSON keys and
+    /// enumeration values stay in English either way.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
 }
 
 /// Request for checkpoint operations.
@@ -174,6 +209,22 @@ pub struct ConfidenceRouteRequest {
     pub budget: Option<String>,
 }
 
+/// Request for the escalation-ladder solver.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SolveRequest {
+    /// Content to reason about.
+    pub content: String,
+    /// Session ID for context continuity. All rungs run in the same session.
+    pub session_id: Option<String>,
+    /// Confidence at or above which the ladder stops (0.0-1.0, default
+    /// `HIGH_CONFIDENCE_THRESHOLD`).
+    pub confidence_threshold: Option<f64>,
+    /// Custom ladder as ordered rung names ("linear", "tree", "graph").
+    /// Default: linear → tree → graph.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ladder: Option<Vec<String>>,
+}
+
 /// Request for auto mode selection.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AutoRequest {
@@ -188,6 +239,17 @@ pub struct AutoRequest {
     /// only with a next_call hint (they need parameters auto cannot infer).
     #[serde(default)]
     pub execute: Option<bool>,
+    /// Output language for natural-language response fields (e.g. "Spanish").
+    /// Overrides the server-wide `RESPONSE_LANGUAGE` default; JSON keys and
+    /// enumeration values stay in English either way.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+/// Request for auto-selection statistics.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AutoStatsRequest {
+    // Empty - no parameters needed
 }
 
 /// Request for meta-reasoning (empirical tool selection).
@@ -215,35 +277,52 @@ pub struct MetaRequest {
 /// Request for graph reasoning.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct GraphRequest {
-    /// Operation: init=start graph with a problem; generate=expand node with continuations;
+    /// Operation: init=start graph with a problem; import=seed graph from external JSON nodes/edges;
+    /// generate=expand node with continuations;
+    /// advance=auto-expand the best frontier nodes in one call;
     /// score=evaluate node quality; aggregate=merge multiple nodes; refine=improve a node;
-    /// prune=remove low-quality nodes below threshold; finalize=synthesize terminal nodes into answer;
+    /// prune=remove low-quality nodes below threshold;
+    /// apply_prune=delete the nodes named in node_ids (refuses roots and best-path nodes unless force=true);
+    /// finalize=synthesize terminal nodes into answer;
     /// state=show current graph structure. Typical sequence: init → generate → score → prune → finalize.
-    #[schemars(example = &"init", example = &"generate", example = &"score", example = &"prune", example = &"finalize", example = &"state")]
+    #[schemars(example = &"init", example = &"import", example = &"generate", example = &"advance", example = &"score", example = &"prune", example = &"apply_prune", example = &"finalize", example = &"state")]
     pub operation: String,
     /// Session ID. Required for all operations except init.
     pub session_id: String,
-    /// Problem description (required for init).
+    /// Problem description (required for init). For import: a JSON graph
+    /// `{"nodes": [{"id", "content", "score"?, "is_terminal"?}], "edges": [{"from", "to"}]}`.
     pub content: Option<String>,
     /// Additional problem context passed to generation/scoring operations.
     pub problem: Option<String>,
     /// Target node ID.
     pub node_id: Option<String>,
-    /// Node IDs (for aggregate).
+    /// Node IDs (for aggregate; the candidates to delete for apply_prune).
     pub node_ids: Option<Vec<String>>,
     /// Continuations to generate (1-10).
     pub k: Option<u32>,
     /// Prune threshold (0.0-1.0).
     pub threshold: Option<f64>,
+    /// For apply_prune: override root/best-path protection and delete anyway.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub force: Option<bool>,
     /// Terminal node IDs (for finalize).
     pub terminal_node_ids: Option<Vec<String>>,
+    /// Frontier nodes to expand (for advance, 1-5; default 3).
+    pub max_nodes: Option<u32>,
+    /// Output language for natural-language response fields (e.g. "Spanish").
+    /// Overrides the server-wide `RESPONSE_LANGUAGE` default; JSON keys and
+    /// enumeration values stay in English either way.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
 }
 
 /// Request for detection.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DetectRequest {
     /// Type: biases=detect cognitive distortions (anchoring, confirmation bias, availability heuristic);
-    /// fallacies=detect logical errors (ad hominem, strawman, false dichotomy, slippery slope).
+    /// fallacies=detect logical errors (ad hominem, strawman, false dichotomy, slippery slope);
+    /// knowledge_gaps=find absent information that could change the conclusion;
+    /// counterargue=generate steelmanned opposing arguments with rebuttal assessment.
     #[serde(rename = "type")]
     #[schemars(example = &"biases", example = &"fallacies")]
     pub detect_type: String,
@@ -259,6 +338,22 @@ pub struct DetectRequest {
     pub check_formal: Option<bool>,
     /// Check informal fallacies.
     pub check_informal: Option<bool>,
+    /// Output language for natural-language response fields (e.g. "Spanish").
+    /// Overrides the server-wide `RESPONSE_LANGUAGE` default; JSON keys and
+    /// enumeration values stay in English either way.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Split oversized content on paragraph boundaries, analyze each chunk
+    /// separately, and merge the deduplicated findings, with cited passages
+    /// mapped back to their offset in the original. Only takes effect when
+    /// the content exceeds the chunk size; defaults to false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk: Option<bool>,
+    /// After fallacy detection, run a follow-up scoring each identified
+    /// premise for plausibility and evidentiary support (fallacies only);
+    /// defaults to false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verify_premises: Option<bool>,
 }
 
 /// Request for decision analysis.
@@ -280,6 +375,42 @@ pub struct DecisionRequest {
     pub session_id: Option<String>,
     /// Context.
     pub context: Option<String>,
+    /// Score normalization for type=weighted, applied per criterion across
+    /// options before totals are computed: none (default, scores as-is),
+    /// min_max (rescale to 0-1), z_score (center on the criterion mean).
+    /// Use when criteria are scored on disparate raw scales.
+    #[schemars(example = &"min_max", example = &"z_score")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalization: Option<String>,
+    /// Output language for natural-language response fields (e.g. "Spanish").
+    /// Overrides the server-wide `RESPONSE_LANGUAGE` default; JSON keys and
+    /// enumeration values stay in English either way.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+/// Request to diff two weighted decision analyses.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DecisionDiffRequest {
+    /// Thought ID of the first (earlier) weighted decision analysis.
+    pub thought_id_a: String,
+    /// Thought ID of the second (later) weighted decision analysis.
+    pub thought_id_b: String,
+}
+
+/// Request to challenge the assumptions behind a weighted decision analysis.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DecisionChallengeRequest {
+    /// Thought ID of the weighted decision analysis to challenge.
+    pub prior_thought_id: String,
+    /// Session for the challenge; defaults to the prior decision's session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Output language for natural-language response fields (e.g. "Spanish").
+    /// Overrides the server-wide `RESPONSE_LANGUAGE` default; JSON keys and
+    /// enumeration values stay in English either way.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
 }
 
 /// Request for evidence evaluation.
@@ -300,6 +431,17 @@ pub struct EvidenceRequest {
     pub session_id: Option<String>,
     /// Context.
     pub context: Option<String>,
+    /// Output language for natural-language response fields (e.g. "Spanish").
+    /// Overrides the server-wide `RESPONSE_LANGUAGE` default; JSON keys and
+    /// enumeration values stay in English either way.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Split oversized content on paragraph boundaries, assess each chunk
+    /// separately, and merge the deduplicated evidence pieces (assess only).
+    /// Only takes effect when the content exceeds the chunk size; defaults
+    /// to false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk: Option<bool>,
 }
 
 /// Request for timeline reasoning.
@@ -326,6 +468,11 @@ pub struct TimelineRequest {
     pub merge_strategy: Option<String>,
     /// Branch label.
     pub label: Option<String>,
+    /// Output language for natural-language response fields (e.g. "Spanish").
+    /// Overrides the server-wide `RESPONSE_LANGUAGE` default; JSON keys and
+    /// enumeration values stay in English either way.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
 }
 
 /// Request for MCTS.
@@ -357,6 +504,11 @@ pub struct MctsRequest {
     pub thinking: Option<String>,
     /// Progress token for streaming notifications (auto-generated if not provided).
     pub progress_token: Option<String>,
+    /// Output language for natural-language response fields (e.g. "Spanish").
+    /// Overrides the server-wide `RESPONSE_LANGUAGE` default; JSON keys and
+    /// enumeration values stay in English either way.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
 }
 
 /// Request for counterfactual analysis.
@@ -377,6 +529,11 @@ pub struct CounterfactualRequest {
     pub analysis_depth: Option<String>,
     /// Progress token for streaming notifications (auto-generated if not provided).
     pub progress_token: Option<String>,
+    /// Output language for natural-language response fields (e.g. "Spanish").
+    /// Overrides the server-wide `RESPONSE_LANGUAGE` default; JSON keys and
+    /// enumeration values stay in English either way.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
 }
 
 /// Request for preset operations.
@@ -395,13 +552,22 @@ pub struct PresetRequest {
     pub session_id: Option<String>,
 }
 
+/// Request for a preset's run history.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PresetHistoryRequest {
+    /// Preset whose recorded runs to list (e.g. "code-review").
+    #[schemars(example = &"code-review")]
+    pub preset_id: String,
+}
+
 /// Request for metrics queries.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MetricsRequest {
     /// Query type: summary=overall usage stats; by_mode=stats for a specific reasoning mode
     /// (requires mode_name); invocations=recent tool call log; fallbacks=cases where mode
-    /// selection fell back; config=current server configuration.
-    #[schemars(example = &"summary", example = &"by_mode", example = &"invocations", example = &"fallbacks", example = &"config")]
+    /// selection fell back; retries=API attempt/retry counters per mode and error kind;
+    /// config=current server configuration.
+    #[schemars(example = &"summary", example = &"by_mode", example = &"invocations", example = &"fallbacks", example = &"retries", example = &"config")]
     pub query: String,
     /// Mode name to query (required for by_mode, e.g. "linear", "tree", "graph").
     pub mode_name: Option<String>,
@@ -415,6 +581,21 @@ pub struct MetricsRequest {
     pub limit: Option<u32>,
 }
 
+/// Request for reasoning tool help.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HelpRequest {
+    /// Mode name to describe (e.g. "tree", "graph"); omit to list every mode.
+    #[schemars(example = &"tree", example = &"graph", example = &"decision")]
+    pub mode: Option<String>,
+}
+
+/// Request for a next-action recommendation.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NextActionRequest {
+    /// Session ID to recommend a follow-up action for.
+    pub session_id: String,
+}
+
 // ============================================================================
 // Self-Improvement Requests
 // ============================================================================
@@ -494,6 +675,62 @@ pub struct ResumeSessionRequest {
     pub compress: Option<bool>,
 }
 
+/// Request for exporting a reasoning session.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExportSessionRequest {
+    /// Session ID to export.
+    pub session_id: String,
+    /// Output format: "json" (full structured dump), "markdown" (readable
+    /// report), or "dot" (Graphviz digraph; requires graph-mode data).
+    pub format: String,
+}
+
+/// Request for undoing the last thought in a reasoning session.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UndoRequest {
+    /// Session ID to undo the last thought in.
+    pub session_id: String,
+}
+
+/// Request for the raw request/response behind a thought (`LINEAR_STORE_RAW_IO`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InspectThoughtRequest {
+    /// Thought ID to inspect (as returned by the reasoning tool that produced it).
+    pub thought_id: String,
+}
+
+/// Request to diff two arbitrary stored thoughts.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ThoughtDiffRequest {
+    /// Thought ID of the first (earlier) result. May come from any session.
+    pub thought_id_a: String,
+    /// Thought ID of the second (later) result. May come from any session.
+    pub thought_id_b: String,
+}
+
+/// Request for a session's aggregate quality and trend.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SessionQualityRequest {
+    /// Session ID to report quality for.
+    pub session_id: String,
+}
+
+/// Request for the open questions accumulated across a session.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct OpenQuestionsRequest {
+    /// Session ID to collect open questions from.
+    pub session_id: String,
+}
+
+/// Request for merging one reasoning session into another.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MergeSessionsRequest {
+    /// Session ID that receives the merged content.
+    pub target_session_id: String,
+    /// Session ID to merge in; deleted after the merge.
+    pub source_session_id: String,
+}
+
 /// Request for semantic search over reasoning sessions.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SearchSessionsRequest {
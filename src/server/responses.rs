@@ -254,6 +254,35 @@ pub struct AutoResponse {
     pub skill_suggestion: Option<SkillSuggestion>,
 }
 
+/// Per-mode aggregate in an [`AutoStatsResponse`].
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AutoStatsEntry {
+    /// The mode auto chose.
+    pub selected_mode: String,
+    /// How many times auto picked this mode.
+    pub selections: u32,
+    /// Average routing confidence across those selections.
+    pub avg_confidence: f64,
+    /// Average resulting score (confidence of the chosen mode's first run in
+    /// the same session), over the selections that have one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_score: Option<f64>,
+    /// How many of the selections have a resulting score.
+    pub scored: u32,
+}
+
+/// Response from auto-selection statistics.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AutoStatsResponse {
+    /// Per-mode aggregates, most-selected first.
+    pub stats: Vec<AutoStatsEntry>,
+    /// Total recorded selections across all modes.
+    pub total_selections: u32,
+    /// Error message if the query failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 /// Response from confidence-based routing.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConfidenceRouteResponse {
@@ -277,6 +306,42 @@ pub struct ConfidenceRouteResponse {
     pub next_call: Option<NextCallHint>,
 }
 
+/// Outcome of one rung of the escalation ladder.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SolveRungOutcome {
+    /// The rung that ran ("linear", "tree", "graph").
+    pub rung: String,
+    /// Confidence the rung produced (0.0 when it failed).
+    pub confidence: f64,
+    /// Whether this rung's confidence met the stop threshold.
+    pub met_threshold: bool,
+    /// Error message when the rung failed; the ladder continues regardless.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response from the escalation-ladder solver.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SolveResponse {
+    /// Session all rungs ran in.
+    pub session_id: String,
+    /// True when a rung met the confidence threshold before the ladder ran out.
+    pub solved: bool,
+    /// The stop threshold that was applied.
+    pub threshold: f64,
+    /// The rung whose result is returned (highest confidence).
+    pub best_rung: String,
+    /// The best rung's confidence.
+    pub best_confidence: f64,
+    /// The best rung's full result.
+    pub result: serde_json::Value,
+    /// Every rung that ran, in order.
+    pub path: Vec<SolveRungOutcome>,
+    /// Set when no rung produced a usable result.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 /// Response from meta-reasoning tool selection.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MetaResponse {
@@ -407,6 +472,26 @@ pub struct ArgumentStructureInfo {
     pub conclusion: String,
     /// Validity: "valid", "invalid", or "partially_valid".
     pub validity: String,
+    /// Per-premise plausibility/support scores (with `verify_premises`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub premise_assessments: Option<Vec<PremiseAssessmentInfo>>,
+    /// Set when verification ran but no clear premises were identified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub premise_note: Option<String>,
+}
+
+/// Plausibility and support scores for one premise (fallacies operation,
+/// `verify_premises`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PremiseAssessmentInfo {
+    /// The premise, as identified in the argument structure.
+    pub premise: String,
+    /// How likely the premise is to be true on its face (0.0-1.0).
+    pub plausibility: f64,
+    /// How well available evidence backs the premise (0.0-1.0).
+    pub evidentiary_support: f64,
+    /// Why the premise earned these scores.
+    pub assessment: String,
 }
 
 /// Result of verifying a detection result against the analyzed content.
@@ -547,6 +632,9 @@ pub struct PairwiseBreakdown {
     pub comparisons: Vec<ComparisonInfo>,
     /// The model's transitivity/consistency note.
     pub consistency_check: String,
+    /// Fraction of fully-compared option triples that are transitive
+    /// (1.0 = perfectly consistent, lower when preferences cycle).
+    pub consistency_ratio: f64,
 }
 
 /// Operation-specific scoring breakdown, so the recommendation is auditable.
@@ -605,6 +693,37 @@ pub struct DecisionResponse {
     pub metadata: Option<crate::metadata::ResponseMetadata>,
 }
 
+/// Response from diffing two weighted decision analyses.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DecisionDiffResponse {
+    /// Thought ID of the first analysis.
+    pub thought_id_a: String,
+    /// Thought ID of the second analysis.
+    pub thought_id_b: String,
+    /// Structured diff: per-option rank/score movements, criteria weight
+    /// changes, added/removed options, and whether the winner changed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<serde_json::Value>,
+    /// Error message when either analysis could not be loaded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response from challenging a weighted decision's assumptions.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DecisionChallengeResponse {
+    /// Thought ID of the challenged analysis.
+    pub prior_thought_id: String,
+    /// Structured challenge: the questioned criteria weights (most
+    /// questionable first), the projected ranking shift, and a summary of
+    /// which assumptions the decision is most exposed to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub challenge: Option<serde_json::Value>,
+    /// Error message when the analysis could not be loaded or challenged.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 /// Evidence assessment result.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct EvidenceAssessment {
@@ -710,6 +829,18 @@ pub struct ConfidenceInterval {
     pub upper: f64,
 }
 
+/// A missing piece of evidence with a concrete next step (assess).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EvidenceGapInfo {
+    /// What evidence is missing.
+    pub gap: String,
+    /// Concrete action to fill the gap.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_action: Option<String>,
+    /// Urgency: "high", "medium", or "low".
+    pub priority: String,
+}
+
 /// Response from evidence evaluation.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct EvidenceResponse {
@@ -736,6 +867,9 @@ pub struct EvidenceResponse {
     /// conclusion (assess).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pivot_evidence: Option<String>,
+    /// Evidence gaps sorted by priority, each with a suggested action (assess).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gaps: Option<Vec<EvidenceGapInfo>>,
     /// Full Bayesian breakdown (probabilistic).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bayesian: Option<BayesianBreakdown>,
@@ -840,6 +974,12 @@ pub struct BranchInfo {
     pub plausibility: f64,
     /// Quality of the outcome (0.0-1.0).
     pub outcome_quality: f64,
+    /// Joint probability of the full event chain (product of per-event
+    /// probabilities, assuming independence). Branches are ranked by this.
+    pub joint_probability: f64,
+    /// True when any event lacked a probability (treated as 1.0, making the
+    /// joint probability an upper bound).
+    pub missing_probabilities: bool,
     /// Events along this branch.
     pub events: Vec<BranchEventInfo>,
 }
@@ -917,6 +1057,18 @@ pub struct FragileStrategyInfo {
     pub failure_modes: String,
 }
 
+/// An actionable recommendation from merging branches (merge).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MergeRecommendationInfo {
+    /// The recommended action.
+    pub action: String,
+    /// IDs of the merged branches that support this recommendation.
+    pub supporting_branches: Vec<String>,
+    /// How confident the synthesis is in this recommendation (0.0-1.0).
+    /// Legacy plain-string recommendations carry a neutral 0.5.
+    pub confidence: f64,
+}
+
 /// Result of validating a timeline's references and value ranges.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TimelineValidationInfo {
@@ -978,9 +1130,14 @@ pub struct TimelineResponse {
     /// Overall synthesis (merge).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub synthesis: Option<String>,
-    /// Actionable recommendations (merge).
+    /// Actionable recommendations (merge): the action texts, kept for
+    /// compatibility with callers that read plain strings.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub recommendations: Option<Vec<String>>,
+    /// Structured recommendations with supporting-branch links and
+    /// per-recommendation confidence (merge).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merge_recommendations: Option<Vec<MergeRecommendationInfo>>,
     /// Branch IDs involved (branch/compare/merge).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub branch_ids: Option<Vec<String>>,
@@ -1343,6 +1500,45 @@ pub struct PresetResponse {
     pub next_call: Option<NextCallHint>,
 }
 
+/// Summary of one recorded preset run.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PresetRunSummary {
+    /// Run identifier.
+    pub run_id: String,
+    /// Session the run executed in.
+    pub session_id: String,
+    /// When the run was recorded (RFC 3339).
+    pub created_at: String,
+    /// Overall run success.
+    pub success: bool,
+    /// Number of steps the run executed.
+    pub steps_total: usize,
+    /// Number of executed steps that failed.
+    pub steps_failed: usize,
+    /// Cumulative spend in USD, when the run tracked it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<f64>,
+}
+
+/// Response from listing a preset's run history.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PresetHistoryResponse {
+    /// Preset the history is for.
+    pub preset_id: String,
+    /// Recorded runs, oldest first.
+    pub runs: Vec<PresetRunSummary>,
+    /// Structured diff between the two most recent runs: overall and per-step
+    /// success flips plus score deltas. Absent with fewer than two runs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<serde_json::Value>,
+    /// Set when the history could not be read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Response metadata for discoverability.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ResponseMetadata>,
+}
+
 /// Summary statistics.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MetricsSummary {
@@ -1410,6 +1606,67 @@ pub struct MetricsResponse {
     /// tools. Present only for the "chains" query.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub chains: Option<serde_json::Value>,
+    /// Retry counters (query="retries"): physical attempts per mode plus
+    /// retries and retry-exhausted counts per `mode/error_kind`, so retries
+    /// are visible separately from logical operations. Present only for the
+    /// "retries" query.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retries: Option<serde_json::Value>,
+}
+
+/// Help entry for a single reasoning mode.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ModeHelp {
+    /// Mode name (e.g. "tree").
+    pub mode: String,
+    /// MCP tool name that invokes the mode (e.g. "reasoning_tree").
+    pub tool: String,
+    /// One-line description of what the mode does.
+    pub description: String,
+    /// Operations the mode distinguishes; empty for single-prompt modes.
+    pub operations: Vec<String>,
+    /// Minimal example input for the tool.
+    pub example_input: serde_json::Value,
+    /// Whether the mode requires extended thinking.
+    pub requires_thinking: bool,
+    /// Extended thinking budget in tokens, when the mode uses one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thinking_budget_tokens: Option<u32>,
+}
+
+/// Response for reasoning tool help.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HelpResponse {
+    /// Help entries, one per mode (a single entry when a mode was requested).
+    pub modes: Vec<ModeHelp>,
+    /// Error message when the requested mode is unknown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response carrying a next-action recommendation for a session.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NextActionResponse {
+    /// Session the recommendation is for.
+    pub session_id: String,
+    /// Recommended tool to call next (absent when the lookup failed).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    /// Recommended operation on that tool, when it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation: Option<String>,
+    /// Why this follow-up fits the session's last step.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Mode of the last thought the recommendation is based on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub based_on_mode: Option<String>,
+    /// Confidence of the last thought the recommendation is based on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub based_on_confidence: Option<f64>,
+    /// Error message when the session could not be read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 // ============================================================================
@@ -1722,6 +1979,138 @@ pub struct RelationshipEdge {
     pub strength: f64,
 }
 
+/// Response from undoing the last thought in a session.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UndoResponse {
+    /// Session the undo was applied to.
+    pub session_id: String,
+    /// The removed thought, for confirmation. `None` when the session had no
+    /// thoughts (nothing to undo).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub removed: Option<ThoughtSummary>,
+    /// Number of thoughts remaining in the session after the undo.
+    pub remaining_thoughts: u32,
+    /// Set when the undo could not run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Response metadata for discoverability.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ResponseMetadata>,
+}
+
+/// Response returning the raw request/response behind a thought.
+///
+/// Populated from the `thought_raw_io` table, which only has rows when
+/// `LINEAR_STORE_RAW_IO` was enabled at the time the thought was produced.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InspectThoughtResponse {
+    /// Thought that was inspected.
+    pub thought_id: String,
+    /// The exact request messages the mode sent, as a JSON array of
+    /// `{role, content}` objects (redacted, size-capped at storage time).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages: Option<serde_json::Value>,
+    /// The unparsed completion text the model returned.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_completion: Option<String>,
+    /// When the exchange was recorded (RFC 3339).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recorded_at: Option<String>,
+    /// Set when no raw IO exists for the thought or the lookup failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Response metadata for discoverability.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ResponseMetadata>,
+}
+
+/// Response from exporting a reasoning session.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExportSessionResponse {
+    /// Session the export was produced for.
+    pub session_id: String,
+    /// Canonical name of the format actually rendered.
+    pub format: String,
+    /// The exported document. Empty on error.
+    pub content: String,
+    /// Error message when the export could not be produced (unknown format,
+    /// missing session, or `dot` on a session with no graph data).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response from diffing two arbitrary stored thoughts.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ThoughtDiffResponse {
+    /// Thought ID of the first result.
+    pub thought_id_a: String,
+    /// Thought ID of the second result.
+    pub thought_id_b: String,
+    /// Structured comparison: a field-level diff for same-type results
+    /// (weighted decisions get rank/score movements), or a textual similarity
+    /// summary when the types differ.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<serde_json::Value>,
+    /// Error message when either thought could not be loaded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response reporting a session's aggregate quality and trend.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SessionQualityResponse {
+    /// Session the quality was computed for.
+    pub session_id: String,
+    /// Recency-weighted quality aggregate (0.0-1.0). Absent when the session
+    /// has no scored thoughts yet.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<f64>,
+    /// Trend versus the session's unweighted mean confidence: "improving",
+    /// "declining", or "stable".
+    pub trend: String,
+    /// Number of thoughts in the session.
+    pub thought_count: u32,
+    /// Set when the quality could not be computed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Response metadata for discoverability.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ResponseMetadata>,
+}
+
+/// Response listing the open questions accumulated across a session.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct OpenQuestionsResponse {
+    /// Session the questions were collected from.
+    pub session_id: String,
+    /// Deduplicated open questions, oldest first. Empty when no reasoning
+    /// step in the session surfaced any.
+    pub open_questions: Vec<String>,
+    /// Set when the questions could not be collected.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Response metadata for discoverability.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ResponseMetadata>,
+}
+
+/// Response from merging one session into another.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MergeSessionsResponse {
+    /// Session that received the merged content.
+    pub target_session_id: String,
+    /// Session that was merged in and deleted.
+    pub source_session_id: String,
+    /// Number of thoughts in the target after the merge.
+    pub total_thoughts: u32,
+    /// Set when the merge could not run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Response metadata for discoverability.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ResponseMetadata>,
+}
+
 // ============================================================================
 // Agent & Skill Responses
 // ============================================================================
@@ -1842,6 +2231,7 @@ impl_into_contents!(
     ReflectionResponse,
     CheckpointResponse,
     AutoResponse,
+    AutoStatsResponse,
     GraphResponse,
     DetectResponse,
     DecisionResponse,
@@ -1850,7 +2240,9 @@ impl_into_contents!(
     MctsResponse,
     CounterfactualResponse,
     PresetResponse,
+    PresetHistoryResponse,
     MetricsResponse,
+    HelpResponse,
     SiStatusResponse,
     SiDiagnosesResponse,
     SiOverridesResponse,
@@ -1862,6 +2254,11 @@ impl_into_contents!(
     ResumeSessionResponse,
     SearchSessionsResponse,
     RelateSessionsResponse,
+    UndoResponse,
+    InspectThoughtResponse,
+    MergeSessionsResponse,
+    SessionQualityResponse,
+    OpenQuestionsResponse,
     AgentInvokeResponse,
     AgentListResponse,
     SkillRunResponse,
@@ -1871,6 +2268,12 @@ impl_into_contents!(
     MetaResponse,
     ConfidenceRouteResponse,
     CrewInvokeResponse,
+    NextActionResponse,
+    DecisionDiffResponse,
+    DecisionChallengeResponse,
+    ThoughtDiffResponse,
+    ExportSessionResponse,
+    SolveResponse,
 );
 
 #[cfg(test)]
@@ -2123,6 +2526,7 @@ mod tests {
             synthesis: Some("Strong evidence".to_string()),
             evidential_support: None,
             pivot_evidence: None,
+            gaps: None,
             bayesian: None,
             validation: None,
             metadata: None,
@@ -2260,6 +2664,7 @@ mod tests {
             invocations: None,
             config: None,
             chains: None,
+            retries: None,
         };
         let contents = response.into_contents();
         assert_eq!(contents.len(), 1);
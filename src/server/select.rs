@@ -0,0 +1,231 @@
+//! Response field selection via JSON pointer.
+//!
+//! Every tool accepts an optional top-level `select` argument holding an
+//! [RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901) JSON pointer
+//! (e.g. `"/balanced_recommendation/option"`). After the mode produces its
+//! typed result, the serialized response is reduced to just the value at the
+//! pointer, so clients that want one field don't have to parse the whole
+//! response.
+//!
+//! Like the `format` argument, selection is applied in the result
+//! serialization layer (`call_tool` in [`super::tools`]), not in the modes or
+//! handlers: handlers always produce full typed responses, and selection is a
+//! uniform transformation of the serialized JSON. Request structs tolerate the
+//! extra `select` key (none of them deny unknown fields), so no per-tool
+//! parameter plumbing is needed.
+//!
+//! Unlike `format` — where a typo silently falls back to JSON — a pointer
+//! that does not resolve returns an error naming the bad path, because
+//! returning the full response where one field was requested would make the
+//! caller's extraction silently wrong.
+
+use rmcp::model::{CallToolResult, ContentBlock, JsonObject};
+use serde_json::Value;
+
+/// A requested response selection (a JSON pointer into the serialized result).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseSelect {
+    pointer: String,
+}
+
+impl ResponseSelect {
+    /// Read the requested selection from a tool call's raw arguments.
+    ///
+    /// Returns `None` when no `select` argument is present or it is not a
+    /// string; validity of the pointer itself is checked when it is applied,
+    /// so the error can name the path.
+    #[must_use]
+    pub fn from_args(arguments: Option<&JsonObject>) -> Option<Self> {
+        arguments
+            .and_then(|args| args.get("select"))
+            .and_then(Value::as_str)
+            .map(|pointer| Self {
+                pointer: pointer.to_string(),
+            })
+    }
+
+    /// Apply this selection to a tool result.
+    ///
+    /// Each JSON text block is replaced with the serialized value at the
+    /// pointer (strings unquoted, everything else pretty-printed JSON).
+    /// Blocks that aren't JSON (or aren't text) pass through untouched. A
+    /// pointer that does not resolve turns the result into an error naming
+    /// the bad path and the available top-level keys.
+    #[must_use]
+    pub fn apply(&self, mut result: CallToolResult) -> CallToolResult {
+        let mut bad_pointer = None;
+
+        result.content = std::mem::take(&mut result.content)
+            .into_iter()
+            .map(|block| {
+                let Some(value) = block
+                    .as_text()
+                    .and_then(|text| serde_json::from_str::<Value>(&text.text).ok())
+                else {
+                    return block;
+                };
+                value.pointer(&self.pointer).map_or_else(
+                    || {
+                        bad_pointer = Some(format!(
+                            "select pointer '{}' not found in response (top-level keys: {}); \
+                             pointers are RFC 6901 paths like \"/session_id\"",
+                            self.pointer,
+                            top_level_keys(&value)
+                        ));
+                        block
+                    },
+                    |selected| ContentBlock::text(render_selected(selected)),
+                )
+            })
+            .collect();
+
+        if let Some(message) = bad_pointer {
+            return CallToolResult::error(vec![ContentBlock::text(message)]);
+        }
+        result
+    }
+}
+
+/// Serialize a selected value: strings unquoted (callers asked for the bare
+/// field), everything else as pretty-printed JSON.
+fn render_selected(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => serde_json::to_string_pretty(other).unwrap_or_else(|_| other.to_string()),
+    }
+}
+
+/// Comma-separated top-level keys of the response, for the error message.
+fn top_level_keys(value: &Value) -> String {
+    value.as_object().map_or_else(
+        || "none".to_string(),
+        |map| map.keys().cloned().collect::<Vec<_>>().join(", "),
+    )
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp,
+    clippy::approx_constant,
+    clippy::unreadable_literal
+)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn decision_result() -> CallToolResult {
+        // Shape of a serialized DecisionResponse, trimmed to what the tests use.
+        let response = json!({
+            "recommendation": "Postgres wins on balance",
+            "balanced_recommendation": {
+                "option": "Postgres",
+                "score": 0.82,
+            },
+            "scores": [
+                {"option": "Postgres", "total": 0.82},
+                {"option": "SQLite", "total": 0.64},
+            ],
+            "session_id": "s-1",
+        });
+        CallToolResult::success(vec![ContentBlock::text(response.to_string())])
+    }
+
+    #[test]
+    fn test_from_args_absent_or_non_string_is_none() {
+        assert!(ResponseSelect::from_args(None).is_none());
+
+        let args = json!({"content": "analyze this"});
+        assert!(ResponseSelect::from_args(args.as_object()).is_none());
+
+        let args = json!({"select": 42});
+        assert!(ResponseSelect::from_args(args.as_object()).is_none());
+    }
+
+    #[test]
+    fn test_from_args_reads_pointer() {
+        let args = json!({"select": "/balanced_recommendation/option"});
+        let select = ResponseSelect::from_args(args.as_object()).unwrap();
+        assert_eq!(select.pointer, "/balanced_recommendation/option");
+    }
+
+    #[test]
+    fn test_select_nested_string_returns_bare_scalar() {
+        let select = ResponseSelect {
+            pointer: "/balanced_recommendation/option".to_string(),
+        };
+        let result = select.apply(decision_result());
+
+        assert_ne!(result.is_error, Some(true));
+        assert_eq!(result.content[0].as_text().unwrap().text, "Postgres");
+    }
+
+    #[test]
+    fn test_select_nested_number_and_array_element() {
+        let select = ResponseSelect {
+            pointer: "/balanced_recommendation/score".to_string(),
+        };
+        let result = select.apply(decision_result());
+        assert_eq!(result.content[0].as_text().unwrap().text, "0.82");
+
+        let select = ResponseSelect {
+            pointer: "/scores/1/option".to_string(),
+        };
+        let result = select.apply(decision_result());
+        assert_eq!(result.content[0].as_text().unwrap().text, "SQLite");
+    }
+
+    #[test]
+    fn test_select_object_returns_json() {
+        let select = ResponseSelect {
+            pointer: "/balanced_recommendation".to_string(),
+        };
+        let result = select.apply(decision_result());
+
+        let text = &result.content[0].as_text().unwrap().text;
+        let value: Value = serde_json::from_str(text).expect("valid JSON");
+        assert_eq!(value["option"], "Postgres");
+    }
+
+    #[test]
+    fn test_invalid_pointer_is_error_naming_the_path() {
+        let select = ResponseSelect {
+            pointer: "/no_such_field/option".to_string(),
+        };
+        let result = select.apply(decision_result());
+
+        assert_eq!(result.is_error, Some(true));
+        let text = &result.content[0].as_text().unwrap().text;
+        assert!(text.contains("/no_such_field/option"));
+        assert!(text.contains("balanced_recommendation"));
+    }
+
+    #[test]
+    fn test_select_leaves_non_json_content_untouched() {
+        let select = ResponseSelect {
+            pointer: "/anything".to_string(),
+        };
+        let result = CallToolResult::success(vec![ContentBlock::text("plain prose, not JSON")]);
+        let applied = select.apply(result);
+
+        assert_ne!(applied.is_error, Some(true));
+        assert_eq!(
+            applied.content[0].as_text().unwrap().text,
+            "plain prose, not JSON"
+        );
+    }
+
+    #[test]
+    fn test_empty_pointer_selects_whole_response() {
+        let select = ResponseSelect {
+            pointer: String::new(),
+        };
+        let result = select.apply(decision_result());
+
+        let text = &result.content[0].as_text().unwrap().text;
+        let value: Value = serde_json::from_str(text).expect("valid JSON");
+        assert_eq!(value["session_id"], "s-1");
+    }
+}
@@ -0,0 +1,194 @@
+//! Sticky-session support (opt-in via `STICKY_SESSION=true`).
+//!
+//! By default every reasoning tool call that omits `session_id` starts a fresh
+//! session. Clients that always work in one session find threading the ID
+//! through every call tedious, so sticky mode remembers the session the last
+//! reasoning call ran in and reuses it when `session_id` is omitted.
+//!
+//! Like response formatting ([`super::format`]), this is applied in
+//! `call_tool` rather than in the handlers: the injection is a uniform
+//! rewrite of the raw arguments before dispatch, and remembering the session
+//! is a uniform read of the serialized result after it. The mode is off by
+//! default ([`crate::config::Config::sticky_session`]) so existing behavior
+//! is unchanged unless explicitly enabled.
+
+use std::sync::Mutex;
+
+use rmcp::model::{CallToolResult, JsonObject};
+use serde_json::Value;
+
+/// Tools for which an omitted `session_id` means "start a new session" — the
+/// calls sticky mode redirects to the remembered session. Discovery and
+/// maintenance tools (search, relate, metrics, ...) are deliberately excluded:
+/// for them an absent `session_id` means "across all sessions", and injecting
+/// one would silently narrow their scope.
+const STICKY_TOOLS: &[&str] = &[
+    "reasoning_linear",
+    "reasoning_tree",
+    "reasoning_divergent",
+    "reasoning_reflection",
+    "reasoning_checkpoint",
+    "reasoning_auto",
+    "reasoning_meta",
+    "reasoning_graph",
+    "reasoning_detect",
+    "reasoning_decision",
+    "reasoning_evidence",
+    "reasoning_timeline",
+    "reasoning_mcts",
+    "reasoning_counterfactual",
+    "reasoning_confidence_route",
+];
+
+/// Last session a reasoning tool ran in on this connection.
+///
+/// Held in [`crate::server::AppState`]; only consulted when sticky mode is
+/// enabled in config.
+#[derive(Debug, Default)]
+pub struct StickySession {
+    last: Mutex<Option<String>>,
+}
+
+impl StickySession {
+    /// Create an empty sticky-session tracker.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fill in the remembered session for a sticky-eligible call that omitted
+    /// `session_id`. An explicit `session_id` (even for another session) is
+    /// never overridden.
+    pub fn inject(&self, tool: &str, arguments: Option<&mut JsonObject>) {
+        if !STICKY_TOOLS.contains(&tool) {
+            return;
+        }
+        let Some(args) = arguments else {
+            return;
+        };
+        if !args.get("session_id").is_none_or(Value::is_null) {
+            return;
+        }
+        if let Ok(guard) = self.last.lock() {
+            if let Some(id) = guard.as_ref() {
+                tracing::debug!(tool, session_id = %id, "Sticky session: reusing last session");
+                args.insert("session_id".to_string(), Value::String(id.clone()));
+            }
+        }
+    }
+
+    /// Remember the session a successful sticky-eligible call ran in, read
+    /// from the `session_id` field of the serialized result.
+    pub fn remember(&self, tool: &str, result: &CallToolResult) {
+        if !STICKY_TOOLS.contains(&tool) || result.is_error == Some(true) {
+            return;
+        }
+        let Some(id) = session_id_from_result(result) else {
+            return;
+        };
+        if let Ok(mut guard) = self.last.lock() {
+            *guard = Some(id);
+        }
+    }
+}
+
+/// Extract a non-empty `session_id` string from the first JSON text block of
+/// a tool result.
+fn session_id_from_result(result: &CallToolResult) -> Option<String> {
+    let text = result.content.first()?.as_text()?;
+    let value: Value = serde_json::from_str(&text.text).ok()?;
+    value
+        .get("session_id")
+        .and_then(Value::as_str)
+        .filter(|id| !id.is_empty())
+        .map(ToString::to_string)
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp,
+    clippy::approx_constant,
+    clippy::unreadable_literal
+)]
+mod tests {
+    use super::*;
+    use rmcp::model::ContentBlock;
+
+    fn result_with_session(id: &str) -> CallToolResult {
+        CallToolResult::success(vec![ContentBlock::text(format!(
+            "{{\"session_id\": \"{id}\", \"content\": \"x\"}}"
+        ))])
+    }
+
+    #[test]
+    fn test_inject_fills_missing_session_id() {
+        let sticky = StickySession::new();
+        sticky.remember("reasoning_linear", &result_with_session("sess-abc"));
+
+        let mut args = JsonObject::new();
+        args.insert("content".to_string(), Value::String("next step".into()));
+        sticky.inject("reasoning_linear", Some(&mut args));
+
+        assert_eq!(args["session_id"], Value::String("sess-abc".into()));
+    }
+
+    #[test]
+    fn test_inject_never_overrides_explicit_session_id() {
+        let sticky = StickySession::new();
+        sticky.remember("reasoning_linear", &result_with_session("sess-abc"));
+
+        let mut args = JsonObject::new();
+        args.insert("session_id".to_string(), Value::String("sess-mine".into()));
+        sticky.inject("reasoning_linear", Some(&mut args));
+
+        assert_eq!(args["session_id"], Value::String("sess-mine".into()));
+    }
+
+    #[test]
+    fn test_inject_skips_non_sticky_tools() {
+        let sticky = StickySession::new();
+        sticky.remember("reasoning_linear", &result_with_session("sess-abc"));
+
+        // relate's missing session_id means "all sessions" — must stay absent.
+        let mut args = JsonObject::new();
+        sticky.inject("reasoning_relate", Some(&mut args));
+        assert!(!args.contains_key("session_id"));
+    }
+
+    #[test]
+    fn test_inject_without_remembered_session_is_noop() {
+        let sticky = StickySession::new();
+        let mut args = JsonObject::new();
+        sticky.inject("reasoning_linear", Some(&mut args));
+        assert!(!args.contains_key("session_id"));
+    }
+
+    #[test]
+    fn test_remember_ignores_errors_and_non_sticky_tools() {
+        let sticky = StickySession::new();
+
+        let mut err = result_with_session("sess-err");
+        err.is_error = Some(true);
+        sticky.remember("reasoning_linear", &err);
+
+        sticky.remember("reasoning_search", &result_with_session("sess-search"));
+
+        let mut args = JsonObject::new();
+        sticky.inject("reasoning_linear", Some(&mut args));
+        assert!(!args.contains_key("session_id"));
+    }
+
+    #[test]
+    fn test_remember_updates_to_latest_session() {
+        let sticky = StickySession::new();
+        sticky.remember("reasoning_linear", &result_with_session("sess-1"));
+        sticky.remember("reasoning_tree", &result_with_session("sess-2"));
+
+        let mut args = JsonObject::new();
+        sticky.inject("reasoning_graph", Some(&mut args));
+        assert_eq!(args["session_id"], Value::String("sess-2".into()));
+    }
+}
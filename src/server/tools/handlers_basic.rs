@@ -9,11 +9,14 @@ use crate::modes::{AutoMode, LinearMode, TreeMode};
 use crate::prompts::ReasoningMode;
 use crate::server::metadata_builders;
 use crate::server::requests::{
-    AutoRequest, DivergentRequest, LinearRequest, MetaRequest, ReflectionRequest, TreeRequest,
+    AutoRequest, AutoStatsRequest, DivergentRequest, LinearRequest, MetaRequest, ReflectionRequest,
+    TreeRequest,
 };
 use crate::server::responses::{
-    AutoResponse, Branch, LinearResponse, MetaResponse, NextCallHint, SkillSuggestion, TreeResponse,
+    AutoResponse, AutoStatsEntry, AutoStatsResponse, Branch, LinearResponse, MetaResponse,
+    NextCallHint, SkillSuggestion, TreeResponse,
 };
+use crate::traits::CompletionProfile;
 
 use super::NO_THINKING;
 
@@ -30,16 +33,6 @@ impl super::ReasoningServer {
             "Tool invocation started"
         );
 
-        let mode = LinearMode::new(
-            Arc::clone(&self.state.storage),
-            Arc::clone(&self.state.client),
-        )
-        .with_defect_sink(crate::self_improvement::heal::DefectSink::new(
-            Arc::clone(&self.state.metrics),
-            Arc::clone(&self.state.defect_log),
-            "reasoning_linear/linear",
-        ));
-
         let input_session_id = req.session_id.clone().unwrap_or_default();
         let session_id_for_metadata = req.session_id.clone();
 
@@ -50,27 +43,56 @@ impl super::ReasoningServer {
             .unwrap_or_else(|| self.state.config.timeout_for_thinking_budget(NO_THINKING));
         let timeout_duration = Duration::from_millis(timeout_ms);
 
-        let result = match tokio::time::timeout(
-            timeout_duration,
-            mode.process(
-                &req.content,
-                req.session_id,
-                req.confidence
-                    .map(super::super::requests::ConfidenceThreshold::value),
-            ),
-        )
-        .await
-        {
-            Ok(inner_result) => inner_result,
-            Err(_elapsed) => {
-                tracing::error!(
-                    tool = "reasoning_linear",
-                    timeout_ms = timeout_ms,
-                    "Tool execution timed out"
-                );
-                Err(ModeError::Timeout {
-                    elapsed_ms: timeout_ms,
-                })
+        // Resolve the per-call completion profile before doing any work; an
+        // unknown name is a caller error, not a reason to burn a completion.
+        let profile = req
+            .profile
+            .as_deref()
+            .map(CompletionProfile::parse)
+            .transpose();
+
+        let result = match profile {
+            Err(e) => Err(e),
+            Ok(profile) => {
+                let mode = LinearMode::new(
+                    Arc::clone(&self.state.storage),
+                    Arc::clone(&self.state.client),
+                )
+                .with_flags(self.state.mode_flags())
+                .with_defect_sink(crate::self_improvement::heal::DefectSink::new(
+                    Arc::clone(&self.state.metrics),
+                    Arc::clone(&self.state.defect_log),
+                    "reasoning_linear/linear",
+                ))
+                .with_language(self.state.resolve_language(req.language.clone()))
+                .with_profile(profile.unwrap_or_default())
+                .with_prompt_version(req.prompt_version.clone())
+                .with_confidence_floor(self.state.config.confidence_floor)
+                .with_raw_io_capture(self.state.config.linear_store_raw_io);
+
+                match tokio::time::timeout(
+                    timeout_duration,
+                    mode.process(
+                        &req.content,
+                        req.session_id,
+                        req.confidence
+                            .map(super::super::requests::ConfidenceThreshold::value),
+                    ),
+                )
+                .await
+                {
+                    Ok(inner_result) => inner_result,
+                    Err(_elapsed) => {
+                        tracing::error!(
+                            tool = "reasoning_linear",
+                            timeout_ms = timeout_ms,
+                            "Tool execution timed out"
+                        );
+                        Err(ModeError::Timeout {
+                            elapsed_ms: timeout_ms,
+                        })
+                    }
+                }
             }
         };
 
@@ -164,7 +186,10 @@ impl super::ReasoningServer {
     }
     pub(super) async fn handle_tree(&self, req: TreeRequest) -> TreeResponse {
         let timer = Timer::start();
-        let operation = req.operation.as_deref().unwrap_or("create");
+        // Resolve the default explicitly so metadata reports what actually ran.
+        let operation = ReasoningMode::Tree
+            .resolve_operation(req.operation.as_deref())
+            .unwrap_or_default();
 
         tracing::info!(
             tool = "reasoning_tree",
@@ -178,11 +203,13 @@ impl super::ReasoningServer {
             Arc::clone(&self.state.storage),
             Arc::clone(&self.state.client),
         )
+        .with_flags(self.state.mode_flags())
         .with_defect_sink(crate::self_improvement::heal::DefectSink::new(
             Arc::clone(&self.state.metrics),
             Arc::clone(&self.state.defect_log),
             "reasoning_tree/tree",
-        ));
+        ))
+        .with_language(self.state.resolve_language(req.language.clone()));
 
         let session_id = req.session_id.clone().unwrap_or_default();
 
@@ -593,7 +620,10 @@ impl super::ReasoningServer {
         let mode = AutoMode::new(
             Arc::clone(&self.state.storage),
             Arc::clone(&self.state.client),
-        );
+        )
+        .with_flags(self.state.mode_flags())
+        .with_language(self.state.resolve_language(req.language.clone()))
+        .with_history_bias(self.state.config.auto_history_bias);
 
         // Apply tool-level timeout (NO_THINKING - fast mode)
         let timeout_ms = self.state.config.timeout_for_thinking_budget(NO_THINKING);
@@ -638,6 +668,19 @@ impl super::ReasoningServer {
 
         match result {
             Ok(resp) => {
+                // Record the selection for reasoning_auto_stats. Best-effort:
+                // history must not fail the tool call. The resulting score is
+                // backfilled when the chosen mode runs in this session.
+                let selection = crate::storage::StoredAutoSelection::new(
+                    uuid::Uuid::new_v4().to_string(),
+                    &resp.session_id,
+                    resp.selected_mode.to_string(),
+                    resp.confidence,
+                );
+                if let Err(e) = self.state.storage.save_auto_selection(&selection).await {
+                    tracing::warn!(error = %e, "Failed to record auto selection");
+                }
+
                 // Pull the model's suggested parameters so execute can apply them
                 // (read before selection_meta consumes the map). Keys are best-effort.
                 let sugg = &resp.suggested_parameters;
@@ -694,6 +737,9 @@ impl super::ReasoningServer {
                                             .ok()
                                     }),
                                     timeout_ms: None,
+                                    language: req.language.clone(),
+                                    profile: None,
+                                    prompt_version: None,
                                 })
                                 .await;
                             return AutoResponse {
@@ -715,7 +761,9 @@ impl super::ReasoningServer {
                                     num_perspectives: sugg_num_perspectives,
                                     challenge_assumptions: sugg_challenge,
                                     force_rebellion: sugg_rebellion,
+                                    include_thinking: None,
                                     progress_token: None,
+                                    language: req.language.clone(),
                                 })
                                 .await;
                             return AutoResponse {
@@ -739,6 +787,7 @@ impl super::ReasoningServer {
                                     max_iterations: sugg_max_iterations,
                                     quality_threshold: sugg_quality_threshold,
                                     progress_token: None,
+                                    language: req.language.clone(),
                                 })
                                 .await;
                             return AutoResponse {
@@ -807,6 +856,45 @@ impl super::ReasoningServer {
         }
     }
 
+    pub(super) async fn handle_auto_stats(&self, _req: AutoStatsRequest) -> AutoStatsResponse {
+        let timer = Timer::start();
+
+        let result = self.state.storage.auto_selection_stats().await;
+        let success = result.is_ok();
+        self.state
+            .metrics
+            .record(MetricEvent::new("auto_stats", timer.elapsed_ms(), success));
+
+        match result {
+            Ok(stats) => {
+                let stats: Vec<AutoStatsEntry> = stats
+                    .into_iter()
+                    .map(|s| AutoStatsEntry {
+                        selected_mode: s.selected_mode,
+                        selections: s.selections,
+                        avg_confidence: s.avg_confidence,
+                        avg_score: s.avg_score,
+                        scored: s.scored,
+                    })
+                    .collect();
+                let total_selections = stats.iter().map(|s| s.selections).sum();
+                AutoStatsResponse {
+                    stats,
+                    total_selections,
+                    error: None,
+                }
+            }
+            Err(e) => {
+                tracing::error!(tool = "reasoning_auto_stats", error = %e, "Stats query failed");
+                AutoStatsResponse {
+                    stats: Vec::new(),
+                    total_selections: 0,
+                    error: Some(format!("Failed to query auto selections: {e}")),
+                }
+            }
+        }
+    }
+
     pub(super) async fn handle_meta(&self, req: MetaRequest) -> MetaResponse {
         let timer = Timer::start();
 
@@ -820,7 +908,8 @@ impl super::ReasoningServer {
         let mode = MetaMode::new(
             Arc::clone(&self.state.storage),
             Arc::clone(&self.state.client),
-        );
+        )
+        .with_flags(self.state.mode_flags());
 
         let timeout_ms = self.state.config.timeout_for_thinking_budget(NO_THINKING);
         let timeout_duration = Duration::from_millis(timeout_ms);
@@ -78,7 +78,12 @@ impl super::ReasoningServer {
         let mode = DivergentMode::new(
             Arc::clone(&self.state.storage),
             Arc::clone(&self.state.client),
-        );
+        )
+        .with_flags(self.state.mode_flags())
+        .with_language(self.state.resolve_language(req.language.clone()))
+        .with_include_thinking(req.include_thinking.unwrap_or(false))
+        .with_per_perspective(self.state.config.divergent_per_perspective)
+        .with_perspective_concurrency(self.state.config.divergent_max_concurrency);
 
         // Create progress reporter (use progress_token or generate one)
         let progress_token = req.progress_token.unwrap_or_else(|| {
@@ -239,9 +244,14 @@ impl super::ReasoningServer {
         let mode = ReflectionMode::new(
             Arc::clone(&self.state.storage),
             Arc::clone(&self.state.client),
-        );
+        )
+        .with_flags(self.state.mode_flags())
+        .with_language(self.state.resolve_language(req.language.clone()));
 
-        let operation = req.operation.as_deref().unwrap_or("process");
+        // Resolve the default explicitly so metadata reports what actually ran.
+        let operation = ReasoningMode::Reflection
+            .resolve_operation(req.operation.as_deref())
+            .unwrap_or_default();
 
         // Create progress reporter (use progress_token or generate one)
         let progress_token = req.progress_token.unwrap_or_else(|| {
@@ -506,7 +516,8 @@ impl super::ReasoningServer {
         let mode = CheckpointMode::new(
             Arc::clone(&self.state.storage),
             Arc::clone(&self.state.client),
-        );
+        )
+        .with_flags(self.state.mode_flags());
 
         let operation = req.operation.as_str();
         let (response, success) = match operation {
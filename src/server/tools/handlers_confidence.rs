@@ -4,14 +4,16 @@ use std::time::Duration;
 use crate::error::enhanced::ComplexityMetrics;
 use crate::error::ModeError;
 use crate::metrics::{MetricEvent, Timer};
-use crate::modes::AutoMode;
+use crate::modes::{AutoMode, EscalationPolicy, EscalationRung, GraphMode};
 use crate::server::metadata_builders;
 use crate::server::requests::{
-    ConfidenceRouteRequest, DivergentRequest, LinearRequest, TreeRequest,
+    ConfidenceRouteRequest, DivergentRequest, LinearRequest, SolveRequest, TreeRequest,
+};
+use crate::server::responses::{
+    ConfidenceRouteResponse, NextCallHint, SolveResponse, SolveRungOutcome,
 };
-use crate::server::responses::{ConfidenceRouteResponse, NextCallHint};
 
-use super::NO_THINKING;
+use super::{NO_THINKING, STANDARD_THINKING};
 
 impl super::ReasoningServer {
     pub(super) async fn handle_confidence_route(
@@ -41,7 +43,9 @@ impl super::ReasoningServer {
         let auto_mode = AutoMode::new(
             Arc::clone(&self.state.storage),
             Arc::clone(&self.state.client),
-        );
+        )
+        .with_flags(self.state.mode_flags())
+        .with_language(self.state.resolve_language(None));
 
         let timeout_ms = self.state.config.timeout_for_thinking_budget(NO_THINKING);
         let timeout_duration = Duration::from_millis(timeout_ms);
@@ -166,6 +170,9 @@ impl super::ReasoningServer {
                         session_id: Some(auto_session_id.clone()),
                         confidence: None,
                         timeout_ms: None,
+                        language: None,
+                        profile: None,
+                        prompt_version: None,
                     })
                     .await;
                 let next = exec.next_call.clone();
@@ -183,7 +190,9 @@ impl super::ReasoningServer {
                         num_perspectives: None,
                         challenge_assumptions: None,
                         force_rebellion: None,
+                        include_thinking: None,
                         progress_token: None,
+                        language: None,
                     })
                     .await;
                 (
@@ -201,6 +210,7 @@ impl super::ReasoningServer {
                         branch_id: None,
                         num_branches: None,
                         completed: None,
+                        language: None,
                     })
                     .await;
                 let tree_session = exec.session_id.clone();
@@ -264,6 +274,249 @@ impl super::ReasoningServer {
             next_call,
         }
     }
+
+    pub(super) async fn handle_solve(&self, req: SolveRequest) -> SolveResponse {
+        let timer = Timer::start();
+
+        tracing::info!(
+            tool = "reasoning_solve",
+            content_length = req.content.len(),
+            ladder = ?req.ladder,
+            threshold = ?req.confidence_threshold,
+            "Tool invocation started"
+        );
+
+        // Caller-supplied threshold wins; otherwise the live Config default.
+        let threshold = req
+            .confidence_threshold
+            .unwrap_or(self.state.config.high_confidence_threshold);
+        let policy = req.ladder.as_deref().map_or_else(
+            || Ok(EscalationPolicy::default_ladder(threshold)),
+            |names| EscalationPolicy::from_names(names, threshold),
+        );
+        let policy = match policy {
+            Ok(p) => p,
+            Err(e) => {
+                self.state
+                    .metrics
+                    .record(MetricEvent::new("solve", timer.elapsed_ms(), false));
+                return SolveResponse {
+                    session_id: req.session_id.unwrap_or_default(),
+                    solved: false,
+                    threshold: threshold.clamp(0.0, 1.0),
+                    best_rung: String::new(),
+                    best_confidence: 0.0,
+                    result: serde_json::Value::Null,
+                    path: Vec::new(),
+                    error: Some(e.to_string()),
+                };
+            }
+        };
+
+        // Walk the ladder. Every rung runs in the same session so later rungs
+        // see the earlier attempts as context; a failed rung contributes a
+        // 0.0-confidence path entry and the ladder continues.
+        let mut session_id = req.session_id.clone();
+        let mut path = Vec::new();
+        let mut best: Option<(EscalationRung, f64, serde_json::Value)> = None;
+        let mut solved = false;
+
+        for &rung in &policy.rungs {
+            let (confidence, result, error, rung_session) = self
+                .run_solve_rung(rung, &req.content, session_id.clone())
+                .await;
+
+            if !rung_session.is_empty() {
+                session_id = Some(rung_session);
+            }
+            let met = error.is_none() && policy.met(confidence);
+            path.push(SolveRungOutcome {
+                rung: rung.name().to_string(),
+                confidence,
+                met_threshold: met,
+                error: error.clone(),
+            });
+            if error.is_none() && best.as_ref().is_none_or(|(_, c, _)| confidence > *c) {
+                best = Some((rung, confidence, result));
+            }
+            if met {
+                solved = true;
+                break;
+            }
+        }
+
+        let elapsed_ms = timer.elapsed_ms();
+        let success = best.is_some();
+        let effective_session = session_id.unwrap_or_default();
+        self.state
+            .metrics
+            .record(MetricEvent::new("solve", elapsed_ms, success));
+        self.state
+            .metrics
+            .record_tool_use(&effective_session, "reasoning_solve", success);
+
+        tracing::info!(
+            tool = "reasoning_solve",
+            elapsed_ms = elapsed_ms,
+            rungs_run = path.len(),
+            solved = solved,
+            "Tool invocation completed"
+        );
+
+        match best {
+            Some((rung, confidence, result)) => SolveResponse {
+                session_id: effective_session,
+                solved,
+                threshold: policy.confidence_threshold,
+                best_rung: rung.name().to_string(),
+                best_confidence: confidence,
+                result,
+                path,
+                error: None,
+            },
+            None => SolveResponse {
+                session_id: effective_session,
+                solved: false,
+                threshold: policy.confidence_threshold,
+                best_rung: String::new(),
+                best_confidence: 0.0,
+                result: serde_json::Value::Null,
+                path,
+                error: Some(
+                    "Every rung of the ladder failed — see path for per-rung errors".to_string(),
+                ),
+            },
+        }
+    }
+
+    /// Run one rung of the escalation ladder.
+    ///
+    /// Returns `(confidence, serialized result, error, session_id)`. Linear
+    /// and tree reuse their tool handlers (which never fail outright — errors
+    /// come back inside the response); graph drives the mode directly so the
+    /// rung's confidence can be read off the finalize conclusions.
+    async fn run_solve_rung(
+        &self,
+        rung: EscalationRung,
+        content: &str,
+        session_id: Option<String>,
+    ) -> (f64, serde_json::Value, Option<String>, String) {
+        match rung {
+            EscalationRung::Linear => {
+                let resp = self
+                    .handle_linear(LinearRequest {
+                        content: content.to_string(),
+                        session_id,
+                        confidence: None,
+                        timeout_ms: None,
+                        language: None,
+                        profile: None,
+                        prompt_version: None,
+                    })
+                    .await;
+                // A failed linear run has an empty thought_id and carries the
+                // error text in `content`.
+                let error = resp.thought_id.is_empty().then(|| resp.content.clone());
+                let session = resp.session_id.clone();
+                let confidence = if error.is_none() {
+                    resp.confidence
+                } else {
+                    0.0
+                };
+                (
+                    confidence,
+                    serde_json::to_value(&resp)
+                        .unwrap_or_else(|_| serde_json::json!({"error": "serialize failed"})),
+                    error,
+                    session,
+                )
+            }
+            EscalationRung::Tree => {
+                let resp = self
+                    .handle_tree(TreeRequest {
+                        operation: Some("create".to_string()),
+                        content: Some(content.to_string()),
+                        session_id,
+                        branch_id: None,
+                        num_branches: None,
+                        completed: None,
+                        language: None,
+                    })
+                    .await;
+                // A tree create without branches failed; the error text is in
+                // `recommendation`. The rung's confidence is the best branch
+                // score — the strongest line of exploration found.
+                let confidence = resp.branches.as_ref().map_or(0.0, |branches| {
+                    branches.iter().fold(0.0_f64, |best, b| best.max(b.score))
+                });
+                let error = resp.branches.is_none().then(|| {
+                    resp.recommendation
+                        .clone()
+                        .unwrap_or_else(|| "tree create failed".to_string())
+                });
+                let session = resp.session_id.clone();
+                (
+                    confidence,
+                    serde_json::to_value(&resp)
+                        .unwrap_or_else(|_| serde_json::json!({"error": "serialize failed"})),
+                    error,
+                    session,
+                )
+            }
+            EscalationRung::Graph => {
+                let mode = GraphMode::new(
+                    Arc::clone(&self.state.storage),
+                    Arc::clone(&self.state.client),
+                )
+                .with_flags(self.state.mode_flags())
+                .with_language(self.state.resolve_language(None))
+                .with_max_graph_nodes(self.state.config.graph_max_nodes)
+                .with_max_returned_children(self.state.config.graph_max_returned_children)
+                .with_rubric_llm_weight(self.state.config.rubric_llm_weight);
+                let timeout_ms = self
+                    .state
+                    .config
+                    .timeout_for_thinking_budget(STANDARD_THINKING);
+                // init + generate + finalize under one budget: the rung is a
+                // single ladder step, not three separate tool calls.
+                let outcome = tokio::time::timeout(Duration::from_millis(timeout_ms), async {
+                    let init = mode.init(content, session_id).await?;
+                    let sid = init.session_id;
+                    mode.generate(Some(content), None, Some(sid.clone()))
+                        .await?;
+                    mode.finalize(content, Some(sid)).await
+                })
+                .await
+                .unwrap_or(Err(ModeError::Timeout {
+                    elapsed_ms: timeout_ms,
+                }));
+                match outcome {
+                    Ok(finalize) => {
+                        // The rung's confidence is the strongest conclusion.
+                        let confidence = finalize
+                            .conclusions
+                            .iter()
+                            .fold(0.0_f64, |best, c| best.max(c.confidence));
+                        let session = finalize.session_id.clone();
+                        (
+                            confidence,
+                            serde_json::to_value(&finalize).unwrap_or_else(
+                                |_| serde_json::json!({"error": "serialize failed"}),
+                            ),
+                            None,
+                            session,
+                        )
+                    }
+                    Err(e) => (
+                        0.0,
+                        serde_json::Value::Null,
+                        Some(e.to_string()),
+                        String::new(),
+                    ),
+                }
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -3,13 +3,19 @@ use std::time::Duration;
 
 use crate::error::enhanced::ComplexityMetrics;
 use crate::metrics::{MetricEvent, Timer};
-use crate::modes::{DecisionMode, DecisionValidation, EvidenceAnalysis, EvidenceMode};
+use crate::modes::{
+    diff_decisions, DecisionMode, DecisionValidation, EvidenceAnalysis, EvidenceMode,
+    WeightedResponse,
+};
 use crate::prompts::ReasoningMode;
 use crate::server::metadata_builders;
-use crate::server::requests::{DecisionRequest, EvidenceRequest};
+use crate::server::requests::{
+    DecisionChallengeRequest, DecisionDiffRequest, DecisionRequest, EvidenceRequest,
+};
 use crate::server::responses::{
-    BayesianBreakdown, BayesianEvidence, ComparisonInfo, CredibilityBreakdown, CriterionInfo,
-    DecisionBreakdown, DecisionResponse, DecisionValidationInfo, DistanceInfo, EvidenceAssessment,
+    BayesianBreakdown, BayesianEvidence, ComparisonInfo, ConfidenceInterval, CredibilityBreakdown,
+    CriterionInfo, DecisionBreakdown, DecisionChallengeResponse, DecisionDiffResponse,
+    DecisionResponse, DecisionValidationInfo, DistanceInfo, EvidenceAssessment, EvidenceGapInfo,
     EvidenceResponse, EvidenceValidationInfo, PairwiseBreakdown, QualityBreakdown, RankedOption,
     StakeholderMap, TopsisBreakdown, TopsisCriterionInfo, WeightedBreakdown,
 };
@@ -133,7 +139,12 @@ impl super::ReasoningServer {
         let mode = DecisionMode::new(
             Arc::clone(&self.state.storage),
             Arc::clone(&self.state.client),
-        );
+        )
+        .with_flags(self.state.mode_flags())
+        .with_language(self.state.resolve_language(req.language.clone()))
+        // An unknown strategy surfaces through the weighted error arm: the
+        // mode rejects it before any API call.
+        .with_normalization(req.normalization.clone());
 
         let base_content = req
             .question
@@ -155,7 +166,10 @@ impl super::ReasoningServer {
         };
         let content = content.as_str();
 
-        let decision_type = req.decision_type.as_deref().unwrap_or("weighted");
+        // Resolve the default explicitly so metadata reports what actually ran.
+        let decision_type = ReasoningMode::Decision
+            .resolve_operation(req.decision_type.as_deref())
+            .unwrap_or_default();
 
         // Apply tool-level timeout to prevent indefinite hangs
         let timeout_ms = self.state.config.timeout_for_thinking_budget(DEEP_THINKING);
@@ -166,6 +180,24 @@ impl super::ReasoningServer {
             match decision_type_for_timeout.as_str() {
                 "weighted" => match mode.weighted(content, req.session_id).await {
                     Ok(resp) => {
+                        // Persist the full analysis on the thought so
+                        // reasoning_decision_diff can reload and compare it
+                        // later. Best-effort: the response stands either way.
+                        match serde_json::to_string(&resp) {
+                            Ok(json) => {
+                                if let Err(e) = self
+                                    .state
+                                    .storage
+                                    .set_thought_metadata(&resp.thought_id, &json)
+                                    .await
+                                {
+                                    tracing::warn!(error = %e, "Failed to persist weighted analysis for later diffing");
+                                }
+                            }
+                            Err(e) => {
+                                tracing::warn!(error = %e, "Failed to serialize weighted analysis for later diffing");
+                            }
+                        }
                         let recommendation = resp
                             .ranking
                             .first()
@@ -270,6 +302,7 @@ impl super::ReasoningServer {
                                     })
                                     .collect(),
                                 consistency_check: resp.consistency_check.clone(),
+                                consistency_ratio: resp.consistency_ratio,
                             }),
                         };
                         (
@@ -557,6 +590,137 @@ impl super::ReasoningServer {
         response
     }
 
+    pub(super) async fn handle_decision_diff(
+        &self,
+        req: DecisionDiffRequest,
+    ) -> DecisionDiffResponse {
+        let timer = Timer::start();
+
+        let loaded_a = self.load_weighted_analysis(&req.thought_id_a).await;
+        let loaded_b = self.load_weighted_analysis(&req.thought_id_b).await;
+
+        let (diff, error, session_id) = match (loaded_a, loaded_b) {
+            (Ok(a), Ok(b)) => {
+                let session_id = a.session_id.clone();
+                let diff = diff_decisions(&a, &b);
+                match serde_json::to_value(&diff) {
+                    Ok(value) => (Some(value), None, session_id),
+                    Err(e) => (
+                        None,
+                        Some(format!("Failed to serialize diff: {e}")),
+                        session_id,
+                    ),
+                }
+            }
+            (Err(e), _) | (Ok(_), Err(e)) => (None, Some(e), String::new()),
+        };
+
+        let success = error.is_none();
+        self.state.metrics.record(MetricEvent::new(
+            "decision_diff",
+            timer.elapsed_ms(),
+            success,
+        ));
+        self.state
+            .metrics
+            .record_tool_use(&session_id, "reasoning_decision_diff", success);
+
+        DecisionDiffResponse {
+            thought_id_a: req.thought_id_a,
+            thought_id_b: req.thought_id_b,
+            diff,
+            error,
+        }
+    }
+
+    pub(super) async fn handle_decision_challenge(
+        &self,
+        req: DecisionChallengeRequest,
+    ) -> DecisionChallengeResponse {
+        let timer = Timer::start();
+
+        let (challenge, error, session_id) =
+            match self.load_weighted_analysis(&req.prior_thought_id).await {
+                Ok(prior) => {
+                    let mode = DecisionMode::new(
+                        Arc::clone(&self.state.storage),
+                        Arc::clone(&self.state.client),
+                    )
+                    .with_flags(self.state.mode_flags())
+                    .with_language(self.state.resolve_language(req.language.clone()));
+                    match mode
+                        .challenge(&req.prior_thought_id, &prior, req.session_id.clone())
+                        .await
+                    {
+                        Ok(resp) => {
+                            let session_id = resp.session_id.clone();
+                            match serde_json::to_value(&resp) {
+                                Ok(value) => (Some(value), None, session_id),
+                                Err(e) => (
+                                    None,
+                                    Some(format!("Failed to serialize challenge: {e}")),
+                                    session_id,
+                                ),
+                            }
+                        }
+                        Err(e) => (
+                            None,
+                            Some(format!("Challenge failed: {e}")),
+                            prior.session_id.clone(),
+                        ),
+                    }
+                }
+                Err(e) => (None, Some(e), String::new()),
+            };
+
+        let success = error.is_none();
+        self.state.metrics.record(MetricEvent::new(
+            "decision_challenge",
+            timer.elapsed_ms(),
+            success,
+        ));
+        self.state
+            .metrics
+            .record_tool_use(&session_id, "reasoning_decision_challenge", success);
+
+        DecisionChallengeResponse {
+            prior_thought_id: req.prior_thought_id,
+            challenge,
+            error,
+        }
+    }
+
+    /// Load the full [`WeightedResponse`] persisted on a decision thought.
+    ///
+    /// Errors are user-facing strings: the diff tool reports them verbatim so
+    /// the caller knows which thought id failed and why.
+    async fn load_weighted_analysis(&self, thought_id: &str) -> Result<WeightedResponse, String> {
+        let thought = self
+            .state
+            .storage
+            .get_stored_thought(thought_id)
+            .await
+            .map_err(|e| format!("Failed to read thought '{thought_id}': {e}"))?
+            .ok_or_else(|| format!("Thought '{thought_id}' not found"))?;
+
+        if thought.mode != "decision_weighted" {
+            return Err(format!(
+                "Thought '{thought_id}' has mode '{}' — diffing requires two decision_weighted thoughts",
+                thought.mode
+            ));
+        }
+
+        let metadata = thought.metadata.ok_or_else(|| {
+            format!(
+                "Thought '{thought_id}' has no stored analysis — rerun reasoning_decision \
+                 type='weighted' to produce a diffable result"
+            )
+        })?;
+
+        serde_json::from_str(&metadata)
+            .map_err(|e| format!("Thought '{thought_id}' has an unreadable stored analysis: {e}"))
+    }
+
     pub(super) async fn handle_evidence(&self, req: EvidenceRequest) -> EvidenceResponse {
         let timer = Timer::start();
         // Effective session id for tool-chain linking (the response carries none).
@@ -564,9 +728,16 @@ impl super::ReasoningServer {
         let mode = EvidenceMode::new(
             Arc::clone(&self.state.storage),
             Arc::clone(&self.state.client),
-        );
+        )
+        .with_flags(self.state.mode_flags())
+        .with_language(self.state.resolve_language(req.language.clone()))
+        .with_chunking(req.chunk.unwrap_or(false))
+        .with_interval_width(self.state.config.evidence_interval_width);
 
-        let evidence_type = req.evidence_type.as_deref().unwrap_or("assess");
+        // Resolve the default explicitly so metadata reports what actually ran.
+        let evidence_type = ReasoningMode::Evidence
+            .resolve_operation(req.evidence_type.as_deref())
+            .unwrap_or_default();
         let content = req
             .claim
             .as_deref()
@@ -608,6 +779,9 @@ impl super::ReasoningServer {
                             .collect();
                         let a = resp.overall_assessment;
                         let pivot = a.pivot_evidence.clone();
+                        let gaps = a.gaps_by_priority();
+                        let gap_texts: Vec<&str> =
+                            gaps.iter().map(|g| g.gap.as_str()).collect();
                         (
                             EvidenceResponse {
                                 overall_credibility: resp.confidence_in_conclusion,
@@ -621,10 +795,20 @@ impl super::ReasoningServer {
                                     "Strengths: {}. Weaknesses: {}. Gaps: {}",
                                     a.key_strengths.join(", "),
                                     a.key_weaknesses.join(", "),
-                                    a.gaps.join(", ")
+                                    gap_texts.join(", ")
                                 )),
                                 evidential_support: Some(a.evidential_support),
                                 pivot_evidence: (!pivot.is_empty()).then_some(pivot),
+                                gaps: Some(
+                                    gaps.into_iter()
+                                        .map(|g| EvidenceGapInfo {
+                                            gap: g.gap,
+                                            suggested_action: (!g.suggested_action.is_empty())
+                                                .then_some(g.suggested_action),
+                                            priority: g.priority.as_str().to_string(),
+                                        })
+                                        .collect(),
+                                ),
                                 bayesian: None,
                                 validation: None,
                                 metadata: None,
@@ -655,6 +839,7 @@ impl super::ReasoningServer {
                             )),
                             evidential_support: None,
                             pivot_evidence: None,
+                            gaps: None,
                             bayesian: None,
                             validation: None,
                             metadata: None,
@@ -705,13 +890,19 @@ impl super::ReasoningServer {
                                 // not just the first piece.
                                 likelihood_ratio: Some(combined_bf),
                                 entropy: Some(binary_entropy(resp.posterior.probability)),
-                                confidence_interval: None,
+                                // Credible interval from propagating likelihood
+                                // uncertainty through the update.
+                                confidence_interval: Some(ConfidenceInterval {
+                                    lower: resp.credible_interval.lower,
+                                    upper: resp.credible_interval.upper,
+                                }),
                                 synthesis: Some(format!(
                                     "{}. Sensitivity: {}",
                                     resp.belief_update.interpretation, resp.sensitivity
                                 )),
                                 evidential_support: None,
                                 pivot_evidence: None,
+                                gaps: None,
                                 bayesian: Some(bayesian),
                                 validation: Some(validation),
                                 metadata: None,
@@ -742,6 +933,7 @@ impl super::ReasoningServer {
                             )),
                             evidential_support: None,
                             pivot_evidence: None,
+                            gaps: None,
                             bayesian: None,
                             validation: None,
                             metadata: None,
@@ -764,6 +956,7 @@ impl super::ReasoningServer {
                         )),
                         evidential_support: None,
                         pivot_evidence: None,
+                        gaps: None,
                         bayesian: None,
                         validation: None,
                         metadata: None,
@@ -794,6 +987,7 @@ impl super::ReasoningServer {
                         synthesis: Some(format!("Tool execution timed out after {}ms", timeout_ms)),
                         evidential_support: None,
                         pivot_evidence: None,
+                        gaps: None,
                         bayesian: None,
                         validation: None,
                         metadata: None,
@@ -856,6 +1050,8 @@ mod bayes_tests {
             likelihood_if_true: lt,
             likelihood_if_false: lf,
             bayes_factor: bf,
+            likelihood_if_true_range: None,
+            likelihood_if_false_range: None,
         }
     }
 
@@ -4,63 +4,17 @@ use std::time::Duration;
 use crate::error::enhanced::ComplexityMetrics;
 use crate::error::ModeError;
 use crate::metrics::{MetricEvent, Timer};
-use crate::modes::{DetectMode, GraphMode};
+use crate::modes::{DetectMode, ExpandedFrontier, GraphMode, RebuttalStrength};
 use crate::prompts::ReasoningMode;
 use crate::server::metadata_builders;
 use crate::server::requests::{DetectRequest, GraphRequest};
 use crate::server::responses::{
     ArgumentStructureInfo, DetectResponse, DetectValidationInfo, Detection, GraphNode,
-    GraphResponse, GraphState, GraphValidationInfo,
+    GraphResponse, GraphState, GraphValidationInfo, PremiseAssessmentInfo,
 };
 
 use super::{DEEP_THINKING, STANDARD_THINKING};
 
-/// Normalize text for quote-grounding comparison: collapse whitespace, lowercase.
-fn normalize_for_grounding(s: &str) -> String {
-    s.split_whitespace()
-        .collect::<Vec<_>>()
-        .join(" ")
-        .to_lowercase()
-}
-
-/// Length of the longest run of words present contiguously in both slices.
-fn longest_common_word_run(a: &[&str], b: &[&str]) -> usize {
-    if a.is_empty() || b.is_empty() {
-        return 0;
-    }
-    let mut prev = vec![0usize; b.len() + 1];
-    let mut best = 0;
-    for ai in a {
-        let mut cur = vec![0usize; b.len() + 1];
-        for (j, bj) in b.iter().enumerate() {
-            if ai == bj {
-                cur[j + 1] = prev[j] + 1;
-                best = best.max(cur[j + 1]);
-            }
-        }
-        prev = cur;
-    }
-    best
-}
-
-/// True when the cited `quote` is grounded in `content`.
-///
-/// Models rarely quote verbatim — they embed the actual passage inside longer
-/// commentary (e.g. `"…never let me down - showing a preference for…"`). So
-/// rather than demand an exact substring, this accepts the quote when a
-/// substantial contiguous run of its words appears in the content: a run of ≥5
-/// words, or ≥half the quote (with a 3-word floor). An empty quote is grounded.
-fn quote_is_grounded(content_normalized: &str, quote: &str) -> bool {
-    let q = normalize_for_grounding(quote);
-    if q.is_empty() || content_normalized.contains(&q) {
-        return true;
-    }
-    let q_words: Vec<&str> = q.split(' ').collect();
-    let c_words: Vec<&str> = content_normalized.split(' ').collect();
-    let run = longest_common_word_run(&q_words, &c_words);
-    run >= 5 || (run >= 3 && run * 2 >= q_words.len())
-}
-
 /// Build a detect validation from a reported count vs. the actual detections
 /// and the names of any detections whose quote was not found in the content.
 fn build_detect_validation(
@@ -134,7 +88,12 @@ impl super::ReasoningServer {
         let mode = GraphMode::new(
             Arc::clone(&self.state.storage),
             Arc::clone(&self.state.client),
-        );
+        )
+        .with_flags(self.state.mode_flags())
+        .with_max_graph_nodes(self.state.config.graph_max_nodes)
+        .with_max_returned_children(self.state.config.graph_max_returned_children)
+        .with_rubric_llm_weight(self.state.config.rubric_llm_weight)
+        .with_language(self.state.resolve_language(req.language.clone()));
 
         let session_id = req.session_id;
         let content = req.content.as_deref().unwrap_or("");
@@ -279,6 +238,50 @@ impl super::ReasoningServer {
                             metadata: None,
                         })
                 }
+                "apply_prune" => {
+                    let sid = session_id.clone();
+                    let candidates = req.node_ids.clone().unwrap_or_default();
+                    let force = req.force.unwrap_or(false);
+                    mode.apply_prune(&session_id, &candidates, force)
+                        .await
+                        .map(move |r| {
+                            let protected_note = (!r.protected.is_empty()).then(|| {
+                                r.protected
+                                    .iter()
+                                    .map(|p| format!("{} ({})", p.node_id, p.reason))
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            });
+                            GraphResponse {
+                                session_id: sid,
+                                node_id: None,
+                                nodes: None,
+                                aggregated_insight: Some(format!(
+                                    "Removed {} node(s): [{}]{}{}",
+                                    r.removed.len(),
+                                    r.removed.join(", "),
+                                    protected_note.map_or_else(String::new, |p| format!(
+                                        "; protected: [{p}] — pass force=true to remove anyway"
+                                    )),
+                                    if r.missing.is_empty() {
+                                        String::new()
+                                    } else {
+                                        format!("; not in graph: [{}]", r.missing.join(", "))
+                                    },
+                                )),
+                                conclusions: None,
+                                state: Some(GraphState {
+                                    total_nodes: 0,
+                                    active_nodes: 0,
+                                    max_depth: 0,
+                                    pruned_count: r.removed.len() as u32,
+                                }),
+                                validation: None,
+                                persistence_warning: None,
+                                metadata: None,
+                            }
+                        })
+                }
                 "finalize" => {
                     let sid = session_id.clone();
                     mode.finalize(content, Some(session_id.clone()))
@@ -297,6 +300,105 @@ impl super::ReasoningServer {
                             metadata: None,
                         })
                 }
+                "import" => {
+                    let sid = session_id.clone();
+                    mode.import(content, Some(session_id.clone()))
+                        .await
+                        .map(move |r| {
+                            let node_count = r.nodes.len() as u32;
+                            let nodes: Vec<GraphNode> = r
+                                .nodes
+                                .into_iter()
+                                .map(|n| GraphNode {
+                                    id: n.id,
+                                    content: n.content,
+                                    score: n.score,
+                                    depth: None,
+                                    parent_id: None,
+                                })
+                                .collect();
+                            GraphResponse {
+                                session_id: sid,
+                                node_id: r.roots.first().cloned(),
+                                nodes: Some(nodes),
+                                aggregated_insight: None,
+                                conclusions: None,
+                                state: Some(GraphState {
+                                    total_nodes: node_count,
+                                    active_nodes: node_count,
+                                    max_depth: 0,
+                                    pruned_count: 0,
+                                }),
+                                validation: None,
+                                persistence_warning: None,
+                                metadata: None,
+                            }
+                        })
+                }
+                "advance" => {
+                    let sid = session_id.clone();
+                    let max_nodes = req.max_nodes.unwrap_or(3) as usize;
+                    // Stream each frontier as it completes over the progress
+                    // channel, so long multi-frontier advances surface early
+                    // results instead of going quiet until the last one.
+                    let progress = self
+                        .state
+                        .create_progress_reporter(format!("graph-advance-{sid}"));
+                    let completed = std::sync::atomic::AtomicUsize::new(0);
+                    let total = max_nodes.max(1);
+                    let observer = |frontier: &ExpandedFrontier| {
+                        let done = completed
+                            .fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+                            + 1;
+                        let percent = (20 + 70 * done / total).min(90) as u32;
+                        progress.report_percent(
+                            percent,
+                            Some(&format!(
+                                "Expanded frontier '{}' ({} child node(s))",
+                                frontier.node_id,
+                                frontier.children.len()
+                            )),
+                        );
+                    };
+                    mode.advance_with_observer(&session_id, max_nodes, Some(&observer))
+                        .await
+                        .map(move |r| {
+                        let persistence_warning = (r.persistence_failures > 0).then(|| {
+                            format!(
+                                "{} graph write(s) did not persist during advance — the \
+                                 generated nodes are returned, but the stored graph is \
+                                 incomplete.",
+                                r.persistence_failures
+                            )
+                        });
+                        let nodes: Vec<GraphNode> = r
+                            .expanded
+                            .into_iter()
+                            .flat_map(|f| {
+                                let parent = f.node_id;
+                                f.children.into_iter().map(move |n| GraphNode {
+                                    id: n.id,
+                                    content: n.content,
+                                    score: Some(n.score),
+                                    depth: None,
+                                    parent_id: Some(parent.clone()),
+                                })
+                            })
+                            .collect();
+                        let validation = Some(verify_graph_generate(&nodes));
+                        GraphResponse {
+                            session_id: sid,
+                            node_id: None,
+                            nodes: Some(nodes),
+                            aggregated_insight: None,
+                            conclusions: None,
+                            state: None,
+                            validation,
+                            persistence_warning,
+                            metadata: None,
+                        }
+                    })
+                }
                 "state" => {
                     let sid = session_id.clone();
                     mode.state(req.content.as_deref(), &session_id)
@@ -374,7 +476,7 @@ impl super::ReasoningServer {
             aggregated_insight: Some(super::error_help::with_recovery_suggestions(
                 format!(
                     "graph {operation} failed: {e}. \
-                     Valid operations: init, generate, score, aggregate, refine, prune, finalize, state. \
+                     Valid operations: init, import, generate, advance, score, aggregate, refine, prune, apply_prune, finalize, state. \
                      Use operation='init' first if no session_id exists, then 'generate' to add nodes."
                 ),
                 "reasoning_graph",
@@ -433,7 +535,11 @@ impl super::ReasoningServer {
         let mode = DetectMode::new(
             Arc::clone(&self.state.storage),
             Arc::clone(&self.state.client),
-        );
+        )
+        .with_flags(self.state.mode_flags())
+        .with_language(self.state.resolve_language(req.language.clone()))
+        .with_chunking(req.chunk.unwrap_or(false))
+        .with_premise_verification(req.verify_premises.unwrap_or(false));
 
         let content = req.content.as_deref().unwrap_or("");
         let detect_type = req.detect_type.as_str();
@@ -442,18 +548,20 @@ impl super::ReasoningServer {
         let timeout_ms = self.state.config.timeout_for_thinking_budget(DEEP_THINKING);
         let timeout_duration = Duration::from_millis(timeout_ms);
         let detect_type_for_timeout = detect_type.to_string();
+        let filter_unverified = self.state.config.detect_filter_unverified;
 
         let (response, success) = match tokio::time::timeout(timeout_duration, async {
             match detect_type_for_timeout.as_str() {
                 "biases" => match mode.biases(content, req.session_id).await {
                     Ok(resp) => {
-                        let content_norm = normalize_for_grounding(content);
                         let mut ungrounded = Vec::new();
-                        let detections: Vec<Detection> = resp
+                        let mut detections: Vec<Detection> = resp
                             .biases_detected
                             .into_iter()
                             .map(|b| {
-                                let grounded = quote_is_grounded(&content_norm, &b.evidence);
+                                // Grounding is computed by the mode's post-parse
+                                // verification (DetectedBias::verified).
+                                let grounded = b.verified;
                                 if !grounded {
                                     ungrounded.push(b.bias.clone());
                                 }
@@ -475,6 +583,10 @@ impl super::ReasoningServer {
                             detections.len(),
                             &ungrounded,
                         );
+                        if filter_unverified {
+                            // Validation above still names the dropped items.
+                            detections.retain(|d| d.grounded != Some(false));
+                        }
                         let altering = resp.overall_assessment.conclusion_altering_biases.clone();
                         (
                             DetectResponse {
@@ -522,13 +634,14 @@ impl super::ReasoningServer {
                 },
                 "fallacies" => match mode.fallacies(content, req.session_id).await {
                     Ok(resp) => {
-                        let content_norm = normalize_for_grounding(content);
                         let mut ungrounded = Vec::new();
-                        let detections: Vec<Detection> = resp
+                        let mut detections: Vec<Detection> = resp
                             .fallacies_detected
                             .into_iter()
                             .map(|f| {
-                                let grounded = quote_is_grounded(&content_norm, &f.passage);
+                                // Grounding is computed by the mode's post-parse
+                                // verification (DetectedFallacy::verified).
+                                let grounded = f.verified;
                                 if !grounded {
                                     ungrounded.push(f.fallacy.clone());
                                 }
@@ -550,10 +663,31 @@ impl super::ReasoningServer {
                             detections.len(),
                             &ungrounded,
                         );
+                        if filter_unverified {
+                            // Validation above still names the dropped items.
+                            detections.retain(|d| d.grounded != Some(false));
+                        }
+                        let (premise_assessments, premise_note) = resp
+                            .premise_verification
+                            .map_or((None, None), |verification| {
+                                let assessments = verification
+                                    .assessments
+                                    .into_iter()
+                                    .map(|a| PremiseAssessmentInfo {
+                                        premise: a.premise,
+                                        plausibility: a.plausibility,
+                                        evidentiary_support: a.evidentiary_support,
+                                        assessment: a.assessment,
+                                    })
+                                    .collect();
+                                (Some(assessments), verification.note)
+                            });
                         let argument_structure = ArgumentStructureInfo {
                             premises: resp.argument_structure.premises,
                             conclusion: resp.argument_structure.conclusion,
                             validity: resp.argument_structure.validity.as_str().to_string(),
+                            premise_assessments,
+                            premise_note,
                         };
                         (
                             DetectResponse {
@@ -679,11 +813,90 @@ impl super::ReasoningServer {
                         false,
                     ),
                 },
+                "counterargue" => match mode.counterargue(content, req.session_id).await {
+                    Ok(resp) => {
+                        let detections: Vec<Detection> = resp
+                            .counterarguments
+                            .into_iter()
+                            .map(|c| {
+                                // Map rebuttal strength → severity INVERSELY: a
+                                // counterargument the claim can barely answer is
+                                // the most severe threat to it.
+                                let severity = match c.rebuttal_strength {
+                                    RebuttalStrength::Weak => "high",
+                                    RebuttalStrength::Partial => "medium",
+                                    RebuttalStrength::Strong => "low",
+                                }
+                                .to_string();
+                                Detection {
+                                    detection_type: c.argument,
+                                    category: None, // Counterarguments don't have categories
+                                    severity,
+                                    confidence: c.strength,
+                                    evidence: c.rationale,
+                                    explanation: c.rebuttal,
+                                    remediation: None,
+                                    changes_conclusion: None,
+                                    grounded: None, // counterarguments are constructed, not cited
+                                }
+                            })
+                            .collect();
+                        let validation = build_detect_validation(
+                            resp.overall_assessment.counterargument_count,
+                            detections.len(),
+                            &[],
+                        );
+                        (
+                            DetectResponse {
+                                summary: Some(format!(
+                                    "{} counterarguments generated. Strongest: {}. \
+                                     Claim resilience: {:.0}%.",
+                                    resp.overall_assessment.counterargument_count,
+                                    resp.overall_assessment.strongest,
+                                    resp.overall_assessment.claim_resilience * 100.0,
+                                )),
+                                overall_quality: Some(resp.overall_assessment.claim_resilience),
+                                debiased_version: None,
+                                argument_structure: None,
+                                unchallenged_assumptions: None,
+                                conclusion_altering_biases: None,
+                                validation: Some(validation),
+                                detections,
+                                metadata: None,
+                            },
+                            true,
+                        )
+                    }
+                    Err(e) => (
+                        DetectResponse {
+                            detections: vec![],
+                            summary: Some(super::error_help::with_recovery_suggestions(
+                                format!(
+                                    "counterargument generation failed: {e}. \
+                                     Provide a non-empty claim to argue against."
+                                ),
+                                "reasoning_detect",
+                                Some("counterargue"),
+                                &e.to_string(),
+                                ComplexityMetrics::from_content(content.len()),
+                                timeout_ms,
+                            )),
+                            overall_quality: None,
+                            debiased_version: None,
+                            argument_structure: None,
+                            unchallenged_assumptions: None,
+                            conclusion_altering_biases: None,
+                            validation: None,
+                            metadata: None,
+                        },
+                        false,
+                    ),
+                },
                 _ => (
                     DetectResponse {
                         detections: vec![],
                         summary: Some(format!(
-                            "Unknown detect type '{}'. Use 'biases', 'fallacies', or 'knowledge_gaps'.",
+                            "Unknown detect type '{}'. Use 'biases', 'fallacies', 'knowledge_gaps', or 'counterargue'.",
                             detect_type_for_timeout
                         )),
                         overall_quality: None,
@@ -743,60 +956,7 @@ impl super::ReasoningServer {
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
 mod detect_helper_tests {
-    use super::{build_detect_validation, normalize_for_grounding, quote_is_grounded};
-
-    #[test]
-    fn test_normalize_collapses_whitespace_and_case() {
-        assert_eq!(
-            normalize_for_grounding("  The   QUICK\nBrown "),
-            "the quick brown"
-        );
-    }
-
-    #[test]
-    fn test_quote_grounded_when_present() {
-        let content = normalize_for_grounding("Our product is superior because customers say so");
-        assert!(quote_is_grounded(&content, "customers say so"));
-        // Whitespace/case differences still match.
-        assert!(quote_is_grounded(&content, "Customers   Say So"));
-    }
-
-    #[test]
-    fn test_quote_not_grounded_when_absent() {
-        let content = normalize_for_grounding("Our product is superior");
-        assert!(!quote_is_grounded(
-            &content,
-            "a paraphrased claim not present"
-        ));
-    }
-
-    #[test]
-    fn test_quote_grounded_when_embedded_in_commentary() {
-        // The real failure mode: the model quotes a passage then appends its own
-        // commentary, so the full evidence string is not a verbatim substring.
-        let content =
-            normalize_for_grounding("I've used them for years and they've never let me down");
-        let evidence =
-            "I've used them for years and they've never let me down - showing a preference \
-             for maintaining the current vendor relationship without objective evaluation";
-        assert!(quote_is_grounded(&content, evidence));
-    }
-
-    #[test]
-    fn test_single_shared_word_does_not_ground_long_quote() {
-        // A lone common word must not count as grounding.
-        let content = normalize_for_grounding("The deployment pipeline is fast and reliable");
-        assert!(!quote_is_grounded(
-            &content,
-            "the moon orbits a distant planet very quietly"
-        ));
-    }
-
-    #[test]
-    fn test_empty_quote_is_grounded() {
-        let content = normalize_for_grounding("anything");
-        assert!(quote_is_grounded(&content, "   "));
-    }
+    use super::build_detect_validation;
 
     #[test]
     fn test_validation_consistent_when_count_matches_and_grounded() {
@@ -1,8 +1,11 @@
+use crate::error::enhanced::ComplexityMetrics;
 use crate::metrics::{MetricEvent, Timer};
-use crate::server::requests::{MetricsRequest, PresetRequest};
+use crate::presets::{diff_preset_runs, PresetResult};
+use crate::prompts::ReasoningMode;
+use crate::server::requests::{HelpRequest, MetricsRequest, PresetHistoryRequest, PresetRequest};
 use crate::server::responses::{
-    Invocation, MetricsResponse, MetricsSummary, ModeStats, NextCallHint, PresetExecution,
-    PresetInfo, PresetResponse,
+    HelpResponse, Invocation, MetricsResponse, MetricsSummary, ModeHelp, ModeStats, NextCallHint,
+    PresetExecution, PresetHistoryResponse, PresetInfo, PresetResponse, PresetRunSummary,
 };
 
 impl super::ReasoningServer {
@@ -186,6 +189,205 @@ impl super::ReasoningServer {
         response
     }
 
+    pub(super) async fn handle_preset_history(
+        &self,
+        req: PresetHistoryRequest,
+    ) -> PresetHistoryResponse {
+        let timer = Timer::start();
+
+        tracing::info!(
+            tool = "reasoning_preset_history",
+            preset_id = %req.preset_id,
+            "Listing preset run history"
+        );
+
+        let result = self.state.storage.get_preset_runs(&req.preset_id).await;
+
+        let elapsed_ms = timer.elapsed_ms();
+        let success = result.is_ok();
+
+        self.state
+            .metrics
+            .record(MetricEvent::new("preset_history", elapsed_ms, success));
+
+        match result {
+            Ok(stored_runs) => {
+                // Unreadable rows (e.g. written by an earlier result shape) are
+                // skipped, not fatal: the rest of the history stays usable.
+                let mut runs = Vec::with_capacity(stored_runs.len());
+                let mut results: Vec<PresetResult> = Vec::with_capacity(stored_runs.len());
+                for stored in &stored_runs {
+                    match serde_json::from_str::<PresetResult>(&stored.result_json) {
+                        Ok(result) => {
+                            runs.push(PresetRunSummary {
+                                run_id: stored.id.clone(),
+                                session_id: stored.session_id.clone(),
+                                created_at: stored.created_at.to_rfc3339(),
+                                success: result.success,
+                                steps_total: result.step_results.len(),
+                                steps_failed: result
+                                    .step_results
+                                    .iter()
+                                    .filter(|s| !s.success)
+                                    .count(),
+                                cost_usd: result.cost_usd,
+                            });
+                            results.push(result);
+                        }
+                        Err(e) => tracing::warn!(
+                            run_id = %stored.id,
+                            error = %e,
+                            "Skipping unreadable preset run record"
+                        ),
+                    }
+                }
+
+                // Diff the two most recent runs, when there are two to compare.
+                let diff = match results.as_slice() {
+                    [.., older, newer] => serde_json::to_value(diff_preset_runs(older, newer)).ok(),
+                    _ => None,
+                };
+
+                PresetHistoryResponse {
+                    preset_id: req.preset_id,
+                    runs,
+                    diff,
+                    error: None,
+                    metadata: None,
+                }
+            }
+            Err(e) => {
+                tracing::error!(
+                    tool = "reasoning_preset_history",
+                    error = %e,
+                    "Failed to read preset run history"
+                );
+                PresetHistoryResponse {
+                    preset_id: req.preset_id,
+                    runs: Vec::new(),
+                    diff: None,
+                    error: Some(super::error_help::with_recovery_suggestions(
+                        format!(
+                            "preset history failed: {e}. \
+                             Use reasoning_preset operation='list' to see valid preset IDs."
+                        ),
+                        "reasoning_preset_history",
+                        None,
+                        &e.to_string(),
+                        ComplexityMetrics::default(),
+                        self.state.config.request_timeout_ms,
+                    )),
+                    metadata: None,
+                }
+            }
+        }
+    }
+
+    /// Minimal valid input for a mode's tool, shown in help output.
+    fn help_example_input(mode: ReasoningMode) -> serde_json::Value {
+        match mode {
+            ReasoningMode::Linear => serde_json::json!({
+                "content": "Analyze the tradeoffs of adding a cache layer"
+            }),
+            ReasoningMode::Tree => serde_json::json!({
+                "operation": "create", "content": "Ways to reduce API latency"
+            }),
+            ReasoningMode::Divergent => serde_json::json!({
+                "content": "Our users want feature X", "num_perspectives": 3
+            }),
+            ReasoningMode::Reflection => serde_json::json!({
+                "operation": "process", "content": "<prior reasoning output to improve>"
+            }),
+            ReasoningMode::Checkpoint => serde_json::json!({
+                "operation": "create", "session_id": "<session-id>", "name": "before risky branch"
+            }),
+            ReasoningMode::Auto => serde_json::json!({
+                "content": "Why did the deployment fail?"
+            }),
+            ReasoningMode::Graph => serde_json::json!({
+                "operation": "init", "session_id": "<session-id>", "content": "Design a rate limiter"
+            }),
+            ReasoningMode::Detect => serde_json::json!({
+                "type": "biases", "content": "<argument to check>"
+            }),
+            ReasoningMode::Decision => serde_json::json!({
+                "type": "weighted", "question": "Which database?", "options": ["Postgres", "SQLite"]
+            }),
+            ReasoningMode::Evidence => serde_json::json!({
+                "type": "assess", "claim": "Caching will halve p99 latency"
+            }),
+            ReasoningMode::Timeline => serde_json::json!({
+                "operation": "create", "content": "Incident events in chronological order"
+            }),
+            ReasoningMode::Mcts => serde_json::json!({
+                "operation": "explore", "content": "Find the best refactoring sequence"
+            }),
+            ReasoningMode::Counterfactual => serde_json::json!({
+                "content": "Would the outage have happened without the config change?"
+            }),
+        }
+    }
+
+    /// Build the help entry for one mode from the live mode registry.
+    fn mode_help(mode: ReasoningMode) -> ModeHelp {
+        ModeHelp {
+            mode: mode.as_str().to_string(),
+            tool: mode.tool_name().to_string(),
+            description: mode.description().to_string(),
+            operations: mode
+                .operations()
+                .iter()
+                .map(|op| op.as_str().to_string())
+                .collect(),
+            example_input: Self::help_example_input(mode),
+            requires_thinking: mode.requires_thinking(),
+            thinking_budget_tokens: mode.thinking_budget(),
+        }
+    }
+
+    pub(super) fn handle_help(&self, req: HelpRequest) -> HelpResponse {
+        let timer = Timer::start();
+
+        let (response, success) = req.mode.map_or_else(
+            || {
+                (
+                    HelpResponse {
+                        modes: ReasoningMode::all()
+                            .iter()
+                            .copied()
+                            .map(Self::mode_help)
+                            .collect(),
+                        error: None,
+                    },
+                    true,
+                )
+            },
+            |name| match name.parse::<ReasoningMode>() {
+                Ok(mode) => (
+                    HelpResponse {
+                        modes: vec![Self::mode_help(mode)],
+                        error: None,
+                    },
+                    true,
+                ),
+                // ParseModeError already names the valid modes in its message.
+                Err(e) => (
+                    HelpResponse {
+                        modes: vec![],
+                        error: Some(e.to_string()),
+                    },
+                    false,
+                ),
+            },
+        );
+
+        self.state
+            .metrics
+            .record(MetricEvent::new("help", timer.elapsed_ms(), success));
+
+        response
+    }
+
     /// Build the all-modes summary response, shared by the `summary` and
     /// empty-`by_mode` queries. Surfaces a `by_mode` serialization failure as an
     /// error (`config.error` + `success = false`) rather than swallowing it into
@@ -211,6 +413,7 @@ impl super::ReasoningServer {
                         invocations: None,
                         config: None,
                         chains: None,
+                        retries: None,
                     },
                     true,
                 )
@@ -226,6 +429,7 @@ impl super::ReasoningServer {
                             "error": format!("Failed to serialize metrics summary: {e}")
                         })),
                         chains: None,
+                        retries: None,
                     },
                     false,
                 )
@@ -289,6 +493,7 @@ impl super::ReasoningServer {
                             invocations: None,
                             config: None,
                             chains: None,
+                            retries: None,
                         },
                         true,
                     )
@@ -337,6 +542,7 @@ impl super::ReasoningServer {
                         invocations: Some(invocations),
                         config: None,
                         chains: None,
+                        retries: None,
                     },
                     true,
                 )
@@ -370,6 +576,7 @@ impl super::ReasoningServer {
                         ),
                         config: None,
                         chains: None,
+                        retries: None,
                     },
                     true,
                 )
@@ -402,6 +609,7 @@ impl super::ReasoningServer {
                                 invocations: None,
                                 config: None,
                                 chains: Some(value),
+                                retries: None,
                             },
                             true,
                         )
@@ -419,6 +627,39 @@ impl super::ReasoningServer {
                                     "error": format!("Failed to serialize chain summary: {e}")
                                 })),
                                 chains: None,
+                                retries: None,
+                            },
+                            false,
+                        )
+                    }
+                }
+            }
+            "retries" => {
+                let summary = self.state.metrics.retry_summary();
+                match serde_json::to_value(&summary) {
+                    Ok(value) => (
+                        MetricsResponse {
+                            summary: None,
+                            mode_stats: None,
+                            invocations: None,
+                            config: None,
+                            chains: None,
+                            retries: Some(value),
+                        },
+                        true,
+                    ),
+                    Err(e) => {
+                        tracing::error!(error = %e, "failed to serialize retry summary");
+                        (
+                            MetricsResponse {
+                                summary: None,
+                                mode_stats: None,
+                                invocations: None,
+                                config: Some(serde_json::json!({
+                                    "error": format!("Failed to serialize retry summary: {e}")
+                                })),
+                                chains: None,
+                                retries: None,
                             },
                             false,
                         )
@@ -437,6 +678,7 @@ impl super::ReasoningServer {
                         "log_level": self.state.config.log_level,
                     })),
                     chains: None,
+                    retries: None,
                 },
                 true,
             ),
@@ -447,11 +689,12 @@ impl super::ReasoningServer {
                     invocations: None,
                     config: Some(serde_json::json!({
                         "error": format!(
-                            "Unknown query: {}. Use 'summary', 'by_mode', 'invocations', 'fallbacks', 'chains', or 'config'.",
+                            "Unknown query: {}. Use 'summary', 'by_mode', 'invocations', 'fallbacks', 'chains', 'retries', or 'config'.",
                             query
                         )
                     })),
                     chains: None,
+                    retries: None,
                 },
                 false,
             ),
@@ -0,0 +1,203 @@
+//! Handler for `reasoning_next`: a lightweight next-best-action
+//! recommendation built from the session's last thought.
+//!
+//! The transition rules live in a static table keyed on the stored thought's
+//! mode string (operation-qualified, e.g. `graph_init`) with an optional
+//! confidence ceiling, so "low-confidence linear → reflection:process" style
+//! rules read as data rather than branching code. First matching rule wins;
+//! rules for the same mode go most-specific first.
+
+use crate::metrics::{MetricEvent, Timer};
+use crate::server::requests::NextActionRequest;
+use crate::server::responses::NextActionResponse;
+
+/// One transition rule: when the last thought ran `mode` (and, if set, its
+/// confidence is below `below_confidence`), recommend `tool`/`operation`.
+struct NextRule {
+    mode: &'static str,
+    below_confidence: Option<f64>,
+    tool: &'static str,
+    operation: Option<&'static str>,
+    reason: &'static str,
+}
+
+/// The transition table. Ordering matters: the first rule whose mode matches
+/// and whose confidence gate passes is the recommendation.
+const NEXT_ACTION_RULES: &[NextRule] = &[
+    NextRule {
+        mode: "linear",
+        below_confidence: Some(0.6),
+        tool: "reasoning_reflection",
+        operation: Some("process"),
+        reason: "The linear analysis reported low confidence — an explicit \
+                 critique-and-improve pass usually recovers it",
+    },
+    NextRule {
+        mode: "linear",
+        below_confidence: None,
+        tool: "reasoning_evidence",
+        operation: Some("assess"),
+        reason: "Confident conclusion — evaluate the strength of the evidence behind it",
+    },
+    NextRule {
+        mode: "tree",
+        below_confidence: None,
+        tool: "reasoning_decision",
+        operation: Some("weighted"),
+        reason: "Compare the explored branches against weighted criteria",
+    },
+    NextRule {
+        mode: "divergent",
+        below_confidence: None,
+        tool: "reasoning_decision",
+        operation: Some("weighted"),
+        reason: "Converge the generated perspectives into a scored decision",
+    },
+    NextRule {
+        mode: "reflection",
+        below_confidence: Some(0.6),
+        tool: "reasoning_reflection",
+        operation: Some("process"),
+        reason: "Quality is still low after reflection — run another \
+                 critique-and-improve pass",
+    },
+    NextRule {
+        mode: "reflection",
+        below_confidence: None,
+        tool: "reasoning_checkpoint",
+        operation: Some("create"),
+        reason: "Save the refined analysis before exploring further",
+    },
+    NextRule {
+        mode: "graph_init",
+        below_confidence: None,
+        tool: "reasoning_graph",
+        operation: Some("generate"),
+        reason: "The initialized graph has no child nodes yet — generate from the root",
+    },
+    NextRule {
+        mode: "graph_generate",
+        below_confidence: None,
+        tool: "reasoning_graph",
+        operation: Some("score"),
+        reason: "Score the generated nodes so aggregate and prune have signal",
+    },
+    NextRule {
+        mode: "graph_score",
+        below_confidence: Some(0.5),
+        tool: "reasoning_graph",
+        operation: Some("refine"),
+        reason: "Scores came back low — refine the weak nodes before aggregating",
+    },
+    NextRule {
+        mode: "graph_score",
+        below_confidence: None,
+        tool: "reasoning_graph",
+        operation: Some("aggregate"),
+        reason: "Nodes are scored — combine the high-score paths",
+    },
+    NextRule {
+        mode: "graph_aggregate",
+        below_confidence: None,
+        tool: "reasoning_graph",
+        operation: Some("finalize"),
+        reason: "Aggregation is done — synthesize a conclusion across the graph",
+    },
+    NextRule {
+        mode: "mcts_explore",
+        below_confidence: Some(0.5),
+        tool: "reasoning_mcts",
+        operation: Some("auto_backtrack"),
+        reason: "Exploration stalled at low quality — backtrack to a stronger node",
+    },
+    NextRule {
+        mode: "decision_weighted",
+        below_confidence: None,
+        tool: "reasoning_checkpoint",
+        operation: Some("create"),
+        reason: "Save the decision analysis before acting on it",
+    },
+    NextRule {
+        mode: "evidence_assess",
+        below_confidence: Some(0.6),
+        tool: "reasoning_evidence",
+        operation: Some("probabilistic"),
+        reason: "Credibility is uncertain — quantify it with a Bayesian update",
+    },
+];
+
+/// Look up the recommendation for a last thought with `mode`/`confidence`.
+/// Falls back to `reasoning_auto` when no rule covers the mode.
+fn next_action_for(mode: &str, confidence: f64) -> (&'static str, Option<&'static str>, String) {
+    for rule in NEXT_ACTION_RULES {
+        if rule.mode == mode && rule.below_confidence.is_none_or(|max| confidence < max) {
+            return (rule.tool, rule.operation, rule.reason.to_string());
+        }
+    }
+    (
+        "reasoning_auto",
+        None,
+        format!("No specific follow-up rule for mode '{mode}' — let auto pick the next tool"),
+    )
+}
+
+impl super::ReasoningServer {
+    pub(super) async fn handle_next(&self, req: NextActionRequest) -> NextActionResponse {
+        let timer = Timer::start();
+
+        let (response, success) = match self.state.storage.get_last_thought(&req.session_id).await {
+            Ok(Some(thought)) => {
+                let (tool, operation, reason) = next_action_for(&thought.mode, thought.confidence);
+                (
+                    NextActionResponse {
+                        session_id: req.session_id.clone(),
+                        tool: Some(tool.to_string()),
+                        operation: operation.map(str::to_string),
+                        reason: Some(reason),
+                        based_on_mode: Some(thought.mode),
+                        based_on_confidence: Some(thought.confidence),
+                        error: None,
+                    },
+                    true,
+                )
+            }
+            // An empty (or unknown) session has nothing to build on; starting
+            // with the router is the honest recommendation, not an error.
+            Ok(None) => (
+                NextActionResponse {
+                    session_id: req.session_id.clone(),
+                    tool: Some("reasoning_auto".to_string()),
+                    operation: None,
+                    reason: Some(
+                        "Session has no thoughts yet — start with reasoning_auto".to_string(),
+                    ),
+                    based_on_mode: None,
+                    based_on_confidence: None,
+                    error: None,
+                },
+                true,
+            ),
+            Err(e) => (
+                NextActionResponse {
+                    session_id: req.session_id.clone(),
+                    tool: None,
+                    operation: None,
+                    reason: None,
+                    based_on_mode: None,
+                    based_on_confidence: None,
+                    error: Some(format!("Failed to read session: {e}")),
+                },
+                false,
+            ),
+        };
+
+        self.state
+            .metrics
+            .record(MetricEvent::new("next", timer.elapsed_ms(), success));
+        self.state
+            .metrics
+            .record_tool_use(&req.session_id, "reasoning_next", success);
+
+        response
+    }
+}
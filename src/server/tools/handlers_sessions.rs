@@ -1,12 +1,15 @@
 use crate::error::enhanced::ComplexityMetrics;
 use crate::metrics::{MetricEvent, Timer};
 use crate::server::requests::{
-    ListSessionsRequest, RelateSessionsRequest, ResumeSessionRequest, SearchSessionsRequest,
+    ExportSessionRequest, InspectThoughtRequest, ListSessionsRequest, MergeSessionsRequest,
+    OpenQuestionsRequest, RelateSessionsRequest, ResumeSessionRequest, SearchSessionsRequest,
+    SessionQualityRequest, ThoughtDiffRequest, UndoRequest,
 };
 use crate::server::responses::{
-    CheckpointInfo, ListSessionsResponse, NextCallHint, RelateSessionsResponse, RelationshipEdge,
-    ResumeSessionResponse, SearchResult, SearchSessionsResponse, SessionNode, SessionSummary,
-    ThoughtSummary,
+    CheckpointInfo, ExportSessionResponse, InspectThoughtResponse, ListSessionsResponse,
+    MergeSessionsResponse, NextCallHint, OpenQuestionsResponse, RelateSessionsResponse,
+    RelationshipEdge, ResumeSessionResponse, SearchResult, SearchSessionsResponse, SessionNode,
+    SessionQualityResponse, SessionSummary, ThoughtDiffResponse, ThoughtSummary, UndoResponse,
 };
 
 impl super::ReasoningServer {
@@ -265,6 +268,457 @@ impl super::ReasoningServer {
         }
     }
 
+    pub(super) async fn handle_undo(&self, req: UndoRequest) -> UndoResponse {
+        let timer = Timer::start();
+
+        tracing::info!(
+            tool = "reasoning_undo",
+            session_id = %req.session_id,
+            "Undoing last thought in session"
+        );
+
+        let result = self
+            .state
+            .storage
+            .delete_last_thought(&req.session_id)
+            .await;
+
+        let elapsed_ms = timer.elapsed_ms();
+        let success = result.is_ok();
+
+        self.state
+            .metrics
+            .record(MetricEvent::new("undo", elapsed_ms, success));
+
+        match result {
+            Ok(removed) => {
+                let remaining_thoughts = self
+                    .state
+                    .storage
+                    .get_stored_thoughts(&req.session_id)
+                    .await
+                    .map_or(0, |thoughts| thoughts.len() as u32);
+                UndoResponse {
+                    session_id: req.session_id,
+                    removed: removed.map(|t| ThoughtSummary {
+                        id: t.id,
+                        mode: t.mode,
+                        content: t.content,
+                        confidence: t.confidence,
+                    }),
+                    remaining_thoughts,
+                    error: None,
+                    metadata: None,
+                }
+            }
+            Err(e) => {
+                tracing::error!(
+                    tool = "reasoning_undo",
+                    error = %e,
+                    "Failed to undo last thought"
+                );
+                UndoResponse {
+                    session_id: req.session_id,
+                    removed: None,
+                    remaining_thoughts: 0,
+                    error: Some(super::error_help::with_recovery_suggestions(
+                        format!(
+                            "undo failed: {e}. \
+                             Verify the session_id is from a previous reasoning session. \
+                             Use reasoning_list_sessions to find valid session IDs."
+                        ),
+                        "reasoning_undo",
+                        None,
+                        &e.to_string(),
+                        ComplexityMetrics::default(),
+                        self.state.config.request_timeout_ms,
+                    )),
+                    metadata: None,
+                }
+            }
+        }
+    }
+
+    pub(super) async fn handle_inspect_thought(
+        &self,
+        req: InspectThoughtRequest,
+    ) -> InspectThoughtResponse {
+        let timer = Timer::start();
+
+        tracing::info!(
+            tool = "reasoning_inspect_thought",
+            thought_id = %req.thought_id,
+            "Inspecting raw IO behind thought"
+        );
+
+        let result = self.state.storage.get_thought_raw_io(&req.thought_id).await;
+
+        let elapsed_ms = timer.elapsed_ms();
+        let success = result.is_ok();
+
+        self.state
+            .metrics
+            .record(MetricEvent::new("inspect_thought", elapsed_ms, success));
+
+        match result {
+            Ok(Some(raw_io)) => InspectThoughtResponse {
+                thought_id: req.thought_id,
+                // messages_json was stored as a JSON array; a row truncated at
+                // the size cap may no longer parse, so fall back to the raw
+                // string rather than losing the capture.
+                messages: Some(
+                    serde_json::from_str(&raw_io.messages_json)
+                        .unwrap_or(serde_json::Value::String(raw_io.messages_json)),
+                ),
+                raw_completion: Some(raw_io.raw_completion),
+                recorded_at: Some(raw_io.created_at.to_rfc3339()),
+                error: None,
+                metadata: None,
+            },
+            Ok(None) => InspectThoughtResponse {
+                thought_id: req.thought_id,
+                messages: None,
+                raw_completion: None,
+                recorded_at: None,
+                error: Some(
+                    "No raw IO recorded for this thought. Raw request/response capture is \
+                     opt-in and linear-only: the server must run with \
+                     LINEAR_STORE_RAW_IO=true and the thought must come from \
+                     reasoning_linear."
+                        .to_string(),
+                ),
+                metadata: None,
+            },
+            Err(e) => {
+                tracing::error!(
+                    tool = "reasoning_inspect_thought",
+                    error = %e,
+                    "Failed to load raw IO"
+                );
+                InspectThoughtResponse {
+                    thought_id: req.thought_id,
+                    messages: None,
+                    raw_completion: None,
+                    recorded_at: None,
+                    error: Some(super::error_help::with_recovery_suggestions(
+                        format!(
+                            "inspect failed: {e}. \
+                             Verify the thought_id came from a reasoning tool response."
+                        ),
+                        "reasoning_inspect_thought",
+                        None,
+                        &e.to_string(),
+                        ComplexityMetrics::default(),
+                        self.state.config.request_timeout_ms,
+                    )),
+                    metadata: None,
+                }
+            }
+        }
+    }
+
+    pub(super) async fn handle_thought_diff(&self, req: ThoughtDiffRequest) -> ThoughtDiffResponse {
+        let timer = Timer::start();
+
+        tracing::info!(
+            tool = "reasoning_diff",
+            thought_id_a = %req.thought_id_a,
+            thought_id_b = %req.thought_id_b,
+            "Diffing two stored thoughts"
+        );
+
+        let loaded_a = self.load_stored_thought(&req.thought_id_a).await;
+        let loaded_b = self.load_stored_thought(&req.thought_id_b).await;
+
+        let (diff, error, session_id) = match (loaded_a, loaded_b) {
+            (Ok(a), Ok(b)) => {
+                let session_id = a.session_id.clone();
+                let diff = crate::modes::diff_thoughts(&a, &b);
+                match serde_json::to_value(&diff) {
+                    Ok(value) => (Some(value), None, session_id),
+                    Err(e) => (
+                        None,
+                        Some(format!("Failed to serialize diff: {e}")),
+                        session_id,
+                    ),
+                }
+            }
+            (Err(e), _) | (Ok(_), Err(e)) => (None, Some(e), String::new()),
+        };
+
+        let success = error.is_none();
+        self.state.metrics.record(MetricEvent::new(
+            "thought_diff",
+            timer.elapsed_ms(),
+            success,
+        ));
+        self.state
+            .metrics
+            .record_tool_use(&session_id, "reasoning_diff", success);
+
+        ThoughtDiffResponse {
+            thought_id_a: req.thought_id_a,
+            thought_id_b: req.thought_id_b,
+            diff,
+            error,
+        }
+    }
+
+    pub(super) async fn handle_export(&self, req: ExportSessionRequest) -> ExportSessionResponse {
+        let timer = Timer::start();
+
+        tracing::info!(
+            tool = "reasoning_export",
+            session_id = %req.session_id,
+            format = %req.format,
+            "Exporting reasoning session"
+        );
+
+        let result = match crate::modes::memory::ExportFormat::parse(&req.format) {
+            Ok(format) => {
+                crate::modes::memory::export_session(&self.state.storage, &req.session_id, format)
+                    .await
+                    .map(|content| (format.as_str().to_string(), content))
+            }
+            Err(e) => Err(e),
+        };
+
+        let success = result.is_ok();
+        self.state.metrics.record(MetricEvent::new(
+            "export_session",
+            timer.elapsed_ms(),
+            success,
+        ));
+        self.state
+            .metrics
+            .record_tool_use(&req.session_id, "reasoning_export", success);
+
+        match result {
+            Ok((format, content)) => ExportSessionResponse {
+                session_id: req.session_id,
+                format,
+                content,
+                error: None,
+            },
+            Err(e) => {
+                tracing::error!(
+                    tool = "reasoning_export",
+                    error = %e,
+                    "Failed to export session"
+                );
+                ExportSessionResponse {
+                    session_id: req.session_id,
+                    format: req.format,
+                    content: String::new(),
+                    error: Some(e.to_string()),
+                }
+            }
+        }
+    }
+
+    /// Load a stored thought by id for diffing, from whichever session owns it.
+    ///
+    /// Errors are user-facing strings: the diff tool reports them verbatim so
+    /// the caller knows which thought id failed and why.
+    async fn load_stored_thought(
+        &self,
+        thought_id: &str,
+    ) -> Result<crate::storage::StoredThought, String> {
+        self.state
+            .storage
+            .get_stored_thought(thought_id)
+            .await
+            .map_err(|e| format!("Failed to read thought '{thought_id}': {e}"))?
+            .ok_or_else(|| format!("Thought '{thought_id}' not found"))
+    }
+
+    pub(super) async fn handle_merge_sessions(
+        &self,
+        req: MergeSessionsRequest,
+    ) -> MergeSessionsResponse {
+        let timer = Timer::start();
+
+        tracing::info!(
+            tool = "reasoning_merge_sessions",
+            target_session_id = %req.target_session_id,
+            source_session_id = %req.source_session_id,
+            "Merging session into target"
+        );
+
+        let result = self
+            .state
+            .storage
+            .merge_sessions(&req.target_session_id, &req.source_session_id)
+            .await;
+
+        let elapsed_ms = timer.elapsed_ms();
+        let success = result.is_ok();
+
+        self.state
+            .metrics
+            .record(MetricEvent::new("merge_sessions", elapsed_ms, success));
+
+        match result {
+            Ok(()) => {
+                let total_thoughts = self
+                    .state
+                    .storage
+                    .get_stored_thoughts(&req.target_session_id)
+                    .await
+                    .map_or(0, |thoughts| thoughts.len() as u32);
+                MergeSessionsResponse {
+                    target_session_id: req.target_session_id,
+                    source_session_id: req.source_session_id,
+                    total_thoughts,
+                    error: None,
+                    metadata: None,
+                }
+            }
+            Err(e) => {
+                tracing::error!(
+                    tool = "reasoning_merge_sessions",
+                    error = %e,
+                    "Failed to merge sessions"
+                );
+                MergeSessionsResponse {
+                    target_session_id: req.target_session_id,
+                    source_session_id: req.source_session_id,
+                    total_thoughts: 0,
+                    error: Some(super::error_help::with_recovery_suggestions(
+                        format!(
+                            "merge failed: {e}. \
+                             Verify both session IDs are from previous reasoning sessions. \
+                             Use reasoning_list_sessions to find valid session IDs."
+                        ),
+                        "reasoning_merge_sessions",
+                        None,
+                        &e.to_string(),
+                        ComplexityMetrics::default(),
+                        self.state.config.request_timeout_ms,
+                    )),
+                    metadata: None,
+                }
+            }
+        }
+    }
+
+    pub(super) async fn handle_session_quality(
+        &self,
+        req: SessionQualityRequest,
+    ) -> SessionQualityResponse {
+        let timer = Timer::start();
+
+        tracing::info!(
+            tool = "reasoning_session_quality",
+            session_id = %req.session_id,
+            "Reporting session quality"
+        );
+
+        let result =
+            crate::modes::memory::session_quality(&self.state.storage, &req.session_id).await;
+
+        let elapsed_ms = timer.elapsed_ms();
+        let success = result.is_ok();
+
+        self.state
+            .metrics
+            .record(MetricEvent::new("session_quality", elapsed_ms, success));
+
+        match result {
+            Ok(report) => SessionQualityResponse {
+                session_id: report.session_id,
+                quality: report.quality,
+                trend: report.trend,
+                thought_count: report.thought_count,
+                error: None,
+                metadata: None,
+            },
+            Err(e) => {
+                tracing::error!(
+                    tool = "reasoning_session_quality",
+                    error = %e,
+                    "Failed to report session quality"
+                );
+                SessionQualityResponse {
+                    session_id: req.session_id,
+                    quality: None,
+                    trend: "stable".to_string(),
+                    thought_count: 0,
+                    error: Some(super::error_help::with_recovery_suggestions(
+                        format!(
+                            "session quality failed: {e}. \
+                             Verify the session_id is from a previous reasoning session. \
+                             Use reasoning_list_sessions to find valid session IDs."
+                        ),
+                        "reasoning_session_quality",
+                        None,
+                        &e.to_string(),
+                        ComplexityMetrics::default(),
+                        self.state.config.request_timeout_ms,
+                    )),
+                    metadata: None,
+                }
+            }
+        }
+    }
+
+    pub(super) async fn handle_open_questions(
+        &self,
+        req: OpenQuestionsRequest,
+    ) -> OpenQuestionsResponse {
+        let timer = Timer::start();
+
+        tracing::info!(
+            tool = "reasoning_open_questions",
+            session_id = %req.session_id,
+            "Collecting session open questions"
+        );
+
+        let result =
+            crate::modes::memory::session_open_questions(&self.state.storage, &req.session_id)
+                .await;
+
+        let elapsed_ms = timer.elapsed_ms();
+        let success = result.is_ok();
+
+        self.state
+            .metrics
+            .record(MetricEvent::new("open_questions", elapsed_ms, success));
+
+        match result {
+            Ok(report) => OpenQuestionsResponse {
+                session_id: report.session_id,
+                open_questions: report.open_questions,
+                error: None,
+                metadata: None,
+            },
+            Err(e) => {
+                tracing::error!(
+                    tool = "reasoning_open_questions",
+                    error = %e,
+                    "Failed to collect session open questions"
+                );
+                OpenQuestionsResponse {
+                    session_id: req.session_id,
+                    open_questions: Vec::new(),
+                    error: Some(super::error_help::with_recovery_suggestions(
+                        format!(
+                            "open questions failed: {e}. \
+                             Verify the session_id is from a previous reasoning session. \
+                             Use reasoning_list_sessions to find valid session IDs."
+                        ),
+                        "reasoning_open_questions",
+                        None,
+                        &e.to_string(),
+                        ComplexityMetrics::default(),
+                        self.state.config.request_timeout_ms,
+                    )),
+                    metadata: None,
+                }
+            }
+        }
+    }
+
     pub(super) async fn handle_relate(&self, req: RelateSessionsRequest) -> RelateSessionsResponse {
         let timer = Timer::start();
 
@@ -21,9 +21,9 @@ use crate::server::responses::{
     CompareRecommendationInfo, CounterfactualResponse, CounterfactualValidationInfo,
     DecisionPointInfo, FragileStrategyInfo, InterventionInfo, MctsAlternative, MctsBackpropagation,
     MctsConvergence, MctsExpandedNode, MctsFrontierNode, MctsNode, MctsRecommendation,
-    MctsResponse, MctsSelectedNode, MctsValidationInfo, OpportunityAssessmentInfo,
-    RiskAssessmentInfo, RobustStrategyInfo, TemporalStructureInfo, TimelineBranch,
-    TimelineEventInfo, TimelineResponse, TimelineValidationInfo,
+    MctsResponse, MctsSelectedNode, MctsValidationInfo, MergeRecommendationInfo,
+    OpportunityAssessmentInfo, RiskAssessmentInfo, RobustStrategyInfo, TemporalStructureInfo,
+    TimelineBranch, TimelineEventInfo, TimelineResponse, TimelineValidationInfo,
 };
 
 /// Validate a created timeline: event causes/effects and the temporal structure
@@ -107,6 +107,13 @@ fn verify_branch(branches: &[ModeTimelineBranch]) -> TimelineValidationInfo {
                 ));
             }
         }
+        if b.missing_probabilities {
+            warnings.push(format!(
+                "Branch '{}' has events without probabilities (treated as 1.0); \
+                 its joint probability is an upper bound",
+                b.id
+            ));
+        }
     }
     TimelineValidationInfo {
         consistent: warnings.is_empty(),
@@ -612,7 +619,9 @@ impl super::ReasoningServer {
         let mode = TimelineMode::new(
             Arc::clone(&self.state.storage),
             Arc::clone(&self.state.client),
-        );
+        )
+        .with_flags(self.state.mode_flags())
+        .with_language(self.state.resolve_language(req.language.clone()));
 
         let content = req.content.as_deref().unwrap_or("");
 
@@ -695,6 +704,8 @@ impl super::ReasoningServer {
                             choice: b.choice.clone(),
                             plausibility: b.plausibility,
                             outcome_quality: b.outcome_quality,
+                            joint_probability: b.joint_probability,
+                            missing_probabilities: b.missing_probabilities,
                             events: b
                                 .events
                                 .iter()
@@ -855,18 +866,33 @@ impl super::ReasoningServer {
                             failure_modes: s.failure_modes.clone(),
                         })
                         .collect();
+                    let actions: Vec<String> = resp
+                        .recommendations
+                        .iter()
+                        .map(|r| r.action.clone())
+                        .collect();
+                    let merge_recommendations = resp
+                        .recommendations
+                        .iter()
+                        .map(|r| MergeRecommendationInfo {
+                            action: r.action.clone(),
+                            supporting_branches: r.supporting_branches.clone(),
+                            confidence: r.confidence,
+                        })
+                        .collect();
                     (
                         TimelineResponse {
                             merged_content: Some(format!(
                                 "Synthesis: {}. Recommendations: {}",
                                 resp.synthesis,
-                                resp.recommendations.join("; ")
+                                actions.join("; ")
                             )),
                             common_patterns: Some(common_patterns),
                             robust_strategies: Some(robust_strategies),
                             fragile_strategies: Some(fragile_strategies),
                             synthesis: Some(resp.synthesis),
-                            recommendations: Some(resp.recommendations),
+                            recommendations: Some(actions),
+                            merge_recommendations: Some(merge_recommendations),
                             branch_ids: Some(resp.branches_merged),
                             validation: Some(validation),
                             ..Default::default()
@@ -965,9 +991,14 @@ impl super::ReasoningServer {
         let mode = MctsMode::new(
             Arc::clone(&self.state.storage),
             Arc::clone(&self.state.client),
-        );
+        )
+        .with_flags(self.state.mode_flags())
+        .with_language(self.state.resolve_language(req.language.clone()));
 
-        let operation = req.operation.as_deref().unwrap_or("explore");
+        // Resolve the default explicitly so metadata reports what actually ran.
+        let operation = ReasoningMode::Mcts
+            .resolve_operation(req.operation.as_deref())
+            .unwrap_or_default();
         let content = req.content.as_deref().unwrap_or("");
         let input_session_id = req.session_id.clone().unwrap_or_default();
 
@@ -1301,7 +1332,9 @@ impl super::ReasoningServer {
         let mode = CounterfactualMode::new(
             Arc::clone(&self.state.storage),
             Arc::clone(&self.state.client),
-        );
+        )
+        .with_flags(self.state.mode_flags())
+        .with_language(self.state.resolve_language(req.language.clone()));
 
         // Build content from scenario and intervention
         let content = format!(
@@ -2110,9 +2143,20 @@ mod timeline_verify_tests {
             }],
             plausibility: plaus,
             outcome_quality: quality,
+            joint_probability: prob,
+            missing_probabilities: false,
         }
     }
 
+    #[test]
+    fn test_branch_missing_probabilities_flagged() {
+        let mut b = branch("b1", 0.8, 0.7, 0.9);
+        b.missing_probabilities = true;
+        let v = verify_branch(&[b]);
+        assert!(!v.consistent);
+        assert!(v.warnings.iter().any(|w| w.contains("upper bound")));
+    }
+
     #[test]
     fn test_branch_ranges_ok_and_flagged() {
         let ok = verify_branch(&[branch("b1", 0.8, 0.7, 0.9)]);
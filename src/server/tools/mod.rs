@@ -20,25 +20,34 @@ use rmcp::{tool, tool_handler, tool_router, Peer};
 
 use progress_bridge::ensure_progress_token;
 
+use crate::error::{ConfigError, McpError};
+
 use super::requests::{
-    AgentInvokeRequest, AgentListRequest, AgentMetricsRequest, AutoRequest, CheckpointRequest,
-    ConfidenceRouteRequest, CounterfactualRequest, CrewInvokeRequest, DecisionRequest,
-    DetectRequest, DivergentRequest, EvidenceRequest, GraphRequest, LinearRequest,
-    ListSessionsRequest, MctsRequest, MetaRequest, MetricsRequest, PresetRequest,
-    ReflectionRequest, RelateSessionsRequest, ResumeSessionRequest, SearchSessionsRequest,
-    SiApproveRequest, SiDiagnosesRequest, SiOverridesRequest, SiRejectRequest, SiRollbackRequest,
-    SiStatusRequest, SiTriggerRequest, SkillRunRequest, TeamListRequest, TeamRunRequest,
-    TimelineRequest, TreeRequest,
+    AgentInvokeRequest, AgentListRequest, AgentMetricsRequest, AutoRequest, AutoStatsRequest,
+    CheckpointRequest, ConfidenceRouteRequest, CounterfactualRequest, CrewInvokeRequest,
+    DecisionChallengeRequest, DecisionDiffRequest, DecisionRequest, DetectRequest,
+    DivergentRequest, EvidenceRequest, ExportSessionRequest, GraphRequest, HelpRequest,
+    InspectThoughtRequest, LinearRequest, ListSessionsRequest, MctsRequest, MergeSessionsRequest,
+    MetaRequest, MetricsRequest, NextActionRequest, OpenQuestionsRequest, PresetHistoryRequest,
+    PresetRequest, ReflectionRequest, RelateSessionsRequest, ResumeSessionRequest,
+    SearchSessionsRequest, SessionQualityRequest, SiApproveRequest, SiDiagnosesRequest,
+    SiOverridesRequest, SiRejectRequest, SiRollbackRequest, SiStatusRequest, SiTriggerRequest,
+    SkillRunRequest, SolveRequest, TeamListRequest, TeamRunRequest, ThoughtDiffRequest,
+    TimelineRequest, TreeRequest, UndoRequest,
 };
 use super::responses::{
-    AgentInvokeResponse, AgentListResponse, AgentMetricsResponse, AutoResponse, CheckpointResponse,
-    ConfidenceRouteResponse, CounterfactualResponse, CrewInvokeResponse, DecisionResponse,
-    DetectResponse, DivergentResponse, EvidenceResponse, GraphResponse, LinearResponse,
-    ListSessionsResponse, MctsResponse, MetaResponse, MetricsResponse, PresetResponse,
-    ReflectionResponse, RelateSessionsResponse, ResumeSessionResponse, SearchSessionsResponse,
+    AgentInvokeResponse, AgentListResponse, AgentMetricsResponse, AutoResponse, AutoStatsResponse,
+    CheckpointResponse, ConfidenceRouteResponse, CounterfactualResponse, CrewInvokeResponse,
+    DecisionChallengeResponse, DecisionDiffResponse, DecisionResponse, DetectResponse,
+    DivergentResponse, EvidenceResponse, ExportSessionResponse, GraphResponse, HelpResponse,
+    InspectThoughtResponse, LinearResponse, ListSessionsResponse, MctsResponse,
+    MergeSessionsResponse, MetaResponse, MetricsResponse, NextActionResponse,
+    OpenQuestionsResponse, PresetHistoryResponse, PresetResponse, ReflectionResponse,
+    RelateSessionsResponse, ResumeSessionResponse, SearchSessionsResponse, SessionQualityResponse,
     SiApproveResponse, SiDiagnosesResponse, SiOverridesResponse, SiRejectResponse,
-    SiRollbackResponse, SiStatusResponse, SiTriggerResponse, SkillRunResponse, TeamListResponse,
-    TeamRunResponse, TimelineResponse, TreeResponse,
+    SiRollbackResponse, SiStatusResponse, SiTriggerResponse, SkillRunResponse, SolveResponse,
+    TeamListResponse, TeamRunResponse, ThoughtDiffResponse, TimelineResponse, TreeResponse,
+    UndoResponse,
 };
 use super::types::AppState;
 
@@ -51,6 +60,7 @@ mod handlers_confidence;
 mod handlers_decision;
 mod handlers_graph;
 mod handlers_infra;
+mod handlers_next;
 mod handlers_sessions;
 mod handlers_si;
 mod handlers_temporal;
@@ -86,16 +96,85 @@ pub struct ReasoningServer {
     /// expansion) surfaced by a recent rmcp version bump.
     #[allow(dead_code)]
     tool_router: ToolRouter<Self>,
+    /// Response post-processing hooks, applied in registration order to every
+    /// tool result before `select`/`format` rendering. Empty by default.
+    hooks: Vec<Arc<dyn super::hooks::ResponseHook>>,
 }
 
 impl ReasoningServer {
     /// Creates a new reasoning server.
+    ///
+    /// Tools filtered out by `ENABLED_TOOLS`/`DISABLED_TOOLS` are disabled on
+    /// the router here, so they are absent from `tools/list` and rejected by
+    /// `tools/call` for the server's whole lifetime.
     #[must_use]
     pub fn new(state: Arc<AppState>) -> Self {
+        let mut tool_router = Self::tool_router();
+        if let Some(enabled) = &state.config.enabled_tools {
+            let names: Vec<String> = tool_router
+                .list_all()
+                .into_iter()
+                .map(|t| t.name.into_owned())
+                .collect();
+            for name in names {
+                if !enabled.contains(&name) {
+                    tool_router.disable_route(name);
+                }
+            }
+        }
+        for name in &state.config.disabled_tools {
+            tool_router.disable_route(name.clone());
+        }
         Self {
             state,
-            tool_router: Self::tool_router(),
+            tool_router,
+            hooks: Vec::new(),
+        }
+    }
+
+    /// Register a response post-processing hook.
+    ///
+    /// Hooks run in registration order on every tool result, before
+    /// `select`/`format` rendering. See [`crate::server::hooks`].
+    #[must_use]
+    pub fn with_response_hook(mut self, hook: Arc<dyn super::hooks::ResponseHook>) -> Self {
+        self.hooks.push(hook);
+        self
+    }
+
+    /// Validate `enabled_tools`/`disabled_tools` against the real tool set.
+    ///
+    /// Run at startup so a misspelled name in `ENABLED_TOOLS` or
+    /// `DISABLED_TOOLS` fails loudly instead of silently exposing (or hiding)
+    /// the wrong tools.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::InvalidValue`] naming the first unknown tool.
+    pub fn validate_tool_filter(config: &crate::config::Config) -> Result<(), ConfigError> {
+        let known: std::collections::HashSet<String> = Self::tool_router()
+            .list_all()
+            .into_iter()
+            .map(|t| t.name.into_owned())
+            .collect();
+        let configured = [
+            (
+                "ENABLED_TOOLS",
+                config.enabled_tools.as_deref().unwrap_or_default(),
+            ),
+            ("DISABLED_TOOLS", config.disabled_tools.as_slice()),
+        ];
+        for (var, names) in configured {
+            for name in names {
+                if !known.contains(name) {
+                    return Err(ConfigError::InvalidValue {
+                        var: var.into(),
+                        reason: format!("unknown tool '{name}'"),
+                    });
+                }
+            }
         }
+        Ok(())
     }
 }
 
@@ -143,6 +222,17 @@ impl ReasoningServer {
         self.handle_auto(req.0).await
     }
 
+    #[tool(
+        name = "reasoning_auto_stats",
+        description = "Summarize reasoning_auto's selection history: how often each mode was chosen, \
+                       the average routing confidence, and the average resulting score (how the chosen \
+                       mode's first run in that session actually scored). \
+                       Use to evaluate auto-selection quality over time. Read-only; takes no parameters."
+    )]
+    async fn reasoning_auto_stats(&self, req: Parameters<AutoStatsRequest>) -> AutoStatsResponse {
+        self.handle_auto_stats(req.0).await
+    }
+
     #[tool(
         name = "reasoning_meta",
         description = "Select the best reasoning tool based on historical effectiveness data. Use instead of reasoning_auto when 10+ prior sessions exist — classifies the problem type and picks the tool with the highest empirical success rate for that class. Falls back to reasoning_auto when no data exists. Does NOT execute reasoning itself — returns a tool recommendation."
@@ -166,6 +256,17 @@ impl ReasoningServer {
         self.handle_confidence_route(req.0).await
     }
 
+    #[tool(
+        name = "reasoning_solve",
+        description = "One-call solver that walks an escalation ladder: linear first, then tree, then graph (init+generate+finalize), stopping as soon as a rung's confidence meets the threshold. \
+                       Returns the best result plus the path taken with per-rung confidence. \
+                       Pass ladder=[...] to customize the rungs, confidence_threshold to tune when it stops. \
+                       Use when you just want a good answer without picking a mode or checking confidence yourself."
+    )]
+    async fn reasoning_solve(&self, req: Parameters<SolveRequest>) -> SolveResponse {
+        self.handle_solve(req.0).await
+    }
+
     // -- Cognitive tools --
 
     #[tool(
@@ -229,6 +330,9 @@ impl ReasoningServer {
         name = "reasoning_graph",
         description = "Graph-of-Thoughts for problems too multi-faceted for linear or tree reasoning — system design, policy analysis, root-cause trees, research synthesis where sub-problems interact. \
                        Typical workflow: init → generate (decompose into sub-thoughts) → score (rate each node) → aggregate (combine high-score paths) → refine (improve weak nodes) → prune (remove low-value nodes) → finalize (synthesize conclusion) → state (inspect graph at any point). \
+                       Use operation='import' with a JSON {nodes, edges} payload to seed a session from an existing argument map instead of init. \
+                       Use operation='advance' (max_nodes, default 3) to auto-expand the highest-potential frontier nodes of the stored graph in one call. \
+                       Use operation='apply_prune' with node_ids to actually delete prune candidates — graph roots and best-path nodes are protected unless force=true. \
                        Use instead of reasoning_tree when sub-problems are interdependent and need cross-pollination between branches. \
                        Returns the updated graph state after each operation; finalize returns a synthesized conclusion across all graph paths."
     )]
@@ -238,7 +342,7 @@ impl ReasoningServer {
 
     #[tool(
         name = "reasoning_detect",
-        description = "Detect flaws and gaps in reasoning: biases=cognitive distortions (anchoring, confirmation bias), fallacies=logical errors (ad hominem, strawman), knowledge_gaps=absent information that could change the conclusion (unknown unknowns, unchecked assumptions)."
+        description = "Detect flaws and gaps in reasoning: biases=cognitive distortions (anchoring, confirmation bias), fallacies=logical errors (ad hominem, strawman), knowledge_gaps=absent information that could change the conclusion (unknown unknowns, unchecked assumptions), counterargue=steelmanned opposing arguments with rebuttal assessment."
     )]
     async fn reasoning_detect(&self, req: Parameters<DetectRequest>) -> DetectResponse {
         self.handle_detect(req.0).await
@@ -254,6 +358,35 @@ impl ReasoningServer {
         self.handle_decision(req.0).await
     }
 
+    #[tool(
+        name = "reasoning_decision_diff",
+        description = "Compare two prior weighted decision analyses by thought id: per-option rank and \
+                       score movements, criteria weight changes, options added or removed, and whether \
+                       the winner flipped. Use after rerunning reasoning_decision type='weighted' with \
+                       changed criteria to see exactly what moved. Read-only; makes no API calls."
+    )]
+    async fn reasoning_decision_diff(
+        &self,
+        req: Parameters<DecisionDiffRequest>,
+    ) -> DecisionDiffResponse {
+        self.handle_decision_diff(req.0).await
+    }
+
+    #[tool(
+        name = "reasoning_decision_challenge",
+        description = "Stress-test a prior weighted decision analysis by thought id: challenges the \
+                       assumptions behind its criteria weights divergent-style, returning which weights \
+                       are most questionable, suggested alternatives, and how the ranking would shift \
+                       if challenged. Use after reasoning_decision type='weighted' to probe whether the \
+                       recommendation survives scrutiny of its inputs."
+    )]
+    async fn reasoning_decision_challenge(
+        &self,
+        req: Parameters<DecisionChallengeRequest>,
+    ) -> DecisionChallengeResponse {
+        self.handle_decision_challenge(req.0).await
+    }
+
     #[tool(
         name = "reasoning_evidence",
         description = "Evaluate evidence quality and update beliefs from it. \
@@ -337,6 +470,19 @@ impl ReasoningServer {
         self.handle_preset(req.0).await
     }
 
+    #[tool(
+        name = "reasoning_preset_history",
+        description = "List the recorded runs of a preset over time, oldest first, with a structured \
+                       diff between the two most recent runs: overall and per-step success flips plus \
+                       score deltas. Use to track how a rerun preset's outputs change across runs."
+    )]
+    async fn reasoning_preset_history(
+        &self,
+        req: Parameters<PresetHistoryRequest>,
+    ) -> PresetHistoryResponse {
+        self.handle_preset_history(req.0).await
+    }
+
     #[tool(
         name = "reasoning_metrics",
         description = "Query usage and performance metrics for the reasoning server. \
@@ -344,12 +490,36 @@ impl ReasoningServer {
                        invocations=raw call log with timestamps. fallbacks=cases where auto-routing changed mode. \
                        chains=observed tool-composition patterns: the A→B transition matrix with success rates, \
                        common multi-tool sequences, and entry/terminal tools — use to see which tool sequences actually work. \
+                       retries=API attempt/retry/exhaustion counters per mode and error kind, separating physical attempts from logical operations. \
                        config=current server configuration. Use to understand which tools are effective before choosing reasoning_meta."
     )]
     async fn reasoning_metrics(&self, req: Parameters<MetricsRequest>) -> MetricsResponse {
         self.handle_metrics(req.0).await
     }
 
+    #[tool(
+        name = "reasoning_help",
+        description = "Describe every reasoning mode: a one-line summary, its operations, a minimal example input, \
+                       and whether it uses extended thinking (with the token budget). \
+                       Pass mode to describe a single mode (e.g. mode=\"tree\"); omit it to list all modes. \
+                       Built from the live mode registry, so it always matches the tools actually exposed. \
+                       Read-only; makes no API calls."
+    )]
+    async fn reasoning_help(&self, req: Parameters<HelpRequest>) -> HelpResponse {
+        self.handle_help(req.0)
+    }
+
+    #[tool(
+        name = "reasoning_next",
+        description = "Recommend the next best action for a session from its last thought's mode and confidence, \
+                       via a static transition table (e.g. low-confidence linear → reflection:process, \
+                       graph init → graph:generate). Falls back to reasoning_auto for modes without a rule \
+                       or empty sessions. Read-only; makes no API calls."
+    )]
+    async fn reasoning_next(&self, req: Parameters<NextActionRequest>) -> NextActionResponse {
+        self.handle_next(req.0).await
+    }
+
     // -- Self-improvement tools --
 
     #[tool(
@@ -478,6 +648,90 @@ impl ReasoningServer {
         self.handle_relate(req.0).await
     }
 
+    #[tool(
+        name = "reasoning_undo",
+        description = "Remove the most recent thought from a reasoning session, returning the removed thought for confirmation. \
+                       Graph nodes/edges created by that step are removed with it. \
+                       Use to back out a step that took the reasoning in a wrong direction before continuing. \
+                       [DESTRUCTIVE: the removed thought cannot be recovered]"
+    )]
+    async fn reasoning_undo(&self, req: Parameters<UndoRequest>) -> UndoResponse {
+        self.handle_undo(req.0).await
+    }
+
+    #[tool(
+        name = "reasoning_inspect_thought",
+        description = "Return the exact request messages and raw model output behind a thought, for deep debugging of prompts and parsing. \
+                       Only reasoning_linear thoughts produced while the server runs with LINEAR_STORE_RAW_IO=true have raw IO to inspect. \
+                       Stored exchanges are redacted and size-capped."
+    )]
+    async fn reasoning_inspect_thought(
+        &self,
+        req: Parameters<InspectThoughtRequest>,
+    ) -> InspectThoughtResponse {
+        self.handle_inspect_thought(req.0).await
+    }
+
+    #[tool(
+        name = "reasoning_diff",
+        description = "Compare two stored thoughts by id: same-type results get a field-level diff (weighted decisions report rank/score movements and criteria weight changes), different types get a textual similarity summary. \
+                       The thoughts may come from different sessions. \
+                       Use to see exactly what changed between two analyses of the same problem."
+    )]
+    async fn reasoning_diff(&self, req: Parameters<ThoughtDiffRequest>) -> ThoughtDiffResponse {
+        self.handle_thought_diff(req.0).await
+    }
+
+    #[tool(
+        name = "reasoning_export",
+        description = "Export a reasoning session as a single document: format=json for a full structured dump (session, thoughts, graph structure), format=markdown for a readable report, or format=dot for a Graphviz digraph of the session's Graph-of-Thoughts structure. \
+                       dot requires graph-mode data in the session and fails with a clear error otherwise. \
+                       Use after reasoning_list_sessions or reasoning_search to share or archive a finished session."
+    )]
+    async fn reasoning_export(
+        &self,
+        req: Parameters<ExportSessionRequest>,
+    ) -> ExportSessionResponse {
+        self.handle_export(req.0).await
+    }
+
+    #[tool(
+        name = "reasoning_merge_sessions",
+        description = "Merge one reasoning session into another: the source's thoughts, branches, checkpoints, and graph data move under the target in chronological order, then the source session is deleted. \
+                       Use to unify a problem that was explored across two sessions before resuming, searching, or relating it as one. \
+                       [DESTRUCTIVE: the source session is deleted after the merge]"
+    )]
+    async fn reasoning_merge_sessions(
+        &self,
+        req: Parameters<MergeSessionsRequest>,
+    ) -> MergeSessionsResponse {
+        self.handle_merge_sessions(req.0).await
+    }
+
+    #[tool(
+        name = "reasoning_session_quality",
+        description = "Report a session's aggregate quality: a recency-weighted average of its thought confidence scores, updated on every thought, plus its trend (improving/declining/stable) versus the session's overall mean. \
+                       Use to check whether a long reasoning session is converging or degrading before continuing it."
+    )]
+    async fn reasoning_session_quality(
+        &self,
+        req: Parameters<SessionQualityRequest>,
+    ) -> SessionQualityResponse {
+        self.handle_session_quality(req.0).await
+    }
+
+    #[tool(
+        name = "reasoning_open_questions",
+        description = "List the open questions accumulated across a reasoning session: the unknowns each step surfaced but could not settle (opt-in, currently from linear and evidence modes). \
+                       Use to see what a long session still doesn't know before drawing conclusions from it."
+    )]
+    async fn reasoning_open_questions(
+        &self,
+        req: Parameters<OpenQuestionsRequest>,
+    ) -> OpenQuestionsResponse {
+        self.handle_open_questions(req.0).await
+    }
+
     // -- Agent & Skill tools --
 
     #[tool(
@@ -574,7 +828,10 @@ impl ReasoningServer {
 // our own `call_tool` so the dashboard sees a request *enter* — Client → Registry
 // → Mode light up at dispatch — then delegate to the router exactly as the macro
 // would. No behavior change to tool execution.
-#[tool_handler]
+// `router = self.tool_router` (not the macro's default fresh `Self::tool_router()`)
+// so `list_tools`/`get_tool` see the ENABLED_TOOLS/DISABLED_TOOLS filtering
+// applied to the instance router in `new`.
+#[tool_handler(router = self.tool_router)]
 impl ServerHandler for ReasoningServer {
     async fn call_tool(
         &self,
@@ -583,6 +840,24 @@ impl ServerHandler for ReasoningServer {
     ) -> Result<rmcp::model::CallToolResult, rmcp::ErrorData> {
         use crate::dashboard::{emit, ActivityEvent, EdgeId, Node, Phase};
         let tool = request.name.to_string();
+        // A tool filtered out by ENABLED_TOOLS/DISABLED_TOOLS is rejected up
+        // front, exactly like one that never existed — before the dashboard
+        // sees a request enter.
+        if self.tool_router.is_disabled(&tool) {
+            let err = McpError::UnknownTool { tool };
+            return Err(rmcp::ErrorData::invalid_params(err.to_string(), None));
+        }
+        // Bounded admission (MAX_PENDING_REQUESTS): the permit is held for the
+        // life of the call, so under a flood of requests everything beyond the
+        // bound is rejected busy up front — before the dashboard sees it enter
+        // and before any work is accepted — until capacity frees.
+        let Ok(_admission_permit) = self.state.request_gate.clone().try_acquire_owned() else {
+            tracing::warn!(tool = %tool, "Request rejected: pending-request bound reached");
+            let err = McpError::Internal {
+                message: "server overloaded".to_string(),
+            };
+            return Err(rmcp::ErrorData::internal_error(err.to_string(), None));
+        };
         emit(
             ActivityEvent::new(Node::Client, Phase::Started)
                 .with_edge(EdgeId::ClientToRegistry)
@@ -593,21 +868,108 @@ impl ServerHandler for ReasoningServer {
                 .with_edge(EdgeId::RegistryToMode)
                 .with_tool(tool.clone()),
         );
-        emit(ActivityEvent::new(Node::Mode, Phase::Started).with_tool(tool));
+        emit(ActivityEvent::new(Node::Mode, Phase::Started).with_tool(tool.clone()));
+        // Read the requested output format before the router consumes the
+        // request; the formatting is applied to the serialized result below.
+        let format = crate::server::format::ResponseFormat::from_args(request.arguments.as_ref());
+        // Optional JSON-pointer selection, applied to the serialized result
+        // before formatting so text rendering sees only the selected value.
+        let select = crate::server::select::ResponseSelect::from_args(request.arguments.as_ref());
+        // Coerce common client argument mismatches against the tool's own
+        // input schema before dispatch: string-typed numbers are parsed, and
+        // explicit nulls for optional fields are dropped (so sticky sessions
+        // below see `"session_id": null` as missing). Genuinely wrong types
+        // pass through and are rejected by deserialization as before.
+        let mut request = request;
+        if let (Some(tool_def), Some(args)) =
+            (self.tool_router.get(&tool), request.arguments.as_mut())
+        {
+            crate::server::coerce::coerce_arguments(&tool_def.input_schema, args);
+        }
+        // Sticky-session mode: fill a missing session_id with the last session
+        // a reasoning call ran in, before the router consumes the request.
+        if self.state.config.sticky_session {
+            self.state.sticky.inject(&tool, request.arguments.as_mut());
+        }
+        // Baseline for the hook context: wall-clock start and the token
+        // counters before dispatch, so the per-call delta can be computed.
+        // Only measured when hooks are registered.
+        let hook_baseline = (!self.hooks.is_empty()).then(|| {
+            (
+                std::time::Instant::now(),
+                self.state.metrics.token_usage_summary(),
+            )
+        });
+        // Capture the raw arguments before the router consumes the request so
+        // the audit record (field names + digest only) can be built afterwards.
+        let arguments = self
+            .state
+            .audit
+            .is_some()
+            .then(|| request.arguments.clone())
+            .flatten();
+        // Every log line from here on — including those from the Anthropic
+        // client calls the handler makes — carries the request's correlation
+        // id via the span.
+        let correlation_id = crate::server::correlation::correlation_id(&context.id);
+        let span = crate::server::correlation::request_span(&tool, &context.id);
         let tcc = rmcp::handler::server::tool::ToolCallContext::new(self, request, context);
-        self.tool_router.call(tcc).await
+        let result = {
+            use tracing::Instrument as _;
+            self.tool_router.call(tcc).instrument(span.clone()).await
+        };
+        // No awaits remain; keep the post-dispatch bookkeeping logs in the
+        // request span too.
+        let _span_guard = span.enter();
+        if let Some(audit) = &self.state.audit {
+            let success = result.as_ref().is_ok_and(|r| r.is_error != Some(true));
+            audit.record(&tool, &correlation_id, arguments.as_ref(), success);
+        }
+        if self.state.config.sticky_session {
+            if let Ok(r) = result.as_ref() {
+                self.state.sticky.remember(&tool, r);
+            }
+        }
+        result.map(|r| {
+            let r = match &hook_baseline {
+                Some((started, before)) => {
+                    let after = self.state.metrics.token_usage_summary();
+                    let total =
+                        |m: &std::collections::HashMap<String, u64>| -> u64 { m.values().sum() };
+                    let context = super::hooks::HookContext {
+                        tool: tool.clone(),
+                        elapsed_ms: u64::try_from(started.elapsed().as_millis())
+                            .unwrap_or(u64::MAX),
+                        input_tokens: total(&after.input_tokens_total)
+                            .saturating_sub(total(&before.input_tokens_total)),
+                        cache_read_tokens: total(&after.cache_read_tokens_total)
+                            .saturating_sub(total(&before.cache_read_tokens_total)),
+                    };
+                    super::hooks::apply_hooks(&self.hooks, &context, r)
+                }
+                None => r,
+            };
+            let r = match &select {
+                Some(select) => select.apply(r),
+                None => r,
+            };
+            format.apply(r)
+        })
     }
 
     fn get_info(&self) -> ServerInfo {
+        // The total is derived from the router so it cannot drift as tools
+        // are added; only the category blurb below is maintained by hand.
+        let tool_count = Self::tool_router().list_all().len();
         ServerInfo::new(ServerCapabilities::builder().enable_tools().build())
-            .with_instructions(
-                "MCP Reasoning Server with 35 tools: 17 core reasoning tools \
-                 (linear/tree/divergent/reflection/graph/mcts/counterfactual/timeline/decision/evidence/detect/checkpoint/auto/meta/preset/metrics/confidence_route), \
+            .with_instructions(format!(
+                "MCP Reasoning Server with {tool_count} tools: 24 core reasoning tools \
+                 (linear/tree/divergent/reflection/graph/mcts/counterfactual/timeline/decision/decision_diff/decision_challenge/evidence/detect/checkpoint/auto/auto_stats/solve/meta/preset/preset_history/metrics/help/next/confidence_route), \
                  7 self-improvement tools (si_*), \
-                 4 session management tools (list_sessions/resume/search/relate), \
+                 11 session management tools (list_sessions/resume/search/relate/undo/merge_sessions/inspect_thought/diff/export/session_quality/open_questions), \
                  7 agent and team tools. \
                  Use reasoning_auto when unsure which tool fits.",
-            )
+            ))
     }
 }
 
@@ -95,6 +95,7 @@ async fn test_meta_high_confidence_threshold() {
 async fn test_tree_summarize_operation() {
     let server = create_test_server().await;
     let req = TreeRequest {
+        language: None,
         operation: Some("summarize".to_string()),
         content: None,
         session_id: Some("s1".to_string()),
@@ -116,6 +117,7 @@ async fn test_tree_summarize_operation() {
 async fn test_tree_summarize_with_content() {
     let server = create_test_server().await;
     let req = TreeRequest {
+        language: None,
         operation: Some("summarize".to_string()),
         content: Some("summarize this tree".to_string()),
         session_id: Some("summary-session".to_string()),
@@ -136,6 +138,7 @@ async fn test_tree_summarize_with_content() {
 async fn test_tree_default_operation_none() {
     let server = create_test_server().await;
     let req = TreeRequest {
+        language: None,
         operation: None,
         content: Some("decision to analyze".to_string()),
         session_id: Some("s-default".to_string()),
@@ -156,6 +159,8 @@ async fn test_tree_default_operation_none() {
 async fn test_detect_knowledge_gaps() {
     let server = create_test_server().await;
     let req = DetectRequest {
+        chunk: None,
+        language: None,
         detect_type: "knowledge_gaps".to_string(),
         content: Some("We should use Rust because it is fast.".to_string()),
         thought_id: None,
@@ -163,6 +168,7 @@ async fn test_detect_knowledge_gaps() {
         check_types: None,
         check_formal: None,
         check_informal: None,
+        verify_premises: None,
     };
     let resp = server.reasoning_detect(Parameters(req)).await;
     // API fails → fallback error response
@@ -178,6 +184,8 @@ async fn test_detect_knowledge_gaps() {
 async fn test_detect_knowledge_gaps_empty_content() {
     let server = create_test_server().await;
     let req = DetectRequest {
+        chunk: None,
+        language: None,
         detect_type: "knowledge_gaps".to_string(),
         content: None,
         thought_id: None,
@@ -185,6 +193,7 @@ async fn test_detect_knowledge_gaps_empty_content() {
         check_types: None,
         check_formal: None,
         check_informal: None,
+        verify_premises: None,
     };
     let resp = server.reasoning_detect(Parameters(req)).await;
     assert!(resp.summary.is_some());
@@ -194,6 +203,8 @@ async fn test_detect_knowledge_gaps_empty_content() {
 async fn test_detect_knowledge_gaps_with_thought_id() {
     let server = create_test_server().await;
     let req = DetectRequest {
+        chunk: None,
+        language: None,
         detect_type: "knowledge_gaps".to_string(),
         content: Some("The analysis shows X is true.".to_string()),
         thought_id: Some("thought-123".to_string()),
@@ -201,6 +212,7 @@ async fn test_detect_knowledge_gaps_with_thought_id() {
         check_types: Some(vec!["assumption".to_string()]),
         check_formal: Some(false),
         check_informal: Some(true),
+        verify_premises: None,
     };
     let resp = server.reasoning_detect(Parameters(req)).await;
     assert!(resp.summary.is_some());
@@ -215,10 +227,13 @@ async fn test_detect_knowledge_gaps_with_thought_id() {
 async fn test_linear_with_custom_timeout_ms() {
     let server = create_test_server().await;
     let req = LinearRequest {
+        language: None,
         content: "analyze this problem".to_string(),
         session_id: Some("timeout-test".to_string()),
         confidence: None,
         timeout_ms: Some(5000),
+        profile: None,
+        prompt_version: None,
     };
     let resp = server.reasoning_linear(Parameters(req)).await;
     // Custom timeout path exercised; API fails → error response
@@ -230,10 +245,13 @@ async fn test_linear_with_custom_timeout_ms() {
 async fn test_linear_no_session_id() {
     let server = create_test_server().await;
     let req = LinearRequest {
+        language: None,
         content: "reasoning problem".to_string(),
         session_id: None,
         confidence: None,
         timeout_ms: None,
+        profile: None,
+        prompt_version: None,
     };
     let resp = server.reasoning_linear(Parameters(req)).await;
     // session_id is None → defaults to empty string in error path
@@ -251,6 +269,7 @@ async fn test_auto_execute_divergent() {
     // it runs handle_divergent directly. We can't force the selection but we can
     // exercise both code paths by calling auto with brainstorming-style content.
     let req = AutoRequest {
+        language: None,
         content: "Brainstorm creative solutions: explore all possible angles".to_string(),
         hints: Some(vec!["divergent".to_string()]),
         session_id: Some("exec-div-test".to_string()),
@@ -270,6 +289,7 @@ async fn test_auto_execute_divergent() {
 async fn test_counterfactual_interventional_depth() {
     let server = create_test_server().await;
     let req = CounterfactualRequest {
+        language: None,
         scenario: "Company hired 10 engineers".to_string(),
         intervention: "Hired 20 instead".to_string(),
         analysis_depth: Some("interventional".to_string()),
@@ -286,6 +306,7 @@ async fn test_counterfactual_interventional_depth() {
 async fn test_counterfactual_causal_depth() {
     let server = create_test_server().await;
     let req = CounterfactualRequest {
+        language: None,
         scenario: "A decision was made".to_string(),
         intervention: "A different decision was made".to_string(),
         analysis_depth: Some("causal".to_string()),
@@ -302,6 +323,7 @@ async fn test_counterfactual_causal_depth() {
 async fn test_counterfactual_no_depth() {
     let server = create_test_server().await;
     let req = CounterfactualRequest {
+        language: None,
         scenario: "status quo".to_string(),
         intervention: "change applied".to_string(),
         analysis_depth: None,
@@ -317,6 +339,7 @@ async fn test_counterfactual_no_depth() {
 async fn test_counterfactual_with_progress_token() {
     let server = create_test_server().await;
     let req = CounterfactualRequest {
+        language: None,
         scenario: "scenario A".to_string(),
         intervention: "intervention B".to_string(),
         analysis_depth: Some("interventional".to_string()),
@@ -337,6 +360,7 @@ async fn test_counterfactual_with_progress_token() {
 async fn test_mcts_no_operation_defaults_explore() {
     let server = create_test_server().await;
     let req = MctsRequest {
+        language: None,
         operation: None,
         content: Some("find optimal path".to_string()),
         session_id: Some("mcts-default".to_string()),
@@ -358,6 +382,7 @@ async fn test_mcts_no_operation_defaults_explore() {
 async fn test_mcts_no_content() {
     let server = create_test_server().await;
     let req = MctsRequest {
+        language: None,
         operation: Some("explore".to_string()),
         content: None,
         session_id: Some("mcts-empty".to_string()),
@@ -378,6 +403,7 @@ async fn test_mcts_no_content() {
 async fn test_mcts_with_progress_token() {
     let server = create_test_server().await;
     let req = MctsRequest {
+        language: None,
         operation: Some("explore".to_string()),
         content: Some("explore decision space".to_string()),
         session_id: Some("mcts-prog".to_string()),
@@ -398,6 +424,7 @@ async fn test_mcts_with_progress_token() {
 async fn test_mcts_auto_backtrack_no_session() {
     let server = create_test_server().await;
     let req = MctsRequest {
+        language: None,
         operation: Some("auto_backtrack".to_string()),
         content: Some("evaluate quality of reasoning".to_string()),
         session_id: None,
@@ -423,6 +450,7 @@ async fn test_mcts_auto_backtrack_no_session() {
 async fn test_timeline_create_no_content() {
     let server = create_test_server().await;
     let req = TimelineRequest {
+        language: None,
         operation: "create".to_string(),
         session_id: Some("t1".to_string()),
         timeline_id: None,
@@ -442,6 +470,7 @@ async fn test_timeline_create_no_content() {
 async fn test_timeline_branch_no_content() {
     let server = create_test_server().await;
     let req = TimelineRequest {
+        language: None,
         operation: "branch".to_string(),
         session_id: Some("t2".to_string()),
         timeline_id: None,
@@ -460,6 +489,7 @@ async fn test_timeline_branch_no_content() {
 async fn test_timeline_compare_no_session() {
     let server = create_test_server().await;
     let req = TimelineRequest {
+        language: None,
         operation: "compare".to_string(),
         session_id: None,
         timeline_id: None,
@@ -482,6 +512,7 @@ async fn test_timeline_compare_no_session() {
 async fn test_reflection_process_no_content() {
     let server = create_test_server().await;
     let req = ReflectionRequest {
+        language: None,
         operation: Some("process".to_string()),
         content: None,
         thought_id: None,
@@ -500,6 +531,7 @@ async fn test_reflection_process_no_content() {
 async fn test_reflection_process_with_progress_token() {
     let server = create_test_server().await;
     let req = ReflectionRequest {
+        language: None,
         operation: Some("process".to_string()),
         content: Some("reflect on this reasoning".to_string()),
         thought_id: None,
@@ -517,6 +549,7 @@ async fn test_reflection_process_with_progress_token() {
 async fn test_reflection_evaluate_no_session() {
     let server = create_test_server().await;
     let req = ReflectionRequest {
+        language: None,
         operation: Some("evaluate".to_string()),
         content: None,
         thought_id: None,
@@ -540,11 +573,13 @@ async fn test_reflection_evaluate_no_session() {
 async fn test_divergent_with_rebellion() {
     let server = create_test_server().await;
     let req = DivergentRequest {
+        language: None,
         content: "conventional solution to a problem".to_string(),
         session_id: Some("div-rebel".to_string()),
         num_perspectives: Some(2),
         challenge_assumptions: Some(true),
         force_rebellion: Some(true),
+        include_thinking: None,
         progress_token: None,
     };
     let resp = server.handle_divergent(req).await;
@@ -561,11 +596,13 @@ async fn test_divergent_with_rebellion() {
 async fn test_divergent_no_options() {
     let server = create_test_server().await;
     let req = DivergentRequest {
+        language: None,
         content: "minimal divergent call".to_string(),
         session_id: None,
         num_perspectives: None,
         challenge_assumptions: None,
         force_rebellion: None,
+        include_thinking: None,
         progress_token: None,
     };
     let resp = server.handle_divergent(req).await;
@@ -578,11 +615,13 @@ async fn test_divergent_no_options() {
 async fn test_divergent_with_progress_token() {
     let server = create_test_server().await;
     let req = DivergentRequest {
+        language: None,
         content: "explore alternatives for X".to_string(),
         session_id: Some("div-prog".to_string()),
         num_perspectives: Some(4),
         challenge_assumptions: Some(false),
         force_rebellion: Some(false),
+        include_thinking: None,
         progress_token: Some("div-progress-abc".to_string()),
     };
     let resp = server.handle_divergent(req).await;
@@ -833,7 +872,9 @@ async fn test_relate_sessions_with_seeded_data() {
 async fn test_decision_weighted_no_question_only_context() {
     let server = create_test_server().await;
     let req = DecisionRequest {
+        language: None,
         decision_type: Some("weighted".to_string()),
+        normalization: None,
         question: None,
         options: None,
         topic: None,
@@ -850,7 +891,9 @@ async fn test_decision_weighted_no_question_only_context() {
 async fn test_decision_pairwise_no_options() {
     let server = create_test_server().await;
     let req = DecisionRequest {
+        language: None,
         decision_type: Some("pairwise".to_string()),
+        normalization: None,
         question: Some("Which is better?".to_string()),
         options: None,
         topic: None,
@@ -867,7 +910,9 @@ async fn test_decision_pairwise_no_options() {
 async fn test_decision_topsis_no_question() {
     let server = create_test_server().await;
     let req = DecisionRequest {
+        language: None,
         decision_type: Some("topsis".to_string()),
+        normalization: None,
         question: None,
         options: Some(vec!["A".to_string(), "B".to_string()]),
         topic: Some("TOPSIS ranking".to_string()),
@@ -884,7 +929,9 @@ async fn test_decision_topsis_no_question() {
 async fn test_decision_no_type_defaults_weighted() {
     let server = create_test_server().await;
     let req = DecisionRequest {
+        language: None,
         decision_type: None,
+        normalization: None,
         question: Some("What should I choose?".to_string()),
         options: Some(vec!["X".to_string(), "Y".to_string()]),
         topic: None,
@@ -906,6 +953,8 @@ async fn test_decision_no_type_defaults_weighted() {
 async fn test_evidence_assess_no_claim() {
     let server = create_test_server().await;
     let req = EvidenceRequest {
+        chunk: None,
+        language: None,
         evidence_type: Some("assess".to_string()),
         claim: None,
         hypothesis: None,
@@ -922,6 +971,8 @@ async fn test_evidence_assess_no_claim() {
 async fn test_evidence_no_type_defaults_assess() {
     let server = create_test_server().await;
     let req = EvidenceRequest {
+        chunk: None,
+        language: None,
         evidence_type: None,
         claim: Some("claim to assess".to_string()),
         hypothesis: None,
@@ -938,6 +989,8 @@ async fn test_evidence_no_type_defaults_assess() {
 async fn test_evidence_probabilistic_no_content() {
     let server = create_test_server().await;
     let req = EvidenceRequest {
+        chunk: None,
+        language: None,
         evidence_type: Some("probabilistic".to_string()),
         claim: None,
         hypothesis: None,
@@ -962,6 +1015,7 @@ async fn test_evidence_probabilistic_no_content() {
 async fn test_graph_init_seeded_session() {
     let server = create_test_server().await;
     let req = GraphRequest {
+        language: None,
         operation: "init".to_string(),
         session_id: "graph-seeded-s1".to_string(),
         content: Some("graph problem".to_string()),
@@ -970,7 +1024,9 @@ async fn test_graph_init_seeded_session() {
         node_ids: None,
         k: Some(3),
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
     let resp = server.reasoning_graph(Parameters(req)).await;
     assert_eq!(resp.session_id, "graph-seeded-s1");
@@ -986,6 +1042,7 @@ async fn test_graph_init_seeded_session() {
 async fn test_graph_state_no_content() {
     let server = create_test_server().await;
     let req = GraphRequest {
+        language: None,
         operation: "state".to_string(),
         session_id: "graph-state-s1".to_string(),
         content: None,
@@ -994,7 +1051,9 @@ async fn test_graph_state_no_content() {
         node_ids: None,
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
     let resp = server.reasoning_graph(Parameters(req)).await;
     assert_eq!(resp.session_id, "graph-state-s1");
@@ -1008,6 +1067,7 @@ async fn test_graph_state_no_content() {
 async fn test_auto_no_session_id() {
     let server = create_test_server().await;
     let req = AutoRequest {
+        language: None,
         content: "analyze something".to_string(),
         hints: None,
         session_id: None,
@@ -1023,6 +1083,7 @@ async fn test_auto_no_session_id() {
 async fn test_auto_no_hints() {
     let server = create_test_server().await;
     let req = AutoRequest {
+        language: None,
         content: "decide between options A and B".to_string(),
         hints: None,
         session_id: Some("auto-no-hints".to_string()),
@@ -1040,10 +1101,13 @@ async fn test_auto_no_hints() {
 async fn test_linear_with_high_confidence_threshold() {
     let server = create_test_server().await;
     let req = LinearRequest {
+        language: None,
         content: "complex reasoning task".to_string(),
         session_id: Some("conf-test".to_string()),
         confidence: Some(ConfidenceThreshold::try_from(0.95).unwrap()),
         timeout_ms: None,
+        profile: None,
+        prompt_version: None,
     };
     let resp = server.reasoning_linear(Parameters(req)).await;
     assert_eq!(resp.session_id, "conf-test");
@@ -1054,10 +1118,13 @@ async fn test_linear_with_high_confidence_threshold() {
 async fn test_linear_minimal_timeout() {
     let server = create_test_server().await;
     let req = LinearRequest {
+        language: None,
         content: "quick task".to_string(),
         session_id: Some("min-timeout".to_string()),
         confidence: None,
         timeout_ms: Some(1), // 1ms — nearly certain to timeout
+        profile: None,
+        prompt_version: None,
     };
     let resp = server.reasoning_linear(Parameters(req)).await;
     // Either times out (with "timeout" in content) or the API fails fast with error
@@ -1270,10 +1337,13 @@ async fn test_metrics_unknown_query_lists_chains_option() {
 async fn test_linear_with_low_timeout() {
     let server = create_test_server().await;
     let req = LinearRequest {
+        language: None,
         content: "test".to_string(),
         session_id: Some("low-timeout-test".to_string()),
         confidence: None,
         timeout_ms: Some(100), // Very short timeout
+        profile: None,
+        prompt_version: None,
     };
     let resp = server.reasoning_linear(Parameters(req)).await;
     assert_eq!(resp.session_id, "low-timeout-test");
@@ -1288,6 +1358,7 @@ async fn test_linear_with_low_timeout() {
 async fn test_tree_complete_default_completed() {
     let server = create_test_server().await;
     let req = TreeRequest {
+        language: None,
         operation: Some("complete".to_string()),
         content: None,
         session_id: Some("complete-default".to_string()),
@@ -1313,6 +1384,8 @@ async fn test_tree_complete_default_completed() {
 async fn test_detect_biases_with_thought_id() {
     let server = create_test_server().await;
     let req = DetectRequest {
+        chunk: None,
+        language: None,
         detect_type: "biases".to_string(),
         content: None,
         thought_id: Some("thought-abc".to_string()),
@@ -1320,6 +1393,7 @@ async fn test_detect_biases_with_thought_id() {
         check_types: Some(vec!["confirmation".to_string(), "anchoring".to_string()]),
         check_formal: None,
         check_informal: None,
+        verify_premises: None,
     };
     let resp = server.reasoning_detect(Parameters(req)).await;
     assert!(resp.summary.is_some());
@@ -1333,6 +1407,7 @@ async fn test_detect_biases_with_thought_id() {
 async fn test_timeline_create_explicitly() {
     let server = create_test_server().await;
     let req = TimelineRequest {
+        language: None,
         operation: "create".to_string(),
         session_id: Some("tl-create".to_string()),
         timeline_id: None,
@@ -1352,6 +1427,7 @@ async fn test_timeline_create_explicitly() {
 async fn test_timeline_merge_with_strategy() {
     let server = create_test_server().await;
     let req = TimelineRequest {
+        language: None,
         operation: "merge".to_string(),
         session_id: Some("tl-merge".to_string()),
         timeline_id: None,
@@ -1442,3 +1518,24 @@ async fn test_confidence_route_threshold_clamp_above_one() {
     // API fails → error path (threshold clamping does not panic)
     assert_eq!(resp.routing_decision, "error");
 }
+
+// ============================================================================
+// Inspect Thought Handler (handlers_sessions.rs)
+// ============================================================================
+
+#[tokio::test]
+async fn test_inspect_thought_without_raw_io_explains_opt_in() {
+    let server = create_test_server().await;
+    let req = InspectThoughtRequest {
+        thought_id: "no-such-thought".to_string(),
+    };
+    let resp = server.reasoning_inspect_thought(Parameters(req)).await;
+    assert_eq!(resp.thought_id, "no-such-thought");
+    assert!(resp.messages.is_none());
+    assert!(resp.raw_completion.is_none());
+    // The miss must point at the LINEAR_STORE_RAW_IO opt-in, not look like a bug.
+    assert!(resp
+        .error
+        .expect("miss explained")
+        .contains("LINEAR_STORE_RAW_IO"));
+}
@@ -229,6 +229,7 @@ fn test_evidence_response_into_contents() {
         synthesis: Some("strong evidence".to_string()),
         evidential_support: None,
         pivot_evidence: None,
+        gaps: None,
         bayesian: None,
         validation: None,
         metadata: None,
@@ -360,6 +361,7 @@ fn test_metrics_response_into_contents() {
         invocations: None,
         config: None,
         chains: None,
+        retries: None,
     };
     let contents = response.into_contents();
     assert_eq!(contents.len(), 1);
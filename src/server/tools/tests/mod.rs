@@ -53,8 +53,10 @@ fn create_test_server_sync() -> ReasoningServer {
     use crate::storage::SqliteStorage;
 
     let config = Config {
+        response_language: None,
         api_key: SecretString::new("test-key"),
         database_path: ":memory:".to_string(),
+        database_max_connections: 5,
         log_level: "info".to_string(),
         request_timeout_ms: 30000,
         request_timeout_deep_ms: 60000,
@@ -68,6 +70,27 @@ fn create_test_server_sync() -> ReasoningServer {
         reflection_quality_threshold: 0.8,
         mcts_quality_threshold: 0.5,
         graph_prune_threshold: 0.3,
+        graph_max_nodes: 200,
+        graph_max_returned_children: None,
+        rubric_llm_weight: 0.5,
+        evidence_interval_width: 0.1,
+        sticky_session: false,
+        detect_filter_unverified: false,
+        strict_parsing: false,
+        require_session_id: false,
+        self_correct_parse: false,
+        structured_output: false,
+        confidence_floor: None,
+        prompt_caching: false,
+        enabled_tools: None,
+        disabled_tools: vec![],
+        divergent_per_perspective: false,
+        divergent_max_concurrency: 3,
+        linear_store_raw_io: false,
+        max_pending_requests: 32,
+        auto_history_bias: false,
+        retry_degenerate: false,
+        prompt_version: None,
     };
 
     let rt = tokio::runtime::Runtime::new().unwrap();
@@ -103,8 +126,10 @@ async fn create_test_server() -> ReasoningServer {
     use crate::storage::SqliteStorage;
 
     let config = Config {
+        response_language: None,
         api_key: SecretString::new("test-key"),
         database_path: ":memory:".to_string(),
+        database_max_connections: 5,
         log_level: "info".to_string(),
         request_timeout_ms: 30000,
         request_timeout_deep_ms: 60000,
@@ -118,6 +143,27 @@ async fn create_test_server() -> ReasoningServer {
         reflection_quality_threshold: 0.8,
         mcts_quality_threshold: 0.5,
         graph_prune_threshold: 0.3,
+        graph_max_nodes: 200,
+        graph_max_returned_children: None,
+        rubric_llm_weight: 0.5,
+        evidence_interval_width: 0.1,
+        sticky_session: false,
+        detect_filter_unverified: false,
+        strict_parsing: false,
+        require_session_id: false,
+        self_correct_parse: false,
+        structured_output: false,
+        confidence_floor: None,
+        prompt_caching: false,
+        enabled_tools: None,
+        disabled_tools: vec![],
+        divergent_per_perspective: false,
+        divergent_max_concurrency: 3,
+        linear_store_raw_io: false,
+        max_pending_requests: 32,
+        auto_history_bias: false,
+        retry_degenerate: false,
+        prompt_version: None,
     };
 
     let storage = SqliteStorage::new_in_memory().await.unwrap();
@@ -225,6 +271,22 @@ fn test_server_handler_get_info() {
     assert!(info.instructions.is_some());
 }
 
+/// The advertised tool count is derived from the router; the hand-written
+/// category breakdown (24 + 7 + 11 + 7) must add up to the same total.
+#[test]
+fn test_get_info_tool_count_matches_router() {
+    let server = create_test_server_sync();
+    let info = server.get_info();
+    let instructions = info.instructions.expect("instructions set");
+
+    let registered = ReasoningServer::tool_router().list_all().len();
+    assert!(
+        instructions.starts_with(&format!("MCP Reasoning Server with {registered} tools")),
+        "instructions advertise {registered} tools: {instructions}"
+    );
+    assert_eq!(registered, 24 + 7 + 11 + 7, "category breakdown drifted");
+}
+
 #[test]
 fn test_reasoning_server_new() {
     let server = create_test_server_sync();
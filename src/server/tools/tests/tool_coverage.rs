@@ -13,7 +13,9 @@ use crate::server::requests::*;
 async fn test_decision_pairwise_type() {
     let server = create_test_server().await;
     let req = DecisionRequest {
+        language: None,
         decision_type: Some("pairwise".to_string()),
+        normalization: None,
         question: Some("A or B?".to_string()),
         options: Some(vec!["A".to_string(), "B".to_string()]),
         topic: None,
@@ -28,7 +30,9 @@ async fn test_decision_pairwise_type() {
 async fn test_decision_topsis_type() {
     let server = create_test_server().await;
     let req = DecisionRequest {
+        language: None,
         decision_type: Some("topsis".to_string()),
+        normalization: None,
         question: Some("rank options".to_string()),
         options: Some(vec!["X".to_string(), "Y".to_string(), "Z".to_string()]),
         topic: None,
@@ -43,7 +47,9 @@ async fn test_decision_topsis_type() {
 async fn test_decision_perspectives_type() {
     let server = create_test_server().await;
     let req = DecisionRequest {
+        language: None,
         decision_type: Some("perspectives".to_string()),
+        normalization: None,
         question: None,
         options: None,
         topic: Some("stakeholder analysis".to_string()),
@@ -58,7 +64,9 @@ async fn test_decision_perspectives_type() {
 async fn test_decision_unknown_type() {
     let server = create_test_server().await;
     let req = DecisionRequest {
+        language: None,
         decision_type: Some("invalid".to_string()),
+        normalization: None,
         question: Some("test".to_string()),
         options: None,
         topic: None,
@@ -73,7 +81,9 @@ async fn test_decision_unknown_type() {
 async fn test_decision_no_options() {
     let server = create_test_server().await;
     let req = DecisionRequest {
+        language: None,
         decision_type: Some("weighted".to_string()),
+        normalization: None,
         question: Some("test question".to_string()),
         options: Some(vec![]),
         topic: None,
@@ -92,6 +102,8 @@ async fn test_decision_no_options() {
 async fn test_evidence_probabilistic_type() {
     let server = create_test_server().await;
     let req = EvidenceRequest {
+        chunk: None,
+        language: None,
         evidence_type: Some("probabilistic".to_string()),
         claim: None,
         hypothesis: Some("hypothesis A".to_string()),
@@ -107,6 +119,8 @@ async fn test_evidence_probabilistic_type() {
 async fn test_evidence_unknown_type() {
     let server = create_test_server().await;
     let req = EvidenceRequest {
+        chunk: None,
+        language: None,
         evidence_type: Some("invalid".to_string()),
         claim: Some("test".to_string()),
         hypothesis: None,
@@ -126,6 +140,7 @@ async fn test_evidence_unknown_type() {
 async fn test_timeline_branch_operation() {
     let server = create_test_server().await;
     let req = TimelineRequest {
+        language: None,
         operation: "branch".to_string(),
         session_id: Some("s1".to_string()),
         timeline_id: None,
@@ -144,6 +159,7 @@ async fn test_timeline_branch_operation() {
 async fn test_timeline_compare_operation() {
     let server = create_test_server().await;
     let req = TimelineRequest {
+        language: None,
         operation: "compare".to_string(),
         session_id: Some("s1".to_string()),
         timeline_id: None,
@@ -162,6 +178,7 @@ async fn test_timeline_compare_operation() {
 async fn test_timeline_merge_operation() {
     let server = create_test_server().await;
     let req = TimelineRequest {
+        language: None,
         operation: "merge".to_string(),
         session_id: Some("s1".to_string()),
         timeline_id: None,
@@ -180,6 +197,7 @@ async fn test_timeline_merge_operation() {
 async fn test_timeline_unknown_operation() {
     let server = create_test_server().await;
     let req = TimelineRequest {
+        language: None,
         operation: "invalid".to_string(),
         session_id: Some("s1".to_string()),
         timeline_id: None,
@@ -202,6 +220,7 @@ async fn test_timeline_unknown_operation() {
 async fn test_mcts_auto_backtrack_operation() {
     let server = create_test_server().await;
     let req = MctsRequest {
+        language: None,
         operation: Some("auto_backtrack".to_string()),
         content: Some("test content".to_string()),
         session_id: Some("s1".to_string()),
@@ -222,6 +241,7 @@ async fn test_mcts_auto_backtrack_operation() {
 async fn test_mcts_unknown_operation() {
     let server = create_test_server().await;
     let req = MctsRequest {
+        language: None,
         operation: Some("invalid".to_string()),
         content: Some("test".to_string()),
         session_id: Some("s1".to_string()),
@@ -246,6 +266,7 @@ async fn test_mcts_unknown_operation() {
 async fn test_graph_generate_operation() {
     let server = create_test_server().await;
     let req = GraphRequest {
+        language: None,
         operation: "generate".to_string(),
         session_id: "s1".to_string(),
         content: Some("generate nodes".to_string()),
@@ -254,7 +275,9 @@ async fn test_graph_generate_operation() {
         node_ids: None,
         k: Some(3),
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
     let resp = server.reasoning_graph(Parameters(req)).await;
     assert_eq!(resp.session_id, "s1");
@@ -264,6 +287,7 @@ async fn test_graph_generate_operation() {
 async fn test_graph_score_operation() {
     let server = create_test_server().await;
     let req = GraphRequest {
+        language: None,
         operation: "score".to_string(),
         session_id: "s1".to_string(),
         content: Some("score this".to_string()),
@@ -272,7 +296,9 @@ async fn test_graph_score_operation() {
         node_ids: None,
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
     let resp = server.reasoning_graph(Parameters(req)).await;
     assert_eq!(resp.session_id, "s1");
@@ -282,6 +308,7 @@ async fn test_graph_score_operation() {
 async fn test_graph_aggregate_operation() {
     let server = create_test_server().await;
     let req = GraphRequest {
+        language: None,
         operation: "aggregate".to_string(),
         session_id: "s1".to_string(),
         content: Some("aggregate".to_string()),
@@ -290,7 +317,9 @@ async fn test_graph_aggregate_operation() {
         node_ids: Some(vec!["n1".to_string(), "n2".to_string()]),
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
     let resp = server.reasoning_graph(Parameters(req)).await;
     assert_eq!(resp.session_id, "s1");
@@ -300,6 +329,7 @@ async fn test_graph_aggregate_operation() {
 async fn test_graph_refine_operation() {
     let server = create_test_server().await;
     let req = GraphRequest {
+        language: None,
         operation: "refine".to_string(),
         session_id: "s1".to_string(),
         content: Some("refine this".to_string()),
@@ -308,7 +338,9 @@ async fn test_graph_refine_operation() {
         node_ids: None,
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
     let resp = server.reasoning_graph(Parameters(req)).await;
     assert_eq!(resp.session_id, "s1");
@@ -318,6 +350,7 @@ async fn test_graph_refine_operation() {
 async fn test_graph_prune_operation() {
     let server = create_test_server().await;
     let req = GraphRequest {
+        language: None,
         operation: "prune".to_string(),
         session_id: "s1".to_string(),
         content: Some("prune low quality".to_string()),
@@ -326,7 +359,9 @@ async fn test_graph_prune_operation() {
         node_ids: None,
         k: None,
         threshold: Some(0.5),
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
     let resp = server.reasoning_graph(Parameters(req)).await;
     assert_eq!(resp.session_id, "s1");
@@ -336,6 +371,7 @@ async fn test_graph_prune_operation() {
 async fn test_graph_finalize_operation() {
     let server = create_test_server().await;
     let req = GraphRequest {
+        language: None,
         operation: "finalize".to_string(),
         session_id: "s1".to_string(),
         content: Some("finalize".to_string()),
@@ -344,7 +380,9 @@ async fn test_graph_finalize_operation() {
         node_ids: None,
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: Some(vec!["t1".to_string()]),
+        max_nodes: None,
     };
     let resp = server.reasoning_graph(Parameters(req)).await;
     assert_eq!(resp.session_id, "s1");
@@ -354,6 +392,7 @@ async fn test_graph_finalize_operation() {
 async fn test_graph_state_operation() {
     let server = create_test_server().await;
     let req = GraphRequest {
+        language: None,
         operation: "state".to_string(),
         session_id: "s1".to_string(),
         content: None,
@@ -362,7 +401,9 @@ async fn test_graph_state_operation() {
         node_ids: None,
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
     let resp = server.reasoning_graph(Parameters(req)).await;
     assert_eq!(resp.session_id, "s1");
@@ -372,6 +413,7 @@ async fn test_graph_state_operation() {
 async fn test_graph_unknown_operation() {
     let server = create_test_server().await;
     let req = GraphRequest {
+        language: None,
         operation: "invalid".to_string(),
         session_id: "s1".to_string(),
         content: None,
@@ -380,7 +422,9 @@ async fn test_graph_unknown_operation() {
         node_ids: None,
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
     let resp = server.reasoning_graph(Parameters(req)).await;
     assert!(resp.aggregated_insight.unwrap().contains("failed"));
@@ -394,6 +438,8 @@ async fn test_graph_unknown_operation() {
 async fn test_detect_fallacies_type() {
     let server = create_test_server().await;
     let req = DetectRequest {
+        chunk: None,
+        language: None,
         detect_type: "fallacies".to_string(),
         content: Some("This is a bad argument because I said so".to_string()),
         thought_id: None,
@@ -401,6 +447,7 @@ async fn test_detect_fallacies_type() {
         check_types: None,
         check_formal: Some(true),
         check_informal: Some(true),
+        verify_premises: None,
     };
     let resp = server.reasoning_detect(Parameters(req)).await;
     let _ = resp.detections;
@@ -410,6 +457,8 @@ async fn test_detect_fallacies_type() {
 async fn test_detect_unknown_type() {
     let server = create_test_server().await;
     let req = DetectRequest {
+        chunk: None,
+        language: None,
         detect_type: "invalid".to_string(),
         content: Some("test".to_string()),
         thought_id: None,
@@ -417,6 +466,7 @@ async fn test_detect_unknown_type() {
         check_types: None,
         check_formal: None,
         check_informal: None,
+        verify_premises: None,
     };
     let resp = server.reasoning_detect(Parameters(req)).await;
     assert!(resp.summary.unwrap().contains("Unknown"));
@@ -7,10 +7,13 @@ use crate::server::requests::*;
 async fn test_reasoning_linear_tool() {
     let server = create_test_server().await;
     let req = LinearRequest {
+        language: None,
         content: "test".to_string(),
         session_id: Some("s1".to_string()),
         confidence: Some(ConfidenceThreshold::try_from(0.8).unwrap()),
         timeout_ms: None,
+        profile: None,
+        prompt_version: None,
     };
     let resp = server.reasoning_linear(Parameters(req)).await;
     assert_eq!(resp.session_id, "s1");
@@ -20,6 +23,7 @@ async fn test_reasoning_linear_tool() {
 async fn test_reasoning_tree_tool() {
     let server = create_test_server().await;
     let req = TreeRequest {
+        language: None,
         operation: Some("create".to_string()),
         content: Some("test".to_string()),
         session_id: Some("s1".to_string()),
@@ -35,11 +39,13 @@ async fn test_reasoning_tree_tool() {
 async fn test_reasoning_divergent_tool() {
     let server = create_test_server().await;
     let req = DivergentRequest {
+        language: None,
         content: "test".to_string(),
         session_id: Some("s1".to_string()),
         num_perspectives: Some(3),
         challenge_assumptions: Some(true),
         force_rebellion: Some(false),
+        include_thinking: None,
         progress_token: None,
     };
     let resp = server.handle_divergent(req).await;
@@ -54,11 +60,13 @@ async fn test_reasoning_divergent_requires_voyage_key() {
     let server = create_test_server().await;
     let resp = server
         .handle_divergent(DivergentRequest {
+            language: None,
             content: "anything".to_string(),
             session_id: None,
             num_perspectives: Some(2),
             challenge_assumptions: None,
             force_rebellion: None,
+            include_thinking: None,
             progress_token: None,
         })
         .await;
@@ -71,6 +79,7 @@ async fn test_reasoning_divergent_requires_voyage_key() {
 async fn test_reasoning_reflection_tool() {
     let server = create_test_server().await;
     let req = ReflectionRequest {
+        language: None,
         operation: Some("process".to_string()),
         content: Some("test".to_string()),
         thought_id: None,
@@ -102,6 +111,7 @@ async fn test_reasoning_checkpoint_tool() {
 async fn test_reasoning_auto_tool() {
     let server = create_test_server().await;
     let req = AutoRequest {
+        language: None,
         content: "test".to_string(),
         hints: Some(vec!["hint".to_string()]),
         session_id: Some("s1".to_string()),
@@ -124,6 +134,7 @@ async fn test_reasoning_auto_tool() {
 async fn test_reasoning_auto_execute_linear() {
     let server = create_test_server().await;
     let req = AutoRequest {
+        language: None,
         content: "Analyze the tradeoffs between SQL and NoSQL databases step by step".to_string(),
         hints: None,
         session_id: Some("s1".to_string()),
@@ -142,6 +153,7 @@ async fn test_reasoning_auto_execute_linear() {
 async fn test_reasoning_auto_execute_false() {
     let server = create_test_server().await;
     let req = AutoRequest {
+        language: None,
         content: "test content".to_string(),
         hints: None,
         session_id: None,
@@ -161,6 +173,7 @@ async fn test_reasoning_auto_execute_false() {
 async fn test_reasoning_graph_tool() {
     let server = create_test_server().await;
     let req = GraphRequest {
+        language: None,
         operation: "init".to_string(),
         session_id: "s1".to_string(),
         content: Some("test".to_string()),
@@ -169,7 +182,9 @@ async fn test_reasoning_graph_tool() {
         node_ids: None,
         k: Some(3),
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
     let resp = server.reasoning_graph(Parameters(req)).await;
     assert_eq!(resp.session_id, "s1");
@@ -179,6 +194,8 @@ async fn test_reasoning_graph_tool() {
 async fn test_reasoning_detect_tool() {
     let server = create_test_server().await;
     let req = DetectRequest {
+        chunk: None,
+        language: None,
         detect_type: "biases".to_string(),
         content: Some("test".to_string()),
         thought_id: None,
@@ -186,6 +203,7 @@ async fn test_reasoning_detect_tool() {
         check_types: None,
         check_formal: Some(true),
         check_informal: Some(true),
+        verify_premises: None,
     };
     let resp = server.reasoning_detect(Parameters(req)).await;
     assert!(resp.detections.is_empty() || !resp.detections.is_empty());
@@ -195,7 +213,9 @@ async fn test_reasoning_detect_tool() {
 async fn test_reasoning_decision_tool() {
     let server = create_test_server().await;
     let req = DecisionRequest {
+        language: None,
         decision_type: Some("weighted".to_string()),
+        normalization: None,
         question: Some("which?".to_string()),
         options: Some(vec!["A".to_string(), "B".to_string()]),
         topic: None,
@@ -211,6 +231,8 @@ async fn test_reasoning_decision_tool() {
 async fn test_reasoning_evidence_tool() {
     let server = create_test_server().await;
     let req = EvidenceRequest {
+        chunk: None,
+        language: None,
         evidence_type: Some("assess".to_string()),
         claim: Some("claim".to_string()),
         hypothesis: None,
@@ -226,6 +248,7 @@ async fn test_reasoning_evidence_tool() {
 async fn test_reasoning_timeline_tool() {
     let server = create_test_server().await;
     let req = TimelineRequest {
+        language: None,
         operation: "create".to_string(),
         session_id: Some("s1".to_string()),
         timeline_id: None,
@@ -245,6 +268,7 @@ async fn test_reasoning_timeline_tool() {
 async fn test_reasoning_mcts_tool() {
     let server = create_test_server().await;
     let req = MctsRequest {
+        language: None,
         operation: Some("explore".to_string()),
         content: Some("test".to_string()),
         session_id: Some("s1".to_string()),
@@ -265,6 +289,7 @@ async fn test_reasoning_mcts_tool() {
 async fn test_reasoning_counterfactual_tool() {
     let server = create_test_server().await;
     let req = CounterfactualRequest {
+        language: None,
         scenario: "base".to_string(),
         intervention: "change".to_string(),
         analysis_depth: Some("counterfactual".to_string()),
@@ -308,6 +333,65 @@ async fn test_reasoning_metrics_tool() {
     let _ = resp.summary;
 }
 
+#[tokio::test]
+async fn test_reasoning_help_lists_every_mode() {
+    use crate::prompts::{Operation, ReasoningMode};
+
+    let server = create_test_server().await;
+    let req = HelpRequest { mode: None };
+    let resp = server.reasoning_help(Parameters(req)).await;
+
+    assert!(resp.error.is_none());
+    assert_eq!(resp.modes.len(), ReasoningMode::all().len());
+    for mode in ReasoningMode::all() {
+        let entry = resp
+            .modes
+            .iter()
+            .find(|m| m.mode == mode.as_str())
+            .unwrap_or_else(|| panic!("help output missing mode {mode}"));
+        assert_eq!(entry.tool, mode.tool_name());
+        assert!(!entry.description.is_empty());
+        assert!(entry.example_input.is_object());
+        assert_eq!(entry.requires_thinking, mode.requires_thinking());
+        assert_eq!(entry.thinking_budget_tokens, mode.thinking_budget());
+        // Operation lists must match the prompt routing table.
+        let expected: Vec<String> = mode
+            .operations()
+            .iter()
+            .map(|op| Operation::as_str(op).to_string())
+            .collect();
+        assert_eq!(entry.operations, expected, "operations mismatch for {mode}");
+    }
+}
+
+#[tokio::test]
+async fn test_reasoning_help_single_mode() {
+    let server = create_test_server().await;
+    let req = HelpRequest {
+        mode: Some("tree".to_string()),
+    };
+    let resp = server.reasoning_help(Parameters(req)).await;
+
+    assert!(resp.error.is_none());
+    assert_eq!(resp.modes.len(), 1);
+    assert_eq!(resp.modes[0].tool, "reasoning_tree");
+    assert!(resp.modes[0].operations.contains(&"summarize".to_string()));
+}
+
+#[tokio::test]
+async fn test_reasoning_help_unknown_mode() {
+    let server = create_test_server().await;
+    let req = HelpRequest {
+        mode: Some("bogus".to_string()),
+    };
+    let resp = server.reasoning_help(Parameters(req)).await;
+
+    assert!(resp.modes.is_empty());
+    let error = resp.error.expect("error for unknown mode");
+    assert!(error.contains("Unknown reasoning mode"));
+    assert!(error.contains("linear"));
+}
+
 // ============================================================================
 // Self-Improvement Tool Tests
 // ============================================================================
@@ -674,3 +758,616 @@ async fn test_agent_and_preset_tools_record_chain_transitions() {
         "agent_invoke -> team_run should be tracked"
     );
 }
+
+async fn seed_last_thought(
+    server: &crate::server::tools::ReasoningServer,
+    session_id: &str,
+    mode: &str,
+    confidence: f64,
+) {
+    use crate::storage::StoredThought;
+
+    server
+        .state
+        .storage
+        .create_session_with_id(session_id)
+        .await
+        .expect("create session");
+    let thought = StoredThought::new(
+        uuid::Uuid::new_v4().to_string(),
+        session_id,
+        mode,
+        "seeded thought content",
+        confidence,
+    );
+    server
+        .state
+        .storage
+        .save_stored_thought(&thought)
+        .await
+        .expect("save thought");
+}
+
+#[tokio::test]
+async fn test_reasoning_next_low_confidence_linear() {
+    let server = create_test_server().await;
+    seed_last_thought(&server, "next-low-linear", "linear", 0.4).await;
+
+    let req = NextActionRequest {
+        session_id: "next-low-linear".to_string(),
+    };
+    let resp = server.reasoning_next(Parameters(req)).await;
+
+    assert!(resp.error.is_none());
+    assert_eq!(resp.tool.as_deref(), Some("reasoning_reflection"));
+    assert_eq!(resp.operation.as_deref(), Some("process"));
+    assert_eq!(resp.based_on_mode.as_deref(), Some("linear"));
+    assert_eq!(resp.based_on_confidence, Some(0.4));
+}
+
+#[tokio::test]
+async fn test_reasoning_next_confident_linear() {
+    let server = create_test_server().await;
+    seed_last_thought(&server, "next-high-linear", "linear", 0.9).await;
+
+    let req = NextActionRequest {
+        session_id: "next-high-linear".to_string(),
+    };
+    let resp = server.reasoning_next(Parameters(req)).await;
+
+    assert!(resp.error.is_none());
+    assert_eq!(resp.tool.as_deref(), Some("reasoning_evidence"));
+    assert_eq!(resp.operation.as_deref(), Some("assess"));
+}
+
+#[tokio::test]
+async fn test_reasoning_next_graph_init_advances_to_generate() {
+    let server = create_test_server().await;
+    seed_last_thought(&server, "next-graph-init", "graph_init", 0.8).await;
+
+    let req = NextActionRequest {
+        session_id: "next-graph-init".to_string(),
+    };
+    let resp = server.reasoning_next(Parameters(req)).await;
+
+    assert!(resp.error.is_none());
+    assert_eq!(resp.tool.as_deref(), Some("reasoning_graph"));
+    assert_eq!(resp.operation.as_deref(), Some("generate"));
+}
+
+#[tokio::test]
+async fn test_reasoning_next_low_score_graph_refines_first() {
+    let server = create_test_server().await;
+    seed_last_thought(&server, "next-graph-score", "graph_score", 0.3).await;
+
+    let req = NextActionRequest {
+        session_id: "next-graph-score".to_string(),
+    };
+    let resp = server.reasoning_next(Parameters(req)).await;
+
+    assert_eq!(resp.tool.as_deref(), Some("reasoning_graph"));
+    assert_eq!(resp.operation.as_deref(), Some("refine"));
+}
+
+#[tokio::test]
+async fn test_reasoning_next_unknown_mode_falls_back_to_auto() {
+    let server = create_test_server().await;
+    seed_last_thought(&server, "next-unknown", "counterfactual", 0.7).await;
+
+    let req = NextActionRequest {
+        session_id: "next-unknown".to_string(),
+    };
+    let resp = server.reasoning_next(Parameters(req)).await;
+
+    assert!(resp.error.is_none());
+    assert_eq!(resp.tool.as_deref(), Some("reasoning_auto"));
+    assert!(resp.operation.is_none());
+    assert!(resp
+        .reason
+        .as_deref()
+        .expect("reason present")
+        .contains("counterfactual"));
+}
+
+#[tokio::test]
+async fn test_reasoning_next_empty_session_recommends_auto() {
+    let server = create_test_server().await;
+
+    let req = NextActionRequest {
+        session_id: "next-empty".to_string(),
+    };
+    let resp = server.reasoning_next(Parameters(req)).await;
+
+    assert!(resp.error.is_none());
+    assert_eq!(resp.tool.as_deref(), Some("reasoning_auto"));
+    assert!(resp.based_on_mode.is_none());
+    assert!(resp.based_on_confidence.is_none());
+}
+
+/// Seed a `decision_weighted` thought carrying a full serialized
+/// [`WeightedResponse`] in its metadata, as the weighted handler persists.
+async fn seed_weighted_thought(
+    server: &crate::server::tools::ReasoningServer,
+    session_id: &str,
+    thought_id: &str,
+    ranking: &[(&str, f64, u32)],
+    criteria: &[(&str, f64)],
+) {
+    use crate::modes::{Criterion, RankedOption, WeightedResponse};
+    use crate::storage::StoredThought;
+    use std::collections::HashMap;
+
+    let response = WeightedResponse::new(
+        thought_id,
+        session_id,
+        ranking.iter().map(|(o, _, _)| (*o).to_string()).collect(),
+        criteria
+            .iter()
+            .map(|(name, weight)| Criterion {
+                name: (*name).to_string(),
+                weight: *weight,
+                description: String::new(),
+            })
+            .collect(),
+        HashMap::new(),
+        HashMap::new(),
+        ranking
+            .iter()
+            .map(|(option, score, rank)| RankedOption {
+                option: (*option).to_string(),
+                score: *score,
+                rank: *rank,
+            })
+            .collect(),
+        "notes",
+    );
+    let metadata = serde_json::to_string(&response).expect("serialize analysis");
+    let thought = StoredThought::new(
+        thought_id,
+        session_id,
+        "decision_weighted",
+        "Decision weighted: seeded",
+        ranking.first().map_or(0.0, |(_, score, _)| *score),
+    )
+    .with_metadata(metadata);
+    server
+        .state
+        .storage
+        .save_stored_thought(&thought)
+        .await
+        .expect("save thought");
+}
+
+#[tokio::test]
+async fn test_reasoning_decision_diff_identifies_flip() {
+    let server = create_test_server().await;
+    server
+        .state
+        .storage
+        .create_session_with_id("diff-flip")
+        .await
+        .expect("create session");
+    seed_weighted_thought(
+        &server,
+        "diff-flip",
+        "dd-a",
+        &[("Alpha", 0.8, 1), ("Beta", 0.6, 2)],
+        &[("cost", 0.5), ("speed", 0.5)],
+    )
+    .await;
+    seed_weighted_thought(
+        &server,
+        "diff-flip",
+        "dd-b",
+        &[("Beta", 0.75, 1), ("Alpha", 0.7, 2)],
+        &[("cost", 0.3), ("speed", 0.7)],
+    )
+    .await;
+
+    let req = DecisionDiffRequest {
+        thought_id_a: "dd-a".to_string(),
+        thought_id_b: "dd-b".to_string(),
+    };
+    let resp = server.reasoning_decision_diff(Parameters(req)).await;
+
+    assert!(resp.error.is_none(), "unexpected error: {:?}", resp.error);
+    let diff = resp.diff.expect("diff present");
+    assert_eq!(diff["winner_changed"], serde_json::json!(true));
+    assert_eq!(diff["options"][0]["option"], "Beta");
+    assert_eq!(diff["options"][0]["rank_delta"], serde_json::json!(1));
+    assert_eq!(diff["options"][1]["option"], "Alpha");
+    assert_eq!(diff["options"][1]["rank_delta"], serde_json::json!(-1));
+    assert_eq!(diff["criteria_changes"][0]["name"], "cost");
+}
+
+#[tokio::test]
+async fn test_reasoning_decision_diff_missing_thought_errors() {
+    let server = create_test_server().await;
+
+    let req = DecisionDiffRequest {
+        thought_id_a: "dd-nope".to_string(),
+        thought_id_b: "dd-also-nope".to_string(),
+    };
+    let resp = server.reasoning_decision_diff(Parameters(req)).await;
+
+    assert!(resp.diff.is_none());
+    assert!(resp.error.expect("error").contains("dd-nope"));
+}
+
+#[tokio::test]
+async fn test_reasoning_decision_diff_rejects_non_weighted_thought() {
+    let server = create_test_server().await;
+    seed_last_thought(&server, "diff-wrong-mode", "linear", 0.7).await;
+    let last = server
+        .state
+        .storage
+        .get_last_thought("diff-wrong-mode")
+        .await
+        .expect("read")
+        .expect("thought");
+
+    let req = DecisionDiffRequest {
+        thought_id_a: last.id.clone(),
+        thought_id_b: last.id,
+    };
+    let resp = server.reasoning_decision_diff(Parameters(req)).await;
+
+    assert!(resp.diff.is_none());
+    assert!(resp
+        .error
+        .expect("error")
+        .contains("requires two decision_weighted thoughts"));
+}
+
+#[tokio::test]
+async fn test_reasoning_decision_challenge_missing_thought_errors() {
+    let server = create_test_server().await;
+
+    let req = DecisionChallengeRequest {
+        prior_thought_id: "dc-nope".to_string(),
+        session_id: None,
+        language: None,
+    };
+    let resp = server.reasoning_decision_challenge(Parameters(req)).await;
+
+    assert!(resp.challenge.is_none());
+    assert_eq!(resp.prior_thought_id, "dc-nope");
+    assert!(resp.error.expect("error").contains("dc-nope"));
+}
+
+#[tokio::test]
+async fn test_reasoning_decision_challenge_rejects_non_weighted_thought() {
+    let server = create_test_server().await;
+    seed_last_thought(&server, "challenge-wrong-mode", "linear", 0.7).await;
+    let last = server
+        .state
+        .storage
+        .get_last_thought("challenge-wrong-mode")
+        .await
+        .expect("read")
+        .expect("thought");
+
+    let req = DecisionChallengeRequest {
+        prior_thought_id: last.id,
+        session_id: None,
+        language: None,
+    };
+    let resp = server.reasoning_decision_challenge(Parameters(req)).await;
+
+    assert!(resp.challenge.is_none());
+    assert!(resp.error.expect("error").contains("decision_weighted"));
+}
+
+#[tokio::test]
+async fn test_with_response_hook_registers_and_transforms_result() {
+    use crate::server::hooks::{apply_hooks, HookContext, ResponseHook};
+    use rmcp::model::{CallToolResult, ContentBlock};
+    use serde_json::Value;
+
+    /// Hook that strips the `content` field from every response.
+    struct RedactContent;
+
+    impl ResponseHook for RedactContent {
+        fn name(&self) -> &'static str {
+            "redact_content"
+        }
+
+        fn apply(&self, _context: &HookContext, mut response: Value) -> Value {
+            if let Some(object) = response.as_object_mut() {
+                object.remove("content");
+            }
+            response
+        }
+    }
+
+    let server = create_test_server()
+        .await
+        .with_response_hook(std::sync::Arc::new(RedactContent));
+    assert_eq!(server.hooks.len(), 1);
+
+    // Run the registered hooks over a serialized result exactly as call_tool
+    // does, and assert the returned response reflects the redaction.
+    let context = HookContext {
+        tool: "reasoning_linear".to_string(),
+        elapsed_ms: 5,
+        input_tokens: 0,
+        cache_read_tokens: 0,
+    };
+    let result = CallToolResult::success(vec![ContentBlock::text(
+        r#"{"content": "secret analysis", "session_id": "s-1"}"#,
+    )]);
+    let applied = apply_hooks(&server.hooks, &context, result);
+
+    let text = &applied.content[0].as_text().expect("text block").text;
+    let value: Value = serde_json::from_str(text).expect("valid JSON");
+    assert!(value.get("content").is_none());
+    assert_eq!(value["session_id"], "s-1");
+}
+
+#[tokio::test]
+async fn test_reasoning_diff_two_decisions_reports_rank_and_score_changes() {
+    let server = create_test_server().await;
+    server
+        .state
+        .storage
+        .create_session_with_id("tdiff-dec")
+        .await
+        .expect("create session");
+    seed_weighted_thought(
+        &server,
+        "tdiff-dec",
+        "td-a",
+        &[("Alpha", 0.8, 1), ("Beta", 0.6, 2)],
+        &[("cost", 0.5), ("speed", 0.5)],
+    )
+    .await;
+    seed_weighted_thought(
+        &server,
+        "tdiff-dec",
+        "td-b",
+        &[("Beta", 0.75, 1), ("Alpha", 0.7, 2)],
+        &[("cost", 0.3), ("speed", 0.7)],
+    )
+    .await;
+
+    let req = ThoughtDiffRequest {
+        thought_id_a: "td-a".to_string(),
+        thought_id_b: "td-b".to_string(),
+    };
+    let resp = server.reasoning_diff(Parameters(req)).await;
+
+    assert!(resp.error.is_none(), "unexpected error: {:?}", resp.error);
+    let diff = resp.diff.expect("diff present");
+    assert_eq!(diff["same_type"], serde_json::json!(true));
+    let decision = &diff["decision_diff"];
+    assert_eq!(decision["winner_changed"], serde_json::json!(true));
+    assert_eq!(decision["options"][0]["option"], "Beta");
+    assert_eq!(decision["options"][0]["rank_delta"], serde_json::json!(1));
+    assert!(
+        (decision["options"][0]["score_delta"]
+            .as_f64()
+            .expect("score delta")
+            - 0.15)
+            .abs()
+            < 1e-9
+    );
+    assert_eq!(decision["options"][1]["option"], "Alpha");
+    assert_eq!(decision["options"][1]["rank_delta"], serde_json::json!(-1));
+}
+
+#[tokio::test]
+async fn test_reasoning_diff_cross_mode_thoughts_from_different_sessions() {
+    use crate::storage::StoredThought;
+
+    let server = create_test_server().await;
+    for (session, id, mode, content) in [
+        ("tdiff-s1", "td-lin", "linear", "Index the database table"),
+        (
+            "tdiff-s2",
+            "td-evi",
+            "evidence_assess",
+            "Shard the database table",
+        ),
+    ] {
+        server
+            .state
+            .storage
+            .create_session_with_id(session)
+            .await
+            .expect("create session");
+        let thought = StoredThought::new(id, session, mode, content, 0.8);
+        server
+            .state
+            .storage
+            .save_stored_thought(&thought)
+            .await
+            .expect("save thought");
+    }
+
+    let req = ThoughtDiffRequest {
+        thought_id_a: "td-lin".to_string(),
+        thought_id_b: "td-evi".to_string(),
+    };
+    let resp = server.reasoning_diff(Parameters(req)).await;
+
+    assert!(resp.error.is_none(), "unexpected error: {:?}", resp.error);
+    let diff = resp.diff.expect("diff present");
+    assert_eq!(diff["same_type"], serde_json::json!(false));
+    assert_eq!(diff["cross_session"], serde_json::json!(true));
+    assert!(diff.get("decision_diff").is_none());
+    let similarity = &diff["similarity"];
+    assert!(similarity["similarity"].as_f64().expect("similarity") > 0.0);
+    assert!(similarity["summary"]
+        .as_str()
+        .expect("summary")
+        .contains("Different result types"));
+}
+
+#[tokio::test]
+async fn test_reasoning_diff_missing_thought_errors() {
+    let server = create_test_server().await;
+
+    let req = ThoughtDiffRequest {
+        thought_id_a: "td-nope".to_string(),
+        thought_id_b: "td-also-nope".to_string(),
+    };
+    let resp = server.reasoning_diff(Parameters(req)).await;
+
+    assert!(resp.diff.is_none());
+    assert!(resp.error.expect("error").contains("td-nope"));
+}
+
+#[tokio::test]
+async fn test_reasoning_auto_stats_reports_frequencies_and_averages() {
+    use crate::storage::{StoredAutoSelection, StoredThought};
+
+    let server = create_test_server().await;
+
+    // Seed: auto picked linear twice and tree once.
+    for (id, session, mode, confidence) in [
+        ("as-1", "as-s1", "linear", 0.8),
+        ("as-2", "as-s2", "linear", 0.6),
+        ("as-3", "as-s3", "tree", 0.5),
+    ] {
+        server
+            .state
+            .storage
+            .save_auto_selection(&StoredAutoSelection::new(id, session, mode, confidence))
+            .await
+            .expect("save selection");
+    }
+
+    // Following the suggestion in as-s1 scores that selection: the thought
+    // write path backfills the pending linear selection with 0.9.
+    server
+        .state
+        .storage
+        .create_session_with_id("as-s1")
+        .await
+        .expect("create session");
+    server
+        .state
+        .storage
+        .save_stored_thought(&StoredThought::new(
+            "as-t1",
+            "as-s1",
+            "linear",
+            "Followed the suggestion",
+            0.9,
+        ))
+        .await
+        .expect("save thought");
+
+    let resp = server
+        .reasoning_auto_stats(Parameters(AutoStatsRequest {}))
+        .await;
+    assert!(resp.error.is_none());
+    assert_eq!(resp.total_selections, 3);
+    assert_eq!(resp.stats.len(), 2);
+    // Most-selected first.
+    assert_eq!(resp.stats[0].selected_mode, "linear");
+    assert_eq!(resp.stats[0].selections, 2);
+    assert!((resp.stats[0].avg_confidence - 0.7).abs() < 1e-9);
+    assert_eq!(resp.stats[0].scored, 1);
+    assert!((resp.stats[0].avg_score.expect("scored") - 0.9).abs() < 1e-9);
+    assert_eq!(resp.stats[1].selected_mode, "tree");
+    assert_eq!(resp.stats[1].selections, 1);
+    assert!(resp.stats[1].avg_score.is_none());
+}
+
+#[tokio::test]
+async fn test_reasoning_auto_stats_empty_without_history() {
+    let server = create_test_server().await;
+    let resp = server
+        .reasoning_auto_stats(Parameters(AutoStatsRequest {}))
+        .await;
+    assert!(resp.error.is_none());
+    assert_eq!(resp.total_selections, 0);
+    assert!(resp.stats.is_empty());
+}
+
+#[tokio::test]
+async fn test_reasoning_export_renders_each_format_for_one_session() {
+    use crate::storage::{StoredGraphEdge, StoredGraphNode, StoredThought};
+
+    let server = create_test_server().await;
+    server
+        .state
+        .storage
+        .create_session_with_id("texp-1")
+        .await
+        .expect("create session");
+    server
+        .state
+        .storage
+        .save_stored_thought(&StoredThought::new(
+            "texp-t1", "texp-1", "linear", "Step one", 0.7,
+        ))
+        .await
+        .expect("save thought");
+    server
+        .state
+        .storage
+        .save_graph_node(&StoredGraphNode::new("texp-n1", "texp-1", "Root"))
+        .await
+        .expect("save node");
+    server
+        .state
+        .storage
+        .save_graph_node(&StoredGraphNode::new("texp-n2", "texp-1", "Child"))
+        .await
+        .expect("save node");
+    server
+        .state
+        .storage
+        .save_graph_edge(&StoredGraphEdge::new(
+            "texp-e1", "texp-1", "texp-n1", "texp-n2",
+        ))
+        .await
+        .expect("save edge");
+
+    let export = |format: &str| {
+        server.reasoning_export(Parameters(ExportSessionRequest {
+            session_id: "texp-1".to_string(),
+            format: format.to_string(),
+        }))
+    };
+
+    let json = export("json").await;
+    assert!(json.error.is_none(), "json error: {:?}", json.error);
+    let parsed: serde_json::Value = serde_json::from_str(&json.content).expect("valid JSON");
+    assert_eq!(parsed["session"]["id"], "texp-1");
+    assert_eq!(parsed["graph_nodes"].as_array().expect("nodes").len(), 2);
+
+    let markdown = export("markdown").await;
+    assert!(markdown.error.is_none());
+    assert!(markdown.content.starts_with("# Session texp-1"));
+    assert!(markdown.content.contains("Step one"));
+
+    let dot = export("dot").await;
+    assert!(dot.error.is_none());
+    assert!(dot.content.starts_with("digraph reasoning {"));
+    assert!(dot.content.contains("\"texp-n1\" -> \"texp-n2\""));
+}
+
+#[tokio::test]
+async fn test_reasoning_export_rejects_unknown_format() {
+    let server = create_test_server().await;
+    server
+        .state
+        .storage
+        .create_session_with_id("texp-2")
+        .await
+        .expect("create session");
+
+    let resp = server
+        .reasoning_export(Parameters(ExportSessionRequest {
+            session_id: "texp-2".to_string(),
+            format: "yaml".to_string(),
+        }))
+        .await;
+
+    assert!(resp.content.is_empty());
+    let error = resp.error.expect("error present");
+    assert!(error.contains("json, markdown, or dot"), "{error}");
+}
@@ -202,6 +202,27 @@ async fn test_metrics_fallbacks() {
     assert!(resp.invocations.is_some());
 }
 
+#[tokio::test]
+async fn test_metrics_retries() {
+    let server = create_test_server().await;
+    // Seed retry counters the way the client retry loop does.
+    server.state.metrics.record_attempt("linear");
+    server.state.metrics.record_attempt("linear");
+    server.state.metrics.record_retry("linear", "rate_limited");
+    let req = MetricsRequest {
+        query: "retries".to_string(),
+        mode_name: None,
+        tool_name: None,
+        session_id: None,
+        success_only: None,
+        limit: None,
+    };
+    let resp = server.reasoning_metrics(Parameters(req)).await;
+    let retries = resp.retries.expect("retries payload");
+    assert_eq!(retries["attempts_total"]["linear"], 2);
+    assert_eq!(retries["retries_total"]["linear/rate_limited"], 1);
+}
+
 #[tokio::test]
 async fn test_metrics_config() {
     let server = create_test_server().await;
@@ -293,6 +314,7 @@ async fn test_checkpoint_unknown_operation() {
 async fn test_reflection_evaluate_operation() {
     let server = create_test_server().await;
     let req = ReflectionRequest {
+        language: None,
         operation: Some("evaluate".to_string()),
         content: Some("test content".to_string()),
         thought_id: None,
@@ -310,6 +332,7 @@ async fn test_reflection_evaluate_operation() {
 async fn test_reflection_unknown_operation() {
     let server = create_test_server().await;
     let req = ReflectionRequest {
+        language: None,
         operation: Some("invalid".to_string()),
         content: Some("test".to_string()),
         thought_id: None,
@@ -331,6 +354,7 @@ async fn test_reflection_unknown_operation() {
 async fn test_tree_focus_operation() {
     let server = create_test_server().await;
     let req = TreeRequest {
+        language: None,
         operation: Some("focus".to_string()),
         content: None,
         session_id: Some("s1".to_string()),
@@ -346,6 +370,7 @@ async fn test_tree_focus_operation() {
 async fn test_tree_list_operation() {
     let server = create_test_server().await;
     let req = TreeRequest {
+        language: None,
         operation: Some("list".to_string()),
         content: None,
         session_id: Some("s1".to_string()),
@@ -361,6 +386,7 @@ async fn test_tree_list_operation() {
 async fn test_tree_complete_operation() {
     let server = create_test_server().await;
     let req = TreeRequest {
+        language: None,
         operation: Some("complete".to_string()),
         content: None,
         session_id: Some("s1".to_string()),
@@ -376,6 +402,7 @@ async fn test_tree_complete_operation() {
 async fn test_tree_unknown_operation() {
     let server = create_test_server().await;
     let req = TreeRequest {
+        language: None,
         operation: Some("invalid".to_string()),
         content: None,
         session_id: Some("s1".to_string()),
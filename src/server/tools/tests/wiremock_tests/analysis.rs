@@ -27,6 +27,7 @@ async fn test_graph_all_operations() {
 
     // Test init
     let init_req = GraphRequest {
+        language: None,
         operation: "init".to_string(),
         session_id: "s1".to_string(),
         content: Some("Problem to explore".to_string()),
@@ -35,7 +36,9 @@ async fn test_graph_all_operations() {
         node_ids: None,
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
     let resp = server.reasoning_graph(Parameters(init_req)).await;
     assert_eq!(resp.session_id, "s1");
@@ -57,6 +60,7 @@ async fn test_graph_all_operations() {
         .await;
 
     let generate_req = GraphRequest {
+        language: None,
         operation: "generate".to_string(),
         session_id: "s1".to_string(),
         content: Some("Generate continuations".to_string()),
@@ -65,13 +69,16 @@ async fn test_graph_all_operations() {
         node_ids: None,
         k: Some(2),
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
     let resp = server.reasoning_graph(Parameters(generate_req)).await;
     assert_eq!(resp.session_id, "s1");
 
     // Test unknown operation
     let unknown_req = GraphRequest {
+        language: None,
         operation: "unknown".to_string(),
         session_id: "s1".to_string(),
         content: None,
@@ -80,7 +87,9 @@ async fn test_graph_all_operations() {
         node_ids: None,
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
     let resp = server.reasoning_graph(Parameters(unknown_req)).await;
     assert!(resp
@@ -125,6 +134,8 @@ async fn test_detect_all_types() {
     let server = create_mocked_server(&mock_server).await;
 
     let biases_req = DetectRequest {
+        chunk: None,
+        language: None,
         detect_type: "biases".to_string(),
         content: Some("Argument with potential bias".to_string()),
         thought_id: None,
@@ -132,6 +143,7 @@ async fn test_detect_all_types() {
         check_types: None,
         check_formal: None,
         check_informal: None,
+        verify_premises: None,
     };
     let resp = server.reasoning_detect(Parameters(biases_req)).await;
     assert!(resp.summary.is_some());
@@ -173,6 +185,8 @@ async fn test_detect_all_types() {
         .await;
 
     let fallacies_req = DetectRequest {
+        chunk: None,
+        language: None,
         detect_type: "fallacies".to_string(),
         content: Some("Argument with fallacy".to_string()),
         thought_id: None,
@@ -180,12 +194,15 @@ async fn test_detect_all_types() {
         check_types: None,
         check_formal: Some(true),
         check_informal: Some(true),
+        verify_premises: None,
     };
     let resp = server.reasoning_detect(Parameters(fallacies_req)).await;
     assert!(resp.summary.is_some());
 
     // Test unknown type
     let unknown_req = DetectRequest {
+        chunk: None,
+        language: None,
         detect_type: "unknown".to_string(),
         content: Some("Content".to_string()),
         thought_id: None,
@@ -193,6 +210,7 @@ async fn test_detect_all_types() {
         check_types: None,
         check_formal: None,
         check_informal: None,
+        verify_premises: None,
     };
     let resp = server.reasoning_detect(Parameters(unknown_req)).await;
     assert!(resp.summary.unwrap().contains("Unknown"));
@@ -233,7 +251,9 @@ async fn test_decision_all_types() {
     let server = create_mocked_server(&mock_server).await;
 
     let weighted_req = DecisionRequest {
+        language: None,
         decision_type: Some("weighted".to_string()),
+        normalization: None,
         question: Some("Which option?".to_string()),
         options: Some(vec!["A".to_string(), "B".to_string()]),
         topic: None,
@@ -265,7 +285,9 @@ async fn test_decision_all_types() {
         .await;
 
     let pairwise_req = DecisionRequest {
+        language: None,
         decision_type: Some("pairwise".to_string()),
+        normalization: None,
         question: Some("Compare options".to_string()),
         options: Some(vec!["A".to_string(), "B".to_string()]),
         topic: None,
@@ -277,7 +299,9 @@ async fn test_decision_all_types() {
 
     // Test unknown type (defaults to weighted)
     let default_req = DecisionRequest {
+        language: None,
         decision_type: None,
+        normalization: None,
         question: Some("Question".to_string()),
         options: None,
         topic: None,
@@ -319,6 +343,8 @@ async fn test_evidence_all_types() {
     let server = create_mocked_server(&mock_server).await;
 
     let assess_req = EvidenceRequest {
+        chunk: None,
+        language: None,
         evidence_type: Some("assess".to_string()),
         claim: Some("The claim".to_string()),
         hypothesis: None,
@@ -348,6 +374,8 @@ async fn test_evidence_all_types() {
         .await;
 
     let prob_req = EvidenceRequest {
+        chunk: None,
+        language: None,
         evidence_type: Some("probabilistic".to_string()),
         claim: None,
         hypothesis: Some("Hypothesis".to_string()),
@@ -360,6 +388,8 @@ async fn test_evidence_all_types() {
 
     // Test unknown type (defaults to assess)
     let default_req = EvidenceRequest {
+        chunk: None,
+        language: None,
         evidence_type: None,
         claim: Some("Claim".to_string()),
         hypothesis: None,
@@ -0,0 +1,125 @@
+//! End-to-end proof of the bounded admission gate (`MAX_PENDING_REQUESTS`).
+//!
+//! A real rmcp client talks to the `ReasoningServer` over an in-process duplex
+//! transport while the mocked Anthropic backend responds slowly. With the
+//! pending-request bound saturated, a further call must be rejected busy
+//! rather than accepted as unbounded work — and must succeed again once
+//! capacity frees.
+
+use std::time::Duration;
+
+use rmcp::model::CallToolRequestParams;
+use rmcp::ServiceExt;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use super::{anthropic_response, create_mocked_server_with};
+use crate::server::tools::ReasoningServer;
+
+/// Serve `server` over an in-process duplex pipe and hand back a connected
+/// client plus the running server (dropping the latter closes the transport).
+async fn connect(
+    server: ReasoningServer,
+) -> (
+    rmcp::service::RunningService<rmcp::service::RoleClient, ()>,
+    rmcp::service::RunningService<rmcp::service::RoleServer, ReasoningServer>,
+) {
+    let (server_io, client_io) = tokio::io::duplex(64 * 1024);
+    let server_task = tokio::spawn(async move { server.serve(server_io).await });
+    let client = ().serve(client_io).await.expect("client init");
+    let server = server_task
+        .await
+        .expect("join server")
+        .expect("server init");
+    (client, server)
+}
+
+fn linear_call() -> CallToolRequestParams {
+    let mut params = CallToolRequestParams::new("reasoning_linear");
+    params.arguments = serde_json::json!({"content": "Analyze this problem"})
+        .as_object()
+        .cloned();
+    params
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn saturated_queue_rejects_busy_until_capacity_frees() {
+    // Slow backend: each admitted call holds its permit for ~1s.
+    let mock = MockServer::start().await;
+    let response_json = serde_json::json!({
+        "analysis": "Detailed reasoning analysis",
+        "confidence": 0.85,
+        "next_step": "Continue"
+    });
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(anthropic_response(&response_json.to_string()))
+                .set_delay(Duration::from_secs(1)),
+        )
+        .mount(&mock)
+        .await;
+
+    let server = create_mocked_server_with(&mock, |c| {
+        c.max_pending_requests = 1;
+    })
+    .await;
+    let (client, _server) = connect(server).await;
+
+    // Saturate the single admission slot with a slow call.
+    let slow_client = client.clone();
+    let slow = tokio::spawn(async move { slow_client.call_tool(linear_call()).await });
+    // Let the slow call reach the server and take its permit before flooding.
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // Every further call is rejected busy up front — no queueing, no new work.
+    let err = client
+        .call_tool(linear_call())
+        .await
+        .expect_err("call beyond the pending bound must be rejected");
+    assert!(
+        err.to_string().contains("server overloaded"),
+        "expected busy error, got: {err}"
+    );
+
+    // The admitted call is unaffected by the rejected flood.
+    let slow_result = slow.await.expect("join slow call").expect("slow call");
+    assert!(!slow_result.is_error.unwrap_or(false));
+
+    // Its permit is released on completion, so capacity frees and the same
+    // request that was just rejected now succeeds.
+    let result = client
+        .call_tool(linear_call())
+        .await
+        .expect("call after capacity freed");
+    assert!(!result.is_error.unwrap_or(false));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn default_bound_admits_normal_traffic() {
+    // No artificial delay and the default bound: sequential calls all pass the
+    // gate untouched.
+    let mock = MockServer::start().await;
+    let response_json = serde_json::json!({
+        "analysis": "Detailed reasoning analysis",
+        "confidence": 0.85,
+        "next_step": "Continue"
+    });
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(anthropic_response(&response_json.to_string())),
+        )
+        .mount(&mock)
+        .await;
+
+    let server = create_mocked_server_with(&mock, |_| {}).await;
+    let (client, _server) = connect(server).await;
+
+    for _ in 0..3 {
+        let result = client.call_tool(linear_call()).await.expect("call_tool");
+        assert!(!result.is_error.unwrap_or(false));
+    }
+}
@@ -74,6 +74,7 @@ async fn test_auto_execute_true_linear() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = AutoRequest {
+        language: None,
         content: "Analyze this step by step".to_string(),
         hints: None,
         session_id: None,
@@ -104,6 +105,7 @@ async fn test_auto_execute_true_divergent() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = AutoRequest {
+        language: None,
         content: "Explore multiple perspectives on this design decision".to_string(),
         hints: None,
         session_id: None,
@@ -133,6 +135,7 @@ async fn test_auto_execute_true_complex_mode_returns_next_call() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = AutoRequest {
+        language: None,
         content: "Explore this complex decision space".to_string(),
         hints: None,
         session_id: None,
@@ -210,6 +213,7 @@ async fn test_tree_focus_success_path() {
 
     // Step 1: Create a tree — branches get stored with generated IDs
     let create_req = TreeRequest {
+        language: None,
         operation: Some("create".to_string()),
         content: Some("Should we use a monolith or microservices architecture?".to_string()),
         session_id: Some("s-tree-focus".to_string()),
@@ -229,6 +233,7 @@ async fn test_tree_focus_success_path() {
 
     // Step 3: Focus on that branch using its real ID
     let focus_req = TreeRequest {
+        language: None,
         operation: Some("focus".to_string()),
         content: None,
         session_id: Some("s-tree-focus".to_string()),
@@ -263,6 +268,7 @@ async fn test_tree_complete_success_path() {
 
     // Step 1: Create branches first
     let create_req = TreeRequest {
+        language: None,
         operation: Some("create".to_string()),
         content: Some("Evaluate the three candidate solutions".to_string()),
         session_id: Some("s-tree-complete".to_string()),
@@ -276,6 +282,7 @@ async fn test_tree_complete_success_path() {
 
     // Step 2: Mark a branch as complete using its real ID
     let complete_req = TreeRequest {
+        language: None,
         operation: Some("complete".to_string()),
         content: None,
         session_id: Some("s-tree-complete".to_string()),
@@ -0,0 +1,131 @@
+//! End-to-end proof of pre-dispatch argument coercion (`server::coerce`).
+//!
+//! A real rmcp client calls `tools/call` over an in-process duplex transport:
+//! a known numeric parameter sent as a string must be coerced and dispatch
+//! normally, an explicit null for an optional must read as absent, and a
+//! genuinely wrong type must still be rejected with a parameter error.
+
+use rmcp::model::CallToolRequestParams;
+use rmcp::ServiceExt;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use super::{anthropic_response, create_mocked_server};
+use crate::server::tools::ReasoningServer;
+
+/// Serve `server` over an in-process duplex pipe and hand back a connected
+/// client plus the running server (dropping the latter closes the transport).
+async fn connect(
+    server: ReasoningServer,
+) -> (
+    rmcp::service::RunningService<rmcp::service::RoleClient, ()>,
+    rmcp::service::RunningService<rmcp::service::RoleServer, ReasoningServer>,
+) {
+    let (server_io, client_io) = tokio::io::duplex(64 * 1024);
+    let server_task = tokio::spawn(async move { server.serve(server_io).await });
+    let client = ().serve(client_io).await.expect("client init");
+    let server = server_task
+        .await
+        .expect("join server")
+        .expect("server init");
+    (client, server)
+}
+
+fn call(name: &'static str, arguments: &serde_json::Value) -> CallToolRequestParams {
+    let mut params = CallToolRequestParams::new(name);
+    params.arguments = arguments.as_object().cloned();
+    params
+}
+
+async fn tree_server(mock: &MockServer) -> ReasoningServer {
+    let create_json = serde_json::json!({
+        "branches": [
+            {"id": "b1", "content": "Branch 1", "score": 0.8},
+            {"id": "b2", "content": "Branch 2", "score": 0.7},
+            {"id": "b3", "content": "Branch 3", "score": 0.6}
+        ],
+        "recommendation": "Explore branch 1 first"
+    });
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(anthropic_response(&create_json.to_string())),
+        )
+        .mount(mock)
+        .await;
+    create_mocked_server(mock).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn string_num_branches_is_coerced_and_dispatches() {
+    let mock = MockServer::start().await;
+    let server = tree_server(&mock).await;
+    let (client, _server) = connect(server).await;
+
+    let result = client
+        .call_tool(call(
+            "reasoning_tree",
+            &serde_json::json!({
+                "operation": "create",
+                "content": "Ways to reduce API latency",
+                "num_branches": "3"
+            }),
+        ))
+        .await
+        .expect("string num_branches must be coerced, not rejected");
+    assert!(!result.is_error.unwrap_or(false));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn null_optional_reads_as_absent() {
+    let mock = MockServer::start().await;
+    let server = tree_server(&mock).await;
+    let (client, _server) = connect(server).await;
+
+    // `session_id: null` spelled out instead of omitted must not fail
+    // deserialization — the handler starts a fresh session as if absent.
+    let result = client
+        .call_tool(call(
+            "reasoning_tree",
+            &serde_json::json!({
+                "operation": "create",
+                "content": "Ways to reduce API latency",
+                "session_id": null
+            }),
+        ))
+        .await
+        .expect("null optional must read as absent");
+    assert!(!result.is_error.unwrap_or(false));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn genuinely_wrong_type_is_still_rejected() {
+    let mock = MockServer::start().await;
+    let server = tree_server(&mock).await;
+    let (client, _server) = connect(server).await;
+
+    // An array can't be coerced to a number; the deserializer must reject it
+    // (surfaced as a tool error result naming the parameter failure).
+    let result = client
+        .call_tool(call(
+            "reasoning_tree",
+            &serde_json::json!({
+                "operation": "create",
+                "content": "Ways to reduce API latency",
+                "num_branches": ["not", "a", "number"]
+            }),
+        ))
+        .await
+        .expect("rejection is a tool error result, not a transport failure");
+    assert_eq!(result.is_error, Some(true));
+    let text = result
+        .content
+        .first()
+        .and_then(|block| block.as_text())
+        .map(|t| t.text.clone())
+        .unwrap_or_default();
+    assert!(
+        text.contains("failed to deserialize parameters"),
+        "unexpected rejection message: {text}"
+    );
+}
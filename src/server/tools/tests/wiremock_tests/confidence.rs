@@ -12,7 +12,7 @@ use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use super::{anthropic_response, create_mocked_server};
-use crate::server::requests::ConfidenceRouteRequest;
+use crate::server::requests::{ConfidenceRouteRequest, SolveRequest};
 
 // ============================================================================
 // Helpers
@@ -349,3 +349,163 @@ async fn test_confidence_route_response_fields_populated() {
     assert!(!resp.routing_reason.is_empty());
     assert!(!resp.result.is_null());
 }
+
+// ============================================================================
+// reasoning_solve: escalation ladder
+// ============================================================================
+
+/// A body that satisfies the linear parser with a chosen confidence.
+fn linear_json(confidence: f64) -> String {
+    serde_json::json!({
+        "analysis": "Initial sequential take on the problem.",
+        "confidence": confidence,
+        "next_step": "Escalate if this is not convincing"
+    })
+    .to_string()
+}
+
+/// A body that satisfies the tree "create" parser with chosen branch scores.
+fn tree_json(scores: &[f64]) -> String {
+    let branches: Vec<serde_json::Value> = scores
+        .iter()
+        .enumerate()
+        .map(|(i, score)| {
+            serde_json::json!({
+                "id": format!("b{}", i + 1),
+                "content": format!("Approach {}", i + 1),
+                "score": score
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "branches": branches,
+        "recommendation": "Explore the strongest branch"
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn test_solve_escalates_to_tree_when_linear_below_threshold() {
+    let mock_server = MockServer::start().await;
+
+    // First call (linear rung): confidence 0.4 < default threshold 0.75.
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(anthropic_response(&linear_json(0.4))),
+        )
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    // Second call (tree rung): best branch 0.9 >= threshold → ladder stops.
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(anthropic_response(&tree_json(&[0.9, 0.8]))),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let server = create_mocked_server(&mock_server).await;
+    let req = SolveRequest {
+        content: "Pick a caching strategy for the read path".to_string(),
+        session_id: None,
+        confidence_threshold: None,
+        ladder: None,
+    };
+
+    let resp = server.reasoning_solve(Parameters(req)).await;
+    assert!(resp.solved);
+    assert!(resp.error.is_none());
+    assert_eq!(resp.best_rung, "tree");
+    assert!((resp.best_confidence - 0.9).abs() < 1e-6);
+    // The ladder stopped at the second rung: graph was never attempted.
+    assert_eq!(resp.path.len(), 2);
+    assert_eq!(resp.path[0].rung, "linear");
+    assert!((resp.path[0].confidence - 0.4).abs() < 1e-6);
+    assert!(!resp.path[0].met_threshold);
+    assert_eq!(resp.path[1].rung, "tree");
+    assert!(resp.path[1].met_threshold);
+    assert!(!resp.session_id.is_empty());
+}
+
+#[tokio::test]
+async fn test_solve_stops_at_first_rung_when_threshold_met() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(anthropic_response(&linear_json(0.9))),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let server = create_mocked_server(&mock_server).await;
+    let req = SolveRequest {
+        content: "What is the obvious first step?".to_string(),
+        session_id: None,
+        confidence_threshold: None,
+        ladder: None,
+    };
+
+    let resp = server.reasoning_solve(Parameters(req)).await;
+    assert!(resp.solved);
+    assert_eq!(resp.best_rung, "linear");
+    assert!((resp.best_confidence - 0.9).abs() < 1e-6);
+    assert_eq!(resp.path.len(), 1);
+    assert!(resp.path[0].met_threshold);
+}
+
+#[tokio::test]
+async fn test_solve_exhausted_ladder_returns_best_unsolved() {
+    let mock_server = MockServer::start().await;
+
+    // Single-rung ladder that never reaches the threshold.
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(anthropic_response(&linear_json(0.5))),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let server = create_mocked_server(&mock_server).await;
+    let req = SolveRequest {
+        content: "A question with no confident answer".to_string(),
+        session_id: None,
+        confidence_threshold: Some(0.95),
+        ladder: Some(vec!["linear".to_string()]),
+    };
+
+    let resp = server.reasoning_solve(Parameters(req)).await;
+    assert!(!resp.solved);
+    // The best attempt is still returned so the caller gets something usable.
+    assert!(resp.error.is_none());
+    assert_eq!(resp.best_rung, "linear");
+    assert!((resp.best_confidence - 0.5).abs() < 1e-6);
+    assert_eq!(resp.path.len(), 1);
+    assert!(!resp.path[0].met_threshold);
+}
+
+#[tokio::test]
+async fn test_solve_invalid_ladder_rung_errors() {
+    let mock_server = MockServer::start().await;
+    let server = create_mocked_server(&mock_server).await;
+
+    let req = SolveRequest {
+        content: "Never reaches the API".to_string(),
+        session_id: None,
+        confidence_threshold: None,
+        ladder: Some(vec!["mcts".to_string()]),
+    };
+
+    let resp = server.reasoning_solve(Parameters(req)).await;
+    assert!(!resp.solved);
+    assert!(resp.path.is_empty());
+    let error = resp.error.expect("invalid ladder should error");
+    assert!(
+        error.contains("ladder"),
+        "error should name the field: {error}"
+    );
+}
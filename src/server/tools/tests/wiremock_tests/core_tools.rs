@@ -26,10 +26,13 @@ async fn test_linear_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = LinearRequest {
+        language: None,
         content: "Analyze this problem".to_string(),
         session_id: None,
         confidence: Some(ConfidenceThreshold::try_from(0.8).unwrap()),
         timeout_ms: None,
+        profile: None,
+        prompt_version: None,
     };
 
     let resp = server.reasoning_linear(Parameters(req)).await;
@@ -63,6 +66,7 @@ async fn test_tree_all_operations() {
 
     // Test create
     let create_req = TreeRequest {
+        language: None,
         operation: Some("create".to_string()),
         content: Some("Explore this topic".to_string()),
         session_id: Some("s1".to_string()),
@@ -75,6 +79,7 @@ async fn test_tree_all_operations() {
 
     // Test list
     let list_req = TreeRequest {
+        language: None,
         operation: Some("list".to_string()),
         content: None,
         session_id: Some("s1".to_string()),
@@ -87,6 +92,7 @@ async fn test_tree_all_operations() {
 
     // Test focus
     let focus_req = TreeRequest {
+        language: None,
         operation: Some("focus".to_string()),
         content: None,
         session_id: Some("s1".to_string()),
@@ -99,6 +105,7 @@ async fn test_tree_all_operations() {
 
     // Test complete
     let complete_req = TreeRequest {
+        language: None,
         operation: Some("complete".to_string()),
         content: None,
         session_id: Some("s1".to_string()),
@@ -111,6 +118,7 @@ async fn test_tree_all_operations() {
 
     // Test unknown operation
     let unknown_req = TreeRequest {
+        language: None,
         operation: Some("unknown".to_string()),
         content: None,
         session_id: Some("s1".to_string()),
@@ -169,6 +177,7 @@ async fn test_tree_summarize_operation() {
 
     // Create a session first
     let create_req = TreeRequest {
+        language: None,
         operation: Some("create".to_string()),
         content: Some("Explore this topic".to_string()),
         session_id: Some("s-summarize".to_string()),
@@ -181,6 +190,7 @@ async fn test_tree_summarize_operation() {
 
     // Now summarize
     let summarize_req = TreeRequest {
+        language: None,
         operation: Some("summarize".to_string()),
         content: None,
         session_id: Some("s-summarize".to_string()),
@@ -222,11 +232,13 @@ async fn test_divergent_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = DivergentRequest {
+        language: None,
         content: "Analyze from multiple perspectives".to_string(),
         session_id: Some("s1".to_string()),
         num_perspectives: Some(2),
         challenge_assumptions: Some(true),
         force_rebellion: Some(true),
+        include_thinking: None,
         progress_token: None,
     };
 
@@ -263,6 +275,7 @@ async fn test_reflection_all_operations() {
 
     // Test process
     let process_req = ReflectionRequest {
+        language: None,
         operation: Some("process".to_string()),
         content: Some("Reasoning to improve".to_string()),
         thought_id: None,
@@ -295,6 +308,7 @@ async fn test_reflection_all_operations() {
         .await;
 
     let evaluate_req = ReflectionRequest {
+        language: None,
         operation: Some("evaluate".to_string()),
         content: None,
         thought_id: None,
@@ -308,6 +322,7 @@ async fn test_reflection_all_operations() {
 
     // Test unknown operation
     let unknown_req = ReflectionRequest {
+        language: None,
         operation: Some("unknown".to_string()),
         content: None,
         thought_id: None,
@@ -404,6 +419,7 @@ async fn test_auto_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = AutoRequest {
+        language: None,
         content: "Complex problem with multiple paths".to_string(),
         hints: Some(vec!["exploration".to_string()]),
         session_id: Some("s1".to_string()),
@@ -50,6 +50,8 @@ async fn test_detect_low_argument_strength() {
     let server = create_mocked_server(&mock_server).await;
 
     let req = DetectRequest {
+        chunk: None,
+        language: None,
         detect_type: "fallacies".to_string(),
         content: Some("Weak argument".to_string()),
         thought_id: None,
@@ -57,6 +59,7 @@ async fn test_detect_low_argument_strength() {
         check_types: None,
         check_formal: None,
         check_informal: None,
+        verify_premises: None,
     };
     let resp = server.reasoning_detect(Parameters(req)).await;
     // Per-fallacy severity "high" passes through from the model field.
@@ -108,6 +111,8 @@ async fn test_detect_medium_argument_strength() {
     let server = create_mocked_server(&mock_server).await;
 
     let req = DetectRequest {
+        chunk: None,
+        language: None,
         detect_type: "fallacies".to_string(),
         content: Some("Medium strength argument".to_string()),
         thought_id: None,
@@ -115,6 +120,7 @@ async fn test_detect_medium_argument_strength() {
         check_types: None,
         check_formal: None,
         check_informal: None,
+        verify_premises: None,
     };
     let resp = server.reasoning_detect(Parameters(req)).await;
     // Per-fallacy severity "medium" passes through from the model field.
@@ -166,6 +172,8 @@ async fn test_detect_high_argument_strength() {
     let server = create_mocked_server(&mock_server).await;
 
     let req = DetectRequest {
+        chunk: None,
+        language: None,
         detect_type: "fallacies".to_string(),
         content: Some("Strong argument".to_string()),
         thought_id: None,
@@ -173,6 +181,7 @@ async fn test_detect_high_argument_strength() {
         check_types: None,
         check_formal: None,
         check_informal: None,
+        verify_premises: None,
     };
     let resp = server.reasoning_detect(Parameters(req)).await;
     // Per-fallacy severity "low" passes through from the model field.
@@ -181,6 +190,84 @@ async fn test_detect_high_argument_strength() {
     }
 }
 
+#[tokio::test]
+async fn test_detect_filters_unverified_when_configured() {
+    let mock_server = MockServer::start().await;
+
+    // One passage quotes the input; the other is fabricated.
+    let fallacies_json = serde_json::json!({
+        "fallacies_detected": [
+            {
+                "fallacy": "ad hominem",
+                "category": "informal",
+                "passage": "you're wrong because you're stupid",
+                "severity": "high",
+                "confidence": 0.9,
+                "explanation": "Attacks the person",
+                "correction": "Address the argument"
+            },
+            {
+                "fallacy": "appeal to popularity",
+                "category": "informal",
+                "passage": "everyone already agrees the plan is perfect",
+                "severity": "medium",
+                "confidence": 0.7,
+                "explanation": "Popularity is not truth",
+                "correction": "Cite evidence"
+            }
+        ],
+        "argument_structure": {
+            "premises": ["P1"],
+            "conclusion": "C",
+            "validity": "invalid"
+        },
+        "overall_assessment": {
+            "fallacy_count": 2,
+            "most_critical": "ad hominem",
+            "argument_strength": 0.3
+        }
+    });
+
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(anthropic_response(&fallacies_json.to_string())),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let server = super::create_mocked_server_with(&mock_server, |config| {
+        config.detect_filter_unverified = true;
+    })
+    .await;
+
+    let req = DetectRequest {
+        chunk: None,
+        language: None,
+        detect_type: "fallacies".to_string(),
+        content: Some("You're wrong because you're stupid, so we reject it".to_string()),
+        thought_id: None,
+        session_id: None,
+        check_types: None,
+        check_formal: None,
+        check_informal: None,
+        verify_premises: None,
+    };
+    let resp = server.reasoning_detect(Parameters(req)).await;
+
+    // The fabricated detection is dropped; the grounded one survives.
+    assert_eq!(resp.detections.len(), 1);
+    assert_eq!(resp.detections[0].detection_type, "ad hominem");
+    assert_eq!(resp.detections[0].grounded, Some(true));
+    // Validation (computed before filtering) still names the dropped item.
+    let validation = resp.validation.expect("validation present");
+    assert!(validation
+        .warnings
+        .iter()
+        .any(|w| w.contains("appeal to popularity")));
+}
+
 #[tokio::test]
 async fn test_into_contents_implementations() {
     // Test all response types' IntoContents implementations
@@ -329,6 +416,7 @@ async fn test_into_contents_implementations() {
         synthesis: None,
         evidential_support: None,
         pivot_evidence: None,
+        gaps: None,
         bayesian: None,
         validation: None,
         metadata: None,
@@ -405,6 +493,7 @@ async fn test_into_contents_implementations() {
         invocations: None,
         config: None,
         chains: None,
+        retries: None,
     };
     let _ = metrics_resp.into_contents();
 }
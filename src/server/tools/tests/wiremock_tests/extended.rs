@@ -40,7 +40,9 @@ async fn test_decision_topsis_and_perspectives() {
     let server = create_mocked_server(&mock_server).await;
 
     let topsis_req = DecisionRequest {
+        language: None,
         decision_type: Some("topsis".to_string()),
+        normalization: None,
         question: Some("Which option using TOPSIS?".to_string()),
         options: Some(vec!["A".to_string(), "B".to_string()]),
         topic: None,
@@ -80,7 +82,9 @@ async fn test_decision_topsis_and_perspectives() {
         .await;
 
     let perspectives_req = DecisionRequest {
+        language: None,
         decision_type: Some("perspectives".to_string()),
+        normalization: None,
         question: None,
         options: None,
         topic: Some("Project stakeholder analysis".to_string()),
@@ -94,7 +98,9 @@ async fn test_decision_topsis_and_perspectives() {
 
     // Test unknown decision type
     let unknown_req = DecisionRequest {
+        language: None,
         decision_type: Some("unknown_type".to_string()),
+        normalization: None,
         question: Some("Question".to_string()),
         options: None,
         topic: None,
@@ -126,6 +132,7 @@ async fn test_graph_score_operation() {
     let server = create_mocked_server(&mock_server).await;
 
     let score_req = GraphRequest {
+        language: None,
         operation: "score".to_string(),
         session_id: "s1".to_string(),
         content: Some("Evaluate this node".to_string()),
@@ -134,7 +141,9 @@ async fn test_graph_score_operation() {
         node_ids: None,
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
     let resp = server.reasoning_graph(Parameters(score_req)).await;
     assert_eq!(resp.session_id, "s1");
@@ -160,6 +169,7 @@ async fn test_graph_aggregate_operation() {
     let server = create_mocked_server(&mock_server).await;
 
     let aggregate_req = GraphRequest {
+        language: None,
         operation: "aggregate".to_string(),
         session_id: "s1".to_string(),
         content: Some("Aggregate these insights".to_string()),
@@ -168,7 +178,9 @@ async fn test_graph_aggregate_operation() {
         node_ids: Some(vec!["n1".to_string(), "n2".to_string()]),
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
     let resp = server.reasoning_graph(Parameters(aggregate_req)).await;
     assert_eq!(resp.session_id, "s1");
@@ -193,6 +205,7 @@ async fn test_graph_refine_operation() {
     let server = create_mocked_server(&mock_server).await;
 
     let refine_req = GraphRequest {
+        language: None,
         operation: "refine".to_string(),
         session_id: "s1".to_string(),
         content: Some("Refine this node".to_string()),
@@ -201,7 +214,9 @@ async fn test_graph_refine_operation() {
         node_ids: None,
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
     let resp = server.reasoning_graph(Parameters(refine_req)).await;
     assert_eq!(resp.session_id, "s1");
@@ -229,6 +244,7 @@ async fn test_graph_prune_operation() {
     let server = create_mocked_server(&mock_server).await;
 
     let prune_req = GraphRequest {
+        language: None,
         operation: "prune".to_string(),
         session_id: "s1".to_string(),
         content: Some("Prune low value nodes".to_string()),
@@ -237,7 +253,9 @@ async fn test_graph_prune_operation() {
         node_ids: None,
         k: None,
         threshold: Some(0.5),
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
     let resp = server.reasoning_graph(Parameters(prune_req)).await;
     assert_eq!(resp.session_id, "s1");
@@ -266,6 +284,7 @@ async fn test_graph_finalize_operation() {
     let server = create_mocked_server(&mock_server).await;
 
     let finalize_req = GraphRequest {
+        language: None,
         operation: "finalize".to_string(),
         session_id: "s1".to_string(),
         content: Some("Generate final conclusions".to_string()),
@@ -274,7 +293,9 @@ async fn test_graph_finalize_operation() {
         node_ids: None,
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: Some(vec!["n1".to_string(), "n2".to_string()]),
+        max_nodes: None,
     };
     let resp = server.reasoning_graph(Parameters(finalize_req)).await;
     assert_eq!(resp.session_id, "s1");
@@ -304,6 +325,7 @@ async fn test_graph_state_operation() {
     let server = create_mocked_server(&mock_server).await;
 
     let state_req = GraphRequest {
+        language: None,
         operation: "state".to_string(),
         session_id: "s1".to_string(),
         content: None,
@@ -312,7 +334,9 @@ async fn test_graph_state_operation() {
         node_ids: None,
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
     let resp = server.reasoning_graph(Parameters(state_req)).await;
     assert_eq!(resp.session_id, "s1");
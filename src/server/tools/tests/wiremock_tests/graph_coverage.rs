@@ -43,7 +43,7 @@ fn graph_generate_json() -> String {
 }
 
 /// `graph.generate` mock whose second child reports a score of 1.5 — outside
-/// the [0, 1] range the prompt specifies. Parses fine; the value is invalid.
+/// the [0, 1] range the prompt specifies. `parse_probability` rejects it.
 fn graph_generate_out_of_range_score_json() -> String {
     serde_json::json!({
         "parent_id": "root-1",
@@ -201,6 +201,7 @@ async fn test_graph_generate_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = GraphRequest {
+        language: None,
         operation: "generate".to_string(),
         session_id: "s-graph-gen".to_string(),
         content: Some("Explore approaches to reducing system latency".to_string()),
@@ -209,7 +210,9 @@ async fn test_graph_generate_success_path() {
         node_ids: None,
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
 
     let resp = server.reasoning_graph(Parameters(req)).await;
@@ -224,7 +227,7 @@ async fn test_graph_generate_success_path() {
 }
 
 #[tokio::test]
-async fn test_graph_generate_flags_out_of_range_score() {
+async fn test_graph_generate_rejects_out_of_range_score() {
     let mock_server = MockServer::start().await;
 
     Mock::given(method("POST"))
@@ -238,6 +241,7 @@ async fn test_graph_generate_flags_out_of_range_score() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = GraphRequest {
+        language: None,
         operation: "generate".to_string(),
         session_id: "s-graph-gen-bad".to_string(),
         content: Some("Explore approaches".to_string()),
@@ -246,17 +250,23 @@ async fn test_graph_generate_flags_out_of_range_score() {
         node_ids: None,
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
 
     let resp = server.reasoning_graph(Parameters(req)).await;
-    // The out-of-range score is surfaced through the handler's validation.
-    let validation = resp.validation.expect("generate should attach validation");
-    assert!(!validation.consistent);
-    assert!(validation
-        .warnings
-        .iter()
-        .any(|w| w.contains("outside the [0, 1]")));
+    // Out-of-range scores are rejected at parse by the shared
+    // `parse_probability`, so the call fails before any validation runs.
+    assert!(resp.validation.is_none());
+    assert!(resp.nodes.is_none());
+    let insight = resp
+        .aggregated_insight
+        .expect("parse rejection should surface through aggregated_insight");
+    assert!(
+        insight.contains("must be between 0.0 and 1.0"),
+        "insight: {insight}"
+    );
 }
 
 #[tokio::test]
@@ -273,6 +283,7 @@ async fn test_graph_score_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = GraphRequest {
+        language: None,
         operation: "score".to_string(),
         session_id: "s-graph-score".to_string(),
         content: Some("Consider the iterative approach first".to_string()),
@@ -281,7 +292,9 @@ async fn test_graph_score_success_path() {
         node_ids: None,
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
 
     let resp = server.reasoning_graph(Parameters(req)).await;
@@ -303,6 +316,7 @@ async fn test_graph_aggregate_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = GraphRequest {
+        language: None,
         operation: "aggregate".to_string(),
         session_id: "s-graph-agg".to_string(),
         content: Some("c1: iterative approach\nc2: supporting evidence".to_string()),
@@ -311,7 +325,9 @@ async fn test_graph_aggregate_success_path() {
         node_ids: Some(vec!["c1".to_string(), "c2".to_string()]),
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
 
     let resp = server.reasoning_graph(Parameters(req)).await;
@@ -336,6 +352,7 @@ async fn test_graph_refine_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = GraphRequest {
+        language: None,
         operation: "refine".to_string(),
         session_id: "s-graph-refine".to_string(),
         content: Some("Consider the iterative approach first".to_string()),
@@ -344,7 +361,9 @@ async fn test_graph_refine_success_path() {
         node_ids: None,
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
 
     let resp = server.reasoning_graph(Parameters(req)).await;
@@ -366,6 +385,7 @@ async fn test_graph_prune_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = GraphRequest {
+        language: None,
         operation: "prune".to_string(),
         session_id: "s-graph-prune".to_string(),
         content: Some("Graph with multiple nodes to evaluate for pruning".to_string()),
@@ -374,7 +394,9 @@ async fn test_graph_prune_success_path() {
         node_ids: None,
         k: None,
         threshold: Some(0.75),
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
 
     let resp = server.reasoning_graph(Parameters(req)).await;
@@ -397,6 +419,7 @@ async fn test_graph_finalize_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = GraphRequest {
+        language: None,
         operation: "finalize".to_string(),
         session_id: "s-graph-finalize".to_string(),
         content: Some("Full graph with all explored nodes and edges".to_string()),
@@ -405,7 +428,9 @@ async fn test_graph_finalize_success_path() {
         node_ids: None,
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
 
     let resp = server.reasoning_graph(Parameters(req)).await;
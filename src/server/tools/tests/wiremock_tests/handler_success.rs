@@ -46,7 +46,9 @@ async fn test_decision_weighted_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = DecisionRequest {
+        language: None,
         decision_type: Some("weighted".to_string()),
+        normalization: None,
         question: Some("Which option?".to_string()),
         options: Some(vec!["Option A".to_string(), "Option B".to_string()]),
         topic: None,
@@ -101,7 +103,9 @@ async fn test_decision_pairwise_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = DecisionRequest {
+        language: None,
         decision_type: Some("pairwise".to_string()),
+        normalization: None,
         question: Some("Which is better?".to_string()),
         options: Some(vec!["A".to_string(), "B".to_string()]),
         topic: None,
@@ -159,7 +163,9 @@ async fn test_decision_topsis_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = DecisionRequest {
+        language: None,
         decision_type: Some("topsis".to_string()),
+        normalization: None,
         question: Some("Rank by TOPSIS".to_string()),
         options: Some(vec!["Option A".to_string(), "Option B".to_string()]),
         topic: None,
@@ -240,7 +246,9 @@ async fn test_decision_perspectives_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = DecisionRequest {
+        language: None,
         decision_type: Some("perspectives".to_string()),
+        normalization: None,
         question: None,
         options: Some(vec!["Option A".to_string(), "Option B".to_string()]),
         topic: Some("Product launch strategy".to_string()),
@@ -288,6 +296,7 @@ async fn test_graph_init_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = GraphRequest {
+        language: None,
         operation: "init".to_string(),
         session_id: "s1".to_string(),
         content: Some("Analyze this problem".to_string()),
@@ -296,7 +305,9 @@ async fn test_graph_init_success_path() {
         node_ids: None,
         k: Some(3),
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
 
     let resp = server.reasoning_graph(Parameters(req)).await;
@@ -341,6 +352,7 @@ async fn test_graph_generate_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = GraphRequest {
+        language: None,
         operation: "generate".to_string(),
         session_id: "s1".to_string(),
         content: None,
@@ -349,7 +361,9 @@ async fn test_graph_generate_success_path() {
         node_ids: None,
         k: Some(2),
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
 
     let resp = server.reasoning_graph(Parameters(req)).await;
@@ -386,6 +400,7 @@ async fn test_graph_score_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = GraphRequest {
+        language: None,
         operation: "score".to_string(),
         session_id: "s1".to_string(),
         content: None,
@@ -394,7 +409,9 @@ async fn test_graph_score_success_path() {
         node_ids: None,
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
 
     let resp = server.reasoning_graph(Parameters(req)).await;
@@ -430,6 +447,7 @@ async fn test_graph_aggregate_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = GraphRequest {
+        language: None,
         operation: "aggregate".to_string(),
         session_id: "s1".to_string(),
         content: None,
@@ -438,7 +456,9 @@ async fn test_graph_aggregate_success_path() {
         node_ids: Some(vec!["n1".to_string(), "n2".to_string()]),
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
 
     let resp = server.reasoning_graph(Parameters(req)).await;
@@ -483,6 +503,7 @@ async fn test_graph_finalize_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = GraphRequest {
+        language: None,
         operation: "finalize".to_string(),
         session_id: "s1".to_string(),
         content: None,
@@ -491,7 +512,9 @@ async fn test_graph_finalize_success_path() {
         node_ids: None,
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: Some(vec!["n3".to_string()]),
+        max_nodes: None,
     };
 
     let resp = server.reasoning_graph(Parameters(req)).await;
@@ -534,6 +557,7 @@ async fn test_graph_state_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = GraphRequest {
+        language: None,
         operation: "state".to_string(),
         session_id: "s1".to_string(),
         content: None,
@@ -542,7 +566,9 @@ async fn test_graph_state_success_path() {
         node_ids: None,
         k: None,
         threshold: None,
+        force: None,
         terminal_node_ids: None,
+        max_nodes: None,
     };
 
     let resp = server.reasoning_graph(Parameters(req)).await;
@@ -685,6 +711,8 @@ async fn test_detect_biases_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = DetectRequest {
+        chunk: None,
+        language: None,
         detect_type: "biases".to_string(),
         content: Some(
             "The evidence clearly shows our product is superior because our customers say so"
@@ -695,6 +723,7 @@ async fn test_detect_biases_success_path() {
         check_types: None,
         check_formal: Some(true),
         check_informal: Some(true),
+        verify_premises: None,
     };
 
     let resp = server.reasoning_detect(Parameters(req)).await;
@@ -732,6 +761,8 @@ async fn test_detect_fallacies_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = DetectRequest {
+        chunk: None,
+        language: None,
         detect_type: "fallacies".to_string(),
         content: Some("You can't trust his argument because he's not an expert".to_string()),
         thought_id: None,
@@ -739,6 +770,7 @@ async fn test_detect_fallacies_success_path() {
         check_types: Some(vec!["formal".to_string()]),
         check_formal: Some(true),
         check_informal: Some(true),
+        verify_premises: None,
     };
 
     let resp = server.reasoning_detect(Parameters(req)).await;
@@ -784,6 +816,8 @@ async fn test_detect_biases_surfaces_debiased_and_grounding() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = DetectRequest {
+        chunk: None,
+        language: None,
         detect_type: "biases".to_string(),
         // Evidence "customers say so" appears verbatim → grounded.
         content: Some("Our product is superior because customers say so".to_string()),
@@ -792,6 +826,7 @@ async fn test_detect_biases_surfaces_debiased_and_grounding() {
         check_types: None,
         check_formal: Some(true),
         check_informal: Some(true),
+        verify_premises: None,
     };
 
     let resp = server.reasoning_detect(Parameters(req)).await;
@@ -845,6 +880,8 @@ async fn test_detect_biases_flags_ungrounded_quote() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = DetectRequest {
+        chunk: None,
+        language: None,
         detect_type: "biases".to_string(),
         content: Some("Entirely unrelated text about pricing".to_string()),
         thought_id: None,
@@ -852,6 +889,7 @@ async fn test_detect_biases_flags_ungrounded_quote() {
         check_types: None,
         check_formal: None,
         check_informal: None,
+        verify_premises: None,
     };
 
     let resp = server.reasoning_detect(Parameters(req)).await;
@@ -902,6 +940,8 @@ async fn test_detect_fallacies_surfaces_argument_structure() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = DetectRequest {
+        chunk: None,
+        language: None,
         detect_type: "fallacies".to_string(),
         content: Some("You can't trust his claim because you're not an expert".to_string()),
         thought_id: None,
@@ -909,6 +949,7 @@ async fn test_detect_fallacies_surfaces_argument_structure() {
         check_types: None,
         check_formal: Some(true),
         check_informal: Some(true),
+        verify_premises: None,
     };
 
     let resp = server.reasoning_detect(Parameters(req)).await;
@@ -957,6 +998,8 @@ async fn test_detect_knowledge_gaps_surfaces_assumptions() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = DetectRequest {
+        chunk: None,
+        language: None,
         detect_type: "knowledge_gaps".to_string(),
         content: Some("Our market opportunity is huge and growth is guaranteed".to_string()),
         thought_id: None,
@@ -964,6 +1007,7 @@ async fn test_detect_knowledge_gaps_surfaces_assumptions() {
         check_types: None,
         check_formal: None,
         check_informal: None,
+        verify_premises: None,
     };
 
     let resp = server.reasoning_detect(Parameters(req)).await;
@@ -1022,6 +1066,8 @@ async fn test_evidence_probabilistic_consistent_math() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = EvidenceRequest {
+        chunk: None,
+        language: None,
         evidence_type: Some("probabilistic".to_string()),
         claim: None,
         hypothesis: Some("The treatment is effective".to_string()),
@@ -1058,6 +1104,8 @@ async fn test_evidence_probabilistic_flags_inconsistent_posterior() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = EvidenceRequest {
+        chunk: None,
+        language: None,
         evidence_type: Some("probabilistic".to_string()),
         claim: None,
         hypothesis: Some("The treatment is effective".to_string()),
@@ -1113,6 +1161,8 @@ async fn test_evidence_assess_surfaces_breakdown_and_pivot() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = EvidenceRequest {
+        chunk: None,
+        language: None,
         evidence_type: Some("assess".to_string()),
         claim: Some("The treatment works".to_string()),
         hypothesis: None,
@@ -1170,7 +1220,9 @@ async fn test_decision_weighted_corrects_injected_bad_arithmetic() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = DecisionRequest {
+        language: None,
         decision_type: Some("weighted".to_string()),
+        normalization: None,
         question: Some("Which option?".to_string()),
         options: Some(vec!["Option A".to_string(), "Option B".to_string()]),
         topic: None,
@@ -6,7 +6,9 @@ use crate::server::tools::ReasoningServer;
 use crate::server::types::AppState;
 
 mod analysis;
+mod backpressure;
 mod basic_coverage;
+mod coercion;
 mod confidence;
 mod core_tools;
 mod detect_extra;
@@ -18,6 +20,7 @@ mod progress_notifications;
 mod streaming_coverage;
 mod temporal;
 mod temporal_coverage;
+mod tool_filter;
 
 // ============================================================================
 // Shared Wiremock Helpers
@@ -56,14 +59,25 @@ fn anthropic_sse_response(text: &str) -> String {
 }
 
 async fn create_mocked_server(mock_server: &MockServer) -> ReasoningServer {
+    create_mocked_server_with(mock_server, |_| {}).await
+}
+
+/// Like [`create_mocked_server`], but lets a test tweak the config (e.g. flip
+/// an opt-in flag) before the server is built.
+async fn create_mocked_server_with(
+    mock_server: &MockServer,
+    configure: impl FnOnce(&mut crate::config::Config),
+) -> ReasoningServer {
     use crate::anthropic::{AnthropicClient, ClientConfig};
     use crate::config::{Config, SecretString};
     use crate::metrics::MetricsCollector;
     use crate::storage::SqliteStorage;
 
-    let config = Config {
+    let mut config = Config {
+        response_language: None,
         api_key: SecretString::new("test-key"),
         database_path: ":memory:".to_string(),
+        database_max_connections: 5,
         log_level: "info".to_string(),
         request_timeout_ms: 5000,
         request_timeout_deep_ms: 60000,
@@ -77,7 +91,29 @@ async fn create_mocked_server(mock_server: &MockServer) -> ReasoningServer {
         reflection_quality_threshold: 0.8,
         mcts_quality_threshold: 0.5,
         graph_prune_threshold: 0.3,
+        graph_max_nodes: 200,
+        graph_max_returned_children: None,
+        rubric_llm_weight: 0.5,
+        evidence_interval_width: 0.1,
+        sticky_session: false,
+        detect_filter_unverified: false,
+        strict_parsing: false,
+        require_session_id: false,
+        self_correct_parse: false,
+        structured_output: false,
+        confidence_floor: None,
+        prompt_caching: false,
+        enabled_tools: None,
+        disabled_tools: vec![],
+        divergent_per_perspective: false,
+        divergent_max_concurrency: 3,
+        linear_store_raw_io: false,
+        max_pending_requests: 32,
+        auto_history_bias: false,
+        retry_degenerate: false,
+        prompt_version: None,
     };
+    configure(&mut config);
 
     let storage = SqliteStorage::new_in_memory().await.unwrap();
     let metrics = Arc::new(MetricsCollector::new());
@@ -13,6 +13,8 @@ async fn test_evidence_unknown_type() {
     let server = create_mocked_server(&mock_server).await;
 
     let unknown_req = EvidenceRequest {
+        chunk: None,
+        language: None,
         evidence_type: Some("unknown_type".to_string()),
         claim: Some("Claim".to_string()),
         hypothesis: None,
@@ -163,6 +165,7 @@ async fn test_timeline_success_paths() {
 
     // Create timeline
     let create_req = TimelineRequest {
+        language: None,
         operation: "create".to_string(),
         session_id: Some("s1".to_string()),
         timeline_id: None,
@@ -194,6 +197,7 @@ async fn test_timeline_success_paths() {
         .await;
 
     let branch_req = TimelineRequest {
+        language: None,
         operation: "branch".to_string(),
         session_id: Some("s1".to_string()),
         timeline_id: Some("tl_123".to_string()),
@@ -225,6 +229,7 @@ async fn test_timeline_success_paths() {
         .await;
 
     let compare_req = TimelineRequest {
+        language: None,
         operation: "compare".to_string(),
         session_id: Some("s1".to_string()),
         timeline_id: Some("tl_123".to_string()),
@@ -255,6 +260,7 @@ async fn test_timeline_success_paths() {
         .await;
 
     let merge_req = TimelineRequest {
+        language: None,
         operation: "merge".to_string(),
         session_id: Some("s1".to_string()),
         timeline_id: Some("tl_123".to_string()),
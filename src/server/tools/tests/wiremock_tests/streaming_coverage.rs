@@ -61,6 +61,7 @@ async fn mount_sse(mock_server: &MockServer, body_text: &str) {
 
 fn mcts_req() -> MctsRequest {
     MctsRequest {
+        language: None,
         operation: Some("explore".to_string()),
         content: Some("Explore strategies".to_string()),
         session_id: Some("s-stream-mcts".to_string()),
@@ -360,6 +361,7 @@ async fn test_counterfactual_streaming_success_surfaces_ladder() {
     let server = create_mocked_server(&mock_server).await;
 
     let req = CounterfactualRequest {
+        language: None,
         scenario: "X happened alongside Z".to_string(),
         intervention: "remove X".to_string(),
         analysis_depth: None,
@@ -30,6 +30,7 @@ async fn test_timeline_all_operations() {
 
     // Test create
     let create_req = TimelineRequest {
+        language: None,
         operation: "create".to_string(),
         session_id: Some("s1".to_string()),
         timeline_id: None,
@@ -45,6 +46,7 @@ async fn test_timeline_all_operations() {
 
     // Test branch
     let branch_req = TimelineRequest {
+        language: None,
         operation: "branch".to_string(),
         session_id: Some("s1".to_string()),
         timeline_id: Some("tl1".to_string()),
@@ -60,6 +62,7 @@ async fn test_timeline_all_operations() {
 
     // Test compare
     let compare_req = TimelineRequest {
+        language: None,
         operation: "compare".to_string(),
         session_id: Some("s1".to_string()),
         timeline_id: Some("tl1".to_string()),
@@ -75,6 +78,7 @@ async fn test_timeline_all_operations() {
 
     // Test merge
     let merge_req = TimelineRequest {
+        language: None,
         operation: "merge".to_string(),
         session_id: Some("s1".to_string()),
         timeline_id: Some("tl1".to_string()),
@@ -90,6 +94,7 @@ async fn test_timeline_all_operations() {
 
     // Test unknown operation
     let unknown_req = TimelineRequest {
+        language: None,
         operation: "unknown".to_string(),
         session_id: Some("s1".to_string()),
         timeline_id: None,
@@ -132,6 +137,7 @@ async fn test_mcts_all_operations() {
 
     // Test explore
     let explore_req = MctsRequest {
+        language: None,
         operation: Some("explore".to_string()),
         content: Some("Problem to search".to_string()),
         session_id: Some("s1".to_string()),
@@ -165,6 +171,7 @@ async fn test_mcts_all_operations() {
         .await;
 
     let backtrack_req = MctsRequest {
+        language: None,
         operation: Some("auto_backtrack".to_string()),
         content: None,
         session_id: Some("s1".to_string()),
@@ -182,6 +189,7 @@ async fn test_mcts_all_operations() {
 
     // Test unknown operation (defaults to explore)
     let default_req = MctsRequest {
+        language: None,
         operation: None,
         content: Some("Content".to_string()),
         session_id: Some("s1".to_string()),
@@ -230,6 +238,7 @@ async fn test_counterfactual_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = CounterfactualRequest {
+        language: None,
         scenario: "Original scenario".to_string(),
         intervention: "What if X changed?".to_string(),
         analysis_depth: Some("counterfactual".to_string()),
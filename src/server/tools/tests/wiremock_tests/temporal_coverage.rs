@@ -159,6 +159,7 @@ async fn test_timeline_create_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = TimelineRequest {
+        language: None,
         operation: "create".to_string(),
         session_id: Some("s-tl-create".to_string()),
         timeline_id: None,
@@ -197,6 +198,7 @@ async fn test_timeline_branch_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = TimelineRequest {
+        language: None,
         operation: "branch".to_string(),
         session_id: Some("s-tl-branch".to_string()),
         timeline_id: Some("tl-2026".to_string()),
@@ -236,6 +238,7 @@ async fn test_timeline_compare_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = TimelineRequest {
+        language: None,
         operation: "compare".to_string(),
         session_id: Some("s-tl-compare".to_string()),
         timeline_id: Some("tl-2026".to_string()),
@@ -279,6 +282,7 @@ async fn test_timeline_merge_success_path() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = TimelineRequest {
+        language: None,
         operation: "merge".to_string(),
         session_id: Some("s-tl-merge".to_string()),
         timeline_id: Some("tl-2026".to_string()),
@@ -361,6 +365,7 @@ async fn test_mcts_explore_returns_valid_response() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = MctsRequest {
+        language: None,
         operation: Some("explore".to_string()),
         content: Some("Search for the optimal product strategy".to_string()),
         session_id: Some("s-mcts".to_string()),
@@ -394,6 +399,7 @@ async fn test_mcts_auto_backtrack_returns_valid_response() {
 
     let server = create_mocked_server(&mock_server).await;
     let req = MctsRequest {
+        language: None,
         operation: Some("auto_backtrack".to_string()),
         content: Some("Quality is declining in the search".to_string()),
         session_id: Some("s-mcts-bt".to_string()),
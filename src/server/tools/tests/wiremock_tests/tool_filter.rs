@@ -0,0 +1,108 @@
+//! End-to-end proof that `ENABLED_TOOLS`/`DISABLED_TOOLS` filter the tool set.
+//!
+//! A real rmcp client talks to the `ReasoningServer` over an in-process duplex
+//! transport: a filtered tool must be absent from `tools/list` and rejected by
+//! `tools/call` as unknown, while the remaining tools keep working.
+
+use rmcp::model::CallToolRequestParams;
+use rmcp::ServiceExt;
+use wiremock::MockServer;
+
+use super::create_mocked_server_with;
+use crate::server::tools::ReasoningServer;
+
+/// Serve `server` over an in-process duplex pipe and hand back a connected
+/// client plus the running server (dropping the latter closes the transport).
+async fn connect(
+    server: ReasoningServer,
+) -> (
+    rmcp::service::RunningService<rmcp::service::RoleClient, ()>,
+    rmcp::service::RunningService<rmcp::service::RoleServer, ReasoningServer>,
+) {
+    let (server_io, client_io) = tokio::io::duplex(64 * 1024);
+    let server_task = tokio::spawn(async move { server.serve(server_io).await });
+    let client = ().serve(client_io).await.expect("client init");
+    let server = server_task
+        .await
+        .expect("join server")
+        .expect("server init");
+    (client, server)
+}
+
+fn call(name: &'static str, arguments: &serde_json::Value) -> CallToolRequestParams {
+    let mut params = CallToolRequestParams::new(name);
+    params.arguments = arguments.as_object().cloned();
+    params
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn disabled_tool_is_hidden_and_rejected() {
+    let mock = MockServer::start().await;
+    let server = create_mocked_server_with(&mock, |c| {
+        c.disabled_tools = vec!["reasoning_mcts".to_string()];
+    })
+    .await;
+    let (client, _server) = connect(server).await;
+
+    // Hidden from tools/list; the rest of the tool set is untouched.
+    let tools = client.list_all_tools().await.expect("list tools");
+    assert!(!tools.iter().any(|t| t.name == "reasoning_mcts"));
+    assert!(tools.iter().any(|t| t.name == "reasoning_linear"));
+
+    // Rejected on call, indistinguishable from a tool that never existed.
+    let err = client
+        .call_tool(call(
+            "reasoning_mcts",
+            &serde_json::json!({"operation": "explore", "content": "x"}),
+        ))
+        .await
+        .expect_err("disabled tool must be rejected");
+    assert!(err.to_string().contains("Unknown tool: reasoning_mcts"));
+
+    // An enabled tool still works (help is read-only, no API call).
+    let result = client
+        .call_tool(call("reasoning_help", &serde_json::json!({})))
+        .await
+        .expect("help call");
+    assert!(!result.is_error.unwrap_or(false));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn allowlist_exposes_only_listed_tools() {
+    let mock = MockServer::start().await;
+    let server = create_mocked_server_with(&mock, |c| {
+        c.enabled_tools = Some(vec![
+            "reasoning_help".to_string(),
+            "reasoning_metrics".to_string(),
+        ]);
+    })
+    .await;
+    let (client, _server) = connect(server).await;
+
+    let mut names: Vec<String> = client
+        .list_all_tools()
+        .await
+        .expect("list tools")
+        .into_iter()
+        .map(|t| t.name.into_owned())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["reasoning_help", "reasoning_metrics"]);
+
+    // Everything off the allowlist is rejected as unknown.
+    let err = client
+        .call_tool(call(
+            "reasoning_linear",
+            &serde_json::json!({"content": "x"}),
+        ))
+        .await
+        .expect_err("tool off the allowlist must be rejected");
+    assert!(err.to_string().contains("Unknown tool: reasoning_linear"));
+
+    // The allowlisted tools work.
+    let result = client
+        .call_tool(call("reasoning_help", &serde_json::json!({})))
+        .await
+        .expect("help call");
+    assert!(!result.is_error.unwrap_or(false));
+}
@@ -72,6 +72,19 @@ pub struct AppState {
     /// enabled. The same bus is injected into `metrics` so every tool call emits
     /// a completion activity. See [`crate::dashboard`].
     pub activity: crate::dashboard::ActivityBus,
+    /// Optional tool-call audit log (compliance). `None` (the default) means no
+    /// auditing; set via [`AppState::with_audit`] when `AUDIT_LOG_PATH` or an
+    /// embedded sink is configured. See [`crate::server::AuditLog`].
+    pub audit: Option<Arc<super::audit::AuditLog>>,
+    /// Sticky-session tracker: the last session a reasoning tool ran in.
+    /// Always present (cheap when idle); only consulted when
+    /// [`crate::config::Config::sticky_session`] is enabled.
+    pub sticky: Arc<super::sticky::StickySession>,
+    /// Bounded admission gate sized by
+    /// [`crate::config::Config::max_pending_requests`]: each tool call holds a
+    /// permit for its lifetime, and a call that cannot get one is rejected
+    /// with a busy error instead of being accepted as unbounded work.
+    pub request_gate: Arc<tokio::sync::Semaphore>,
 }
 
 impl AppState {
@@ -128,6 +141,8 @@ impl AppState {
         // threading the bus through their constructors.
         crate::dashboard::set_global(activity.clone());
 
+        let request_gate = Arc::new(tokio::sync::Semaphore::new(config.max_pending_requests));
+
         Self {
             storage: Arc::new(storage),
             client: Arc::new(client),
@@ -146,9 +161,45 @@ impl AppState {
             metadata_builder: Arc::new(metadata_builder),
             progress_tx,
             activity,
+            audit: None,
+            sticky: Arc::new(super::sticky::StickySession::new()),
+            request_gate,
         }
     }
 
+    /// Attach a tool-call audit log; every dispatched tool call is then
+    /// recorded (redacted) to its sink.
+    #[must_use]
+    pub fn with_audit(mut self, audit: super::audit::AuditLog) -> Self {
+        self.audit = Some(Arc::new(audit));
+        self
+    }
+
+    /// The mode behavior flags this server's configuration selects.
+    ///
+    /// Handlers inject these into every mode they construct (via
+    /// `with_flags`), replacing the process-global toggles the flags once
+    /// lived in.
+    #[must_use]
+    pub fn mode_flags(&self) -> crate::modes::ModeFlags {
+        crate::modes::ModeFlags {
+            strict_parsing: self.config.strict_parsing,
+            self_correct_parse: self.config.self_correct_parse,
+            structured_output: self.config.structured_output,
+            require_session_id: self.config.require_session_id,
+            retry_degenerate: self.config.retry_degenerate,
+        }
+    }
+
+    /// Resolve the effective output language for a request: the per-request
+    /// override when given, otherwise the configured `RESPONSE_LANGUAGE`
+    /// default. Modes receive the merged value and never consult the
+    /// configuration themselves.
+    #[must_use]
+    pub fn resolve_language(&self, override_language: Option<String>) -> Option<String> {
+        override_language.or_else(|| self.config.response_language.clone())
+    }
+
     /// Create a progress reporter for an operation.
     ///
     /// # Arguments
@@ -190,8 +241,10 @@ mod tests {
 
     fn test_config() -> Config {
         Config {
+            response_language: None,
             api_key: SecretString::new("test-key"),
             database_path: ":memory:".to_string(),
+            database_max_connections: 5,
             log_level: "info".to_string(),
             request_timeout_ms: 30000,
             request_timeout_deep_ms: 60000,
@@ -205,6 +258,27 @@ mod tests {
             reflection_quality_threshold: 0.8,
             mcts_quality_threshold: 0.5,
             graph_prune_threshold: 0.3,
+            graph_max_nodes: 200,
+            graph_max_returned_children: None,
+            rubric_llm_weight: 0.5,
+            evidence_interval_width: 0.1,
+            sticky_session: false,
+            detect_filter_unverified: false,
+            strict_parsing: false,
+            require_session_id: false,
+            self_correct_parse: false,
+            structured_output: false,
+            confidence_floor: None,
+            prompt_caching: false,
+            enabled_tools: None,
+            disabled_tools: vec![],
+            divergent_per_perspective: false,
+            divergent_max_concurrency: 3,
+            linear_store_raw_io: false,
+            max_pending_requests: 32,
+            auto_history_bias: false,
+            retry_degenerate: false,
+            prompt_version: None,
         }
     }
 
@@ -271,6 +345,51 @@ mod tests {
         assert!(Arc::strong_count(&state.self_improvement) >= 1);
     }
 
+    #[tokio::test]
+    async fn test_mode_flags_and_language_mirror_config() {
+        let storage = SqliteStorage::new_in_memory().await.unwrap();
+        let client_config = ClientConfig::default();
+        let client = AnthropicClient::new("test-key", client_config).unwrap();
+        let mut config = test_config();
+        config.strict_parsing = true;
+        config.retry_degenerate = true;
+        config.response_language = Some("Spanish".to_string());
+        let metrics = Arc::new(MetricsCollector::new());
+        let si_handle = create_test_handle(metrics.clone(), &storage).await;
+        let metadata_builder = crate::metadata::MetadataBuilder::new(
+            Arc::new(crate::metadata::TimingDatabase::new(Arc::new(
+                storage.clone(),
+            ))),
+            Arc::new(crate::metadata::PresetIndex::build()),
+            30000,
+        );
+        let (progress_tx, _rx) = broadcast::channel(100);
+
+        let state = AppState::new(
+            storage,
+            client,
+            config,
+            metrics,
+            si_handle,
+            metadata_builder,
+            progress_tx,
+        );
+
+        let flags = state.mode_flags();
+        assert!(flags.strict_parsing);
+        assert!(flags.retry_degenerate);
+        assert!(!flags.self_correct_parse);
+        assert!(!flags.structured_output);
+        assert!(!flags.require_session_id);
+
+        // A per-request override wins; otherwise the configured default.
+        assert_eq!(
+            state.resolve_language(Some("Japanese".to_string())),
+            Some("Japanese".to_string())
+        );
+        assert_eq!(state.resolve_language(None), Some("Spanish".to_string()));
+    }
+
     #[tokio::test]
     async fn test_app_state_debug() {
         let storage = SqliteStorage::new_in_memory().await.unwrap();
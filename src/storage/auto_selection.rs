@@ -0,0 +1,188 @@
+//! Auto-selection history storage (the `auto_selections` table).
+//!
+//! Each `reasoning_auto` run records the mode it chose and the router's
+//! confidence; the resulting score is backfilled from the thought write path
+//! when the chosen mode later runs in the same session (see
+//! [`SqliteStorage::score_auto_selection`]). The `reasoning_auto_stats` tool
+//! reads the per-mode aggregates.
+
+#![allow(clippy::missing_errors_doc)]
+
+use sqlx::Row;
+
+use super::core::SqliteStorage;
+use super::types::{AutoSelectionStat, StoredAutoSelection};
+use crate::error::StorageError;
+
+const INSERT_AUTO_SELECTION: &str = "INSERT INTO auto_selections (id, session_id, selected_mode, confidence, score, created_at) VALUES (?, ?, ?, ?, ?, ?)";
+
+/// Backfill the newest unscored selection of a mode within a session. Only
+/// one row is touched so repeated runs of the chosen mode don't rewrite an
+/// already-settled outcome.
+const SCORE_AUTO_SELECTION: &str = "UPDATE auto_selections SET score = ? WHERE id = (
+    SELECT id FROM auto_selections
+    WHERE session_id = ? AND selected_mode = ? AND score IS NULL
+    ORDER BY created_at DESC, id DESC LIMIT 1
+)";
+
+const SELECT_AUTO_SELECTION_STATS: &str = "SELECT selected_mode, COUNT(*) AS selections, AVG(confidence) AS avg_confidence, AVG(score) AS avg_score, COUNT(score) AS scored \
+     FROM auto_selections GROUP BY selected_mode ORDER BY selections DESC, selected_mode ASC";
+
+impl SqliteStorage {
+    /// Persist one auto selection.
+    pub async fn save_auto_selection(
+        &self,
+        selection: &StoredAutoSelection,
+    ) -> Result<(), StorageError> {
+        sqlx::query(INSERT_AUTO_SELECTION)
+            .bind(&selection.id)
+            .bind(&selection.session_id)
+            .bind(&selection.selected_mode)
+            .bind(selection.confidence)
+            .bind(selection.score)
+            .bind(selection.created_at.to_rfc3339())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Self::query_error("INSERT auto_selections", format!("{e}")))?;
+        Ok(())
+    }
+
+    /// Record how a followed selection turned out: fill the newest unscored
+    /// selection of `selected_mode` in `session_id` with `score`. A no-op when
+    /// no matching unscored selection exists.
+    pub async fn score_auto_selection(
+        &self,
+        session_id: &str,
+        selected_mode: &str,
+        score: f64,
+    ) -> Result<(), StorageError> {
+        sqlx::query(SCORE_AUTO_SELECTION)
+            .bind(score)
+            .bind(session_id)
+            .bind(selected_mode)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Self::query_error("UPDATE auto_selections", format!("{e}")))?;
+        Ok(())
+    }
+
+    /// Per-mode selection frequency and averages, most-selected first.
+    pub async fn auto_selection_stats(&self) -> Result<Vec<AutoSelectionStat>, StorageError> {
+        let rows = sqlx::query(SELECT_AUTO_SELECTION_STATS)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| Self::query_error("SELECT auto_selections stats", format!("{e}")))?;
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let selections: i64 = row.get("selections");
+                let scored: i64 = row.get("scored");
+                AutoSelectionStat {
+                    selected_mode: row.get("selected_mode"),
+                    selections: u32::try_from(selections).unwrap_or(u32::MAX),
+                    avg_confidence: row.get("avg_confidence"),
+                    avg_score: row.get("avg_score"),
+                    scored: u32::try_from(scored).unwrap_or(u32::MAX),
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    fn selection(id: &str, session: &str, mode: &str, confidence: f64) -> StoredAutoSelection {
+        StoredAutoSelection::new(id, session, mode, confidence)
+    }
+
+    #[tokio::test]
+    async fn test_stats_report_frequencies_and_averages_per_mode() {
+        let storage = SqliteStorage::new_in_memory().await.expect("storage");
+        let mut linear_a = selection("a", "s1", "linear", 0.8);
+        linear_a.score = Some(0.9);
+        let mut linear_b = selection("b", "s2", "linear", 0.6);
+        linear_b.score = Some(0.7);
+        let tree = selection("c", "s3", "tree", 0.5);
+
+        for sel in [&linear_a, &linear_b, &tree] {
+            storage.save_auto_selection(sel).await.expect("save");
+        }
+
+        let stats = storage.auto_selection_stats().await.expect("stats");
+        assert_eq!(stats.len(), 2);
+        // Most-selected first.
+        assert_eq!(stats[0].selected_mode, "linear");
+        assert_eq!(stats[0].selections, 2);
+        assert!((stats[0].avg_confidence - 0.7).abs() < 1e-9);
+        assert!((stats[0].avg_score.expect("scored") - 0.8).abs() < 1e-9);
+        assert_eq!(stats[0].scored, 2);
+        assert_eq!(stats[1].selected_mode, "tree");
+        assert_eq!(stats[1].selections, 1);
+        assert!(stats[1].avg_score.is_none());
+        assert_eq!(stats[1].scored, 0);
+    }
+
+    #[tokio::test]
+    async fn test_score_backfills_newest_unscored_selection_only() {
+        let storage = SqliteStorage::new_in_memory().await.expect("storage");
+        let older = selection("old", "s1", "linear", 0.5);
+        let mut newer = selection("new", "s1", "linear", 0.6);
+        newer.created_at = older.created_at + chrono::Duration::seconds(1);
+        storage.save_auto_selection(&older).await.expect("save");
+        storage.save_auto_selection(&newer).await.expect("save");
+
+        storage
+            .score_auto_selection("s1", "linear", 0.95)
+            .await
+            .expect("score");
+
+        let stats = storage.auto_selection_stats().await.expect("stats");
+        assert_eq!(stats[0].scored, 1);
+        assert_eq!(stats[0].avg_score, Some(0.95));
+
+        // A second score lands on the remaining unscored row, not the settled one.
+        storage
+            .score_auto_selection("s1", "linear", 0.55)
+            .await
+            .expect("score");
+        let stats = storage.auto_selection_stats().await.expect("stats");
+        assert_eq!(stats[0].scored, 2);
+        assert!((stats[0].avg_score.expect("scored") - 0.75).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_score_is_scoped_to_session_and_mode() {
+        let storage = SqliteStorage::new_in_memory().await.expect("storage");
+        storage
+            .save_auto_selection(&selection("a", "s1", "linear", 0.5))
+            .await
+            .expect("save");
+
+        // Wrong session and wrong mode are both no-ops.
+        storage
+            .score_auto_selection("s2", "linear", 0.9)
+            .await
+            .expect("score");
+        storage
+            .score_auto_selection("s1", "tree", 0.9)
+            .await
+            .expect("score");
+
+        let stats = storage.auto_selection_stats().await.expect("stats");
+        assert_eq!(stats[0].scored, 0);
+        assert!(stats[0].avg_score.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stats_empty_without_selections() {
+        let storage = SqliteStorage::new_in_memory().await.expect("storage");
+        assert!(storage
+            .auto_selection_stats()
+            .await
+            .expect("stats")
+            .is_empty());
+    }
+}
@@ -6,18 +6,75 @@
 
 use crate::error::StorageError;
 use chrono::{DateTime, Utc};
-use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions};
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions, SqliteSynchronous,
+};
 use std::path::Path;
 use std::str::FromStr;
+use std::time::Duration;
 use uuid::Uuid;
 
+/// How long a connection waits on a locked database before giving up.
+///
+/// WAL mode lets reads proceed alongside a writer, but writes still serialize;
+/// under concurrent pool usage a second writer sees the lock and should wait
+/// rather than immediately fail with `SQLITE_BUSY`.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Map the config-level journal mode to its sqlx counterpart.
+const fn journal_mode(mode: super::types::JournalMode) -> SqliteJournalMode {
+    match mode {
+        super::types::JournalMode::Wal => SqliteJournalMode::Wal,
+        super::types::JournalMode::Delete => SqliteJournalMode::Delete,
+        super::types::JournalMode::Truncate => SqliteJournalMode::Truncate,
+        super::types::JournalMode::Persist => SqliteJournalMode::Persist,
+        super::types::JournalMode::Memory => SqliteJournalMode::Memory,
+    }
+}
+
+/// Map the config-level synchronous level to its sqlx counterpart.
+const fn synchronous(mode: super::types::SynchronousMode) -> SqliteSynchronous {
+    match mode {
+        super::types::SynchronousMode::Off => SqliteSynchronous::Off,
+        super::types::SynchronousMode::Normal => SqliteSynchronous::Normal,
+        super::types::SynchronousMode::Full => SqliteSynchronous::Full,
+        super::types::SynchronousMode::Extra => SqliteSynchronous::Extra,
+    }
+}
+
 /// `SQLite` storage backend.
 ///
 /// Provides persistent storage for sessions, thoughts, branches,
 /// checkpoints, graph data, metrics, and self-improvement actions.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct SqliteStorage {
     pub(crate) pool: SqlitePool,
+    /// Near-duplicate thought handling on save; `None` disables dedup.
+    pub(crate) thought_dedup: Option<super::types::ThoughtDedupConfig>,
+    /// Persistence sampling on save; `None` persists every thought.
+    pub(crate) thought_sampling: Option<super::types::ThoughtSamplingConfig>,
+    /// Automatic mode/topic tagging on save; `None` disables tagging.
+    pub(crate) auto_tag: Option<super::types::ThoughtAutoTagConfig>,
+    /// Client for LLM-derived topic tags; only used when `auto_tag` opts in.
+    pub(crate) topic_tag_client: Option<std::sync::Arc<dyn crate::traits::AnthropicClientTrait>>,
+}
+
+impl std::fmt::Debug for SqliteStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SqliteStorage")
+            .field("pool", &self.pool)
+            .field("thought_dedup", &self.thought_dedup)
+            .field("thought_sampling", &self.thought_sampling)
+            .field("auto_tag", &self.auto_tag)
+            .field(
+                "topic_tag_client",
+                &self
+                    .topic_tag_client
+                    .as_ref()
+                    .map(|_| "AnthropicClientTrait"),
+            )
+            .finish()
+    }
 }
 
 impl SqliteStorage {
@@ -29,7 +86,53 @@ impl SqliteStorage {
         self.pool.clone()
     }
 
-    /// Create a new `SQLite` storage instance.
+    /// Enable deduplication of near-identical thoughts on save.
+    ///
+    /// See [`ThoughtDedupConfig`](super::types::ThoughtDedupConfig) for the
+    /// threshold and skip-vs-link strategy.
+    #[must_use]
+    pub const fn with_thought_dedup(mut self, config: super::types::ThoughtDedupConfig) -> Self {
+        self.thought_dedup = Some(config);
+        self
+    }
+
+    /// Enable persistence sampling of thoughts on save.
+    ///
+    /// See [`ThoughtSamplingConfig`](super::types::ThoughtSamplingConfig) for
+    /// the rate and which thoughts are always kept.
+    #[must_use]
+    pub const fn with_thought_sampling(
+        mut self,
+        config: super::types::ThoughtSamplingConfig,
+    ) -> Self {
+        self.thought_sampling = Some(config);
+        self
+    }
+
+    /// Enable automatic mode/topic tagging of thoughts on save.
+    ///
+    /// See [`ThoughtAutoTagConfig`](super::types::ThoughtAutoTagConfig) for
+    /// the keyword-vs-LLM topic choice.
+    #[must_use]
+    pub const fn with_auto_tagging(mut self, config: super::types::ThoughtAutoTagConfig) -> Self {
+        self.auto_tag = Some(config);
+        self
+    }
+
+    /// Attach the client used for LLM-derived topic tags.
+    ///
+    /// Only consulted when [`with_auto_tagging`](Self::with_auto_tagging) was
+    /// called with `llm_topics` set; without a client the keyword table is used.
+    #[must_use]
+    pub fn with_topic_tag_client(
+        mut self,
+        client: std::sync::Arc<dyn crate::traits::AnthropicClientTrait>,
+    ) -> Self {
+        self.topic_tag_client = Some(client);
+        self
+    }
+
+    /// Create a new `SQLite` storage instance with the default pool size.
     ///
     /// # Arguments
     ///
@@ -39,6 +142,52 @@ impl SqliteStorage {
     ///
     /// Returns [`StorageError::ConnectionFailed`] if the connection fails.
     pub async fn new(database_path: impl AsRef<Path>) -> Result<Self, StorageError> {
+        Self::new_with_max_connections(
+            database_path,
+            crate::config::DEFAULT_DATABASE_MAX_CONNECTIONS,
+        )
+        .await
+    }
+
+    /// Create a new `SQLite` storage instance with an explicit pool size
+    /// (`DATABASE_MAX_CONNECTIONS` via [`Config`](crate::config::Config)).
+    ///
+    /// The pool runs in WAL mode with a busy timeout, so concurrent tool calls
+    /// each acquire their own connection: reads proceed in parallel and a
+    /// write that hits the `SQLite` write lock waits instead of failing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::ConnectionFailed`] if the connection fails.
+    pub async fn new_with_max_connections(
+        database_path: impl AsRef<Path>,
+        max_connections: u32,
+    ) -> Result<Self, StorageError> {
+        Self::new_with_pragmas(
+            database_path,
+            max_connections,
+            super::types::StoragePragmas::new(),
+        )
+        .await
+    }
+
+    /// Create a new `SQLite` storage instance with explicit connection pragmas
+    /// (`SQLITE_JOURNAL_MODE` etc. via
+    /// [`StoragePragmas::from_env`](super::types::StoragePragmas::from_env)).
+    ///
+    /// The default pragmas are WAL mode with NORMAL synchronous, so concurrent
+    /// tool calls each acquire their own connection: reads proceed in parallel
+    /// and a write that hits the `SQLite` write lock waits (busy timeout)
+    /// instead of failing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::ConnectionFailed`] if the connection fails.
+    pub async fn new_with_pragmas(
+        database_path: impl AsRef<Path>,
+        max_connections: u32,
+        pragmas: super::types::StoragePragmas,
+    ) -> Result<Self, StorageError> {
         let path = database_path.as_ref();
 
         // Create parent directories if they don't exist
@@ -48,23 +197,37 @@ impl SqliteStorage {
             })?;
         }
 
-        let options =
+        let mut options =
             SqliteConnectOptions::from_str(&format!("sqlite://{}?mode=rwc", path.display()))
                 .map_err(|e| StorageError::ConnectionFailed {
                     message: format!("Invalid database path: {e}"),
                 })?
-                .journal_mode(SqliteJournalMode::Wal)
+                .journal_mode(journal_mode(pragmas.journal_mode))
+                .synchronous(synchronous(pragmas.synchronous))
+                .busy_timeout(BUSY_TIMEOUT)
                 .create_if_missing(true);
+        if let Some(cache_size) = pragmas.cache_size {
+            options = options.pragma("cache_size", cache_size.to_string());
+        }
+        if let Some(mmap_size) = pragmas.mmap_size {
+            options = options.pragma("mmap_size", mmap_size.to_string());
+        }
 
         let pool = SqlitePoolOptions::new()
-            .max_connections(5)
+            .max_connections(max_connections.max(1))
             .connect_with(options)
             .await
             .map_err(|e| StorageError::ConnectionFailed {
                 message: format!("Failed to connect to database: {e}"),
             })?;
 
-        let storage = Self { pool };
+        let storage = Self {
+            pool,
+            thought_dedup: None,
+            thought_sampling: None,
+            auto_tag: None,
+            topic_tag_client: None,
+        };
         storage.run_migrations().await?;
 
         Ok(storage)
@@ -80,7 +243,8 @@ impl SqliteStorage {
             .map_err(|e| StorageError::ConnectionFailed {
                 message: format!("Invalid memory database options: {e}"),
             })?
-            .journal_mode(SqliteJournalMode::Wal);
+            .journal_mode(SqliteJournalMode::Wal)
+            .busy_timeout(BUSY_TIMEOUT);
 
         let pool = SqlitePoolOptions::new()
             .max_connections(1)
@@ -90,7 +254,13 @@ impl SqliteStorage {
                 message: format!("Failed to create in-memory database: {e}"),
             })?;
 
-        let storage = Self { pool };
+        let storage = Self {
+            pool,
+            thought_dedup: None,
+            thought_sampling: None,
+            auto_tag: None,
+            topic_tag_client: None,
+        };
         storage.run_migrations().await?;
 
         Ok(storage)
@@ -218,6 +388,116 @@ impl SqliteStorage {
             }
         }
 
+        // Migration 012: session-scoped working memory (mirrors
+        // migrations/012_session_working_memory.sql). Same inline pattern as 011:
+        // ALTER TABLE ADD COLUMN is not idempotent, so tolerate "duplicate column
+        // name" on subsequent boots.
+        if let Err(e) = sqlx::query("ALTER TABLE sessions ADD COLUMN working_memory TEXT")
+            .execute(&self.pool)
+            .await
+        {
+            if !e.to_string().contains("duplicate column name") {
+                return Err(StorageError::MigrationFailed {
+                    version: "012".to_string(),
+                    message: format!("Failed to run migration 012: {e}"),
+                });
+            }
+        }
+
+        // Migration 013: session-level aggregate quality (mirrors
+        // migrations/013_session_quality.sql). Same inline pattern as 011/012.
+        if let Err(e) = sqlx::query("ALTER TABLE sessions ADD COLUMN quality REAL")
+            .execute(&self.pool)
+            .await
+        {
+            if !e.to_string().contains("duplicate column name") {
+                return Err(StorageError::MigrationFailed {
+                    version: "013".to_string(),
+                    message: format!("Failed to run migration 013: {e}"),
+                });
+            }
+        }
+
+        // Migration 014: persisted preset run history
+        let schema_014 = include_str!("../../migrations/014_preset_runs.sql");
+        sqlx::query(schema_014)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| StorageError::MigrationFailed {
+                version: "014".to_string(),
+                message: format!("Failed to run migration 014: {e}"),
+            })?;
+
+        // Migration 015: per-session thought sequence numbers (mirrors
+        // migrations/015_thought_sequence.sql). The ALTER follows the inline
+        // pattern of 011-013; the backfill and index are idempotent on their own.
+        if let Err(e) = sqlx::query("ALTER TABLE thoughts ADD COLUMN sequence INTEGER")
+            .execute(&self.pool)
+            .await
+        {
+            if !e.to_string().contains("duplicate column name") {
+                return Err(StorageError::MigrationFailed {
+                    version: "015".to_string(),
+                    message: format!("Failed to run migration 015: {e}"),
+                });
+            }
+        }
+        for stmt in [
+            "UPDATE thoughts SET sequence = (
+                SELECT COUNT(*) FROM thoughts t2
+                WHERE t2.session_id = thoughts.session_id
+                  AND (t2.created_at < thoughts.created_at
+                       OR (t2.created_at = thoughts.created_at AND t2.id <= thoughts.id))
+            ) WHERE sequence IS NULL",
+            "CREATE INDEX IF NOT EXISTS idx_thoughts_session_sequence \
+             ON thoughts (session_id, sequence)",
+        ] {
+            sqlx::query(stmt).execute(&self.pool).await.map_err(|e| {
+                StorageError::MigrationFailed {
+                    version: "015".to_string(),
+                    message: format!("Failed to run migration 015: {e}"),
+                }
+            })?;
+        }
+
+        // Migration 016: opt-in raw request/response capture per thought
+        let schema_016 = include_str!("../../migrations/016_thought_raw_io.sql");
+        sqlx::query(schema_016)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| StorageError::MigrationFailed {
+                version: "016".to_string(),
+                message: format!("Failed to run migration 016: {e}"),
+            })?;
+
+        // Migration 017: structured auto-selection history
+        let schema_017 = include_str!("../../migrations/017_auto_selections.sql");
+        sqlx::query(schema_017)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| StorageError::MigrationFailed {
+                version: "017".to_string(),
+                message: format!("Failed to run migration 017: {e}"),
+            })?;
+
+        // Migration 018: graph node provenance (mirrors
+        // migrations/018_graph_node_provenance.sql). Same inline pattern as 011:
+        // ALTER TABLE ADD COLUMN is not idempotent, so tolerate "duplicate column
+        // name" on subsequent boots.
+        for stmt in [
+            "ALTER TABLE graph_nodes ADD COLUMN origin TEXT",
+            "ALTER TABLE graph_nodes ADD COLUMN parent_ids TEXT",
+        ] {
+            if let Err(e) = sqlx::query(stmt).execute(&self.pool).await {
+                if !e.to_string().contains("duplicate column name") {
+                    return Err(StorageError::MigrationFailed {
+                        version: "018".to_string(),
+                        message: format!("Failed to run migration 018: {e}"),
+                    });
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -405,5 +685,131 @@ pub mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_new_with_max_connections() {
+        let temp_dir = std::env::temp_dir();
+        let db_path = temp_dir.join("test_mcp_reasoning_pool.db");
+        let _ = std::fs::remove_file(&db_path);
+
+        let storage = SqliteStorage::new_with_max_connections(&db_path, 8).await;
+        assert!(storage.is_ok());
+
+        // A zero pool size is clamped to one connection rather than failing.
+        let _ = std::fs::remove_file(&db_path);
+        let storage = SqliteStorage::new_with_max_connections(&db_path, 0).await;
+        assert!(storage.is_ok());
+
+        let _ = std::fs::remove_file(&db_path);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_default_pragmas_enable_wal() {
+        let temp_dir = std::env::temp_dir();
+        let db_path = temp_dir.join("test_mcp_reasoning_pragmas_default.db");
+        let _ = std::fs::remove_file(&db_path);
+
+        let storage = SqliteStorage::new(&db_path).await.expect("create storage");
+        let row: (String,) = sqlx::query_as("PRAGMA journal_mode")
+            .fetch_one(&storage.get_pool())
+            .await
+            .expect("query journal_mode");
+        assert_eq!(row.0, "wal");
+
+        let _ = std::fs::remove_file(&db_path);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_custom_pragmas_are_applied() {
+        use crate::storage::types::{JournalMode, StoragePragmas, SynchronousMode};
+
+        let temp_dir = std::env::temp_dir();
+        let db_path = temp_dir.join("test_mcp_reasoning_pragmas_custom.db");
+        let _ = std::fs::remove_file(&db_path);
+
+        let pragmas = StoragePragmas {
+            journal_mode: JournalMode::Delete,
+            synchronous: SynchronousMode::Full,
+            cache_size: Some(-2000),
+            mmap_size: None,
+        };
+        let storage = SqliteStorage::new_with_pragmas(&db_path, 4, pragmas)
+            .await
+            .expect("create storage");
+
+        let journal: (String,) = sqlx::query_as("PRAGMA journal_mode")
+            .fetch_one(&storage.get_pool())
+            .await
+            .expect("query journal_mode");
+        assert_eq!(journal.0, "delete");
+
+        // FULL synchronous reports as 2.
+        let sync: (i64,) = sqlx::query_as("PRAGMA synchronous")
+            .fetch_one(&storage.get_pool())
+            .await
+            .expect("query synchronous");
+        assert_eq!(sync.0, 2);
+
+        let cache: (i64,) = sqlx::query_as("PRAGMA cache_size")
+            .fetch_one(&storage.get_pool())
+            .await
+            .expect("query cache_size");
+        assert_eq!(cache.0, -2000);
+
+        let _ = std::fs::remove_file(&db_path);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_concurrent_thought_writes_and_reads() {
+        use crate::storage::types::StoredThought;
+
+        let temp_dir = std::env::temp_dir();
+        let db_path = temp_dir.join("test_mcp_reasoning_concurrent.db");
+        let _ = std::fs::remove_file(&db_path);
+
+        let storage = SqliteStorage::new_with_max_connections(&db_path, 8)
+            .await
+            .expect("create storage");
+        storage
+            .create_session_with_id("sess-concurrent")
+            .await
+            .expect("create session");
+
+        // Many tasks write and read through the shared pool at once; WAL mode
+        // plus the busy timeout means none of them should see SQLITE_BUSY.
+        let mut handles = Vec::new();
+        for task in 0..16 {
+            let storage = storage.clone();
+            handles.push(tokio::spawn(async move {
+                for i in 0..4 {
+                    let thought = StoredThought::new(
+                        format!("t-{task}-{i}"),
+                        "sess-concurrent",
+                        "linear",
+                        format!("concurrent thought {task}-{i}"),
+                        0.5,
+                    );
+                    storage.save_stored_thought(&thought).await?;
+                    storage.get_stored_thoughts("sess-concurrent").await?;
+                }
+                Ok::<(), StorageError>(())
+            }));
+        }
+        for handle in handles {
+            handle.await.expect("task panicked").expect("storage error");
+        }
+
+        let thoughts = storage
+            .get_stored_thoughts("sess-concurrent")
+            .await
+            .expect("get thoughts");
+        assert_eq!(thoughts.len(), 16 * 4);
+
+        let _ = std::fs::remove_file(&db_path);
+    }
+
     use chrono::Datelike;
 }
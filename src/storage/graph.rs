@@ -6,12 +6,15 @@ use crate::error::StorageError;
 use sqlx::Row;
 
 use super::core::SqliteStorage;
-use super::types::{GraphEdgeType, GraphNodeType, StoredGraphEdge, StoredGraphNode};
+use super::thought::INSERT_THOUGHT;
+use super::types::{
+    GraphEdgeType, GraphNodeOrigin, GraphNodeType, StoredGraphEdge, StoredGraphNode, StoredThought,
+};
 
 // SQL query constants for graph nodes
-const INSERT_GRAPH_NODE: &str = "INSERT OR REPLACE INTO graph_nodes (id, session_id, content, node_type, score, is_terminal, metadata, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)";
-const SELECT_GRAPH_NODE: &str = "SELECT id, session_id, content, node_type, score, is_terminal, metadata, created_at FROM graph_nodes WHERE id = ?";
-const SELECT_GRAPH_NODES_BY_SESSION: &str = "SELECT id, session_id, content, node_type, score, is_terminal, metadata, created_at FROM graph_nodes WHERE session_id = ? ORDER BY created_at ASC";
+const INSERT_GRAPH_NODE: &str = "INSERT OR REPLACE INTO graph_nodes (id, session_id, content, node_type, score, is_terminal, metadata, origin, parent_ids, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)";
+const SELECT_GRAPH_NODE: &str = "SELECT id, session_id, content, node_type, score, is_terminal, metadata, origin, parent_ids, created_at FROM graph_nodes WHERE id = ?";
+const SELECT_GRAPH_NODES_BY_SESSION: &str = "SELECT id, session_id, content, node_type, score, is_terminal, metadata, origin, parent_ids, created_at FROM graph_nodes WHERE session_id = ? ORDER BY created_at ASC";
 const UPDATE_GRAPH_NODE_SCORE: &str = "UPDATE graph_nodes SET score = ? WHERE id = ?";
 const UPDATE_GRAPH_NODE_TERMINAL: &str = "UPDATE graph_nodes SET is_terminal = 1 WHERE id = ?";
 const DELETE_GRAPH_NODE: &str = "DELETE FROM graph_nodes WHERE id = ?";
@@ -41,6 +44,8 @@ impl SqliteStorage {
             .bind(node.score)
             .bind(is_terminal_i32)
             .bind(&node.metadata)
+            .bind(node.origin.map(|o| o.as_str()))
+            .bind(Self::parent_ids_to_json(&node.parent_ids))
             .bind(&created_at_str)
             .execute(&self.pool)
             .await
@@ -49,6 +54,94 @@ impl SqliteStorage {
         Ok(())
     }
 
+    /// Save a thought plus a batch of graph nodes and edges in one transaction.
+    ///
+    /// Either every row persists or none do: a failure on any insert rolls the
+    /// whole batch back, so a mid-batch error can't leave a node without its
+    /// edges, or a thought recording a graph step that was never stored.
+    pub async fn save_graph_batch(
+        &self,
+        thought: &StoredThought,
+        nodes: &[StoredGraphNode],
+        edges: &[StoredGraphEdge],
+    ) -> Result<(), StorageError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| Self::query_error("BEGIN save_graph_batch", format!("{e}")))?;
+
+        sqlx::query(INSERT_THOUGHT)
+            .bind(&thought.id)
+            .bind(&thought.session_id)
+            .bind(&thought.parent_id)
+            .bind(&thought.mode)
+            .bind(&thought.content)
+            .bind(thought.confidence)
+            .bind(&thought.metadata)
+            .bind(thought.created_at.to_rfc3339())
+            .bind(&thought.session_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Self::query_error("INSERT thoughts", format!("{e}")))?;
+
+        // Fold the thought's confidence into the session's quality aggregate
+        // inside the same transaction, as save_stored_thought does outside one.
+        sqlx::query(super::session::UPDATE_SESSION_QUALITY)
+            .bind(thought.confidence)
+            .bind(super::session::SESSION_QUALITY_RECENCY_WEIGHT)
+            .bind(&thought.session_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Self::query_error("UPDATE sessions.quality", format!("{e}")))?;
+
+        for node in nodes {
+            sqlx::query(INSERT_GRAPH_NODE)
+                .bind(&node.id)
+                .bind(&node.session_id)
+                .bind(&node.content)
+                .bind(node.node_type.as_str())
+                .bind(node.score)
+                .bind(i32::from(node.is_terminal))
+                .bind(&node.metadata)
+                .bind(node.origin.map(|o| o.as_str()))
+                .bind(Self::parent_ids_to_json(&node.parent_ids))
+                .bind(node.created_at.to_rfc3339())
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| Self::query_error("INSERT graph_nodes", format!("{e}")))?;
+        }
+
+        for edge in edges {
+            sqlx::query(INSERT_GRAPH_EDGE)
+                .bind(&edge.id)
+                .bind(&edge.session_id)
+                .bind(&edge.from_node_id)
+                .bind(&edge.to_node_id)
+                .bind(edge.edge_type.as_str())
+                .bind(edge.created_at.to_rfc3339())
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| Self::query_error("INSERT graph_edges", format!("{e}")))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| Self::query_error("COMMIT save_graph_batch", format!("{e}")))?;
+
+        // Queue the session for background (re)embedding, as a thought write
+        // does. Best-effort: the cache is derived data and self-heals.
+        if let Err(e) = self.enqueue_embedding(&thought.session_id).await {
+            tracing::warn!(
+                session_id = %thought.session_id,
+                error = %e,
+                "Failed to enqueue session for background embedding"
+            );
+        }
+
+        Ok(())
+    }
+
     /// Get a graph node by ID.
     pub async fn get_graph_node(&self, id: &str) -> Result<Option<StoredGraphNode>, StorageError> {
         let row = sqlx::query(SELECT_GRAPH_NODE)
@@ -138,6 +231,16 @@ impl SqliteStorage {
     }
 
     /// Convert a database row to a `StoredGraphNode`.
+    /// Serialize parent ids as a JSON array, `NULL` when the producing
+    /// operation had no inputs.
+    fn parent_ids_to_json(parent_ids: &[String]) -> Option<String> {
+        if parent_ids.is_empty() {
+            None
+        } else {
+            serde_json::to_string(parent_ids).ok()
+        }
+    }
+
     fn row_to_graph_node(row: &sqlx::sqlite::SqliteRow) -> Result<StoredGraphNode, StorageError> {
         let id: String = row.get("id");
         let session_id: String = row.get("session_id");
@@ -146,6 +249,8 @@ impl SqliteStorage {
         let score: Option<f64> = row.get("score");
         let is_terminal: i32 = row.get("is_terminal");
         let metadata: Option<String> = row.get("metadata");
+        let origin_str: Option<String> = row.get("origin");
+        let parent_ids_json: Option<String> = row.get("parent_ids");
         let created_at_str: String = row.get("created_at");
 
         let node_type = GraphNodeType::from_str(&node_type_str).unwrap_or_default();
@@ -153,6 +258,13 @@ impl SqliteStorage {
 
         let mut node = StoredGraphNode::new(&id, &session_id, &content).with_node_type(node_type);
         node.created_at = created_at;
+        // Provenance is tolerant on read: rows predating migration 018 (or an
+        // unrecognized origin) come back as "no provenance recorded".
+        node.origin = origin_str.as_deref().and_then(GraphNodeOrigin::from_str);
+        node.parent_ids = parent_ids_json
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default();
 
         if let Some(s) = score {
             node = node.with_score(s);
@@ -353,6 +465,41 @@ mod tests {
         assert_eq!(fetched.score, Some(0.85));
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_graph_node_provenance_roundtrip() {
+        let storage = test_storage().await;
+        storage
+            .create_session_with_id("sess-123")
+            .await
+            .expect("create session");
+
+        let node = StoredGraphNode::new("n-1", "sess-123", "Synthesis").with_provenance(
+            GraphNodeOrigin::Aggregate,
+            vec!["n-a".to_string(), "n-b".to_string()],
+        );
+        storage.save_graph_node(&node).await.expect("save");
+
+        let fetched = storage
+            .get_graph_node("n-1")
+            .await
+            .expect("fetch")
+            .expect("node exists");
+        assert_eq!(fetched.origin, Some(GraphNodeOrigin::Aggregate));
+        assert_eq!(fetched.parent_ids, vec!["n-a", "n-b"]);
+
+        // A node saved without provenance reads back as "none recorded".
+        let bare = StoredGraphNode::new("n-2", "sess-123", "Bare");
+        storage.save_graph_node(&bare).await.expect("save bare");
+        let fetched = storage
+            .get_graph_node("n-2")
+            .await
+            .expect("fetch")
+            .expect("node exists");
+        assert!(fetched.origin.is_none());
+        assert!(fetched.parent_ids.is_empty());
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_get_graph_node_not_found() {
@@ -492,6 +639,71 @@ mod tests {
         assert!(fetched.is_terminal);
     }
 
+    // ========== Graph Batch Tests ==========
+
+    #[tokio::test]
+    #[serial]
+    async fn test_save_graph_batch_persists_everything() {
+        let storage = test_storage().await;
+        storage
+            .create_session_with_id("sess-batch")
+            .await
+            .expect("create session");
+
+        let thought = StoredThought::new("t-1", "sess-batch", "graph_generate", "2 children", 0.7);
+        let nodes = vec![
+            StoredGraphNode::new("n-1", "sess-batch", "First"),
+            StoredGraphNode::new("n-2", "sess-batch", "Second"),
+        ];
+        let edges = vec![StoredGraphEdge::new("e-1", "sess-batch", "n-1", "n-2")];
+
+        storage
+            .save_graph_batch(&thought, &nodes, &edges)
+            .await
+            .expect("batch");
+
+        let nodes = storage.get_graph_nodes("sess-batch").await.expect("nodes");
+        assert_eq!(nodes.len(), 2);
+        let edges = storage.get_graph_edges("sess-batch").await.expect("edges");
+        assert_eq!(edges.len(), 1);
+        let thoughts = storage
+            .get_stored_thoughts("sess-batch")
+            .await
+            .expect("thoughts");
+        assert_eq!(thoughts.len(), 1);
+        assert_eq!(thoughts[0].id, "t-1");
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_save_graph_batch_rolls_back_on_failure() {
+        let storage = test_storage().await;
+        storage
+            .create_session_with_id("sess-batch")
+            .await
+            .expect("create session");
+
+        // The edge references a node the batch never creates, so the foreign
+        // key fails mid-transaction — nothing may survive, not even the rows
+        // inserted before the failure.
+        let thought = StoredThought::new("t-1", "sess-batch", "graph_generate", "1 child", 0.7);
+        let nodes = vec![StoredGraphNode::new("n-1", "sess-batch", "Child")];
+        let edges = vec![StoredGraphEdge::new("e-1", "sess-batch", "missing", "n-1")];
+
+        let result = storage.save_graph_batch(&thought, &nodes, &edges).await;
+        assert!(result.is_err());
+
+        let nodes = storage.get_graph_nodes("sess-batch").await.expect("nodes");
+        assert!(nodes.is_empty(), "node insert must be rolled back");
+        let edges = storage.get_graph_edges("sess-batch").await.expect("edges");
+        assert!(edges.is_empty());
+        let thoughts = storage
+            .get_stored_thoughts("sess-batch")
+            .await
+            .expect("thoughts");
+        assert!(thoughts.is_empty(), "thought insert must be rolled back");
+    }
+
     // ========== Graph Edge Tests ==========
 
     #[tokio::test]
@@ -44,6 +44,7 @@
 
 mod actions;
 mod agent_metrics;
+mod auto_selection;
 mod branch;
 mod checkpoint;
 mod core;
@@ -51,16 +52,23 @@ mod embedding_queue;
 mod embeddings;
 mod graph;
 mod metrics;
+mod preset_runs;
+mod raw_io;
 mod session;
+mod tagging;
 mod thought;
 mod trait_impl;
 mod types;
 
 pub use self::core::SqliteStorage;
 pub use embeddings::content_hash;
+pub use session::SESSION_QUALITY_RECENCY_WEIGHT;
+pub(crate) use thought::content_similarity;
 pub use types::{
-    ActionStatus, BranchStatus, GraphEdgeType, GraphNodeType, StoredAgentInvocation,
-    StoredAgentMessage, StoredBranch, StoredCheckpoint, StoredDiscoveredSkill, StoredEmbedding,
-    StoredGraphEdge, StoredGraphNode, StoredMetric, StoredSelfImprovementAction, StoredSession,
-    StoredThought,
+    ActionStatus, AutoSelectionStat, BranchStatus, GraphEdgeType, GraphNodeOrigin, GraphNodeType,
+    JournalMode, StoragePragmas, StoredAgentInvocation, StoredAgentMessage, StoredAutoSelection,
+    StoredBranch, StoredCheckpoint, StoredDiscoveredSkill, StoredEmbedding, StoredGraphEdge,
+    StoredGraphNode, StoredMetric, StoredPresetRun, StoredRawIo, StoredSelfImprovementAction,
+    StoredSession, StoredThought, SynchronousMode, ThoughtAutoTagConfig, ThoughtDedupConfig,
+    ThoughtDedupStrategy, ThoughtSamplingConfig,
 };
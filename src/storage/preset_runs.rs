@@ -0,0 +1,96 @@
+//! Preset run history storage (the `preset_runs` table).
+//!
+//! Each row holds a full serialized `PresetResult` tagged by preset id and
+//! timestamp. The `reasoning_preset_history` tool reads this table to list a
+//! preset's runs over time and diff two of them.
+
+#![allow(clippy::missing_errors_doc)]
+
+use sqlx::Row;
+
+use super::core::SqliteStorage;
+use super::types::StoredPresetRun;
+use crate::error::StorageError;
+
+const INSERT_PRESET_RUN: &str = "INSERT INTO preset_runs (id, preset_id, session_id, result_json, created_at) VALUES (?, ?, ?, ?, ?)";
+const SELECT_PRESET_RUNS: &str =
+    "SELECT id, preset_id, session_id, result_json, created_at FROM preset_runs \
+     WHERE preset_id = ? ORDER BY created_at ASC, id ASC";
+
+impl SqliteStorage {
+    /// Persist one preset run.
+    pub async fn save_preset_run(&self, run: &StoredPresetRun) -> Result<(), StorageError> {
+        sqlx::query(INSERT_PRESET_RUN)
+            .bind(&run.id)
+            .bind(&run.preset_id)
+            .bind(&run.session_id)
+            .bind(&run.result_json)
+            .bind(run.created_at.to_rfc3339())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Self::query_error("INSERT preset_runs", format!("{e}")))?;
+        Ok(())
+    }
+
+    /// Get every recorded run of a preset, oldest first.
+    pub async fn get_preset_runs(
+        &self,
+        preset_id: &str,
+    ) -> Result<Vec<StoredPresetRun>, StorageError> {
+        let rows = sqlx::query(SELECT_PRESET_RUNS)
+            .bind(preset_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| Self::query_error("SELECT preset_runs", format!("{e}")))?;
+        rows.iter().map(Self::row_to_preset_run).collect()
+    }
+
+    fn row_to_preset_run(row: &sqlx::sqlite::SqliteRow) -> Result<StoredPresetRun, StorageError> {
+        let created_at_str: String = row.get("created_at");
+        Ok(StoredPresetRun {
+            id: row.get("id"),
+            preset_id: row.get("preset_id"),
+            session_id: row.get("session_id"),
+            result_json: row.get("result_json"),
+            created_at: Self::parse_datetime(&created_at_str)?,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_save_and_list_runs_in_order() {
+        let storage = SqliteStorage::new_in_memory().await.expect("storage");
+        let first = StoredPresetRun::new("run-1", "code-review", "s1", "{\"success\":true}");
+        let mut second = StoredPresetRun::new("run-2", "code-review", "s2", "{\"success\":false}");
+        second.created_at = first.created_at + chrono::Duration::seconds(1);
+
+        storage.save_preset_run(&second).await.expect("save second");
+        storage.save_preset_run(&first).await.expect("save first");
+
+        let runs = storage.get_preset_runs("code-review").await.expect("list");
+        assert_eq!(runs.len(), 2);
+        // Oldest first, regardless of insertion order.
+        assert_eq!(runs[0], first);
+        assert_eq!(runs[1], second);
+    }
+
+    #[tokio::test]
+    async fn test_runs_are_scoped_to_preset() {
+        let storage = SqliteStorage::new_in_memory().await.expect("storage");
+        storage
+            .save_preset_run(&StoredPresetRun::new("run-1", "code-review", "s1", "{}"))
+            .await
+            .expect("save");
+
+        assert!(storage
+            .get_preset_runs("debug-analysis")
+            .await
+            .expect("list")
+            .is_empty());
+    }
+}
@@ -0,0 +1,110 @@
+//! Raw API exchange storage (the `thought_raw_io` table).
+//!
+//! Each row links one thought to the exact request messages and unparsed
+//! completion that produced it. Rows exist only when `LINEAR_STORE_RAW_IO` is
+//! enabled — the modes redact and cap the payload before calling in here
+//! (see `modes/core.rs`) — and are read back by `reasoning_inspect_thought`.
+
+#![allow(clippy::missing_errors_doc)]
+
+use sqlx::Row;
+
+use super::core::SqliteStorage;
+use super::types::StoredRawIo;
+use crate::error::StorageError;
+
+// INSERT OR REPLACE: a confidence-floor rerun or self-correction retry can
+// re-record the same thought id with the exchange that actually won.
+const INSERT_RAW_IO: &str = "INSERT OR REPLACE INTO thought_raw_io (thought_id, messages_json, raw_completion, created_at) VALUES (?, ?, ?, ?)";
+const SELECT_RAW_IO: &str =
+    "SELECT thought_id, messages_json, raw_completion, created_at FROM thought_raw_io \
+     WHERE thought_id = ?";
+
+impl SqliteStorage {
+    /// Persist the raw exchange behind one thought, replacing any earlier row.
+    pub async fn save_thought_raw_io(&self, raw_io: &StoredRawIo) -> Result<(), StorageError> {
+        sqlx::query(INSERT_RAW_IO)
+            .bind(&raw_io.thought_id)
+            .bind(&raw_io.messages_json)
+            .bind(&raw_io.raw_completion)
+            .bind(raw_io.created_at.to_rfc3339())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Self::query_error("INSERT thought_raw_io", format!("{e}")))?;
+        Ok(())
+    }
+
+    /// Get the raw exchange behind a thought, if one was recorded.
+    pub async fn get_thought_raw_io(
+        &self,
+        thought_id: &str,
+    ) -> Result<Option<StoredRawIo>, StorageError> {
+        let row = sqlx::query(SELECT_RAW_IO)
+            .bind(thought_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| Self::query_error("SELECT thought_raw_io", format!("{e}")))?;
+        row.as_ref().map(Self::row_to_raw_io).transpose()
+    }
+
+    fn row_to_raw_io(row: &sqlx::sqlite::SqliteRow) -> Result<StoredRawIo, StorageError> {
+        let created_at_str: String = row.get("created_at");
+        Ok(StoredRawIo {
+            thought_id: row.get("thought_id"),
+            messages_json: row.get("messages_json"),
+            raw_completion: row.get("raw_completion"),
+            created_at: Self::parse_datetime(&created_at_str)?,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_save_and_get_roundtrip() {
+        let storage = SqliteStorage::new_in_memory().await.expect("storage");
+        let raw_io = StoredRawIo::new("t-1", "[{\"role\":\"user\",\"content\":\"hi\"}]", "raw");
+
+        storage.save_thought_raw_io(&raw_io).await.expect("save");
+
+        let loaded = storage
+            .get_thought_raw_io("t-1")
+            .await
+            .expect("get")
+            .expect("recorded");
+        assert_eq!(loaded, raw_io);
+    }
+
+    #[tokio::test]
+    async fn test_missing_thought_returns_none() {
+        let storage = SqliteStorage::new_in_memory().await.expect("storage");
+        assert!(storage
+            .get_thought_raw_io("absent")
+            .await
+            .expect("get")
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_resave_replaces_earlier_exchange() {
+        let storage = SqliteStorage::new_in_memory().await.expect("storage");
+        storage
+            .save_thought_raw_io(&StoredRawIo::new("t-1", "[]", "first pass"))
+            .await
+            .expect("save first");
+        storage
+            .save_thought_raw_io(&StoredRawIo::new("t-1", "[]", "escalated rerun"))
+            .await
+            .expect("save second");
+
+        let loaded = storage
+            .get_thought_raw_io("t-1")
+            .await
+            .expect("get")
+            .expect("recorded");
+        assert_eq!(loaded.raw_completion, "escalated rerun");
+    }
+}
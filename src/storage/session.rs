@@ -5,6 +5,7 @@
 use crate::error::StorageError;
 use chrono::Utc;
 use sqlx::Row;
+use std::collections::HashSet;
 
 use super::core::SqliteStorage;
 use super::types::StoredSession;
@@ -15,6 +16,45 @@ const SELECT_SESSION: &str =
     "SELECT id, created_at, updated_at, metadata FROM sessions WHERE id = ?";
 const UPDATE_SESSION_TIMESTAMP: &str = "UPDATE sessions SET updated_at = ? WHERE id = ?";
 const DELETE_SESSION: &str = "DELETE FROM sessions WHERE id = ?";
+const SELECT_WORKING_MEMORY: &str = "SELECT working_memory FROM sessions WHERE id = ?";
+const UPDATE_WORKING_MEMORY: &str =
+    "UPDATE sessions SET working_memory = ?, updated_at = ? WHERE id = ?";
+const SELECT_SESSION_QUALITY: &str = "SELECT quality FROM sessions WHERE id = ?";
+// The first CASE arm handles the first scored thought: with no prior
+// aggregate, the session quality is the score itself.
+// `pub(super)` so the transactional graph batch write folds quality in the
+// same transaction that saves its thought.
+pub(super) const UPDATE_SESSION_QUALITY: &str = "UPDATE sessions SET quality = CASE WHEN quality IS NULL THEN ?1 ELSE ?1 * ?2 + quality * (1.0 - ?2) END WHERE id = ?3";
+
+/// Weight the newest thought's score carries in the session quality aggregate.
+///
+/// The prior aggregate keeps the remainder. Chosen so the metric tracks
+/// recent reasoning without a single thought dominating it.
+pub const SESSION_QUALITY_RECENCY_WEIGHT: f64 = 0.3;
+
+// Merge queries: ids are globally-unique TEXT primary keys, so moving data
+// between sessions is a re-parenting UPDATE rather than a copy.
+const SELECT_SESSION_GRAPH_NODE_IDS: &str = "SELECT id FROM graph_nodes WHERE session_id = ?";
+const SELECT_SESSION_GRAPH_EDGE_IDS: &str = "SELECT id FROM graph_edges WHERE session_id = ?";
+const REMAP_GRAPH_NODE_ID: &str = "UPDATE graph_nodes SET id = ? WHERE id = ?";
+const REMAP_GRAPH_EDGE_ID: &str = "UPDATE graph_edges SET id = ? WHERE id = ?";
+const REMAP_EDGE_FROM_NODE: &str = "UPDATE graph_edges SET from_node_id = ? WHERE from_node_id = ?";
+const REMAP_EDGE_TO_NODE: &str = "UPDATE graph_edges SET to_node_id = ? WHERE to_node_id = ?";
+const REPARENT_THOUGHTS: &str = "UPDATE thoughts SET session_id = ? WHERE session_id = ?";
+const REPARENT_BRANCHES: &str = "UPDATE branches SET session_id = ? WHERE session_id = ?";
+const REPARENT_CHECKPOINTS: &str = "UPDATE checkpoints SET session_id = ? WHERE session_id = ?";
+const REPARENT_GRAPH_NODES: &str = "UPDATE graph_nodes SET session_id = ? WHERE session_id = ?";
+const REPARENT_GRAPH_EDGES: &str = "UPDATE graph_edges SET session_id = ? WHERE session_id = ?";
+// Source and target both number their thoughts from 1, so after re-parenting
+// the merged set holds colliding sequences; renumber by the (created_at, id)
+// rank migration 015's backfill uses, keeping ordered reads chronological.
+const RESEQUENCE_MERGED_THOUGHTS: &str = "UPDATE thoughts SET sequence = (
+    SELECT COUNT(*) FROM thoughts t2
+    WHERE t2.session_id = thoughts.session_id
+      AND (t2.created_at < thoughts.created_at
+           OR (t2.created_at = thoughts.created_at AND t2.id <= thoughts.id))
+) WHERE session_id = ?";
+const DELETE_MERGED_EMBEDDINGS: &str = "DELETE FROM session_embeddings WHERE session_id IN (?, ?)";
 
 impl SqliteStorage {
     /// Create a new session.
@@ -91,6 +131,260 @@ impl SqliteStorage {
         Ok(())
     }
 
+    /// Get a session's working memory.
+    ///
+    /// Returns `None` when the session doesn't exist or no memory has been set.
+    pub async fn get_working_memory(&self, id: &str) -> Result<Option<String>, StorageError> {
+        let row = sqlx::query(SELECT_WORKING_MEMORY)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| Self::query_error("SELECT sessions.working_memory", format!("{e}")))?;
+
+        Ok(row.and_then(|row| row.get::<Option<String>, _>("working_memory")))
+    }
+
+    /// Set a session's working memory, bumping `updated_at`.
+    pub async fn set_working_memory(&self, id: &str, memory: &str) -> Result<(), StorageError> {
+        let now = Utc::now().to_rfc3339();
+
+        let result = sqlx::query(UPDATE_WORKING_MEMORY)
+            .bind(memory)
+            .bind(&now)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Self::query_error("UPDATE sessions.working_memory", format!("{e}")))?;
+
+        if result.rows_affected() == 0 {
+            return Err(StorageError::SessionNotFound {
+                session_id: id.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Get a session's recency-weighted quality aggregate.
+    ///
+    /// Returns `None` when the session doesn't exist or no thought has been
+    /// scored yet.
+    pub async fn get_session_quality(&self, id: &str) -> Result<Option<f64>, StorageError> {
+        let row = sqlx::query(SELECT_SESSION_QUALITY)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| Self::query_error("SELECT sessions.quality", format!("{e}")))?;
+
+        Ok(row.and_then(|row| row.get::<Option<f64>, _>("quality")))
+    }
+
+    /// Fold a new thought score into the session's quality aggregate.
+    ///
+    /// Exponential moving average favoring recent thoughts: the new score
+    /// carries [`SESSION_QUALITY_RECENCY_WEIGHT`] and the prior aggregate
+    /// keeps the remainder. The first scored thought sets the quality to its
+    /// score. A quiet no-op when the session does not exist.
+    pub async fn update_session_quality(&self, id: &str, score: f64) -> Result<(), StorageError> {
+        sqlx::query(UPDATE_SESSION_QUALITY)
+            .bind(score)
+            .bind(SESSION_QUALITY_RECENCY_WEIGHT)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Self::query_error("UPDATE sessions.quality", format!("{e}")))?;
+
+        Ok(())
+    }
+
+    /// Merge `source_id` into `target_id`, then delete the source session.
+    ///
+    /// The source's thoughts, branches, checkpoints, and graph data are
+    /// re-parented under the target. Because every id is a globally-unique
+    /// TEXT primary key, rows move unchanged — except graph ids created by
+    /// `reasoning_graph` import, which are namespaced as
+    /// `"{session_id}::{external_id}"` and would leave the target holding
+    /// ids in a dead namespace (or collide, when both sessions imported the
+    /// same external graph). Those are remapped into the target's namespace
+    /// with a numeric suffix on collision, and edge endpoints follow the
+    /// node remap. Thoughts also carry per-session sequence numbers that
+    /// both sessions count from 1, so the merged set is resequenced by
+    /// `created_at` inside the transaction — without that, ordered reads
+    /// would interleave the two sessions' thoughts on the colliding
+    /// sequences instead of preserving chronology.
+    ///
+    /// The merge runs in one transaction: either everything moves and the
+    /// source is deleted, or nothing changes. Cached embeddings for both
+    /// sessions are dropped — the cache is derived data and self-heals on
+    /// the next search. Merging a session into itself is a no-op: the
+    /// target already contains everything the merge would move.
+    pub async fn merge_sessions(
+        &self,
+        target_id: &str,
+        source_id: &str,
+    ) -> Result<(), StorageError> {
+        for id in [target_id, source_id] {
+            if self.get_stored_session(id).await?.is_none() {
+                return Err(StorageError::SessionNotFound {
+                    session_id: id.to_string(),
+                });
+            }
+        }
+        if target_id == source_id {
+            return Ok(());
+        }
+
+        let node_remap = Self::namespace_remap(
+            target_id,
+            source_id,
+            &self
+                .session_row_ids(SELECT_SESSION_GRAPH_NODE_IDS, source_id)
+                .await?,
+            &self
+                .session_row_ids(SELECT_SESSION_GRAPH_NODE_IDS, target_id)
+                .await?,
+        );
+        let edge_remap = Self::namespace_remap(
+            target_id,
+            source_id,
+            &self
+                .session_row_ids(SELECT_SESSION_GRAPH_EDGE_IDS, source_id)
+                .await?,
+            &self
+                .session_row_ids(SELECT_SESSION_GRAPH_EDGE_IDS, target_id)
+                .await?,
+        );
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| Self::query_error("BEGIN merge_sessions", format!("{e}")))?;
+
+        // Remapping a node id while edges still reference the old id would
+        // trip the graph_edges foreign keys mid-transaction, so defer the
+        // checks until commit.
+        sqlx::query("PRAGMA defer_foreign_keys = ON")
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Self::query_error("PRAGMA defer_foreign_keys", format!("{e}")))?;
+
+        for (old, new) in &node_remap {
+            for sql in [
+                REMAP_GRAPH_NODE_ID,
+                REMAP_EDGE_FROM_NODE,
+                REMAP_EDGE_TO_NODE,
+            ] {
+                sqlx::query(sql)
+                    .bind(new)
+                    .bind(old)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| Self::query_error("UPDATE graph node remap", format!("{e}")))?;
+            }
+        }
+        for (old, new) in &edge_remap {
+            sqlx::query(REMAP_GRAPH_EDGE_ID)
+                .bind(new)
+                .bind(old)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| Self::query_error("UPDATE graph edge remap", format!("{e}")))?;
+        }
+
+        for sql in [
+            REPARENT_THOUGHTS,
+            REPARENT_BRANCHES,
+            REPARENT_CHECKPOINTS,
+            REPARENT_GRAPH_NODES,
+            REPARENT_GRAPH_EDGES,
+        ] {
+            sqlx::query(sql)
+                .bind(target_id)
+                .bind(source_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| Self::query_error("UPDATE merge re-parent", format!("{e}")))?;
+        }
+
+        sqlx::query(RESEQUENCE_MERGED_THOUGHTS)
+            .bind(target_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Self::query_error("UPDATE merge resequence", format!("{e}")))?;
+
+        sqlx::query(DELETE_MERGED_EMBEDDINGS)
+            .bind(target_id)
+            .bind(source_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Self::query_error("DELETE session_embeddings", format!("{e}")))?;
+
+        sqlx::query(UPDATE_SESSION_TIMESTAMP)
+            .bind(Utc::now().to_rfc3339())
+            .bind(target_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Self::query_error("UPDATE sessions", format!("{e}")))?;
+
+        sqlx::query(DELETE_SESSION)
+            .bind(source_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Self::query_error("DELETE sessions", format!("{e}")))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| Self::query_error("COMMIT merge_sessions", format!("{e}")))?;
+
+        Ok(())
+    }
+
+    /// Fetch the `id` column for a session's rows via one of the
+    /// `SELECT_SESSION_GRAPH_*_IDS` queries.
+    async fn session_row_ids(
+        &self,
+        query: &'static str,
+        session_id: &str,
+    ) -> Result<Vec<String>, StorageError> {
+        let rows = sqlx::query(query)
+            .bind(session_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| Self::query_error("SELECT merge ids", format!("{e}")))?;
+
+        Ok(rows.iter().map(|row| row.get("id")).collect())
+    }
+
+    /// Build old→new pairs moving `"{source_id}::x"` ids into the target's
+    /// namespace, suffixing `-2`, `-3`, … when the remapped id is taken.
+    /// Non-namespaced (UUID-based) ids are left alone.
+    fn namespace_remap(
+        target_id: &str,
+        source_id: &str,
+        source_ids: &[String],
+        target_ids: &[String],
+    ) -> Vec<(String, String)> {
+        let source_prefix = format!("{source_id}::");
+        let mut taken: HashSet<String> = target_ids.iter().cloned().collect();
+        let mut remap = Vec::new();
+
+        for old in source_ids {
+            if let Some(rest) = old.strip_prefix(&source_prefix) {
+                let mut candidate = format!("{target_id}::{rest}");
+                let mut n = 2u32;
+                while taken.contains(&candidate) {
+                    candidate = format!("{target_id}::{rest}-{n}");
+                    n += 1;
+                }
+                taken.insert(candidate.clone());
+                remap.push((old.clone(), candidate));
+            }
+        }
+
+        remap
+    }
+
     /// Delete a session and all related data.
     pub async fn delete_session(&self, id: &str) -> Result<(), StorageError> {
         let result = sqlx::query(DELETE_SESSION)
@@ -221,6 +515,241 @@ mod tests {
         assert!(matches!(result, Err(StorageError::SessionNotFound { .. })));
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_working_memory_roundtrip() {
+        let storage = test_storage().await;
+        storage
+            .create_session_with_id("sess-wm")
+            .await
+            .expect("create");
+
+        // Unset memory reads back as None.
+        let memory = storage.get_working_memory("sess-wm").await.expect("get");
+        assert!(memory.is_none());
+
+        storage
+            .set_working_memory("sess-wm", "key facts: X depends on Y")
+            .await
+            .expect("set");
+
+        let memory = storage.get_working_memory("sess-wm").await.expect("get");
+        assert_eq!(memory, Some("key facts: X depends on Y".to_string()));
+
+        // Overwrite replaces the previous value.
+        storage
+            .set_working_memory("sess-wm", "revised: Y was ruled out")
+            .await
+            .expect("set again");
+        let memory = storage.get_working_memory("sess-wm").await.expect("get");
+        assert_eq!(memory, Some("revised: Y was ruled out".to_string()));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_working_memory_missing_session() {
+        let storage = test_storage().await;
+        let memory = storage
+            .get_working_memory("nonexistent")
+            .await
+            .expect("get");
+        assert!(memory.is_none());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_set_working_memory_missing_session() {
+        let storage = test_storage().await;
+        let result = storage.set_working_memory("nonexistent", "memo").await;
+
+        assert!(result.is_err());
+        assert!(matches!(result, Err(StorageError::SessionNotFound { .. })));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_merge_sessions_combines_thoughts_in_time_order() {
+        use crate::storage::types::{StoredCheckpoint, StoredThought};
+
+        let storage = test_storage().await;
+        storage
+            .create_session_with_id("sess-tgt")
+            .await
+            .expect("create target");
+        storage
+            .create_session_with_id("sess-src")
+            .await
+            .expect("create source");
+
+        // Write all source thoughts before all target thoughts: both sessions
+        // sequence from 1, so the merged order is only correct if the merge
+        // resequences by creation time — a bare re-parent would read the
+        // colliding sequences interleaved (first, third, second, fourth).
+        for (session, content) in [
+            ("sess-src", "first"),
+            ("sess-src", "second"),
+            ("sess-tgt", "third"),
+            ("sess-tgt", "fourth"),
+        ] {
+            let thought =
+                StoredThought::new(format!("t-{content}"), session, "linear", content, 0.8);
+            storage
+                .save_stored_thought(&thought)
+                .await
+                .expect("save thought");
+            tokio::time::sleep(tokio::time::Duration::from_millis(5)).await;
+        }
+
+        let checkpoint = StoredCheckpoint::new("cp-src", "sess-src", "before-pivot", "{}");
+        storage
+            .save_checkpoint(&checkpoint)
+            .await
+            .expect("save checkpoint");
+
+        storage
+            .merge_sessions("sess-tgt", "sess-src")
+            .await
+            .expect("merge");
+
+        let thoughts = storage
+            .get_stored_thoughts("sess-tgt")
+            .await
+            .expect("thoughts");
+        let contents: Vec<&str> = thoughts.iter().map(|t| t.content.as_str()).collect();
+        assert_eq!(contents, vec!["first", "second", "third", "fourth"]);
+
+        let checkpoints = storage
+            .get_checkpoints("sess-tgt")
+            .await
+            .expect("checkpoints");
+        assert_eq!(checkpoints.len(), 1);
+        assert_eq!(checkpoints[0].id, "cp-src");
+
+        let source = storage.get_stored_session("sess-src").await.expect("fetch");
+        assert!(source.is_none(), "source session should be deleted");
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_merge_sessions_remaps_namespaced_graph_ids() {
+        use crate::storage::types::{StoredGraphEdge, StoredGraphNode};
+
+        let storage = test_storage().await;
+        storage
+            .create_session_with_id("sess-tgt")
+            .await
+            .expect("create target");
+        storage
+            .create_session_with_id("sess-src")
+            .await
+            .expect("create source");
+
+        // Both sessions imported the same external graph, so the namespaced
+        // ids collide once the source's are moved into the target namespace.
+        for session in ["sess-tgt", "sess-src"] {
+            for node in ["ext-a", "ext-b"] {
+                storage
+                    .save_graph_node(&StoredGraphNode::new(
+                        format!("{session}::{node}"),
+                        session,
+                        format!("{node} in {session}"),
+                    ))
+                    .await
+                    .expect("save node");
+            }
+            storage
+                .save_graph_edge(&StoredGraphEdge::new(
+                    format!("{session}::e1"),
+                    session,
+                    format!("{session}::ext-a"),
+                    format!("{session}::ext-b"),
+                ))
+                .await
+                .expect("save edge");
+        }
+
+        storage
+            .merge_sessions("sess-tgt", "sess-src")
+            .await
+            .expect("merge");
+
+        let nodes = storage.get_graph_nodes("sess-tgt").await.expect("nodes");
+        let mut node_ids: Vec<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+        node_ids.sort_unstable();
+        assert_eq!(
+            node_ids,
+            vec![
+                "sess-tgt::ext-a",
+                "sess-tgt::ext-a-2",
+                "sess-tgt::ext-b",
+                "sess-tgt::ext-b-2",
+            ]
+        );
+
+        let edges = storage.get_graph_edges("sess-tgt").await.expect("edges");
+        assert_eq!(edges.len(), 2);
+        let moved = edges
+            .iter()
+            .find(|e| e.id == "sess-tgt::e1-2")
+            .expect("remapped edge");
+        assert_eq!(moved.from_node_id, "sess-tgt::ext-a-2");
+        assert_eq!(moved.to_node_id, "sess-tgt::ext-b-2");
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_merge_sessions_self_merge_is_noop() {
+        use crate::storage::types::StoredThought;
+
+        let storage = test_storage().await;
+        storage
+            .create_session_with_id("sess-self")
+            .await
+            .expect("create");
+        storage
+            .save_stored_thought(&StoredThought::new(
+                "t-1",
+                "sess-self",
+                "linear",
+                "only",
+                0.8,
+            ))
+            .await
+            .expect("save thought");
+
+        storage
+            .merge_sessions("sess-self", "sess-self")
+            .await
+            .expect("self-merge");
+
+        let session = storage
+            .get_stored_session("sess-self")
+            .await
+            .expect("fetch");
+        assert!(session.is_some(), "self-merge must not delete the session");
+        let thoughts = storage
+            .get_stored_thoughts("sess-self")
+            .await
+            .expect("thoughts");
+        assert_eq!(thoughts.len(), 1);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_merge_sessions_missing_session() {
+        let storage = test_storage().await;
+        storage
+            .create_session_with_id("sess-tgt")
+            .await
+            .expect("create");
+
+        let result = storage.merge_sessions("sess-tgt", "nonexistent").await;
+        assert!(matches!(result, Err(StorageError::SessionNotFound { .. })));
+
+        let result = storage.merge_sessions("nonexistent", "sess-tgt").await;
+        assert!(matches!(result, Err(StorageError::SessionNotFound { .. })));
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_get_stored_session_with_metadata() {
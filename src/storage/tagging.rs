@@ -0,0 +1,261 @@
+//! Automatic thought tagging on save.
+//!
+//! When enabled (see [`with_auto_tagging`](super::SqliteStorage::with_auto_tagging)),
+//! every saved thought gets a `mode:<mode>` tag plus `topic:<name>` tags in its
+//! JSON metadata under `"tags"`, to aid later retrieval. Topics come from a
+//! cheap local keyword table by default; LLM-derived topics are a further
+//! opt-in (they cost an API call per save) and fall back to keywords on any
+//! failure — tagging is best-effort and must never fail a thought write.
+
+use std::collections::HashSet;
+
+use crate::traits::{AnthropicClientTrait, CompletionConfig, Message};
+
+/// Prefix for the tag carrying the thought's reasoning mode.
+pub(super) const MODE_TAG_PREFIX: &str = "mode:";
+/// Prefix for topic tags (keyword- or LLM-derived).
+pub(super) const TOPIC_TAG_PREFIX: &str = "topic:";
+
+/// Maximum topic tags attached per thought (keyword and LLM paths alike).
+const MAX_TOPIC_TAGS: usize = 3;
+/// Content sent to the LLM topic extractor is capped to keep the call cheap.
+const LLM_TOPIC_CONTENT_CHARS: usize = 2_000;
+
+/// Topic → trigger keywords. A topic is attached when any of its keywords
+/// appears as a whole word in the content. Deliberately small and local —
+/// this runs on every thought write when auto-tagging is enabled.
+const KEYWORD_TOPICS: &[(&str, &[&str])] = &[
+    (
+        "database",
+        &["database", "sql", "sqlite", "schema", "migration", "index"],
+    ),
+    (
+        "performance",
+        &[
+            "performance",
+            "latency",
+            "throughput",
+            "optimization",
+            "benchmark",
+            "caching",
+        ],
+    ),
+    (
+        "security",
+        &[
+            "security",
+            "vulnerability",
+            "encryption",
+            "authentication",
+            "credentials",
+        ],
+    ),
+    (
+        "testing",
+        &["test", "tests", "testing", "coverage", "regression"],
+    ),
+    (
+        "architecture",
+        &[
+            "architecture",
+            "refactor",
+            "module",
+            "coupling",
+            "interface",
+        ],
+    ),
+    ("api", &["api", "endpoint", "protocol", "integration"]),
+    ("cost", &["cost", "budget", "pricing", "revenue"]),
+    ("risk", &["risk", "failure", "mitigation", "contingency"]),
+];
+
+/// Derive topic tags from the content via the static keyword table.
+///
+/// Matches whole words (case-insensitive, punctuation-stripped) so "testing"
+/// triggers the `testing` topic but "latest" does not trigger `test`. Returns
+/// at most [`MAX_TOPIC_TAGS`] topics in table order.
+pub(super) fn keyword_topics(content: &str) -> Vec<String> {
+    let words: HashSet<String> = content
+        .split_whitespace()
+        .map(|w| {
+            w.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    KEYWORD_TOPICS
+        .iter()
+        .filter(|(_, keywords)| keywords.iter().any(|k| words.contains(*k)))
+        .map(|(topic, _)| (*topic).to_string())
+        .take(MAX_TOPIC_TAGS)
+        .collect()
+}
+
+/// Derive topic tags from the content via a small LLM call.
+///
+/// Best-effort: any API or parse failure returns `None` so the caller can
+/// fall back to [`keyword_topics`] — a tagging hiccup must never fail or
+/// block a thought write beyond the call itself.
+pub(super) async fn llm_topics(
+    client: &dyn AnthropicClientTrait,
+    content: &str,
+) -> Option<Vec<String>> {
+    let excerpt: String = content.chars().take(LLM_TOPIC_CONTENT_CHARS).collect();
+    let prompt = format!(
+        "Extract 1-{MAX_TOPIC_TAGS} short topic tags for the following reasoning content. \
+         Respond with ONLY a JSON array of lowercase single-word or hyphenated tags, \
+         e.g. [\"database\", \"cost-analysis\"].\n\nContent:\n{excerpt}"
+    );
+
+    let config = CompletionConfig::new()
+        .with_mode("auto_tag")
+        .with_max_tokens(256)
+        .with_temperature(0.0);
+
+    let response = match client.complete(vec![Message::user(prompt)], config).await {
+        Ok(response) => response,
+        Err(e) => {
+            tracing::debug!(error = %e, "LLM topic extraction failed — falling back to keywords");
+            return None;
+        }
+    };
+
+    parse_topic_array(&response.content)
+}
+
+/// Extract a JSON string array from a (possibly fenced or prefixed) response.
+fn parse_topic_array(text: &str) -> Option<Vec<String>> {
+    let start = text.find('[')?;
+    let end = text.rfind(']')?;
+    let topics: Vec<String> = serde_json::from_str(text.get(start..=end)?).ok()?;
+    let topics: Vec<String> = topics
+        .into_iter()
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .take(MAX_TOPIC_TAGS)
+        .collect();
+    if topics.is_empty() {
+        None
+    } else {
+        Some(topics)
+    }
+}
+
+/// Merge `new_tags` into the metadata's `"tags"` array, preserving any
+/// existing metadata object and existing tags (non-object metadata is
+/// replaced, matching `duplicate_of` linking). Duplicates are not re-added.
+pub(super) fn merge_tags(metadata: Option<&str>, new_tags: &[String]) -> String {
+    let mut metadata_json = metadata
+        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+        .filter(serde_json::Value::is_object)
+        .unwrap_or_else(|| serde_json::json!({}));
+
+    let mut tags: Vec<serde_json::Value> = metadata_json
+        .get("tags")
+        .and_then(serde_json::Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    for tag in new_tags {
+        if !tags.iter().any(|t| t.as_str() == Some(tag)) {
+            tags.push(serde_json::Value::String(tag.clone()));
+        }
+    }
+    metadata_json["tags"] = serde_json::Value::Array(tags);
+    metadata_json.to_string()
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp,
+    clippy::approx_constant,
+    clippy::unreadable_literal
+)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyword_topics_matches_known_terms() {
+        let topics = keyword_topics("We should add an index to the database schema");
+        assert_eq!(topics, vec!["database".to_string()]);
+
+        let topics = keyword_topics("Benchmark the API endpoint latency under load");
+        assert_eq!(topics, vec!["performance".to_string(), "api".to_string()]);
+    }
+
+    #[test]
+    fn test_keyword_topics_whole_words_only() {
+        // "latest" contains "test" but is not the word "test".
+        assert!(keyword_topics("the latest release notes").is_empty());
+        // Punctuation and case are normalized.
+        assert_eq!(
+            keyword_topics("Improve Testing, please."),
+            vec!["testing".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_keyword_topics_no_match() {
+        assert!(keyword_topics("completely unrelated prose").is_empty());
+        assert!(keyword_topics("").is_empty());
+    }
+
+    #[test]
+    fn test_keyword_topics_capped() {
+        let topics = keyword_topics("database latency security testing architecture api");
+        assert_eq!(topics.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_topic_array() {
+        assert_eq!(
+            parse_topic_array(r#"["Database", " caching "]"#),
+            Some(vec!["database".to_string(), "caching".to_string()])
+        );
+        // Fenced / prefixed responses still parse.
+        assert_eq!(
+            parse_topic_array("Here are the tags:\n```json\n[\"cost\"]\n```"),
+            Some(vec!["cost".to_string()])
+        );
+        // More than the cap is truncated.
+        assert_eq!(
+            parse_topic_array(r#"["a", "b", "c", "d"]"#).map(|t| t.len()),
+            Some(3)
+        );
+        assert_eq!(parse_topic_array("no json here"), None);
+        assert_eq!(parse_topic_array("[]"), None);
+        assert_eq!(parse_topic_array("[1, 2]"), None);
+    }
+
+    #[test]
+    fn test_merge_tags_into_empty_metadata() {
+        let merged = merge_tags(None, &["mode:linear".to_string()]);
+        let json: serde_json::Value = serde_json::from_str(&merged).unwrap();
+        assert_eq!(json["tags"], serde_json::json!(["mode:linear"]));
+    }
+
+    #[test]
+    fn test_merge_tags_preserves_existing_metadata_and_tags() {
+        let merged = merge_tags(
+            Some(r#"{"duplicate_of": "t-1", "tags": ["topic:cost"]}"#),
+            &["mode:linear".to_string(), "topic:cost".to_string()],
+        );
+        let json: serde_json::Value = serde_json::from_str(&merged).unwrap();
+        assert_eq!(json["duplicate_of"], "t-1");
+        // Existing tag kept once; new mode tag appended.
+        assert_eq!(
+            json["tags"],
+            serde_json::json!(["topic:cost", "mode:linear"])
+        );
+    }
+
+    #[test]
+    fn test_merge_tags_replaces_non_object_metadata() {
+        let merged = merge_tags(Some("not json"), &["mode:tree".to_string()]);
+        let json: serde_json::Value = serde_json::from_str(&merged).unwrap();
+        assert_eq!(json["tags"], serde_json::json!(["mode:tree"]));
+    }
+}
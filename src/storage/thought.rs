@@ -2,17 +2,72 @@
 
 #![allow(clippy::missing_errors_doc)]
 
+use std::collections::HashSet;
+
 use crate::error::StorageError;
 use sqlx::Row;
 
 use super::core::SqliteStorage;
-use super::types::StoredThought;
+use super::types::{StoredThought, ThoughtDedupStrategy};
 
 // SQL query constants to avoid repeated allocations
-const INSERT_THOUGHT: &str = "INSERT INTO thoughts (id, session_id, parent_id, mode, content, confidence, metadata, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)";
-const SELECT_THOUGHT: &str = "SELECT id, session_id, parent_id, mode, content, confidence, metadata, created_at FROM thoughts WHERE id = ?";
-const SELECT_THOUGHTS_BY_SESSION: &str = "SELECT id, session_id, parent_id, mode, content, confidence, metadata, created_at FROM thoughts WHERE session_id = ? ORDER BY created_at ASC";
+// `pub(super)` so the transactional graph batch write reuses the same insert.
+// The insert computes the per-session sequence (MAX + 1) in the same statement,
+// so concurrent saves serialize on SQLite's write lock and the sequence stays
+// contiguous even when timestamps collide. The trailing parameter is the
+// session id a second time.
+pub(super) const INSERT_THOUGHT: &str = "INSERT INTO thoughts (id, session_id, parent_id, mode, content, confidence, metadata, created_at, sequence) VALUES (?, ?, ?, ?, ?, ?, ?, ?, (SELECT COALESCE(MAX(sequence), 0) + 1 FROM thoughts WHERE session_id = ?))";
+const SELECT_THOUGHT: &str = "SELECT id, session_id, parent_id, mode, content, confidence, metadata, created_at, sequence FROM thoughts WHERE id = ?";
+const SELECT_THOUGHTS_BY_SESSION: &str = "SELECT id, session_id, parent_id, mode, content, confidence, metadata, created_at, sequence FROM thoughts WHERE session_id = ? ORDER BY sequence ASC, created_at ASC";
 const DELETE_THOUGHT: &str = "DELETE FROM thoughts WHERE id = ?";
+const SELECT_LAST_THOUGHT: &str = "SELECT id, session_id, parent_id, mode, content, confidence, metadata, created_at, sequence FROM thoughts WHERE session_id = ? ORDER BY sequence DESC, created_at DESC, id DESC LIMIT 1";
+const DELETE_GRAPH_EDGES_SINCE: &str =
+    "DELETE FROM graph_edges WHERE session_id = ? AND created_at >= ?";
+const DELETE_GRAPH_NODES_SINCE: &str =
+    "DELETE FROM graph_nodes WHERE session_id = ? AND created_at >= ?";
+const SELECT_THOUGHT_CONTENTS: &str =
+    "SELECT id, content FROM thoughts WHERE session_id = ? ORDER BY sequence ASC, created_at ASC";
+const UPDATE_THOUGHT_METADATA: &str = "UPDATE thoughts SET metadata = ? WHERE id = ?";
+const SELECT_MODE_CONFIDENCE: &str = "SELECT mode, AVG(confidence) AS avg_confidence, COUNT(*) AS samples FROM thoughts GROUP BY mode ORDER BY avg_confidence DESC";
+// FTS5 restriction: aggregate only the thoughts whose content matches the
+// query, via the rowid-linked thoughts_fts index (migration 007).
+const SELECT_MODE_CONFIDENCE_FTS: &str = "SELECT t.mode, AVG(t.confidence) AS avg_confidence, COUNT(*) AS samples FROM thoughts_fts JOIN thoughts t ON t.rowid = thoughts_fts.rowid WHERE thoughts_fts MATCH ? GROUP BY t.mode ORDER BY avg_confidence DESC";
+
+/// Content similarity in [0.0, 1.0]: 1.0 for identical normalized text, else
+/// Jaccard overlap of the word sets. Deliberately cheap and local — dedup runs
+/// on every thought write and must not cost an embedding call. Graph merge
+/// reuses the same measure to fold near-identical nodes.
+pub fn content_similarity(a: &str, b: &str) -> f64 {
+    let normalize =
+        |text: &str| -> Vec<String> { text.split_whitespace().map(str::to_lowercase).collect() };
+    let words_a = normalize(a);
+    let words_b = normalize(b);
+    if words_a == words_b {
+        return 1.0;
+    }
+    if words_a.is_empty() || words_b.is_empty() {
+        return 0.0;
+    }
+
+    let set_a: HashSet<&String> = words_a.iter().collect();
+    let set_b: HashSet<&String> = words_b.iter().collect();
+    let intersection = set_a.intersection(&set_b).count();
+    let union = set_a.union(&set_b).count();
+    intersection as f64 / union as f64
+}
+
+/// Reduce raw caller text to a safe FTS5 match expression: lowercased
+/// alphanumeric words OR-ed together. Anything else (quotes, operators,
+/// punctuation) is dropped, and lowercasing keeps words like "AND" from being
+/// read as FTS5 operators, so arbitrary content can never produce an FTS5
+/// syntax error.
+fn sanitize_fts_query(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" OR ")
+}
 
 impl SqliteStorage {
     /// Delete a stored thought by ID. No-op if it does not exist.
@@ -25,8 +80,145 @@ impl SqliteStorage {
         Ok(())
     }
 
+    /// Get the most recent thought in a session, or `Ok(None)` when the
+    /// session has no thoughts.
+    pub async fn get_last_thought(
+        &self,
+        session_id: &str,
+    ) -> Result<Option<StoredThought>, StorageError> {
+        let row = sqlx::query(SELECT_LAST_THOUGHT)
+            .bind(session_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| Self::query_error("SELECT thoughts", format!("{e}")))?;
+        match row {
+            Some(row) => Ok(Some(Self::row_to_stored_thought(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Delete the most recent thought in a session, returning it for
+    /// confirmation. `Ok(None)` when the session has no thoughts.
+    ///
+    /// Graph nodes and edges created at or after the thought are cascaded:
+    /// graph operations persist their artifacts in the same call that saves
+    /// the thought, so anything newer in the session belongs to the undone
+    /// step. Earlier thoughts and graph state are untouched — only the last
+    /// thought can be removed, so nothing left behind depends on it.
+    ///
+    /// The cascade runs in one transaction: either the thought and every
+    /// newer graph artifact go together, or nothing changes — a failure
+    /// mid-cascade can't leave orphaned graph rows behind an undone thought.
+    pub async fn delete_last_thought(
+        &self,
+        session_id: &str,
+    ) -> Result<Option<StoredThought>, StorageError> {
+        let Some(thought) = self.get_last_thought(session_id).await? else {
+            return Ok(None);
+        };
+        let created_at_str = thought.created_at.to_rfc3339();
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| Self::query_error("BEGIN delete_last_thought", format!("{e}")))?;
+
+        // Edges first: they reference nodes about to be deleted.
+        let edges = sqlx::query(DELETE_GRAPH_EDGES_SINCE)
+            .bind(session_id)
+            .bind(&created_at_str)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Self::query_error("DELETE graph_edges", format!("{e}")))?;
+        let nodes = sqlx::query(DELETE_GRAPH_NODES_SINCE)
+            .bind(session_id)
+            .bind(&created_at_str)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Self::query_error("DELETE graph_nodes", format!("{e}")))?;
+
+        sqlx::query(DELETE_THOUGHT)
+            .bind(&thought.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Self::query_error("DELETE thoughts", format!("{e}")))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| Self::query_error("COMMIT delete_last_thought", format!("{e}")))?;
+
+        if edges.rows_affected() > 0 || nodes.rows_affected() > 0 {
+            tracing::info!(
+                session_id,
+                thought_id = %thought.id,
+                nodes_removed = nodes.rows_affected(),
+                edges_removed = edges.rows_affected(),
+                "Cascaded graph artifacts while undoing last thought"
+            );
+        }
+
+        Ok(Some(thought))
+    }
+
     /// Save a stored thought to the database.
+    ///
+    /// When persistence sampling is enabled (see
+    /// [`with_thought_sampling`](Self::with_thought_sampling)), ordinary
+    /// thoughts are persisted at the configured rate; important thoughts —
+    /// terminal-operation modes and high-confidence results — always are.
+    /// A sampled-out write returns `Ok(())` without a row.
+    ///
+    /// When thought dedup is enabled (see
+    /// [`with_thought_dedup`](Self::with_thought_dedup)) and an existing
+    /// thought in the session is near-identical, the write is either skipped
+    /// or saved with a `duplicate_of` link, per the configured strategy.
+    ///
+    /// When auto-tagging is enabled (see
+    /// [`with_auto_tagging`](Self::with_auto_tagging)), a `mode:<mode>` tag
+    /// and any derived `topic:` tags are merged into the thought's metadata
+    /// before the write.
     pub async fn save_stored_thought(&self, thought: &StoredThought) -> Result<(), StorageError> {
+        if let Some(sampling) = self.thought_sampling {
+            if !sampling.should_persist(thought) {
+                tracing::debug!(
+                    session_id = %thought.session_id,
+                    thought_id = %thought.id,
+                    rate = sampling.rate,
+                    "Sampling out thought persistence"
+                );
+                return Ok(());
+            }
+        }
+        let mut thought = std::borrow::Cow::Borrowed(thought);
+        if let Some(dedup) = self.thought_dedup {
+            if let Some(original_id) = self
+                .find_duplicate_thought(&thought.session_id, &thought.content, dedup.threshold)
+                .await?
+            {
+                match dedup.strategy {
+                    ThoughtDedupStrategy::Skip => {
+                        tracing::debug!(
+                            session_id = %thought.session_id,
+                            thought_id = %thought.id,
+                            duplicate_of = %original_id,
+                            "Skipping near-duplicate thought"
+                        );
+                        return Ok(());
+                    }
+                    ThoughtDedupStrategy::Link => {
+                        let linked = Self::link_duplicate(thought.into_owned(), &original_id);
+                        thought = std::borrow::Cow::Owned(linked);
+                    }
+                }
+            }
+        }
+        if self.auto_tag.is_some() {
+            let tags = self.collect_auto_tags(&thought).await;
+            let merged = super::tagging::merge_tags(thought.metadata.as_deref(), &tags);
+            thought.to_mut().metadata = Some(merged);
+        }
+        let thought = thought.as_ref();
         let created_at_str = thought.created_at.to_rfc3339();
 
         sqlx::query(INSERT_THOUGHT)
@@ -38,10 +230,32 @@ impl SqliteStorage {
             .bind(thought.confidence)
             .bind(&thought.metadata)
             .bind(&created_at_str)
+            .bind(&thought.session_id)
             .execute(&self.pool)
             .await
             .map_err(|e| Self::query_error("INSERT thoughts", format!("{e}")))?;
 
+        // Fold the thought's confidence into the session's recency-weighted
+        // quality aggregate (see update_session_quality).
+        self.update_session_quality(&thought.session_id, thought.confidence)
+            .await?;
+
+        // Settle any pending auto selection of this mode in the session: the
+        // thought's confidence becomes the selection's resulting score.
+        // Best-effort: selection history is advisory and must not fail the write.
+        if thought.mode != "auto" {
+            if let Err(e) = self
+                .score_auto_selection(&thought.session_id, &thought.mode, thought.confidence)
+                .await
+            {
+                tracing::warn!(
+                    session_id = %thought.session_id,
+                    error = %e,
+                    "Failed to score auto selection"
+                );
+            }
+        }
+
         // Queue the session for background (re)embedding. Best-effort: a queue
         // failure must not fail the thought write, and the cache is derived data
         // recomputed on demand if the worker never runs.
@@ -56,6 +270,83 @@ impl SqliteStorage {
         Ok(())
     }
 
+    /// Compute the tags to attach to a thought: the `mode:` tag plus `topic:`
+    /// tags (LLM-derived when configured and a client is attached, falling
+    /// back to the keyword table on any failure).
+    async fn collect_auto_tags(&self, thought: &StoredThought) -> Vec<String> {
+        let mut tags = vec![format!(
+            "{}{}",
+            super::tagging::MODE_TAG_PREFIX,
+            thought.mode
+        )];
+        let use_llm = self.auto_tag.is_some_and(|c| c.llm_topics);
+        let topics = match (use_llm, self.topic_tag_client.as_deref()) {
+            (true, Some(client)) => super::tagging::llm_topics(client, &thought.content)
+                .await
+                .unwrap_or_else(|| super::tagging::keyword_topics(&thought.content)),
+            _ => super::tagging::keyword_topics(&thought.content),
+        };
+        tags.extend(
+            topics
+                .iter()
+                .map(|t| format!("{}{t}", super::tagging::TOPIC_TAG_PREFIX)),
+        );
+        tags
+    }
+
+    /// Find an existing thought in the session whose content similarity to
+    /// `content` is at or above `threshold`, returning its ID.
+    async fn find_duplicate_thought(
+        &self,
+        session_id: &str,
+        content: &str,
+        threshold: f64,
+    ) -> Result<Option<String>, StorageError> {
+        let rows = sqlx::query(SELECT_THOUGHT_CONTENTS)
+            .bind(session_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| Self::query_error("SELECT thoughts", format!("{e}")))?;
+
+        for row in &rows {
+            let existing_content: String = row.get("content");
+            if content_similarity(content, &existing_content) >= threshold {
+                return Ok(Some(row.get("id")));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Record `duplicate_of` in the thought's JSON metadata, preserving any
+    /// existing metadata object (non-object metadata is replaced).
+    fn link_duplicate(mut thought: StoredThought, original_id: &str) -> StoredThought {
+        let mut metadata = thought
+            .metadata
+            .as_deref()
+            .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+            .filter(serde_json::Value::is_object)
+            .unwrap_or_else(|| serde_json::json!({}));
+        metadata["duplicate_of"] = serde_json::Value::String(original_id.to_string());
+        thought.metadata = Some(metadata.to_string());
+        thought
+    }
+
+    /// Replace a thought's JSON metadata. No-op if the thought does not
+    /// exist (e.g. its write was skipped by dedup).
+    pub async fn set_thought_metadata(
+        &self,
+        thought_id: &str,
+        metadata: &str,
+    ) -> Result<(), StorageError> {
+        sqlx::query(UPDATE_THOUGHT_METADATA)
+            .bind(metadata)
+            .bind(thought_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Self::query_error("UPDATE thoughts", format!("{e}")))?;
+        Ok(())
+    }
+
     /// Get a stored thought by ID.
     pub async fn get_stored_thought(
         &self,
@@ -95,6 +386,45 @@ impl SqliteStorage {
         Ok(thoughts)
     }
 
+    /// Aggregate stored thought confidence by mode, strongest first.
+    ///
+    /// `fts_query` is raw caller text, not FTS5 syntax: it is reduced to its
+    /// alphanumeric words OR-ed together, so punctuation can never break the
+    /// match expression. A query with no usable words (or none at all)
+    /// aggregates every thought.
+    pub async fn mode_confidence_stats(
+        &self,
+        fts_query: Option<&str>,
+    ) -> Result<Vec<crate::traits::ModeConfidenceStat>, StorageError> {
+        let match_expr = fts_query.map(sanitize_fts_query).filter(|q| !q.is_empty());
+        let rows = match match_expr {
+            Some(expr) => sqlx::query(SELECT_MODE_CONFIDENCE_FTS)
+                .bind(expr)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| Self::query_error("SELECT thoughts_fts", format!("{e}")))?,
+            None => sqlx::query(SELECT_MODE_CONFIDENCE)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| Self::query_error("SELECT thoughts", format!("{e}")))?,
+        };
+
+        rows.iter()
+            .map(|row| {
+                let mode: String = row.get("mode");
+                let avg_confidence: f64 = row.get("avg_confidence");
+                let samples: i64 = row.get("samples");
+                let samples = u32::try_from(samples)
+                    .map_err(|e| Self::query_error("SELECT thoughts samples", format!("{e}")))?;
+                Ok(crate::traits::ModeConfidenceStat::new(
+                    mode,
+                    avg_confidence,
+                    samples,
+                ))
+            })
+            .collect()
+    }
+
     /// Convert a database row to a `StoredThought`.
     fn row_to_stored_thought(row: &sqlx::sqlite::SqliteRow) -> Result<StoredThought, StorageError> {
         let id: String = row.get("id");
@@ -105,6 +435,7 @@ impl SqliteStorage {
         let confidence: f64 = row.get("confidence");
         let metadata: Option<String> = row.get("metadata");
         let created_at_str: String = row.get("created_at");
+        let sequence: Option<i64> = row.get("sequence");
 
         let created_at = Self::parse_datetime(&created_at_str)?;
 
@@ -117,6 +448,9 @@ impl SqliteStorage {
         if let Some(m) = metadata {
             thought = thought.with_metadata(m);
         }
+        if let Some(s) = sequence {
+            thought = thought.with_sequence(s);
+        }
 
         Ok(thought)
     }
@@ -134,6 +468,7 @@ impl SqliteStorage {
 mod tests {
     use super::*;
     use crate::storage::core::tests::test_storage;
+    use crate::storage::types::{ThoughtAutoTagConfig, ThoughtDedupConfig, ThoughtSamplingConfig};
     use serial_test::serial;
 
     #[tokio::test]
@@ -151,6 +486,36 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_set_thought_metadata() {
+        let storage = test_storage().await;
+        storage
+            .create_session_with_id("sess-123")
+            .await
+            .expect("create session");
+
+        let thought = StoredThought::new("t-1", "sess-123", "linear", "Test content", 0.85);
+        storage.save_stored_thought(&thought).await.expect("save");
+
+        storage
+            .set_thought_metadata("t-1", r#"{"key": "value"}"#)
+            .await
+            .expect("set metadata");
+        let fetched = storage
+            .get_stored_thought("t-1")
+            .await
+            .expect("fetch")
+            .expect("thought exists");
+        assert_eq!(fetched.metadata.as_deref(), Some(r#"{"key": "value"}"#));
+
+        // Updating a missing thought is a quiet no-op.
+        storage
+            .set_thought_metadata("t-missing", "{}")
+            .await
+            .expect("no-op update");
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_get_stored_thought() {
@@ -297,4 +662,598 @@ mod tests {
         assert_eq!(fetched.id, "t-meta");
         assert_eq!(fetched.metadata, Some(r#"{"key":"value"}"#.to_string()));
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_delete_last_thought_removes_most_recent() {
+        let storage = test_storage().await;
+        storage
+            .create_session_with_id("sess-undo")
+            .await
+            .expect("create session");
+
+        let base = chrono::Utc::now();
+        for (i, id) in [(1_i64, "t-1"), (2, "t-2"), (3, "t-3")] {
+            let thought =
+                StoredThought::new(id, "sess-undo", "linear", format!("Thought {i}"), 0.8)
+                    .with_timestamp(base + chrono::Duration::seconds(i));
+            storage.save_stored_thought(&thought).await.expect("save");
+        }
+
+        let removed = storage
+            .delete_last_thought("sess-undo")
+            .await
+            .expect("undo")
+            .expect("thought removed");
+        assert_eq!(removed.id, "t-3");
+
+        let remaining = storage
+            .get_stored_thoughts("sess-undo")
+            .await
+            .expect("thoughts");
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining.iter().all(|t| t.id != "t-3"));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_concurrent_saves_get_contiguous_stable_sequence() {
+        let storage = test_storage().await;
+        storage
+            .create_session_with_id("sess-seq")
+            .await
+            .expect("create session");
+
+        // Identical timestamps: ordering by created_at alone would be unstable.
+        let ts = chrono::Utc::now();
+        let mut handles = Vec::new();
+        for i in 0..10 {
+            let storage = storage.clone();
+            let thought = StoredThought::new(
+                format!("t-{i}"),
+                "sess-seq",
+                "linear",
+                format!("Thought number {i}"),
+                0.8,
+            )
+            .with_timestamp(ts);
+            handles.push(tokio::spawn(async move {
+                storage.save_stored_thought(&thought).await
+            }));
+        }
+        for handle in handles {
+            handle.await.expect("join").expect("save");
+        }
+
+        let thoughts = storage
+            .get_stored_thoughts("sess-seq")
+            .await
+            .expect("thoughts");
+        assert_eq!(thoughts.len(), 10);
+
+        // Contiguous, gap-free, 1-based.
+        let sequences: Vec<i64> = thoughts
+            .iter()
+            .map(|t| t.sequence.expect("sequence assigned at save"))
+            .collect();
+        assert_eq!(sequences, (1..=10).collect::<Vec<i64>>());
+
+        // Stable: a second read returns the same order.
+        let again = storage
+            .get_stored_thoughts("sess-seq")
+            .await
+            .expect("thoughts");
+        let ids: Vec<&str> = thoughts.iter().map(|t| t.id.as_str()).collect();
+        let ids_again: Vec<&str> = again.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, ids_again);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_sequence_is_per_session() {
+        let storage = test_storage().await;
+        for session in ["sess-a", "sess-b"] {
+            storage
+                .create_session_with_id(session)
+                .await
+                .expect("create session");
+            for i in 0..3 {
+                let thought = StoredThought::new(
+                    format!("{session}-t-{i}"),
+                    session,
+                    "linear",
+                    format!("Thought number {i}"),
+                    0.8,
+                );
+                storage.save_stored_thought(&thought).await.expect("save");
+            }
+        }
+
+        for session in ["sess-a", "sess-b"] {
+            let sequences: Vec<i64> = storage
+                .get_stored_thoughts(session)
+                .await
+                .expect("thoughts")
+                .iter()
+                .map(|t| t.sequence.expect("sequence assigned"))
+                .collect();
+            assert_eq!(sequences, vec![1, 2, 3]);
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_migration_backfills_sequence_from_timestamp() {
+        let storage = test_storage().await;
+        storage
+            .create_session_with_id("sess-legacy")
+            .await
+            .expect("create session");
+
+        // Simulate pre-migration rows: inserted without a sequence, out of
+        // timestamp order.
+        let base = chrono::Utc::now();
+        let legacy_insert = "INSERT INTO thoughts (id, session_id, mode, content, confidence, created_at) VALUES (?, 'sess-legacy', 'linear', 'legacy', 0.8, ?)";
+        for (id, offset) in [("t-late", 10_i64), ("t-early", 0), ("t-mid", 5)] {
+            sqlx::query(legacy_insert)
+                .bind(id)
+                .bind((base + chrono::Duration::seconds(offset)).to_rfc3339())
+                .execute(&storage.pool)
+                .await
+                .expect("insert legacy row");
+        }
+
+        // Re-running migrations backfills only the NULL sequences.
+        storage.run_migrations().await.expect("migrations");
+
+        let thoughts = storage
+            .get_stored_thoughts("sess-legacy")
+            .await
+            .expect("thoughts");
+        let ordered: Vec<(&str, i64)> = thoughts
+            .iter()
+            .map(|t| (t.id.as_str(), t.sequence.expect("backfilled")))
+            .collect();
+        assert_eq!(ordered, vec![("t-early", 1), ("t-mid", 2), ("t-late", 3)]);
+
+        // A new save continues the sequence after the backfilled rows.
+        let next = StoredThought::new("t-next", "sess-legacy", "linear", "new row", 0.8);
+        storage.save_stored_thought(&next).await.expect("save");
+        let last = storage
+            .get_last_thought("sess-legacy")
+            .await
+            .expect("last")
+            .expect("present");
+        assert_eq!(last.id, "t-next");
+        assert_eq!(last.sequence, Some(4));
+    }
+
+    #[test]
+    fn test_content_similarity() {
+        assert!((content_similarity("The answer is 42", "the  answer is 42") - 1.0).abs() < 1e-9);
+        assert_eq!(content_similarity("alpha beta gamma", "delta epsilon"), 0.0);
+        assert_eq!(content_similarity("", "anything"), 0.0);
+
+        // Three of four words shared -> 3/5 Jaccard.
+        let sim = content_similarity("one two three four", "one two three five");
+        assert!((sim - 0.6).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_dedup_skip_keeps_single_row() {
+        let storage = test_storage()
+            .await
+            .with_thought_dedup(ThoughtDedupConfig::new(ThoughtDedupStrategy::Skip));
+        storage
+            .create_session_with_id("sess-dedup")
+            .await
+            .expect("create session");
+
+        let first = StoredThought::new("t-1", "sess-dedup", "linear", "The answer is 42", 0.8);
+        let near = StoredThought::new("t-2", "sess-dedup", "linear", "the  Answer is 42", 0.9);
+        storage.save_stored_thought(&first).await.expect("save 1");
+        storage.save_stored_thought(&near).await.expect("save 2");
+
+        let thoughts = storage
+            .get_stored_thoughts("sess-dedup")
+            .await
+            .expect("thoughts");
+        assert_eq!(thoughts.len(), 1);
+        assert_eq!(thoughts[0].id, "t-1");
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_dedup_link_saves_both_with_duplicate_of() {
+        let storage = test_storage()
+            .await
+            .with_thought_dedup(ThoughtDedupConfig::new(ThoughtDedupStrategy::Link));
+        storage
+            .create_session_with_id("sess-link")
+            .await
+            .expect("create session");
+
+        let first = StoredThought::new("t-1", "sess-link", "linear", "The answer is 42", 0.8);
+        let near = StoredThought::new("t-2", "sess-link", "linear", "the  Answer is 42", 0.9);
+        storage.save_stored_thought(&first).await.expect("save 1");
+        storage.save_stored_thought(&near).await.expect("save 2");
+
+        let thoughts = storage
+            .get_stored_thoughts("sess-link")
+            .await
+            .expect("thoughts");
+        assert_eq!(thoughts.len(), 2);
+
+        let linked = thoughts.iter().find(|t| t.id == "t-2").expect("t-2 saved");
+        let metadata: serde_json::Value =
+            serde_json::from_str(linked.metadata.as_deref().expect("metadata set"))
+                .expect("valid JSON");
+        assert_eq!(metadata["duplicate_of"], "t-1");
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_dedup_leaves_dissimilar_thoughts_alone() {
+        let storage = test_storage()
+            .await
+            .with_thought_dedup(ThoughtDedupConfig::new(ThoughtDedupStrategy::Skip));
+        storage
+            .create_session_with_id("sess-distinct")
+            .await
+            .expect("create session");
+
+        let first = StoredThought::new("t-1", "sess-distinct", "linear", "The answer is 42", 0.8);
+        let other = StoredThought::new(
+            "t-2",
+            "sess-distinct",
+            "linear",
+            "A completely different line of reasoning",
+            0.9,
+        );
+        storage.save_stored_thought(&first).await.expect("save 1");
+        storage.save_stored_thought(&other).await.expect("save 2");
+
+        let thoughts = storage
+            .get_stored_thoughts("sess-distinct")
+            .await
+            .expect("thoughts");
+        assert_eq!(thoughts.len(), 2);
+        assert!(thoughts.iter().all(|t| t.metadata.is_none()));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_auto_tag_attaches_mode_tag() {
+        let storage = test_storage()
+            .await
+            .with_auto_tagging(ThoughtAutoTagConfig::new());
+        storage
+            .create_session_with_id("sess-tag")
+            .await
+            .expect("create session");
+
+        let thought = StoredThought::new("t-1", "sess-tag", "linear", "plain prose", 0.8);
+        storage.save_stored_thought(&thought).await.expect("save");
+
+        let saved = storage
+            .get_stored_thought("t-1")
+            .await
+            .expect("fetch")
+            .expect("thought exists");
+        let metadata: serde_json::Value =
+            serde_json::from_str(saved.metadata.as_deref().expect("metadata set"))
+                .expect("valid JSON");
+        assert_eq!(metadata["tags"], serde_json::json!(["mode:linear"]));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_auto_tag_attaches_keyword_topics() {
+        let storage = test_storage()
+            .await
+            .with_auto_tagging(ThoughtAutoTagConfig::new());
+        storage
+            .create_session_with_id("sess-tag-kw")
+            .await
+            .expect("create session");
+
+        let thought = StoredThought::new(
+            "t-1",
+            "sess-tag-kw",
+            "tree",
+            "Add an index to the database schema",
+            0.8,
+        );
+        storage.save_stored_thought(&thought).await.expect("save");
+
+        let saved = storage
+            .get_stored_thought("t-1")
+            .await
+            .expect("fetch")
+            .expect("thought exists");
+        let metadata: serde_json::Value =
+            serde_json::from_str(saved.metadata.as_deref().expect("metadata set"))
+                .expect("valid JSON");
+        assert_eq!(
+            metadata["tags"],
+            serde_json::json!(["mode:tree", "topic:database"])
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_auto_tag_llm_topics_via_client() {
+        let client = crate::test_utils::mock_anthropic_success(r#"["caching"]"#, 10, 5);
+        let storage = test_storage()
+            .await
+            .with_auto_tagging(ThoughtAutoTagConfig { llm_topics: true })
+            .with_topic_tag_client(std::sync::Arc::new(client));
+        storage
+            .create_session_with_id("sess-tag-llm")
+            .await
+            .expect("create session");
+
+        let thought = StoredThought::new("t-1", "sess-tag-llm", "linear", "plain prose", 0.8);
+        storage.save_stored_thought(&thought).await.expect("save");
+
+        let saved = storage
+            .get_stored_thought("t-1")
+            .await
+            .expect("fetch")
+            .expect("thought exists");
+        let metadata: serde_json::Value =
+            serde_json::from_str(saved.metadata.as_deref().expect("metadata set"))
+                .expect("valid JSON");
+        assert_eq!(
+            metadata["tags"],
+            serde_json::json!(["mode:linear", "topic:caching"])
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_auto_tag_disabled_leaves_metadata_untouched() {
+        let storage = test_storage().await;
+        storage
+            .create_session_with_id("sess-untagged")
+            .await
+            .expect("create session");
+
+        let thought = StoredThought::new(
+            "t-1",
+            "sess-untagged",
+            "linear",
+            "Add an index to the database schema",
+            0.8,
+        );
+        storage.save_stored_thought(&thought).await.expect("save");
+
+        let saved = storage
+            .get_stored_thought("t-1")
+            .await
+            .expect("fetch")
+            .expect("thought exists");
+        assert!(saved.metadata.is_none());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_delete_last_thought_empty_session() {
+        let storage = test_storage().await;
+        storage
+            .create_session_with_id("sess-empty")
+            .await
+            .expect("create session");
+
+        let removed = storage
+            .delete_last_thought("sess-empty")
+            .await
+            .expect("undo");
+        assert!(removed.is_none());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_delete_last_thought_cascades_graph_artifacts() {
+        let storage = test_storage().await;
+        storage
+            .create_session_with_id("sess-cascade")
+            .await
+            .expect("create session");
+
+        let base = chrono::Utc::now();
+        let earlier = StoredThought::new("t-1", "sess-cascade", "graph", "Init", 0.8)
+            .with_timestamp(base - chrono::Duration::seconds(10));
+        let last = StoredThought::new("t-2", "sess-cascade", "graph", "Generate", 0.8)
+            .with_timestamp(base);
+        storage.save_stored_thought(&earlier).await.expect("save");
+        storage.save_stored_thought(&last).await.expect("save");
+
+        // Graph rows inserted directly so their timestamps place one before
+        // the last thought (survives) and one at/after it (cascaded).
+        let insert_node = "INSERT INTO graph_nodes (id, session_id, content, node_type, score, is_terminal, created_at) VALUES (?, ?, ?, 'thought', 0.5, 0, ?)";
+        for (id, ts) in [
+            ("n-old", base - chrono::Duration::seconds(10)),
+            ("n-new", base),
+        ] {
+            sqlx::query(insert_node)
+                .bind(id)
+                .bind("sess-cascade")
+                .bind("content")
+                .bind(ts.to_rfc3339())
+                .execute(&storage.pool)
+                .await
+                .expect("insert node");
+        }
+        sqlx::query("INSERT INTO graph_edges (id, session_id, from_node_id, to_node_id, edge_type, created_at) VALUES (?, 'sess-cascade', 'n-old', 'n-new', 'continues', ?)")
+            .bind("e-new")
+            .bind(base.to_rfc3339())
+            .execute(&storage.pool)
+            .await
+            .expect("insert edge");
+
+        let removed = storage
+            .delete_last_thought("sess-cascade")
+            .await
+            .expect("undo")
+            .expect("thought removed");
+        assert_eq!(removed.id, "t-2");
+
+        let nodes = storage
+            .get_graph_nodes("sess-cascade")
+            .await
+            .expect("nodes");
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].id, "n-old");
+        let edges = storage
+            .get_graph_edges("sess-cascade")
+            .await
+            .expect("edges");
+        assert!(edges.is_empty());
+    }
+
+    #[test]
+    fn test_sanitize_fts_query_strips_operators() {
+        assert_eq!(
+            sanitize_fts_query("compare \"Databases\" AND (designs)!"),
+            "compare OR databases OR and OR designs"
+        );
+        assert_eq!(sanitize_fts_query("  ...  "), "");
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_mode_confidence_stats_aggregates_strongest_first() {
+        let storage = test_storage().await;
+        storage
+            .create_session_with_id("sess-stats")
+            .await
+            .expect("create session");
+
+        for (id, mode, confidence) in [
+            ("t-1", "tree", 0.9),
+            ("t-2", "tree", 0.8),
+            ("t-3", "linear", 0.5),
+            ("t-4", "linear", 0.7),
+        ] {
+            let thought = StoredThought::new(id, "sess-stats", mode, "Database design", confidence);
+            storage.save_stored_thought(&thought).await.expect("save");
+        }
+
+        let stats = storage.mode_confidence_stats(None).await.expect("stats");
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].mode, "tree");
+        assert!((stats[0].avg_confidence - 0.85).abs() < 1e-9);
+        assert_eq!(stats[0].samples, 2);
+        assert_eq!(stats[1].mode, "linear");
+        assert!((stats[1].avg_confidence - 0.6).abs() < 1e-9);
+        assert_eq!(stats[1].samples, 2);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_mode_confidence_stats_fts_filters_by_content() {
+        let storage = test_storage().await;
+        storage
+            .create_session_with_id("sess-fts")
+            .await
+            .expect("create session");
+
+        let matching =
+            StoredThought::new("t-1", "sess-fts", "tree", "Compare database schemas", 0.9);
+        let unrelated = StoredThought::new("t-2", "sess-fts", "linear", "Plan the sprint", 0.5);
+        storage.save_stored_thought(&matching).await.expect("save");
+        storage.save_stored_thought(&unrelated).await.expect("save");
+
+        // Punctuation-heavy raw text is sanitized, not passed through as FTS5
+        // syntax.
+        let stats = storage
+            .mode_confidence_stats(Some("which database (schemas)?"))
+            .await
+            .expect("stats");
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].mode, "tree");
+        assert_eq!(stats[0].samples, 1);
+
+        // No usable words falls back to the unfiltered aggregate.
+        let stats = storage
+            .mode_confidence_stats(Some("!?!"))
+            .await
+            .expect("stats");
+        assert_eq!(stats.len(), 2);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_sampling_rate_zero_keeps_only_important_thoughts() {
+        let storage = test_storage()
+            .await
+            .with_thought_sampling(ThoughtSamplingConfig::new(0.0));
+        storage
+            .create_session_with_id("sess-123")
+            .await
+            .expect("create session");
+
+        // Important: high confidence, and a terminal-operation mode.
+        let confident = StoredThought::new("t-conf", "sess-123", "linear", "Result", 0.95);
+        let terminal = StoredThought::new("t-term", "sess-123", "tree_complete", "Done", 0.2);
+        // Ordinary: low confidence, non-terminal mode.
+        let ordinary = StoredThought::new("t-ord", "sess-123", "linear", "Step", 0.3);
+
+        storage.save_stored_thought(&confident).await.expect("save");
+        storage.save_stored_thought(&terminal).await.expect("save");
+        storage.save_stored_thought(&ordinary).await.expect("save");
+
+        assert!(storage
+            .get_stored_thought("t-conf")
+            .await
+            .expect("fetch")
+            .is_some());
+        assert!(storage
+            .get_stored_thought("t-term")
+            .await
+            .expect("fetch")
+            .is_some());
+        assert!(storage
+            .get_stored_thought("t-ord")
+            .await
+            .expect("fetch")
+            .is_none());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_sampling_persists_approximately_the_configured_fraction() {
+        let storage = test_storage()
+            .await
+            .with_thought_sampling(ThoughtSamplingConfig::new(0.5));
+        storage
+            .create_session_with_id("sess-123")
+            .await
+            .expect("create session");
+
+        let batch = 400;
+        for i in 0..batch {
+            let thought = StoredThought::new(
+                uuid::Uuid::new_v4().to_string(),
+                "sess-123",
+                "linear",
+                format!("Step {i}"),
+                0.3,
+            );
+            storage.save_stored_thought(&thought).await.expect("save");
+        }
+
+        let persisted = storage
+            .get_stored_thoughts("sess-123")
+            .await
+            .expect("fetch")
+            .len();
+        // ~50% of 400; a generous band keeps this robust to hash variance.
+        assert!(
+            (140..=260).contains(&persisted),
+            "expected roughly half of {batch} persisted, got {persisted}"
+        );
+    }
 }
@@ -11,7 +11,7 @@ use crate::traits::{Session, StorageTrait, StoredBranchStatus, Thought};
 
 use super::core::SqliteStorage;
 use super::types::{
-    StoredBranch, StoredCheckpoint, StoredGraphEdge, StoredGraphNode, StoredThought,
+    StoredBranch, StoredCheckpoint, StoredGraphEdge, StoredGraphNode, StoredRawIo, StoredThought,
 };
 
 /// Emit a `SQLite` activity event (dashboard) for a request-path read/write.
@@ -28,6 +28,15 @@ fn emit_sqlite(session_id: &str, note: &str) {
     );
 }
 
+/// Extract the recorded prompt version from a thought's metadata JSON.
+/// Absent or unreadable metadata yields `None`.
+fn prompt_version_from_metadata(metadata: Option<&str>) -> Option<String> {
+    let json: serde_json::Value = serde_json::from_str(metadata?).ok()?;
+    json.get("prompt_version")
+        .and_then(serde_json::Value::as_str)
+        .map(String::from)
+}
+
 #[async_trait]
 impl StorageTrait for SqliteStorage {
     async fn get_session(&self, id: &str) -> Result<Option<Session>, StorageError> {
@@ -50,8 +59,20 @@ impl StorageTrait for SqliteStorage {
         Ok(session)
     }
 
+    async fn get_working_memory(&self, session_id: &str) -> Result<Option<String>, StorageError> {
+        Self::get_working_memory(self, session_id).await
+    }
+
+    async fn set_working_memory(&self, session_id: &str, memory: &str) -> Result<(), StorageError> {
+        Self::set_working_memory(self, session_id, memory).await
+    }
+
+    async fn merge_sessions(&self, target_id: &str, source_id: &str) -> Result<(), StorageError> {
+        Self::merge_sessions(self, target_id, source_id).await
+    }
+
     async fn save_thought(&self, thought: &Thought) -> Result<(), StorageError> {
-        let stored = StoredThought::new(
+        let mut stored = StoredThought::new(
             &thought.id,
             &thought.session_id,
             &thought.mode,
@@ -59,6 +80,12 @@ impl StorageTrait for SqliteStorage {
             thought.confidence,
         )
         .with_timestamp(thought.created_at);
+        // The applied prompt version rides in the metadata JSON, so recording
+        // it needs no schema change.
+        if let Some(version) = &thought.prompt_version {
+            stored =
+                stored.with_metadata(serde_json::json!({ "prompt_version": version }).to_string());
+        }
 
         self.save_stored_thought(&stored).await?;
         emit_sqlite(&thought.session_id, "persist");
@@ -79,14 +106,41 @@ impl StorageTrait for SqliteStorage {
                     s.confidence,
                     s.created_at,
                 )
+                .with_prompt_version(prompt_version_from_metadata(s.metadata.as_deref()))
             })
             .collect())
     }
 
+    async fn mode_confidence_stats(
+        &self,
+        fts_query: Option<String>,
+    ) -> Result<Vec<crate::traits::ModeConfidenceStat>, StorageError> {
+        Self::mode_confidence_stats(self, fts_query.as_deref()).await
+    }
+
+    async fn delete_last_thought(&self, session_id: &str) -> Result<Option<Thought>, StorageError> {
+        let removed = Self::delete_last_thought(self, session_id).await?;
+        Ok(removed.map(|s| {
+            Thought::with_timestamp(
+                &s.id,
+                &s.session_id,
+                &s.content,
+                &s.mode,
+                s.confidence,
+                s.created_at,
+            )
+            .with_prompt_version(prompt_version_from_metadata(s.metadata.as_deref()))
+        }))
+    }
+
     async fn delete_thought(&self, id: &str) -> Result<(), StorageError> {
         Self::delete_thought(self, id).await
     }
 
+    async fn save_thought_raw_io(&self, raw_io: &StoredRawIo) -> Result<(), StorageError> {
+        Self::save_thought_raw_io(self, raw_io).await
+    }
+
     async fn save_checkpoint(&self, checkpoint: &StoredCheckpoint) -> Result<(), StorageError> {
         Self::save_checkpoint(self, checkpoint).await
     }
@@ -145,6 +199,26 @@ impl StorageTrait for SqliteStorage {
         Self::save_graph_edge(self, edge).await
     }
 
+    async fn save_graph_batch(
+        &self,
+        thought: &Thought,
+        nodes: &[StoredGraphNode],
+        edges: &[StoredGraphEdge],
+    ) -> Result<(), StorageError> {
+        let stored = StoredThought::new(
+            &thought.id,
+            &thought.session_id,
+            &thought.mode,
+            &thought.content,
+            thought.confidence,
+        )
+        .with_timestamp(thought.created_at);
+
+        Self::save_graph_batch(self, &stored, nodes, edges).await?;
+        emit_sqlite(&thought.session_id, "persist");
+        Ok(())
+    }
+
     async fn get_graph_edges(
         &self,
         session_id: &str,
@@ -159,6 +233,10 @@ impl StorageTrait for SqliteStorage {
     async fn delete_graph_node(&self, id: &str) -> Result<(), StorageError> {
         Self::delete_graph_node(self, id).await
     }
+
+    async fn delete_graph_edge(&self, id: &str) -> Result<(), StorageError> {
+        Self::delete_graph_edge(self, id).await
+    }
 }
 
 /// Blanket implementation for `Arc<SqliteStorage>` to allow sharing storage across threads.
@@ -172,6 +250,18 @@ impl StorageTrait for Arc<SqliteStorage> {
         self.as_ref().get_or_create_session(id).await
     }
 
+    async fn get_working_memory(&self, session_id: &str) -> Result<Option<String>, StorageError> {
+        StorageTrait::get_working_memory(self.as_ref(), session_id).await
+    }
+
+    async fn set_working_memory(&self, session_id: &str, memory: &str) -> Result<(), StorageError> {
+        StorageTrait::set_working_memory(self.as_ref(), session_id, memory).await
+    }
+
+    async fn merge_sessions(&self, target_id: &str, source_id: &str) -> Result<(), StorageError> {
+        StorageTrait::merge_sessions(self.as_ref(), target_id, source_id).await
+    }
+
     async fn save_thought(&self, thought: &Thought) -> Result<(), StorageError> {
         self.as_ref().save_thought(thought).await
     }
@@ -180,10 +270,25 @@ impl StorageTrait for Arc<SqliteStorage> {
         self.as_ref().get_thoughts(session_id).await
     }
 
+    async fn mode_confidence_stats(
+        &self,
+        fts_query: Option<String>,
+    ) -> Result<Vec<crate::traits::ModeConfidenceStat>, StorageError> {
+        StorageTrait::mode_confidence_stats(self.as_ref(), fts_query).await
+    }
+
     async fn delete_thought(&self, id: &str) -> Result<(), StorageError> {
         self.as_ref().delete_thought(id).await
     }
 
+    async fn delete_last_thought(&self, session_id: &str) -> Result<Option<Thought>, StorageError> {
+        StorageTrait::delete_last_thought(self.as_ref(), session_id).await
+    }
+
+    async fn save_thought_raw_io(&self, raw_io: &StoredRawIo) -> Result<(), StorageError> {
+        self.as_ref().save_thought_raw_io(raw_io).await
+    }
+
     async fn save_checkpoint(&self, checkpoint: &StoredCheckpoint) -> Result<(), StorageError> {
         self.as_ref().save_checkpoint(checkpoint).await
     }
@@ -242,6 +347,15 @@ impl StorageTrait for Arc<SqliteStorage> {
         self.as_ref().save_graph_edge(edge).await
     }
 
+    async fn save_graph_batch(
+        &self,
+        thought: &Thought,
+        nodes: &[StoredGraphNode],
+        edges: &[StoredGraphEdge],
+    ) -> Result<(), StorageError> {
+        StorageTrait::save_graph_batch(self.as_ref(), thought, nodes, edges).await
+    }
+
     async fn get_graph_edges(
         &self,
         session_id: &str,
@@ -256,6 +370,10 @@ impl StorageTrait for Arc<SqliteStorage> {
     async fn delete_graph_node(&self, id: &str) -> Result<(), StorageError> {
         self.as_ref().delete_graph_node(id).await
     }
+
+    async fn delete_graph_edge(&self, id: &str) -> Result<(), StorageError> {
+        self.as_ref().delete_graph_edge(id).await
+    }
 }
 
 #[cfg(test)]
@@ -15,6 +15,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::error::ConfigError;
+
 /// Session stored in database.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StoredSession {
@@ -83,6 +85,10 @@ pub struct StoredThought {
     pub metadata: Option<String>,
     /// Creation timestamp.
     pub created_at: DateTime<Utc>,
+    /// Monotonic per-session position, assigned by the database at save time.
+    /// `None` on a thought that has not been saved yet; always set on reads.
+    #[serde(default)]
+    pub sequence: Option<i64>,
 }
 
 impl StoredThought {
@@ -104,6 +110,7 @@ impl StoredThought {
             confidence,
             metadata: None,
             created_at: Utc::now(),
+            sequence: None,
         }
     }
 
@@ -114,6 +121,13 @@ impl StoredThought {
         self
     }
 
+    /// Set the per-session sequence number (used when reading stored rows).
+    #[must_use]
+    pub const fn with_sequence(mut self, sequence: i64) -> Self {
+        self.sequence = Some(sequence);
+        self
+    }
+
     /// Set metadata.
     #[must_use]
     pub fn with_metadata(mut self, metadata: impl Into<String>) -> Self {
@@ -129,6 +143,338 @@ impl StoredThought {
     }
 }
 
+/// What to do with a thought whose content near-duplicates an existing one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThoughtDedupStrategy {
+    /// Do not create a new row; the existing thought stands.
+    Skip,
+    /// Create the row but record `duplicate_of` in its metadata.
+    Link,
+}
+
+/// Default content similarity above which a thought counts as a duplicate.
+pub const DEFAULT_THOUGHT_DEDUP_THRESHOLD: f64 = 0.9;
+
+/// Configuration for deduplicating near-identical thoughts on save.
+///
+/// Off by default: dedup runs only when this is attached to the storage via
+/// [`SqliteStorage::with_thought_dedup`](crate::storage::SqliteStorage::with_thought_dedup).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThoughtDedupConfig {
+    /// Content similarity (0.0–1.0) at or above which a thought is a duplicate.
+    pub threshold: f64,
+    /// Whether a duplicate is skipped or saved with a link to the original.
+    pub strategy: ThoughtDedupStrategy,
+}
+
+impl ThoughtDedupConfig {
+    /// Create a dedup config with the given strategy and the default threshold.
+    #[must_use]
+    pub const fn new(strategy: ThoughtDedupStrategy) -> Self {
+        Self {
+            threshold: DEFAULT_THOUGHT_DEDUP_THRESHOLD,
+            strategy,
+        }
+    }
+
+    /// Override the similarity threshold (clamped to [0, 1]).
+    #[must_use]
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Build from the environment, if configured.
+    ///
+    /// Reads `THOUGHT_DEDUP` (`skip` or `link`; unset or anything else leaves
+    /// dedup off) and `THOUGHT_DEDUP_THRESHOLD` (default 0.9).
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        let strategy = match std::env::var("THOUGHT_DEDUP").ok()?.to_lowercase().as_str() {
+            "skip" => ThoughtDedupStrategy::Skip,
+            "link" => ThoughtDedupStrategy::Link,
+            _ => return None,
+        };
+        let mut config = Self::new(strategy);
+        if let Some(threshold) = std::env::var("THOUGHT_DEDUP_THRESHOLD")
+            .ok()
+            .and_then(|v| v.parse::<f64>().ok())
+        {
+            config = config.with_threshold(threshold);
+        }
+        Some(config)
+    }
+}
+
+/// Confidence at or above which a thought is always persisted under sampling.
+pub const IMPORTANT_THOUGHT_CONFIDENCE: f64 = 0.8;
+
+/// Configuration for sampling thought persistence on save.
+///
+/// Off by default: sampling runs only when this is attached to the storage via
+/// [`SqliteStorage::with_thought_sampling`](crate::storage::SqliteStorage::with_thought_sampling).
+/// High-throughput batch use may not want every intermediate thought stored;
+/// with sampling on, only `rate` of ordinary thoughts are persisted while
+/// important ones — terminal-operation modes and high-confidence results —
+/// are always kept.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThoughtSamplingConfig {
+    /// Fraction (0.0–1.0) of non-important thoughts that are persisted.
+    pub rate: f64,
+}
+
+impl ThoughtSamplingConfig {
+    /// Mode-name suffixes whose thoughts conclude a piece of work (checkpoint
+    /// state, branch completions, merges, summaries) and are never sampled
+    /// out.
+    const TERMINAL_MODE_SUFFIXES: &'static [&'static str] = &[
+        "checkpoint",
+        "complete",
+        "finalize",
+        "merge",
+        "restore",
+        "summarize",
+    ];
+
+    /// Create a sampling config with the given rate (clamped to [0, 1]).
+    #[must_use]
+    pub fn new(rate: f64) -> Self {
+        Self {
+            rate: rate.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Build from the environment, if configured.
+    ///
+    /// Reads `THOUGHT_SAMPLE_RATE` (fraction in [0, 1]). Unset, unparsable,
+    /// or a rate of 1.0+ leaves sampling off — everything is persisted, the
+    /// default.
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        let rate = std::env::var("THOUGHT_SAMPLE_RATE")
+            .ok()?
+            .parse::<f64>()
+            .ok()?;
+        (rate < 1.0).then(|| Self::new(rate))
+    }
+
+    /// Whether a thought is important enough to bypass sampling: its mode is
+    /// terminal (see [`Self::TERMINAL_MODE_SUFFIXES`]) or its confidence is at
+    /// least [`IMPORTANT_THOUGHT_CONFIDENCE`].
+    #[must_use]
+    pub fn is_important(thought: &StoredThought) -> bool {
+        thought.confidence >= IMPORTANT_THOUGHT_CONFIDENCE
+            || Self::TERMINAL_MODE_SUFFIXES
+                .iter()
+                .any(|suffix| thought.mode.ends_with(suffix))
+    }
+
+    /// Decide whether to persist a thought: important ones always, the rest
+    /// at `rate`. The decision hashes the thought id, so a retried write of
+    /// the same thought gets the same answer.
+    #[must_use]
+    pub fn should_persist(&self, thought: &StoredThought) -> bool {
+        use std::hash::{Hash, Hasher};
+        if Self::is_important(thought) {
+            return true;
+        }
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        thought.id.hash(&mut hasher);
+        let fraction = (hasher.finish() % 10_000) as f64 / 10_000.0;
+        fraction < self.rate
+    }
+}
+
+/// Configuration for automatically tagging thoughts on save.
+///
+/// Off by default: tagging runs only when this is attached to the storage via
+/// [`SqliteStorage::with_auto_tagging`](crate::storage::SqliteStorage::with_auto_tagging).
+/// Every saved thought gets a `mode:<mode>` tag; topic tags come from a local
+/// keyword table, or from an LLM call when `llm_topics` is set and a topic
+/// tag client is attached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThoughtAutoTagConfig {
+    /// Derive topic tags via an LLM call instead of the keyword table
+    /// (falls back to keywords on any failure). Opt-in due to per-save cost.
+    pub llm_topics: bool,
+}
+
+impl ThoughtAutoTagConfig {
+    /// Create an auto-tag config with keyword-based topics.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { llm_topics: false }
+    }
+
+    /// Build from the environment, if configured.
+    ///
+    /// Reads `AUTO_TAG_THOUGHTS` (`true` enables; unset or anything else
+    /// leaves tagging off) and `AUTO_TAG_LLM_TOPICS` (`true` switches topic
+    /// extraction to the LLM path).
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        if std::env::var("AUTO_TAG_THOUGHTS").ok()?.to_lowercase() != "true" {
+            return None;
+        }
+        let llm_topics =
+            std::env::var("AUTO_TAG_LLM_TOPICS").is_ok_and(|v| v.to_lowercase() == "true");
+        Some(Self { llm_topics })
+    }
+}
+
+impl Default for ThoughtAutoTagConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `SQLite` journal mode for [`StoragePragmas`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JournalMode {
+    /// Write-ahead logging (default): readers proceed alongside a writer.
+    #[default]
+    Wal,
+    /// Rollback journal deleted after each transaction.
+    Delete,
+    /// Rollback journal truncated instead of deleted.
+    Truncate,
+    /// Rollback journal header zeroed instead of deleted.
+    Persist,
+    /// Journal kept in memory: fastest, but a crash mid-transaction can
+    /// corrupt the database.
+    Memory,
+}
+
+impl std::str::FromStr for JournalMode {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "wal" => Ok(Self::Wal),
+            "delete" => Ok(Self::Delete),
+            "truncate" => Ok(Self::Truncate),
+            "persist" => Ok(Self::Persist),
+            "memory" => Ok(Self::Memory),
+            _ => Err(ConfigError::InvalidValue {
+                var: "SQLITE_JOURNAL_MODE".to_string(),
+                reason: format!(
+                    "unknown journal mode '{s}' — use wal (default; concurrent reads \
+                     during writes), delete, truncate, persist (rollback-journal \
+                     variants; writes serialize against reads), or memory (fastest, \
+                     but a crash mid-transaction can corrupt the database)"
+                ),
+            }),
+        }
+    }
+}
+
+/// `SQLite` synchronous level for [`StoragePragmas`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SynchronousMode {
+    /// No fsync: fastest, but an OS crash or power loss can corrupt the
+    /// database.
+    Off,
+    /// Fsync at critical moments (default): safe with WAL — a power loss can
+    /// lose the last commits but never corrupts the database.
+    #[default]
+    Normal,
+    /// Fsync on every commit: durable through power loss, slowest writes.
+    Full,
+    /// Like full, plus the directory is synced: maximum durability.
+    Extra,
+}
+
+impl std::str::FromStr for SynchronousMode {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "off" => Ok(Self::Off),
+            "normal" => Ok(Self::Normal),
+            "full" => Ok(Self::Full),
+            "extra" => Ok(Self::Extra),
+            _ => Err(ConfigError::InvalidValue {
+                var: "SQLITE_SYNCHRONOUS".to_string(),
+                reason: format!(
+                    "unknown synchronous level '{s}' — use off (fastest; an OS crash \
+                     or power loss can corrupt the database), normal (default; safe \
+                     with WAL, power loss can lose the last commits), full (durable \
+                     through power loss, slower writes), or extra (full plus \
+                     directory sync)"
+                ),
+            }),
+        }
+    }
+}
+
+/// `SQLite` pragmas applied at connection setup.
+///
+/// Defaults are the safe WAL + NORMAL combination the storage has always
+/// used; performance-sensitive deployments can tune journal mode,
+/// synchronous level, page cache, and memory-mapped I/O via the
+/// `SQLITE_*` environment variables (see [`Self::from_env`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StoragePragmas {
+    /// Journal mode (`PRAGMA journal_mode`).
+    pub journal_mode: JournalMode,
+    /// Synchronous level (`PRAGMA synchronous`).
+    pub synchronous: SynchronousMode,
+    /// Page cache size (`PRAGMA cache_size`): pages, or KiB when negative.
+    /// `None` keeps the `SQLite` default.
+    pub cache_size: Option<i64>,
+    /// Memory-mapped I/O limit in bytes (`PRAGMA mmap_size`). `None` keeps
+    /// mmap off.
+    pub mmap_size: Option<u64>,
+}
+
+impl StoragePragmas {
+    /// Create the default pragmas (WAL + NORMAL synchronous).
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            journal_mode: JournalMode::Wal,
+            synchronous: SynchronousMode::Normal,
+            cache_size: None,
+            mmap_size: None,
+        }
+    }
+
+    /// Build from the environment, validating every value.
+    ///
+    /// Reads `SQLITE_JOURNAL_MODE`, `SQLITE_SYNCHRONOUS`, `SQLITE_CACHE_SIZE`
+    /// (pages; negative values are KiB), and `SQLITE_MMAP_SIZE` (bytes);
+    /// unset variables keep their defaults.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::InvalidValue`] for any unparseable value, with
+    /// the durability tradeoffs of the valid choices spelled out — a typo
+    /// must fail at startup, not silently run with a weaker setting.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let mut pragmas = Self::new();
+        if let Ok(v) = std::env::var("SQLITE_JOURNAL_MODE") {
+            pragmas.journal_mode = v.parse()?;
+        }
+        if let Ok(v) = std::env::var("SQLITE_SYNCHRONOUS") {
+            pragmas.synchronous = v.parse()?;
+        }
+        if let Ok(v) = std::env::var("SQLITE_CACHE_SIZE") {
+            pragmas.cache_size = Some(v.parse().map_err(|_| ConfigError::InvalidValue {
+                var: "SQLITE_CACHE_SIZE".to_string(),
+                reason: format!("'{v}' is not an integer (pages; negative values are KiB)"),
+            })?);
+        }
+        if let Ok(v) = std::env::var("SQLITE_MMAP_SIZE") {
+            pragmas.mmap_size = Some(v.parse().map_err(|_| ConfigError::InvalidValue {
+                var: "SQLITE_MMAP_SIZE".to_string(),
+                reason: format!("'{v}' is not a non-negative integer (bytes)"),
+            })?);
+        }
+        Ok(pragmas)
+    }
+}
+
 /// Branch status for tree mode.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
@@ -226,6 +572,142 @@ impl StoredEmbedding {
     }
 }
 
+/// A persisted preset run (the `preset_runs` table).
+///
+/// `result_json` is the full `PresetResult` serialized to JSON; the row tags
+/// it with the preset id and a timestamp so `reasoning_preset_history` can
+/// list a preset's runs over time and diff two of them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredPresetRun {
+    /// Unique run identifier.
+    pub id: String,
+    /// Preset that was run (e.g. `code-review`).
+    pub preset_id: String,
+    /// Session the run executed in.
+    pub session_id: String,
+    /// The serialized `PresetResult`.
+    pub result_json: String,
+    /// When the run was recorded.
+    pub created_at: DateTime<Utc>,
+}
+
+impl StoredPresetRun {
+    /// Create a new preset run record, timestamped now.
+    #[must_use]
+    pub fn new(
+        id: impl Into<String>,
+        preset_id: impl Into<String>,
+        session_id: impl Into<String>,
+        result_json: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            preset_id: preset_id.into(),
+            session_id: session_id.into(),
+            result_json: result_json.into(),
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// A persisted auto-mode selection (the `auto_selections` table).
+///
+/// Recorded when `reasoning_auto` picks a mode: `confidence` is the router's
+/// confidence in its choice. `score` starts `None` and is backfilled with the
+/// confidence of the first thought the chosen mode later writes in the same
+/// session — how well following the suggestion actually went. Aggregated by
+/// `reasoning_auto_stats`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredAutoSelection {
+    /// Unique selection identifier.
+    pub id: String,
+    /// Session the selection was made in.
+    pub session_id: String,
+    /// Mode auto chose (e.g. `linear`, `tree`).
+    pub selected_mode: String,
+    /// The router's confidence in the choice (0.0-1.0).
+    pub confidence: f64,
+    /// Resulting score once the chosen mode ran, if it has.
+    pub score: Option<f64>,
+    /// When the selection was recorded.
+    pub created_at: DateTime<Utc>,
+}
+
+impl StoredAutoSelection {
+    /// Create a new unscored selection record, timestamped now.
+    #[must_use]
+    pub fn new(
+        id: impl Into<String>,
+        session_id: impl Into<String>,
+        selected_mode: impl Into<String>,
+        confidence: f64,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            session_id: session_id.into(),
+            selected_mode: selected_mode.into(),
+            confidence,
+            score: None,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Per-mode aggregate over [`StoredAutoSelection`] rows.
+///
+/// Produced by `auto_selection_stats`: how often auto picked each mode, how
+/// confident it was on average, and the average resulting score across the
+/// selections that have one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutoSelectionStat {
+    /// The chosen mode.
+    pub selected_mode: String,
+    /// How many times auto picked this mode.
+    pub selections: u32,
+    /// Average routing confidence across those selections.
+    pub avg_confidence: f64,
+    /// Average resulting score, over scored selections only.
+    pub avg_score: Option<f64>,
+    /// How many of the selections have a resulting score.
+    pub scored: u32,
+}
+
+/// A persisted raw API exchange for one thought (the `thought_raw_io` table).
+///
+/// Written only when `LINEAR_STORE_RAW_IO` is enabled: `messages_json` is the exact
+/// request message array the mode sent (as a JSON array of `{role, content}`
+/// objects) and `raw_completion` is the unparsed completion text, both
+/// redacted and size-capped before storage. Retrieved by
+/// `reasoning_inspect_thought`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredRawIo {
+    /// Thought this exchange produced.
+    pub thought_id: String,
+    /// The request messages, serialized to JSON.
+    pub messages_json: String,
+    /// The raw completion text, before any parsing.
+    pub raw_completion: String,
+    /// When the exchange was recorded.
+    pub created_at: DateTime<Utc>,
+}
+
+impl StoredRawIo {
+    /// Create a new raw IO record, timestamped now.
+    #[must_use]
+    pub fn new(
+        thought_id: impl Into<String>,
+        messages_json: impl Into<String>,
+        raw_completion: impl Into<String>,
+    ) -> Self {
+        Self {
+            thought_id: thought_id.into(),
+            messages_json: messages_json.into(),
+            raw_completion: raw_completion.into(),
+            created_at: Utc::now(),
+        }
+    }
+}
+
 impl StoredBranch {
     /// Create a new stored branch.
     #[must_use]
@@ -347,6 +829,52 @@ impl GraphNodeType {
     }
 }
 
+/// Operation that produced a graph node.
+///
+/// Recorded alongside the node so `state` and the DOT export can show how a
+/// node arose. Rows predating migration 018 have no origin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphNodeOrigin {
+    /// Root node created by `init`.
+    Init,
+    /// Child expanded from a parent by `generate`.
+    Generate,
+    /// Synthesis of multiple input nodes by `aggregate`.
+    Aggregate,
+    /// Improved version of a node produced by `refine`.
+    Refine,
+    /// Node supplied by an external graph via `import`.
+    Import,
+}
+
+impl GraphNodeOrigin {
+    /// Convert to string representation.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Init => "init",
+            Self::Generate => "generate",
+            Self::Aggregate => "aggregate",
+            Self::Refine => "refine",
+            Self::Import => "import",
+        }
+    }
+
+    /// Parse from string.
+    #[must_use]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "init" => Some(Self::Init),
+            "generate" => Some(Self::Generate),
+            "aggregate" => Some(Self::Aggregate),
+            "refine" => Some(Self::Refine),
+            "import" => Some(Self::Import),
+            _ => None,
+        }
+    }
+}
+
 /// Graph node stored in database (for `GoT` mode).
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StoredGraphNode {
@@ -364,6 +892,12 @@ pub struct StoredGraphNode {
     pub is_terminal: bool,
     /// Optional JSON metadata.
     pub metadata: Option<String>,
+    /// Operation that produced this node, when recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<GraphNodeOrigin>,
+    /// Parent node id(s) the producing operation consumed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parent_ids: Vec<String>,
     /// Creation timestamp.
     pub created_at: DateTime<Utc>,
 }
@@ -384,6 +918,8 @@ impl StoredGraphNode {
             score: None,
             is_terminal: false,
             metadata: None,
+            origin: None,
+            parent_ids: Vec::new(),
             created_at: Utc::now(),
         }
     }
@@ -415,6 +951,14 @@ impl StoredGraphNode {
         self.metadata = Some(metadata.into());
         self
     }
+
+    /// Set provenance: the producing operation and its parent node id(s).
+    #[must_use]
+    pub fn with_provenance(mut self, origin: GraphNodeOrigin, parent_ids: Vec<String>) -> Self {
+        self.origin = Some(origin);
+        self.parent_ids = parent_ids;
+        self
+    }
 }
 
 /// Graph edge type.
@@ -732,6 +1276,119 @@ pub struct StoredDiscoveredSkill {
 )]
 mod tests {
     use super::*;
+    use serial_test::serial;
+
+    // StoragePragmas Tests
+    #[test]
+    fn test_storage_pragmas_safe_defaults() {
+        let pragmas = StoragePragmas::new();
+        assert_eq!(pragmas.journal_mode, JournalMode::Wal);
+        assert_eq!(pragmas.synchronous, SynchronousMode::Normal);
+        assert_eq!(pragmas.cache_size, None);
+        assert_eq!(pragmas.mmap_size, None);
+        assert_eq!(pragmas, StoragePragmas::default());
+    }
+
+    #[test]
+    fn test_journal_mode_parse() {
+        assert_eq!("wal".parse::<JournalMode>().unwrap(), JournalMode::Wal);
+        assert_eq!("WAL".parse::<JournalMode>().unwrap(), JournalMode::Wal);
+        assert_eq!(
+            "delete".parse::<JournalMode>().unwrap(),
+            JournalMode::Delete
+        );
+        assert_eq!(
+            "memory".parse::<JournalMode>().unwrap(),
+            JournalMode::Memory
+        );
+    }
+
+    #[test]
+    fn test_journal_mode_parse_invalid_names_tradeoffs() {
+        let err = "rollback".parse::<JournalMode>().expect_err("rejected");
+        let message = err.to_string();
+        assert!(message.contains("SQLITE_JOURNAL_MODE"), "{message}");
+        assert!(message.contains("rollback"), "{message}");
+        // The error spells out the durability tradeoff of the risky choice.
+        assert!(message.contains("corrupt"), "{message}");
+    }
+
+    #[test]
+    fn test_synchronous_mode_parse() {
+        assert_eq!(
+            "off".parse::<SynchronousMode>().unwrap(),
+            SynchronousMode::Off
+        );
+        assert_eq!(
+            "NORMAL".parse::<SynchronousMode>().unwrap(),
+            SynchronousMode::Normal
+        );
+        assert_eq!(
+            "full".parse::<SynchronousMode>().unwrap(),
+            SynchronousMode::Full
+        );
+        assert_eq!(
+            "extra".parse::<SynchronousMode>().unwrap(),
+            SynchronousMode::Extra
+        );
+    }
+
+    #[test]
+    fn test_synchronous_mode_parse_invalid_names_tradeoffs() {
+        let err = "fastest".parse::<SynchronousMode>().expect_err("rejected");
+        let message = err.to_string();
+        assert!(message.contains("SQLITE_SYNCHRONOUS"), "{message}");
+        assert!(message.contains("power loss"), "{message}");
+    }
+
+    fn clear_pragma_env() {
+        for var in [
+            "SQLITE_JOURNAL_MODE",
+            "SQLITE_SYNCHRONOUS",
+            "SQLITE_CACHE_SIZE",
+            "SQLITE_MMAP_SIZE",
+        ] {
+            std::env::remove_var(var);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_storage_pragmas_from_env_defaults_when_unset() {
+        clear_pragma_env();
+        let pragmas = StoragePragmas::from_env().expect("defaults load");
+        assert_eq!(pragmas, StoragePragmas::new());
+    }
+
+    #[test]
+    #[serial]
+    fn test_storage_pragmas_from_env_reads_overrides() {
+        clear_pragma_env();
+        std::env::set_var("SQLITE_SYNCHRONOUS", "full");
+        std::env::set_var("SQLITE_CACHE_SIZE", "-64000");
+        std::env::set_var("SQLITE_MMAP_SIZE", "268435456");
+        let pragmas = StoragePragmas::from_env().expect("overrides load");
+        clear_pragma_env();
+
+        assert_eq!(pragmas.journal_mode, JournalMode::Wal);
+        assert_eq!(pragmas.synchronous, SynchronousMode::Full);
+        assert_eq!(pragmas.cache_size, Some(-64000));
+        assert_eq!(pragmas.mmap_size, Some(268435456));
+    }
+
+    #[test]
+    #[serial]
+    fn test_storage_pragmas_from_env_rejects_invalid_value() {
+        clear_pragma_env();
+        std::env::set_var("SQLITE_JOURNAL_MODE", "fast");
+        let err = StoragePragmas::from_env().expect_err("invalid value rejected");
+        clear_pragma_env();
+
+        assert!(
+            matches!(&err, ConfigError::InvalidValue { var, .. } if var == "SQLITE_JOURNAL_MODE"),
+            "{err}"
+        );
+    }
 
     // StoredSession Tests
     #[test]
@@ -969,6 +1626,21 @@ mod tests {
         assert_eq!(json, r#""aggregation""#);
     }
 
+    // GraphNodeOrigin Tests
+    #[test]
+    fn test_graph_node_origin_as_str_from_str_roundtrip() {
+        for origin in [
+            GraphNodeOrigin::Init,
+            GraphNodeOrigin::Generate,
+            GraphNodeOrigin::Aggregate,
+            GraphNodeOrigin::Refine,
+            GraphNodeOrigin::Import,
+        ] {
+            assert_eq!(GraphNodeOrigin::from_str(origin.as_str()), Some(origin));
+        }
+        assert_eq!(GraphNodeOrigin::from_str("invalid"), None);
+    }
+
     // StoredGraphNode Tests
     #[test]
     fn test_stored_graph_node_new() {
@@ -980,6 +1652,18 @@ mod tests {
         assert!(node.score.is_none());
         assert!(!node.is_terminal);
         assert!(node.metadata.is_none());
+        assert!(node.origin.is_none());
+        assert!(node.parent_ids.is_empty());
+    }
+
+    #[test]
+    fn test_stored_graph_node_with_provenance() {
+        let node = StoredGraphNode::new("n-1", "sess-1", "Synthesis").with_provenance(
+            GraphNodeOrigin::Aggregate,
+            vec!["n-a".to_string(), "n-b".to_string()],
+        );
+        assert_eq!(node.origin, Some(GraphNodeOrigin::Aggregate));
+        assert_eq!(node.parent_ids, vec!["n-a", "n-b"]);
     }
 
     #[test]
@@ -1182,4 +1866,56 @@ mod tests {
         let cloned = action.clone();
         assert_eq!(action, cloned);
     }
+
+    #[test]
+    fn test_thought_sampling_rate_clamped() {
+        assert!((ThoughtSamplingConfig::new(1.7).rate - 1.0).abs() < f64::EPSILON);
+        assert!(ThoughtSamplingConfig::new(-0.2).rate.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_thought_sampling_importance() {
+        // High confidence bypasses sampling whatever the mode.
+        let confident = StoredThought::new("t-1", "s-1", "linear", "c", 0.9);
+        assert!(ThoughtSamplingConfig::is_important(&confident));
+
+        // Terminal modes bypass sampling whatever the confidence.
+        for mode in ["checkpoint", "tree_complete", "timeline_merge"] {
+            let terminal = StoredThought::new("t-2", "s-1", mode, "c", 0.1);
+            assert!(ThoughtSamplingConfig::is_important(&terminal), "{mode}");
+        }
+
+        let ordinary = StoredThought::new("t-3", "s-1", "linear", "c", 0.3);
+        assert!(!ThoughtSamplingConfig::is_important(&ordinary));
+    }
+
+    #[test]
+    fn test_thought_sampling_decision_is_stable_per_id() {
+        let config = ThoughtSamplingConfig::new(0.5);
+        let thought = StoredThought::new("t-stable", "s-1", "linear", "c", 0.3);
+        let first = config.should_persist(&thought);
+        assert_eq!(config.should_persist(&thought), first);
+    }
+
+    #[test]
+    #[serial]
+    fn test_thought_sampling_from_env() {
+        std::env::remove_var("THOUGHT_SAMPLE_RATE");
+        assert_eq!(ThoughtSamplingConfig::from_env(), None);
+
+        std::env::set_var("THOUGHT_SAMPLE_RATE", "0.25");
+        assert_eq!(
+            ThoughtSamplingConfig::from_env(),
+            Some(ThoughtSamplingConfig::new(0.25))
+        );
+
+        // A rate of 1.0+ means persist everything — sampling stays off.
+        std::env::set_var("THOUGHT_SAMPLE_RATE", "1.0");
+        assert_eq!(ThoughtSamplingConfig::from_env(), None);
+
+        std::env::set_var("THOUGHT_SAMPLE_RATE", "not-a-number");
+        assert_eq!(ThoughtSamplingConfig::from_env(), None);
+
+        std::env::remove_var("THOUGHT_SAMPLE_RATE");
+    }
 }
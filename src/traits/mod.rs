@@ -24,11 +24,16 @@
 
 mod types;
 
-pub use types::{CompletionConfig, CompletionResponse, Message, Session, Thought, Usage};
+pub use types::{
+    CompletionConfig, CompletionProfile, CompletionResponse, Message, ModeConfidenceStat,
+    OutputSchema, Session, Thought, Usage,
+};
 
 // Re-export storage types needed by modes
 pub use crate::storage::BranchStatus as StoredBranchStatus;
-pub use crate::storage::{StoredBranch, StoredCheckpoint, StoredGraphEdge, StoredGraphNode};
+pub use crate::storage::{
+    StoredBranch, StoredCheckpoint, StoredGraphEdge, StoredGraphNode, StoredRawIo,
+};
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -143,6 +148,33 @@ pub trait StorageTrait: Send + Sync {
     /// Returns [`StorageError`] if the database operation fails.
     async fn get_or_create_session(&self, id: Option<String>) -> Result<Session, StorageError>;
 
+    /// Get a session's working-memory scratchpad.
+    ///
+    /// Returns `None` when the session doesn't exist or no memory has been set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the database operation fails.
+    async fn get_working_memory(&self, session_id: &str) -> Result<Option<String>, StorageError>;
+
+    /// Set a session's working-memory scratchpad, replacing any previous value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the session doesn't exist or the operation fails.
+    async fn set_working_memory(&self, session_id: &str, memory: &str) -> Result<(), StorageError>;
+
+    /// Merge `source_id` into `target_id`, re-parenting the source's
+    /// thoughts, branches, checkpoints, and graph data under the target
+    /// (remapping namespaced graph ids to avoid collisions), then delete
+    /// the source session. Merging a session into itself is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::SessionNotFound`] when either session
+    /// doesn't exist, or [`StorageError`] if the database operation fails.
+    async fn merge_sessions(&self, target_id: &str, source_id: &str) -> Result<(), StorageError>;
+
     /// Save a thought to the database.
     ///
     /// # Errors
@@ -157,6 +189,21 @@ pub trait StorageTrait: Send + Sync {
     /// Returns [`StorageError`] if the database operation fails.
     async fn get_thoughts(&self, session_id: &str) -> Result<Vec<Thought>, StorageError>;
 
+    /// Aggregate stored thought confidence by mode, strongest mode first.
+    ///
+    /// When `fts_query` is given, only thoughts whose content matches it
+    /// (FTS5 keyword search) are aggregated, so the stats reflect history
+    /// similar to the caller's current input rather than everything ever
+    /// stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the database operation fails.
+    async fn mode_confidence_stats(
+        &self,
+        fts_query: Option<String>,
+    ) -> Result<Vec<ModeConfidenceStat>, StorageError>;
+
     /// Delete a thought by ID (used when rolling a session back to a checkpoint).
     ///
     /// # Errors
@@ -164,6 +211,23 @@ pub trait StorageTrait: Send + Sync {
     /// Returns [`StorageError`] if the database operation fails.
     async fn delete_thought(&self, id: &str) -> Result<(), StorageError>;
 
+    /// Delete the most recent thought in a session, cascading any graph
+    /// nodes/edges created by the same step, and return what was removed.
+    /// `Ok(None)` when the session has no thoughts.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the database operation fails.
+    async fn delete_last_thought(&self, session_id: &str) -> Result<Option<Thought>, StorageError>;
+
+    /// Persist the raw request/response behind a thought (`LINEAR_STORE_RAW_IO`),
+    /// replacing any earlier record for the same thought id.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the database operation fails.
+    async fn save_thought_raw_io(&self, raw_io: &StoredRawIo) -> Result<(), StorageError>;
+
     /// Save a checkpoint to the database.
     ///
     /// # Errors
@@ -262,6 +326,22 @@ pub trait StorageTrait: Send + Sync {
     /// Returns [`StorageError`] if the database operation fails.
     async fn save_graph_edge(&self, edge: &StoredGraphEdge) -> Result<(), StorageError>;
 
+    /// Save a thought plus a batch of graph nodes and edges atomically.
+    ///
+    /// The whole batch runs in one transaction: either every row persists or
+    /// none do, so a mid-batch failure can't leave a node without its edges
+    /// (or a thought recording a graph step that was never stored).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if any write fails; nothing is persisted.
+    async fn save_graph_batch(
+        &self,
+        thought: &Thought,
+        nodes: &[StoredGraphNode],
+        edges: &[StoredGraphEdge],
+    ) -> Result<(), StorageError>;
+
     /// Get all graph edges for a session.
     ///
     /// # Errors
@@ -283,6 +363,13 @@ pub trait StorageTrait: Send + Sync {
     ///
     /// Returns [`StorageError`] if the database operation fails.
     async fn delete_graph_node(&self, id: &str) -> Result<(), StorageError>;
+
+    /// Delete a graph edge by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the database operation fails.
+    async fn delete_graph_edge(&self, id: &str) -> Result<(), StorageError>;
 }
 
 /// Time provider trait for deterministic testing.
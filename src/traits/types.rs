@@ -10,6 +10,8 @@
 
 use chrono::{DateTime, Utc};
 
+use crate::error::ModeError;
+
 /// Message for API requests.
 ///
 /// Represents a single message in a conversation with the model.
@@ -50,6 +52,34 @@ impl Message {
     }
 }
 
+/// JSON schema constraining a completion's output (structured output).
+///
+/// When attached to a [`CompletionConfig`], the client presents the schema
+/// to the API as a forced tool call, so the model's answer arrives as a
+/// schema-conforming JSON payload instead of free-form text. The payload is
+/// surfaced on [`CompletionResponse::structured`]; when the API returns no
+/// tool call (for example under extended thinking, where a forced tool
+/// choice is not allowed), `structured` stays `None` and callers fall back
+/// to the free-form text parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputSchema {
+    /// Schema name, surfaced to the API as the tool name.
+    pub name: String,
+    /// JSON Schema the response payload must conform to.
+    pub schema: serde_json::Value,
+}
+
+impl OutputSchema {
+    /// Create a new output schema.
+    #[must_use]
+    pub fn new(name: impl Into<String>, schema: serde_json::Value) -> Self {
+        Self {
+            name: name.into(),
+            schema,
+        }
+    }
+}
+
 /// Completion configuration.
 ///
 /// Configuration options for API completion requests.
@@ -61,10 +91,27 @@ pub struct CompletionConfig {
     pub max_tokens: Option<u32>,
     /// Temperature for sampling (0.0 to 1.0).
     pub temperature: Option<f32>,
+    /// Nucleus-sampling cumulative probability cutoff (0.0 to 1.0).
+    pub top_p: Option<f32>,
+    /// Top-k sampling cutoff: only the k highest-probability tokens are
+    /// considered. Must be positive.
+    pub top_k: Option<u32>,
     /// System prompt to prepend.
     pub system_prompt: Option<String>,
     /// Extended thinking budget in tokens (minimum 1024).
     pub thinking_budget: Option<u32>,
+    /// Mode label for retry-metrics attribution (e.g. "linear"). Never sent
+    /// to the API; calls without a label are counted as "unlabeled".
+    pub mode: Option<String>,
+    /// Byte length of the static prompt prefix of the first user message.
+    /// When prompt caching is enabled on the client, that prefix is marked
+    /// cacheable so repeated calls read it from the prompt cache instead of
+    /// reprocessing it; the dynamic remainder stays uncached. Ignored when
+    /// caching is disabled, so modes can set it unconditionally.
+    pub cached_prompt_len: Option<usize>,
+    /// Schema constraining the response to structured JSON output. `None`
+    /// (the default) leaves the response free-form.
+    pub output_schema: Option<OutputSchema>,
 }
 
 impl CompletionConfig {
@@ -88,6 +135,38 @@ impl CompletionConfig {
         self
     }
 
+    /// Set nucleus-sampling cutoff (`top_p`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModeError::InvalidValue`] when `top_p` is outside 0.0 to 1.0.
+    pub fn with_top_p(mut self, top_p: f32) -> Result<Self, ModeError> {
+        if !(0.0..=1.0).contains(&top_p) {
+            return Err(ModeError::InvalidValue {
+                field: "top_p".to_string(),
+                reason: format!("must be between 0.0 and 1.0, got {top_p}"),
+            });
+        }
+        self.top_p = Some(top_p);
+        Ok(self)
+    }
+
+    /// Set top-k sampling cutoff (`top_k`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModeError::InvalidValue`] when `top_k` is zero.
+    pub fn with_top_k(mut self, top_k: u32) -> Result<Self, ModeError> {
+        if top_k == 0 {
+            return Err(ModeError::InvalidValue {
+                field: "top_k".to_string(),
+                reason: "must be positive, got 0".to_string(),
+            });
+        }
+        self.top_k = Some(top_k);
+        Ok(self)
+    }
+
     /// Set system prompt.
     #[must_use]
     pub fn with_system_prompt(mut self, system_prompt: impl Into<String>) -> Self {
@@ -103,6 +182,31 @@ impl CompletionConfig {
         self
     }
 
+    /// Set the mode label for retry-metrics attribution.
+    #[must_use]
+    pub fn with_mode(mut self, mode: impl Into<String>) -> Self {
+        self.mode = Some(mode.into());
+        self
+    }
+
+    /// Declare the static prompt prefix length of the first user message,
+    /// making that prefix eligible for prompt caching when the client has
+    /// caching enabled. A no-op otherwise.
+    #[must_use]
+    pub const fn with_cached_prompt_len(mut self, len: usize) -> Self {
+        self.cached_prompt_len = Some(len);
+        self
+    }
+
+    /// Constrain the response to structured JSON output conforming to
+    /// `schema`. See [`OutputSchema`] for the fallback behavior when the
+    /// API returns free-form text anyway.
+    #[must_use]
+    pub fn with_output_schema(mut self, schema: OutputSchema) -> Self {
+        self.output_schema = Some(schema);
+        self
+    }
+
     /// Enable standard thinking budget (4096 tokens).
     /// Suitable for divergent thinking and graph reasoning.
     #[must_use]
@@ -125,6 +229,108 @@ impl CompletionConfig {
     }
 }
 
+/// Named completion profile: a curated bundle of sampling and thinking
+/// settings selectable per tool call.
+///
+/// Profiles centralize the temperature / max-tokens / thinking-budget knobs
+/// that were previously hardcoded per mode. A mode picks a default profile
+/// and callers may override it per call; the resolved profile produces the
+/// base [`CompletionConfig`] the mode then decorates (mode label, cached
+/// prompt length, and so on).
+///
+/// Operators can tune a profile without a rebuild via environment variables
+/// `PROFILE_{FAST,BALANCED,DEEP}_{MAX_TOKENS,TEMPERATURE,THINKING_BUDGET}`.
+/// Unparseable values are logged and ignored; a thinking budget of `0`
+/// disables extended thinking for that profile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CompletionProfile {
+    /// Quick, exploratory sampling: 4096 max tokens, temperature 0.7, no
+    /// extended thinking. Matches the historical linear-mode defaults.
+    #[default]
+    Fast,
+    /// Moderate depth: 8192 max tokens, temperature 0.5, standard thinking
+    /// budget (4096 tokens).
+    Balanced,
+    /// Thorough analysis: 16384 max tokens, temperature 0.3, deep thinking
+    /// budget (8192 tokens).
+    Deep,
+}
+
+impl CompletionProfile {
+    /// Parse a profile name as supplied in a tool call.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModeError::InvalidValue`] when the name is not one of
+    /// `fast`, `balanced`, or `deep`.
+    pub fn parse(name: &str) -> Result<Self, ModeError> {
+        match name {
+            "fast" => Ok(Self::Fast),
+            "balanced" => Ok(Self::Balanced),
+            "deep" => Ok(Self::Deep),
+            other => Err(ModeError::InvalidValue {
+                field: "profile".to_string(),
+                reason: format!("must be one of: fast, balanced, deep (got '{other}')"),
+            }),
+        }
+    }
+
+    /// The canonical profile name.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Fast => "fast",
+            Self::Balanced => "balanced",
+            Self::Deep => "deep",
+        }
+    }
+
+    /// Built-in settings: (`max_tokens`, temperature, thinking budget).
+    const fn defaults(self) -> (u32, f32, Option<u32>) {
+        match self {
+            Self::Fast => (4096, 0.7, None),
+            Self::Balanced => (8192, 0.5, Some(4096)),
+            Self::Deep => (16384, 0.3, Some(8192)),
+        }
+    }
+
+    /// Resolve this profile into a base [`CompletionConfig`], applying any
+    /// environment overrides for the profile's name.
+    #[must_use]
+    pub fn config(self) -> CompletionConfig {
+        let (default_max_tokens, default_temperature, default_thinking) = self.defaults();
+        let max_tokens = env_override(self, "MAX_TOKENS").unwrap_or(default_max_tokens);
+        let temperature = env_override(self, "TEMPERATURE").unwrap_or(default_temperature);
+        let thinking = match env_override::<u32>(self, "THINKING_BUDGET") {
+            Some(0) => None,
+            Some(budget) => Some(budget),
+            None => default_thinking,
+        };
+
+        let mut config = CompletionConfig::new()
+            .with_max_tokens(max_tokens)
+            .with_temperature(temperature);
+        if let Some(budget) = thinking {
+            config = config.with_thinking_budget(budget);
+        }
+        config
+    }
+}
+
+/// Read a `PROFILE_<NAME>_<KNOB>` environment override, ignoring (with a
+/// warning) values that fail to parse.
+fn env_override<T: std::str::FromStr>(profile: CompletionProfile, knob: &str) -> Option<T> {
+    let var = format!("PROFILE_{}_{knob}", profile.as_str().to_ascii_uppercase());
+    let raw = std::env::var(&var).ok()?;
+    raw.parse().map_or_else(
+        |_| {
+            tracing::warn!(var = %var, value = %raw, "Ignoring unparseable profile override");
+            None
+        },
+        Some,
+    )
+}
+
 /// Token usage information.
 ///
 /// Tracks the number of tokens used in a request/response.
@@ -134,6 +340,10 @@ pub struct Usage {
     pub input_tokens: u32,
     /// Output tokens (completion).
     pub output_tokens: u32,
+    /// Input tokens served from the prompt cache. Counted separately from
+    /// `input_tokens` (the API reports cached reads in their own field, billed
+    /// at a reduced rate); zero when prompt caching was not in play.
+    pub cache_read_input_tokens: u32,
 }
 
 impl Usage {
@@ -143,10 +353,18 @@ impl Usage {
         Self {
             input_tokens,
             output_tokens,
+            cache_read_input_tokens: 0,
         }
     }
 
-    /// Total tokens used.
+    /// Set the tokens served from the prompt cache.
+    #[must_use]
+    pub const fn with_cache_read_tokens(mut self, cache_read_input_tokens: u32) -> Self {
+        self.cache_read_input_tokens = cache_read_input_tokens;
+        self
+    }
+
+    /// Total uncached tokens used (cache reads are tracked separately).
     #[must_use]
     pub const fn total(&self) -> u32 {
         self.input_tokens + self.output_tokens
@@ -162,6 +380,13 @@ pub struct CompletionResponse {
     pub content: String,
     /// Token usage.
     pub usage: Usage,
+    /// Raw extended-thinking trace, when the request set a thinking budget
+    /// and the API returned a thinking block. `None` otherwise.
+    pub thinking: Option<String>,
+    /// Schema-conforming JSON payload, when the request set an
+    /// [`OutputSchema`] and the API honored it with a tool call. `None`
+    /// otherwise; callers fall back to parsing `content` as free-form text.
+    pub structured: Option<serde_json::Value>,
 }
 
 impl CompletionResponse {
@@ -171,8 +396,25 @@ impl CompletionResponse {
         Self {
             content: content.into(),
             usage,
+            thinking: None,
+            structured: None,
         }
     }
+
+    /// Attach the extended-thinking trace from the API response.
+    #[must_use]
+    pub fn with_thinking(mut self, thinking: impl Into<String>) -> Self {
+        self.thinking = Some(thinking.into());
+        self
+    }
+
+    /// Attach the structured JSON payload from a schema-constrained
+    /// response.
+    #[must_use]
+    pub fn with_structured(mut self, structured: serde_json::Value) -> Self {
+        self.structured = Some(structured);
+        self
+    }
 }
 
 /// Session data.
@@ -206,6 +448,35 @@ impl Session {
     }
 }
 
+/// Aggregate of stored thought confidence for one reasoning mode.
+///
+/// Produced by [`StorageTrait::mode_confidence_stats`]: how confident the
+/// thoughts a mode has written historically were, and over how many samples.
+/// Consumers decide what counts as enough samples to act on.
+///
+/// [`StorageTrait::mode_confidence_stats`]: super::StorageTrait::mode_confidence_stats
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModeConfidenceStat {
+    /// Mode name as recorded on the thoughts (e.g. `linear`, `tree`).
+    pub mode: String,
+    /// Mean confidence across the mode's stored thoughts (0.0-1.0).
+    pub avg_confidence: f64,
+    /// Number of thoughts the mean is computed over.
+    pub samples: u32,
+}
+
+impl ModeConfidenceStat {
+    /// Create a new mode confidence stat.
+    #[must_use]
+    pub fn new(mode: impl Into<String>, avg_confidence: f64, samples: u32) -> Self {
+        Self {
+            mode: mode.into(),
+            avg_confidence,
+            samples,
+        }
+    }
+}
+
 /// Thought data.
 ///
 /// Represents a single reasoning step within a session.
@@ -223,6 +494,9 @@ pub struct Thought {
     pub confidence: f64,
     /// Creation timestamp.
     pub created_at: DateTime<Utc>,
+    /// Name of the registered prompt version that produced this thought
+    /// ([`crate::prompts::PromptRegistry`]); `None` for the built-in prompt.
+    pub prompt_version: Option<String>,
 }
 
 impl Thought {
@@ -242,6 +516,7 @@ impl Thought {
             mode: mode.into(),
             confidence,
             created_at: Utc::now(),
+            prompt_version: None,
         }
     }
 
@@ -262,8 +537,16 @@ impl Thought {
             mode: mode.into(),
             confidence,
             created_at,
+            prompt_version: None,
         }
     }
+
+    /// Record the registered prompt version that produced this thought.
+    #[must_use]
+    pub fn with_prompt_version(mut self, prompt_version: Option<String>) -> Self {
+        self.prompt_version = prompt_version;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -362,6 +645,45 @@ mod tests {
         assert!((config.temperature.unwrap_or(0.0) - 0.7).abs() < f32::EPSILON);
     }
 
+    #[test]
+    fn test_completion_config_sampling_unset_by_default() {
+        let config = CompletionConfig::new();
+        assert!(config.top_p.is_none());
+        assert!(config.top_k.is_none());
+    }
+
+    #[test]
+    fn test_completion_config_with_top_p() {
+        let config = CompletionConfig::new()
+            .with_top_p(0.9)
+            .expect("valid top_p");
+        assert!((config.top_p.unwrap_or(0.0) - 0.9).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_completion_config_rejects_top_p_out_of_range() {
+        for invalid in [-0.1, 1.5] {
+            let err = CompletionConfig::new()
+                .with_top_p(invalid)
+                .expect_err("rejected");
+            assert!(err.to_string().contains("top_p"), "{err}");
+            assert!(err.to_string().contains("between 0.0 and 1.0"), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_completion_config_with_top_k() {
+        let config = CompletionConfig::new().with_top_k(40).expect("valid top_k");
+        assert_eq!(config.top_k, Some(40));
+    }
+
+    #[test]
+    fn test_completion_config_rejects_zero_top_k() {
+        let err = CompletionConfig::new().with_top_k(0).expect_err("rejected");
+        assert!(err.to_string().contains("top_k"), "{err}");
+        assert!(err.to_string().contains("positive"), "{err}");
+    }
+
     #[test]
     fn test_completion_config_with_system_prompt() {
         let config = CompletionConfig::new().with_system_prompt("Be helpful");
@@ -421,12 +743,40 @@ mod tests {
         assert_eq!(config.thinking_budget, Some(8192));
     }
 
+    #[test]
+    fn test_completion_config_with_cached_prompt_len() {
+        let config = CompletionConfig::new();
+        assert!(config.cached_prompt_len.is_none());
+        let config = config.with_cached_prompt_len(1024);
+        assert_eq!(config.cached_prompt_len, Some(1024));
+    }
+
+    #[test]
+    fn test_completion_config_with_output_schema() {
+        let config = CompletionConfig::new();
+        assert!(config.output_schema.is_none());
+        let schema = serde_json::json!({"type": "object"});
+        let config = config.with_output_schema(OutputSchema::new("linear_result", schema.clone()));
+        let attached = config.output_schema.expect("schema set");
+        assert_eq!(attached.name, "linear_result");
+        assert_eq!(attached.schema, schema);
+    }
+
     // Usage Tests
     #[test]
     fn test_usage_default() {
         let usage = Usage::default();
         assert_eq!(usage.input_tokens, 0);
         assert_eq!(usage.output_tokens, 0);
+        assert_eq!(usage.cache_read_input_tokens, 0);
+    }
+
+    #[test]
+    fn test_usage_with_cache_read_tokens() {
+        let usage = Usage::new(100, 50).with_cache_read_tokens(900);
+        assert_eq!(usage.cache_read_input_tokens, 900);
+        // Cache reads stay out of the uncached total.
+        assert_eq!(usage.total(), 150);
     }
 
     #[test]
@@ -470,6 +820,23 @@ mod tests {
         assert_eq!(response.content, "Hello");
         assert_eq!(response.usage.input_tokens, 10);
         assert_eq!(response.usage.output_tokens, 5);
+        assert!(response.thinking.is_none());
+    }
+
+    #[test]
+    fn test_completion_response_with_thinking() {
+        let response =
+            CompletionResponse::new("Hello", Usage::new(10, 5)).with_thinking("working it out");
+        assert_eq!(response.thinking.as_deref(), Some("working it out"));
+    }
+
+    #[test]
+    fn test_completion_response_with_structured() {
+        let response = CompletionResponse::new("", Usage::new(10, 5));
+        assert!(response.structured.is_none());
+        let payload = serde_json::json!({"analysis": "done"});
+        let response = response.with_structured(payload.clone());
+        assert_eq!(response.structured, Some(payload));
     }
 
     #[test]
@@ -555,4 +922,96 @@ mod tests {
         assert!(debug.contains("sess-1"));
         assert!(debug.contains("linear"));
     }
+
+    // CompletionProfile Tests
+
+    #[test]
+    fn test_profile_fast_config() {
+        let config = CompletionProfile::Fast.config();
+        assert_eq!(config.max_tokens, Some(4096));
+        assert_eq!(config.temperature, Some(0.7));
+        assert_eq!(config.thinking_budget, None);
+    }
+
+    #[test]
+    fn test_profile_balanced_config() {
+        let config = CompletionProfile::Balanced.config();
+        assert_eq!(config.max_tokens, Some(8192));
+        assert_eq!(config.temperature, Some(0.5));
+        assert_eq!(config.thinking_budget, Some(4096));
+    }
+
+    #[test]
+    fn test_profile_deep_config() {
+        let config = CompletionProfile::Deep.config();
+        assert_eq!(config.max_tokens, Some(16384));
+        assert_eq!(config.temperature, Some(0.3));
+        assert_eq!(config.thinking_budget, Some(8192));
+    }
+
+    #[test]
+    fn test_profile_default_is_fast() {
+        assert_eq!(CompletionProfile::default(), CompletionProfile::Fast);
+    }
+
+    #[test]
+    fn test_profile_parse_valid_names() {
+        assert_eq!(
+            CompletionProfile::parse("fast").unwrap(),
+            CompletionProfile::Fast
+        );
+        assert_eq!(
+            CompletionProfile::parse("balanced").unwrap(),
+            CompletionProfile::Balanced
+        );
+        assert_eq!(
+            CompletionProfile::parse("deep").unwrap(),
+            CompletionProfile::Deep
+        );
+    }
+
+    #[test]
+    fn test_profile_parse_rejects_unknown_name() {
+        let err = CompletionProfile::parse("turbo").expect_err("should reject");
+        assert!(err.to_string().contains("profile"));
+        assert!(err.to_string().contains("turbo"));
+    }
+
+    #[test]
+    fn test_profile_as_str_roundtrip() {
+        for profile in [
+            CompletionProfile::Fast,
+            CompletionProfile::Balanced,
+            CompletionProfile::Deep,
+        ] {
+            assert_eq!(CompletionProfile::parse(profile.as_str()).unwrap(), profile);
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_profile_env_overrides() {
+        std::env::set_var("PROFILE_BALANCED_MAX_TOKENS", "2048");
+        std::env::set_var("PROFILE_BALANCED_TEMPERATURE", "0.9");
+        std::env::set_var("PROFILE_BALANCED_THINKING_BUDGET", "0");
+        let config = CompletionProfile::Balanced.config();
+        std::env::remove_var("PROFILE_BALANCED_MAX_TOKENS");
+        std::env::remove_var("PROFILE_BALANCED_TEMPERATURE");
+        std::env::remove_var("PROFILE_BALANCED_THINKING_BUDGET");
+
+        assert_eq!(config.max_tokens, Some(2048));
+        assert_eq!(config.temperature, Some(0.9));
+        // A budget of 0 disables thinking rather than sending an invalid value.
+        assert_eq!(config.thinking_budget, None);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_profile_env_override_ignores_garbage() {
+        std::env::set_var("PROFILE_FAST_MAX_TOKENS", "not-a-number");
+        let config = CompletionProfile::Fast.config();
+        std::env::remove_var("PROFILE_FAST_MAX_TOKENS");
+
+        assert_eq!(config.max_tokens, Some(4096));
+    }
 }
@@ -46,8 +46,10 @@ async fn create_test_state(server: &MockServer) -> AppState {
         .with_timeout_ms(5_000);
     let client = AnthropicClient::new("test-api-key", client_config).unwrap();
     let config = Config {
+        response_language: None,
         api_key: SecretString::new("test-key"),
         database_path: ":memory:".to_string(),
+        database_max_connections: 5,
         log_level: "info".to_string(),
         request_timeout_ms: 30000,
         request_timeout_deep_ms: 60000,
@@ -61,6 +63,27 @@ async fn create_test_state(server: &MockServer) -> AppState {
         reflection_quality_threshold: 0.8,
         mcts_quality_threshold: 0.5,
         graph_prune_threshold: 0.3,
+        graph_max_nodes: 200,
+        graph_max_returned_children: None,
+        rubric_llm_weight: 0.5,
+        evidence_interval_width: 0.1,
+        sticky_session: false,
+        detect_filter_unverified: false,
+        strict_parsing: false,
+        require_session_id: false,
+        self_correct_parse: false,
+        structured_output: false,
+        confidence_floor: None,
+        prompt_caching: false,
+        enabled_tools: None,
+        disabled_tools: vec![],
+        divergent_per_perspective: false,
+        divergent_max_concurrency: 3,
+        linear_store_raw_io: false,
+        max_pending_requests: 32,
+        auto_history_bias: false,
+        retry_degenerate: false,
+        prompt_version: None,
     };
 
     let metadata_builder = mcp_reasoning::metadata::MetadataBuilder::new(
@@ -1000,10 +1023,13 @@ mod server_tests {
     #[test]
     fn test_linear_request_with_all_fields() {
         let req = LinearRequest {
+            language: None,
             content: "test content".to_string(),
             session_id: Some("session-1".to_string()),
             confidence: Some(ConfidenceThreshold::try_from(0.8).unwrap()),
             timeout_ms: None,
+            profile: None,
+            prompt_version: None,
         };
         let json = serde_json::to_string(&req).unwrap();
         assert!(json.contains("test content"));
@@ -1018,10 +1044,13 @@ mod server_tests {
     fn test_linear_request_timeout_ms_override() {
         // timeout_ms should round-trip through JSON correctly
         let req_with_timeout = LinearRequest {
+            language: None,
             content: "test".to_string(),
             session_id: None,
             confidence: Some(ConfidenceThreshold::try_from(0.5).unwrap()),
             timeout_ms: Some(5_000),
+            profile: None,
+            prompt_version: None,
         };
         let json = serde_json::to_string(&req_with_timeout).unwrap();
         assert!(json.contains("5000"), "timeout_ms should be serialized");
@@ -1030,10 +1059,13 @@ mod server_tests {
 
         // timeout_ms: None should be omitted from JSON (skip_serializing_if)
         let req_no_timeout = LinearRequest {
+            language: None,
             content: "test".to_string(),
             session_id: None,
             confidence: None,
             timeout_ms: None,
+            profile: None,
+            prompt_version: None,
         };
         let json_no_timeout = serde_json::to_string(&req_no_timeout).unwrap();
         assert!(
@@ -1053,6 +1085,7 @@ mod server_tests {
     #[test]
     fn test_tree_request_with_all_operations() {
         let create_req = TreeRequest {
+            language: None,
             operation: Some("create".to_string()),
             content: Some("Topic to explore".to_string()),
             session_id: Some("s1".to_string()),
@@ -1064,6 +1097,7 @@ mod server_tests {
         assert!(json.contains("create"));
 
         let focus_req = TreeRequest {
+            language: None,
             operation: Some("focus".to_string()),
             content: None,
             session_id: Some("s1".to_string()),
@@ -1079,11 +1113,13 @@ mod server_tests {
     #[test]
     fn test_divergent_request_serialization() {
         let req = DivergentRequest {
+            language: None,
             content: "Analyze this".to_string(),
             session_id: None,
             num_perspectives: Some(4),
             challenge_assumptions: Some(true),
             force_rebellion: Some(false),
+            include_thinking: None,
             progress_token: None,
         };
         let json = serde_json::to_string(&req).unwrap();
@@ -1095,6 +1131,7 @@ mod server_tests {
     #[test]
     fn test_reflection_request_operations() {
         let process_req = ReflectionRequest {
+            language: None,
             operation: Some("process".to_string()),
             content: Some("Reasoning to reflect on".to_string()),
             thought_id: None,
@@ -1111,6 +1148,7 @@ mod server_tests {
     #[test]
     fn test_auto_request_serialization() {
         let req = AutoRequest {
+            language: None,
             content: "Content for auto-routing".to_string(),
             session_id: None,
             hints: Some(vec!["technical".to_string(), "complex".to_string()]),
@@ -1124,6 +1162,7 @@ mod server_tests {
     #[test]
     fn test_graph_request_all_operations() {
         let init_req = GraphRequest {
+            language: None,
             operation: "init".to_string(),
             session_id: "s1".to_string(),
             content: Some("Problem to explore".to_string()),
@@ -1132,12 +1171,15 @@ mod server_tests {
             k: None,
             problem: None,
             threshold: None,
+            force: None,
             terminal_node_ids: None,
+            max_nodes: None,
         };
         let json = serde_json::to_string(&init_req).unwrap();
         assert!(json.contains("init"));
 
         let generate_req = GraphRequest {
+            language: None,
             operation: "generate".to_string(),
             session_id: "s1".to_string(),
             content: None,
@@ -1146,7 +1188,9 @@ mod server_tests {
             k: Some(3),
             problem: Some("The problem".to_string()),
             threshold: None,
+            force: None,
             terminal_node_ids: None,
+            max_nodes: None,
         };
         let json = serde_json::to_string(&generate_req).unwrap();
         assert!(json.contains("generate"));
@@ -1155,6 +1199,8 @@ mod server_tests {
     #[test]
     fn test_detect_request_types() {
         let biases_req = DetectRequest {
+            chunk: None,
+            language: None,
             detect_type: "biases".to_string(),
             content: Some("Content to analyze for biases".to_string()),
             session_id: None,
@@ -1162,6 +1208,7 @@ mod server_tests {
             check_formal: None,
             check_informal: Some(true),
             check_types: Some(vec!["confirmation".to_string()]),
+            verify_premises: None,
         };
         let json = serde_json::to_string(&biases_req).unwrap();
         assert!(json.contains("biases"));
@@ -1170,7 +1217,9 @@ mod server_tests {
     #[test]
     fn test_decision_request_types() {
         let weighted_req = DecisionRequest {
+            language: None,
             decision_type: Some("weighted".to_string()),
+            normalization: None,
             question: Some("Which option is best?".to_string()),
             options: Some(vec!["A".to_string(), "B".to_string()]),
             context: Some("Business context".to_string()),
@@ -1181,7 +1230,9 @@ mod server_tests {
         assert!(json.contains("weighted"));
 
         let perspectives_req = DecisionRequest {
+            language: None,
             decision_type: Some("perspectives".to_string()),
+            normalization: None,
             question: None,
             options: None,
             context: None,
@@ -1195,6 +1246,8 @@ mod server_tests {
     #[test]
     fn test_evidence_request_types() {
         let assess_req = EvidenceRequest {
+            chunk: None,
+            language: None,
             evidence_type: Some("assess".to_string()),
             claim: Some("The claim to evaluate".to_string()),
             hypothesis: None,
@@ -1206,6 +1259,8 @@ mod server_tests {
         assert!(json.contains("assess"));
 
         let prob_req = EvidenceRequest {
+            chunk: None,
+            language: None,
             evidence_type: Some("probabilistic".to_string()),
             claim: None,
             hypothesis: Some("Hypothesis to update".to_string()),
@@ -1220,6 +1275,7 @@ mod server_tests {
     #[test]
     fn test_timeline_request_operations() {
         let create_req = TimelineRequest {
+            language: None,
             operation: "create".to_string(),
             session_id: None,
             timeline_id: None,
@@ -1234,6 +1290,7 @@ mod server_tests {
         assert!(json.contains("create"));
 
         let branch_req = TimelineRequest {
+            language: None,
             operation: "branch".to_string(),
             session_id: None,
             timeline_id: Some("tl-1".to_string()),
@@ -1251,6 +1308,7 @@ mod server_tests {
     #[test]
     fn test_mcts_request_operations() {
         let explore_req = MctsRequest {
+            language: None,
             operation: Some("explore".to_string()),
             content: Some("Search state".to_string()),
             session_id: None,
@@ -1268,6 +1326,7 @@ mod server_tests {
         assert!(json.contains("exploration_constant"));
 
         let backtrack_req = MctsRequest {
+            language: None,
             operation: Some("auto_backtrack".to_string()),
             content: None,
             session_id: Some("s1".to_string()),
@@ -1287,6 +1346,7 @@ mod server_tests {
     #[test]
     fn test_counterfactual_request() {
         let req = CounterfactualRequest {
+            language: None,
             scenario: "The original scenario".to_string(),
             intervention: "What if X changed".to_string(),
             session_id: None,